@@ -0,0 +1,25 @@
+#![no_main]
+
+// 导入的图集 plist 来自外部：任意内容的解析与单帧还原都不应 panic
+
+use ezplist_core::atlas_plist::parse_atlas_plist;
+use ezplist_core::atlas_unpack::{extract_frame, frame_file_path};
+use image::RgbaImage;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(atlas) = parse_atlas_plist(data) else {
+        return;
+    };
+
+    let texture = RgbaImage::new(64, 64);
+    for frame in atlas.frames.iter().take(64) {
+        let _ = frame_file_path(&frame.name);
+        // 限制原始尺寸，避免分配过大的画布
+        if frame.original_width <= 4096 && frame.original_height <= 4096 {
+            if let Ok(image) = extract_frame(&texture, frame) {
+                assert_eq!(image.dimensions(), (frame.original_width, frame.original_height));
+            }
+        }
+    }
+});
@@ -0,0 +1,16 @@
+#![no_main]
+
+// 任意 DDS 文件内容的解码都不应 panic，成功时尺寸与文件头一致
+
+use ezplist_core::dds::{decode_uncompressed, DdsHeader};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let mut file = b"DDS ".to_vec();
+    file.extend_from_slice(data);
+
+    if let Ok(image) = decode_uncompressed(&file) {
+        let header = DdsHeader::parse(&file).unwrap();
+        assert_eq!(image.dimensions(), (header.width, header.height));
+    }
+});
@@ -0,0 +1,34 @@
+#![no_main]
+
+// 任意像素内容的轮廓提取与凸分解都应终止且不 panic，顶点不超出图像范围
+
+use ezplist_core::outline::{extract_outline, OutlineOptions};
+use ezplist_core::physics::decompose_convex;
+use image::RgbaImage;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: (u8, u8, u8, Vec<u8>)| {
+    let (width, height, threshold, pixels) = data;
+    let (width, height) = (width as u32 % 64, height as u32 % 64);
+
+    let mut buf = pixels;
+    buf.resize((width * height * 4) as usize, 0);
+    let Some(image) = RgbaImage::from_raw(width, height, buf) else {
+        return;
+    };
+
+    let options = OutlineOptions {
+        alpha_threshold: threshold,
+        ..Default::default()
+    };
+    for polygon in extract_outline(&image, &options) {
+        assert!(polygon.len() >= 3);
+        for piece in decompose_convex(&polygon, 8) {
+            assert!(piece.len() >= 3 && piece.len() <= 8);
+        }
+        for p in polygon {
+            assert!(p.x >= 0.0 && p.x <= width as f32);
+            assert!(p.y >= 0.0 && p.y <= height as f32);
+        }
+    }
+});
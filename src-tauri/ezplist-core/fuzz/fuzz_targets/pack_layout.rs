@@ -0,0 +1,57 @@
+#![no_main]
+
+// 任意精灵尺寸、间距与容器尺寸下打包都不应 panic，且结果不越界
+
+use arbitrary::Arbitrary;
+use ezplist_core::packer::{find_optimal_size, MaxRectsPacker, PackHeuristic, PackerOptions, SortOrder, SpriteInput};
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    width: u16,
+    height: u16,
+    allow_rotation: bool,
+    padding: u32,
+    extrude: u8,
+    heuristic: u8,
+    sort: u8,
+    sprites: Vec<(u32, u32)>,
+}
+
+fuzz_target!(|input: Input| {
+    let sprites: Vec<SpriteInput> = input
+        .sprites
+        .iter()
+        .take(64)
+        .enumerate()
+        .map(|(i, &(width, height))| SpriteInput {
+            id: i.to_string(),
+            name: format!("{}.png", i),
+            width,
+            height,
+            original_width: width,
+            original_height: height,
+            offset_x: 0,
+            offset_y: 0,
+            trimmed: false,
+            allow_rotation: true,
+        })
+        .collect();
+
+    let (width, height) = (input.width as u32, input.height as u32);
+    let options = PackerOptions {
+        allow_rotation: input.allow_rotation,
+        padding: input.padding,
+        extrude: input.extrude as u32,
+        heuristic: PackHeuristic::ALL[input.heuristic as usize % PackHeuristic::ALL.len()],
+        sort: SortOrder::ALL[input.sort as usize % SortOrder::ALL.len()],
+    };
+    let mut packer = MaxRectsPacker::with_options(width, height, &options);
+    for sprite in packer.pack(&sprites) {
+        assert!(sprite.x as u64 + sprite.width as u64 <= width as u64);
+        assert!(sprite.y as u64 + sprite.height as u64 <= height as u64);
+    }
+    let _ = packer.fill_rate();
+
+    let _ = find_optimal_size(&sprites, 1024, &options);
+});
@@ -0,0 +1,20 @@
+#![no_main]
+
+// plist 中的几何字符串来自外部工具：任意文本的解析都不应 panic，
+// 整数解析成功时浮点解析也必须成功
+
+use ezplist_core::plist_parse::{parse_pair_f64, parse_point, parse_rect, parse_rect_f64, parse_size};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    if parse_rect(text).is_ok() {
+        assert!(parse_rect_f64(text).is_ok());
+    }
+    if parse_point(text).is_ok() || parse_size(text).is_ok() {
+        assert!(parse_pair_f64(text).is_ok());
+    }
+});
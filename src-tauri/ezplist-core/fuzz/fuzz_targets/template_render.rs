@@ -0,0 +1,26 @@
+#![no_main]
+
+// 用户可注册任意模板文件：任意内容都不应导致解析或渲染 panic
+
+use ezplist_core::template_exporter::{render_template, validate_template, TemplateContext};
+use ezplist_core::types::FrameInfo;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|source: &str| {
+    if validate_template(source).is_err() {
+        return;
+    }
+
+    let frames = [FrameInfo {
+        name: "frame_01.png".to_string(),
+        x: 0,
+        y: 0,
+        width: 16,
+        height: 16,
+        row: 0,
+        col: 0,
+        anchor: None,
+    }];
+    let context = TemplateContext::from_frames(&frames, 16, 16, "sheet.png");
+    let _ = render_template(source, &context);
+});
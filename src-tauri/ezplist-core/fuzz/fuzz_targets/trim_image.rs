@@ -0,0 +1,22 @@
+#![no_main]
+
+// 任意像素内容的图像裁剪都不应 panic，且裁剪结果不超出原图
+
+use ezplist_core::trim::trim_transparent;
+use image::RgbaImage;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: (u8, u8, Vec<u8>)| {
+    let (width, height, pixels) = data;
+    let (width, height) = (width as u32, height as u32);
+
+    let mut buf = pixels;
+    buf.resize((width * height * 4) as usize, 0);
+    let Some(image) = RgbaImage::from_raw(width, height, buf) else {
+        return;
+    };
+
+    let result = trim_transparent(&image);
+    assert!(result.trimmed_width <= width.max(1));
+    assert!(result.trimmed_height <= height.max(1));
+});
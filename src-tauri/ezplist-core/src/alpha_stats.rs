@@ -0,0 +1,143 @@
+/// 透明度统计 (Alpha Statistics)
+///
+/// 统计每个精灵的 alpha 分布（完全不透明、1 位透明、渐变透明），
+/// 并找出绘图工具导出时常见的问题，如边缘残留几乎不可见的像素（会扩大裁剪区域），
+/// 或硬边像素画中混入少量半透明像素
+
+use image::RgbaImage;
+use serde::Serialize;
+
+/// 不高于此值的非零 alpha 视为“几乎不可见”
+pub const FAINT_ALPHA: u8 = 16;
+
+/// 半透明像素占不透明区域的比例低于此值时，视为 1 位透明图像中的杂散像素
+const STRAY_PARTIAL_RATIO: f64 = 0.01;
+
+/// alpha 分布类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AlphaKind {
+    /// 所有像素完全不透明
+    Opaque,
+    /// 只有完全透明与完全不透明（1 位透明）
+    Binary,
+    /// 含半透明像素
+    Gradient,
+}
+
+/// 单个精灵的 alpha 统计
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlphaStats {
+    /// 分布类型
+    pub kind: AlphaKind,
+    /// 完全不透明的像素数
+    pub opaque_pixels: u64,
+    /// 完全透明的像素数
+    pub transparent_pixels: u64,
+    /// 半透明像素数
+    pub partial_pixels: u64,
+    /// 位于图像最外圈、几乎不可见的像素数
+    pub faint_border_pixels: u64,
+}
+
+/// 统计图像的 alpha 分布
+///
+/// # Arguments
+/// * `img` - 图像（通常为裁剪后的图像，最外圈即裁剪边界）
+///
+/// # Returns
+/// * `AlphaStats` - 统计结果
+pub fn alpha_stats(img: &RgbaImage) -> AlphaStats {
+    let (width, height) = img.dimensions();
+    let mut stats = AlphaStats {
+        kind: AlphaKind::Opaque,
+        opaque_pixels: 0,
+        transparent_pixels: 0,
+        partial_pixels: 0,
+        faint_border_pixels: 0,
+    };
+
+    for (x, y, pixel) in img.enumerate_pixels() {
+        match pixel[3] {
+            0 => stats.transparent_pixels += 1,
+            255 => stats.opaque_pixels += 1,
+            alpha => {
+                stats.partial_pixels += 1;
+                let on_border = x == 0 || y == 0 || x + 1 == width || y + 1 == height;
+                if on_border && alpha <= FAINT_ALPHA {
+                    stats.faint_border_pixels += 1;
+                }
+            }
+        }
+    }
+
+    stats.kind = if stats.partial_pixels > 0 {
+        AlphaKind::Gradient
+    } else if stats.transparent_pixels > 0 {
+        AlphaKind::Binary
+    } else {
+        AlphaKind::Opaque
+    };
+    stats
+}
+
+/// 根据统计结果生成警告
+///
+/// # Arguments
+/// * `name` - 帧名称
+/// * `stats` - alpha 统计
+///
+/// # Returns
+/// * `Vec<String>` - 警告信息（没有问题时为空）
+pub fn alpha_warnings(name: &str, stats: &AlphaStats) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if stats.faint_border_pixels > 0 {
+        warnings.push(format!(
+            "精灵 {} 的边缘有 {} 个几乎不可见的半透明杂散像素，可能扩大了裁剪区域",
+            name, stats.faint_border_pixels
+        ));
+    }
+    let visible = stats.opaque_pixels + stats.partial_pixels;
+    if stats.partial_pixels > 0 && (stats.partial_pixels as f64) < visible as f64 * STRAY_PARTIAL_RATIO {
+        warnings.push(format!(
+            "精灵 {} 几乎是 1 位透明，但含 {} 个半透明像素",
+            name, stats.partial_pixels
+        ));
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn test_alpha_kinds() {
+        assert_eq!(alpha_stats(&RgbaImage::from_pixel(4, 4, Rgba([1, 2, 3, 255]))).kind, AlphaKind::Opaque);
+
+        let mut binary = RgbaImage::from_pixel(4, 4, Rgba([1, 2, 3, 255]));
+        binary.put_pixel(0, 0, Rgba([0, 0, 0, 0]));
+        let stats = alpha_stats(&binary);
+        assert_eq!((stats.kind, stats.transparent_pixels, stats.opaque_pixels), (AlphaKind::Binary, 1, 15));
+        assert!(alpha_warnings("a.png", &stats).is_empty());
+
+        let gradient = RgbaImage::from_fn(4, 4, |x, _| Rgba([0, 0, 0, 64 * x as u8 + 60]));
+        assert_eq!(alpha_stats(&gradient).kind, AlphaKind::Gradient);
+        assert!(alpha_warnings("a.png", &alpha_stats(&gradient)).is_empty());
+    }
+
+    #[test]
+    fn test_stray_pixel_warnings() {
+        // 硬边精灵，边缘残留一个 alpha = 3 的像素
+        let mut img = RgbaImage::from_pixel(20, 20, Rgba([255, 0, 0, 255]));
+        img.put_pixel(19, 10, Rgba([255, 0, 0, 3]));
+        let stats = alpha_stats(&img);
+        assert_eq!(stats.faint_border_pixels, 1);
+
+        let warnings = alpha_warnings("hero.png", &stats);
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings.iter().all(|w| w.contains("hero.png")));
+    }
+}
@@ -0,0 +1,159 @@
+/// 锚点估算 (Anchor Estimation)
+///
+/// 根据帧内容为一组帧建议轴心点：站在地面上的角色取不透明区域的底边中点，
+/// 特效等悬空内容取 alpha 加权质心。轴心点使用 Cocos2d-x 约定，
+/// 为相对帧尺寸的 0~1 坐标，Y 轴向上（0 为底边）
+
+use image::RgbaImage;
+use serde::{Deserialize, Serialize};
+
+/// 内容底边距帧底边不超过帧高的此比例时，视为“站在地面上”
+const GROUNDED_RATIO: f64 = 0.1;
+
+/// 帧内容的范围与质心
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContentMetrics {
+    /// 不透明区域 (left, top, right, bottom)
+    pub bounds: (u32, u32, u32, u32),
+    /// alpha 加权质心（像素坐标，Y 轴向下）
+    pub centroid: (f64, f64),
+}
+
+/// 统计帧内容的范围与质心
+///
+/// # Arguments
+/// * `img` - 帧图像
+///
+/// # Returns
+/// * `Option<ContentMetrics>` - 统计结果；完全透明时返回 None
+pub fn content_metrics(img: &RgbaImage) -> Option<ContentMetrics> {
+    let (mut left, mut top, mut right, mut bottom) = (u32::MAX, u32::MAX, 0, 0);
+    let (mut sum_x, mut sum_y, mut sum_alpha) = (0.0f64, 0.0f64, 0.0f64);
+    for (x, y, pixel) in img.enumerate_pixels() {
+        let alpha = pixel[3] as f64;
+        if alpha == 0.0 {
+            continue;
+        }
+        left = left.min(x);
+        top = top.min(y);
+        right = right.max(x + 1);
+        bottom = bottom.max(y + 1);
+        sum_x += (x as f64 + 0.5) * alpha;
+        sum_y += (y as f64 + 0.5) * alpha;
+        sum_alpha += alpha;
+    }
+    (sum_alpha > 0.0).then(|| ContentMetrics {
+        bounds: (left, top, right, bottom),
+        centroid: (sum_x / sum_alpha, sum_y / sum_alpha),
+    })
+}
+
+/// 锚点估算策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AnchorStrategy {
+    /// 所有帧的内容都贴近底边时使用底边中点，否则使用质心
+    #[default]
+    Auto,
+    /// 不透明区域的底边中点（角色）
+    BottomCenter,
+    /// alpha 加权质心（特效）
+    Centroid,
+}
+
+/// 一组帧的锚点估算结果
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnchorEstimate {
+    /// 实际使用的策略（`Auto` 已解析为具体策略）
+    pub strategy: AnchorStrategy,
+    /// 每帧的轴心点（与输入顺序一致，完全透明的帧为 None）
+    pub pivots: Vec<Option<(f32, f32)>>,
+}
+
+/// 为一组帧估算轴心点
+///
+/// # Arguments
+/// * `frames` - 帧图像（原始尺寸，未裁剪）
+/// * `strategy` - 估算策略
+///
+/// # Returns
+/// * `AnchorEstimate` - 估算结果
+pub fn estimate_anchors(frames: &[RgbaImage], strategy: AnchorStrategy) -> AnchorEstimate {
+    let metrics: Vec<Option<ContentMetrics>> = frames.iter().map(content_metrics).collect();
+    let strategy = match strategy {
+        AnchorStrategy::Auto => {
+            let grounded = frames.iter().zip(&metrics).all(|(frame, metrics)| {
+                metrics.is_none_or(|m| {
+                    let gap = (frame.height() - m.bounds.3) as f64;
+                    gap <= frame.height() as f64 * GROUNDED_RATIO
+                })
+            });
+            if grounded && metrics.iter().any(Option::is_some) {
+                AnchorStrategy::BottomCenter
+            } else {
+                AnchorStrategy::Centroid
+            }
+        }
+        other => other,
+    };
+
+    let pivots = frames
+        .iter()
+        .zip(&metrics)
+        .map(|(frame, metrics)| {
+            let m = (*metrics)?;
+            let (x, y) = match strategy {
+                AnchorStrategy::BottomCenter => ((m.bounds.0 + m.bounds.2) as f64 / 2.0, m.bounds.3 as f64),
+                _ => m.centroid,
+            };
+            let (width, height) = (frame.width() as f64, frame.height() as f64);
+            Some((round_pivot(x / width), round_pivot(1.0 - y / height)))
+        })
+        .collect();
+
+    AnchorEstimate { strategy, pivots }
+}
+
+/// 保留 3 位小数
+fn round_pivot(value: f64) -> f32 {
+    ((value * 1000.0).round() / 1000.0) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    fn frame(x: u32, y: u32, w: u32, h: u32) -> RgbaImage {
+        let mut img = RgbaImage::new(20, 20);
+        for py in y..y + h {
+            for px in x..x + w {
+                img.put_pixel(px, py, Rgba([255, 255, 255, 255]));
+            }
+        }
+        img
+    }
+
+    #[test]
+    fn test_character_frames_use_bottom_center() {
+        // 两帧都站在底边上
+        let frames = vec![frame(4, 6, 8, 14), frame(6, 10, 8, 9), RgbaImage::new(20, 20)];
+        let estimate = estimate_anchors(&frames, AnchorStrategy::Auto);
+        assert_eq!(estimate.strategy, AnchorStrategy::BottomCenter);
+        assert_eq!(estimate.pivots, vec![Some((0.4, 0.0)), Some((0.5, 0.05)), None]);
+    }
+
+    #[test]
+    fn test_floating_frames_use_centroid() {
+        let frames = vec![frame(2, 2, 6, 4)];
+        let estimate = estimate_anchors(&frames, AnchorStrategy::Auto);
+        assert_eq!(estimate.strategy, AnchorStrategy::Centroid);
+        // 质心 (5, 4)
+        assert_eq!(estimate.pivots, vec![Some((0.25, 0.8))]);
+
+        let forced = estimate_anchors(&frames, AnchorStrategy::BottomCenter);
+        assert_eq!(forced.pivots, vec![Some((0.25, 0.7))]);
+        assert_eq!(content_metrics(&RgbaImage::new(4, 4)), None);
+    }
+}
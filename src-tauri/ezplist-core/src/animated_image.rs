@@ -0,0 +1,179 @@
+/// 动图导入 (Animated Image Import)
+///
+/// GIF、APNG 与动画 WebP 的帧解码由应用完成（见 `utils::image_loader::load_animation`），
+/// 这里负责识别动画文件、换算帧时长、生成帧名称与动画描述，
+/// 以及把帧排成网格图集供切分使用
+
+use image::{imageops, RgbaImage};
+use serde::Serialize;
+
+/// 单个动图的最大帧数
+pub const MAX_ANIMATION_FRAMES: usize = 2000;
+
+/// 未设置或过短的帧时长按此值播放（毫秒，与浏览器一致）
+pub const DEFAULT_FRAME_DELAY_MS: u32 = 100;
+
+/// 动画中的一帧
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimationFrame {
+    /// 导入后的精灵 ID（以图集方式导入或该帧导入失败时为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sprite_id: Option<String>,
+    /// 帧名称（`{文件名}_0001.png` 起）
+    pub name: String,
+    /// 播放时长（毫秒）
+    pub duration_ms: u32,
+}
+
+/// 导入的动画描述
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedAnimation {
+    /// 动画名称（源文件名，不含扩展名）
+    pub name: String,
+    /// 源文件路径
+    pub source: String,
+    /// 按播放顺序排列的帧
+    pub frames: Vec<AnimationFrame>,
+    /// 总时长（毫秒）
+    pub duration_ms: u32,
+}
+
+impl ImportedAnimation {
+    /// 由帧时长创建动画描述（精灵 ID 为空）
+    ///
+    /// # Arguments
+    /// * `name` - 动画名称
+    /// * `source` - 源文件路径
+    /// * `delays_ms` - 各帧时长（毫秒）
+    pub fn new(name: &str, source: &str, delays_ms: &[u32]) -> Self {
+        let frames = delays_ms
+            .iter()
+            .enumerate()
+            .map(|(index, &duration_ms)| AnimationFrame {
+                sprite_id: None,
+                name: animation_frame_name(name, index),
+                duration_ms,
+            })
+            .collect();
+        Self {
+            name: name.to_string(),
+            source: source.to_string(),
+            frames,
+            duration_ms: delays_ms.iter().sum(),
+        }
+    }
+}
+
+/// 动图第 `index` 帧（从 0 开始）的名称，如 `walk_0001.png`
+pub fn animation_frame_name(name: &str, index: usize) -> String {
+    format!("{}_{:04}.png", name, index + 1)
+}
+
+/// 把解码器给出的帧时长（`numer / denom` 毫秒）换算为播放时长
+///
+/// 不超过 10 毫秒的时长（含 0）按 `DEFAULT_FRAME_DELAY_MS` 处理，与浏览器播放 GIF 的行为一致
+pub fn frame_delay_ms(numer: u32, denom: u32) -> u32 {
+    let ms = if denom == 0 { 0 } else { (numer as f64 / denom as f64).round() as u32 };
+    if ms <= 10 {
+        DEFAULT_FRAME_DELAY_MS
+    } else {
+        ms
+    }
+}
+
+/// PNG 是否为 APNG（`IDAT` 之前有 `acTL` 块）
+pub fn is_animated_png(data: &[u8]) -> bool {
+    if !data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return false;
+    }
+    let mut pos = 8;
+    while pos + 8 <= data.len() {
+        let len = u32::from_be_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]) as usize;
+        match &data[pos + 4..pos + 8] {
+            b"acTL" => return true,
+            b"IDAT" | b"IEND" => return false,
+            _ => {}
+        }
+        // 长度 + 类型 + 数据 + CRC
+        pos = match pos.checked_add(12 + len) {
+            Some(next) => next,
+            None => return false,
+        };
+    }
+    false
+}
+
+/// WebP 是否为动画（`VP8X` 块的动画标志位）
+pub fn is_animated_webp(data: &[u8]) -> bool {
+    data.len() >= 21 && &data[0..4] == b"RIFF" && &data[8..12] == b"WEBP" && &data[12..16] == b"VP8X" && data[20] & 0x02 != 0
+}
+
+/// 把帧排成接近正方形的网格图集（按行排列，单元格为最大帧尺寸，帧在单元格内居中）
+///
+/// # Arguments
+/// * `frames` - 按播放顺序排列的帧
+///
+/// # Returns
+/// * `Option<(RgbaImage, u32, u32)>` - 图集与列数、行数；没有帧时返回 None
+pub fn compose_frame_sheet(frames: &[RgbaImage]) -> Option<(RgbaImage, u32, u32)> {
+    let cell_width = frames.iter().map(|f| f.width()).max()?;
+    let cell_height = frames.iter().map(|f| f.height()).max()?;
+    let count = frames.len() as u32;
+    let cols = (count as f64).sqrt().ceil() as u32;
+    let rows = count.div_ceil(cols);
+    let mut sheet = RgbaImage::new(cell_width * cols, cell_height * rows);
+    for (index, frame) in frames.iter().enumerate() {
+        let (col, row) = (index as u32 % cols, index as u32 / cols);
+        let x = col * cell_width + (cell_width - frame.width()) / 2;
+        let y = row * cell_height + (cell_height - frame.height()) / 2;
+        imageops::replace(&mut sheet, frame, x as i64, y as i64);
+    }
+    Some((sheet, cols, rows))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn test_animation_detection_and_delays() {
+        let mut apng = b"\x89PNG\r\n\x1a\n".to_vec();
+        apng.extend_from_slice(&[0, 0, 0, 13]);
+        apng.extend_from_slice(b"IHDR");
+        apng.extend_from_slice(&[0; 17]);
+        apng.extend_from_slice(&[0, 0, 0, 8]);
+        apng.extend_from_slice(b"acTL");
+        assert!(is_animated_png(&apng));
+        apng[37..41].copy_from_slice(b"IDAT");
+        assert!(!is_animated_png(&apng));
+
+        let mut webp = b"RIFF\0\0\0\0WEBPVP8X\x0a\0\0\0\x02".to_vec();
+        assert!(is_animated_webp(&webp));
+        webp[20] = 0x10;
+        assert!(!is_animated_webp(&webp));
+
+        assert_eq!(frame_delay_ms(0, 1), DEFAULT_FRAME_DELAY_MS);
+        assert_eq!(frame_delay_ms(10, 1), DEFAULT_FRAME_DELAY_MS);
+        assert_eq!(frame_delay_ms(200, 3), 67);
+
+        let animation = ImportedAnimation::new("walk", "/tmp/walk.gif", &[100, 50]);
+        assert_eq!(animation.duration_ms, 150);
+        assert_eq!(animation.frames[1].name, "walk_0002.png");
+        let json = serde_json::to_value(&animation).unwrap();
+        assert_eq!(json["frames"][0], serde_json::json!({ "name": "walk_0001.png", "durationMs": 100 }));
+    }
+
+    #[test]
+    fn test_compose_frame_sheet() {
+        let frames: Vec<RgbaImage> = (0..5).map(|i| RgbaImage::from_pixel(4, 3, Rgba([i, 0, 0, 255]))).collect();
+        let (sheet, cols, rows) = compose_frame_sheet(&frames).unwrap();
+        assert_eq!((cols, rows, sheet.dimensions()), (3, 2, (12, 6)));
+        assert_eq!(sheet.get_pixel(4, 0)[0], 1);
+        assert_eq!(sheet.get_pixel(4, 3)[0], 4);
+        assert_eq!(sheet.get_pixel(8, 3)[3], 0);
+        assert!(compose_frame_sheet(&[]).is_none());
+    }
+}
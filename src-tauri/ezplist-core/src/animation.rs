@@ -0,0 +1,259 @@
+/// 动画预览 (Animation Preview)
+///
+/// 动画播放所需的帧时长计算、洋葱皮合成与 GIF / APNG 预览的帧准备。
+/// 每帧的起始时间按 `round(i * 1000 / fps)` 计算，
+/// 避免逐帧取整导致整段动画的时长漂移
+
+use crate::strip::strip_cell_size;
+use image::{imageops, RgbaImage};
+use serde::{Deserialize, Serialize};
+
+/// 允许的最大帧率
+pub const MAX_PREVIEW_FPS: f32 = 120.0;
+
+/// 计算每帧的播放时长
+///
+/// # Arguments
+/// * `frame_count` - 帧数
+/// * `fps` - 帧率
+///
+/// # Returns
+/// * `Result<Vec<u32>, String>` - 每帧时长（毫秒），总和等于 `round(frame_count * 1000 / fps)`
+pub fn frame_durations_ms(frame_count: usize, fps: f32) -> Result<Vec<u32>, String> {
+    if !fps.is_finite() || fps <= 0.0 || fps > MAX_PREVIEW_FPS {
+        return Err(format!("帧率无效: {}（范围 0~{}）", fps, MAX_PREVIEW_FPS));
+    }
+
+    let start_ms = |i: usize| (i as f64 * 1000.0 / fps as f64).round() as u32;
+    Ok((0..frame_count)
+        .map(|i| start_ms(i + 1) - start_ms(i))
+        .collect())
+}
+
+/// 洋葱皮着色强度（与原色按此比例混合）
+const ONION_TINT_STRENGTH: f32 = 0.5;
+
+/// 洋葱皮合成选项
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnionSkinOptions {
+    /// 前一帧的不透明度（0~1），更早的帧线性递减
+    pub opacity: f32,
+    /// 前序帧的着色（RGB，可选）
+    pub tint: Option<[u8; 3]>,
+}
+
+impl Default for OnionSkinOptions {
+    fn default() -> Self {
+        Self {
+            opacity: 0.5,
+            tint: None,
+        }
+    }
+}
+
+/// 合成洋葱皮预览图
+///
+/// 最后一帧为当前帧，以原始不透明度绘制在最上层；
+/// 之前的帧按时间由远到近叠加，距离当前帧 k 步的帧不透明度为
+/// `opacity * (N - k + 1) / N`（N 为前序帧数）。
+/// 尺寸不一致的帧在画布内居中
+///
+/// # Arguments
+/// * `frames` - 按播放顺序排列的连续帧，最后一帧为当前帧
+/// * `options` - 合成选项
+///
+/// # Returns
+/// * `Option<RgbaImage>` - 合成图像，帧列表为空时返回 None
+pub fn compose_onion_skin(frames: &[RgbaImage], options: &OnionSkinOptions) -> Option<RgbaImage> {
+    let (current, previous) = frames.split_last()?;
+    let (width, height) = strip_cell_size(frames);
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    let opacity = options.opacity.clamp(0.0, 1.0);
+    let count = previous.len();
+    let mut canvas = RgbaImage::new(width, height);
+
+    for (i, frame) in previous.iter().enumerate() {
+        let steps_back = count - i;
+        let frame_opacity = opacity * (count - steps_back + 1) as f32 / count as f32;
+        let ghost = ghost_frame(frame, frame_opacity, options.tint);
+        overlay_centered(&mut canvas, &ghost);
+    }
+    overlay_centered(&mut canvas, current);
+
+    Some(canvas)
+}
+
+/// 生成降低不透明度（并可选着色）的帧
+fn ghost_frame(frame: &RgbaImage, opacity: f32, tint: Option<[u8; 3]>) -> RgbaImage {
+    let mut ghost = frame.clone();
+    for pixel in ghost.pixels_mut() {
+        if let Some(tint) = tint {
+            for c in 0..3 {
+                let mixed = pixel[c] as f32 * (1.0 - ONION_TINT_STRENGTH) + tint[c] as f32 * ONION_TINT_STRENGTH;
+                pixel[c] = mixed.round() as u8;
+            }
+        }
+        pixel[3] = (pixel[3] as f32 * opacity).round() as u8;
+    }
+    ghost
+}
+
+fn overlay_centered(canvas: &mut RgbaImage, frame: &RgbaImage) {
+    let x = (canvas.width() - frame.width()) / 2;
+    let y = (canvas.height() - frame.height()) / 2;
+    imageops::overlay(canvas, frame, x as i64, y as i64);
+}
+
+/// 动图预览的文件格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PreviewFormat {
+    /// GIF（256 色，帧时长精度 10 毫秒）
+    #[default]
+    Gif,
+    /// APNG（真彩色与完整 alpha）
+    Apng,
+}
+
+impl PreviewFormat {
+    /// 文件扩展名（APNG 沿用 `.png`）
+    pub fn extension(self) -> &'static str {
+        match self {
+            PreviewFormat::Gif => "gif",
+            PreviewFormat::Apng => "png",
+        }
+    }
+
+    /// MIME 类型（用于 data URL）
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            PreviewFormat::Gif => "image/gif",
+            PreviewFormat::Apng => "image/apng",
+        }
+    }
+}
+
+/// 动图预览导出选项
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimationExportOptions {
+    /// 文件格式（默认 GIF）
+    #[serde(default)]
+    pub format: PreviewFormat,
+    /// Plist 路径；设置时动图写到 Plist 旁（见 `preview_file_name`），否则以 base64 data URL 返回
+    pub plist_path: Option<String>,
+}
+
+/// 写到 Plist 旁的动图文件名，如 `hero.plist` 对应 `hero_preview.gif`
+///
+/// 加上 `_preview` 后缀，避免 APNG 覆盖同名的纹理 PNG
+pub fn preview_file_name(plist_stem: &str, format: PreviewFormat) -> String {
+    format!("{}_preview.{}", plist_stem, format.extension())
+}
+
+/// 把帧居中放入统一尺寸的画布（GIF / APNG 的每帧都覆盖整个画布）
+///
+/// # Arguments
+/// * `frames` - 按播放顺序排列的帧
+///
+/// # Returns
+/// * `Option<Vec<RgbaImage>>` - 与最大帧同尺寸的帧，帧列表为空或尺寸为 0 时返回 None
+pub fn pad_frames(frames: &[RgbaImage]) -> Option<Vec<RgbaImage>> {
+    let (width, height) = strip_cell_size(frames);
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    Some(
+        frames
+            .iter()
+            .map(|frame| {
+                if frame.dimensions() == (width, height) {
+                    return frame.clone();
+                }
+                let mut canvas = RgbaImage::new(width, height);
+                let x = (width - frame.width()) / 2;
+                let y = (height - frame.height()) / 2;
+                imageops::replace(&mut canvas, frame, x as i64, y as i64);
+                canvas
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_durations_do_not_drift() {
+        let durations = frame_durations_ms(24, 24.0).unwrap();
+        assert_eq!(durations.iter().sum::<u32>(), 1000);
+        assert!(durations.iter().all(|d| *d == 41 || *d == 42));
+
+        assert_eq!(frame_durations_ms(3, 10.0).unwrap(), vec![100, 100, 100]);
+    }
+
+    #[test]
+    fn test_invalid_fps() {
+        assert!(frame_durations_ms(4, 0.0).is_err());
+        assert!(frame_durations_ms(4, -12.0).is_err());
+        assert!(frame_durations_ms(4, f32::NAN).is_err());
+        assert!(frame_durations_ms(4, 1000.0).is_err());
+    }
+
+    #[test]
+    fn test_onion_skin_fades_previous_frames() {
+        use image::Rgba;
+
+        // 三帧各占一个像素列，便于分别检查
+        let mut frames = Vec::new();
+        for x in 0..3 {
+            let mut frame = RgbaImage::new(3, 1);
+            frame.put_pixel(x, 0, Rgba([200, 200, 200, 255]));
+            frames.push(frame);
+        }
+
+        let options = OnionSkinOptions {
+            opacity: 0.6,
+            tint: Some([255, 0, 0]),
+        };
+        let result = compose_onion_skin(&frames, &options).unwrap();
+
+        // 最早的帧不透明度最低，前一帧为 opacity
+        assert_eq!(result.get_pixel(0, 0)[3], 77);
+        assert_eq!(result.get_pixel(1, 0)[3], 153);
+        // 着色后偏向红色
+        let tinted = result.get_pixel(1, 0);
+        assert!(tinted[0] > 220 && tinted[1] < 110 && tinted[2] < 110);
+        // 当前帧保持原样
+        assert_eq!(result.get_pixel(2, 0), &Rgba([200, 200, 200, 255]));
+
+        assert!(compose_onion_skin(&[], &options).is_none());
+    }
+
+    #[test]
+    fn test_preview_frames_and_names() {
+        use image::Rgba;
+
+        let frames = vec![
+            RgbaImage::from_pixel(4, 2, Rgba([255, 0, 0, 255])),
+            RgbaImage::from_pixel(2, 2, Rgba([0, 255, 0, 255])),
+        ];
+        let padded = pad_frames(&frames).unwrap();
+        assert!(padded.iter().all(|f| f.dimensions() == (4, 2)));
+        assert_eq!(padded[0], frames[0]);
+        assert_eq!(padded[1].get_pixel(0, 0)[3], 0);
+        assert_eq!(padded[1].get_pixel(1, 1), &Rgba([0, 255, 0, 255]));
+        assert!(pad_frames(&[]).is_none());
+
+        assert_eq!(preview_file_name("hero", PreviewFormat::Gif), "hero_preview.gif");
+        assert_eq!(preview_file_name("hero", PreviewFormat::Apng), "hero_preview.png");
+        let options: AnimationExportOptions = serde_json::from_str(r#"{"format":"apng"}"#).unwrap();
+        assert_eq!((options.format, options.plist_path), (PreviewFormat::Apng, None));
+    }
+}
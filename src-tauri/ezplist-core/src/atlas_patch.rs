@@ -0,0 +1,178 @@
+/// 锁定布局的就地修补 (Locked-Layout Atlas Patching)
+///
+/// 保持已有图集中每一帧的位置、尺寸、旋转与裁剪偏移不变，只把替换图像写回对应区域，
+/// plist 无需改动。替换图像可以是原始尺寸（按原裁剪区域截取，区域外必须完全透明），
+/// 也可以是已裁剪为帧尺寸的图像。旋转帧按 Cocos2d-x 约定在纹理中顺时针旋转 90 度存放
+
+use crate::types::PackedSprite;
+use image::imageops;
+use image::RgbaImage;
+
+/// 推算帧的裁剪区域在原始图像中的左上角
+///
+/// 与 `trim::trim_transparent` 的偏移计算互逆；偏移经过取整，
+/// 原始尺寸与帧尺寸之差为奇数时有两个候选位置，取第一个与偏移一致的位置
+///
+/// # Returns
+/// * `Option<(u32, u32)>` - 左上角；偏移与尺寸不一致时返回 None
+pub fn trim_origin(frame: &PackedSprite) -> Option<(u32, u32)> {
+    offset_origin(
+        frame.frame_size(),
+        (frame.original_width, frame.original_height),
+        (frame.offset_x, frame.offset_y),
+    )
+}
+
+/// 由帧尺寸、原始尺寸与偏移推算裁剪区域的左上角（见 [`trim_origin`]）
+///
+/// # Arguments
+/// * `size` - 帧尺寸（未旋转）
+/// * `source` - 原始尺寸
+/// * `offset` - 裁剪偏移（Cocos2d-x Y 轴向上）
+pub fn offset_origin(size: (u32, u32), source: (u32, u32), offset: (i32, i32)) -> Option<(u32, u32)> {
+    let left = axis_origin(source.0, size.0, offset.0)?;
+    let top = axis_origin(source.1, size.1, -offset.1)?;
+    Some((left, top))
+}
+
+/// 单个方向上满足 `round(start + size / 2 - original / 2) == offset` 的起点
+fn axis_origin(original: u32, size: u32, offset: i32) -> Option<u32> {
+    if size > original {
+        return None;
+    }
+    let center = (original as f64 - size as f64) / 2.0 + offset as f64;
+    [center.floor(), center.ceil()]
+        .into_iter()
+        .filter(|start| *start >= 0.0 && start + size as f64 <= original as f64)
+        .find(|start| (start + size as f64 / 2.0 - original as f64 / 2.0).round() as i32 == offset)
+        .map(|start| start as u32)
+}
+
+/// 把替换图像写入纹理中该帧的区域
+///
+/// # Arguments
+/// * `texture` - 图集纹理
+/// * `frame` - 帧（来自已有 plist）
+/// * `replacement` - 替换图像（原始尺寸或帧尺寸）
+///
+/// # Returns
+/// * `Result<bool, String>` - 区域内容是否发生变化；尺寸不符、
+///   不透明像素超出原裁剪区域或帧超出纹理时返回错误
+pub fn patch_frame(texture: &mut RgbaImage, frame: &PackedSprite, replacement: &RgbaImage) -> Result<bool, String> {
+    let dimensions = replacement.dimensions();
+    let (width, height) = frame.frame_size();
+    let content = if dimensions == (frame.original_width, frame.original_height) {
+        let (left, top) = trim_origin(frame).ok_or_else(|| format!("帧 {} 的裁剪偏移无效", frame.name))?;
+        if has_opaque_outside(replacement, left, top, width, height) {
+            return Err(format!(
+                "帧 {} 的新图像超出原裁剪区域，无法保持布局（需要重新打包）",
+                frame.name
+            ));
+        }
+        imageops::crop_imm(replacement, left, top, width, height).to_image()
+    } else if dimensions == (width, height) {
+        replacement.clone()
+    } else {
+        return Err(format!(
+            "帧 {} 的新图像尺寸 {}x{} 与原图 {}x{} 或裁剪后 {}x{} 都不一致",
+            frame.name,
+            dimensions.0,
+            dimensions.1,
+            frame.original_width,
+            frame.original_height,
+            width,
+            height
+        ));
+    };
+    let content = if frame.rotated { imageops::rotate90(&content) } else { content };
+
+    let fits = frame.x.checked_add(content.width()).is_some_and(|r| r <= texture.width())
+        && frame.y.checked_add(content.height()).is_some_and(|b| b <= texture.height());
+    if !fits {
+        return Err(format!("帧 {} 超出纹理范围", frame.name));
+    }
+
+    let current = imageops::crop_imm(texture, frame.x, frame.y, content.width(), content.height()).to_image();
+    if current == content {
+        return Ok(false);
+    }
+    // 直接覆盖而不是混合，透明像素也要写回
+    imageops::replace(texture, &content, frame.x as i64, frame.y as i64);
+    Ok(true)
+}
+
+/// 指定矩形之外是否有不透明像素
+fn has_opaque_outside(img: &RgbaImage, left: u32, top: u32, width: u32, height: u32) -> bool {
+    img.enumerate_pixels().any(|(x, y, pixel)| {
+        let inside = x >= left && x < left + width && y >= top && y < top + height;
+        !inside && pixel[3] != 0
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trim::trim_transparent;
+    use image::Rgba;
+
+    fn frame(trim: &crate::trim::TrimResult, x: u32, y: u32, rotated: bool) -> PackedSprite {
+        let (width, height) = (trim.trimmed_width, trim.trimmed_height);
+        PackedSprite {
+            id: "a".to_string(),
+            name: "a.png".to_string(),
+            x,
+            y,
+            width: if rotated { height } else { width },
+            height: if rotated { width } else { height },
+            rotated,
+            original_width: trim.original_width,
+            original_height: trim.original_height,
+            trimmed: true,
+            offset_x: trim.offset_x,
+            offset_y: trim.offset_y,
+            flip_x: false,
+            flip_y: false,
+            anchor: None,
+            nine_slice: None,
+        }
+    }
+
+    fn sprite(left: u32, top: u32, color: [u8; 4]) -> RgbaImage {
+        let mut img = RgbaImage::new(9, 7);
+        for y in top..top + 3 {
+            for x in left..left + 2 {
+                img.put_pixel(x, y, Rgba(color));
+            }
+        }
+        img
+    }
+
+    #[test]
+    fn test_trim_origin_inverts_trim_offsets() {
+        for left in 0..7 {
+            for top in 0..4 {
+                let trim = trim_transparent(&sprite(left, top, [255, 0, 0, 255]));
+                assert_eq!(trim_origin(&frame(&trim, 0, 0, false)), Some((left, top)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_patch_frame_writes_only_changes() {
+        let original = sprite(3, 2, [255, 0, 0, 255]);
+        let trim = trim_transparent(&original);
+        let rotated = frame(&trim, 4, 1, true);
+        let mut texture = RgbaImage::new(16, 16);
+        imageops::replace(&mut texture, &imageops::rotate90(&trim.trimmed_image), 4, 1);
+
+        assert!(!patch_frame(&mut texture, &rotated, &original).unwrap());
+        assert!(patch_frame(&mut texture, &rotated, &sprite(3, 2, [0, 255, 0, 255])).unwrap());
+        // 旋转后占用 3x2 区域
+        assert_eq!(*texture.get_pixel(6, 2), Rgba([0, 255, 0, 255]));
+        assert_eq!(texture.get_pixel(7, 1)[3], 0);
+
+        // 内容移出原裁剪区域时拒绝
+        assert!(patch_frame(&mut texture, &rotated, &sprite(0, 0, [0, 0, 255, 255])).is_err());
+        assert!(patch_frame(&mut texture, &rotated, &RgbaImage::new(5, 5)).is_err());
+    }
+}
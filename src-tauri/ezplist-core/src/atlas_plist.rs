@@ -0,0 +1,223 @@
+/// 图集 Plist 读取 (Atlas Plist Reader)
+///
+/// 读取已有的 Cocos2d-x 图集 plist（format 2 与 3，包括 TexturePacker 的输出），
+/// 还原每帧在纹理中的位置、旋转与裁剪信息，供就地修补等基于已有图集的功能使用
+
+use crate::plist_parse::{parse_pair_f64, point_field, rect_field, size_field, string_field};
+use crate::types::{NineSlice, PackedSprite};
+use plist::{Dictionary, Value};
+
+/// 已有图集的描述
+#[derive(Debug, Clone)]
+pub struct AtlasPlist {
+    /// plist 格式版本（2 或 3）
+    pub format: i64,
+    /// 纹理文件名（相对于 plist 所在目录）
+    pub texture: String,
+    /// 纹理宽度
+    pub width: u32,
+    /// 纹理高度
+    pub height: u32,
+    /// 帧（ID 与名称相同，顺序与文件中一致；宽高为在纹理中占用的尺寸）
+    pub frames: Vec<PackedSprite>,
+}
+
+impl AtlasPlist {
+    /// 按名称查找帧
+    pub fn frame(&self, name: &str) -> Option<&PackedSprite> {
+        self.frames.iter().find(|f| f.name == name)
+    }
+}
+
+/// 解析图集 plist
+///
+/// # Arguments
+/// * `data` - plist 文件内容（XML 或二进制）
+///
+/// # Returns
+/// * `Result<AtlasPlist, String>` - 图集描述；格式不支持或字段缺失时返回错误
+pub fn parse_atlas_plist(data: &[u8]) -> Result<AtlasPlist, String> {
+    let root = parse_root(data)?;
+    let frames = root
+        .frames
+        .iter()
+        .map(|(name, value)| {
+            let dict = value
+                .as_dictionary()
+                .ok_or_else(|| format!("帧 {} 不是字典", name))?;
+            parse_frame(name, dict, root.format).map_err(|e| format!("帧 {}: {}", name, e))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    Ok(AtlasPlist {
+        format: root.format,
+        texture: root.texture,
+        width: root.width,
+        height: root.height,
+        frames,
+    })
+}
+
+/// 图集 plist 的 metadata 与尚未解析的帧字典
+pub(crate) struct AtlasRoot {
+    pub format: i64,
+    pub texture: String,
+    pub width: u32,
+    pub height: u32,
+    pub frames: Dictionary,
+}
+
+/// 解析 plist 根字典与 metadata
+pub(crate) fn parse_root(data: &[u8]) -> Result<AtlasRoot, String> {
+    let root = Value::from_reader(std::io::Cursor::new(data))
+        .map_err(|e| format!("无法解析 Plist: {}", e))?;
+    let mut root = root.into_dictionary().ok_or("Plist 根节点不是字典")?;
+    let metadata = root
+        .get("metadata")
+        .and_then(Value::as_dictionary)
+        .ok_or("Plist 缺少 metadata")?;
+
+    let format = metadata
+        .get("format")
+        .and_then(|v| v.as_signed_integer())
+        .ok_or("metadata 缺少 format")?;
+    if format != 2 && format != 3 {
+        return Err(format!("不支持的 Plist 格式: {}（仅支持 2 与 3）", format));
+    }
+
+    let texture = ["realTextureFileName", "textureFileName"]
+        .iter()
+        .find_map(|key| metadata.get(key).and_then(Value::as_string))
+        .ok_or("metadata 缺少纹理文件名")?
+        .to_string();
+    let (width, height) = size_field(metadata, "size")?;
+
+    let frames = root
+        .remove("frames")
+        .and_then(Value::into_dictionary)
+        .ok_or("Plist 缺少 frames")?;
+
+    Ok(AtlasRoot {
+        format,
+        texture,
+        width,
+        height,
+        frames,
+    })
+}
+
+/// 解析单帧（format 2 与 3 的键名不同，含义相同）
+pub(crate) fn parse_frame(name: &str, dict: &Dictionary, format: i64) -> Result<PackedSprite, String> {
+    let (rect_key, rotated_key, offset_key, source_key) = if format == 3 {
+        ("textureRect", "textureRotated", "spriteOffset", "spriteSourceSize")
+    } else {
+        ("frame", "rotated", "offset", "sourceSize")
+    };
+
+    let (x, y, width, height) = rect_field(dict, rect_key)?;
+    let rotated = dict.get(rotated_key).and_then(Value::as_boolean).unwrap_or(false);
+    let (offset_x, offset_y) = match dict.get(offset_key) {
+        Some(_) => point_field(dict, offset_key)?,
+        None => (0, 0),
+    };
+    let (original_width, original_height) = match dict.get(source_key) {
+        Some(_) => size_field(dict, source_key)?,
+        None => (width, height),
+    };
+    let trimmed = width != original_width || height != original_height;
+    let flag = |key: &str| dict.get(key).and_then(Value::as_boolean).unwrap_or(false);
+    let anchor = match dict.get("anchor") {
+        Some(_) => {
+            let (x, y) = parse_pair_f64(string_field(dict, "anchor")?).map_err(|e| format!("字段 anchor: {}", e))?;
+            Some((x as f32, y as f32))
+        }
+        None => None,
+    };
+    let nine_slice = match dict.get("capInsets") {
+        Some(_) => {
+            let (left, top, center_width, center_height) = rect_field(dict, "capInsets")?;
+            let right = left.checked_add(center_width).and_then(|used| width.checked_sub(used));
+            let bottom = top.checked_add(center_height).and_then(|used| height.checked_sub(used));
+            match (right, bottom) {
+                (Some(right), Some(bottom)) => Some(NineSlice { left, right, top, bottom }),
+                _ => return Err("字段 capInsets 超出帧尺寸".to_string()),
+            }
+        }
+        None => None,
+    };
+    // plist 中的矩形尺寸为未旋转时的帧尺寸，PackedSprite 记录在纹理中占用的尺寸
+    let (width, height) = if rotated { (height, width) } else { (width, height) };
+
+    Ok(PackedSprite {
+        id: name.to_string(),
+        name: name.to_string(),
+        x,
+        y,
+        width,
+        height,
+        rotated,
+        original_width,
+        original_height,
+        trimmed,
+        offset_x,
+        offset_y,
+        flip_x: flag("flipX"),
+        flip_y: flag("flipY"),
+        anchor,
+        nine_slice,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORMAT3: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0"><dict>
+<key>frames</key><dict>
+  <key>hero.png</key><dict>
+    <key>spriteOffset</key><string>{-1,2}</string>
+    <key>spriteSize</key><string>{10,20}</string>
+    <key>spriteSourceSize</key><string>{16,32}</string>
+    <key>textureRect</key><string>{{4,8},{10,20}}</string>
+    <key>textureRotated</key><true/>
+  </dict>
+</dict>
+<key>metadata</key><dict>
+  <key>format</key><integer>3</integer>
+  <key>realTextureFileName</key><string>ui.png</string>
+  <key>size</key><string>{64,128}</string>
+  <key>textureFileName</key><string>ui.png</string>
+</dict>
+</dict></plist>"#;
+
+    #[test]
+    fn test_parse_format3() {
+        let atlas = parse_atlas_plist(FORMAT3.as_bytes()).unwrap();
+        assert_eq!((atlas.format, atlas.texture.as_str(), atlas.width, atlas.height), (3, "ui.png", 64, 128));
+        let hero = atlas.frame("hero.png").unwrap();
+        assert_eq!((hero.x, hero.y, hero.width, hero.height), (4, 8, 20, 10));
+        assert_eq!(hero.frame_size(), (10, 20));
+        assert_eq!((hero.offset_x, hero.offset_y), (-1, 2));
+        assert_eq!((hero.original_width, hero.original_height), (16, 32));
+        assert!(hero.rotated && hero.trimmed);
+    }
+
+    #[test]
+    fn test_parse_format2_and_errors() {
+        let format2 = FORMAT3
+            .replace("<integer>3</integer>", "<integer>2</integer>")
+            .replace("textureRect", "frame")
+            .replace("textureRotated", "rotated")
+            .replace("spriteOffset", "offset")
+            .replace("spriteSourceSize", "sourceSize")
+            .replace("{{4,8},{10,20}}", "{{ 4, 8 }, { 10.0, 20 }}");
+        let atlas = parse_atlas_plist(format2.as_bytes()).unwrap();
+        assert_eq!(atlas.frame("hero.png").unwrap().frame_size(), (10, 20));
+
+        let format1 = FORMAT3.replace("<integer>3</integer>", "<integer>1</integer>");
+        assert!(parse_atlas_plist(format1.as_bytes()).is_err());
+        let broken = FORMAT3.replace("{{4,8},{10,20}}", "{{4,8},{10}}");
+        assert!(parse_atlas_plist(broken.as_bytes()).unwrap_err().contains("hero.png"));
+    }
+}
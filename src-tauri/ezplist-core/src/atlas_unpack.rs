@@ -0,0 +1,163 @@
+/// 图集拆分 (Atlas Unpacking)
+///
+/// 从已有图集的纹理中裁出每一帧：旋转帧转回正向，
+/// 再按 spriteSourceSize 与 spriteOffset 把裁剪后的内容放回原始尺寸的透明画布，
+/// 得到与打包前一致的单帧图像
+
+use crate::atlas_patch::trim_origin;
+use crate::mirror::flip;
+use crate::types::PackedSprite;
+use image::{imageops, RgbaImage};
+use std::path::{Component, Path, PathBuf};
+
+/// 从纹理中还原一帧的原始图像
+///
+/// # Arguments
+/// * `texture` - 图集纹理
+/// * `frame` - 帧（来自已有 plist，宽高为在纹理中占用的尺寸）
+///
+/// # Returns
+/// * `Result<RgbaImage, String>` - 原始尺寸的帧图像；帧超出纹理或偏移无效时返回错误
+pub fn extract_frame(texture: &RgbaImage, frame: &PackedSprite) -> Result<RgbaImage, String> {
+    let fits = frame.x.checked_add(frame.width).is_some_and(|r| r <= texture.width())
+        && frame.y.checked_add(frame.height).is_some_and(|b| b <= texture.height());
+    if !fits {
+        return Err(format!(
+            "帧 {} 的区域 ({}, {}, {}x{}) 超出纹理 {}x{}",
+            frame.name,
+            frame.x,
+            frame.y,
+            frame.width,
+            frame.height,
+            texture.width(),
+            texture.height()
+        ));
+    }
+
+    let content = imageops::crop_imm(texture, frame.x, frame.y, frame.width, frame.height).to_image();
+    // 旋转帧在纹理中顺时针旋转了 90 度
+    let content = if frame.rotated { imageops::rotate270(&content) } else { content };
+    // 翻转帧的纹理区域为源帧
+    let content = if frame.is_flipped() { flip(&content, frame.flip_x, frame.flip_y) } else { content };
+    if (content.width(), content.height()) == (frame.original_width, frame.original_height) {
+        return Ok(content);
+    }
+
+    let (left, top) = trim_origin(frame).ok_or_else(|| format!("帧 {} 的裁剪偏移无效", frame.name))?;
+    let mut image = RgbaImage::new(frame.original_width, frame.original_height);
+    imageops::replace(&mut image, &content, left as i64, top as i64);
+    Ok(image)
+}
+
+/// 帧名称对应的输出相对路径
+///
+/// 保留名称中的子目录（如 `hero/run_01.png`），没有扩展名时补上 `.png`
+///
+/// # Arguments
+/// * `name` - 帧名称
+///
+/// # Returns
+/// * `Result<PathBuf, String>` - 相对路径；名称为空、为绝对路径或包含 `..` 时返回错误
+pub fn frame_file_path(name: &str) -> Result<PathBuf, String> {
+    let path = Path::new(name);
+    let mut relative = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => relative.push(part),
+            Component::CurDir => {}
+            _ => return Err(format!("帧名称 {} 不是安全的相对路径", name)),
+        }
+    }
+    if relative.as_os_str().is_empty() {
+        return Err("帧名称为空".to_string());
+    }
+    if relative.extension().is_none() {
+        relative.set_extension("png");
+    }
+    Ok(relative)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image_processor::render_texture;
+    use crate::job::PackJob;
+    use crate::trim::trim_transparent;
+    use image::Rgba;
+
+    /// 16x12 的精灵，内容为 (3,2)-(9,7) 的渐变色块
+    fn sprite() -> RgbaImage {
+        let mut img = RgbaImage::new(16, 12);
+        for y in 2..7 {
+            for x in 3..9 {
+                img.put_pixel(x, y, Rgba([x as u8 * 20, y as u8 * 30, 100, 255]));
+            }
+        }
+        img
+    }
+
+    fn frame(name: &str, x: u32, y: u32, rotated: bool) -> PackedSprite {
+        let trim = trim_transparent(&sprite());
+        let (width, height) = (trim.trimmed_width, trim.trimmed_height);
+        PackedSprite {
+            id: name.to_string(),
+            name: name.to_string(),
+            x,
+            y,
+            width: if rotated { height } else { width },
+            height: if rotated { width } else { height },
+            rotated,
+            original_width: trim.original_width,
+            original_height: trim.original_height,
+            trimmed: true,
+            offset_x: trim.offset_x,
+            offset_y: trim.offset_y,
+            flip_x: false,
+            flip_y: false,
+            anchor: None,
+            nine_slice: None,
+        }
+    }
+
+    #[test]
+    fn test_extract_frame_round_trip() {
+        let trimmed = trim_transparent(&sprite()).trimmed_image;
+        let frames = vec![frame("a.png", 0, 0, false), frame("b.png", 8, 0, true)];
+        let texture = render_texture(&frames, 16, 8, |_| Some(&trimmed)).unwrap();
+
+        for frame in &frames {
+            assert_eq!(extract_frame(&texture, frame).unwrap(), sprite(), "{}", frame.name);
+        }
+
+        let outside = PackedSprite { x: 12, ..frames[0].clone() };
+        assert!(extract_frame(&texture, &outside).is_err());
+    }
+
+    #[test]
+    fn test_packed_rotation_round_trip() {
+        // 裁剪后 6x5 的内容只有旋转后才能放入 5x6 的纹理
+        let output = PackJob::new()
+            .max_dimensions(5, 6)
+            .auto_size(false)
+            .padding(0)
+            .add_sprite("a", "a.png", sprite())
+            .run()
+            .unwrap();
+        let frame = &output.result.packed_sprites[0];
+        assert!(frame.rotated);
+        let texture = render_texture(&output.result.packed_sprites, 5, 6, |s| {
+            output.trims.get(&s.id).map(|trim| &trim.trimmed_image)
+        })
+        .unwrap();
+        assert_eq!(extract_frame(&texture, frame).unwrap(), sprite());
+    }
+
+    #[test]
+    fn test_frame_file_path() {
+        assert_eq!(frame_file_path("hero/run_01.png").unwrap(), Path::new("hero/run_01.png"));
+        assert_eq!(frame_file_path("./coin").unwrap(), Path::new("coin.png"));
+        assert!(frame_file_path("../escape.png").is_err());
+        assert!(frame_file_path("/abs.png").is_err());
+        assert!(frame_file_path("").is_err());
+    }
+}
@@ -0,0 +1,128 @@
+/// 帧渗色检查 (Frame Bleed Detection)
+///
+/// 双线性过滤会在帧边缘采样相邻像素，相邻帧间隔不足时邻帧的颜色会渗入当前帧。
+/// 不使用 mipmap 时 1 像素间隔即可，使用 mipmap 时需要 `mipmap::required_padding` 给出的间距。
+/// 用于检查已导入的图集，提示用户开启边缘扩展后重新打包
+
+use crate::mipmap::required_padding;
+use crate::types::PackedSprite;
+use serde::Serialize;
+
+/// 一对间隔不足的相邻帧
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BleedPair {
+    /// 第一帧名称
+    pub first: String,
+    /// 第二帧名称
+    pub second: String,
+    /// 两帧在纹理中的间隔（像素，重叠时为 0）
+    pub distance: u32,
+}
+
+/// 双线性过滤下不渗色所需的最小间隔
+///
+/// # Arguments
+/// * `mipmap_levels` - 原始尺寸之外的 mipmap 级数
+///
+/// # Returns
+/// * `u32` - 最小间隔（至少为 1）
+pub fn min_bleed_distance(mipmap_levels: u32) -> u32 {
+    required_padding(mipmap_levels).max(1)
+}
+
+/// 纹理中的矩形 [left, top, right, bottom)
+type Rect = (u64, u64, u64, u64);
+
+/// 帧在纹理中占用的矩形
+fn footprint(frame: &PackedSprite) -> Rect {
+    let (x, y) = (frame.x as u64, frame.y as u64);
+    (x, y, x + frame.width as u64, y + frame.height as u64)
+}
+
+/// 查找间隔小于 `min_distance` 的帧对
+///
+/// 两帧的间隔取水平与垂直间隔中的较大值（对角相邻时两个方向都不足才会渗色）
+///
+/// # Arguments
+/// * `frames` - 图集中的帧
+/// * `min_distance` - 最小间隔
+///
+/// # Returns
+/// * `Vec<BleedPair>` - 间隔不足的帧对，按间隔从小到大排列
+pub fn find_bleed_pairs(frames: &[PackedSprite], min_distance: u32) -> Vec<BleedPair> {
+    let mut sorted: Vec<(&PackedSprite, Rect)> = frames.iter().map(|f| (f, footprint(f))).collect();
+    sorted.sort_by_key(|(_, rect)| rect.0);
+
+    let min_distance = min_distance as u64;
+    let mut pairs = Vec::new();
+    for (i, (frame, a)) in sorted.iter().enumerate() {
+        // 按左边界排序，之后的帧左边界超出范围即可停止
+        for (other, b) in sorted[i + 1..].iter().take_while(|(_, b)| b.0 < a.2 + min_distance) {
+            let dx = b.0.saturating_sub(a.2).max(a.0.saturating_sub(b.2));
+            let dy = b.1.saturating_sub(a.3).max(a.1.saturating_sub(b.3));
+            let distance = dx.max(dy);
+            if distance < min_distance {
+                pairs.push(BleedPair {
+                    first: frame.name.clone(),
+                    second: other.name.clone(),
+                    distance: distance as u32,
+                });
+            }
+        }
+    }
+    pairs.sort_by_key(|p| p.distance);
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(name: &str, x: u32, y: u32, width: u32, height: u32, rotated: bool) -> PackedSprite {
+        PackedSprite {
+            id: name.to_string(),
+            name: name.to_string(),
+            x,
+            y,
+            width,
+            height,
+            rotated,
+            original_width: if rotated { height } else { width },
+            original_height: if rotated { width } else { height },
+            trimmed: false,
+            offset_x: 0,
+            offset_y: 0,
+            flip_x: false,
+            flip_y: false,
+            anchor: None,
+            nine_slice: None,
+        }
+    }
+
+    #[test]
+    fn test_find_bleed_pairs() {
+        let frames = vec![
+            frame("a", 0, 0, 10, 10, false),
+            // 紧贴 a 的右侧
+            frame("b", 10, 0, 10, 10, false),
+            // 与 b 间隔 3 像素
+            frame("c", 23, 0, 10, 10, false),
+            // 旋转后占用 10x4，与 a 在垂直方向间隔 2 像素
+            frame("d", 0, 12, 10, 4, true),
+            // 与 d 对角相邻，水平间隔 4、垂直间隔 1
+            frame("e", 14, 17, 4, 4, false),
+        ];
+
+        let pairs = find_bleed_pairs(&frames, min_bleed_distance(0));
+        assert_eq!(
+            pairs,
+            vec![BleedPair { first: "a".to_string(), second: "b".to_string(), distance: 0 }]
+        );
+
+        let pairs = find_bleed_pairs(&frames, min_bleed_distance(2));
+        let names: Vec<(&str, &str, u32)> =
+            pairs.iter().map(|p| (p.first.as_str(), p.second.as_str(), p.distance)).collect();
+        assert_eq!(names, vec![("a", "b", 0), ("a", "d", 2), ("d", "b", 2), ("b", "c", 3)]);
+    }
+}
@@ -0,0 +1,249 @@
+/// 连通区域帧检测 (Blob Frame Detection)
+///
+/// 紧密排布但没有网格、也没有帧数据的图集无法按行列切分。这里用泛洪填充找出
+/// 所有不透明的连通区域，把相距很近的区域（如角色与分离的武器、特效粒子）合并，
+/// 得到每帧的包围盒
+
+use serde::Deserialize;
+use image::RgbaImage;
+
+/// 连通区域检测选项
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BlobOptions {
+    /// alpha 大于该值的像素视为不透明
+    pub alpha_threshold: u8,
+    /// 包围盒间距不超过该像素数的区域合并为一帧
+    pub merge_distance: u32,
+    /// 不透明像素少于该数量的区域视为噪点丢弃
+    pub min_pixels: u32,
+}
+
+impl Default for BlobOptions {
+    fn default() -> Self {
+        Self {
+            alpha_threshold: 10,
+            merge_distance: 2,
+            min_pixels: 4,
+        }
+    }
+}
+
+/// 检测到的帧包围盒
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Blob {
+    /// 左上角 X 坐标
+    pub x: u32,
+    /// 左上角 Y 坐标
+    pub y: u32,
+    /// 宽度
+    pub width: u32,
+    /// 高度
+    pub height: u32,
+    /// 所在行（按阅读顺序分行，从 0 开始）
+    pub row: u32,
+    /// 行内序号（从 0 开始）
+    pub col: u32,
+    /// 包含的不透明像素数
+    pub pixels: u32,
+}
+
+impl Blob {
+    fn right(&self) -> u32 {
+        self.x + self.width
+    }
+
+    fn bottom(&self) -> u32 {
+        self.y + self.height
+    }
+
+    /// 两个包围盒在水平和垂直方向上的间距都不超过 `distance` 时视为相邻
+    fn is_near(&self, other: &Blob, distance: u32) -> bool {
+        let gap_x = other.x.saturating_sub(self.right()).max(self.x.saturating_sub(other.right()));
+        let gap_y = other.y.saturating_sub(self.bottom()).max(self.y.saturating_sub(other.bottom()));
+        gap_x <= distance && gap_y <= distance
+    }
+
+    fn union(&self, other: &Blob) -> Blob {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        Blob {
+            x,
+            y,
+            width: self.right().max(other.right()) - x,
+            height: self.bottom().max(other.bottom()) - y,
+            row: 0,
+            col: 0,
+            pixels: self.pixels + other.pixels,
+        }
+    }
+}
+
+/// 检测图像中的不透明连通区域
+///
+/// 像素按 8 邻域连通；相邻区域反复合并直到稳定，结果按阅读顺序排列：
+/// 垂直方向有重叠的帧归为一行，行内从左到右
+///
+/// # Arguments
+/// * `img` - 图集图像
+/// * `options` - 检测选项
+///
+/// # Returns
+/// * `Vec<Blob>` - 各帧包围盒
+pub fn detect_blobs(img: &RgbaImage, options: &BlobOptions) -> Vec<Blob> {
+    let (width, height) = img.dimensions();
+    let opaque = |x: u32, y: u32| img.get_pixel(x, y)[3] > options.alpha_threshold;
+    let mut visited = vec![false; width as usize * height as usize];
+    let mut blobs = Vec::new();
+    let mut stack = Vec::new();
+
+    for start_y in 0..height {
+        for start_x in 0..width {
+            let index = (start_y * width + start_x) as usize;
+            if visited[index] || !opaque(start_x, start_y) {
+                continue;
+            }
+
+            // 用显式栈泛洪填充，避免大区域递归溢出
+            visited[index] = true;
+            stack.push((start_x, start_y));
+            let (mut min_x, mut min_y, mut max_x, mut max_y) = (start_x, start_y, start_x, start_y);
+            let mut pixels = 0u32;
+            while let Some((x, y)) = stack.pop() {
+                pixels += 1;
+                min_x = min_x.min(x);
+                min_y = min_y.min(y);
+                max_x = max_x.max(x);
+                max_y = max_y.max(y);
+                for ny in y.saturating_sub(1)..=(y + 1).min(height - 1) {
+                    for nx in x.saturating_sub(1)..=(x + 1).min(width - 1) {
+                        let neighbor = (ny * width + nx) as usize;
+                        if !visited[neighbor] && opaque(nx, ny) {
+                            visited[neighbor] = true;
+                            stack.push((nx, ny));
+                        }
+                    }
+                }
+            }
+
+            blobs.push(Blob {
+                x: min_x,
+                y: min_y,
+                width: max_x - min_x + 1,
+                height: max_y - min_y + 1,
+                row: 0,
+                col: 0,
+                pixels,
+            });
+        }
+    }
+
+    let mut blobs = merge_nearby(blobs, options.merge_distance);
+    blobs.retain(|blob| blob.pixels >= options.min_pixels);
+    order_by_rows(&mut blobs);
+    blobs
+}
+
+/// 合并相邻区域；合并后的包围盒变大可能又与其他区域相邻，因此重复到没有可合并的为止
+fn merge_nearby(mut blobs: Vec<Blob>, distance: u32) -> Vec<Blob> {
+    loop {
+        let mut merged: Vec<Blob> = Vec::with_capacity(blobs.len());
+        let mut changed = false;
+        for blob in blobs {
+            match merged.iter_mut().find(|existing| existing.is_near(&blob, distance)) {
+                Some(existing) => {
+                    *existing = existing.union(&blob);
+                    changed = true;
+                }
+                None => merged.push(blob),
+            }
+        }
+        if !changed {
+            return merged;
+        }
+        blobs = merged;
+    }
+}
+
+/// 按阅读顺序排序并填写行列号
+fn order_by_rows(blobs: &mut [Blob]) {
+    blobs.sort_by_key(|blob| (blob.y, blob.x));
+
+    let mut row = 0;
+    let mut row_start = 0;
+    let mut row_bottom = 0;
+    for i in 0..blobs.len() {
+        if i > row_start && blobs[i].y >= row_bottom {
+            blobs[row_start..i].sort_by_key(|blob| blob.x);
+            row += 1;
+            row_start = i;
+        }
+        if i == row_start {
+            row_bottom = blobs[i].bottom();
+        } else {
+            row_bottom = row_bottom.max(blobs[i].bottom());
+        }
+        blobs[i].row = row;
+    }
+    blobs[row_start..].sort_by_key(|blob| blob.x);
+
+    for i in 0..blobs.len() {
+        blobs[i].col = if i > 0 && blobs[i - 1].row == blobs[i].row { blobs[i - 1].col + 1 } else { 0 };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    fn fill(img: &mut RgbaImage, x: u32, y: u32, width: u32, height: u32) {
+        for py in y..y + height {
+            for px in x..x + width {
+                img.put_pixel(px, py, Rgba([255, 255, 255, 255]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_detect_irregular_blobs() {
+        let mut img = RgbaImage::new(64, 48);
+        // 第一行：两帧，第二帧比第一帧低一些
+        fill(&mut img, 2, 2, 10, 12);
+        fill(&mut img, 20, 6, 16, 8);
+        // 与第二帧相距 2 像素的碎片合并到第二帧
+        fill(&mut img, 38, 6, 2, 2);
+        // 第二行
+        fill(&mut img, 4, 30, 8, 8);
+        // 单个噪点被丢弃
+        fill(&mut img, 60, 44, 1, 1);
+
+        let blobs = detect_blobs(&img, &BlobOptions::default());
+        let rects: Vec<_> = blobs.iter().map(|b| (b.x, b.y, b.width, b.height, b.row, b.col)).collect();
+        assert_eq!(rects, vec![
+            (2, 2, 10, 12, 0, 0),
+            (20, 6, 20, 8, 0, 1),
+            (4, 30, 8, 8, 1, 0),
+        ]);
+        assert_eq!(blobs[1].pixels, 16 * 8 + 4);
+    }
+
+    #[test]
+    fn test_diagonal_pixels_connect_and_merge_chains() {
+        let mut img = RgbaImage::new(16, 16);
+        for i in 0..4 {
+            fill(&mut img, i, i, 1, 1);
+        }
+        // 不合并时三个互相间隔 3 像素的块各自成帧，合并距离为 3 时连成一帧
+        fill(&mut img, 7, 0, 2, 2);
+        fill(&mut img, 12, 0, 2, 2);
+        let separate = detect_blobs(&img, &BlobOptions { merge_distance: 0, ..Default::default() });
+        assert_eq!(separate.len(), 3);
+        assert_eq!((separate[0].width, separate[0].height), (4, 4));
+
+        let merged = detect_blobs(&img, &BlobOptions { merge_distance: 3, ..Default::default() });
+        assert_eq!(merged.len(), 1);
+        assert_eq!((merged[0].x, merged[0].width, merged[0].height), (0, 14, 4));
+        assert!(detect_blobs(&RgbaImage::new(8, 8), &BlobOptions::default()).is_empty());
+    }
+}
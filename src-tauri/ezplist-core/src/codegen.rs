@@ -0,0 +1,293 @@
+/// 帧索引代码生成 (Frame Index Code Generation)
+///
+/// 把帧名称映射为图集序号与帧序号，生成 TypeScript 枚举、C++ 头文件或 Lua 表，
+/// 游戏代码用生成的常量引用帧，帧被重命名或删除时在编译期（或加载时）暴露问题。
+///
+/// 常量名由帧名称转换而来：去掉扩展名，非字母数字字符替换为下划线并转为大写，
+/// 例如 `hero/run_01.png` -> `HERO_RUN_01`。TypeScript 与 C++ 中的序号从 0 开始，
+/// Lua 中从 1 开始（与 Lua 数组下标一致）
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// 输出语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CodegenLanguage {
+    /// TypeScript 枚举（.ts）
+    TypeScript,
+    /// C++ 头文件（.h，需要 C++17）
+    Cpp,
+    /// Lua 模块（.lua）
+    Lua,
+}
+
+impl CodegenLanguage {
+    /// 输出文件的默认扩展名
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::TypeScript => "ts",
+            Self::Cpp => "h",
+            Self::Lua => "lua",
+        }
+    }
+}
+
+/// 一张图集中的帧
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameIndexAtlas {
+    /// 纹理文件名
+    pub texture: String,
+    /// 帧名称（按图集中的顺序）
+    pub frames: Vec<String>,
+}
+
+/// 一帧的常量信息
+struct FrameEntry<'a> {
+    ident: String,
+    name: &'a str,
+    atlas: usize,
+    index: usize,
+}
+
+/// 帧名称转换为常量名
+///
+/// # Arguments
+/// * `name` - 帧名称
+///
+/// # Returns
+/// * `Option<String>` - 常量名；名称中没有任何 ASCII 字母或数字时返回 None
+pub fn frame_identifier(name: &str) -> Option<String> {
+    // 只去掉最后一段路径中的扩展名
+    let stem = match name.rfind('.') {
+        Some(dot) if dot > 0 && !name[dot..].contains('/') => &name[..dot],
+        _ => name,
+    };
+
+    let mut ident = String::new();
+    for c in stem.chars() {
+        if c.is_ascii_alphanumeric() {
+            ident.push(c.to_ascii_uppercase());
+        } else if !ident.is_empty() && !ident.ends_with('_') {
+            ident.push('_');
+        }
+    }
+    let ident = ident.trim_end_matches('_');
+    if ident.is_empty() {
+        return None;
+    }
+    if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        return Some(format!("FRAME_{}", ident));
+    }
+    Some(ident.to_string())
+}
+
+/// 生成帧索引代码
+///
+/// # Arguments
+/// * `type_name` - 生成的类型/模块名（如 `HeroFrame`），须为合法标识符
+/// * `atlases` - 图集及其帧（多页图集按页序排列）
+/// * `language` - 输出语言
+///
+/// # Returns
+/// * `Result<String, String>` - 代码文本；常量名冲突或无法转换时返回错误
+pub fn generate_frame_index(
+    type_name: &str,
+    atlases: &[FrameIndexAtlas],
+    language: CodegenLanguage,
+) -> Result<String, String> {
+    if !is_identifier(type_name) {
+        return Err(format!("类型名称不是合法的标识符: {}", type_name));
+    }
+
+    let mut entries = Vec::new();
+    let mut seen: HashMap<String, &str> = HashMap::new();
+    for (atlas, sheet) in atlases.iter().enumerate() {
+        for (index, name) in sheet.frames.iter().enumerate() {
+            let ident = frame_identifier(name)
+                .ok_or_else(|| format!("无法为帧 {} 生成常量名，请使用包含字母或数字的名称", name))?;
+            if let Some(other) = seen.insert(ident.clone(), name) {
+                return Err(format!("帧 {} 与 {} 的常量名冲突: {}", other, name, ident));
+            }
+            entries.push(FrameEntry { ident, name, atlas, index });
+        }
+    }
+    if entries.is_empty() {
+        return Err("没有帧可导出".to_string());
+    }
+
+    let textures: Vec<&str> = atlases.iter().map(|a| a.texture.as_str()).collect();
+    Ok(match language {
+        CodegenLanguage::TypeScript => render_typescript(type_name, &textures, &entries),
+        CodegenLanguage::Cpp => render_cpp(type_name, &textures, &entries),
+        CodegenLanguage::Lua => render_lua(type_name, &textures, &entries),
+    })
+}
+
+const HEADER: &str = "由 EzPlist 生成，请勿手动修改";
+
+fn render_typescript(type_name: &str, textures: &[&str], entries: &[FrameEntry]) -> String {
+    let mut out = format!("// {}\n\n", HEADER);
+    let _ = writeln!(out, "export enum {} {{", type_name);
+    for (i, e) in entries.iter().enumerate() {
+        let _ = writeln!(out, "  {} = {},", e.ident, i);
+    }
+    out.push_str("}\n\n");
+
+    let _ = writeln!(out, "export const {}Textures = [", type_name);
+    for texture in textures {
+        let _ = writeln!(out, "  {},", quote(texture));
+    }
+    out.push_str("] as const;\n\n");
+
+    let _ = writeln!(out, "/** 按枚举值索引：图集序号、图集内帧序号、帧名称 */");
+    let _ = writeln!(out, "export const {}Info = [", type_name);
+    for e in entries {
+        let _ = writeln!(out, "  {{ atlas: {}, index: {}, name: {} }},", e.atlas, e.index, quote(e.name));
+    }
+    out.push_str("] as const;\n");
+    out
+}
+
+fn render_cpp(type_name: &str, textures: &[&str], entries: &[FrameEntry]) -> String {
+    let mut out = format!("// {}\n#pragma once\n\n#include <cstdint>\n\n", HEADER);
+    let _ = writeln!(out, "enum class {} : std::uint32_t {{", type_name);
+    for (i, e) in entries.iter().enumerate() {
+        let _ = writeln!(out, "    {} = {},", e.ident, i);
+    }
+    out.push_str("};\n\n");
+
+    let _ = writeln!(out, "struct {}Info {{", type_name);
+    out.push_str("    std::uint32_t atlas;\n    std::uint32_t index;\n    const char* name;\n};\n\n");
+
+    let _ = writeln!(out, "inline constexpr const char* k{}Textures[] = {{", type_name);
+    for texture in textures {
+        let _ = writeln!(out, "    {},", quote(texture));
+    }
+    out.push_str("};\n\n");
+
+    let _ = writeln!(out, "// 按枚举值索引");
+    let _ = writeln!(out, "inline constexpr {0}Info k{0}Info[] = {{", type_name);
+    for e in entries {
+        let _ = writeln!(out, "    {{{}, {}, {}}},", e.atlas, e.index, quote(e.name));
+    }
+    out.push_str("};\n");
+    out
+}
+
+fn render_lua(type_name: &str, textures: &[&str], entries: &[FrameEntry]) -> String {
+    let mut out = format!("-- {}\n-- 序号从 1 开始\n\n", HEADER);
+    let _ = writeln!(out, "local {} = {{}}\n", type_name);
+
+    let _ = writeln!(out, "{}.textures = {{", type_name);
+    for texture in textures {
+        let _ = writeln!(out, "  {},", quote(texture));
+    }
+    out.push_str("}\n\n");
+
+    let _ = writeln!(out, "{}.frames = {{", type_name);
+    for e in entries {
+        let _ = writeln!(
+            out,
+            "  {} = {{ atlas = {}, index = {}, name = {} }},",
+            e.ident,
+            e.atlas + 1,
+            e.index + 1,
+            quote(e.name)
+        );
+    }
+    out.push_str("}\n\n");
+    let _ = writeln!(out, "return {}", type_name);
+    out
+}
+
+/// 是否为合法标识符（三种语言的公共子集）
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// 转为双引号字符串字面量（三种语言的转义规则在这些字符上一致）
+fn quote(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn atlases() -> Vec<FrameIndexAtlas> {
+        vec![
+            FrameIndexAtlas {
+                texture: "hero-0.png".to_string(),
+                frames: vec!["hero/idle_0.png".to_string(), "hero/run-1.png".to_string()],
+            },
+            FrameIndexAtlas {
+                texture: "hero-1.png".to_string(),
+                frames: vec!["3.png".to_string()],
+            },
+        ]
+    }
+
+    #[test]
+    fn test_frame_identifier() {
+        assert_eq!(frame_identifier("hero/idle_0.png").as_deref(), Some("HERO_IDLE_0"));
+        assert_eq!(frame_identifier("Run  Left.v2.png").as_deref(), Some("RUN_LEFT_V2"));
+        assert_eq!(frame_identifier("01.png").as_deref(), Some("FRAME_01"));
+        assert_eq!(frame_identifier("dir.v1/walk").as_deref(), Some("DIR_V1_WALK"));
+        assert_eq!(frame_identifier("英雄.png"), None);
+    }
+
+    #[test]
+    fn test_generate_typescript() {
+        let code = generate_frame_index("HeroFrame", &atlases(), CodegenLanguage::TypeScript).unwrap();
+        assert!(code.contains("export enum HeroFrame {\n  HERO_IDLE_0 = 0,\n  HERO_RUN_1 = 1,\n  FRAME_3 = 2,\n}"));
+        assert!(code.contains("export const HeroFrameTextures = [\n  \"hero-0.png\",\n  \"hero-1.png\",\n] as const;"));
+        assert!(code.contains("  { atlas: 1, index: 0, name: \"3.png\" },"));
+    }
+
+    #[test]
+    fn test_generate_cpp_and_lua() {
+        let cpp = generate_frame_index("HeroFrame", &atlases(), CodegenLanguage::Cpp).unwrap();
+        assert!(cpp.contains("enum class HeroFrame : std::uint32_t {"));
+        assert!(cpp.contains("inline constexpr HeroFrameInfo kHeroFrameInfo[] = {\n    {0, 0, \"hero/idle_0.png\"},"));
+
+        let lua = generate_frame_index("HeroFrame", &atlases(), CodegenLanguage::Lua).unwrap();
+        assert!(lua.contains("  FRAME_3 = { atlas = 2, index = 1, name = \"3.png\" },"));
+        assert!(lua.ends_with("return HeroFrame\n"));
+    }
+
+    #[test]
+    fn test_generate_rejects_invalid_input() {
+        let mut colliding = atlases();
+        colliding[1].frames.push("hero-idle-0.png".to_string());
+        let err = generate_frame_index("HeroFrame", &colliding, CodegenLanguage::Lua).unwrap_err();
+        assert!(err.contains("HERO_IDLE_0"));
+
+        assert!(generate_frame_index("hero frame", &atlases(), CodegenLanguage::Lua).is_err());
+        assert!(generate_frame_index("HeroFrame", &[], CodegenLanguage::Lua).is_err());
+    }
+
+    #[test]
+    fn test_quote_escapes() {
+        assert_eq!(quote("a\"b\\c"), "\"a\\\"b\\\\c\"");
+    }
+}
@@ -0,0 +1,252 @@
+/// 未压缩 DDS 解码 (Uncompressed DDS Decoding)
+///
+/// image crate 只支持 DXT 压缩的 DDS，旧游戏资源中常见的未压缩 DDS
+/// （按位掩码存储的 RGB/RGBA/亮度数据）在这里解码为 RGBA8。
+/// 只读取第一层 mipmap
+
+use image::RgbaImage;
+
+/// DDS 文件头长度（含 4 字节魔数）
+pub const DDS_HEADER_LEN: usize = 128;
+
+/// DX10 扩展头长度
+const DX10_HEADER_LEN: usize = 20;
+
+const DDPF_ALPHAPIXELS: u32 = 0x1;
+const DDPF_ALPHA: u32 = 0x2;
+const DDPF_FOURCC: u32 = 0x4;
+const DDPF_RGB: u32 = 0x40;
+const DDPF_LUMINANCE: u32 = 0x20000;
+
+/// 像素数据的存储方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DdsLayout {
+    /// 按位掩码存储的未压缩数据
+    Masked {
+        bit_count: u32,
+        masks: [u32; 4],
+        luminance: bool,
+    },
+    /// DXT/BC 等压缩数据（交给 image crate 解码）
+    Compressed,
+}
+
+/// DDS 文件头
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DdsHeader {
+    pub width: u32,
+    pub height: u32,
+    pub layout: DdsLayout,
+    /// 像素数据在文件中的起始位置
+    pub data_offset: usize,
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([data[offset], data[offset + 1], data[offset + 2], data[offset + 3]])
+}
+
+impl DdsHeader {
+    /// 解析 DDS 文件头
+    ///
+    /// # Arguments
+    /// * `data` - 文件开头的字节（至少 `DDS_HEADER_LEN`，DX10 格式需再多 20 字节）
+    ///
+    /// # Returns
+    /// * `Result<DdsHeader, String>` - 文件头或错误信息
+    pub fn parse(data: &[u8]) -> Result<DdsHeader, String> {
+        if data.len() < DDS_HEADER_LEN || !data.starts_with(b"DDS ") {
+            return Err("无效的 DDS 文件头".to_string());
+        }
+        if read_u32(data, 4) != 124 || read_u32(data, 76) != 32 {
+            return Err("无效的 DDS 文件头大小".to_string());
+        }
+
+        let height = read_u32(data, 12);
+        let width = read_u32(data, 16);
+        let flags = read_u32(data, 80);
+        let fourcc = &data[84..88];
+
+        let (layout, data_offset) = if flags & DDPF_FOURCC != 0 && fourcc == b"DX10" {
+            if data.len() < DDS_HEADER_LEN + DX10_HEADER_LEN {
+                return Err("DDS DX10 扩展头不完整".to_string());
+            }
+            let layout = match read_u32(data, DDS_HEADER_LEN) {
+                // DXGI_FORMAT_R8G8B8A8_UNORM(_SRGB)
+                28 | 29 => DdsLayout::Masked {
+                    bit_count: 32,
+                    masks: [0x0000_00FF, 0x0000_FF00, 0x00FF_0000, 0xFF00_0000],
+                    luminance: false,
+                },
+                // DXGI_FORMAT_B8G8R8A8_UNORM(_SRGB)
+                87 | 91 => DdsLayout::Masked {
+                    bit_count: 32,
+                    masks: [0x00FF_0000, 0x0000_FF00, 0x0000_00FF, 0xFF00_0000],
+                    luminance: false,
+                },
+                // DXGI_FORMAT_B8G8R8X8_UNORM(_SRGB)
+                88 | 93 => DdsLayout::Masked {
+                    bit_count: 32,
+                    masks: [0x00FF_0000, 0x0000_FF00, 0x0000_00FF, 0],
+                    luminance: false,
+                },
+                _ => DdsLayout::Compressed,
+            };
+            (layout, DDS_HEADER_LEN + DX10_HEADER_LEN)
+        } else if flags & DDPF_FOURCC != 0 {
+            (DdsLayout::Compressed, DDS_HEADER_LEN)
+        } else if flags & (DDPF_RGB | DDPF_LUMINANCE | DDPF_ALPHA) != 0 {
+            let bit_count = read_u32(data, 88);
+            if !matches!(bit_count, 8 | 16 | 24 | 32) {
+                return Err(format!("不支持的 DDS 位深: {}", bit_count));
+            }
+            let has_alpha = flags & (DDPF_ALPHAPIXELS | DDPF_ALPHA) != 0;
+            let masks = [
+                read_u32(data, 92),
+                read_u32(data, 96),
+                read_u32(data, 100),
+                if has_alpha { read_u32(data, 104) } else { 0 },
+            ];
+            let layout = DdsLayout::Masked {
+                bit_count,
+                masks,
+                luminance: flags & DDPF_LUMINANCE != 0,
+            };
+            (layout, DDS_HEADER_LEN)
+        } else {
+            return Err("无法识别的 DDS 像素格式".to_string());
+        };
+
+        Ok(DdsHeader {
+            width,
+            height,
+            layout,
+            data_offset,
+        })
+    }
+}
+
+/// 从掩码中提取通道值并缩放到 8 位
+fn extract_channel(pixel: u32, mask: u32) -> Option<u8> {
+    if mask == 0 {
+        return None;
+    }
+    let shift = mask.trailing_zeros();
+    let max = (mask >> shift) as u64;
+    let value = ((pixel & mask) >> shift) as u64;
+    Some((value * 255 / max) as u8)
+}
+
+/// 解码未压缩 DDS
+///
+/// # Arguments
+/// * `data` - 完整的 DDS 文件内容
+///
+/// # Returns
+/// * `Result<RgbaImage, String>` - 解码后的 RGBA 图像；压缩格式或数据不完整时返回错误
+pub fn decode_uncompressed(data: &[u8]) -> Result<RgbaImage, String> {
+    let header = DdsHeader::parse(data)?;
+    let DdsLayout::Masked {
+        bit_count,
+        masks,
+        luminance,
+    } = header.layout
+    else {
+        return Err("压缩的 DDS 数据不能按未压缩格式解码".to_string());
+    };
+
+    if header.width == 0 || header.height == 0 {
+        return Err(format!("DDS 尺寸无效: {}x{}", header.width, header.height));
+    }
+
+    let bytes_per_pixel = (bit_count / 8) as usize;
+    let row_len = (header.width as usize)
+        .checked_mul(bytes_per_pixel)
+        .ok_or("DDS 尺寸过大")?;
+    let needed = row_len
+        .checked_mul(header.height as usize)
+        .and_then(|len| len.checked_add(header.data_offset))
+        .ok_or("DDS 尺寸过大")?;
+    if data.len() < needed {
+        return Err("DDS 像素数据不完整".to_string());
+    }
+
+    let pixels = &data[header.data_offset..needed];
+    let mut img = RgbaImage::new(header.width, header.height);
+
+    for (chunk, out) in pixels.chunks_exact(bytes_per_pixel).zip(img.pixels_mut()) {
+        let mut raw = [0u8; 4];
+        raw[..bytes_per_pixel].copy_from_slice(chunk);
+        let pixel = u32::from_le_bytes(raw);
+
+        let alpha = extract_channel(pixel, masks[3]).unwrap_or(255);
+        out.0 = if luminance {
+            let l = extract_channel(pixel, masks[0]).unwrap_or(0);
+            [l, l, l, alpha]
+        } else {
+            [
+                extract_channel(pixel, masks[0]).unwrap_or(0),
+                extract_channel(pixel, masks[1]).unwrap_or(0),
+                extract_channel(pixel, masks[2]).unwrap_or(0),
+                alpha,
+            ]
+        };
+    }
+
+    Ok(img)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn legacy_header(width: u32, height: u32, flags: u32, bit_count: u32, masks: [u32; 4]) -> Vec<u8> {
+        let mut data = vec![0u8; DDS_HEADER_LEN];
+        data[0..4].copy_from_slice(b"DDS ");
+        data[4..8].copy_from_slice(&124u32.to_le_bytes());
+        data[12..16].copy_from_slice(&height.to_le_bytes());
+        data[16..20].copy_from_slice(&width.to_le_bytes());
+        data[76..80].copy_from_slice(&32u32.to_le_bytes());
+        data[80..84].copy_from_slice(&flags.to_le_bytes());
+        data[88..92].copy_from_slice(&bit_count.to_le_bytes());
+        for (i, mask) in masks.iter().enumerate() {
+            data[92 + i * 4..96 + i * 4].copy_from_slice(&mask.to_le_bytes());
+        }
+        data
+    }
+
+    #[test]
+    fn test_decode_bgra32() {
+        let mut data = legacy_header(
+            2,
+            1,
+            DDPF_RGB | DDPF_ALPHAPIXELS,
+            32,
+            [0x00FF_0000, 0x0000_FF00, 0x0000_00FF, 0xFF00_0000],
+        );
+        data.extend_from_slice(&[0x30, 0x20, 0x10, 0x80, 0x00, 0x00, 0xFF, 0xFF]);
+
+        let img = decode_uncompressed(&data).unwrap();
+        assert_eq!(img.get_pixel(0, 0).0, [0x10, 0x20, 0x30, 0x80]);
+        assert_eq!(img.get_pixel(1, 0).0, [0xFF, 0x00, 0x00, 0xFF]);
+    }
+
+    #[test]
+    fn test_decode_rgb565_without_alpha() {
+        let mut data = legacy_header(1, 1, DDPF_RGB, 16, [0xF800, 0x07E0, 0x001F, 0xFFFF]);
+        data.extend_from_slice(&0xF800u16.to_le_bytes());
+
+        let img = decode_uncompressed(&data).unwrap();
+        assert_eq!(img.get_pixel(0, 0).0, [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_rejects_truncated_and_compressed() {
+        let data = legacy_header(4, 4, DDPF_RGB, 32, [0xFF, 0xFF00, 0xFF_0000, 0]);
+        assert!(decode_uncompressed(&data).is_err());
+
+        let mut dxt = legacy_header(4, 4, DDPF_FOURCC, 0, [0; 4]);
+        dxt[84..88].copy_from_slice(b"DXT5");
+        assert_eq!(DdsHeader::parse(&dxt).unwrap().layout, DdsLayout::Compressed);
+        assert!(decode_uncompressed(&dxt).is_err());
+    }
+}
@@ -0,0 +1,352 @@
+/// 精灵效果烘焙 (Sprite Effect Baking)
+///
+/// 在打包前把投影或描边直接绘制进精灵图像，画布按效果范围向外扩展，
+/// 之后的透明裁剪会把效果像素一并计入裁剪边界。
+/// 多个效果按顺序叠加，后一个效果作用于前一个效果的结果
+
+use image::{imageops, Rgba, RgbaImage};
+use serde::{Deserialize, Serialize};
+
+/// 单个效果允许的最大尺寸与偏移（像素）
+pub const MAX_EFFECT_SIZE: u32 = 64;
+
+/// alpha 不小于该值的像素视为描边的实心区域
+const SOLID_ALPHA: u8 = 128;
+
+/// 精灵效果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SpriteEffect {
+    /// 投影：按偏移平移 alpha 蒙版，模糊后以指定颜色绘制在精灵下方
+    #[serde(rename_all = "camelCase")]
+    DropShadow {
+        /// 水平偏移（向右为正）
+        offset_x: i32,
+        /// 垂直偏移（向下为正）
+        offset_y: i32,
+        /// 模糊半径，0 表示硬边投影
+        size: u32,
+        /// RGBA 颜色
+        color: [u8; 4],
+    },
+    /// 实心描边：沿不透明像素向外扩展指定宽度
+    Outline {
+        /// 描边宽度
+        size: u32,
+        /// RGBA 颜色
+        color: [u8; 4],
+    },
+}
+
+impl SpriteEffect {
+    /// 检查参数范围
+    pub fn validate(&self) -> Result<(), String> {
+        let (size, offset) = match self {
+            SpriteEffect::DropShadow {
+                offset_x,
+                offset_y,
+                size,
+                ..
+            } => (*size, offset_x.unsigned_abs().max(offset_y.unsigned_abs())),
+            SpriteEffect::Outline { size, .. } => {
+                if *size == 0 {
+                    return Err("描边宽度必须大于 0".to_string());
+                }
+                (*size, 0)
+            }
+        };
+        if size > MAX_EFFECT_SIZE || offset > MAX_EFFECT_SIZE {
+            return Err(format!("效果尺寸与偏移不能超过 {} 像素", MAX_EFFECT_SIZE));
+        }
+        Ok(())
+    }
+}
+
+/// 检查效果列表
+///
+/// # Arguments
+/// * `effects` - 效果列表
+///
+/// # Returns
+/// * `Result<(), String>` - 第一个无效参数的错误信息
+pub fn validate_effects(effects: &[SpriteEffect]) -> Result<(), String> {
+    effects.iter().try_for_each(SpriteEffect::validate)
+}
+
+/// 按顺序烘焙效果
+///
+/// # Arguments
+/// * `image` - 精灵图像
+/// * `effects` - 效果列表（调用前应通过 `validate_effects` 检查）
+///
+/// # Returns
+/// * `RgbaImage` - 扩展画布后的图像；效果列表为空时返回原图副本
+pub fn bake_effects(image: &RgbaImage, effects: &[SpriteEffect]) -> RgbaImage {
+    effects
+        .iter()
+        .fold(image.clone(), |current, effect| apply_effect(&current, effect))
+}
+
+fn apply_effect(image: &RgbaImage, effect: &SpriteEffect) -> RgbaImage {
+    match *effect {
+        SpriteEffect::DropShadow {
+            offset_x,
+            offset_y,
+            size,
+            color,
+        } => bake_drop_shadow(image, offset_x, offset_y, size, color),
+        SpriteEffect::Outline { size, color } => bake_outline(image, size, color),
+    }
+}
+
+fn bake_drop_shadow(image: &RgbaImage, offset_x: i32, offset_y: i32, size: u32, color: [u8; 4]) -> RgbaImage {
+    let (width, height) = image.dimensions();
+    let left = size + (-offset_x).max(0) as u32;
+    let top = size + (-offset_y).max(0) as u32;
+    let canvas_width = width + left + size + offset_x.max(0) as u32;
+    let canvas_height = height + top + size + offset_y.max(0) as u32;
+
+    // 平移后的 alpha 蒙版
+    let shadow_x = (left as i32 + offset_x) as u32;
+    let shadow_y = (top as i32 + offset_y) as u32;
+    let mut mask = vec![0u32; (canvas_width * canvas_height) as usize];
+    for (x, y, pixel) in image.enumerate_pixels() {
+        mask[((y + shadow_y) * canvas_width + x + shadow_x) as usize] = pixel[3] as u32;
+    }
+    box_blur(&mut mask, canvas_width, canvas_height, size);
+
+    let mut canvas = RgbaImage::from_fn(canvas_width, canvas_height, |x, y| {
+        let coverage = mask[(y * canvas_width + x) as usize];
+        Rgba([color[0], color[1], color[2], ((color[3] as u32 * coverage + 127) / 255) as u8])
+    });
+    imageops::overlay(&mut canvas, image, left as i64, top as i64);
+    canvas
+}
+
+fn bake_outline(image: &RgbaImage, size: u32, color: [u8; 4]) -> RgbaImage {
+    let (width, height) = image.dimensions();
+    let canvas_width = width + size * 2;
+    let canvas_height = height + size * 2;
+
+    let mut solid = vec![false; (canvas_width * canvas_height) as usize];
+    for (x, y, pixel) in image.enumerate_pixels() {
+        solid[((y + size) * canvas_width + x + size) as usize] = pixel[3] >= SOLID_ALPHA;
+    }
+    let distances = distance_field(&solid, canvas_width, canvas_height);
+
+    // 距离在 size 以内完全覆盖，向外 1 像素内线性衰减，得到抗锯齿边缘
+    let mut canvas = RgbaImage::from_fn(canvas_width, canvas_height, |x, y| {
+        let distance = distances[(y * canvas_width + x) as usize].sqrt();
+        let coverage = (size as f32 + 1.0 - distance).clamp(0.0, 1.0);
+        Rgba([color[0], color[1], color[2], (color[3] as f32 * coverage).round() as u8])
+    });
+    imageops::overlay(&mut canvas, image, size as i64, size as i64);
+    canvas
+}
+
+/// 可分离的盒式模糊，画布外视为 0
+fn box_blur(values: &mut [u32], width: u32, height: u32, radius: u32) {
+    if radius == 0 {
+        return;
+    }
+    let (width, height, radius) = (width as usize, height as usize, radius as usize);
+    let window = (radius * 2 + 1) as u32;
+    let mut line = Vec::new();
+
+    let blur_line = |line: &mut Vec<u32>| {
+        let source = line.clone();
+        let mut sum: u32 = source.iter().take(radius).sum();
+        for i in 0..source.len() {
+            if let Some(&entering) = source.get(i + radius) {
+                sum += entering;
+            }
+            line[i] = (sum + window / 2) / window;
+            if i >= radius {
+                sum -= source[i - radius];
+            }
+        }
+    };
+
+    for row in values.chunks_mut(width) {
+        line.clear();
+        line.extend_from_slice(row);
+        blur_line(&mut line);
+        row.copy_from_slice(&line);
+    }
+    for x in 0..width {
+        line.clear();
+        line.extend((0..height).map(|y| values[y * width + x]));
+        blur_line(&mut line);
+        for (y, value) in line.iter().enumerate() {
+            values[y * width + x] = *value;
+        }
+    }
+}
+
+/// 到最近实心像素的距离平方（Felzenszwalb 精确欧氏距离变换）
+///
+/// 没有任何实心像素时所有值为 `f32::INFINITY`
+fn distance_field(solid: &[bool], width: u32, height: u32) -> Vec<f32> {
+    let (width, height) = (width as usize, height as usize);
+    let mut field: Vec<f32> = solid
+        .iter()
+        .map(|&s| if s { 0.0 } else { f32::INFINITY })
+        .collect();
+    let mut line = Vec::new();
+    let mut out = Vec::new();
+
+    for x in 0..width {
+        line.clear();
+        line.extend((0..height).map(|y| field[y * width + x]));
+        distance_1d(&line, &mut out);
+        for (y, value) in out.iter().enumerate() {
+            field[y * width + x] = *value;
+        }
+    }
+    for row in field.chunks_mut(width) {
+        line.clear();
+        line.extend_from_slice(row);
+        distance_1d(&line, &mut out);
+        row.copy_from_slice(&out);
+    }
+    field
+}
+
+/// 一维距离变换：out[q] = min_p (q - p)² + f[p]，只考虑有限值的位置
+fn distance_1d(f: &[f32], out: &mut Vec<f32>) {
+    out.clear();
+    let square = |i: usize| (i * i) as f32;
+
+    // 下包络中的抛物线顶点及其起始位置
+    let mut vertices: Vec<usize> = Vec::new();
+    let mut starts: Vec<f32> = Vec::new();
+    for q in (0..f.len()).filter(|&q| f[q].is_finite()) {
+        let mut start = f32::NEG_INFINITY;
+        while let (Some(&p), Some(&p_start)) = (vertices.last(), starts.last()) {
+            start = ((f[q] + square(q)) - (f[p] + square(p))) / (2.0 * (q - p) as f32);
+            if start > p_start {
+                break;
+            }
+            vertices.pop();
+            starts.pop();
+            start = f32::NEG_INFINITY;
+        }
+        vertices.push(q);
+        starts.push(start);
+    }
+
+    if vertices.is_empty() {
+        out.resize(f.len(), f32::INFINITY);
+        return;
+    }
+
+    let mut k = 0;
+    for q in 0..f.len() {
+        while k + 1 < vertices.len() && starts[k + 1] < q as f32 {
+            k += 1;
+        }
+        let p = vertices[k];
+        out.push((q as f32 - p as f32).powi(2) + f[p]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const BLACK: [u8; 4] = [0, 0, 0, 255];
+
+    fn sprite(width: u32, height: u32) -> RgbaImage {
+        RgbaImage::from_pixel(width, height, Rgba([255, 255, 255, 255]))
+    }
+
+    #[test]
+    fn test_outline_expands_canvas() {
+        let baked = bake_effects(&sprite(4, 4), &[SpriteEffect::Outline { size: 2, color: RED }]);
+        assert_eq!(baked.dimensions(), (8, 8));
+        // 原图保持在描边之上
+        assert_eq!(baked.get_pixel(2, 2).0, [255, 255, 255, 255]);
+        // 边缘中点在描边范围内，角点只有抗锯齿的残留
+        assert_eq!(baked.get_pixel(0, 4).0, RED);
+        assert!(baked.get_pixel(0, 0)[3] < 64);
+    }
+
+    #[test]
+    fn test_outline_follows_shape() {
+        let mut img = RgbaImage::new(5, 5);
+        img.put_pixel(2, 2, Rgba([255, 255, 255, 255]));
+        let baked = bake_effects(&img, &[SpriteEffect::Outline { size: 1, color: RED }]);
+        assert_eq!(baked.dimensions(), (7, 7));
+        assert_eq!(baked.get_pixel(3, 2).0, RED);
+        // 远离像素的位置保持透明
+        assert_eq!(baked.get_pixel(0, 0)[3], 0);
+        assert_eq!(baked.get_pixel(5, 5)[3], 0);
+    }
+
+    #[test]
+    fn test_drop_shadow_offset() {
+        let shadow = SpriteEffect::DropShadow {
+            offset_x: 3,
+            offset_y: -2,
+            size: 0,
+            color: BLACK,
+        };
+        let baked = bake_effects(&sprite(4, 4), &[shadow]);
+        // 向右扩展 3 像素，向上扩展 2 像素
+        assert_eq!(baked.dimensions(), (7, 6));
+        assert_eq!(baked.get_pixel(0, 2).0, [255, 255, 255, 255]);
+        assert_eq!(baked.get_pixel(6, 0).0, BLACK);
+        assert_eq!(baked.get_pixel(0, 0)[3], 0);
+        assert_eq!(baked.get_pixel(6, 5)[3], 0);
+    }
+
+    #[test]
+    fn test_drop_shadow_blur_fades() {
+        let shadow = SpriteEffect::DropShadow {
+            offset_x: 0,
+            offset_y: 0,
+            size: 2,
+            color: BLACK,
+        };
+        let baked = bake_effects(&sprite(6, 6), &[shadow]);
+        assert_eq!(baked.dimensions(), (10, 10));
+        let edge = baked.get_pixel(1, 5)[3];
+        let outer = baked.get_pixel(0, 5)[3];
+        assert!(edge > outer && outer > 0);
+    }
+
+    #[test]
+    fn test_effects_apply_in_order() {
+        let effects = [
+            SpriteEffect::Outline { size: 1, color: RED },
+            SpriteEffect::DropShadow {
+                offset_x: 1,
+                offset_y: 1,
+                size: 0,
+                color: BLACK,
+            },
+        ];
+        let baked = bake_effects(&sprite(2, 2), &effects);
+        assert_eq!(baked.dimensions(), (5, 5));
+        assert_eq!(baked.get_pixel(4, 3).0, BLACK);
+        assert_eq!(bake_effects(&sprite(2, 2), &[]).dimensions(), (2, 2));
+    }
+
+    #[test]
+    fn test_validate_effects() {
+        assert!(validate_effects(&[SpriteEffect::Outline { size: 0, color: RED }]).is_err());
+        assert!(validate_effects(&[SpriteEffect::DropShadow {
+            offset_x: -(MAX_EFFECT_SIZE as i32) - 1,
+            offset_y: 0,
+            size: 1,
+            color: BLACK,
+        }])
+        .is_err());
+
+        let effect: SpriteEffect =
+            serde_json::from_str(r#"{"type":"dropShadow","offsetX":2,"offsetY":2,"size":1,"color":[0,0,0,128]}"#)
+                .unwrap();
+        assert!(effect.validate().is_ok());
+    }
+}
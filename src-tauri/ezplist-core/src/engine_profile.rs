@@ -0,0 +1,314 @@
+/// 引擎目标配置 (Engine Target Profiles)
+///
+/// 各游戏引擎对图集的限制（最大纹理尺寸、2 的幂、支持的数据格式、旋转与帧名规则），
+/// 用于在导出前检查打包/导出配置是否适用于目标引擎
+
+use crate::exporters::SheetFormat;
+use crate::packer::SizeConstraint;
+use serde::{Deserialize, Serialize};
+
+/// 每条帧名问题最多列出的帧名数量
+const MAX_LISTED_NAMES: usize = 5;
+
+/// 帧名检查项：是否启用、说明、是否违反
+type NameCheck = (bool, &'static str, fn(&str) -> bool);
+
+/// 目标引擎
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EngineProfile {
+    /// Cocos2d-x 3.x / 4.x
+    Cocos2dx,
+    /// Phaser 3
+    Phaser,
+    /// PixiJS
+    PixiJs,
+    /// Unity（通过 JSON 导入插件）
+    Unity,
+    /// 面向 OpenGL ES 2.0 老设备的保守配置
+    LegacyMobile,
+}
+
+/// 帧名规则
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NameRules {
+    /// 只允许 ASCII 字符
+    pub ascii_only: bool,
+    /// 不允许空白字符
+    pub no_whitespace: bool,
+    /// 帧名必须带图片扩展名（如 `.png`）
+    pub require_extension: bool,
+}
+
+/// 引擎限制
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileSpec {
+    /// 最大纹理边长
+    pub max_texture_size: u32,
+    /// 纹理宽高必须为 2 的幂
+    pub require_pot: bool,
+    /// 能读取的数据格式
+    pub formats: &'static [SheetFormat],
+    /// 是否支持旋转的帧
+    pub supports_rotation: bool,
+    /// 帧名规则
+    pub names: NameRules,
+}
+
+impl EngineProfile {
+    /// 所有引擎配置
+    pub const ALL: [EngineProfile; 5] = [
+        EngineProfile::Cocos2dx,
+        EngineProfile::Phaser,
+        EngineProfile::PixiJs,
+        EngineProfile::Unity,
+        EngineProfile::LegacyMobile,
+    ];
+
+    /// 显示名称
+    pub fn display_name(self) -> &'static str {
+        match self {
+            EngineProfile::Cocos2dx => "Cocos2d-x",
+            EngineProfile::Phaser => "Phaser 3",
+            EngineProfile::PixiJs => "PixiJS",
+            EngineProfile::Unity => "Unity",
+            EngineProfile::LegacyMobile => "老设备 (GLES 2.0)",
+        }
+    }
+
+    /// 引擎限制
+    pub fn spec(self) -> ProfileSpec {
+        const JSON: &[SheetFormat] = &[SheetFormat::JsonHash, SheetFormat::JsonArray];
+        match self {
+            EngineProfile::Cocos2dx => ProfileSpec {
+                max_texture_size: 4096,
+                require_pot: false,
+                formats: &[SheetFormat::Plist],
+                supports_rotation: true,
+                names: NameRules { ascii_only: false, no_whitespace: false, require_extension: true },
+            },
+            EngineProfile::Phaser => ProfileSpec {
+                max_texture_size: 4096,
+                require_pot: false,
+                formats: JSON,
+                supports_rotation: true,
+                names: NameRules { ascii_only: false, no_whitespace: false, require_extension: false },
+            },
+            EngineProfile::PixiJs => ProfileSpec {
+                max_texture_size: 4096,
+                require_pot: false,
+                formats: &[SheetFormat::JsonHash],
+                supports_rotation: true,
+                names: NameRules { ascii_only: false, no_whitespace: false, require_extension: false },
+            },
+            EngineProfile::Unity => ProfileSpec {
+                max_texture_size: 8192,
+                require_pot: false,
+                formats: JSON,
+                supports_rotation: false,
+                names: NameRules { ascii_only: false, no_whitespace: false, require_extension: false },
+            },
+            EngineProfile::LegacyMobile => ProfileSpec {
+                max_texture_size: 2048,
+                require_pot: true,
+                formats: &[SheetFormat::Plist, SheetFormat::JsonHash, SheetFormat::JsonArray],
+                supports_rotation: true,
+                names: NameRules { ascii_only: true, no_whitespace: true, require_extension: true },
+            },
+        }
+    }
+}
+
+/// 待检查的打包/导出配置
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileTarget {
+    /// 最大纹理宽度
+    pub max_width: u32,
+    /// 最大纹理高度
+    pub max_height: u32,
+    /// 是否自动尺寸（否则纹理尺寸即最大尺寸）
+    pub auto_size: bool,
+    /// 自动尺寸时的纹理尺寸约束
+    pub size_constraint: SizeConstraint,
+    /// 是否允许旋转
+    pub allow_rotation: bool,
+    /// 数据文件格式
+    pub format: SheetFormat,
+    /// 帧名
+    pub sprite_names: Vec<String>,
+}
+
+/// 问题类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProfileIssueKind {
+    /// 纹理超出引擎最大尺寸
+    TextureTooLarge,
+    /// 纹理尺寸可能不是 2 的幂
+    NotPowerOfTwo,
+    /// 引擎无法读取该数据格式
+    UnsupportedFormat,
+    /// 引擎不支持旋转的帧
+    RotationUnsupported,
+    /// 帧名不符合引擎规则
+    InvalidName,
+}
+
+/// 配置问题
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileIssue {
+    /// 问题类型
+    pub kind: ProfileIssueKind,
+    /// 是否会导致引擎无法使用图集（否则仅为警告）
+    pub blocking: bool,
+    /// 说明
+    pub message: String,
+}
+
+/// 按引擎配置检查打包/导出配置
+///
+/// # Arguments
+/// * `profile` - 目标引擎
+/// * `target` - 打包/导出配置
+///
+/// # Returns
+/// * `Vec<ProfileIssue>` - 发现的问题，全部满足时为空
+pub fn validate_for_profile(profile: EngineProfile, target: &ProfileTarget) -> Vec<ProfileIssue> {
+    let spec = profile.spec();
+    let engine = profile.display_name();
+    let mut issues = Vec::new();
+    let mut issue = |kind, blocking, message: String| issues.push(ProfileIssue { kind, blocking, message });
+
+    if target.max_width > spec.max_texture_size || target.max_height > spec.max_texture_size {
+        issue(
+            ProfileIssueKind::TextureTooLarge,
+            true,
+            format!(
+                "最大纹理尺寸 {}x{} 超出 {} 的上限 {}，请降低最大尺寸或使用多页打包",
+                target.max_width, target.max_height, engine, spec.max_texture_size
+            ),
+        );
+    }
+
+    if spec.require_pot {
+        // 自动尺寸按约束取整；固定尺寸时纹理即最大尺寸
+        let pot = if target.auto_size {
+            matches!(target.size_constraint, SizeConstraint::Pot | SizeConstraint::Square)
+        } else {
+            target.max_width.is_power_of_two() && target.max_height.is_power_of_two()
+        };
+        if !pot {
+            issue(
+                ProfileIssueKind::NotPowerOfTwo,
+                true,
+                format!("{} 要求纹理宽高为 2 的幂，请使用 2 的幂尺寸约束", engine),
+            );
+        }
+    }
+
+    if !spec.formats.contains(&target.format) {
+        issue(
+            ProfileIssueKind::UnsupportedFormat,
+            true,
+            format!("{} 无法读取 {:?} 格式，可用格式: {:?}", engine, target.format, spec.formats),
+        );
+    }
+
+    if target.allow_rotation && !spec.supports_rotation {
+        issue(
+            ProfileIssueKind::RotationUnsupported,
+            true,
+            format!("{} 不支持旋转的帧，请关闭旋转", engine),
+        );
+    }
+
+    let rules = spec.names;
+    let name_checks: [NameCheck; 3] = [
+        (rules.ascii_only, "包含非 ASCII 字符", |name| !name.is_ascii()),
+        (rules.no_whitespace, "包含空白字符", |name| name.chars().any(char::is_whitespace)),
+        (rules.require_extension, "缺少图片扩展名", |name| {
+            std::path::Path::new(name).extension().is_none_or(|ext| ext.is_empty())
+        }),
+    ];
+    for (enabled, reason, violates) in name_checks {
+        if !enabled {
+            continue;
+        }
+        let bad: Vec<&str> = target.sprite_names.iter().map(String::as_str).filter(|n| violates(n)).collect();
+        if bad.is_empty() {
+            continue;
+        }
+        let listed = bad[..bad.len().min(MAX_LISTED_NAMES)].join(", ");
+        let more = if bad.len() > MAX_LISTED_NAMES { " 等" } else { "" };
+        issue(
+            ProfileIssueKind::InvalidName,
+            false,
+            format!("{} 个帧名{}: {}{}", bad.len(), reason, listed, more),
+        );
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target() -> ProfileTarget {
+        ProfileTarget {
+            max_width: 2048,
+            max_height: 2048,
+            auto_size: true,
+            size_constraint: SizeConstraint::Pot,
+            allow_rotation: true,
+            format: SheetFormat::Plist,
+            sprite_names: vec!["hero_01.png".to_string()],
+        }
+    }
+
+    fn kinds(issues: &[ProfileIssue]) -> Vec<ProfileIssueKind> {
+        issues.iter().map(|i| i.kind).collect()
+    }
+
+    #[test]
+    fn test_valid_target() {
+        assert!(validate_for_profile(EngineProfile::Cocos2dx, &target()).is_empty());
+        assert!(validate_for_profile(EngineProfile::LegacyMobile, &target()).is_empty());
+    }
+
+    #[test]
+    fn test_limits() {
+        let big = ProfileTarget { max_width: 4096, ..target() };
+        let issues = validate_for_profile(EngineProfile::LegacyMobile, &big);
+        assert_eq!(kinds(&issues), vec![ProfileIssueKind::TextureTooLarge]);
+        assert!(issues[0].blocking);
+        assert!(validate_for_profile(EngineProfile::Cocos2dx, &big).is_empty());
+
+        let npot = ProfileTarget { auto_size: false, max_width: 1000, ..target() };
+        assert_eq!(kinds(&validate_for_profile(EngineProfile::LegacyMobile, &npot)), vec![ProfileIssueKind::NotPowerOfTwo]);
+
+        assert_eq!(
+            kinds(&validate_for_profile(EngineProfile::Unity, &target())),
+            vec![ProfileIssueKind::UnsupportedFormat, ProfileIssueKind::RotationUnsupported]
+        );
+    }
+
+    #[test]
+    fn test_name_rules() {
+        let names = ProfileTarget {
+            sprite_names: vec!["英雄.png".to_string(), "idle 01.png".to_string(), "walk".to_string()],
+            ..target()
+        };
+        let issues = validate_for_profile(EngineProfile::LegacyMobile, &names);
+        assert_eq!(issues.len(), 3);
+        assert!(issues.iter().all(|i| i.kind == ProfileIssueKind::InvalidName && !i.blocking));
+        assert!(issues[2].message.contains("walk"));
+
+        let cocos = validate_for_profile(EngineProfile::Cocos2dx, &names);
+        assert_eq!(cocos.len(), 1);
+    }
+}
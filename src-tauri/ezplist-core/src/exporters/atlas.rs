@@ -0,0 +1,212 @@
+/// libGDX 与 Spine 文本图集导出器 (`.atlas`)
+///
+/// 两者都以纹理页头开始，之后每个区域一段：
+/// - libGDX：旧版格式，`rotate` / `xy` / `size` / `orig` / `offset` / `index`，
+///   名称末尾的 `_数字` 拆为动画序号（与 libGDX TexturePacker 一致）
+/// - Spine：4.x 格式，`bounds` / `offsets`，名称保持完整、序号为 -1
+///
+/// 区域名称不含扩展名；坐标 Y 轴向下，裁剪偏移从原图左下角计算。
+/// 两种运行时的 `rotate` 表示逆时针旋转，而 EzPlist 的旋转帧为顺时针（Cocos2d-x 约定），
+/// 翻转帧也无法表示，因此不声明这两种能力，遇到这两类帧时返回错误
+
+use super::{ExporterCapabilities, SheetData, SheetExporter};
+use crate::atlas_patch::trim_origin;
+use crate::types::PackedSprite;
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::path::Path;
+
+/// 文本图集的方言
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AtlasFlavor {
+    /// libGDX TextureAtlas
+    Libgdx,
+    /// Spine 运行时（4.x）
+    Spine,
+}
+
+/// libGDX / Spine `.atlas`
+#[derive(Debug, Clone, Copy)]
+pub struct AtlasExporter {
+    /// 方言
+    pub flavor: AtlasFlavor,
+}
+
+/// 一个区域（尺寸为未旋转时的帧尺寸）
+struct Region<'a> {
+    name: &'a str,
+    index: i32,
+    sprite: &'a PackedSprite,
+    /// 裁剪区域在原图中的左边距与下边距
+    offset: (u32, u32),
+}
+
+impl SheetExporter for AtlasExporter {
+    fn extension(&self) -> &'static str {
+        "atlas"
+    }
+
+    fn export(&self, sheet: &SheetData) -> Result<Vec<u8>, String> {
+        let mut seen = HashSet::new();
+        let mut regions = Vec::with_capacity(sheet.sprites.len());
+        for sprite in sheet.sprites {
+            if sprite.rotated {
+                return Err(format!("帧 {} 为顺时针旋转，{:?} 图集不支持，请关闭旋转后重新打包", sprite.name, self.flavor));
+            }
+            if sprite.is_flipped() {
+                return Err(format!("帧 {} 为翻转帧，{:?} 图集不支持，请关闭翻转帧合并后重新打包", sprite.name, self.flavor));
+            }
+            let (name, index) = region_name(&sprite.name, self.flavor == AtlasFlavor::Libgdx);
+            if !seen.insert((name, index)) {
+                return Err(format!("区域名称重复: {}", sprite.name));
+            }
+            let (left, top) = trim_origin(sprite).ok_or_else(|| format!("帧 {} 的裁剪偏移无效", sprite.name))?;
+            let bottom = sprite.original_height - top - sprite.height;
+            regions.push(Region { name, index, sprite, offset: (left, bottom) });
+        }
+
+        let text = match self.flavor {
+            AtlasFlavor::Libgdx => libgdx_atlas(sheet, &regions),
+            AtlasFlavor::Spine => spine_atlas(sheet, &regions),
+        };
+        Ok(text.into_bytes())
+    }
+
+    fn capabilities(&self) -> ExporterCapabilities {
+        ExporterCapabilities { rotation: false, flip: false }
+    }
+}
+
+/// libGDX 旧版格式（页头前有一个空行）
+fn libgdx_atlas(sheet: &SheetData, regions: &[Region]) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out);
+    let _ = writeln!(out, "{}", sheet.texture_name);
+    let _ = writeln!(out, "size: {}, {}", sheet.texture_width, sheet.texture_height);
+    let _ = writeln!(out, "format: {}", sheet.pixel_format.name());
+    let _ = writeln!(out, "filter: Linear, Linear");
+    let _ = writeln!(out, "repeat: none");
+    for region in regions {
+        let sprite = region.sprite;
+        let _ = writeln!(out, "{}", region.name);
+        let _ = writeln!(out, "  rotate: false");
+        let _ = writeln!(out, "  xy: {}, {}", sprite.x, sprite.y);
+        let _ = writeln!(out, "  size: {}, {}", sprite.width, sprite.height);
+        let _ = writeln!(out, "  orig: {}, {}", sprite.original_width, sprite.original_height);
+        let _ = writeln!(out, "  offset: {}, {}", region.offset.0, region.offset.1);
+        let _ = writeln!(out, "  index: {}", region.index);
+    }
+    out
+}
+
+/// Spine 4.x 格式（未裁剪的区域省略 `offsets`）
+fn spine_atlas(sheet: &SheetData, regions: &[Region]) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "{}", sheet.texture_name);
+    let _ = writeln!(out, "\tsize: {}, {}", sheet.texture_width, sheet.texture_height);
+    let _ = writeln!(out, "\tfilter: Linear, Linear");
+    for region in regions {
+        let sprite = region.sprite;
+        let _ = writeln!(out, "{}", region.name);
+        let _ = writeln!(out, "\tbounds: {}, {}, {}, {}", sprite.x, sprite.y, sprite.width, sprite.height);
+        if (sprite.width, sprite.height) != (sprite.original_width, sprite.original_height) {
+            let _ = writeln!(
+                out,
+                "\toffsets: {}, {}, {}, {}",
+                region.offset.0, region.offset.1, sprite.original_width, sprite.original_height
+            );
+        }
+    }
+    out
+}
+
+/// 去掉扩展名；`split_index` 时把末尾的 `_数字` 拆为序号（没有时为 -1）
+fn region_name(name: &str, split_index: bool) -> (&str, i32) {
+    let stem = match Path::new(name).extension() {
+        Some(ext) => &name[..name.len() - ext.len() - 1],
+        None => name,
+    };
+    if !split_index {
+        return (stem, -1);
+    }
+    match stem.rsplit_once('_') {
+        Some((base, digits)) if !base.is_empty() && !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) => {
+            match digits.parse() {
+                Ok(index) => (base, index),
+                Err(_) => (stem, -1),
+            }
+        }
+        _ => (stem, -1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::SmartUpdate;
+    use crate::texture_format::PixelFormat;
+
+    fn sprite(name: &str, x: u32, trimmed: bool) -> PackedSprite {
+        PackedSprite {
+            id: name.to_string(),
+            name: name.to_string(),
+            x,
+            y: 4,
+            width: 10,
+            height: 6,
+            rotated: false,
+            original_width: if trimmed { 16 } else { 10 },
+            original_height: if trimmed { 8 } else { 6 },
+            trimmed,
+            offset_x: if trimmed { 1 } else { 0 },
+            offset_y: if trimmed { -1 } else { 0 },
+            flip_x: false,
+            flip_y: false,
+            anchor: None,
+            nine_slice: None,
+        }
+    }
+
+    fn export(flavor: AtlasFlavor, sprites: &[PackedSprite]) -> Result<String, String> {
+        let sheet = SheetData { sprites, texture_width: 64, texture_height: 32, texture_name: "ui.png", pixel_format: PixelFormat::Rgba8888, smartupdate: SmartUpdate::default() };
+        AtlasExporter { flavor }.export(&sheet).map(|data| String::from_utf8(data).unwrap())
+    }
+
+    #[test]
+    fn test_libgdx_atlas() {
+        let sprites = vec![sprite("hero/run_01.png", 2, true), sprite("coin.png", 20, false)];
+        let text = export(AtlasFlavor::Libgdx, &sprites).unwrap();
+        // 原图 16x8 中 10x6 的裁剪区域，偏移 (1, -1) → 左上角 (4, 2)，下边距 0
+        assert!(text.starts_with("\nui.png\nsize: 64, 32\n"));
+        assert!(text.contains("hero/run\n  rotate: false\n  xy: 2, 4\n  size: 10, 6\n  orig: 16, 8\n  offset: 4, 0\n  index: 1\n"));
+        assert!(text.contains("coin\n  rotate: false\n  xy: 20, 4\n  size: 10, 6\n  orig: 10, 6\n  offset: 0, 0\n  index: -1\n"));
+    }
+
+    #[test]
+    fn test_spine_atlas() {
+        let sprites = vec![sprite("hero/run_01.png", 2, true), sprite("coin.png", 20, false)];
+        let text = export(AtlasFlavor::Spine, &sprites).unwrap();
+        assert_eq!(
+            text,
+            "ui.png\n\tsize: 64, 32\n\tfilter: Linear, Linear\n\
+             hero/run_01\n\tbounds: 2, 4, 10, 6\n\toffsets: 4, 0, 16, 8\n\
+             coin\n\tbounds: 20, 4, 10, 6\n"
+        );
+    }
+
+    #[test]
+    fn test_unsupported_frames() {
+        let rotated = PackedSprite { rotated: true, width: 6, height: 10, ..sprite("a.png", 0, false) };
+        assert!(export(AtlasFlavor::Spine, &[rotated]).unwrap_err().contains("旋转"));
+        let flipped = PackedSprite { flip_x: true, ..sprite("a.png", 0, false) };
+        assert!(export(AtlasFlavor::Libgdx, &[flipped]).unwrap_err().contains("翻转"));
+        // libGDX 中 run_1 与 run_01 都是 run 的第 1 帧
+        let duplicated = vec![sprite("run_1.png", 0, false), sprite("run_01.png", 12, false)];
+        assert!(export(AtlasFlavor::Libgdx, &duplicated).is_err());
+        assert!(export(AtlasFlavor::Spine, &duplicated).is_ok());
+        assert_eq!(region_name("fx.v2", false), ("fx", -1));
+        assert!(!crate::exporters::SheetFormat::SpineAtlas.capabilities().rotation);
+        assert!(crate::exporters::SheetFormat::JsonHash.capabilities().flip);
+        assert_eq!(region_name("walk_", true), ("walk_", -1));
+    }
+}
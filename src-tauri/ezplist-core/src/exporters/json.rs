@@ -0,0 +1,231 @@
+/// TexturePacker JSON 导出器（hash 与 array）
+///
+/// 与 TexturePacker 的 "JSON (Hash)" / "JSON (Array)" 输出一致：
+/// `frame` 为纹理中的位置与未旋转时的帧尺寸（旋转帧由引擎互换宽高），
+/// `spriteSourceSize` 为裁剪区域在原图中的位置，
+/// `scale9Borders` 为九宫格中间可拉伸区域（相对帧尺寸）
+
+use super::{SheetData, SheetExporter};
+use crate::atlas_patch::trim_origin;
+use crate::types::PackedSprite;
+use serde::ser::{SerializeMap, Serializer};
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// TexturePacker JSON
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonExporter {
+    /// 是否输出为数组（否则为以帧名为键的对象）
+    pub array: bool,
+}
+
+#[derive(Serialize)]
+struct Rect {
+    x: u32,
+    y: u32,
+    w: u32,
+    h: u32,
+}
+
+#[derive(Serialize)]
+struct Size {
+    w: u32,
+    h: u32,
+}
+
+#[derive(Serialize)]
+struct Pivot {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonFrame<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    filename: Option<&'a str>,
+    frame: Rect,
+    rotated: bool,
+    trimmed: bool,
+    sprite_source_size: Rect,
+    source_size: Size,
+    pivot: Pivot,
+    /// 翻转帧引用源帧的纹理区域，只在需要翻转时输出
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    flip_x: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    flip_y: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scale9_borders: Option<Rect>,
+}
+
+#[derive(Serialize)]
+struct Meta<'a> {
+    app: &'static str,
+    version: &'static str,
+    image: &'a str,
+    format: &'static str,
+    size: Size,
+    scale: &'static str,
+}
+
+/// 按传入顺序序列化为对象的帧
+struct FrameHash<'a>(Vec<(&'a str, JsonFrame<'a>)>);
+
+impl Serialize for FrameHash<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (name, frame) in &self.0 {
+            map.serialize_entry(name, frame)?;
+        }
+        map.end()
+    }
+}
+
+#[derive(Serialize)]
+struct JsonSheet<'a, F: Serialize> {
+    frames: F,
+    meta: Meta<'a>,
+}
+
+impl SheetExporter for JsonExporter {
+    fn extension(&self) -> &'static str {
+        "json"
+    }
+
+    fn export(&self, sheet: &SheetData) -> Result<Vec<u8>, String> {
+        let mut names = HashSet::new();
+        let mut frames = Vec::with_capacity(sheet.sprites.len());
+        for sprite in sheet.sprites {
+            if !names.insert(sprite.name.as_str()) {
+                return Err(format!("帧名称重复: {}", sprite.name));
+            }
+            frames.push((sprite.name.as_str(), json_frame(sprite, self.array)?));
+        }
+
+        let meta = Meta {
+            app: "EzPlist",
+            version: "1.0",
+            image: sheet.texture_name,
+            format: sheet.pixel_format.name(),
+            size: Size { w: sheet.texture_width, h: sheet.texture_height },
+            scale: "1",
+        };
+        let json = if self.array {
+            let frames: Vec<JsonFrame> = frames.into_iter().map(|(_, frame)| frame).collect();
+            serde_json::to_vec_pretty(&JsonSheet { frames, meta })
+        } else {
+            serde_json::to_vec_pretty(&JsonSheet { frames: FrameHash(frames), meta })
+        };
+        json.map_err(|e| format!("序列化 JSON 失败: {}", e))
+    }
+}
+
+/// 单帧的 JSON 描述
+fn json_frame(sprite: &PackedSprite, with_filename: bool) -> Result<JsonFrame<'_>, String> {
+    let (width, height) = sprite.frame_size();
+    let (left, top) = trim_origin(sprite).ok_or_else(|| format!("帧 {} 的裁剪偏移无效", sprite.name))?;
+    Ok(JsonFrame {
+        filename: with_filename.then_some(sprite.name.as_str()),
+        frame: Rect { x: sprite.x, y: sprite.y, w: width, h: height },
+        rotated: sprite.rotated,
+        trimmed: sprite.trimmed,
+        sprite_source_size: Rect { x: left, y: top, w: width, h: height },
+        source_size: Size { w: sprite.original_width, h: sprite.original_height },
+        pivot: pivot(sprite.anchor),
+        flip_x: sprite.flip_x,
+        flip_y: sprite.flip_y,
+        scale9_borders: scale9_borders(sprite, width, height)?,
+    })
+}
+
+/// 九宫格中间可拉伸区域；边距超出帧尺寸时返回错误
+fn scale9_borders(sprite: &PackedSprite, width: u32, height: u32) -> Result<Option<Rect>, String> {
+    let Some(nine_slice) = sprite.nine_slice else {
+        return Ok(None);
+    };
+    nine_slice.validate(width, height).map_err(|e| format!("帧 {}: {}", sprite.name, e))?;
+    let (x, y, w, h) = nine_slice.center(width, height);
+    Ok(Some(Rect { x, y, w, h }))
+}
+
+/// TexturePacker JSON 的轴心点（Y 轴向下；未设置锚点时为中心）
+fn pivot(anchor: Option<(f32, f32)>) -> Pivot {
+    let (x, y) = anchor.unwrap_or((0.5, 0.5));
+    Pivot { x, y: 1.0 - y }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::SmartUpdate;
+    use crate::texture_format::PixelFormat;
+    use crate::types::NineSlice;
+
+    fn sprite(name: &str, rotated: bool) -> PackedSprite {
+        PackedSprite {
+            id: name.to_string(),
+            name: name.to_string(),
+            x: 2,
+            y: 4,
+            width: if rotated { 6 } else { 10 },
+            height: if rotated { 10 } else { 6 },
+            rotated,
+            original_width: 16,
+            original_height: 8,
+            trimmed: true,
+            offset_x: 1,
+            offset_y: -1,
+            flip_x: false,
+            flip_y: false,
+            anchor: None,
+            nine_slice: None,
+        }
+    }
+
+    fn sheet(sprites: &[PackedSprite]) -> SheetData<'_> {
+        SheetData { sprites, texture_width: 32, texture_height: 16, texture_name: "ui.png", pixel_format: PixelFormat::Rgba8888, smartupdate: SmartUpdate::default() }
+    }
+
+    #[test]
+    fn test_json_hash_keeps_order_and_unrotated_size() {
+        let sprites = vec![sprite("z.png", true), sprite("a.png", false)];
+        let json = String::from_utf8(JsonExporter { array: false }.export(&sheet(&sprites)).unwrap()).unwrap();
+        assert!(json.find("z.png").unwrap() < json.find("a.png").unwrap());
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let z = &value["frames"]["z.png"];
+        assert_eq!((z["frame"]["w"].as_u64(), z["frame"]["h"].as_u64()), (Some(10), Some(6)));
+        // 原图 16x8 中 10x6 的裁剪区域，偏移 (1, -1) → 左上角 (4, 2)
+        assert_eq!((z["spriteSourceSize"]["x"].as_u64(), z["spriteSourceSize"]["y"].as_u64()), (Some(4), Some(2)));
+        assert_eq!(value["meta"]["image"], "ui.png");
+        assert_eq!((z["pivot"]["x"].as_f64(), z["pivot"]["y"].as_f64()), (Some(0.5), Some(0.5)));
+
+        // Cocos 锚点 Y 轴向上，JSON 的 pivot Y 轴向下
+        let feet = vec![PackedSprite { anchor: Some((0.25, 0.0)), ..sprite("feet.png", false) }];
+        let value: serde_json::Value = serde_json::from_slice(&JsonExporter { array: false }.export(&sheet(&feet)).unwrap()).unwrap();
+        let pivot = &value["frames"]["feet.png"]["pivot"];
+        assert_eq!((pivot["x"].as_f64(), pivot["y"].as_f64()), (Some(0.25), Some(1.0)));
+        assert!(value["frames"]["feet.png"].get("scale9Borders").is_none());
+
+        // 九宫格输出为帧内的中间区域
+        let panel = vec![PackedSprite { nine_slice: Some(NineSlice { left: 2, right: 3, top: 1, bottom: 1 }), ..sprite("panel.png", true) }];
+        let value: serde_json::Value = serde_json::from_slice(&JsonExporter { array: false }.export(&sheet(&panel)).unwrap()).unwrap();
+        let borders = &value["frames"]["panel.png"]["scale9Borders"];
+        assert_eq!((borders["x"].as_u64(), borders["y"].as_u64(), borders["w"].as_u64(), borders["h"].as_u64()), (Some(2), Some(1), Some(5), Some(4)));
+
+        let oversized = vec![PackedSprite { nine_slice: Some(NineSlice { left: 8, right: 8, top: 0, bottom: 0 }), ..sprite("panel.png", false) }];
+        assert!(JsonExporter { array: false }.export(&sheet(&oversized)).unwrap_err().contains("九宫格"));
+    }
+
+    #[test]
+    fn test_json_array_and_duplicates() {
+        let sprites = vec![sprite("a.png", false)];
+        let json = JsonExporter { array: true }.export(&sheet(&sprites)).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(value["frames"][0]["filename"], "a.png");
+
+        let duplicated = vec![sprite("a.png", false), sprite("a.png", true)];
+        assert!(JsonExporter { array: true }.export(&sheet(&duplicated)).is_err());
+    }
+}
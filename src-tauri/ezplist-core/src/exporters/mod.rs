@@ -0,0 +1,110 @@
+/// 图集数据导出格式 (Sheet Exporters)
+///
+/// 同一份打包结果可以写成不同引擎使用的数据文件：
+/// - plist: Cocos2d-x plist（Format 3 / Format 2 / 二进制，见 `PlistFormat`）
+/// - jsonHash / jsonArray: TexturePacker JSON（Phaser、PixiJS 等 Web 引擎）
+/// - libgdxAtlas / spineAtlas: libGDX 与 Spine 运行时的文本 `.atlas`
+///
+/// 新格式实现 `SheetExporter` 并在 `SheetFormat` 中登记即可被导出命令选用；
+/// 无法表示旋转或翻转帧的格式通过 `capabilities` 声明，打包时据此关闭对应选项
+
+pub mod atlas;
+pub mod json;
+pub mod plist;
+
+use crate::hash::SmartUpdate;
+use crate::plist_generator::PlistFormat;
+use crate::texture_format::PixelFormat;
+use crate::types::PackedSprite;
+use serde::{Deserialize, Serialize};
+
+/// 一张图集的导出数据
+#[derive(Debug, Clone, Copy)]
+pub struct SheetData<'a> {
+    /// 已打包的精灵（宽高为在纹理中占用的尺寸）
+    pub sprites: &'a [PackedSprite],
+    /// 纹理宽度
+    pub texture_width: u32,
+    /// 纹理高度
+    pub texture_height: u32,
+    /// 纹理文件名
+    pub texture_name: &'a str,
+    /// 纹理像素格式
+    pub pixel_format: PixelFormat,
+    /// 计算 SmartUpdate 哈希的纹理像素与模式（只有 plist 写入）
+    pub smartupdate: SmartUpdate<'a>,
+}
+
+/// 图集数据文件导出器
+pub trait SheetExporter {
+    /// 数据文件扩展名（不含点）
+    fn extension(&self) -> &'static str;
+
+    /// 生成数据文件内容
+    ///
+    /// # Arguments
+    /// * `sheet` - 图集数据
+    ///
+    /// # Returns
+    /// * `Result<Vec<u8>, String>` - 文件内容（二进制 plist 不是文本）；帧名称重复等无法表示的情况返回错误
+    fn export(&self, sheet: &SheetData) -> Result<Vec<u8>, String>;
+
+    /// 能表示的帧属性（默认支持旋转与翻转帧）
+    fn capabilities(&self) -> ExporterCapabilities {
+        ExporterCapabilities::default()
+    }
+}
+
+/// 导出器能表示的帧属性
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExporterCapabilities {
+    /// 能表示旋转的帧
+    pub rotation: bool,
+    /// 能表示翻转帧（`flip_x` / `flip_y`）
+    pub flip: bool,
+}
+
+impl Default for ExporterCapabilities {
+    fn default() -> Self {
+        Self { rotation: true, flip: true }
+    }
+}
+
+/// 数据文件格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SheetFormat {
+    /// Cocos2d-x plist
+    #[default]
+    Plist,
+    /// TexturePacker JSON（frames 为以帧名为键的对象）
+    JsonHash,
+    /// TexturePacker JSON（frames 为数组）
+    JsonArray,
+    /// libGDX TextureAtlas（`.atlas`）
+    LibgdxAtlas,
+    /// Spine 运行时图集（`.atlas`）
+    SpineAtlas,
+}
+
+impl SheetFormat {
+    /// 对应的导出器
+    ///
+    /// # Arguments
+    /// * `plist_format` - plist 的键名版本与编码（其他格式忽略）
+    pub fn exporter(self, plist_format: PlistFormat) -> Box<dyn SheetExporter> {
+        match self {
+            SheetFormat::Plist => Box::new(plist::PlistExporter { format: plist_format }),
+            SheetFormat::JsonHash => Box::new(json::JsonExporter { array: false }),
+            SheetFormat::JsonArray => Box::new(json::JsonExporter { array: true }),
+            SheetFormat::LibgdxAtlas => Box::new(atlas::AtlasExporter { flavor: atlas::AtlasFlavor::Libgdx }),
+            SheetFormat::SpineAtlas => Box::new(atlas::AtlasExporter { flavor: atlas::AtlasFlavor::Spine }),
+        }
+    }
+
+    /// 该格式能表示的帧属性
+    pub fn capabilities(self) -> ExporterCapabilities {
+        self.exporter(PlistFormat::default()).capabilities()
+    }
+}
@@ -0,0 +1,29 @@
+/// Cocos2d-x plist 导出器
+
+use super::{SheetData, SheetExporter};
+use crate::plist_generator::{generate_plist_data, PlistFormat};
+
+/// Cocos2d-x plist
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PlistExporter {
+    /// 键名版本与编码
+    pub format: PlistFormat,
+}
+
+impl SheetExporter for PlistExporter {
+    fn extension(&self) -> &'static str {
+        "plist"
+    }
+
+    fn export(&self, sheet: &SheetData) -> Result<Vec<u8>, String> {
+        generate_plist_data(
+            sheet.sprites,
+            sheet.texture_width,
+            sheet.texture_height,
+            sheet.texture_name,
+            self.format,
+            sheet.pixel_format,
+            sheet.smartupdate,
+        )
+    }
+}
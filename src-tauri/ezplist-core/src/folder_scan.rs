@@ -0,0 +1,172 @@
+/// 文件夹导入筛选 (Folder Import Filtering)
+///
+/// 按扩展名与通配符筛选文件夹中的图片，并决定帧名称：可以把相对子文件夹作为前缀
+/// （如 `enemies/slime_01.png`），大型素材目录打包后帧名仍然有意义且不会重名。
+/// 目录遍历由调用方完成，这里只处理相对路径
+
+use crate::sprite_filter::compile_glob;
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// 默认导入的图片扩展名
+pub const IMAGE_EXTENSIONS: [&str; 8] = ["png", "jpg", "jpeg", "gif", "webp", "bmp", "tga", "dds"];
+
+/// 单次文件夹导入的最大图片数
+pub const MAX_FOLDER_IMAGES: usize = 20_000;
+
+/// 文件夹导入选项
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FolderImportOptions {
+    /// 是否扫描子文件夹（默认是）
+    pub recursive: bool,
+    /// 导入的扩展名（不区分大小写，默认 `IMAGE_EXTENSIONS`）
+    pub extensions: Option<Vec<String>>,
+    /// 只导入匹配的文件（通配符；包含 `/` 时匹配相对路径，否则匹配文件名）
+    pub include: Option<String>,
+    /// 排除匹配的文件（规则同 `include`）
+    pub exclude: Option<String>,
+    /// 帧名称带相对子文件夹前缀（如 `enemies/slime_01.png`）
+    pub subfolder_names: bool,
+}
+
+impl Default for FolderImportOptions {
+    fn default() -> Self {
+        Self {
+            recursive: true,
+            extensions: None,
+            include: None,
+            exclude: None,
+            subfolder_names: false,
+        }
+    }
+}
+
+/// 选中的文件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FolderEntry {
+    /// 相对路径（以 `/` 分隔）
+    pub relative_path: String,
+    /// 帧名称
+    pub frame_name: String,
+}
+
+/// 筛选文件并生成帧名称
+///
+/// # Arguments
+/// * `relative_paths` - 文件夹中所有文件的相对路径（`/` 或 `\` 分隔）
+/// * `options` - 导入选项
+///
+/// # Returns
+/// * `Result<(Vec<FolderEntry>, Vec<String>), String>` - 按相对路径排序的文件与重名警告；
+///   通配符无效或图片数量超过 `MAX_FOLDER_IMAGES` 时返回错误
+pub fn select_folder_files(
+    relative_paths: &[String],
+    options: &FolderImportOptions,
+) -> Result<(Vec<FolderEntry>, Vec<String>), String> {
+    let extensions: Vec<String> = match &options.extensions {
+        Some(list) => list.iter().map(|ext| ext.trim_start_matches('.').to_ascii_lowercase()).collect(),
+        None => IMAGE_EXTENSIONS.iter().map(|ext| ext.to_string()).collect(),
+    };
+    if extensions.is_empty() {
+        return Err("导入的扩展名列表为空".to_string());
+    }
+    let include = compile_pattern(&options.include)?;
+    let exclude = compile_pattern(&options.exclude)?;
+
+    let mut entries: Vec<FolderEntry> = Vec::new();
+    for path in relative_paths {
+        let relative_path = path.replace('\\', "/");
+        let file_name = relative_path.rsplit('/').next().unwrap_or(&relative_path).to_string();
+        if !options.recursive && relative_path.contains('/') {
+            continue;
+        }
+        let extension = file_name.rsplit_once('.').map(|(_, ext)| ext.to_ascii_lowercase());
+        if !extension.is_some_and(|ext| extensions.contains(&ext)) {
+            continue;
+        }
+        let target = |by_path: bool| if by_path { relative_path.as_str() } else { file_name.as_str() };
+        if include.as_ref().is_some_and(|(by_path, re)| !re.is_match(target(*by_path))) {
+            continue;
+        }
+        if exclude.as_ref().is_some_and(|(by_path, re)| re.is_match(target(*by_path))) {
+            continue;
+        }
+        let frame_name = if options.subfolder_names { relative_path.clone() } else { file_name };
+        entries.push(FolderEntry { relative_path, frame_name });
+    }
+    if entries.len() > MAX_FOLDER_IMAGES {
+        return Err(format!("文件夹中的图片超过 {} 张: {}", MAX_FOLDER_IMAGES, entries.len()));
+    }
+    entries.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for entry in &entries {
+        *counts.entry(&entry.frame_name).or_default() += 1;
+    }
+    let mut warnings: Vec<String> = counts
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .map(|(name, count)| format!("{} 个文件的帧名称都是 {}，可开启子文件夹前缀避免重名", count, name))
+        .collect();
+    warnings.sort();
+    Ok((entries, warnings))
+}
+
+/// 编译筛选通配符
+///
+/// # Returns
+/// * `Result<Option<(bool, Regex)>, String>` - (是否按相对路径匹配, 正则)
+fn compile_pattern(glob: &Option<String>) -> Result<Option<(bool, Regex)>, String> {
+    glob.as_deref()
+        .map(|glob| Ok((glob.contains('/'), compile_glob(glob)?)))
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(list: &[&str]) -> Vec<String> {
+        list.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn test_select_folder_files() {
+        let files = paths(&[
+            "hero.png",
+            "enemies\\slime_01.png",
+            "enemies/slime_02.PNG",
+            "enemies/boss/hero.png",
+            "notes.txt",
+            "ui/button.psd",
+        ]);
+        let (entries, warnings) = select_folder_files(&files, &FolderImportOptions::default()).unwrap();
+        let names: Vec<&str> = entries.iter().map(|e| e.frame_name.as_str()).collect();
+        assert_eq!(names, vec!["hero.png", "slime_01.png", "slime_02.PNG", "hero.png"]);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("hero.png"));
+
+        let prefixed = FolderImportOptions { subfolder_names: true, ..Default::default() };
+        let (entries, warnings) = select_folder_files(&files, &prefixed).unwrap();
+        assert_eq!(entries[0].frame_name, "enemies/boss/hero.png");
+        assert_eq!(entries[1].frame_name, "enemies/slime_01.png");
+        assert!(warnings.is_empty());
+
+        let filtered = FolderImportOptions {
+            include: Some("enemies/*".to_string()),
+            exclude: Some("*_02.png".to_string()),
+            ..Default::default()
+        };
+        let (entries, _) = select_folder_files(&files, &filtered).unwrap();
+        let selected: Vec<&str> = entries.iter().map(|e| e.relative_path.as_str()).collect();
+        assert_eq!(selected, vec!["enemies/boss/hero.png", "enemies/slime_01.png"]);
+
+        let flat = FolderImportOptions { recursive: false, extensions: Some(paths(&[".PNG"])), ..Default::default() };
+        let (entries, _) = select_folder_files(&files, &flat).unwrap();
+        assert_eq!(entries.len(), 1);
+        let none = FolderImportOptions { extensions: Some(Vec::new()), ..Default::default() };
+        assert!(select_folder_files(&files, &none).is_err());
+    }
+}
@@ -0,0 +1,202 @@
+/// 动画帧对齐 (Center-on-Grid Normalization)
+///
+/// 把一组动画帧放到统一尺寸的画布上，并按不透明像素的质心或底部基线对齐内容，
+/// 使不同姿势、裁剪边界不同的帧播放时不会抖动。
+/// 对齐后再裁剪时，偏移量会保留帧在统一画布中的位置
+
+use crate::anchor::{content_metrics, ContentMetrics};
+use crate::limits::check_texture_size;
+use image::{imageops, RgbaImage};
+use serde::{Deserialize, Serialize};
+
+/// 对齐基准
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlignAnchor {
+    /// 质心对齐到画布中心
+    #[default]
+    Centroid,
+    /// 内容底边对齐到画布底边，质心水平居中（适合站在地面上的角色）
+    Baseline,
+}
+
+/// 帧对齐选项
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NormalizeOptions {
+    /// 对齐基准
+    pub anchor: AlignAnchor,
+    /// 画布宽度（可选，不设置时取对齐后能容纳所有帧的最小宽度）
+    pub canvas_width: Option<u32>,
+    /// 画布高度（可选，不设置时取对齐后能容纳所有帧的最小高度）
+    pub canvas_height: Option<u32>,
+}
+
+impl NormalizeOptions {
+    /// 检查画布尺寸
+    pub fn validate(&self) -> Result<(), String> {
+        if self.canvas_width == Some(0) || self.canvas_height == Some(0) {
+            return Err("对齐画布尺寸不能为 0".to_string());
+        }
+        check_texture_size(self.canvas_width.unwrap_or(1), self.canvas_height.unwrap_or(1))
+    }
+}
+
+/// 单帧内容相对于对齐点的范围
+struct FrameExtent {
+    /// 内容区域 (left, top, right, bottom)
+    bounds: (u32, u32, u32, u32),
+    /// 对齐点（像素边界坐标）
+    anchor: (u32, u32),
+}
+
+impl FrameExtent {
+    fn left(&self) -> u32 {
+        self.anchor.0 - self.bounds.0
+    }
+
+    fn right(&self) -> u32 {
+        self.bounds.2 - self.anchor.0
+    }
+
+    fn top(&self) -> u32 {
+        self.anchor.1 - self.bounds.1
+    }
+
+    fn bottom(&self) -> u32 {
+        self.bounds.3 - self.anchor.1
+    }
+}
+
+/// 计算单帧的内容范围与对齐点，完全透明时返回 None
+fn frame_extent(img: &RgbaImage, anchor: AlignAnchor) -> Option<FrameExtent> {
+    let ContentMetrics { bounds, centroid } = content_metrics(img)?;
+    let (left, top, right, bottom) = bounds;
+    let centroid_x = (centroid.0.round() as u32).clamp(left, right);
+    let anchor_y = match anchor {
+        AlignAnchor::Centroid => (centroid.1.round() as u32).clamp(top, bottom),
+        AlignAnchor::Baseline => bottom,
+    };
+    Some(FrameExtent {
+        bounds,
+        anchor: (centroid_x, anchor_y),
+    })
+}
+
+/// 把帧放到统一画布上并对齐内容
+///
+/// 对齐点在画布中水平居中；质心对齐时垂直居中，基线对齐时位于画布底边。
+/// 完全透明的帧输出为空白画布
+///
+/// # Arguments
+/// * `frames` - 动画帧（名称与图像）
+/// * `options` - 对齐选项（调用前应通过 `validate` 检查）
+///
+/// # Returns
+/// * `Result<Vec<RgbaImage>, String>` - 对齐后的帧（顺序不变）；指定的画布放不下某一帧时返回错误
+pub fn normalize_frames(frames: &[(&str, &RgbaImage)], options: &NormalizeOptions) -> Result<Vec<RgbaImage>, String> {
+    let extents: Vec<Option<FrameExtent>> =
+        frames.iter().map(|(_, f)| frame_extent(f, options.anchor)).collect();
+    let max = |side: fn(&FrameExtent) -> u32| extents.iter().flatten().map(side).max().unwrap_or(0);
+
+    let width = options
+        .canvas_width
+        .unwrap_or_else(|| (2 * max(FrameExtent::left).max(max(FrameExtent::right))).max(1));
+    let height = options.canvas_height.unwrap_or_else(|| match options.anchor {
+        AlignAnchor::Centroid => (2 * max(FrameExtent::top).max(max(FrameExtent::bottom))).max(1),
+        AlignAnchor::Baseline => max(FrameExtent::top).max(1),
+    });
+    check_texture_size(width, height)?;
+    let anchor_x = width / 2;
+    let anchor_y = match options.anchor {
+        AlignAnchor::Centroid => height / 2,
+        AlignAnchor::Baseline => height,
+    };
+
+    frames
+        .iter()
+        .zip(&extents)
+        .map(|(&(name, frame), extent)| {
+            let mut canvas = RgbaImage::new(width, height);
+            let Some(extent) = extent else {
+                return Ok(canvas);
+            };
+            let fits = extent.left() <= anchor_x
+                && extent.right() <= width - anchor_x
+                && extent.top() <= anchor_y
+                && extent.bottom() <= height - anchor_y;
+            if !fits {
+                return Err(format!(
+                    "帧 {} 对齐后超出 {}x{} 的画布，请增大画布尺寸",
+                    name,
+                    width,
+                    height
+                ));
+            }
+            let (left, top, right, bottom) = extent.bounds;
+            let content = imageops::crop_imm(frame, left, top, right - left, bottom - top).to_image();
+            let x = anchor_x - extent.left();
+            let y = anchor_y - extent.top();
+            imageops::replace(&mut canvas, &content, x as i64, y as i64);
+            Ok(canvas)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    /// 在 width x height 的透明画布上 (x, y) 处画一个 w x h 的色块
+    fn frame(width: u32, height: u32, x: u32, y: u32, w: u32, h: u32) -> RgbaImage {
+        let mut img = RgbaImage::new(width, height);
+        for py in y..y + h {
+            for px in x..x + w {
+                img.put_pixel(px, py, Rgba([255, 0, 0, 255]));
+            }
+        }
+        img
+    }
+
+    fn named(frames: &[RgbaImage]) -> Vec<(&str, &RgbaImage)> {
+        frames.iter().map(|f| ("frame.png", f)).collect()
+    }
+
+    #[test]
+    fn test_centroid_alignment_removes_jitter() {
+        // 同样的色块在不同尺寸的帧中位置不同
+        let frames = vec![frame(10, 10, 1, 2, 4, 4), frame(16, 8, 9, 3, 4, 4)];
+        let aligned = normalize_frames(&named(&frames), &NormalizeOptions::default()).unwrap();
+        assert_eq!(aligned[0].dimensions(), (4, 4));
+        assert_eq!(aligned[0], aligned[1]);
+
+        let options = NormalizeOptions { canvas_width: Some(12), canvas_height: Some(8), ..Default::default() };
+        let aligned = normalize_frames(&named(&frames), &options).unwrap();
+        assert_eq!(aligned[0].dimensions(), (12, 8));
+        assert_eq!(aligned[0], aligned[1]);
+        // 色块的质心对齐到画布中心 (6, 4)，色块从 (4, 2) 开始
+        assert_eq!(aligned[0].get_pixel(4, 2)[3], 255);
+        assert_eq!(aligned[0].get_pixel(3, 2)[3], 0);
+    }
+
+    #[test]
+    fn test_baseline_alignment() {
+        // 高度不同的两帧，底边对齐
+        let frames = vec![frame(8, 8, 0, 0, 2, 6), frame(8, 8, 4, 5, 2, 2), RgbaImage::new(3, 3)];
+        let options = NormalizeOptions { anchor: AlignAnchor::Baseline, ..Default::default() };
+        let aligned = normalize_frames(&named(&frames), &options).unwrap();
+        assert_eq!(aligned[0].dimensions(), (2, 6));
+        assert_eq!(aligned[1].get_pixel(0, 5)[3], 255);
+        assert_eq!(aligned[1].get_pixel(0, 3)[3], 0);
+        assert!(aligned[2].pixels().all(|p| p[3] == 0));
+    }
+
+    #[test]
+    fn test_canvas_too_small() {
+        let frames = vec![frame(10, 10, 0, 0, 6, 6)];
+        let options = NormalizeOptions { canvas_width: Some(4), canvas_height: Some(8), ..Default::default() };
+        assert!(normalize_frames(&named(&frames), &options).unwrap_err().contains("frame.png"));
+        assert!(NormalizeOptions { canvas_width: Some(0), ..Default::default() }.validate().is_err());
+    }
+}
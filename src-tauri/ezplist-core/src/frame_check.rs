@@ -0,0 +1,148 @@
+/// 帧尺寸约束检查 (Frame Size Constraints)
+///
+/// 切分结果的可选检查：最小帧尺寸、2 的幂、偶数尺寸。
+/// 不满足时返回结构化警告而不是错误，便于在导出前发现网格配置问题
+
+use crate::types::FrameInfo;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 帧尺寸约束
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FrameConstraints {
+    /// 最小帧宽（0 表示不限制）
+    pub min_width: u32,
+    /// 最小帧高（0 表示不限制）
+    pub min_height: u32,
+    /// 帧宽高是否应为 2 的幂
+    pub power_of_two: bool,
+    /// 帧宽高是否应为偶数
+    pub even: bool,
+}
+
+/// 警告类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FrameWarningKind {
+    /// 小于最小帧尺寸
+    BelowMinimum,
+    /// 不是 2 的幂
+    NotPowerOfTwo,
+    /// 不是偶数
+    OddSize,
+}
+
+/// 帧尺寸警告（相同尺寸的帧合并为一条）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameWarning {
+    /// 警告类型
+    pub kind: FrameWarningKind,
+    /// 帧宽度
+    pub width: u32,
+    /// 帧高度
+    pub height: u32,
+    /// 该尺寸的帧数
+    pub frame_count: u32,
+    /// 说明
+    pub message: String,
+}
+
+/// 按约束检查帧尺寸
+///
+/// # Arguments
+/// * `frames` - 切分得到的帧
+/// * `constraints` - 尺寸约束
+///
+/// # Returns
+/// * `Vec<FrameWarning>` - 按尺寸排序的警告，全部满足时为空
+pub fn check_frame_sizes(frames: &[FrameInfo], constraints: &FrameConstraints) -> Vec<FrameWarning> {
+    let mut sizes: BTreeMap<(u32, u32), u32> = BTreeMap::new();
+    for frame in frames {
+        *sizes.entry((frame.width, frame.height)).or_default() += 1;
+    }
+
+    let mut warnings = Vec::new();
+    for ((width, height), frame_count) in sizes {
+        let mut warn = |kind, message: String| {
+            warnings.push(FrameWarning {
+                kind,
+                width,
+                height,
+                frame_count,
+                message,
+            });
+        };
+
+        if width < constraints.min_width || height < constraints.min_height {
+            warn(
+                FrameWarningKind::BelowMinimum,
+                format!(
+                    "{} 个帧尺寸 {}x{} 小于最小尺寸 {}x{}",
+                    frame_count, width, height, constraints.min_width, constraints.min_height
+                ),
+            );
+        }
+        if constraints.power_of_two && !(width.is_power_of_two() && height.is_power_of_two()) {
+            warn(
+                FrameWarningKind::NotPowerOfTwo,
+                format!("{} 个帧尺寸 {}x{} 不是 2 的幂", frame_count, width, height),
+            );
+        }
+        if constraints.even && (!width.is_multiple_of(2) || !height.is_multiple_of(2)) {
+            warn(
+                FrameWarningKind::OddSize,
+                format!("{} 个帧尺寸 {}x{} 不是偶数", frame_count, width, height),
+            );
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(width: u32, height: u32) -> FrameInfo {
+        FrameInfo {
+            name: "frame_01.png".to_string(),
+            x: 0,
+            y: 0,
+            width,
+            height,
+            row: 0,
+            col: 0,
+            anchor: None,
+        }
+    }
+
+    #[test]
+    fn test_no_constraints() {
+        let frames = [frame(33, 7)];
+        assert!(check_frame_sizes(&frames, &FrameConstraints::default()).is_empty());
+    }
+
+    #[test]
+    fn test_warnings_grouped_by_size() {
+        let constraints = FrameConstraints {
+            min_width: 16,
+            min_height: 16,
+            power_of_two: true,
+            even: true,
+        };
+        let frames = [frame(64, 64), frame(48, 48), frame(48, 48), frame(15, 16)];
+        let warnings = check_frame_sizes(&frames, &constraints);
+
+        let kinds: Vec<_> = warnings.iter().map(|w| (w.kind, w.width, w.frame_count)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (FrameWarningKind::BelowMinimum, 15, 1),
+                (FrameWarningKind::NotPowerOfTwo, 15, 1),
+                (FrameWarningKind::OddSize, 15, 1),
+                (FrameWarningKind::NotPowerOfTwo, 48, 2),
+            ]
+        );
+    }
+}
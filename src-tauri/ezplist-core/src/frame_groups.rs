@@ -0,0 +1,236 @@
+/// 帧分组导出 (Frame Groups)
+///
+/// 把同一张纹理中的帧按文件夹或标签分成几组，每组写一个单独的数据文件，
+/// 所有数据文件引用同一张纹理。引擎可以按需加载帧的子集，而纹理在内存中只有一份
+///
+/// 同样的分组方式也用于多页打包的页面亲和：同一分组的帧（如一段动画）不会被拆到两张纹理
+
+use crate::types::PackedSprite;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 一个自定义分组
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameGroup {
+    /// 分组名称（用于文件名）
+    pub name: String,
+    /// 分组中的精灵 ID（一个精灵可以属于多个分组）
+    pub sprite_ids: Vec<String>,
+}
+
+/// 分组方式
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "by", rename_all = "camelCase")]
+pub enum FrameGrouping {
+    /// 按帧名称中的文件夹分组（`hero/run_01.png` 属于 `hero`，多级文件夹用 `_` 连接）
+    Folder,
+    /// 自定义分组（如前端按标签生成）
+    Groups {
+        /// 各分组
+        groups: Vec<FrameGroup>,
+    },
+}
+
+/// 分组结果
+#[derive(Debug, Clone)]
+pub struct GroupedFrames {
+    /// 分组名称
+    pub name: String,
+    /// 分组中的帧（保持图集中的顺序）
+    pub sprites: Vec<PackedSprite>,
+}
+
+/// 把帧分组
+///
+/// # Arguments
+/// * `sprites` - 同一张纹理中的帧
+/// * `grouping` - 分组方式
+///
+/// # Returns
+/// * `Result<(Vec<GroupedFrames>, usize), String>` - 非空的分组与未分组的帧数；
+///   自定义分组名称无效、重复或引用了不在纹理中的精灵时返回错误
+pub fn group_frames(sprites: &[PackedSprite], grouping: &FrameGrouping) -> Result<(Vec<GroupedFrames>, usize), String> {
+    let mut groups: Vec<GroupedFrames> = Vec::new();
+    let mut ungrouped = 0;
+    match grouping {
+        FrameGrouping::Folder => {
+            for sprite in sprites {
+                let Some(folder) = folder_group(&sprite.name) else {
+                    ungrouped += 1;
+                    continue;
+                };
+                match groups.iter_mut().find(|group| group.name == folder) {
+                    Some(group) => group.sprites.push(sprite.clone()),
+                    None => groups.push(GroupedFrames {
+                        name: folder,
+                        sprites: vec![sprite.clone()],
+                    }),
+                }
+            }
+        }
+        FrameGrouping::Groups { groups: custom } => {
+            for (index, group) in custom.iter().enumerate() {
+                validate_group_name(&group.name)?;
+                if custom[..index].iter().any(|other| other.name == group.name) {
+                    return Err(format!("分组名称重复: {}", group.name));
+                }
+                if let Some(id) = group.sprite_ids.iter().find(|id| !sprites.iter().any(|s| &s.id == *id)) {
+                    return Err(format!("分组 {} 中的精灵 {} 不在图集中", group.name, id));
+                }
+            }
+            ungrouped = sprites
+                .iter()
+                .filter(|s| !custom.iter().any(|group| group.sprite_ids.contains(&s.id)))
+                .count();
+            groups = custom
+                .iter()
+                .map(|group| GroupedFrames {
+                    name: group.name.clone(),
+                    sprites: sprites.iter().filter(|s| group.sprite_ids.contains(&s.id)).cloned().collect(),
+                })
+                .filter(|group| !group.sprites.is_empty())
+                .collect();
+        }
+    }
+    Ok((groups, ungrouped))
+}
+
+/// 每个精灵所属的分组（用于多页打包时让同一分组的帧留在同一页）
+///
+/// # Arguments
+/// * `sprites` - 精灵 ID 与帧名称
+/// * `grouping` - 分组方式
+///
+/// # Returns
+/// * `Result<HashMap<String, String>, String>` - 精灵 ID 到分组名称（未分组的精灵不在其中）；
+///   自定义分组无效、引用了未知精灵，或一个精灵属于多个分组时返回错误
+pub fn sprite_groups<'a>(
+    sprites: impl IntoIterator<Item = (&'a str, &'a str)>,
+    grouping: &FrameGrouping,
+) -> Result<HashMap<String, String>, String> {
+    let sprites: Vec<(&str, &str)> = sprites.into_iter().collect();
+    let mut result = HashMap::new();
+    match grouping {
+        FrameGrouping::Folder => {
+            for (id, name) in sprites {
+                if let Some(folder) = folder_group(name) {
+                    result.insert(id.to_string(), folder);
+                }
+            }
+        }
+        FrameGrouping::Groups { groups } => {
+            for group in groups {
+                validate_group_name(&group.name)?;
+                for id in &group.sprite_ids {
+                    if !sprites.iter().any(|(sprite_id, _)| sprite_id == id) {
+                        return Err(format!("分组 {} 中的精灵 {} 不在打包列表中", group.name, id));
+                    }
+                    if let Some(other) = result.insert(id.clone(), group.name.clone()) {
+                        if other != group.name {
+                            return Err(format!("精灵 {} 同时属于分组 {} 和 {}，无法保证在同一页", id, other, group.name));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// 帧名称所在的文件夹（没有文件夹时返回 None）
+fn folder_group(name: &str) -> Option<String> {
+    let (folder, _) = name.rsplit_once('/')?;
+    let parts: Vec<&str> = folder.split('/').filter(|part| !part.is_empty() && *part != ".").collect();
+    (!parts.is_empty()).then(|| parts.join("_"))
+}
+
+/// 分组名称会成为文件名的一部分
+fn validate_group_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("分组名称不能为空".to_string());
+    }
+    if name.contains(['/', '\\']) || name.contains("..") {
+        return Err(format!("分组名称 {} 不能包含路径", name));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sprite(id: &str, name: &str) -> PackedSprite {
+        PackedSprite {
+            id: id.to_string(),
+            name: name.to_string(),
+            x: 0,
+            y: 0,
+            width: 4,
+            height: 4,
+            rotated: false,
+            original_width: 4,
+            original_height: 4,
+            trimmed: false,
+            offset_x: 0,
+            offset_y: 0,
+            flip_x: false,
+            flip_y: false,
+            anchor: None,
+            nine_slice: None,
+        }
+    }
+
+    #[test]
+    fn test_group_by_folder() {
+        let sprites = vec![
+            sprite("1", "hero/run_01.png"),
+            sprite("2", "coin.png"),
+            sprite("3", "fx/hit/spark.png"),
+            sprite("4", "hero/run_02.png"),
+        ];
+        let (groups, ungrouped) = group_frames(&sprites, &FrameGrouping::Folder).unwrap();
+        let summary: Vec<(&str, Vec<&str>)> = groups
+            .iter()
+            .map(|g| (g.name.as_str(), g.sprites.iter().map(|s| s.id.as_str()).collect()))
+            .collect();
+        assert_eq!(summary, vec![("hero", vec!["1", "4"]), ("fx_hit", vec!["3"])]);
+        assert_eq!(ungrouped, 1);
+
+        let pages = sprite_groups(sprites.iter().map(|s| (s.id.as_str(), s.name.as_str())), &FrameGrouping::Folder).unwrap();
+        assert_eq!(pages.len(), 3);
+        assert_eq!(pages["3"], "fx_hit");
+    }
+
+    #[test]
+    fn test_custom_groups() {
+        let sprites = vec![sprite("1", "a.png"), sprite("2", "b.png"), sprite("3", "c.png")];
+        let group = |name: &str, ids: &[&str]| FrameGroup {
+            name: name.to_string(),
+            sprite_ids: ids.iter().map(|id| id.to_string()).collect(),
+        };
+        let grouping = FrameGrouping::Groups {
+            groups: vec![group("ui", &["2", "1"]), group("shared", &["1"]), group("empty", &[])],
+        };
+        let (groups, ungrouped) = group_frames(&sprites, &grouping).unwrap();
+        assert_eq!(groups.len(), 2);
+        // 保持图集中的顺序；一个精灵可以属于多个分组
+        assert_eq!(groups[0].sprites.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(), vec!["1", "2"]);
+        assert_eq!(groups[1].sprites[0].id, "1");
+        assert_eq!(ungrouped, 1);
+
+        let invalid = |groups| group_frames(&sprites, &FrameGrouping::Groups { groups }).unwrap_err();
+        assert!(invalid(vec![group("ui", &["9"])]).contains("9"));
+        assert!(invalid(vec![group("ui", &[]), group("ui", &[])]).contains("重复"));
+        assert!(invalid(vec![group("../ui", &[])]).contains("路径"));
+
+        let ids = ["1", "2", "3"].map(|id| (id, "x.png"));
+        let pages = sprite_groups(ids, &FrameGrouping::Groups { groups: vec![group("ui", &["1", "2"])] }).unwrap();
+        assert_eq!((pages.get("1").map(String::as_str), pages.get("3")), (Some("ui"), None));
+        let overlapping = FrameGrouping::Groups { groups: vec![group("ui", &["1"]), group("hud", &["1"])] };
+        assert!(sprite_groups(ids, &overlapping).unwrap_err().contains("同时属于"));
+
+        let json: FrameGrouping = serde_json::from_str(r#"{"by":"groups","groups":[{"name":"ui","spriteIds":["1"]}]}"#).unwrap();
+        assert!(matches!(json, FrameGrouping::Groups { ref groups } if groups[0].sprite_ids == ["1"]));
+    }
+}
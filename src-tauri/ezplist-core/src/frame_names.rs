@@ -0,0 +1,232 @@
+/// 帧名称重名检测 (Frame Name Collisions)
+///
+/// 帧以名称为键写入数据文件，`run/01.png` 与 `jump/01.png` 导入后都叫 `01.png`，
+/// 后写入的帧会覆盖前一个。打包前按策略检测重名：报错并列出所有重名的帧，
+/// 或自动改名（加文件夹前缀或数字后缀），改名结果返回给前端
+
+use crate::types::SpriteData;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// 单次报错最多列出的重名数
+const MAX_REPORTED_COLLISIONS: usize = 10;
+
+/// 重名处理策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NameCollisionPolicy {
+    /// 报错并列出重名的帧
+    #[default]
+    Error,
+    /// 重名的帧都加上所在文件夹名作为前缀（如 `run/01.png`），仍重名时再加数字后缀
+    FolderPrefix,
+    /// 第一个帧保留原名，其余加数字后缀（如 `01_2.png`）
+    NumericSuffix,
+}
+
+/// 一组重名的帧
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NameCollision {
+    /// 重复的名称
+    pub name: String,
+    /// 使用该名称的帧来源（文件路径，没有路径时为 ID），按出现顺序
+    pub sources: Vec<String>,
+}
+
+/// 自动改名的帧
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameRename {
+    /// 精灵 ID
+    pub id: String,
+    /// 原名称
+    pub from: String,
+    /// 新名称
+    pub to: String,
+}
+
+/// 查找重名的帧
+///
+/// # Arguments
+/// * `frames` - 帧名称与来源
+///
+/// # Returns
+/// * `Vec<NameCollision>` - 重名的分组，按名称首次出现的顺序
+pub fn find_collisions<'a>(frames: impl IntoIterator<Item = (&'a str, &'a str)>) -> Vec<NameCollision> {
+    let mut groups: Vec<NameCollision> = Vec::new();
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    for (name, source) in frames {
+        let position = *index.entry(name).or_insert_with(|| {
+            groups.push(NameCollision { name: name.to_string(), sources: Vec::new() });
+            groups.len() - 1
+        });
+        groups[position].sources.push(source.to_string());
+    }
+    groups.retain(|group| group.sources.len() > 1);
+    groups
+}
+
+/// 确认帧名称不重复
+///
+/// # Arguments
+/// * `names` - 帧名称
+///
+/// # Returns
+/// * `Result<(), String>` - 有重名时返回列出重复名称的错误
+pub fn check_unique_names<'a>(names: impl IntoIterator<Item = &'a str>) -> Result<(), String> {
+    let collisions = find_collisions(names.into_iter().map(|name| (name, name)));
+    if collisions.is_empty() {
+        return Ok(());
+    }
+    let listed: Vec<String> = collisions
+        .iter()
+        .take(MAX_REPORTED_COLLISIONS)
+        .map(|c| format!("{}（{} 个）", c.name, c.sources.len()))
+        .collect();
+    Err(format!("帧名称重复: {}{}", listed.join("、"), more_suffix(collisions.len())))
+}
+
+/// 按策略处理精灵的重名
+///
+/// # Arguments
+/// * `sprites` - 精灵列表
+/// * `policy` - 重名处理策略
+///
+/// # Returns
+/// * `Result<Vec<FrameRename>, String>` - 需要改名的帧（没有重名时为空）；
+///   策略为报错且存在重名时返回列出重名帧及其来源的错误
+pub fn resolve_collisions(sprites: &[SpriteData], policy: NameCollisionPolicy) -> Result<Vec<FrameRename>, String> {
+    let collisions = find_collisions(sprites.iter().map(|s| (s.name.as_str(), source_of(s))));
+    if collisions.is_empty() {
+        return Ok(Vec::new());
+    }
+    if policy == NameCollisionPolicy::Error {
+        let listed: Vec<String> = collisions
+            .iter()
+            .take(MAX_REPORTED_COLLISIONS)
+            .map(|c| format!("{}（{}）", c.name, c.sources.join("、")))
+            .collect();
+        return Err(format!(
+            "{} 个帧名称重复，可以选择自动改名: {}{}",
+            collisions.len(),
+            listed.join("；"),
+            more_suffix(collisions.len())
+        ));
+    }
+
+    let colliding: HashSet<&str> = collisions.iter().map(|c| c.name.as_str()).collect();
+    // 新名称不能与任何已有名称或已分配的新名称重复
+    let mut taken: HashSet<String> = sprites.iter().map(|s| s.name.clone()).collect();
+    let mut kept: HashSet<&str> = HashSet::new();
+    let mut renames = Vec::new();
+    for sprite in sprites.iter().filter(|s| colliding.contains(s.name.as_str())) {
+        let prefixed = match policy {
+            NameCollisionPolicy::FolderPrefix => folder_of(&sprite.path).map(|folder| format!("{}/{}", folder, sprite.name)),
+            _ => None,
+        };
+        let to = match prefixed {
+            Some(name) if !taken.contains(&name) => name,
+            Some(name) => suffixed(&name, &taken),
+            // 数字后缀策略下第一个帧保留原名
+            None if policy == NameCollisionPolicy::NumericSuffix && kept.insert(sprite.name.as_str()) => continue,
+            None => suffixed(&sprite.name, &taken),
+        };
+        taken.insert(to.clone());
+        renames.push(FrameRename { id: sprite.id.clone(), from: sprite.name.clone(), to });
+    }
+    Ok(renames)
+}
+
+/// 报错信息中的来源：文件路径，没有路径时为 ID
+fn source_of(sprite: &SpriteData) -> &str {
+    if sprite.path.is_empty() {
+        &sprite.id
+    } else {
+        &sprite.path
+    }
+}
+
+/// 文件所在文件夹的名称（兼容 `/` 与 `\` 分隔符）
+fn folder_of(path: &str) -> Option<&str> {
+    let mut parts = path.rsplit(['/', '\\']).filter(|part| !part.is_empty());
+    parts.next()?;
+    parts.next().filter(|folder| !folder.ends_with(':'))
+}
+
+/// 在扩展名前加上第一个未被占用的数字后缀，如 `01.png` → `01_2.png`
+fn suffixed(name: &str, taken: &HashSet<String>) -> String {
+    let (stem, ext) = match name.rfind('.') {
+        Some(dot) if dot > 0 => name.split_at(dot),
+        _ => (name, ""),
+    };
+    (2..)
+        .map(|n| format!("{}_{}{}", stem, n, ext))
+        .find(|candidate| !taken.contains(candidate))
+        .unwrap_or_else(|| name.to_string())
+}
+
+/// 超出列出数量时的省略说明
+fn more_suffix(total: usize) -> String {
+    if total > MAX_REPORTED_COLLISIONS {
+        format!(" 等 {} 个", total)
+    } else {
+        String::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sprite(id: &str, name: &str, path: &str) -> SpriteData {
+        SpriteData {
+            id: id.to_string(),
+            name: name.to_string(),
+            path: path.to_string(),
+            width: 4,
+            height: 4,
+            trimmed_width: 4,
+            trimmed_height: 4,
+            anchor: None,
+            nine_slice: None,
+        }
+    }
+
+    fn sprites() -> Vec<SpriteData> {
+        vec![
+            sprite("a", "01.png", "/art/run/01.png"),
+            sprite("b", "01.png", "C:\\art\\jump\\01.png"),
+            sprite("c", "01_2.png", "/art/misc/01_2.png"),
+            sprite("d", "02.png", "/art/run/02.png"),
+        ]
+    }
+
+    #[test]
+    fn test_error_policy_reports_sources() {
+        let err = resolve_collisions(&sprites(), NameCollisionPolicy::Error).unwrap_err();
+        assert!(err.contains("01.png（/art/run/01.png、C:\\art\\jump\\01.png）"));
+        assert!(!err.contains("02.png"));
+        assert!(resolve_collisions(&sprites()[2..], NameCollisionPolicy::Error).unwrap().is_empty());
+
+        assert!(check_unique_names(["a.png", "b.png"]).is_ok());
+        assert_eq!(check_unique_names(["a.png", "b.png", "a.png"]).unwrap_err(), "帧名称重复: a.png（2 个）");
+    }
+
+    #[test]
+    fn test_folder_prefix_and_numeric_suffix() {
+        let renames = resolve_collisions(&sprites(), NameCollisionPolicy::FolderPrefix).unwrap();
+        let names: Vec<(&str, &str)> = renames.iter().map(|r| (r.id.as_str(), r.to.as_str())).collect();
+        assert_eq!(names, [("a", "run/01.png"), ("b", "jump/01.png")]);
+
+        // 后缀跳过已有的 01_2.png
+        let renames = resolve_collisions(&sprites(), NameCollisionPolicy::NumericSuffix).unwrap();
+        assert_eq!(renames, [FrameRename { id: "b".to_string(), from: "01.png".to_string(), to: "01_3.png".to_string() }]);
+
+        // 没有文件夹或文件夹同名时退回数字后缀
+        let same_folder = vec![sprite("a", "x.png", "run/x.png"), sprite("b", "x.png", "/other/run/x.png"), sprite("c", "x.png", "")];
+        let renames = resolve_collisions(&same_folder, NameCollisionPolicy::FolderPrefix).unwrap();
+        let names: Vec<&str> = renames.iter().map(|r| r.to.as_str()).collect();
+        assert_eq!(names, ["run/x.png", "run/x_2.png", "x_2.png"]);
+    }
+}
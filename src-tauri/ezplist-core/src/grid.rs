@@ -0,0 +1,211 @@
+/// 网格切分坐标 (Grid Split Coordinates)
+///
+/// 图集尺寸不能被行列数整除时（如 725 / 6），统一使用截断后的帧尺寸会让后面的帧
+/// 逐渐偏离实际位置。这里按取整策略计算每条网格线的精确坐标
+
+use serde::{Deserialize, Serialize};
+
+/// 不能整除时的取整策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SplitRounding {
+    /// 统一使用截断后的帧尺寸，余下的像素留在图集末端
+    #[default]
+    Truncate,
+    /// 把余下的像素分摊到各帧，帧尺寸相差最多 1 像素，最后一帧对齐图集边缘
+    Distribute,
+    /// 不能整除时报错
+    Error,
+}
+
+/// 计算一个方向上的网格线坐标
+///
+/// # Arguments
+/// * `total` - 图集在该方向的尺寸
+/// * `count` - 帧数（行数或列数），必须大于 0
+/// * `rounding` - 取整策略
+///
+/// # Returns
+/// * `Result<Vec<u32>, String>` - `count + 1` 条网格线坐标，相邻两条之间为一帧
+pub fn grid_edges(total: u32, count: u32, rounding: SplitRounding) -> Result<Vec<u32>, String> {
+    if count == 0 {
+        return Err("行数和列数必须大于0".to_string());
+    }
+
+    let size = total / count;
+    match rounding {
+        SplitRounding::Error if !total.is_multiple_of(count) => Err(format!(
+            "{} 不能被 {} 整除（余 {} 像素）",
+            total,
+            count,
+            total % count
+        )),
+        SplitRounding::Distribute => Ok((0..=count)
+            .map(|i| (i as u64 * total as u64 / count as u64) as u32)
+            .collect()),
+        _ => Ok(uniform_edges(size, count)),
+    }
+}
+
+/// 固定帧尺寸的网格线坐标
+pub fn uniform_edges(size: u32, count: u32) -> Vec<u32> {
+    (0..=count).map(|i| i.saturating_mul(size)).collect()
+}
+
+/// 自动检测时尝试的最大边距/间距/偏移
+const MAX_DETECTED_GUTTER: u32 = 8;
+
+/// 一个方向上帧以外的像素：外边距、帧间距与起始偏移
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GridGutter {
+    /// 外边距（图集两侧各留出的像素）
+    pub margin: u32,
+    /// 相邻两帧之间的间距
+    pub spacing: u32,
+    /// 起始偏移（只在起始一侧，叠加在外边距上）
+    pub offset: u32,
+}
+
+impl GridGutter {
+    /// 第一帧的起点
+    pub fn start(&self) -> u32 {
+        self.margin.saturating_add(self.offset)
+    }
+
+    /// 扣除边距、偏移与帧间距后留给 `count` 帧的尺寸
+    pub fn content_size(&self, total: u32, count: u32) -> u32 {
+        let gaps = self.spacing.saturating_mul(count.saturating_sub(1));
+        total
+            .saturating_sub(self.margin.saturating_mul(2))
+            .saturating_sub(self.offset)
+            .saturating_sub(gaps)
+    }
+
+    /// 把不含间距的网格线坐标（见 `grid_edges` / `uniform_edges`）转换为每帧的起点与尺寸
+    ///
+    /// # Returns
+    /// * `Vec<(u32, u32)>` - 每帧在图集中的 (起点, 尺寸)
+    pub fn cells(&self, edges: &[u32]) -> Vec<(u32, u32)> {
+        edges
+            .windows(2)
+            .enumerate()
+            .map(|(i, w)| {
+                let gaps = self.spacing.saturating_mul(i as u32);
+                let start = self.start().saturating_add(w[0]).saturating_add(gaps);
+                (start, w[1].saturating_sub(w[0]))
+            })
+            .collect()
+    }
+}
+
+/// 按全透明的列（或行）推断一个方向上的帧尺寸与边距/间距
+///
+/// 在较小的边距、间距、偏移组合中寻找能让 `count` 帧等分剩余尺寸、
+/// 且所有非帧像素都透明的一组。优先选择帧的首尾两列（行）都贴着内容的组合，
+/// 其次选择非帧像素最少的组合（没有这样的间隙时与直接等分一致）
+///
+/// # Arguments
+/// * `transparent` - 每一列（或行）是否全透明
+/// * `count` - 帧数（列数或行数）
+///
+/// # Returns
+/// * `Option<(u32, GridGutter)>` - 帧尺寸与边距/间距，找不到时为 None
+pub fn detect_gutter(transparent: &[bool], count: u32) -> Option<(u32, GridGutter)> {
+    let total = transparent.len() as u32;
+    if count == 0 || total == 0 {
+        return None;
+    }
+
+    let mut candidates = Vec::new();
+    for margin in 0..=MAX_DETECTED_GUTTER {
+        for spacing in 0..=MAX_DETECTED_GUTTER {
+            for offset in 0..=MAX_DETECTED_GUTTER {
+                candidates.push(GridGutter { margin, spacing, offset });
+            }
+        }
+    }
+    candidates.sort_by_key(|g| (g.margin * 2 + g.offset + g.spacing * count.saturating_sub(1), g.offset));
+
+    let mut fallback = None;
+    for gutter in candidates {
+        let content = gutter.content_size(total, count);
+        if content == 0 || !content.is_multiple_of(count) {
+            continue;
+        }
+        let size = content / count;
+        let cells = gutter.cells(&uniform_edges(size, count));
+        // 帧之前、帧之间与最后一帧之后的像素都必须透明
+        let mut clear = true;
+        let mut cursor = 0;
+        for &(start, len) in cells.iter().chain(std::iter::once(&(total, 0))) {
+            clear &= transparent[cursor as usize..start as usize].iter().all(|&t| t);
+            cursor = start + len;
+        }
+        if !clear {
+            continue;
+        }
+        let has_content = |p: u32| !transparent[p as usize];
+        let tight = cells.iter().any(|&(start, _)| has_content(start))
+            && cells.iter().any(|&(start, len)| has_content(start + len - 1));
+        if tight {
+            return Some((size, gutter));
+        }
+        fallback.get_or_insert((size, gutter));
+    }
+    fallback
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_leaves_remainder() {
+        let edges = grid_edges(725, 6, SplitRounding::Truncate).unwrap();
+        assert_eq!(edges, vec![0, 120, 240, 360, 480, 600, 720]);
+    }
+
+    #[test]
+    fn test_distribute_covers_sheet() {
+        let edges = grid_edges(725, 6, SplitRounding::Distribute).unwrap();
+        assert_eq!(edges.first(), Some(&0));
+        assert_eq!(edges.last(), Some(&725));
+        assert!(edges.windows(2).all(|w| (120..=121).contains(&(w[1] - w[0]))));
+    }
+
+    #[test]
+    fn test_gutter_cells() {
+        let gutter = GridGutter { margin: 2, spacing: 1, offset: 3 };
+        assert_eq!(gutter.content_size(100, 4), 100 - 4 - 3 - 3);
+        let cells = gutter.cells(&uniform_edges(16, 3));
+        assert_eq!(cells, vec![(5, 16), (22, 16), (39, 16)]);
+    }
+
+    #[test]
+    fn test_detect_gutter() {
+        // 外边距 1、间距 2 的 4 帧 16 像素：1 + 4*16 + 3*2 + 1 = 72
+        let gutter = GridGutter { margin: 1, spacing: 2, offset: 0 };
+        let cells = gutter.cells(&uniform_edges(16, 4));
+        let mut transparent = vec![true; 72];
+        for &(start, _) in &cells {
+            transparent[start as usize] = false;
+            transparent[(start + 15) as usize] = false;
+        }
+        assert_eq!(detect_gutter(&transparent, 4), Some((16, gutter)));
+
+        // 没有边距时与等分一致
+        let plain = vec![false; 64];
+        assert_eq!(detect_gutter(&plain, 4), Some((16, GridGutter::default())));
+        let mut padded = vec![true; 64];
+        padded[20] = false;
+        assert_eq!(detect_gutter(&padded, 4), Some((16, GridGutter::default())));
+        assert_eq!(detect_gutter(&[false; 65], 4), None);
+    }
+
+    #[test]
+    fn test_error_policy() {
+        assert!(grid_edges(725, 6, SplitRounding::Error).is_err());
+        assert_eq!(grid_edges(720, 6, SplitRounding::Error).unwrap()[6], 720);
+        assert!(grid_edges(720, 0, SplitRounding::Truncate).is_err());
+    }
+}
@@ -0,0 +1,93 @@
+/// 哈希计算工具
+use image::RgbaImage;
+use md5::{Md5, Digest};
+use serde::{Deserialize, Serialize};
+
+pub fn calculate_md5(data: &[u8]) -> String {
+    let mut hasher = Md5::new();
+    hasher.update(data);
+    format!("{:x}", hasher.finalize())
+}
+
+/// SmartUpdate 哈希模式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SmartUpdateMode {
+    /// 按纹理文件名、帧布局与纹理像素计算
+    #[default]
+    Content,
+    /// 不含纹理文件名：同一份内容以不同名称导出时哈希相同，用于可复现构建
+    Deterministic,
+}
+
+/// SmartUpdate 哈希的输入
+///
+/// 打包导出、切分与合成生成的 plist 都用它计算 `metadata.smartupdate`：
+/// 与 TexturePacker 相同，只有帧布局或纹理像素变化时哈希才会变化，
+/// 引擎据此判断图集是否需要重新加载
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SmartUpdate<'a> {
+    /// 纹理像素（可选，为空时只按帧布局计算）
+    pub texture: Option<&'a RgbaImage>,
+    /// 哈希模式
+    pub mode: SmartUpdateMode,
+}
+
+impl<'a> SmartUpdate<'a> {
+    /// 按纹理像素计算
+    pub fn new(texture: &'a RgbaImage, mode: SmartUpdateMode) -> Self {
+        Self { texture: Some(texture), mode }
+    }
+
+    /// 计算哈希
+    ///
+    /// # Arguments
+    /// * `texture_name` - 纹理文件名
+    /// * `layout` - 帧布局的规范化描述（与数据文件的键名版本和编码无关）
+    ///
+    /// # Returns
+    /// * `String` - 十六进制 MD5
+    pub fn hash(&self, texture_name: &str, layout: &str) -> String {
+        let mut hasher = Md5::new();
+        if self.mode == SmartUpdateMode::Content {
+            hasher.update(texture_name.as_bytes());
+            hasher.update([0]);
+        }
+        hasher.update(layout.as_bytes());
+        if let Some(texture) = self.texture {
+            hasher.update([0]);
+            hasher.update(texture.width().to_le_bytes());
+            hasher.update(texture.height().to_le_bytes());
+            hasher.update(texture.as_raw());
+        }
+        format!("{:x}", hasher.finalize())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn test_smartupdate_hash() {
+        let red = RgbaImage::from_pixel(2, 2, Rgba([255, 0, 0, 255]));
+        let mut changed = red.clone();
+        changed.put_pixel(1, 1, Rgba([0, 0, 255, 255]));
+        let same = red.clone();
+        let content = |texture| SmartUpdate::new(texture, SmartUpdateMode::Content);
+
+        // 相同内容的哈希稳定，像素或布局变化时改变
+        assert_eq!(content(&red).hash("a.png", "layout"), content(&same).hash("a.png", "layout"));
+        assert_ne!(content(&red).hash("a.png", "layout"), content(&changed).hash("a.png", "layout"));
+        assert_ne!(content(&red).hash("a.png", "layout"), content(&red).hash("a.png", "layout2"));
+        assert_ne!(content(&red).hash("a.png", "layout"), SmartUpdate::default().hash("a.png", "layout"));
+
+        // 确定性模式不含纹理文件名
+        assert_ne!(content(&red).hash("a.png", "layout"), content(&red).hash("b.png", "layout"));
+        let deterministic = SmartUpdate::new(&red, SmartUpdateMode::Deterministic);
+        assert_eq!(deterministic.hash("a.png", "layout"), deterministic.hash("b.png", "layout"));
+        let mode: SmartUpdateMode = serde_json::from_str("\"deterministic\"").unwrap();
+        assert_eq!(mode, SmartUpdateMode::Deterministic);
+    }
+}
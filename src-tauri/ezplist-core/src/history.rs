@@ -0,0 +1,179 @@
+/// 导出历史 (Export History)
+///
+/// 每次成功导出都记录精灵列表、打包与导出配置的快照、输入指纹和输出文件，随项目文件保存。
+/// 重新导出时按记录重新打包并导出，源图修改后不必重新设置就能生成同样配置的图集
+
+use crate::hash::calculate_md5;
+use crate::types::{ExportConfig, PagedExportConfig, SpriteData};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// 保留的历史记录条数（超出时丢弃最早的记录）
+pub const MAX_EXPORT_HISTORY: usize = 50;
+
+/// 导出配置快照（不含打包结果，重新导出时由新的打包结果填充）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "config", rename_all = "camelCase")]
+pub enum ExportSnapshot {
+    /// 单页导出（`export_sprite_sheet`）
+    Single(ExportConfig),
+    /// 多页导出（`export_sprite_sheet_pages`）
+    Pages(PagedExportConfig),
+}
+
+impl ExportSnapshot {
+    /// 单页导出的快照（去掉已打包的精灵与打包结果 ID）
+    pub fn single(config: &ExportConfig) -> Self {
+        Self::Single(ExportConfig {
+            packed_sprites: Vec::new(),
+            job_id: String::new(),
+            ..config.clone()
+        })
+    }
+
+    /// 多页导出的快照（去掉各页与打包结果 ID）
+    pub fn pages(config: &PagedExportConfig) -> Self {
+        Self::Pages(PagedExportConfig {
+            pages: Vec::new(),
+            job_id: String::new(),
+            ..config.clone()
+        })
+    }
+
+    /// 是否需要多页打包
+    pub fn multi_page(&self) -> bool {
+        matches!(self, Self::Pages(_))
+    }
+
+    /// 输出目录
+    pub fn output_dir_mut(&mut self) -> &mut String {
+        match self {
+            Self::Single(config) => &mut config.output_dir,
+            Self::Pages(config) => &mut config.output_dir,
+        }
+    }
+}
+
+/// 一次导出的记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportRecord {
+    /// 记录 ID
+    pub id: String,
+    /// 导出时间（Unix 毫秒）
+    pub created_ms: u64,
+    /// 参与打包的精灵
+    pub sprites: Vec<SpriteData>,
+    /// 打包配置（`PackConfig`）
+    pub pack_config: Value,
+    /// 导出配置
+    pub export: ExportSnapshot,
+    /// 导出时的输入指纹（精灵来源内容与打包配置），见 `inputs_hash`
+    pub inputs_hash: String,
+    /// 写出的数据文件或 ZIP 路径
+    pub outputs: Vec<String>,
+}
+
+/// 计算输入指纹
+///
+/// 指纹覆盖每个精灵的 ID、名称与来源文件内容，以及打包配置；
+/// 任何一项变化都会得到不同的指纹
+///
+/// # Arguments
+/// * `sprites` - 参与打包的精灵
+/// * `pack_config` - 打包配置
+/// * `file_hash` - 读取来源文件的内容哈希，文件不存在时返回 None
+///
+/// # Returns
+/// * `String` - 十六进制 MD5
+pub fn inputs_hash(
+    sprites: &[SpriteData],
+    pack_config: &Value,
+    mut file_hash: impl FnMut(&str) -> Option<String>,
+) -> String {
+    let mut text = pack_config.to_string();
+    for sprite in sprites {
+        let hash = file_hash(&sprite.path).unwrap_or_else(|| "missing".to_string());
+        text.push_str(&format!("\n{}\t{}\t{}", sprite.id, sprite.name, hash));
+    }
+    calculate_md5(text.as_bytes())
+}
+
+/// 加入一条记录，超出 `MAX_EXPORT_HISTORY` 时丢弃最早的记录
+pub fn push_record(history: &mut Vec<ExportRecord>, record: ExportRecord) {
+    history.push(record);
+    if history.len() > MAX_EXPORT_HISTORY {
+        let excess = history.len() - MAX_EXPORT_HISTORY;
+        history.drain(..excess);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sprite(id: &str) -> SpriteData {
+        SpriteData {
+            id: id.to_string(),
+            name: format!("{}.png", id),
+            path: format!("/art/{}.png", id),
+            width: 8,
+            height: 8,
+            trimmed_width: 8,
+            trimmed_height: 8,
+            anchor: None,
+            nine_slice: None,
+        }
+    }
+
+    #[test]
+    fn test_inputs_hash() {
+        let sprites = vec![sprite("a"), sprite("b")];
+        let config = serde_json::json!({ "padding": 2 });
+        let base = inputs_hash(&sprites, &config, |path| Some(format!("hash:{}", path)));
+        assert_eq!(base, inputs_hash(&sprites, &config, |path| Some(format!("hash:{}", path))));
+
+        // 源图内容、打包配置或精灵列表变化时指纹不同
+        let edited = inputs_hash(&sprites, &config, |path| Some(if path.ends_with("b.png") { "new" } else { path }.to_string()));
+        assert_ne!(base, edited);
+        assert_ne!(base, inputs_hash(&sprites, &serde_json::json!({ "padding": 4 }), |p| Some(format!("hash:{}", p))));
+        assert_ne!(base, inputs_hash(&sprites[..1], &config, |path| Some(format!("hash:{}", path))));
+    }
+
+    #[test]
+    fn test_snapshot_and_history_limit() {
+        let config: ExportConfig = serde_json::from_value(serde_json::json!({
+            "packedSprites": [],
+            "textureWidth": 64,
+            "textureHeight": 64,
+            "outputDir": "/out",
+            "outputName": "ui",
+            "zipOutput": false,
+            "jobId": "job-1",
+        }))
+        .unwrap();
+        let snapshot = ExportSnapshot::single(&config);
+        assert!(!snapshot.multi_page());
+        let json = serde_json::to_value(&snapshot).unwrap();
+        assert_eq!(json["kind"], "single");
+        assert_eq!(json["config"]["jobId"], "");
+        let restored: ExportSnapshot = serde_json::from_value(json).unwrap();
+        assert!(matches!(restored, ExportSnapshot::Single(c) if c.output_name == "ui"));
+
+        let mut history = Vec::new();
+        for index in 0..MAX_EXPORT_HISTORY + 3 {
+            let record = ExportRecord {
+                id: index.to_string(),
+                created_ms: index as u64,
+                sprites: Vec::new(),
+                pack_config: Value::Null,
+                export: snapshot.clone(),
+                inputs_hash: String::new(),
+                outputs: Vec::new(),
+            };
+            push_record(&mut history, record);
+        }
+        assert_eq!(history.len(), MAX_EXPORT_HISTORY);
+        assert_eq!(history[0].id, "3");
+    }
+}
@@ -0,0 +1,143 @@
+/// 碰撞区域元数据 (Hit-Area Metadata)
+///
+/// 每帧可定义多个命名的矩形或多边形区域（如 hitbox、hurtbox），
+/// 以帧名称为键导出为 JSON 附属文件。
+/// 坐标相对帧左上角（像素）
+
+use crate::outline::Point;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+/// 区域形状
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum HitShape {
+    /// 矩形
+    Rect { x: f32, y: f32, width: f32, height: f32 },
+    /// 多边形
+    Polygon { points: Vec<Point> },
+}
+
+/// 命名的碰撞区域
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HitArea {
+    /// 区域名称（如 "hitbox"）
+    pub name: String,
+    /// 区域形状
+    #[serde(flatten)]
+    pub shape: HitShape,
+}
+
+/// 附属文件元数据
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HitAreaMeta {
+    app: &'static str,
+    version: &'static str,
+    frame_count: usize,
+}
+
+/// 附属文件内容
+#[derive(Debug, Serialize)]
+struct HitAreaFile<'a> {
+    frames: &'a BTreeMap<String, Vec<HitArea>>,
+    meta: HitAreaMeta,
+}
+
+/// 校验一帧的碰撞区域
+///
+/// # Arguments
+/// * `areas` - 碰撞区域列表
+///
+/// # Returns
+/// * `Result<(), String>` - 名称为空或重复、尺寸无效、坐标非有限数时返回错误信息
+pub fn validate_hit_areas(areas: &[HitArea]) -> Result<(), String> {
+    let mut names = HashSet::new();
+
+    for area in areas {
+        if area.name.trim().is_empty() {
+            return Err("碰撞区域名称不能为空".to_string());
+        }
+        if !names.insert(area.name.as_str()) {
+            return Err(format!("碰撞区域名称重复: {}", area.name));
+        }
+
+        let valid = match &area.shape {
+            HitShape::Rect { x, y, width, height } => {
+                [x, y, width, height].iter().all(|v| v.is_finite()) && *width > 0.0 && *height > 0.0
+            }
+            HitShape::Polygon { points } => {
+                points.len() >= 3 && points.iter().all(|p| p.x.is_finite() && p.y.is_finite())
+            }
+        };
+        if !valid {
+            return Err(format!("碰撞区域 {} 的形状无效", area.name));
+        }
+    }
+
+    Ok(())
+}
+
+/// 生成碰撞区域 JSON 附属文件
+///
+/// # Arguments
+/// * `frames` - 帧名称到碰撞区域的映射（按帧名称排序输出）
+///
+/// # Returns
+/// * `Result<String, String>` - JSON 内容或错误信息
+pub fn generate_hit_area_json(frames: &BTreeMap<String, Vec<HitArea>>) -> Result<String, String> {
+    let file = HitAreaFile {
+        frames,
+        meta: HitAreaMeta {
+            app: "EzPlist",
+            version: env!("CARGO_PKG_VERSION"),
+            frame_count: frames.len(),
+        },
+    };
+
+    serde_json::to_string_pretty(&file).map_err(|e| format!("序列化 JSON 失败: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(name: &str, width: f32) -> HitArea {
+        HitArea {
+            name: name.to_string(),
+            shape: HitShape::Rect {
+                x: 0.0,
+                y: 0.0,
+                width,
+                height: 10.0,
+            },
+        }
+    }
+
+    #[test]
+    fn test_validate_hit_areas() {
+        assert!(validate_hit_areas(&[rect("hitbox", 4.0), rect("hurtbox", 8.0)]).is_ok());
+        assert!(validate_hit_areas(&[rect("hitbox", 4.0), rect("hitbox", 8.0)]).is_err());
+        assert!(validate_hit_areas(&[rect(" ", 4.0)]).is_err());
+        assert!(validate_hit_areas(&[rect("hitbox", 0.0)]).is_err());
+
+        let line = HitArea {
+            name: "line".to_string(),
+            shape: HitShape::Polygon {
+                points: vec![Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 1.0 }],
+            },
+        };
+        assert!(validate_hit_areas(&[line]).is_err());
+    }
+
+    #[test]
+    fn test_shape_is_tagged() {
+        let json = serde_json::to_value(rect("hitbox", 4.0)).unwrap();
+        assert_eq!(json["type"], "rect");
+        assert_eq!(json["name"], "hitbox");
+        assert_eq!(json["width"], 4.0);
+
+        let parsed: HitArea = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, rect("hitbox", 4.0));
+    }
+}
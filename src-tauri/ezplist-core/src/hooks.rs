@@ -0,0 +1,188 @@
+/// 导出钩子 (Export Hooks)
+///
+/// 导出前后执行外部命令（如 `pngquant {png}`、复制到游戏工程目录、触发引擎热重载），
+/// 让 EzPlist 接入已有的资源流水线。这里只负责配置、校验与参数模板，进程由应用层启动。
+/// 命令直接执行而不经过 shell，参数中的路径不会被 shell 解释
+
+use serde::{Deserialize, Serialize};
+
+/// 单个钩子的默认超时（秒）
+pub const DEFAULT_HOOK_TIMEOUT_SECS: u64 = 60;
+
+/// 单个钩子允许的最大超时（秒）
+pub const MAX_HOOK_TIMEOUT_SECS: u64 = 3600;
+
+/// 钩子执行时机
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HookStage {
+    /// 写入文件之前
+    Pre,
+    /// 写入文件之后
+    #[default]
+    Post,
+}
+
+/// 导出钩子配置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportHook {
+    /// 可执行文件
+    pub command: String,
+    /// 参数模板，支持 `{png}`、`{plist}`、`{dir}`、`{name}`
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// 执行时机
+    #[serde(default)]
+    pub stage: HookStage,
+    /// 工作目录（可选，默认为输出目录）
+    #[serde(default)]
+    pub working_dir: Option<String>,
+    /// 超时（秒，可选）
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    /// 失败后是否继续导出（默认失败即中止）
+    #[serde(default)]
+    pub continue_on_error: bool,
+}
+
+impl ExportHook {
+    /// 校验配置
+    pub fn validate(&self) -> Result<(), String> {
+        if self.command.trim().is_empty() {
+            return Err("钩子命令不能为空".to_string());
+        }
+        if self.timeout_secs.is_some_and(|t| t == 0 || t > MAX_HOOK_TIMEOUT_SECS) {
+            return Err(format!("钩子超时必须在 1 ~ {} 秒之间", MAX_HOOK_TIMEOUT_SECS));
+        }
+        let sample = HookContext::default();
+        for arg in &self.args {
+            render_hook_arg(arg, &sample)?;
+        }
+        Ok(())
+    }
+
+    /// 实际使用的超时（秒）
+    pub fn timeout(&self) -> u64 {
+        self.timeout_secs.unwrap_or(DEFAULT_HOOK_TIMEOUT_SECS)
+    }
+}
+
+/// 参数模板可用的变量
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HookContext {
+    /// 纹理路径
+    pub png: String,
+    /// 数据文件路径
+    pub plist: String,
+    /// 输出目录
+    pub dir: String,
+    /// 输出文件名（不含扩展名）
+    pub name: String,
+}
+
+/// 钩子执行结果（返回给前端显示）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookOutput {
+    /// 执行的命令行（仅用于显示）
+    pub command_line: String,
+    /// 执行时机
+    pub stage: HookStage,
+    /// 是否成功
+    pub success: bool,
+    /// 退出码（超时或被终止时为 None）
+    pub exit_code: Option<i32>,
+    /// 标准输出
+    pub stdout: String,
+    /// 标准错误
+    pub stderr: String,
+}
+
+/// 渲染单个参数模板
+///
+/// # Arguments
+/// * `template` - 参数模板，`{{` 与 `}}` 表示字面量括号
+/// * `context` - 模板变量
+///
+/// # Returns
+/// * `Result<String, String>` - 渲染结果；含未知变量或括号不匹配时返回错误
+pub fn render_hook_arg(template: &str, context: &HookContext) -> Result<String, String> {
+    let mut output = String::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                output.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                output.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err(format!("钩子参数括号不匹配: {}", template)),
+                    }
+                }
+                let value = match name.as_str() {
+                    "png" => &context.png,
+                    "plist" => &context.plist,
+                    "dir" => &context.dir,
+                    "name" => &context.name,
+                    _ => return Err(format!("未知的钩子变量: {{{}}}", name)),
+                };
+                output.push_str(value);
+            }
+            '}' => return Err(format!("钩子参数括号不匹配: {}", template)),
+            c => output.push(c),
+        }
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> HookContext {
+        HookContext {
+            png: "/out/hero.png".to_string(),
+            plist: "/out/hero.plist".to_string(),
+            dir: "/out".to_string(),
+            name: "hero".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_render_hook_arg() {
+        assert_eq!(render_hook_arg("{png}", &context()).unwrap(), "/out/hero.png");
+        assert_eq!(render_hook_arg("--output={dir}/{name}-fs8.png", &context()).unwrap(), "--output=/out/hero-fs8.png");
+        assert_eq!(render_hook_arg("{{literal}}", &context()).unwrap(), "{literal}");
+        assert!(render_hook_arg("{unknown}", &context()).is_err());
+        assert!(render_hook_arg("{png", &context()).is_err());
+        assert!(render_hook_arg("png}", &context()).is_err());
+    }
+
+    #[test]
+    fn test_validate_hook() {
+        let hook: ExportHook = serde_json::from_str(r#"{"command":"pngquant","args":["--force","{png}"]}"#).unwrap();
+        assert_eq!(hook.stage, HookStage::Post);
+        assert!(hook.validate().is_ok());
+
+        let bad_arg = ExportHook {
+            args: vec!["{texture}".to_string()],
+            ..hook.clone()
+        };
+        assert!(bad_arg.validate().is_err());
+        let bad_timeout = ExportHook {
+            timeout_secs: Some(0),
+            ..hook
+        };
+        assert!(bad_timeout.validate().is_err());
+    }
+}
@@ -0,0 +1,201 @@
+/// 图像格式识别 (Image Format Detection)
+///
+/// 根据文件头部的魔数识别图像格式，不信任文件扩展名。
+/// 导入时只解码允许列表中的格式。
+/// TGA 没有魔数，只在扩展名为 .tga 且文件头字段合法时识别
+
+use image::{ImageFormat, RgbaImage};
+use serde::{Deserialize, Serialize};
+
+/// 识别格式所需的文件头字节数（TGA 文件头为 18 字节）
+pub const SNIFF_LEN: usize = 18;
+
+/// 支持导入的源图像格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceFormat {
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+    Bmp,
+    Tga,
+    Dds,
+}
+
+impl SourceFormat {
+    /// 所有支持的格式（默认允许列表）
+    pub const ALL: [SourceFormat; 7] = [
+        SourceFormat::Png,
+        SourceFormat::Jpeg,
+        SourceFormat::Gif,
+        SourceFormat::Webp,
+        SourceFormat::Bmp,
+        SourceFormat::Tga,
+        SourceFormat::Dds,
+    ];
+
+    /// 格式名称（小写）
+    pub fn name(&self) -> &'static str {
+        match self {
+            SourceFormat::Png => "png",
+            SourceFormat::Jpeg => "jpeg",
+            SourceFormat::Gif => "gif",
+            SourceFormat::Webp => "webp",
+            SourceFormat::Bmp => "bmp",
+            SourceFormat::Tga => "tga",
+            SourceFormat::Dds => "dds",
+        }
+    }
+
+    /// 对应的 image crate 格式
+    pub fn image_format(&self) -> ImageFormat {
+        match self {
+            SourceFormat::Png => ImageFormat::Png,
+            SourceFormat::Jpeg => ImageFormat::Jpeg,
+            SourceFormat::Gif => ImageFormat::Gif,
+            SourceFormat::Webp => ImageFormat::WebP,
+            SourceFormat::Bmp => ImageFormat::Bmp,
+            SourceFormat::Tga => ImageFormat::Tga,
+            SourceFormat::Dds => ImageFormat::Dds,
+        }
+    }
+
+    /// 旧格式的 alpha 通道不可靠：很多导出工具写入 32 位像素但 alpha 全为 0
+    pub fn has_unreliable_alpha(&self) -> bool {
+        matches!(self, SourceFormat::Bmp | SourceFormat::Tga | SourceFormat::Dds)
+    }
+}
+
+/// 根据文件头识别图像格式
+///
+/// # Arguments
+/// * `header` - 文件开头的字节（至少 `SNIFF_LEN` 字节时识别最可靠）
+/// * `extension` - 文件扩展名（仅用于识别没有魔数的 TGA）
+///
+/// # Returns
+/// * `Option<SourceFormat>` - 识别出的格式；无法识别时返回 None
+pub fn detect_format(header: &[u8], extension: Option<&str>) -> Option<SourceFormat> {
+    if header.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some(SourceFormat::Png)
+    } else if header.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some(SourceFormat::Jpeg)
+    } else if header.starts_with(b"GIF87a") || header.starts_with(b"GIF89a") {
+        Some(SourceFormat::Gif)
+    } else if header.len() >= 12 && &header[0..4] == b"RIFF" && &header[8..12] == b"WEBP" {
+        Some(SourceFormat::Webp)
+    } else if header.starts_with(b"BM") {
+        Some(SourceFormat::Bmp)
+    } else if header.starts_with(b"DDS ") {
+        Some(SourceFormat::Dds)
+    } else if extension.is_some_and(|ext| ext.eq_ignore_ascii_case("tga")) && is_plausible_tga(header) {
+        Some(SourceFormat::Tga)
+    } else {
+        None
+    }
+}
+
+/// 检查 TGA 文件头字段是否合法
+fn is_plausible_tga(header: &[u8]) -> bool {
+    if header.len() < 18 {
+        return false;
+    }
+    let color_map_type = header[1];
+    let image_type = header[2];
+    let pixel_depth = header[16];
+    color_map_type <= 1
+        && matches!(image_type, 1 | 2 | 3 | 9 | 10 | 11)
+        && matches!(pixel_depth, 8 | 15 | 16 | 24 | 32)
+}
+
+/// 检查文件头是否为允许的图像格式
+///
+/// # Arguments
+/// * `header` - 文件开头的字节
+/// * `extension` - 文件扩展名
+/// * `allowed` - 允许的格式列表
+///
+/// # Returns
+/// * `Result<SourceFormat, String>` - 识别出的格式，或无法识别/不在允许列表时的错误信息
+pub fn check_format(
+    header: &[u8],
+    extension: Option<&str>,
+    allowed: &[SourceFormat],
+) -> Result<SourceFormat, String> {
+    let format = detect_format(header, extension).ok_or_else(|| "无法识别的图像格式".to_string())?;
+    if !allowed.contains(&format) {
+        return Err(format!("不允许导入的图像格式: {}", format.name()));
+    }
+    Ok(format)
+}
+
+/// 修正旧格式中未使用的 alpha 通道
+///
+/// alpha 全为 0 时视为导出工具没有写入 alpha，将图像改为完全不透明
+///
+/// # Arguments
+/// * `img` - 解码后的图像
+///
+/// # Returns
+/// * `bool` - 是否进行了修正
+pub fn normalize_legacy_alpha(img: &mut RgbaImage) -> bool {
+    if img.is_empty() || img.pixels().any(|p| p[3] != 0) {
+        return false;
+    }
+    for pixel in img.pixels_mut() {
+        pixel[3] = 255;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_by_magic_bytes() {
+        assert_eq!(detect_format(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR", None), Some(SourceFormat::Png));
+        assert_eq!(detect_format(&[0xFF, 0xD8, 0xFF, 0xE0], None), Some(SourceFormat::Jpeg));
+        assert_eq!(detect_format(b"GIF89a\x01\0", None), Some(SourceFormat::Gif));
+        assert_eq!(detect_format(b"RIFF\x10\0\0\0WEBPVP8 ", None), Some(SourceFormat::Webp));
+        assert_eq!(detect_format(b"BM\x36\0\0\0", None), Some(SourceFormat::Bmp));
+        assert_eq!(detect_format(b"<?xml version", None), None);
+        assert_eq!(detect_format(b"RIFF\x10\0\0\0WAVE", None), None);
+        assert_eq!(detect_format(b"DDS |\0\0\0", None), Some(SourceFormat::Dds));
+        assert_eq!(detect_format(b"", None), None);
+    }
+
+    #[test]
+    fn test_detect_tga_requires_extension() {
+        // 无颜色表、未压缩真彩色、32 位
+        let mut header = [0u8; 18];
+        header[2] = 2;
+        header[16] = 32;
+        assert_eq!(detect_format(&header, Some("TGA")), Some(SourceFormat::Tga));
+        assert_eq!(detect_format(&header, Some("png")), None);
+        assert_eq!(detect_format(&header, None), None);
+
+        header[2] = 7;
+        assert_eq!(detect_format(&header, Some("tga")), None);
+    }
+
+    #[test]
+    fn test_normalize_legacy_alpha() {
+        let mut img = RgbaImage::from_pixel(2, 2, image::Rgba([10, 20, 30, 0]));
+        assert!(normalize_legacy_alpha(&mut img));
+        assert!(img.pixels().all(|p| p[3] == 255));
+
+        let mut img = RgbaImage::from_pixel(2, 2, image::Rgba([10, 20, 30, 0]));
+        img.put_pixel(1, 1, image::Rgba([0, 0, 0, 128]));
+        assert!(!normalize_legacy_alpha(&mut img));
+        assert_eq!(img.get_pixel(0, 0)[3], 0);
+    }
+
+    #[test]
+    fn test_check_against_allow_list() {
+        let png = b"\x89PNG\r\n\x1a\n";
+        assert_eq!(check_format(png, None, &SourceFormat::ALL), Ok(SourceFormat::Png));
+        assert!(check_format(png, None, &[SourceFormat::Jpeg]).is_err());
+        assert!(check_format(b"not an image", None, &SourceFormat::ALL).is_err());
+    }
+}
@@ -0,0 +1,649 @@
+/// 图像处理模块
+/// 
+/// 包含：
+/// - 透明像素检测
+/// - 图像裁剪
+/// - 纹理图渲染
+/// - 透明像素渗色（alpha bleeding）
+/// - 导出时的图像调整（亮度/对比度/饱和度/色相/伽马）
+/// - 绿幕/蓝幕抠像
+
+use crate::types::PackedSprite;
+use image::{imageops, Rgba, RgbaImage};
+use serde::{Deserialize, Serialize};
+
+/// 透明裁剪结果
+#[derive(Debug)]
+pub struct TrimResult {
+    /// 裁剪后的图像
+    pub trimmed_image: RgbaImage,
+    /// 偏移量 (x, y)
+    pub offset: (i32, i32),
+    /// 原始尺寸 (width, height)
+    pub original_size: (u32, u32),
+}
+
+/// 透明像素裁剪
+/// 
+/// TODO: Phase 3 实现
+pub fn trim_transparent(_img: &RgbaImage) -> Option<TrimResult> {
+    // 占位实现
+    None
+}
+
+/// 渲染纹理图
+///
+/// 把每个精灵（裁剪后的图像）直接覆盖到打包位置；旋转帧按 Cocos2d-x 约定顺时针旋转 90 度存放
+///
+/// # Arguments
+/// * `sprites` - 已打包的精灵
+/// * `width` / `height` - 纹理尺寸
+/// * `image_for` - 取得精灵的图像（未旋转的裁剪后图像，尺寸应与 `frame_size` 一致）
+///
+/// # Returns
+/// * `Result<RgbaImage, String>` - 纹理；缺少图像、尺寸与打包结果不符或超出纹理时返回错误
+pub fn render_texture<'a>(
+    sprites: &[PackedSprite],
+    width: u32,
+    height: u32,
+    image_for: impl Fn(&PackedSprite) -> Option<&'a RgbaImage>,
+) -> Result<RgbaImage, String> {
+    let mut texture = RgbaImage::new(width, height);
+    // 翻转帧与源帧共用纹理区域，由源帧绘制
+    for sprite in sprites.iter().filter(|s| !s.is_flipped()) {
+        let image = image_for(sprite).ok_or_else(|| format!("缺少精灵 {} 的图像", sprite.name))?;
+        let (frame_width, frame_height) = sprite.frame_size();
+        if image.dimensions() != (frame_width, frame_height) {
+            return Err(format!(
+                "精灵 {} 的图像尺寸 {}x{} 与打包结果 {}x{} 不一致，请重新打包",
+                sprite.name,
+                image.width(),
+                image.height(),
+                frame_width,
+                frame_height
+            ));
+        }
+        let rotated;
+        let image = if sprite.rotated {
+            rotated = imageops::rotate90(image);
+            &rotated
+        } else {
+            image
+        };
+        let fits = sprite.x.checked_add(image.width()).is_some_and(|r| r <= width)
+            && sprite.y.checked_add(image.height()).is_some_and(|b| b <= height);
+        if !fits {
+            return Err(format!("精灵 {} 超出纹理范围", sprite.name));
+        }
+        imageops::replace(&mut texture, image, sprite.x as i64, sprite.y as i64);
+    }
+    Ok(texture)
+}
+
+/// 边缘挤出：把每个精灵的边缘像素复制到四周 `extrude` 像素的区域
+///
+/// 打包时已为挤出区域预留空间，精灵位置（textureRect）仍指向内部区域；
+/// 超出纹理范围的部分会被忽略
+///
+/// # Arguments
+/// * `texture` - 已渲染的纹理
+/// * `sprites` - 纹理中的精灵
+/// * `extrude` - 挤出像素
+pub fn extrude_edges(texture: &mut RgbaImage, sprites: &[PackedSprite], extrude: u32) {
+    if extrude == 0 {
+        return;
+    }
+    let (width, height) = texture.dimensions();
+    for sprite in sprites {
+        if sprite.width == 0 || sprite.height == 0 {
+            continue;
+        }
+        let right = sprite.x.saturating_add(sprite.width).min(width);
+        let bottom = sprite.y.saturating_add(sprite.height).min(height);
+        if sprite.x >= right || sprite.y >= bottom {
+            continue;
+        }
+        let outer_right = right.saturating_add(extrude).min(width);
+        let outer_bottom = bottom.saturating_add(extrude).min(height);
+        for y in sprite.y.saturating_sub(extrude)..outer_bottom {
+            let src_y = y.clamp(sprite.y, bottom - 1);
+            for x in sprite.x.saturating_sub(extrude)..outer_right {
+                let src_x = x.clamp(sprite.x, right - 1);
+                if (src_x, src_y) != (x, y) {
+                    let pixel = *texture.get_pixel(src_x, src_y);
+                    texture.put_pixel(x, y, pixel);
+                }
+            }
+        }
+    }
+}
+
+/// 渗色迭代次数上限
+pub const MAX_BLEED_ITERATIONS: u32 = 32;
+
+/// 透明像素渗色（alpha bleeding）
+///
+/// 透明像素的 RGB 通常为黑色，纹理缩放或线性过滤时会混入精灵边缘，形成暗边。
+/// 每次迭代把不透明像素（及上一轮已渗色的像素）的颜色平均后写入相邻的透明像素，
+/// 只修改 RGB，alpha 保持为 0，不影响显示结果
+///
+/// # Arguments
+/// * `texture` - 已渲染的纹理
+/// * `iterations` - 向外扩散的像素圈数（0 为不处理）
+pub fn bleed_alpha(texture: &mut RgbaImage, iterations: u32) {
+    if iterations == 0 {
+        return;
+    }
+    let (width, height) = texture.dimensions();
+    let index = |x: u32, y: u32| y as usize * width as usize + x as usize;
+    // 颜色已确定的像素：不透明，或已渗色
+    let mut known: Vec<bool> = texture.pixels().map(|p| p[3] > 0).collect();
+    let mut queued = known.clone();
+    let mut frontier: Vec<(u32, u32)> = Vec::new();
+    for y in 0..height {
+        for x in 0..width {
+            if !known[index(x, y)] && neighbors(x, y, width, height).any(|(nx, ny)| known[index(nx, ny)]) {
+                queued[index(x, y)] = true;
+                frontier.push((x, y));
+            }
+        }
+    }
+
+    for _ in 0..iterations {
+        if frontier.is_empty() {
+            break;
+        }
+        // 先计算整圈颜色再写入，同一圈内的像素互不影响
+        let colors: Vec<[u8; 3]> = frontier
+            .iter()
+            .map(|&(x, y)| {
+                let (mut sum, mut count) = ([0u32; 3], 0u32);
+                for (nx, ny) in neighbors(x, y, width, height).filter(|&(nx, ny)| known[index(nx, ny)]) {
+                    let pixel = texture.get_pixel(nx, ny);
+                    for (total, channel) in sum.iter_mut().zip(pixel.0) {
+                        *total += channel as u32;
+                    }
+                    count += 1;
+                }
+                sum.map(|total| (total / count.max(1)) as u8)
+            })
+            .collect();
+        for (&(x, y), [r, g, b]) in frontier.iter().zip(colors) {
+            texture.put_pixel(x, y, Rgba([r, g, b, 0]));
+            known[index(x, y)] = true;
+        }
+
+        let mut next = Vec::new();
+        for &(x, y) in &frontier {
+            for (nx, ny) in neighbors(x, y, width, height) {
+                if !queued[index(nx, ny)] {
+                    queued[index(nx, ny)] = true;
+                    next.push((nx, ny));
+                }
+            }
+        }
+        frontier = next;
+    }
+}
+
+/// 检查渗色迭代次数
+///
+/// # Returns
+/// * `Result<(), String>` - 超过 [`MAX_BLEED_ITERATIONS`] 时返回错误
+pub fn validate_bleed(iterations: u32) -> Result<(), String> {
+    if iterations > MAX_BLEED_ITERATIONS {
+        return Err(format!("渗色迭代次数 {} 无效（应为 0-{}）", iterations, MAX_BLEED_ITERATIONS));
+    }
+    Ok(())
+}
+
+/// 纹理范围内的 8 邻域
+fn neighbors(x: u32, y: u32, width: u32, height: u32) -> impl Iterator<Item = (u32, u32)> {
+    (-1i64..=1)
+        .flat_map(|dy| (-1i64..=1).map(move |dx| (dx, dy)))
+        .filter(|&offset| offset != (0, 0))
+        .filter_map(move |(dx, dy)| {
+            let (nx, ny) = (x as i64 + dx, y as i64 + dy);
+            (nx >= 0 && ny >= 0 && nx < width as i64 && ny < height as i64).then_some((nx as u32, ny as u32))
+        })
+}
+
+/// 图像调整参数
+///
+/// 所有字段默认为不调整。处理顺序：伽马 -> 亮度 -> 对比度 -> 饱和度 -> 色相，
+/// 只修改 RGB，保留 alpha
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ImageAdjustments {
+    /// 亮度偏移（-1 ~ 1）
+    pub brightness: f32,
+    /// 对比度（-1 ~ 1，-1 为纯灰）
+    pub contrast: f32,
+    /// 饱和度（-1 ~ 1，-1 为灰度）
+    pub saturation: f32,
+    /// 色相旋转（角度）
+    pub hue_shift: f32,
+    /// 伽马（大于 0，1 为不调整）
+    pub gamma: f32,
+}
+
+impl Default for ImageAdjustments {
+    fn default() -> Self {
+        Self {
+            brightness: 0.0,
+            contrast: 0.0,
+            saturation: 0.0,
+            hue_shift: 0.0,
+            gamma: 1.0,
+        }
+    }
+}
+
+impl ImageAdjustments {
+    /// 校验参数范围
+    pub fn validate(&self) -> Result<(), String> {
+        let in_range = |v: f32| v.is_finite() && (-1.0..=1.0).contains(&v);
+        if !in_range(self.brightness) || !in_range(self.contrast) || !in_range(self.saturation) {
+            return Err("亮度、对比度、饱和度必须在 -1 ~ 1 之间".to_string());
+        }
+        if !self.hue_shift.is_finite() {
+            return Err("色相旋转角度无效".to_string());
+        }
+        if !self.gamma.is_finite() || self.gamma <= 0.0 {
+            return Err(format!("伽马值必须大于 0: {}", self.gamma));
+        }
+        Ok(())
+    }
+
+    /// 是否不做任何调整
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// 伽马、亮度、对比度合并为逐通道查找表
+    fn channel_lut(&self) -> [u8; 256] {
+        let mut lut = [0u8; 256];
+        for (v, out) in lut.iter_mut().enumerate() {
+            let mut value = 255.0 * (v as f32 / 255.0).powf(1.0 / self.gamma);
+            value += self.brightness * 255.0;
+            value = (value - 128.0) * (1.0 + self.contrast) + 128.0;
+            *out = value.round().clamp(0.0, 255.0) as u8;
+        }
+        lut
+    }
+
+    /// 饱和度与色相旋转合并为 3x3 颜色矩阵（与 CSS saturate/hue-rotate 滤镜一致）
+    fn color_matrix(&self) -> [[f32; 3]; 3] {
+        let s = 1.0 + self.saturation;
+        let saturate = [
+            [0.213 + 0.787 * s, 0.715 - 0.715 * s, 0.072 - 0.072 * s],
+            [0.213 - 0.213 * s, 0.715 + 0.285 * s, 0.072 - 0.072 * s],
+            [0.213 - 0.213 * s, 0.715 - 0.715 * s, 0.072 + 0.928 * s],
+        ];
+
+        let (sin, cos) = self.hue_shift.to_radians().sin_cos();
+        let hue = [
+            [
+                0.213 + cos * 0.787 - sin * 0.213,
+                0.715 - cos * 0.715 - sin * 0.715,
+                0.072 - cos * 0.072 + sin * 0.928,
+            ],
+            [
+                0.213 - cos * 0.213 + sin * 0.143,
+                0.715 + cos * 0.285 + sin * 0.140,
+                0.072 - cos * 0.072 - sin * 0.283,
+            ],
+            [
+                0.213 - cos * 0.213 - sin * 0.787,
+                0.715 - cos * 0.715 + sin * 0.715,
+                0.072 + cos * 0.928 + sin * 0.072,
+            ],
+        ];
+
+        let mut matrix = [[0.0; 3]; 3];
+        for (i, row) in matrix.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell = (0..3).map(|k| hue[i][k] * saturate[k][j]).sum();
+            }
+        }
+        matrix
+    }
+}
+
+/// 抠像背景色
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScreenColor {
+    /// 绿幕
+    #[default]
+    Green,
+    /// 蓝幕
+    Blue,
+}
+
+/// 绿幕/蓝幕抠像参数
+///
+/// 以背景通道超出其余两个通道最大值的程度作为键值：
+/// 键值不超过 `threshold` 的像素保留，超过 `threshold + softness` 的像素完全透明，
+/// 之间线性过渡
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ChromaKey {
+    /// 背景色
+    pub screen: ScreenColor,
+    /// 开始变透明的键值（0 ~ 1）
+    pub threshold: f32,
+    /// 边缘过渡宽度（0 ~ 1）
+    pub softness: f32,
+    /// 是否抑制前景边缘的背景色溢出
+    pub spill_suppression: bool,
+}
+
+impl Default for ChromaKey {
+    fn default() -> Self {
+        Self {
+            screen: ScreenColor::Green,
+            threshold: 0.1,
+            softness: 0.1,
+            spill_suppression: true,
+        }
+    }
+}
+
+impl ChromaKey {
+    /// 校验参数范围
+    pub fn validate(&self) -> Result<(), String> {
+        let in_range = |v: f32| v.is_finite() && (0.0..=1.0).contains(&v);
+        if !in_range(self.threshold) || !in_range(self.softness) {
+            return Err("抠像阈值与过渡宽度必须在 0 ~ 1 之间".to_string());
+        }
+        Ok(())
+    }
+
+    /// 背景通道与其余两个通道
+    fn channels(&self) -> (usize, [usize; 2]) {
+        match self.screen {
+            ScreenColor::Green => (1, [0, 2]),
+            ScreenColor::Blue => (2, [0, 1]),
+        }
+    }
+}
+
+/// 绿幕/蓝幕抠像
+///
+/// 背景像素的 alpha 按键值降低（与原 alpha 相乘）；启用溢出抑制时，
+/// 背景通道被限制为不超过其余两个通道的最大值
+///
+/// # Arguments
+/// * `img` - 要处理的图像（原地修改）
+/// * `key` - 抠像参数（需先通过 `validate`）
+pub fn apply_chroma_key(img: &mut RgbaImage, key: &ChromaKey) {
+    let (screen, [a, b]) = key.channels();
+    for pixel in img.pixels_mut().filter(|p| p[3] > 0) {
+        let others = pixel[a].max(pixel[b]);
+        let strength = pixel[screen].saturating_sub(others) as f32 / 255.0;
+        let keep = if strength <= key.threshold {
+            1.0
+        } else if key.softness > 0.0 {
+            (1.0 - (strength - key.threshold) / key.softness).max(0.0)
+        } else {
+            0.0
+        };
+        pixel[3] = (pixel[3] as f32 * keep).round() as u8;
+        if key.spill_suppression {
+            pixel[screen] = pixel[screen].min(others);
+        }
+    }
+}
+
+/// 应用图像调整
+///
+/// 完全透明的像素不处理
+///
+/// # Arguments
+/// * `img` - 要调整的图像（原地修改）
+/// * `adjustments` - 调整参数（需先通过 `validate`）
+pub fn apply_adjustments(img: &mut RgbaImage, adjustments: &ImageAdjustments) {
+    if adjustments.is_identity() {
+        return;
+    }
+
+    let lut = adjustments.channel_lut();
+    let apply_matrix = adjustments.saturation != 0.0 || adjustments.hue_shift % 360.0 != 0.0;
+    let matrix = adjustments.color_matrix();
+
+    for pixel in img.pixels_mut().filter(|p| p[3] > 0) {
+        let rgb = [lut[pixel[0] as usize], lut[pixel[1] as usize], lut[pixel[2] as usize]];
+        if apply_matrix {
+            for (c, row) in matrix.iter().enumerate() {
+                let value: f32 = row.iter().zip(rgb).map(|(m, v)| m * v as f32).sum();
+                pixel[c] = value.round().clamp(0.0, 255.0) as u8;
+            }
+        } else {
+            pixel[0] = rgb[0];
+            pixel[1] = rgb[1];
+            pixel[2] = rgb[2];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    fn adjusted(color: [u8; 4], adjustments: ImageAdjustments) -> [u8; 4] {
+        let mut img = RgbaImage::from_pixel(1, 1, Rgba(color));
+        apply_adjustments(&mut img, &adjustments);
+        img.get_pixel(0, 0).0
+    }
+
+    #[test]
+    fn test_identity_and_transparent_pixels() {
+        assert_eq!(adjusted([10, 20, 30, 255], ImageAdjustments::default()), [10, 20, 30, 255]);
+
+        let brighter = ImageAdjustments {
+            brightness: 0.5,
+            ..Default::default()
+        };
+        assert_eq!(adjusted([10, 20, 30, 0], brighter.clone()), [10, 20, 30, 0]);
+        assert_eq!(adjusted([10, 20, 30, 128], brighter), [138, 148, 158, 128]);
+    }
+
+    #[test]
+    fn test_contrast_saturation_gamma() {
+        let flat = ImageAdjustments {
+            contrast: -1.0,
+            ..Default::default()
+        };
+        assert_eq!(adjusted([0, 100, 255, 255], flat), [128, 128, 128, 255]);
+
+        let gray = ImageAdjustments {
+            saturation: -1.0,
+            ..Default::default()
+        };
+        let [r, g, b, _] = adjusted([255, 0, 0, 255], gray);
+        assert!(r == g && g == b);
+
+        let gamma = ImageAdjustments {
+            gamma: 2.2,
+            ..Default::default()
+        };
+        assert!(adjusted([64, 64, 64, 255], gamma)[0] > 64);
+    }
+
+    #[test]
+    fn test_hue_shift_rotates_primaries() {
+        let shift = ImageAdjustments {
+            hue_shift: 120.0,
+            ..Default::default()
+        };
+        let [r, g, b, _] = adjusted([255, 0, 0, 255], shift);
+        assert!(g > r && g > b);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(ImageAdjustments::default().validate().is_ok());
+        let invalid = ImageAdjustments {
+            gamma: 0.0,
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+        let invalid = ImageAdjustments {
+            brightness: 2.0,
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_chroma_key() {
+        let key = ChromaKey::default();
+        let keyed = |color: [u8; 4], key: &ChromaKey| {
+            let mut img = RgbaImage::from_pixel(1, 1, Rgba(color));
+            apply_chroma_key(&mut img, key);
+            img.get_pixel(0, 0).0
+        };
+        // 纯绿背景完全透明，前景不变
+        assert_eq!(keyed([0, 255, 0, 255], &key)[3], 0);
+        assert_eq!(keyed([200, 120, 90, 255], &key), [200, 120, 90, 255]);
+        // 过渡区域半透明，溢出的绿色被压低
+        let [r, g, b, a] = keyed([100, 138, 100, 255], &key);
+        assert_eq!((r, g, b), (100, 100, 100));
+        assert!((120..140).contains(&a));
+
+        let blue = ChromaKey { screen: ScreenColor::Blue, spill_suppression: false, ..key };
+        assert_eq!(keyed([0, 0, 255, 255], &blue)[3], 0);
+        assert_eq!(keyed([0, 255, 0, 255], &blue)[3], 255);
+        assert!(ChromaKey { softness: 1.5, ..key }.validate().is_err());
+    }
+
+    #[test]
+    fn test_render_texture_places_sprites() {
+        let sprite = PackedSprite {
+            id: "a".to_string(),
+            name: "a.png".to_string(),
+            x: 2,
+            y: 1,
+            width: 3,
+            height: 2,
+            rotated: false,
+            original_width: 3,
+            original_height: 2,
+            trimmed: false,
+            offset_x: 0,
+            offset_y: 0,
+            flip_x: false,
+            flip_y: false,
+            anchor: None,
+            nine_slice: None,
+        };
+        let image = RgbaImage::from_pixel(3, 2, Rgba([255, 0, 0, 255]));
+        let texture = render_texture(std::slice::from_ref(&sprite), 8, 4, |_| Some(&image)).unwrap();
+        assert_eq!(texture.dimensions(), (8, 4));
+        assert_eq!(*texture.get_pixel(4, 2), Rgba([255, 0, 0, 255]));
+        assert_eq!(texture.get_pixel(5, 2)[3], 0);
+
+        assert!(render_texture(std::slice::from_ref(&sprite), 8, 4, |_| None).is_err());
+        assert!(render_texture(std::slice::from_ref(&sprite), 4, 4, |_| Some(&image)).is_err());
+        let stale = RgbaImage::new(2, 2);
+        assert!(render_texture(&[sprite], 8, 4, |_| Some(&stale)).is_err());
+    }
+
+    #[test]
+    fn test_render_texture_rotates_clockwise() {
+        // 3x2 的帧，每个像素颜色不同
+        let mut image = RgbaImage::new(3, 2);
+        for (x, y, pixel) in image.enumerate_pixels_mut() {
+            *pixel = Rgba([x as u8 * 50, y as u8 * 100, 7, 255]);
+        }
+        let sprite = PackedSprite {
+            id: "r".to_string(),
+            name: "r.png".to_string(),
+            x: 1,
+            y: 0,
+            width: 2,
+            height: 3,
+            rotated: true,
+            original_width: 3,
+            original_height: 2,
+            trimmed: false,
+            offset_x: 0,
+            offset_y: 0,
+            flip_x: false,
+            flip_y: false,
+            anchor: None,
+            nine_slice: None,
+        };
+        let texture = render_texture(std::slice::from_ref(&sprite), 4, 4, |_| Some(&image)).unwrap();
+
+        // 顺时针旋转 90 度：左上角到右上角，右下角到左下角
+        assert_eq!(texture.get_pixel(2, 0), image.get_pixel(0, 0));
+        assert_eq!(texture.get_pixel(1, 0), image.get_pixel(0, 1));
+        assert_eq!(texture.get_pixel(1, 2), image.get_pixel(2, 1));
+        assert_eq!(texture.get_pixel(0, 0)[3], 0);
+        // 纹理区域逆时针转回即为原图（Cocos2d-x textureRotated 的读取方式）
+        let region = imageops::crop_imm(&texture, sprite.x, sprite.y, sprite.width, sprite.height).to_image();
+        assert_eq!(imageops::rotate270(&region), image);
+
+        // 图像必须是未旋转的方向
+        let pre_rotated = imageops::rotate90(&image);
+        assert!(render_texture(&[sprite], 4, 4, |_| Some(&pre_rotated)).is_err());
+    }
+
+    #[test]
+    fn test_extrude_edges_replicates_border() {
+        let sprite = PackedSprite {
+            id: "a".to_string(),
+            name: "a.png".to_string(),
+            x: 1,
+            y: 1,
+            width: 2,
+            height: 2,
+            rotated: false,
+            original_width: 2,
+            original_height: 2,
+            trimmed: false,
+            offset_x: 0,
+            offset_y: 0,
+            flip_x: false,
+            flip_y: false,
+            anchor: None,
+            nine_slice: None,
+        };
+        let mut image = RgbaImage::from_pixel(2, 2, Rgba([255, 0, 0, 255]));
+        image.put_pixel(1, 1, Rgba([0, 0, 255, 255]));
+        let mut texture = render_texture(std::slice::from_ref(&sprite), 5, 4, |_| Some(&image)).unwrap();
+        extrude_edges(&mut texture, std::slice::from_ref(&sprite), 2);
+
+        // 左上角挤出区域复制左上角像素，右下角复制右下角像素（超出纹理的部分被忽略）
+        assert_eq!(*texture.get_pixel(0, 0), Rgba([255, 0, 0, 255]));
+        assert_eq!(*texture.get_pixel(4, 3), Rgba([0, 0, 255, 255]));
+        assert_eq!(*texture.get_pixel(3, 0), Rgba([255, 0, 0, 255]));
+        // 内部区域不变
+        assert_eq!(*texture.get_pixel(2, 2), Rgba([0, 0, 255, 255]));
+    }
+
+    #[test]
+    fn test_bleed_alpha() {
+        let mut texture = RgbaImage::new(6, 3);
+        texture.put_pixel(0, 1, Rgba([200, 0, 0, 255]));
+        texture.put_pixel(2, 1, Rgba([0, 0, 100, 128]));
+        bleed_alpha(&mut texture, 2);
+
+        // 不透明像素不变；相邻两色取平均，alpha 保持为 0
+        assert_eq!(*texture.get_pixel(0, 1), Rgba([200, 0, 0, 255]));
+        assert_eq!(*texture.get_pixel(2, 1), Rgba([0, 0, 100, 128]));
+        assert_eq!(*texture.get_pixel(1, 1), Rgba([100, 0, 50, 0]));
+        assert_eq!(*texture.get_pixel(3, 0), Rgba([0, 0, 100, 0]));
+        // 第二圈来自第一圈的渗色结果，第三圈不处理
+        assert_eq!(*texture.get_pixel(4, 1), Rgba([0, 0, 100, 0]));
+        assert_eq!(*texture.get_pixel(5, 1), Rgba([0, 0, 0, 0]));
+
+        let mut empty = RgbaImage::new(2, 2);
+        bleed_alpha(&mut empty, 4);
+        assert_eq!(empty, RgbaImage::new(2, 2));
+        assert!(validate_bleed(MAX_BLEED_ITERATIONS + 1).is_err());
+    }
+}
@@ -0,0 +1,1495 @@
+//! 打包任务构建器 (Pack Job Builder)
+//!
+//! 供其他 Rust 工具直接嵌入 EzPlist 打包流程的公开 API，
+//! 串联透明裁剪、尺寸选择与 MaxRects 打包，不经过 Tauri 命令
+//!
+//! ```
+//! use ezplist_core::job::PackJob;
+//! use ezplist_core::packer::PackHeuristic;
+//! use image::{Rgba, RgbaImage};
+//!
+//! let output = PackJob::new()
+//!     .add_sprite("hero", "hero.png", RgbaImage::from_pixel(32, 48, Rgba([255, 0, 0, 255])))
+//!     .add_sprite("coin", "coin.png", RgbaImage::from_pixel(16, 16, Rgba([255, 255, 0, 255])))
+//!     .max_size(2048)
+//!     .heuristic(PackHeuristic::BestShortSideFit)
+//!     .run()
+//!     .unwrap();
+//!
+//! assert_eq!(output.result.packed_sprites.len(), 2);
+//! ```
+
+use crate::alpha_stats::{alpha_stats, alpha_warnings, AlphaStats};
+use crate::mipmap::{check_frame_size, required_padding};
+use crate::mirror::{find_mirrors, MirrorMatch};
+use crate::packer::{
+    find_optimal_size, pack_pages, MaxRectsPacker, PackHeuristic, PackerOptions, SizeConstraint, SortOrder, SpriteInput,
+};
+use crate::solid::SolidRegion;
+use crate::trim::{
+    centered_source_offset, is_below_alpha, is_fully_keyed, trim_alpha, trim_color_key_alpha, ColorKey, TrimMode,
+    TrimResult, DEFAULT_ALPHA_THRESHOLD,
+};
+use crate::timings::{Phase, Timings};
+use crate::types::{NineSlice, PackResult, PackedSprite};
+use image::RgbaImage;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Instant;
+
+/// 打包任务中的精灵来源
+#[derive(Debug, Clone)]
+enum JobSource {
+    /// 已解码的图像（可裁剪）
+    Image(RgbaImage),
+    /// 仅尺寸（无法加载图像时的占位）
+    Size(u32, u32),
+}
+
+/// 打包任务中的单个精灵
+#[derive(Debug, Clone)]
+struct JobSprite {
+    id: String,
+    name: String,
+    source: JobSource,
+    allow_rotation: bool,
+    color_key: Option<ColorKey>,
+    source_size: Option<(u32, u32)>,
+    page_group: Option<String>,
+    anchor: Option<(f32, f32)>,
+    nine_slice: Option<NineSlice>,
+}
+
+/// 完全透明精灵的处理策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EmptySpritePolicy {
+    /// 保留为 1x1 透明占位帧
+    #[default]
+    Placeholder,
+    /// 从图集中排除
+    Exclude,
+    /// 打包失败
+    Fail,
+}
+
+/// 打包错误
+#[derive(Debug, Clone, PartialEq)]
+pub enum PackError {
+    /// 没有精灵可打包
+    NoSprites,
+    /// 存在完全透明的精灵（空精灵策略为 `Fail` 时）
+    EmptySprites {
+        /// 精灵名称
+        names: Vec<String>,
+    },
+    /// 纹理尺寸不足，部分精灵无法放置
+    InsufficientSpace {
+        /// 已放置的精灵数
+        packed: usize,
+        /// 精灵总数
+        total: usize,
+    },
+    /// 单个精灵超过最大纹理尺寸（多页打包也无法放置）
+    SpriteTooLarge {
+        /// 精灵名称
+        name: String,
+        /// 裁剪后宽度
+        width: u32,
+        /// 裁剪后高度
+        height: u32,
+    },
+    /// 自定义源尺寸小于精灵图像
+    SourceSizeTooSmall {
+        /// 精灵名称
+        name: String,
+        /// 自定义源宽度
+        width: u32,
+        /// 自定义源高度
+        height: u32,
+    },
+    /// 精灵名称与注入的纯色区域重名
+    ReservedName {
+        /// 纯色区域名称
+        name: String,
+    },
+    /// 页面亲和分组的精灵单独也放不下一页
+    GroupTooLarge {
+        /// 分组名称
+        group: String,
+    },
+    /// 满足尺寸约束的纹理超过最大尺寸
+    ConstraintExceedsMaxSize {
+        /// 取整后的宽度
+        width: u32,
+        /// 取整后的高度
+        height: u32,
+    },
+}
+
+impl fmt::Display for PackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PackError::NoSprites => write!(f, "没有精灵可打包"),
+            PackError::EmptySprites { names } => {
+                write!(f, "以下精灵完全透明: {}", names.join(", "))
+            }
+            PackError::InsufficientSpace { packed, total } => write!(
+                f,
+                "纹理尺寸不足：只打包了 {}/{} 个精灵。请增大最大尺寸或减少精灵数量。",
+                packed, total
+            ),
+            PackError::SpriteTooLarge { name, width, height } => write!(
+                f,
+                "精灵 {} ({}x{}) 超过最大纹理尺寸，无法放入任何一页",
+                name, width, height
+            ),
+            PackError::SourceSizeTooSmall { name, width, height } => write!(
+                f,
+                "精灵 {} 的自定义源尺寸 {}x{} 小于图像尺寸",
+                name, width, height
+            ),
+            PackError::ReservedName { name } => {
+                write!(f, "精灵名称 {} 与注入的纯色区域重名，请重命名精灵", name)
+            }
+            PackError::GroupTooLarge { group } => write!(
+                f,
+                "分组 {} 的精灵单独也放不下一页，请增大最大尺寸或拆分分组",
+                group
+            ),
+            PackError::ConstraintExceedsMaxSize { width, height } => write!(
+                f,
+                "满足尺寸约束的纹理 {}x{} 超过最大纹理尺寸",
+                width, height
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PackError {}
+
+/// 打包任务输出
+#[derive(Debug)]
+pub struct PackOutput {
+    /// 打包结果（布局信息）
+    pub result: PackResult,
+    /// 裁剪结果（按精灵 ID 索引，用于后续渲染纹理；不裁剪时为整张图像）
+    pub trims: HashMap<String, TrimResult>,
+}
+
+/// 多页打包输出
+#[derive(Debug)]
+pub struct PagedPackOutput {
+    /// 每页的打包结果（页号从 0 开始）
+    pub pages: Vec<PackResult>,
+    /// 裁剪结果（按精灵 ID 索引，覆盖所有页）
+    pub trims: HashMap<String, TrimResult>,
+}
+
+/// 打包任务构建器
+#[derive(Debug, Clone)]
+pub struct PackJob {
+    sprites: Vec<JobSprite>,
+    max_width: u32,
+    max_height: u32,
+    trim_mode: TrimMode,
+    trim_margin: u32,
+    alpha_threshold: u8,
+    allow_rotation: bool,
+    padding: u32,
+    extrude: u32,
+    auto_size: bool,
+    size_constraint: SizeConstraint,
+    heuristic: PackHeuristic,
+    auto_heuristic: bool,
+    mipmap_levels: u32,
+    empty_policy: EmptySpritePolicy,
+    alias_mirrors: bool,
+    solid_regions: Vec<SolidRegion>,
+}
+
+impl Default for PackJob {
+    fn default() -> Self {
+        Self {
+            sprites: Vec::new(),
+            max_width: 2048,
+            max_height: 2048,
+            trim_mode: TrimMode::default(),
+            trim_margin: 0,
+            alpha_threshold: DEFAULT_ALPHA_THRESHOLD,
+            allow_rotation: true,
+            padding: 1,
+            extrude: 0,
+            auto_size: true,
+            size_constraint: SizeConstraint::default(),
+            heuristic: PackHeuristic::default(),
+            auto_heuristic: false,
+            mipmap_levels: 0,
+            empty_policy: EmptySpritePolicy::default(),
+            alias_mirrors: false,
+            solid_regions: Vec::new(),
+        }
+    }
+}
+
+impl PackJob {
+    /// 创建打包任务（默认 2048x2048、启用裁剪与旋转、间距 1、自动尺寸）
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加精灵图像
+    ///
+    /// # Arguments
+    /// * `id` - 精灵唯一标识
+    /// * `name` - 帧名称
+    /// * `image` - 已解码的 RGBA 图像
+    pub fn add_sprite(mut self, id: impl Into<String>, name: impl Into<String>, image: RgbaImage) -> Self {
+        self.sprites.push(JobSprite {
+            id: id.into(),
+            name: name.into(),
+            source: JobSource::Image(image),
+            allow_rotation: true,
+            color_key: None,
+            source_size: None,
+            page_group: None,
+            anchor: None,
+            nine_slice: None,
+        });
+        self
+    }
+
+    /// 添加仅有尺寸的精灵（不参与裁剪）
+    pub fn add_sprite_size(
+        mut self,
+        id: impl Into<String>,
+        name: impl Into<String>,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        self.sprites.push(JobSprite {
+            id: id.into(),
+            name: name.into(),
+            source: JobSource::Size(width, height),
+            allow_rotation: true,
+            color_key: None,
+            source_size: None,
+            page_group: None,
+            anchor: None,
+            nine_slice: None,
+        });
+        self
+    }
+
+    /// 设置单个精灵是否允许旋转（已添加的精灵，整体禁止旋转时无效）
+    pub fn allow_sprite_rotation(mut self, id: &str, allow: bool) -> Self {
+        for sprite in self.sprites.iter_mut().filter(|s| s.id == id) {
+            sprite.allow_rotation = allow;
+        }
+        self
+    }
+
+    /// 设置单个精灵按颜色键裁剪（已添加的精灵，仅在启用裁剪时生效）
+    pub fn sprite_color_key(mut self, id: &str, key: ColorKey) -> Self {
+        for sprite in self.sprites.iter_mut().filter(|s| s.id == id) {
+            sprite.color_key = Some(key);
+        }
+        self
+    }
+
+    /// 设置单个精灵的虚拟源尺寸（sourceSize 覆盖，已添加的精灵）
+    ///
+    /// 原图居中放入虚拟尺寸并重新计算偏移，使尺寸不同的动画帧保持一致的锚点；
+    /// 虚拟尺寸小于图像时 `run` 返回 `PackError::SourceSizeTooSmall`
+    pub fn sprite_source_size(mut self, id: &str, width: u32, height: u32) -> Self {
+        for sprite in self.sprites.iter_mut().filter(|s| s.id == id) {
+            sprite.source_size = Some((width, height));
+        }
+        self
+    }
+
+    /// 设置单个精灵的锚点（相对原图的 0~1 坐标，Y 轴向上，已添加的精灵）
+    ///
+    /// 输出时换算到帧的 sourceSize：`Trim` 模式下为裁剪后的内容，设置了虚拟源尺寸时为虚拟格子
+    pub fn sprite_anchor(mut self, id: &str, anchor: (f32, f32)) -> Self {
+        for sprite in self.sprites.iter_mut().filter(|s| s.id == id) {
+            sprite.anchor = Some(anchor);
+        }
+        self
+    }
+
+    /// 设置单个精灵的九宫格边距（相对原图，已添加的精灵）
+    ///
+    /// 输出时按实际裁剪区域换算到帧内，被裁掉的透明边缘从对应边距中扣除
+    pub fn sprite_nine_slice(mut self, id: &str, nine_slice: NineSlice) -> Self {
+        for sprite in self.sprites.iter_mut().filter(|s| s.id == id) {
+            sprite.nine_slice = Some(nine_slice);
+        }
+        self
+    }
+
+    /// 设置单个精灵的页面亲和分组（已添加的精灵，仅影响多页打包）
+    ///
+    /// 同一分组的精灵（如一段动画的所有帧）总在同一页，分组单独也放不下一页时
+    /// `run_pages` 返回 `PackError::GroupTooLarge`；翻转帧跟随源帧所在的页
+    pub fn sprite_page_group(mut self, id: &str, group: impl Into<String>) -> Self {
+        let group = group.into();
+        for sprite in self.sprites.iter_mut().filter(|s| s.id == id) {
+            sprite.page_group = Some(group.clone());
+        }
+        self
+    }
+
+    /// 设置最大纹理尺寸（宽高相同）
+    pub fn max_size(self, size: u32) -> Self {
+        self.max_dimensions(size, size)
+    }
+
+    /// 分别设置最大纹理宽高
+    pub fn max_dimensions(mut self, width: u32, height: u32) -> Self {
+        self.max_width = width;
+        self.max_height = height;
+        self
+    }
+
+    /// 是否启用透明裁剪（启用时保留原始尺寸，等同 `TrimMode::CropKeepSize`）
+    pub fn trim(mut self, trim: bool) -> Self {
+        self.trim_mode = if trim { TrimMode::CropKeepSize } else { TrimMode::None };
+        self
+    }
+
+    /// 设置裁剪模式（默认 `TrimMode::CropKeepSize`）
+    pub fn trim_mode(mut self, mode: TrimMode) -> Self {
+        self.trim_mode = mode;
+        self
+    }
+
+    /// 裁剪时的 alpha 阈值：不大于此值的像素视为透明（默认 `DEFAULT_ALPHA_THRESHOLD`）
+    pub fn alpha_threshold(mut self, threshold: u8) -> Self {
+        self.alpha_threshold = threshold;
+        self
+    }
+
+    /// 裁剪时在内容四周保留的透明像素数（默认 0，即裁剪到最后一个不透明像素）
+    pub fn trim_margin(mut self, margin: u32) -> Self {
+        self.trim_margin = margin;
+        self
+    }
+
+    /// 是否允许旋转
+    pub fn allow_rotation(mut self, allow: bool) -> Self {
+        self.allow_rotation = allow;
+        self
+    }
+
+    /// 设置精灵间距
+    pub fn padding(mut self, padding: u32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// 是否自动选择最优纹理尺寸
+    pub fn auto_size(mut self, auto_size: bool) -> Self {
+        self.auto_size = auto_size;
+        self
+    }
+
+    /// 设置边缘挤出像素
+    ///
+    /// 每个精灵四周额外预留的像素，渲染纹理时用精灵的边缘像素填充，
+    /// 避免线性过滤时采样到相邻精灵
+    pub fn extrude(mut self, extrude: u32) -> Self {
+        self.extrude = extrude;
+        self
+    }
+
+    /// 设置纹理尺寸约束
+    ///
+    /// 自动尺寸时纹理取内容边界并按约束向上取整，固定尺寸时不生效
+    pub fn size_constraint(mut self, constraint: SizeConstraint) -> Self {
+        self.size_constraint = constraint;
+        self
+    }
+
+    /// 设置打包启发式策略
+    pub fn heuristic(mut self, heuristic: PackHeuristic) -> Self {
+        self.heuristic = heuristic;
+        self
+    }
+
+    /// 是否自动选择启发式策略
+    ///
+    /// 依次尝试所有启发式策略与放置顺序，单页时保留纹理面积最小（其次填充率最高）的结果，
+    /// 多页时保留页数最少的结果；启用后忽略 `heuristic`
+    pub fn auto_heuristic(mut self, auto: bool) -> Self {
+        self.auto_heuristic = auto;
+        self
+    }
+
+    /// 设置 mipmap 级数
+    ///
+    /// 间距会自动增大到所有级别都安全的值，帧尺寸不能被缩小倍数整除时输出警告
+    pub fn mipmap_levels(mut self, levels: u32) -> Self {
+        self.mipmap_levels = levels;
+        self
+    }
+
+    /// 设置完全透明精灵的处理策略（仅在启用裁剪时检测）
+    pub fn empty_sprites(mut self, policy: EmptySpritePolicy) -> Self {
+        self.empty_policy = policy;
+        self
+    }
+
+    /// 设置是否把翻转帧合并到源帧（仅在启用裁剪时检测）
+    ///
+    /// 裁剪后的图像是前面某个精灵水平/垂直翻转结果的精灵不参与排布，
+    /// 输出时引用源帧的纹理区域并设置 `flip_x` / `flip_y`
+    pub fn alias_mirrors(mut self, alias: bool) -> Self {
+        self.alias_mirrors = alias;
+        self
+    }
+
+    /// 在每页图集中注入纯色区域（如 `__white`）
+    ///
+    /// 区域不裁剪、不旋转，以名称作为精灵 ID；多页打包时每页底部预留一条放置区域的空间
+    pub fn solid_region(mut self, region: SolidRegion) -> Self {
+        self.solid_regions.push(region);
+        self
+    }
+
+    /// 执行打包
+    ///
+    /// # Returns
+    /// * `Result<PackOutput, PackError>` - 打包结果与裁剪数据
+    pub fn run(self) -> Result<PackOutput, PackError> {
+        let mut timings = Timings::default();
+        let prepared = timings.time(Phase::Trim, || self.prepare())?;
+        let inputs: Vec<SpriteInput> = prepared.inputs.iter().chain(&prepared.solids).cloned().collect();
+        let page = timings.time(Phase::Pack, || self.pack_page(&inputs))?;
+        let all: Vec<&SpriteInput> = inputs.iter().collect();
+        let mut result = prepared.page_result(page, &all, true);
+        result.timings = timings;
+        Ok(PackOutput {
+            result,
+            trims: prepared.trims,
+        })
+    }
+
+    /// 执行多页打包
+    ///
+    /// 一页放不下的精灵依次放入新的一页，每页的尺寸上限都为最大纹理尺寸，
+    /// 启用自动尺寸时每页单独选择最优尺寸；同一页面亲和分组的精灵总在同一页
+    ///
+    /// # Returns
+    /// * `Result<PagedPackOutput, PackError>` - 每页的打包结果与裁剪数据；
+    ///   某个精灵单独也放不下一页时返回 `SpriteTooLarge`，某个分组放不下时返回 `GroupTooLarge`
+    pub fn run_pages(self) -> Result<PagedPackOutput, PackError> {
+        let mut timings = Timings::default();
+        let prepared = timings.time(Phase::Trim, || self.prepare())?;
+        let pack_start = Instant::now();
+        // 每页底部预留纯色区域所在的条带
+        let strip = self.solid_strip_height(&prepared);
+        let affinity = self.page_affinity();
+        let pages = self
+            .packer_candidates(prepared.padding)
+            .iter()
+            .filter_map(|options| {
+                pack_pages(&prepared.inputs, &affinity, self.max_width, self.max_height.saturating_sub(strip), options)
+            })
+            .min_by_key(Vec::len)
+            .ok_or_else(|| self.too_large_error(&prepared, &affinity))?;
+
+        let mut results = Vec::with_capacity(pages.len());
+        for (index, page) in pages.iter().enumerate() {
+            let page_inputs: Vec<SpriteInput> = prepared
+                .inputs
+                .iter()
+                .filter(|input| page.iter().any(|s| s.id == input.id))
+                .chain(&prepared.solids)
+                .cloned()
+                .collect();
+            // 自动尺寸重新打包失败时沿用多页打包的布局，纯色区域放入预留条带
+            let packed = match self.auto_size.then(|| self.pack_page(&page_inputs).ok()).flatten() {
+                Some(packed) => packed,
+                None => {
+                    let mut sprites = page.clone();
+                    sprites.extend(self.pack_solid_strip(&prepared, strip)?);
+                    PagePacking::from_sprites(sprites, self.max_width, self.max_height)
+                }
+            };
+            let refs: Vec<&SpriteInput> = page_inputs.iter().collect();
+            results.push(prepared.page_result(packed, &refs, index == 0));
+        }
+        timings.add(Phase::Pack, pack_start.elapsed());
+        for result in &mut results {
+            result.timings = timings;
+        }
+
+        Ok(PagedPackOutput {
+            pages: results,
+            trims: prepared.trims,
+        })
+    }
+
+    /// 裁剪、检测空精灵并生成打包输入
+    fn prepare(&self) -> Result<PreparedSprites, PackError> {
+        if self.sprites.is_empty() {
+            return Err(PackError::NoSprites);
+        }
+
+        let mut trims = HashMap::new();
+        let mut stats: HashMap<String, AlphaStats> = HashMap::new();
+        let mut sprite_inputs: Vec<SpriteInput> = Vec::with_capacity(self.sprites.len());
+        let mut anchors: HashMap<String, (f32, f32)> = HashMap::new();
+        let mut nine_slices: HashMap<String, NineSlice> = HashMap::new();
+        let mut empty_sprites: Vec<&JobSprite> = Vec::new();
+        let trim = self.trim_mode != TrimMode::None;
+
+        for sprite in &self.sprites {
+            // 完全透明的图像裁剪后只剩 1x1，按策略记录或排除
+            if let JobSource::Image(image) = &sprite.source {
+                let empty = match &sprite.color_key {
+                    Some(key) => is_fully_keyed(image, key, self.alpha_threshold),
+                    None => is_below_alpha(image, self.alpha_threshold),
+                };
+                if trim && empty {
+                    empty_sprites.push(sprite);
+                    if self.empty_policy == EmptySpritePolicy::Exclude {
+                        continue;
+                    }
+                }
+            }
+
+            let (input, mut placement) = match &sprite.source {
+                JobSource::Image(image) if trim => {
+                    let trim_result = match &sprite.color_key {
+                        Some(key) => trim_color_key_alpha(image, key, self.trim_margin, self.alpha_threshold),
+                        None => trim_alpha(image, self.trim_margin, self.alpha_threshold),
+                    };
+                    let placement = SourcePlacement::trimmed(&trim_result, self.trim_mode);
+                    let mut trim_result = trim_result.with_mode(self.trim_mode);
+                    if let Some((width, height)) = sprite.source_size {
+                        trim_result = trim_result
+                            .with_source_size(width, height)
+                            .ok_or_else(|| source_size_error(sprite, width, height))?;
+                    }
+                    let input = SpriteInput {
+                        id: sprite.id.clone(),
+                        name: sprite.name.clone(),
+                        width: trim_result.trimmed_width,
+                        height: trim_result.trimmed_height,
+                        original_width: trim_result.original_width,
+                        original_height: trim_result.original_height,
+                        offset_x: trim_result.offset_x,
+                        offset_y: trim_result.offset_y,
+                        trimmed: trim_result.trimmed_width != trim_result.original_width
+                            || trim_result.trimmed_height != trim_result.original_height,
+                        allow_rotation: true,
+                    };
+                    stats.insert(sprite.id.clone(), alpha_stats(&trim_result.trimmed_image));
+                    trims.insert(sprite.id.clone(), trim_result);
+                    (input, placement)
+                }
+                JobSource::Image(image) => {
+                    stats.insert(sprite.id.clone(), alpha_stats(image));
+                    trims.insert(sprite.id.clone(), TrimResult::untrimmed(image.clone()));
+                    let input = untrimmed_input(&sprite.id, &sprite.name, image.width(), image.height());
+                    (input, SourcePlacement::whole(image.dimensions()))
+                }
+                JobSource::Size(width, height) => {
+                    let input = untrimmed_input(&sprite.id, &sprite.name, *width, *height);
+                    (input, SourcePlacement::whole((*width, *height)))
+                }
+            };
+            if let Some(size) = sprite.source_size {
+                placement = placement.centered(size);
+            }
+            if let Some(anchor) = sprite.anchor {
+                anchors.insert(sprite.id.clone(), placement.anchor(anchor));
+            }
+            if let Some(nine_slice) = &sprite.nine_slice {
+                nine_slices.insert(sprite.id.clone(), placement.nine_slice(nine_slice));
+            }
+            let mut input = SpriteInput {
+                allow_rotation: sprite.allow_rotation,
+                ..input
+            };
+            // 未裁剪的精灵整张图像即帧内容，同样居中放入虚拟源尺寸
+            let trimmed = trim && matches!(sprite.source, JobSource::Image(_));
+            if let (Some((width, height)), false) = (sprite.source_size, trimmed) {
+                let bounds = (0, 0, input.width, input.height);
+                let (offset_x, offset_y) =
+                    centered_source_offset(bounds, (input.width, input.height), (width, height))
+                        .ok_or_else(|| source_size_error(sprite, width, height))?;
+                input = SpriteInput {
+                    original_width: width,
+                    original_height: height,
+                    offset_x,
+                    offset_y,
+                    trimmed: (width, height) != (input.width, input.height),
+                    ..input
+                };
+            }
+            sprite_inputs.push(input);
+        }
+
+        if self.empty_policy == EmptySpritePolicy::Fail && !empty_sprites.is_empty() {
+            return Err(PackError::EmptySprites {
+                names: empty_sprites.iter().map(|s| s.name.clone()).collect(),
+            });
+        }
+        if sprite_inputs.is_empty() {
+            return Err(PackError::NoSprites);
+        }
+
+        // 翻转帧不参与排布，输出时引用源帧
+        let mut mirrors = Vec::new();
+        if self.alias_mirrors {
+            let images: Vec<(&str, &RgbaImage)> = sprite_inputs
+                .iter()
+                .filter_map(|input| trims.get(&input.id).map(|trim| (input.id.as_str(), &trim.trimmed_image)))
+                .collect();
+            let matches = find_mirrors(&images);
+            sprite_inputs.retain(|input| match matches.iter().find(|m| m.id == input.id) {
+                Some(found) => {
+                    mirrors.push((input.clone(), found.clone()));
+                    false
+                }
+                None => true,
+            });
+        }
+
+        // 纯色区域不裁剪，导出时直接使用整张图像
+        let mut solids = Vec::with_capacity(self.solid_regions.len());
+        for region in &self.solid_regions {
+            if self.sprites.iter().any(|s| s.name == region.name || s.id == region.name) {
+                return Err(PackError::ReservedName {
+                    name: region.name.clone(),
+                });
+            }
+            trims.insert(region.name.clone(), TrimResult::untrimmed(region.image()));
+            solids.push(SpriteInput {
+                allow_rotation: false,
+                ..untrimmed_input(&region.name, &region.name, region.size, region.size)
+            });
+        }
+
+        let empty_ids: Vec<String> = empty_sprites.iter().map(|s| s.id.clone()).collect();
+        let mut warnings: HashMap<String, Vec<String>> = HashMap::new();
+        for input in &sprite_inputs {
+            let mut sprite_warnings: Vec<String> =
+                check_frame_size(&input.name, input.width, input.height, self.mipmap_levels)
+                    .into_iter()
+                    .collect();
+            // 完全透明的精灵已单独报告，不再重复警告
+            if let Some(stats) = stats.get(&input.id).filter(|_| !empty_ids.contains(&input.id)) {
+                sprite_warnings.extend(alpha_warnings(&input.name, stats));
+            }
+            warnings.insert(input.id.clone(), sprite_warnings);
+        }
+
+        Ok(PreparedSprites {
+            inputs: sprite_inputs,
+            trims,
+            stats,
+            warnings,
+            empty_ids,
+            mirrors,
+            solids,
+            anchors,
+            nine_slices,
+            padding: self.padding.max(required_padding(self.mipmap_levels)),
+        })
+    }
+
+    /// 打包器设置候选（自动选择启发式策略时为所有策略与放置顺序的组合）
+    fn packer_candidates(&self, padding: u32) -> Vec<PackerOptions> {
+        let base = PackerOptions {
+            allow_rotation: self.allow_rotation,
+            padding,
+            extrude: self.extrude,
+            heuristic: self.heuristic,
+            sort: SortOrder::default(),
+        };
+        if !self.auto_heuristic {
+            return vec![base];
+        }
+        PackHeuristic::ALL
+            .into_iter()
+            .flat_map(|heuristic| SortOrder::ALL.into_iter().map(move |sort| PackerOptions { heuristic, sort, ..base }))
+            .collect()
+    }
+
+    /// 把一组精灵打包到一页（自动尺寸时选择能容纳全部精灵的最小尺寸）
+    fn pack_page(&self, inputs: &[SpriteInput]) -> Result<PagePacking, PackError> {
+        let padding = self.padding.max(required_padding(self.mipmap_levels));
+        let mut best: Option<PagePacking> = None;
+        let mut first_error = None;
+        for options in self.packer_candidates(padding) {
+            match self.pack_page_with(inputs, &options) {
+                Ok(page) => {
+                    if best.as_ref().is_none_or(|current| page.is_better_than(current)) {
+                        best = Some(page);
+                    }
+                }
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        match (best, first_error) {
+            (Some(page), _) => Ok(page),
+            (None, Some(e)) => Err(e),
+            (None, None) => Err(PackError::NoSprites),
+        }
+    }
+
+    /// 按指定的打包器设置打包一页
+    fn pack_page_with(&self, inputs: &[SpriteInput], options: &PackerOptions) -> Result<PagePacking, PackError> {
+        let (tex_width, tex_height) = if self.auto_size {
+            find_optimal_size(inputs, self.max_width.max(self.max_height), options)
+                .unwrap_or((self.max_width, self.max_height))
+        } else {
+            (self.max_width, self.max_height)
+        };
+
+        let mut packer = MaxRectsPacker::with_options(tex_width, tex_height, options);
+        let packed_sprites = packer.pack(inputs);
+
+        if packed_sprites.len() != inputs.len() {
+            return Err(PackError::InsufficientSpace {
+                packed: packed_sprites.len(),
+                total: inputs.len(),
+            });
+        }
+
+        let (actual_width, actual_height) = packer.actual_bounds();
+        let (width, height) = if self.auto_size {
+            packer.constrained_bounds(self.size_constraint)
+        } else {
+            (tex_width, tex_height)
+        };
+        if width > self.max_width || height > self.max_height {
+            return Err(PackError::ConstraintExceedsMaxSize { width, height });
+        }
+        Ok(PagePacking {
+            fill_rate: calculate_fill_rate(&packed_sprites, actual_width, actual_height),
+            sprites: packed_sprites,
+            width,
+            height,
+        })
+    }
+
+    /// 纯色区域条带的高度（没有纯色区域时为 0）
+    fn solid_strip_height(&self, prepared: &PreparedSprites) -> u32 {
+        prepared
+            .solids
+            .iter()
+            .map(|solid| solid.height)
+            .max()
+            .map_or(0, |height| height + prepared.padding + self.extrude * 2)
+    }
+
+    /// 把纯色区域放入页面底部的预留条带（固定尺寸时使用）
+    fn pack_solid_strip(&self, prepared: &PreparedSprites, strip: u32) -> Result<Vec<PackedSprite>, PackError> {
+        if prepared.solids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let options = PackerOptions {
+            allow_rotation: false,
+            padding: prepared.padding,
+            extrude: self.extrude,
+            ..Default::default()
+        };
+        let mut packer = MaxRectsPacker::with_options(self.max_width, strip, &options);
+        let placed = packer.pack(&prepared.solids);
+        if placed.len() != prepared.solids.len() {
+            return Err(PackError::InsufficientSpace {
+                packed: placed.len(),
+                total: prepared.solids.len(),
+            });
+        }
+        let top = self.max_height - strip;
+        Ok(placed.into_iter().map(|sprite| PackedSprite { y: sprite.y + top, ..sprite }).collect())
+    }
+
+    /// 精灵 ID 到页面亲和分组的映射
+    fn page_affinity(&self) -> HashMap<String, String> {
+        self.sprites
+            .iter()
+            .filter_map(|sprite| Some((sprite.id.clone(), sprite.page_group.clone()?)))
+            .collect()
+    }
+
+    /// 找出单独也放不下一页的精灵或页面亲和分组
+    fn too_large_error(&self, prepared: &PreparedSprites, affinity: &HashMap<String, String>) -> PackError {
+        let options = PackerOptions {
+            allow_rotation: self.allow_rotation,
+            padding: prepared.padding,
+            extrude: self.extrude,
+            ..Default::default()
+        };
+        let fits = |inputs: &[SpriteInput]| {
+            let mut packer = MaxRectsPacker::with_options(self.max_width, self.max_height, &options);
+            packer.pack(inputs).len() == inputs.len()
+        };
+        if let Some(input) = prepared.inputs.iter().find(|input| !fits(std::slice::from_ref(*input))) {
+            return PackError::SpriteTooLarge {
+                name: input.name.clone(),
+                width: input.width,
+                height: input.height,
+            };
+        }
+        let mut groups: Vec<&String> = affinity.values().collect();
+        groups.sort();
+        groups.dedup();
+        let too_large = groups.into_iter().find(|group| {
+            let members: Vec<SpriteInput> = prepared
+                .inputs
+                .iter()
+                .filter(|input| affinity.get(&input.id) == Some(*group))
+                .cloned()
+                .collect();
+            !fits(&members)
+        });
+        match too_large {
+            Some(group) => PackError::GroupTooLarge { group: group.clone() },
+            None => PackError::InsufficientSpace {
+                packed: 0,
+                total: prepared.inputs.len(),
+            },
+        }
+    }
+}
+
+/// 裁剪后等待打包的精灵
+struct PreparedSprites {
+    inputs: Vec<SpriteInput>,
+    trims: HashMap<String, TrimResult>,
+    stats: HashMap<String, AlphaStats>,
+    /// 每个精灵的警告（按精灵 ID 索引）
+    warnings: HashMap<String, Vec<String>>,
+    /// 完全透明的精灵 ID（包括已排除的）
+    empty_ids: Vec<String>,
+    /// 不参与排布的翻转帧及其源帧
+    mirrors: Vec<(SpriteInput, MirrorMatch)>,
+    /// 每页都注入的纯色区域
+    solids: Vec<SpriteInput>,
+    /// 换算到 sourceSize 的锚点（按精灵 ID 索引）
+    anchors: HashMap<String, (f32, f32)>,
+    /// 换算到帧内的九宫格边距（按精灵 ID 索引）
+    nine_slices: HashMap<String, NineSlice>,
+    /// 实际使用的间距（已按 mipmap 增大）
+    padding: u32,
+}
+
+impl PreparedSprites {
+    /// 生成一页的打包结果，只包含该页精灵的警告与统计
+    ///
+    /// `first_page` 为 true 时额外报告已排除（不在任何一页中）的空精灵
+    fn page_result(&self, page: PagePacking, inputs: &[&SpriteInput], first_page: bool) -> PackResult {
+        let on_page = |id: &String| inputs.iter().any(|input| &input.id == id);
+        PackResult {
+            warnings: inputs
+                .iter()
+                .flat_map(|input| self.warnings.get(&input.id).into_iter().flatten().cloned())
+                .collect(),
+            empty_sprites: self
+                .empty_ids
+                .iter()
+                .filter(|id| on_page(id) || (first_page && !self.inputs.iter().any(|input| &input.id == *id)))
+                .cloned()
+                .collect(),
+            alpha_stats: self
+                .stats
+                .iter()
+                .filter(|(id, _)| on_page(id))
+                .map(|(id, stats)| (id.clone(), stats.clone()))
+                .collect(),
+            packed_sprites: self.with_frame_metadata(self.with_mirrors(page.sprites)),
+            texture_width: page.width,
+            texture_height: page.height,
+            fill_rate: page.fill_rate,
+            job_id: String::new(),
+            timings: Timings::default(),
+            summary: String::new(),
+            renamed_frames: Vec::new(),
+        }
+    }
+
+    /// 填写各帧换算后的锚点与九宫格边距（翻转帧使用自己的值）
+    fn with_frame_metadata(&self, mut sprites: Vec<PackedSprite>) -> Vec<PackedSprite> {
+        for sprite in &mut sprites {
+            sprite.anchor = self.anchors.get(&sprite.id).copied();
+            sprite.nine_slice = self.nine_slices.get(&sprite.id).copied();
+        }
+        sprites
+    }
+
+    /// 在源帧所在的页追加翻转帧：纹理区域与旋转取自源帧，原始尺寸与偏移为翻转帧自己的
+    fn with_mirrors(&self, mut sprites: Vec<PackedSprite>) -> Vec<PackedSprite> {
+        for (input, mirror) in &self.mirrors {
+            if let Some(source) = sprites.iter().find(|s| s.id == mirror.source_id).cloned() {
+                sprites.push(PackedSprite {
+                    id: input.id.clone(),
+                    name: input.name.clone(),
+                    original_width: input.original_width,
+                    original_height: input.original_height,
+                    trimmed: input.trimmed,
+                    offset_x: input.offset_x,
+                    offset_y: input.offset_y,
+                    flip_x: mirror.flip_x,
+                    flip_y: mirror.flip_y,
+                    ..source
+                });
+            }
+        }
+        sprites
+    }
+}
+
+/// 一页的布局
+struct PagePacking {
+    sprites: Vec<PackedSprite>,
+    width: u32,
+    height: u32,
+    fill_rate: f32,
+}
+
+impl PagePacking {
+    /// 纹理面积更小，或面积相同而填充率更高
+    fn is_better_than(&self, other: &PagePacking) -> bool {
+        let area = |page: &PagePacking| page.width as u64 * page.height as u64;
+        area(self) < area(other) || (area(self) == area(other) && self.fill_rate > other.fill_rate)
+    }
+
+    /// 使用已有布局（固定尺寸时直接采用多页打包的结果）
+    fn from_sprites(sprites: Vec<PackedSprite>, width: u32, height: u32) -> Self {
+        let right = sprites.iter().map(|s| s.x + s.width).max().unwrap_or(0);
+        let bottom = sprites.iter().map(|s| s.y + s.height).max().unwrap_or(0);
+        Self {
+            fill_rate: calculate_fill_rate(&sprites, right, bottom),
+            sprites,
+            width,
+            height,
+        }
+    }
+}
+
+/// 帧内容与原图在输出的 sourceSize 中的位置，用于把相对原图的锚点与九宫格边距换算到帧上
+#[derive(Debug, Clone, Copy)]
+struct SourcePlacement {
+    /// 原图尺寸
+    image: (u32, u32),
+    /// 帧内容 (left, top, right, bottom)，原图坐标
+    bounds: (u32, u32, u32, u32),
+    /// 原图左上角在 sourceSize 中的位置（`Trim` 模式下为负的裁剪起点）
+    origin: (i64, i64),
+    /// 输出的 sourceSize
+    source: (u32, u32),
+}
+
+impl SourcePlacement {
+    /// sourceSize 即原图
+    fn whole(image: (u32, u32)) -> Self {
+        Self {
+            image,
+            bounds: (0, 0, image.0, image.1),
+            origin: (0, 0),
+            source: image,
+        }
+    }
+
+    /// 按裁剪模式确定 sourceSize（`with_mode` 之前的裁剪结果）：`Trim` 模式下为裁剪后的内容
+    fn trimmed(trim: &TrimResult, mode: TrimMode) -> Self {
+        let placement = Self {
+            bounds: trim.trim_bounds,
+            ..Self::whole((trim.original_width, trim.original_height))
+        };
+        match mode {
+            TrimMode::Trim => {
+                let (left, top, right, bottom) = trim.trim_bounds;
+                Self {
+                    origin: (-(left as i64), -(top as i64)),
+                    source: (right - left, bottom - top),
+                    ..placement
+                }
+            }
+            TrimMode::CropKeepSize | TrimMode::None => placement,
+        }
+    }
+
+    /// 居中放入虚拟源尺寸（与 `centered_source_offset` 相同，多出的 1 像素放在右/下侧）
+    fn centered(self, source: (u32, u32)) -> Self {
+        let pad_x = source.0.saturating_sub(self.source.0) / 2;
+        let pad_y = source.1.saturating_sub(self.source.1) / 2;
+        Self {
+            origin: (self.origin.0 + pad_x as i64, self.origin.1 + pad_y as i64),
+            source,
+            ..self
+        }
+    }
+
+    /// 把相对原图的锚点（Y 轴向上）换算为相对 sourceSize
+    fn anchor(&self, (x, y): (f32, f32)) -> (f32, f32) {
+        let (width, height) = (self.image.0 as f64, self.image.1 as f64);
+        let (source_width, source_height) = (self.source.0.max(1) as f64, self.source.1.max(1) as f64);
+        // 原图底边到 sourceSize 底边的距离
+        let bottom = source_height - (self.origin.1 as f64 + height);
+        (
+            ((x as f64 * width + self.origin.0 as f64) / source_width) as f32,
+            ((y as f64 * height + bottom) / source_height) as f32,
+        )
+    }
+
+    /// 把相对原图的九宫格边距换算为帧内容内的边距
+    fn nine_slice(&self, nine_slice: &NineSlice) -> NineSlice {
+        let (left, top, right, bottom) = self.bounds;
+        nine_slice.trimmed(self.image, (left, top, right - left, bottom - top))
+    }
+}
+
+/// 自定义源尺寸过小的错误
+fn source_size_error(sprite: &JobSprite, width: u32, height: u32) -> PackError {
+    PackError::SourceSizeTooSmall {
+        name: sprite.name.clone(),
+        width,
+        height,
+    }
+}
+
+/// 构建未裁剪的打包输入
+fn untrimmed_input(id: &str, name: &str, width: u32, height: u32) -> SpriteInput {
+    SpriteInput {
+        id: id.to_string(),
+        name: name.to_string(),
+        width,
+        height,
+        original_width: width,
+        original_height: height,
+        offset_x: 0,
+        offset_y: 0,
+        trimmed: false,
+        allow_rotation: true,
+    }
+}
+
+/// 计算填充率（相对实际使用边界）
+pub fn calculate_fill_rate(sprites: &[PackedSprite], width: u32, height: u32) -> f32 {
+    let total_area = width as u64 * height as u64;
+    if total_area == 0 {
+        return 0.0;
+    }
+
+    let used_area: u64 = sprites.iter().map(|s| s.width as u64 * s.height as u64).sum();
+    (used_area as f32 / total_area as f32) * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn test_empty_job() {
+        assert_eq!(PackJob::new().run().unwrap_err(), PackError::NoSprites);
+    }
+
+    #[test]
+    fn test_trim_results_keyed_by_id() {
+        let mut image = RgbaImage::new(10, 10);
+        image.put_pixel(4, 4, Rgba([255, 255, 255, 255]));
+
+        let output = PackJob::new()
+            .add_sprite("a", "a.png", image)
+            .add_sprite_size("b", "b.png", 8, 8)
+            .run()
+            .unwrap();
+
+        assert_eq!(output.result.packed_sprites.len(), 2);
+        assert!(output.result.packed_sprites[0].trimmed);
+        assert_eq!(output.trims["a"].trimmed_width, 1);
+        assert!(!output.trims.contains_key("b"));
+    }
+
+    #[test]
+    fn test_trim_margin() {
+        let mut img = RgbaImage::new(20, 20);
+        for y in 8..12 {
+            for x in 8..12 {
+                img.put_pixel(x, y, Rgba([255, 0, 0, 255]));
+            }
+        }
+        let job = PackJob::new().add_sprite("a", "a.png", img);
+        let exact = job.clone().run().unwrap();
+        assert_eq!(exact.result.packed_sprites[0].width, 4);
+
+        let output = job.trim_margin(3).run().unwrap();
+        let sprite = &output.result.packed_sprites[0];
+        assert_eq!((sprite.width, sprite.height, sprite.offset_x, sprite.offset_y), (10, 10, 0, 0));
+        assert_eq!(output.trims["a"].trim_bounds, (5, 5, 15, 15));
+    }
+
+    #[test]
+    fn test_trim_modes_in_plist() {
+        use crate::atlas_plist::parse_atlas_plist;
+        use crate::hash::SmartUpdate;
+        use crate::plist_generator::{generate_plist_data, PlistFormat};
+        use crate::texture_format::PixelFormat;
+
+        // 20x10 图像：不透明内容在 (2,2)-(8,6)，右侧 (8,2)-(12,6) 为 alpha 30 的阴影
+        let mut img = RgbaImage::new(20, 10);
+        for y in 2..6 {
+            for x in 2..12 {
+                img.put_pixel(x, y, Rgba([0, 0, 0, if x < 8 { 255 } else { 30 }]));
+            }
+        }
+        let frame = |job: PackJob| {
+            let output = job.add_sprite("a", "a.png", img.clone()).run().unwrap();
+            let plist = generate_plist_data(&output.result.packed_sprites, 64, 64, "a.png", PlistFormat::XmlV3, PixelFormat::Rgba8888, SmartUpdate::default()).unwrap();
+            let frame = parse_atlas_plist(&plist).unwrap().frame("a.png").unwrap().clone();
+            (frame.width, frame.height, frame.original_width, frame.original_height, frame.offset_x, frame.offset_y)
+        };
+
+        assert_eq!(frame(PackJob::new()), (10, 4, 20, 10, -3, 1));
+        assert_eq!(frame(PackJob::new().alpha_threshold(30)), (6, 4, 20, 10, -5, 1));
+        assert_eq!(frame(PackJob::new().trim_mode(TrimMode::Trim).alpha_threshold(30)), (6, 4, 6, 4, 0, 0));
+        assert_eq!(frame(PackJob::new().trim_mode(TrimMode::None)), (20, 10, 20, 10, 0, 0));
+        // 阈值以下的图像按空精灵处理
+        let faint = PackJob::new().alpha_threshold(30).empty_sprites(EmptySpritePolicy::Exclude);
+        assert!(faint.add_sprite("f", "f.png", RgbaImage::from_pixel(4, 4, Rgba([0, 0, 0, 30]))).run().is_err());
+    }
+
+    #[test]
+    fn test_color_key_trim() {
+        let mut image = RgbaImage::from_pixel(10, 10, Rgba([255, 0, 255, 255]));
+        image.put_pixel(4, 4, Rgba([0, 0, 0, 255]));
+        let key = ColorKey { color: [255, 0, 255], tolerance: 0, to_transparent: true };
+
+        let output = PackJob::new()
+            .add_sprite("a", "a.png", image.clone())
+            .add_sprite("b", "b.png", image)
+            .sprite_color_key("a", key)
+            .run()
+            .unwrap();
+
+        assert_eq!(output.trims["a"].trimmed_width, 1);
+        assert_eq!(output.trims["b"].trimmed_width, 10);
+    }
+
+    #[test]
+    fn test_insufficient_space() {
+        let err = PackJob::new()
+            .add_sprite_size("a", "a.png", 100, 100)
+            .max_size(64)
+            .auto_size(false)
+            .run()
+            .unwrap_err();
+        assert_eq!(err, PackError::InsufficientSpace { packed: 0, total: 1 });
+    }
+
+    #[test]
+    fn test_mipmap_padding_and_warnings() {
+        let output = PackJob::new()
+            .add_sprite_size("a", "a.png", 16, 16)
+            .add_sprite_size("b", "b.png", 16, 12)
+            .allow_rotation(false)
+            .mipmap_levels(3)
+            .run()
+            .unwrap();
+
+        let sprites = &output.result.packed_sprites;
+        let gap = sprites[0].x.abs_diff(sprites[1].x) + sprites[0].y.abs_diff(sprites[1].y);
+        assert!(gap >= 16 + 8);
+        assert_eq!(output.result.warnings.len(), 1);
+        assert!(output.result.warnings[0].contains("b.png"));
+    }
+
+    #[test]
+    fn test_empty_sprite_policies() {
+        let job = PackJob::new()
+            .add_sprite("blank", "blank.png", RgbaImage::new(8, 8))
+            .add_sprite("coin", "coin.png", RgbaImage::from_pixel(4, 4, Rgba([255, 255, 0, 255])));
+
+        let output = job.clone().run().unwrap();
+        assert_eq!(output.result.empty_sprites, vec!["blank".to_string()]);
+        assert_eq!(output.result.packed_sprites.len(), 2);
+
+        let output = job.clone().empty_sprites(EmptySpritePolicy::Exclude).run().unwrap();
+        assert_eq!(output.result.empty_sprites, vec!["blank".to_string()]);
+        assert_eq!(output.result.packed_sprites.len(), 1);
+        assert!(!output.trims.contains_key("blank"));
+
+        let err = job.empty_sprites(EmptySpritePolicy::Fail).run().unwrap_err();
+        assert_eq!(err, PackError::EmptySprites { names: vec!["blank.png".to_string()] });
+    }
+
+    #[test]
+    fn test_alias_mirrors() {
+        // 朝右的帧左侧有透明边，朝左的帧为其水平镜像
+        let mut right = RgbaImage::new(12, 8);
+        for (x, y) in [(4, 1), (5, 1), (4, 2), (10, 6)] {
+            right.put_pixel(x, y, Rgba([255, 0, 0, 255]));
+        }
+        let left = image::imageops::flip_horizontal(&right);
+        let job = PackJob::new()
+            .add_sprite("r", "walk_r.png", right.clone())
+            .add_sprite("l", "walk_l.png", left.clone())
+            .add_sprite("coin", "coin.png", RgbaImage::from_pixel(4, 4, Rgba([255, 255, 0, 255])));
+
+        assert!(job.clone().run().unwrap().result.packed_sprites.iter().all(|s| !s.is_flipped()));
+
+        let output = job.alias_mirrors(true).run().unwrap();
+        let sprites = &output.result.packed_sprites;
+        assert_eq!(sprites.len(), 3);
+        let source = sprites.iter().find(|s| s.id == "r").unwrap();
+        let mirror = sprites.iter().find(|s| s.id == "l").unwrap();
+        assert!(mirror.flip_x && !mirror.flip_y);
+        assert_eq!((mirror.x, mirror.y, mirror.width, mirror.height), (source.x, source.y, source.width, source.height));
+        assert_eq!(mirror.offset_x, -source.offset_x);
+
+        // 渲染后按翻转标志还原出原始帧
+        let texture = crate::image_processor::render_texture(
+            sprites,
+            output.result.texture_width,
+            output.result.texture_height,
+            |s| output.trims.get(&s.id).map(|trim| &trim.trimmed_image),
+        )
+        .unwrap();
+        assert_eq!(crate::atlas_unpack::extract_frame(&texture, mirror).unwrap(), left);
+        assert_eq!(crate::atlas_unpack::extract_frame(&texture, source).unwrap(), right);
+    }
+
+    #[test]
+    fn test_solid_regions_on_every_page() {
+        use crate::solid::{SolidRegion, WHITE_REGION_NAME};
+
+        let output = PackJob::new()
+            .add_sprite("coin", "coin.png", RgbaImage::from_pixel(8, 8, Rgba([255, 255, 0, 255])))
+            .solid_region(SolidRegion::white())
+            .run()
+            .unwrap();
+        let white = output.result.packed_sprites.iter().find(|s| s.name == WHITE_REGION_NAME).unwrap();
+        assert_eq!((white.width, white.height, white.rotated, white.trimmed), (4, 4, false, false));
+        assert_eq!(output.trims[WHITE_REGION_NAME].trimmed_image.get_pixel(2, 2), &Rgba([255, 255, 255, 255]));
+
+        // 固定尺寸的多页打包：每页底部的预留条带中都有白色区域，且不与精灵重叠
+        let mut job = PackJob::new().max_size(64).padding(0).auto_size(false).solid_region(SolidRegion::white());
+        for i in 0..3 {
+            job = job.add_sprite_size(format!("s{}", i), format!("s{}.png", i), 64, 48);
+        }
+        let output = job.clone().run_pages().unwrap();
+        assert_eq!(output.pages.len(), 3);
+        for page in &output.pages {
+            let white = page.packed_sprites.iter().find(|s| s.id == WHITE_REGION_NAME).unwrap();
+            assert_eq!((white.y, white.height), (60, 4));
+            assert!(page.packed_sprites.iter().filter(|s| s.id != white.id).all(|s| s.y + s.height <= 60));
+        }
+
+        let err = job.add_sprite_size("w", WHITE_REGION_NAME, 4, 4).run().unwrap_err();
+        assert_eq!(err, PackError::ReservedName { name: WHITE_REGION_NAME.to_string() });
+    }
+
+    #[test]
+    fn test_per_sprite_rotation() {
+        // 只有旋转后才能放入 16x64 的纹理
+        let job = PackJob::new()
+            .add_sprite_size("bar", "bar.png", 64, 16)
+            .max_dimensions(16, 64)
+            .auto_size(false)
+            .padding(0);
+
+        assert!(job.clone().run().unwrap().result.packed_sprites[0].rotated);
+        let err = job.allow_sprite_rotation("bar", false).run().unwrap_err();
+        assert_eq!(err, PackError::InsufficientSpace { packed: 0, total: 1 });
+    }
+
+    #[test]
+    fn test_sprite_source_size() {
+        // 6x6 图像，内容在 (0,0)-(4,4)
+        let mut img = RgbaImage::new(6, 6);
+        for y in 0..4 {
+            for x in 0..4 {
+                img.put_pixel(x, y, image::Rgba([255, 0, 0, 255]));
+            }
+        }
+        let job = PackJob::new()
+            .add_sprite("a", "a.png", img)
+            .add_sprite_size("b", "b.png", 10, 8)
+            .sprite_source_size("a", 16, 16)
+            .sprite_source_size("b", 16, 16);
+
+        let output = job.clone().run().unwrap();
+        let a = output.result.packed_sprites.iter().find(|s| s.id == "a").unwrap();
+        assert_eq!((a.original_width, a.original_height, a.offset_x, a.offset_y), (16, 16, -1, 1));
+        assert_eq!(output.trims["a"].original_width, 16);
+        let b = output.result.packed_sprites.iter().find(|s| s.id == "b").unwrap();
+        assert_eq!((b.original_width, b.original_height, b.offset_x, b.offset_y), (16, 16, 0, 0));
+        assert!(b.trimmed);
+
+        let err = job.sprite_source_size("b", 8, 8).run().unwrap_err();
+        assert_eq!(err, PackError::SourceSizeTooSmall { name: "b.png".to_string(), width: 8, height: 8 });
+    }
+
+    #[test]
+    fn test_sprite_anchor_follows_source_size() {
+        // 20x10 图像：不透明内容在 (2,2)-(8,6)，锚点指向内容中的像素 (5, 5)
+        let mut img = RgbaImage::new(20, 10);
+        for y in 2..6 {
+            for x in 2..8 {
+                img.put_pixel(x, y, Rgba([255, 0, 0, 255]));
+            }
+        }
+        let anchor = |job: PackJob, cell: Option<(u32, u32)>| {
+            let mut job = job.add_sprite("a", "a.png", img.clone()).sprite_anchor("a", (0.25, 0.5));
+            if let Some((width, height)) = cell {
+                job = job.sprite_source_size("a", width, height);
+            }
+            job.run().unwrap().result.packed_sprites[0].anchor.unwrap()
+        };
+
+        assert_eq!(anchor(PackJob::new(), None), (0.25, 0.5));
+        assert_eq!(anchor(PackJob::new().trim_mode(TrimMode::None), None), (0.25, 0.5));
+        // Trim 模式下 sourceSize 为裁剪后的 6x4 内容
+        assert_eq!(anchor(PackJob::new().trim_mode(TrimMode::Trim), None), (0.5, 0.25));
+        // 原图居中放入 40x20 的虚拟格子，左侧与上方各留白 10、5 像素
+        assert_eq!(anchor(PackJob::new(), Some((40, 20))), (0.375, 0.5));
+        assert_eq!(anchor(PackJob::new().trim_mode(TrimMode::Trim), Some((8, 8))), (0.5, 0.375));
+    }
+
+    #[test]
+    fn test_sprite_nine_slice_follows_trim_bounds() {
+        // 10x10 中只有 (2, 2) 起 6x6 的区域不透明，四边各裁掉 2 像素
+        let mut img = RgbaImage::new(10, 10);
+        for y in 2..8 {
+            for x in 2..8 {
+                img.put_pixel(x, y, Rgba([255, 0, 0, 255]));
+            }
+        }
+        let insets = NineSlice { left: 3, right: 3, top: 1, bottom: 4 };
+        let nine_slice = |job: PackJob, cell: Option<(u32, u32)>| {
+            let mut job = job.add_sprite("a", "a.png", img.clone()).sprite_nine_slice("a", insets);
+            if let Some((width, height)) = cell {
+                job = job.sprite_source_size("a", width, height);
+            }
+            job.run().unwrap().result.packed_sprites[0].nine_slice.unwrap()
+        };
+
+        let trimmed = NineSlice { left: 1, right: 1, top: 0, bottom: 2 };
+        assert_eq!(nine_slice(PackJob::new(), None), trimmed);
+        assert_eq!(nine_slice(PackJob::new().trim_mode(TrimMode::Trim), None), trimmed);
+        // 虚拟格子的留白不属于原图，不从边距中扣除
+        assert_eq!(nine_slice(PackJob::new(), Some((16, 16))), trimmed);
+        assert_eq!(nine_slice(PackJob::new().trim_mode(TrimMode::Trim), Some((16, 16))), trimmed);
+        assert_eq!(nine_slice(PackJob::new().trim_mode(TrimMode::None), Some((16, 16))), insets);
+    }
+
+    #[test]
+    fn test_run_pages() {
+        let mut job = PackJob::new().max_size(128).padding(0).allow_rotation(false);
+        for i in 0..6 {
+            job = job.add_sprite_size(format!("s{}", i), format!("s{}.png", i), 62, 62);
+        }
+        // 第 1 级 mipmap 需要 2 像素间距，每页恰好放下 4 个 62x62 精灵
+        job = job.add_sprite_size("odd", "odd.png", 31, 30).mipmap_levels(1);
+
+        // 单页放不下
+        assert!(matches!(job.clone().run(), Err(PackError::InsufficientSpace { .. })));
+
+        let output = job.clone().run_pages().unwrap();
+        assert_eq!(output.pages.len(), 2);
+        let total: usize = output.pages.iter().map(|p| p.packed_sprites.len()).sum();
+        assert_eq!(total, 7);
+        // 自动尺寸时每页单独缩小，警告只出现在所在的页
+        let last = output.pages.last().unwrap();
+        assert!(last.texture_width <= 128 && last.texture_height <= 128);
+        let warned: Vec<usize> = output
+            .pages
+            .iter()
+            .enumerate()
+            .filter(|(_, page)| !page.warnings.is_empty())
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(warned.len(), 1);
+        assert!(output.pages[warned[0]].packed_sprites.iter().any(|s| s.id == "odd"));
+
+        let err = job.add_sprite_size("big", "big.png", 300, 10).run_pages().unwrap_err();
+        assert!(matches!(err, PackError::SpriteTooLarge { ref name, .. } if name == "big.png"));
+    }
+
+    #[test]
+    fn test_page_group_affinity() {
+        let mut job = PackJob::new().max_size(128).padding(0).allow_rotation(false);
+        for i in 0..9 {
+            job = job.add_sprite_size(format!("s{}", i), format!("s{}.png", i), 64, 64);
+        }
+        for i in 3..6 {
+            job = job.sprite_page_group(&format!("s{}", i), "run");
+        }
+        let output = job.clone().run_pages().unwrap();
+        let pages_of_run: Vec<usize> = output
+            .pages
+            .iter()
+            .enumerate()
+            .filter(|(_, page)| page.packed_sprites.iter().any(|s| ["s3", "s4", "s5"].contains(&s.id.as_str())))
+            .map(|(i, _)| i)
+            .collect();
+        // run 分组整体移到第二页，第一页的空位由未分组的精灵填补
+        assert_eq!(pages_of_run, vec![1]);
+        assert_eq!(output.pages.iter().map(|p| p.packed_sprites.len()).collect::<Vec<_>>(), vec![4, 4, 1]);
+
+        let err = (0..9).fold(job, |job, i| job.sprite_page_group(&format!("s{}", i), "all")).run_pages().unwrap_err();
+        assert_eq!(err, PackError::GroupTooLarge { group: "all".to_string() });
+    }
+
+    #[test]
+    fn test_extrude_grows_texture() {
+        let job = PackJob::new().padding(0).allow_rotation(false).add_sprite_size("a", "a.png", 128, 128);
+        let output = job.clone().run().unwrap();
+        assert_eq!(output.result.texture_width, 128);
+
+        let output = job.clone().extrude(2).run().unwrap();
+        assert_eq!(output.result.texture_width, 256);
+        let a = &output.result.packed_sprites[0];
+        assert_eq!((a.x, a.y, a.width, a.height), (2, 2, 128, 128));
+
+        let err = job.clone().max_size(130).auto_size(false).extrude(2).run().unwrap_err();
+        assert!(matches!(err, PackError::SpriteTooLarge { .. } | PackError::InsufficientSpace { .. }));
+    }
+
+    #[test]
+    fn test_auto_heuristic_never_worse() {
+        let mut job = PackJob::new().padding(0).size_constraint(SizeConstraint::Any);
+        for (i, (w, h)) in [(70, 20), (30, 60), (50, 50), (20, 90), (64, 16), (40, 40), (10, 70)].iter().enumerate() {
+            job = job.add_sprite_size(format!("s{}", i), format!("s{}.png", i), *w, *h);
+        }
+        let area = |job: PackJob| {
+            let result = job.run().unwrap().result;
+            assert_eq!(result.packed_sprites.len(), 7);
+            result.texture_width * result.texture_height
+        };
+        let fixed = area(job.clone());
+        let auto = area(job.clone().auto_heuristic(true));
+        assert!(auto <= fixed);
+        assert!(area(job.heuristic(PackHeuristic::ContactPoint)) >= auto);
+    }
+
+    #[test]
+    fn test_size_constraint() {
+        let job = PackJob::new().padding(0).add_sprite_size("a", "a.png", 130, 60);
+        let size = |job: PackJob| {
+            let result = job.run().unwrap().result;
+            (result.texture_width, result.texture_height)
+        };
+        assert_eq!(size(job.clone()), (256, 64));
+        assert_eq!(size(job.clone().size_constraint(SizeConstraint::Square)), (256, 256));
+        assert_eq!(size(job.clone().size_constraint(SizeConstraint::MultipleOf4)), (132, 60));
+        assert_eq!(size(job.clone().size_constraint(SizeConstraint::Any)), (130, 60));
+        // 固定尺寸时保持最大尺寸
+        assert_eq!(size(job.clone().max_dimensions(300, 100).auto_size(false)), (300, 100));
+
+        let err = job.max_dimensions(256, 128).size_constraint(SizeConstraint::Square).run().unwrap_err();
+        assert_eq!(err, PackError::ConstraintExceedsMaxSize { width: 256, height: 256 });
+    }
+}
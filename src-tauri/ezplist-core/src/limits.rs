@@ -0,0 +1,76 @@
+/// 输入大小限制 (Input Limits)
+///
+/// 用户会加载从网上下载的任意文件（模板、图集描述文件等），
+/// 在解析前统一检查大小，避免异常输入耗尽内存或长时间阻塞后端
+
+/// 文本输入文件（模板、图集描述文件）的最大字节数
+pub const MAX_TEXT_INPUT_BYTES: u64 = 16 * 1024 * 1024;
+
+/// 生成纹理的最大边长（16384x16384 RGBA 约 1 GiB）
+pub const MAX_TEXTURE_SIZE: u32 = 16384;
+
+/// 导入图像的默认最大像素数（约 6700 万像素，解码后 RGBA 约 256 MiB）
+///
+/// 在完整解码前根据文件头中的尺寸检查，防止解压炸弹
+pub const MAX_IMAGE_PIXELS: u64 = 64 * 1024 * 1024;
+
+/// 检查文本输入文件大小
+///
+/// # Arguments
+/// * `len` - 文件字节数
+///
+/// # Returns
+/// * `Result<(), String>` - 超出限制时返回错误信息
+pub fn check_text_input_size(len: u64) -> Result<(), String> {
+    if len > MAX_TEXT_INPUT_BYTES {
+        return Err(format!(
+            "文件过大: {} 字节（上限 {} 字节）",
+            len, MAX_TEXT_INPUT_BYTES
+        ));
+    }
+    Ok(())
+}
+
+/// 检查待生成纹理的尺寸
+///
+/// # Arguments
+/// * `width` - 纹理宽度
+/// * `height` - 纹理高度
+///
+/// # Returns
+/// * `Result<(), String>` - 尺寸为 0 或超出限制时返回错误信息
+pub fn check_texture_size(width: u32, height: u32) -> Result<(), String> {
+    if width == 0 || height == 0 {
+        return Err(format!("纹理尺寸无效: {}x{}", width, height));
+    }
+    if width > MAX_TEXTURE_SIZE || height > MAX_TEXTURE_SIZE {
+        return Err(format!(
+            "纹理尺寸 {}x{} 超出上限 {}x{}",
+            width, height, MAX_TEXTURE_SIZE, MAX_TEXTURE_SIZE
+        ));
+    }
+    Ok(())
+}
+
+/// 检查待解码图像的像素数
+///
+/// # Arguments
+/// * `width` - 图像宽度
+/// * `height` - 图像高度
+/// * `max_pixels` - 允许的最大像素数
+///
+/// # Returns
+/// * `Result<(), String>` - 尺寸为 0 或像素数超出限制时返回错误信息
+pub fn check_image_pixels(width: u32, height: u32, max_pixels: u64) -> Result<(), String> {
+    if width == 0 || height == 0 {
+        return Err(format!("图像尺寸无效: {}x{}", width, height));
+    }
+    let pixels = width as u64 * height as u64;
+    if pixels > max_pixels {
+        return Err(format!(
+            "图像 {}x{} 共 {} 像素，超出上限 {} 像素",
+            width, height, pixels, max_pixels
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,107 @@
+/// Mipmap 安全间距 (Mipmap-Safe Padding)
+///
+/// 图集生成 mipmap 时每一级尺寸减半，基础级的 1 像素间距在第 1 级就只剩半个像素，
+/// 相邻帧的颜色会互相渗入。第 L 级需要基础级 2^L 像素的间距才能保留 1 像素的间隔，
+/// 每个帧向外扩展其中一半；帧尺寸能被 2^L 整除时，帧边缘才会落在该级的像素边界上
+
+use serde::Serialize;
+
+/// 允许的最大 mipmap 级数（第 8 级需要 256 像素间距，已无实际意义）
+pub const MAX_MIPMAP_LEVELS: u32 = 8;
+
+/// 单个 mipmap 级别的要求
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MipLevel {
+    /// 级别（0 为原始尺寸）
+    pub level: u32,
+    /// 相对原始尺寸的缩小倍数
+    pub divisor: u32,
+    /// 该级保留 1 像素间隔所需的基础级间距
+    pub padding: u32,
+    /// 每个帧每侧需要的基础级边缘扩展
+    pub extrusion: u32,
+}
+
+/// 检查 mipmap 级数
+pub fn validate_mipmap_levels(levels: u32) -> Result<(), String> {
+    if levels > MAX_MIPMAP_LEVELS {
+        return Err(format!("mipmap 级数不能超过 {}，当前为 {}", MAX_MIPMAP_LEVELS, levels));
+    }
+    Ok(())
+}
+
+/// 计算各级 mipmap 的间距与扩展要求
+///
+/// # Arguments
+/// * `levels` - 原始尺寸之外的 mipmap 级数
+///
+/// # Returns
+/// * `Vec<MipLevel>` - 第 1 级到第 `levels` 级的要求
+pub fn mip_levels(levels: u32) -> Vec<MipLevel> {
+    (1..=levels.min(MAX_MIPMAP_LEVELS))
+        .map(|level| MipLevel {
+            level,
+            divisor: 1 << level,
+            padding: 1 << level,
+            extrusion: 1 << (level - 1),
+        })
+        .collect()
+}
+
+/// 所有级别都安全所需的基础级间距
+pub fn required_padding(levels: u32) -> u32 {
+    mip_levels(levels).last().map_or(0, |level| level.padding)
+}
+
+/// 检查帧尺寸能否被各级缩小倍数整除
+///
+/// # Arguments
+/// * `name` - 帧名称
+/// * `width` / `height` - 帧尺寸（裁剪后）
+/// * `levels` - mipmap 级数
+///
+/// # Returns
+/// * `Option<String>` - 不能整除时的警告，指出第一个出现跨像素采样的级别
+pub fn check_frame_size(name: &str, width: u32, height: u32, levels: u32) -> Option<String> {
+    let level = mip_levels(levels)
+        .into_iter()
+        .find(|level| !width.is_multiple_of(level.divisor) || !height.is_multiple_of(level.divisor))?;
+    Some(format!(
+        "帧 {} 尺寸 {}x{} 不能被 {} 整除，从第 {} 级 mipmap 开始边缘会跨像素采样",
+        name, width, height, level.divisor, level.level
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levels_and_padding() {
+        let levels = mip_levels(3);
+        assert_eq!(levels.len(), 3);
+        assert_eq!(
+            levels[2],
+            MipLevel {
+                level: 3,
+                divisor: 8,
+                padding: 8,
+                extrusion: 4,
+            }
+        );
+        assert_eq!(required_padding(3), 8);
+        assert_eq!(required_padding(0), 0);
+        assert!(validate_mipmap_levels(MAX_MIPMAP_LEVELS + 1).is_err());
+    }
+
+    #[test]
+    fn test_frame_size_warning() {
+        assert_eq!(check_frame_size("a.png", 64, 32, 3), None);
+        assert_eq!(check_frame_size("a.png", 63, 32, 0), None);
+
+        let warning = check_frame_size("b.png", 12, 16, 3).unwrap();
+        assert!(warning.contains("b.png"));
+        assert!(warning.contains("第 3 级"));
+    }
+}
@@ -0,0 +1,130 @@
+/// 翻转帧检测 (Mirror Frame Detection)
+///
+/// 朝左与朝右的动画常常互为镜像。找出与前面某帧的翻转结果完全相同的帧后，
+/// 图集只需保存源帧，翻转帧通过 `flip_x` / `flip_y` 引用源帧的纹理区域，运行时翻转显示
+
+use image::imageops;
+use image::RgbaImage;
+use serde::Serialize;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+/// 一个翻转帧
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MirrorMatch {
+    /// 翻转帧 ID
+    pub id: String,
+    /// 源帧 ID（源帧本身不是翻转帧）
+    pub source_id: String,
+    /// 源帧水平翻转后与该帧相同
+    pub flip_x: bool,
+    /// 源帧垂直翻转后与该帧相同
+    pub flip_y: bool,
+}
+
+/// 查找翻转帧
+///
+/// 按传入顺序处理，先出现的帧作为源帧；只有水平、垂直或同时翻转后与源帧逐像素相同才算匹配。
+/// 沿某个方向对称的帧翻转后与自身相同，这类翻转不视为匹配（完全相同的重复帧不在此处理）
+///
+/// # Arguments
+/// * `images` - (帧 ID, 帧图像)，通常为裁剪后的图像
+///
+/// # Returns
+/// * `Vec<MirrorMatch>` - 翻转帧及其源帧
+pub fn find_mirrors(images: &[(&str, &RgbaImage)]) -> Vec<MirrorMatch> {
+    // 内容哈希 -> 源帧下标
+    let mut sources: HashMap<u64, Vec<usize>> = HashMap::new();
+    let mut matches = Vec::new();
+
+    for (index, &(id, image)) in images.iter().enumerate() {
+        let found = [(true, false), (false, true), (true, true)].into_iter().find_map(|(flip_x, flip_y)| {
+            let flipped = flip(image, flip_x, flip_y);
+            if flipped == *image {
+                return None;
+            }
+            let source = sources
+                .get(&content_hash(&flipped))?
+                .iter()
+                .find(|&&source| *images[source].1 == flipped)?;
+            Some(MirrorMatch {
+                id: id.to_string(),
+                source_id: images[*source].0.to_string(),
+                flip_x,
+                flip_y,
+            })
+        });
+
+        match found {
+            Some(found) => matches.push(found),
+            None => sources.entry(content_hash(image)).or_default().push(index),
+        }
+    }
+    matches
+}
+
+/// 按标志翻转图像
+pub fn flip(image: &RgbaImage, flip_x: bool, flip_y: bool) -> RgbaImage {
+    match (flip_x, flip_y) {
+        (true, true) => imageops::rotate180(image),
+        (true, false) => imageops::flip_horizontal(image),
+        (false, true) => imageops::flip_vertical(image),
+        (false, false) => image.clone(),
+    }
+}
+
+fn content_hash(image: &RgbaImage) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    image.dimensions().hash(&mut hasher);
+    image.as_raw().hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    /// 左上角有一个像素的 3x2 图像（不对称）
+    fn sample() -> RgbaImage {
+        let mut img = RgbaImage::new(3, 2);
+        img.put_pixel(0, 0, Rgba([255, 0, 0, 255]));
+        img.put_pixel(1, 0, Rgba([0, 255, 0, 255]));
+        img
+    }
+
+    #[test]
+    fn test_find_mirrors() {
+        let right = sample();
+        let left = flip(&right, true, false);
+        let upside_down = flip(&right, false, true);
+        let both = flip(&right, true, true);
+        let other = RgbaImage::from_pixel(3, 2, Rgba([1, 2, 3, 255]));
+        let images = [
+            ("walk_r", &right),
+            ("other", &other),
+            ("walk_l", &left),
+            ("fall", &upside_down),
+            ("spin", &both),
+            ("walk_r_copy", &right),
+        ];
+
+        let matches = find_mirrors(&images);
+        let summary: Vec<_> = matches.iter().map(|m| (m.id.as_str(), m.source_id.as_str(), m.flip_x, m.flip_y)).collect();
+        assert_eq!(summary, vec![
+            ("walk_l", "walk_r", true, false),
+            ("fall", "walk_r", false, true),
+            ("spin", "walk_r", true, true),
+        ]);
+    }
+
+    #[test]
+    fn test_symmetric_frames_are_not_mirrors() {
+        // 水平对称的帧翻转后与自身相同，两个相同的对称帧只是重复帧
+        let mut symmetric = RgbaImage::new(3, 2);
+        symmetric.put_pixel(1, 0, Rgba([255, 255, 255, 255]));
+        assert!(find_mirrors(&[("a", &symmetric), ("b", &symmetric)]).is_empty());
+    }
+}
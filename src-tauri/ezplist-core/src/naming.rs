@@ -0,0 +1,189 @@
+/// 命名模板 (Naming Templates)
+///
+/// 根据模板生成帧名称，支持以下占位符：
+/// - `{name}`: 原名称去掉扩展名
+/// - `{ext}`: 原扩展名（不含点）
+/// - `{index}`: 序号；`{index:N}` 补零到 N 位
+///
+/// 序号的起始值、步长与补零位数由 `FrameNumbering` 统一描述，各导出器共用
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// 模板渲染所需的上下文
+#[derive(Debug, Clone, Copy)]
+pub struct NameContext<'a> {
+    /// 原名称（可带扩展名）
+    pub name: &'a str,
+    /// 序号
+    pub index: u32,
+}
+
+/// 序号补零的最大位数
+const MAX_INDEX_DIGITS: usize = 9;
+
+/// 帧序号规则
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FrameNumbering {
+    /// 第一帧的序号（默认 1）
+    pub start: u32,
+    /// 相邻帧序号的差（默认 1）
+    pub step: u32,
+    /// 补零位数（默认 2，0 表示不补零）
+    pub digits: usize,
+}
+
+impl Default for FrameNumbering {
+    fn default() -> Self {
+        Self {
+            start: 1,
+            step: 1,
+            digits: 2,
+        }
+    }
+}
+
+impl FrameNumbering {
+    /// 校验参数
+    pub fn validate(&self) -> Result<(), String> {
+        if self.step == 0 {
+            return Err("序号步长必须大于 0".to_string());
+        }
+        if self.digits > MAX_INDEX_DIGITS {
+            return Err(format!("序号位数不能超过 {}，当前为 {}", MAX_INDEX_DIGITS, self.digits));
+        }
+        Ok(())
+    }
+
+    /// 第 `position` 帧（从 0 开始）的序号
+    ///
+    /// # Returns
+    /// * `Result<u32, String>` - 序号；超出 u32 范围时返回错误
+    pub fn number(&self, position: u32) -> Result<u32, String> {
+        self.step
+            .checked_mul(position)
+            .and_then(|offset| self.start.checked_add(offset))
+            .ok_or_else(|| format!("第 {} 帧的序号超出范围", position + 1))
+    }
+
+    /// 第 `position` 帧的名称，如 `walk_01.png`
+    ///
+    /// # Arguments
+    /// * `prefix` - 名称前缀
+    /// * `position` - 帧位置（从 0 开始）
+    ///
+    /// # Returns
+    /// * `Result<String, String>` - 帧名称；序号超出范围时返回错误
+    pub fn frame_name(&self, prefix: &str, position: u32) -> Result<String, String> {
+        Ok(format!(
+            "{}_{:0width$}.png",
+            prefix,
+            self.number(position)?,
+            width = self.digits
+        ))
+    }
+}
+
+/// 渲染命名模板
+///
+/// # Arguments
+/// * `template` - 命名模板，如 `hero_{index:03}.{ext}`
+/// * `context` - 原名称与序号
+///
+/// # Returns
+/// * `Result<String, String>` - 生成的名称；模板含未知占位符、括号不匹配、
+///   结果为空或含路径分隔符时返回错误
+pub fn render_name(template: &str, context: &NameContext) -> Result<String, String> {
+    let (stem, ext) = split_extension(context.name);
+    let mut output = String::new();
+    let mut rest = template;
+
+    while let Some(open) = rest.find('{') {
+        output.push_str(&rest[..open]);
+        let close = rest[open..]
+            .find('}')
+            .ok_or_else(|| format!("命名模板括号不匹配: {}", template))?;
+        let placeholder = &rest[open + 1..open + close];
+
+        match placeholder.split_once(':') {
+            None if placeholder == "name" => output.push_str(stem),
+            None if placeholder == "ext" => output.push_str(ext),
+            None if placeholder == "index" => {
+                let _ = write!(output, "{}", context.index);
+            }
+            Some(("index", digits)) => {
+                let width: usize = digits
+                    .parse()
+                    .ok()
+                    .filter(|w| *w <= MAX_INDEX_DIGITS)
+                    .ok_or_else(|| format!("无效的序号位数: {{{}}}", placeholder))?;
+                let _ = write!(output, "{:0width$}", context.index, width = width);
+            }
+            _ => return Err(format!("未知的命名占位符: {{{}}}", placeholder)),
+        }
+        rest = &rest[open + close + 1..];
+    }
+    if rest.contains('}') {
+        return Err(format!("命名模板括号不匹配: {}", template));
+    }
+    output.push_str(rest);
+
+    if output.is_empty() {
+        return Err("生成的名称为空".to_string());
+    }
+    if output.contains(['/', '\\']) {
+        return Err(format!("名称不能包含路径分隔符: {}", output));
+    }
+    Ok(output)
+}
+
+/// 拆分名称与扩展名（没有扩展名时扩展名为空）
+fn split_extension(name: &str) -> (&str, &str) {
+    match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => (stem, ext),
+        _ => (name, ""),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(template: &str, name: &str, index: u32) -> Result<String, String> {
+        render_name(template, &NameContext { name, index })
+    }
+
+    #[test]
+    fn test_placeholders() {
+        assert_eq!(render("{name}_{index:03}.{ext}", "hero.png", 7).unwrap(), "hero_007.png");
+        assert_eq!(render("run_{index}.png", "whatever.png", 12).unwrap(), "run_12.png");
+        assert_eq!(render("{name}", ".hidden", 1).unwrap(), ".hidden");
+    }
+
+    #[test]
+    fn test_frame_numbering() {
+        let default = FrameNumbering::default();
+        assert_eq!(default.frame_name("walk", 0).unwrap(), "walk_01.png");
+        assert_eq!(default.frame_name("walk", 9).unwrap(), "walk_10.png");
+
+        let custom = FrameNumbering { start: 0, step: 5, digits: 4 };
+        assert_eq!(custom.frame_name("walk", 3).unwrap(), "walk_0015.png");
+        let plain = FrameNumbering { digits: 0, ..default };
+        assert_eq!(plain.frame_name("walk", 0).unwrap(), "walk_1.png");
+
+        assert!(FrameNumbering { step: 0, ..default }.validate().is_err());
+        assert!(FrameNumbering { digits: 10, ..default }.validate().is_err());
+        assert!(FrameNumbering { start: u32::MAX, ..default }.number(1).is_err());
+    }
+
+    #[test]
+    fn test_invalid_templates() {
+        assert!(render("{unknown}.png", "a.png", 1).is_err());
+        assert!(render("{name", "a.png", 1).is_err());
+        assert!(render("name}", "a.png", 1).is_err());
+        assert!(render("{index:x}", "a.png", 1).is_err());
+        assert!(render("dir/{name}.png", "a.png", 1).is_err());
+        assert!(render("{ext}", "noext", 1).is_err());
+    }
+}
@@ -0,0 +1,124 @@
+/// 帧备注 (Frame Notes)
+///
+/// 美术可以给精灵或帧附加自由文本备注（如「发布前替换」），备注随会话与项目文件保存，
+/// 导出时可写出 `{名称}.notes.json` 附属文件交给程序或下一位美术
+
+use crate::types::PackedSprite;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// 单条备注的最大字符数
+pub const MAX_NOTE_CHARS: usize = 2000;
+
+/// 检查备注内容
+///
+/// # Returns
+/// * `Result<(), String>` - 备注为空白或过长时返回错误信息
+pub fn validate_note(note: &str) -> Result<(), String> {
+    if note.trim().is_empty() {
+        return Err("备注不能为空".to_string());
+    }
+    let chars = note.chars().count();
+    if chars > MAX_NOTE_CHARS {
+        return Err(format!("备注不能超过 {} 个字符: {}", MAX_NOTE_CHARS, chars));
+    }
+    Ok(())
+}
+
+/// 附属文件中的一条备注
+#[derive(Debug, Serialize)]
+struct FrameNote<'a> {
+    /// 帧名称
+    frame: &'a str,
+    /// 备注
+    note: &'a str,
+}
+
+/// 备注附属文件内容
+#[derive(Debug, Serialize)]
+struct NotesFile<'a> {
+    /// 对应的纹理文件名
+    texture: &'a str,
+    /// 有备注的帧，按帧名称排序
+    notes: Vec<FrameNote<'a>>,
+}
+
+/// 生成一页的备注附属文件
+///
+/// # Arguments
+/// * `sprites` - 该页已打包的精灵
+/// * `notes` - 按精灵 ID 索引的备注
+/// * `texture_name` - 纹理文件名
+///
+/// # Returns
+/// * `Result<Option<Vec<u8>>, String>` - JSON 内容；该页没有带备注的帧时为 None
+pub fn notes_sidecar(
+    sprites: &[PackedSprite],
+    notes: &HashMap<String, String>,
+    texture_name: &str,
+) -> Result<Option<Vec<u8>>, String> {
+    let mut entries: Vec<FrameNote> = sprites
+        .iter()
+        .filter_map(|sprite| {
+            let note = notes.get(&sprite.id)?;
+            Some(FrameNote { frame: &sprite.name, note })
+        })
+        .collect();
+    if entries.is_empty() {
+        return Ok(None);
+    }
+    for entry in &entries {
+        validate_note(entry.note).map_err(|e| format!("{}: {}", entry.frame, e))?;
+    }
+    entries.sort_by(|a, b| a.frame.cmp(b.frame));
+    let file = NotesFile { texture: texture_name, notes: entries };
+    serde_json::to_vec_pretty(&file)
+        .map(Some)
+        .map_err(|e| format!("生成备注文件失败: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packed(id: &str, name: &str) -> PackedSprite {
+        PackedSprite {
+            id: id.to_string(),
+            name: name.to_string(),
+            x: 0,
+            y: 0,
+            width: 4,
+            height: 4,
+            rotated: false,
+            original_width: 4,
+            original_height: 4,
+            trimmed: false,
+            offset_x: 0,
+            offset_y: 0,
+            flip_x: false,
+            flip_y: false,
+            anchor: None,
+            nine_slice: None,
+        }
+    }
+
+    #[test]
+    fn test_notes_sidecar() {
+        let sprites = vec![packed("1", "hero.png"), packed("2", "boss.png"), packed("3", "coin.png")];
+        let notes = HashMap::from([
+            ("1".to_string(), "发布前替换".to_string()),
+            ("2".to_string(), "placeholder".to_string()),
+            ("9".to_string(), "其他页的帧".to_string()),
+        ]);
+        let json: serde_json::Value =
+            serde_json::from_slice(&notes_sidecar(&sprites, &notes, "ui.png").unwrap().unwrap()).unwrap();
+        assert_eq!(json["texture"], "ui.png");
+        let frames: Vec<&str> = json["notes"].as_array().unwrap().iter().map(|n| n["frame"].as_str().unwrap()).collect();
+        assert_eq!(frames, vec!["boss.png", "hero.png"]);
+
+        assert!(notes_sidecar(&sprites[2..], &notes, "ui.png").unwrap().is_none());
+        let blank = HashMap::from([("3".to_string(), "  ".to_string())]);
+        assert!(notes_sidecar(&sprites, &blank, "ui.png").unwrap_err().contains("coin.png"));
+        assert!(validate_note(&"x".repeat(MAX_NOTE_CHARS + 1)).is_err());
+    }
+}
@@ -0,0 +1,347 @@
+/// 轮廓提取 (Outline Extraction)
+///
+/// 使用 Marching Squares 沿不透明区域的边界行走，得到每个连通区域的外轮廓多边形，
+/// 再用 Douglas-Peucker 算法简化顶点。
+/// 顶点坐标位于像素角点上，原点为帧左上角
+
+use image::RgbaImage;
+use serde::{Deserialize, Serialize};
+
+/// 多边形顶点
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+/// 轮廓提取选项
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutlineOptions {
+    /// alpha 大于该值的像素视为不透明
+    pub alpha_threshold: u8,
+    /// 简化容差（像素），0 表示只合并共线顶点
+    pub tolerance: f32,
+}
+
+impl Default for OutlineOptions {
+    fn default() -> Self {
+        Self {
+            alpha_threshold: 0,
+            tolerance: 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// 提取图像中不透明区域的外轮廓
+///
+/// 每个 4 连通的不透明区域生成一个多边形（不包含内部空洞），
+/// 顶点按不透明区域在左侧的方向排列
+///
+/// # Arguments
+/// * `image` - 帧图像
+/// * `options` - 提取选项
+///
+/// # Returns
+/// * `Vec<Vec<Point>>` - 轮廓多边形列表，按区域最上方像素的位置排序
+pub fn extract_outline(image: &RgbaImage, options: &OutlineOptions) -> Vec<Vec<Point>> {
+    let (width, height) = image.dimensions();
+    let solid: Vec<bool> = image
+        .pixels()
+        .map(|p| p[3] > options.alpha_threshold)
+        .collect();
+
+    let labels = label_components(&solid, width, height);
+    let mut seen = vec![false; labels.count];
+    let mut polygons = Vec::new();
+
+    for y in 0..height {
+        for x in 0..width {
+            let idx = (y * width + x) as usize;
+            let Some(label) = labels.map[idx] else {
+                continue;
+            };
+            if seen[label] {
+                continue;
+            }
+            seen[label] = true;
+
+            let in_component = |px: i64, py: i64| {
+                px >= 0
+                    && py >= 0
+                    && px < width as i64
+                    && py < height as i64
+                    && labels.map[(py as u32 * width + px as u32) as usize] == Some(label)
+            };
+            let contour = trace_contour(x as i64, y as i64, in_component);
+            polygons.push(simplify_polygon(&contour, options.tolerance));
+        }
+    }
+
+    polygons
+}
+
+/// 连通区域标记结果
+struct Components {
+    map: Vec<Option<usize>>,
+    count: usize,
+}
+
+/// 4 连通区域标记（与轮廓行走对对角像素的处理一致）
+fn label_components(solid: &[bool], width: u32, height: u32) -> Components {
+    let mut map = vec![None; solid.len()];
+    let mut count = 0;
+    let mut stack = Vec::new();
+
+    for start in 0..solid.len() {
+        if !solid[start] || map[start].is_some() {
+            continue;
+        }
+        map[start] = Some(count);
+        stack.push(start);
+
+        while let Some(idx) = stack.pop() {
+            let x = (idx % width as usize) as u32;
+            let y = (idx / width as usize) as u32;
+            let neighbors = [
+                (x > 0).then(|| idx - 1),
+                (x + 1 < width).then(|| idx + 1),
+                (y > 0).then(|| idx - width as usize),
+                (y + 1 < height).then(|| idx + width as usize),
+            ];
+            for n in neighbors.into_iter().flatten() {
+                if solid[n] && map[n].is_none() {
+                    map[n] = Some(count);
+                    stack.push(n);
+                }
+            }
+        }
+        count += 1;
+    }
+
+    Components { map, count }
+}
+
+/// 从区域最上方最左侧的像素出发，沿边界行走一周
+///
+/// 返回方向改变处的角点（已合并共线顶点）
+fn trace_contour(start_x: i64, start_y: i64, solid: impl Fn(i64, i64) -> bool) -> Vec<Point> {
+    let (mut x, mut y) = (start_x, start_y);
+    let mut previous: Option<Step> = None;
+    let mut points = Vec::new();
+
+    loop {
+        let state = solid(x - 1, y - 1) as u8
+            | (solid(x, y - 1) as u8) << 1
+            | (solid(x - 1, y) as u8) << 2
+            | (solid(x, y) as u8) << 3;
+
+        let step = match state {
+            1 | 5 | 13 => Step::Up,
+            2 | 3 | 7 => Step::Right,
+            4 | 12 | 14 => Step::Left,
+            8 | 10 | 11 => Step::Down,
+            6 => {
+                if previous == Some(Step::Up) {
+                    Step::Left
+                } else {
+                    Step::Right
+                }
+            }
+            9 => {
+                if previous == Some(Step::Right) {
+                    Step::Up
+                } else {
+                    Step::Down
+                }
+            }
+            // 起点必然位于边界上，不会出现 0 或 15
+            _ => break,
+        };
+
+        if previous != Some(step) {
+            points.push(Point {
+                x: x as f32,
+                y: y as f32,
+            });
+        }
+        previous = Some(step);
+
+        match step {
+            Step::Up => y -= 1,
+            Step::Down => y += 1,
+            Step::Left => x -= 1,
+            Step::Right => x += 1,
+        }
+
+        if x == start_x && y == start_y {
+            break;
+        }
+    }
+
+    points
+}
+
+/// 简化闭合多边形（Douglas-Peucker）
+///
+/// # Arguments
+/// * `points` - 闭合多边形顶点（首尾不重复）
+/// * `tolerance` - 允许的最大偏差（像素）
+///
+/// # Returns
+/// * `Vec<Point>` - 简化后的顶点；顶点少于 3 个时原样返回
+pub fn simplify_polygon(points: &[Point], tolerance: f32) -> Vec<Point> {
+    if points.len() <= 3 || tolerance <= 0.0 {
+        return points.to_vec();
+    }
+
+    // 以距离第一个顶点最远的顶点把闭合多边形分成两条折线
+    let far = (1..points.len())
+        .max_by(|&a, &b| distance_sq(points[0], points[a]).total_cmp(&distance_sq(points[0], points[b])))
+        .unwrap_or(1);
+
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[far] = true;
+
+    let mut chain: Vec<usize> = (0..=far).collect();
+    douglas_peucker(points, &chain, tolerance, &mut keep);
+    chain = (far..points.len()).chain(std::iter::once(0)).collect();
+    douglas_peucker(points, &chain, tolerance, &mut keep);
+
+    let simplified: Vec<Point> = points
+        .iter()
+        .zip(&keep)
+        .filter(|(_, k)| **k)
+        .map(|(p, _)| *p)
+        .collect();
+
+    if simplified.len() < 3 {
+        points.to_vec()
+    } else {
+        simplified
+    }
+}
+
+fn douglas_peucker(points: &[Point], chain: &[usize], tolerance: f32, keep: &mut [bool]) {
+    if chain.len() < 3 {
+        return;
+    }
+    let (first, last) = (points[chain[0]], points[chain[chain.len() - 1]]);
+
+    let mut max_distance = 0.0;
+    let mut max_index = 0;
+    for (i, &idx) in chain.iter().enumerate().take(chain.len() - 1).skip(1) {
+        let distance = segment_distance(points[idx], first, last);
+        if distance > max_distance {
+            max_distance = distance;
+            max_index = i;
+        }
+    }
+
+    if max_distance > tolerance {
+        keep[chain[max_index]] = true;
+        douglas_peucker(points, &chain[..=max_index], tolerance, keep);
+        douglas_peucker(points, &chain[max_index..], tolerance, keep);
+    }
+}
+
+fn distance_sq(a: Point, b: Point) -> f32 {
+    (a.x - b.x).powi(2) + (a.y - b.y).powi(2)
+}
+
+/// 点到线段的距离
+fn segment_distance(p: Point, a: Point, b: Point) -> f32 {
+    let length_sq = distance_sq(a, b);
+    if length_sq == 0.0 {
+        return distance_sq(p, a).sqrt();
+    }
+    let t = (((p.x - a.x) * (b.x - a.x) + (p.y - a.y) * (b.y - a.y)) / length_sq).clamp(0.0, 1.0);
+    let projection = Point {
+        x: a.x + t * (b.x - a.x),
+        y: a.y + t * (b.y - a.y),
+    };
+    distance_sq(p, projection).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    fn image_from(rows: &[&str]) -> RgbaImage {
+        let mut img = RgbaImage::new(rows[0].len() as u32, rows.len() as u32);
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                if c == '#' {
+                    img.put_pixel(x as u32, y as u32, Rgba([255, 255, 255, 255]));
+                }
+            }
+        }
+        img
+    }
+
+    fn pt(x: f32, y: f32) -> Point {
+        Point { x, y }
+    }
+
+    #[test]
+    fn test_single_pixel() {
+        let polygons = extract_outline(&image_from(&["...", ".#.", "..."]), &OutlineOptions::default());
+        assert_eq!(polygons, vec![vec![pt(1.0, 1.0), pt(1.0, 2.0), pt(2.0, 2.0), pt(2.0, 1.0)]]);
+    }
+
+    #[test]
+    fn test_l_shape_corners() {
+        let img = image_from(&["#..", "#..", "###"]);
+        let options = OutlineOptions {
+            tolerance: 0.0,
+            ..Default::default()
+        };
+        let polygons = extract_outline(&img, &options);
+        assert_eq!(polygons.len(), 1);
+        assert_eq!(
+            polygons[0],
+            vec![pt(0.0, 0.0), pt(0.0, 3.0), pt(3.0, 3.0), pt(3.0, 2.0), pt(1.0, 2.0), pt(1.0, 0.0)]
+        );
+    }
+
+    #[test]
+    fn test_separate_and_diagonal_regions() {
+        // 对角相接的像素属于不同区域
+        let img = image_from(&["#...", ".#..", "...#"]);
+        let polygons = extract_outline(&img, &OutlineOptions::default());
+        assert_eq!(polygons.len(), 3);
+        assert!(polygons.iter().all(|p| p.len() == 4));
+    }
+
+    #[test]
+    fn test_alpha_threshold_and_empty() {
+        let mut img = RgbaImage::new(4, 4);
+        img.put_pixel(1, 1, Rgba([0, 0, 0, 10]));
+        assert_eq!(extract_outline(&img, &OutlineOptions::default()).len(), 1);
+
+        let options = OutlineOptions {
+            alpha_threshold: 10,
+            ..Default::default()
+        };
+        assert!(extract_outline(&img, &options).is_empty());
+    }
+
+    #[test]
+    fn test_simplify_staircase() {
+        // 对角阶梯在容差内简化为三角形
+        let img = image_from(&["#....", "##...", "###..", "####.", "#####"]);
+        let polygons = extract_outline(&img, &OutlineOptions::default());
+        assert_eq!(polygons[0].len(), 3);
+        assert!(polygons[0].iter().all(|p| (0.0..=5.0).contains(&p.x) && (0.0..=5.0).contains(&p.y)));
+    }
+}
@@ -0,0 +1,906 @@
+/// MaxRects 打包算法实现 (MaxRects Bin Packing Algorithm)
+/// 
+/// 参考论文: "A Thousand Ways to Pack the Bin" by Jukka Jylänki
+/// 支持 BSSF、BLSF、BAF、Bottom-Left、Contact Point 启发式策略与多种放置顺序
+
+use crate::types::PackedSprite;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// 待打包的精灵输入数据
+#[derive(Debug, Clone)]
+pub struct SpriteInput {
+    /// 精灵 ID
+    pub id: String,
+    /// 精灵名称
+    pub name: String,
+    /// 裁剪后宽度
+    pub width: u32,
+    /// 裁剪后高度
+    pub height: u32,
+    /// 原始宽度
+    pub original_width: u32,
+    /// 原始高度
+    pub original_height: u32,
+    /// 裁剪偏移量 X
+    pub offset_x: i32,
+    /// 裁剪偏移量 Y
+    pub offset_y: i32,
+    /// 是否已裁剪
+    pub trimmed: bool,
+    /// 是否允许旋转该精灵（打包器也需允许旋转）
+    pub allow_rotation: bool,
+}
+
+/// 打包启发式策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PackHeuristic {
+    /// Best Short Side Fit：优先使剩余短边最小
+    #[default]
+    BestShortSideFit,
+    /// Best Long Side Fit：优先使剩余长边最小
+    BestLongSideFit,
+    /// Best Area Fit：优先放入剩余面积最小的空闲区域
+    BestAreaFit,
+    /// Bottom-Left：优先放在最靠上、最靠左的位置（Y 轴向下）
+    BottomLeft,
+    /// Contact Point：优先与已放置精灵及容器边缘接触最多的位置
+    ContactPoint,
+}
+
+impl PackHeuristic {
+    /// 所有启发式策略
+    pub const ALL: [PackHeuristic; 5] = [
+        PackHeuristic::BestShortSideFit,
+        PackHeuristic::BestLongSideFit,
+        PackHeuristic::BestAreaFit,
+        PackHeuristic::BottomLeft,
+        PackHeuristic::ContactPoint,
+    ];
+}
+
+/// 精灵放置顺序（均为降序，大图优先）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SortOrder {
+    /// 按面积（含间距）
+    #[default]
+    Area,
+    /// 按最长边
+    MaxSide,
+    /// 按周长
+    Perimeter,
+    /// 按高度
+    Height,
+    /// 按宽度
+    Width,
+}
+
+impl SortOrder {
+    /// 所有放置顺序
+    pub const ALL: [SortOrder; 5] =
+        [SortOrder::Area, SortOrder::MaxSide, SortOrder::Perimeter, SortOrder::Height, SortOrder::Width];
+
+    /// 排序键（越大越先放置）
+    fn key(&self, sprite: &SpriteInput, reserved: u32) -> (u64, u64) {
+        let (w, h) = (sprite.width as u64, sprite.height as u64);
+        let area = padded_area(sprite, reserved);
+        match self {
+            SortOrder::Area => (area, 0),
+            SortOrder::MaxSide => (w.max(h), area),
+            SortOrder::Perimeter => (w + h, area),
+            SortOrder::Height => (h, w),
+            SortOrder::Width => (w, h),
+        }
+    }
+}
+
+/// 打包器设置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PackerOptions {
+    /// 是否允许旋转
+    pub allow_rotation: bool,
+    /// 精灵间距
+    pub padding: u32,
+    /// 每个精灵四周的挤出像素
+    pub extrude: u32,
+    /// 启发式策略
+    pub heuristic: PackHeuristic,
+    /// 放置顺序
+    pub sort: SortOrder,
+}
+
+impl PackerOptions {
+    /// 每个精灵额外占用的宽高（间距 + 两侧挤出）
+    fn reserved(&self) -> u32 {
+        self.padding.saturating_add(self.extrude.saturating_mul(2))
+    }
+}
+
+/// 纹理尺寸约束（自动尺寸时把内容边界向上取整）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SizeConstraint {
+    /// 宽高都为 2 的幂
+    #[default]
+    Pot,
+    /// 宽高相等且为 2 的幂（PVRTC 等格式要求）
+    Square,
+    /// 宽高为 4 的倍数（ETC、DXT 等块压缩格式要求）
+    MultipleOf4,
+    /// 任意尺寸（紧贴内容）
+    Any,
+}
+
+impl SizeConstraint {
+    /// 把尺寸向上取整到满足约束的最小值（至少 1x1）
+    pub fn apply(&self, width: u32, height: u32) -> (u32, u32) {
+        let pot = |v: u32| v.max(1).checked_next_power_of_two().unwrap_or(u32::MAX);
+        let (width, height) = (width.max(1), height.max(1));
+        match self {
+            SizeConstraint::Pot => (pot(width), pot(height)),
+            SizeConstraint::Square => {
+                let side = pot(width.max(height));
+                (side, side)
+            }
+            SizeConstraint::MultipleOf4 => {
+                let round = |v: u32| v.div_ceil(4).saturating_mul(4);
+                (round(width), round(height))
+            }
+            SizeConstraint::Any => (width, height),
+        }
+    }
+}
+
+/// 矩形结构
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rect {
+    /// 创建新矩形
+    pub fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+        Self { x, y, width, height }
+    }
+    
+    /// 检查两个矩形是否相交
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.x < other.x + other.width
+            && self.x + self.width > other.x
+            && self.y < other.y + other.height
+            && self.y + self.height > other.y
+    }
+    
+    /// 检查 self 是否完全包含在 other 内
+    pub fn is_contained_in(&self, other: &Rect) -> bool {
+        self.x >= other.x
+            && self.y >= other.y
+            && self.x + self.width <= other.x + other.width
+            && self.y + self.height <= other.y + other.height
+    }
+}
+
+/// 放置结果
+#[derive(Debug, Clone)]
+struct Placement {
+    /// 放置位置
+    rect: Rect,
+    /// 是否旋转 90 度
+    rotated: bool,
+    /// 评分（越小越好）
+    score1: i64,
+    score2: i64,
+}
+
+/// MaxRects 打包器
+pub struct MaxRectsPacker {
+    /// 容器宽度
+    width: u32,
+    /// 容器高度
+    height: u32,
+    /// 空闲矩形列表
+    free_rects: Vec<Rect>,
+    /// 已放置的矩形列表
+    used_rects: Vec<Rect>,
+    /// 是否允许旋转
+    allow_rotation: bool,
+    /// 边距
+    padding: u32,
+    /// 每个精灵四周额外预留的挤出像素
+    extrude: u32,
+    /// 启发式策略
+    heuristic: PackHeuristic,
+    /// 放置顺序
+    sort: SortOrder,
+}
+
+impl MaxRectsPacker {
+    /// 创建新的打包器
+    /// 
+    /// # Arguments
+    /// * `width` - 容器宽度
+    /// * `height` - 容器高度
+    /// * `allow_rotation` - 是否允许旋转优化
+    /// * `padding` - 精灵间距
+    pub fn new(width: u32, height: u32, allow_rotation: bool, padding: u32) -> Self {
+        Self {
+            width,
+            height,
+            free_rects: vec![Rect::new(0, 0, width, height)],
+            used_rects: Vec::new(),
+            allow_rotation,
+            padding,
+            extrude: 0,
+            heuristic: PackHeuristic::default(),
+            sort: SortOrder::default(),
+        }
+    }
+
+    /// 按完整设置创建打包器
+    pub fn with_options(width: u32, height: u32, options: &PackerOptions) -> Self {
+        Self::new(width, height, options.allow_rotation, options.padding)
+            .with_extrude(options.extrude)
+            .with_heuristic(options.heuristic)
+            .with_sort(options.sort)
+    }
+
+    /// 设置启发式策略
+    pub fn with_heuristic(mut self, heuristic: PackHeuristic) -> Self {
+        self.heuristic = heuristic;
+        self
+    }
+
+    /// 设置放置顺序
+    pub fn with_sort(mut self, sort: SortOrder) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// 设置边缘挤出像素：每个精灵四周额外预留 `extrude` 像素，
+    /// 结果中的位置指向内部区域
+    pub fn with_extrude(mut self, extrude: u32) -> Self {
+        self.extrude = extrude;
+        self
+    }
+
+    /// 每个精灵额外占用的宽高（间距 + 两侧挤出）
+    fn reserved(&self) -> u32 {
+        self.padding.saturating_add(self.extrude.saturating_mul(2))
+    }
+    
+    /// 打包精灵列表
+    /// 
+    /// # Arguments
+    /// * `sprites` - 待打包的精灵列表
+    /// 
+    /// # Returns
+    /// * `Vec<PackedSprite>` - 打包结果
+    pub fn pack(&mut self, sprites: &[SpriteInput]) -> Vec<PackedSprite> {
+        // 复制并按放置顺序降序排序（大图优先）
+        let mut sorted_sprites: Vec<(usize, &SpriteInput)> = sprites.iter().enumerate().collect();
+        let reserved = self.reserved();
+        sorted_sprites.sort_by_key(|(_, sprite)| std::cmp::Reverse(self.sort.key(sprite, reserved)));
+        
+        let mut result = Vec::with_capacity(sprites.len());
+        
+        for (original_idx, sprite) in sorted_sprites {
+            let w = sprite.width.saturating_add(self.reserved());
+            let h = sprite.height.saturating_add(self.reserved());
+            
+            let rotatable = self.allow_rotation && sprite.allow_rotation;
+            if let Some(placement) = self.find_best_position(w, h, rotatable) {
+                // 放置矩形
+                self.place_rect(placement.rect);
+                
+                // 记录结果（去掉 padding 与挤出区域）
+                result.push((original_idx, PackedSprite {
+                    id: sprite.id.clone(),
+                    name: sprite.name.clone(),
+                    x: placement.rect.x + self.extrude,
+                    y: placement.rect.y + self.extrude,
+                    width: if placement.rotated { sprite.height } else { sprite.width },
+                    height: if placement.rotated { sprite.width } else { sprite.height },
+                    rotated: placement.rotated,
+                    original_width: sprite.original_width,
+                    original_height: sprite.original_height,
+                    trimmed: sprite.trimmed,
+                    offset_x: sprite.offset_x,
+                    offset_y: sprite.offset_y,
+                    flip_x: false,
+                    flip_y: false,
+                    anchor: None,
+                    nine_slice: None,
+                }));
+            } else {
+                // 无法放置，跳过（调用者需要处理）
+                println!("警告: 无法放置精灵 {} ({}x{})", sprite.name, sprite.width, sprite.height);
+            }
+        }
+        
+        // 按原始顺序排序
+        result.sort_by_key(|(idx, _)| *idx);
+        result.into_iter().map(|(_, s)| s).collect()
+    }
+    
+    /// 按启发式策略查找最佳放置位置
+    fn find_best_position(&self, width: u32, height: u32, rotatable: bool) -> Option<Placement> {
+        let mut best: Option<Placement> = None;
+        
+        for rect in &self.free_rects {
+            // 尝试不旋转与旋转 90 度
+            let orientations = [(width, height, false), (height, width, true)];
+            for (w, h, rotated) in orientations.into_iter().filter(|o| !o.2 || rotatable) {
+                if w > rect.width || h > rect.height {
+                    continue;
+                }
+                let placed = Rect::new(rect.x, rect.y, w, h);
+                let (score1, score2) = self.score(rect, &placed);
+                let placement = Placement { rect: placed, rotated, score1, score2 };
+                if Self::is_better_placement(&placement, &best) {
+                    best = Some(placement);
+                }
+            }
+        }
+        
+        best
+    }
+    
+    /// 计算放置方案的评分（越小越好）
+    fn score(&self, free: &Rect, placed: &Rect) -> (i64, i64) {
+        let leftover_h = (free.width - placed.width) as i64;
+        let leftover_v = (free.height - placed.height) as i64;
+        let short_side = leftover_h.min(leftover_v);
+        let long_side = leftover_h.max(leftover_v);
+        match self.heuristic {
+            PackHeuristic::BestShortSideFit => (short_side, long_side),
+            PackHeuristic::BestLongSideFit => (long_side, short_side),
+            PackHeuristic::BestAreaFit => {
+                let area = |r: &Rect| r.width as i64 * r.height as i64;
+                (area(free) - area(placed), short_side)
+            }
+            PackHeuristic::BottomLeft => (placed.y as i64 + placed.height as i64, placed.x as i64),
+            PackHeuristic::ContactPoint => (-self.contact_score(placed), 0),
+        }
+    }
+    
+    /// 与容器边缘及已放置矩形的接触边长
+    fn contact_score(&self, placed: &Rect) -> i64 {
+        let overlap = |a: u32, a_len: u32, b: u32, b_len: u32| {
+            (a as i64 + a_len as i64).min(b as i64 + b_len as i64) - (a as i64).max(b as i64)
+        };
+        let (right, bottom) = (placed.x as u64 + placed.width as u64, placed.y as u64 + placed.height as u64);
+        let mut score = 0;
+        for touches in [placed.x == 0, right == self.width as u64] {
+            score += if touches { placed.height as i64 } else { 0 };
+        }
+        for touches in [placed.y == 0, bottom == self.height as u64] {
+            score += if touches { placed.width as i64 } else { 0 };
+        }
+        for used in &self.used_rects {
+            let (used_right, used_bottom) = (used.x as u64 + used.width as u64, used.y as u64 + used.height as u64);
+            if used.x as u64 == right || used_right == placed.x as u64 {
+                score += overlap(placed.y, placed.height, used.y, used.height).max(0);
+            }
+            if used.y as u64 == bottom || used_bottom == placed.y as u64 {
+                score += overlap(placed.x, placed.width, used.x, used.width).max(0);
+            }
+        }
+        score
+    }
+    
+    /// 比较两个放置方案（先比较主评分，再比较次评分）
+    fn is_better_placement(new_placement: &Placement, current_best: &Option<Placement>) -> bool {
+        match current_best {
+            None => true,
+            Some(current) => (new_placement.score1, new_placement.score2) < (current.score1, current.score2),
+        }
+    }
+    
+    /// 放置矩形并更新空闲区域
+    fn place_rect(&mut self, placed: Rect) {
+        // 分割所有与放置矩形相交的空闲矩形
+        let mut new_free_rects = Vec::new();
+        
+        self.free_rects.retain(|free_rect| {
+            if !placed.intersects(free_rect) {
+                return true; // 不相交，保留
+            }
+            
+            // 分割空闲矩形（四个可能的新矩形）
+            
+            // 上方矩形
+            if placed.y > free_rect.y {
+                new_free_rects.push(Rect::new(
+                    free_rect.x,
+                    free_rect.y,
+                    free_rect.width,
+                    placed.y - free_rect.y,
+                ));
+            }
+            
+            // 下方矩形
+            if placed.y + placed.height < free_rect.y + free_rect.height {
+                new_free_rects.push(Rect::new(
+                    free_rect.x,
+                    placed.y + placed.height,
+                    free_rect.width,
+                    free_rect.y + free_rect.height - placed.y - placed.height,
+                ));
+            }
+            
+            // 左侧矩形
+            if placed.x > free_rect.x {
+                new_free_rects.push(Rect::new(
+                    free_rect.x,
+                    free_rect.y,
+                    placed.x - free_rect.x,
+                    free_rect.height,
+                ));
+            }
+            
+            // 右侧矩形
+            if placed.x + placed.width < free_rect.x + free_rect.width {
+                new_free_rects.push(Rect::new(
+                    placed.x + placed.width,
+                    free_rect.y,
+                    free_rect.x + free_rect.width - placed.x - placed.width,
+                    free_rect.height,
+                ));
+            }
+            
+            false // 移除原始矩形
+        });
+        
+        // 添加新的空闲矩形
+        self.free_rects.append(&mut new_free_rects);
+        
+        // 移除被包含的矩形（优化）
+        self.prune_free_rects();
+        
+        // 记录已使用
+        self.used_rects.push(placed);
+    }
+    
+    /// 移除被其他矩形完全包含的空闲矩形
+    fn prune_free_rects(&mut self) {
+        let len = self.free_rects.len();
+        let mut to_remove = vec![false; len];
+        
+        for i in 0..len {
+            if to_remove[i] {
+                continue;
+            }
+            for j in (i + 1)..len {
+                if to_remove[j] {
+                    continue;
+                }
+                
+                if self.free_rects[i].is_contained_in(&self.free_rects[j]) {
+                    to_remove[i] = true;
+                    break;
+                }
+                if self.free_rects[j].is_contained_in(&self.free_rects[i]) {
+                    to_remove[j] = true;
+                }
+            }
+        }
+        
+        let mut idx = 0;
+        self.free_rects.retain(|_| {
+            let keep = !to_remove[idx];
+            idx += 1;
+            keep
+        });
+    }
+    
+    /// 获取填充率
+    pub fn fill_rate(&self) -> f32 {
+        let total_area = self.width as u64 * self.height as u64;
+        if total_area == 0 {
+            return 0.0;
+        }
+        
+        let used_area: u64 = self.used_rects.iter().map(|r| r.width as u64 * r.height as u64).sum();
+        (used_area as f32 / total_area as f32) * 100.0
+    }
+    
+    /// 获取实际使用的边界
+    pub fn actual_bounds(&self) -> (u32, u32) {
+        if self.used_rects.is_empty() {
+            return (0, 0);
+        }
+        
+        let max_x = self.used_rects.iter().map(|r| r.x + r.width).max().unwrap_or(0);
+        let max_y = self.used_rects.iter().map(|r| r.y + r.height).max().unwrap_or(0);
+        
+        (max_x, max_y)
+    }
+    
+    /// 获取按尺寸约束向上取整后的实际边界
+    pub fn constrained_bounds(&self, constraint: SizeConstraint) -> (u32, u32) {
+        let (width, height) = self.actual_bounds();
+        constraint.apply(width, height)
+    }
+}
+
+/// 计算含间距的精灵面积（超大尺寸时饱和，避免溢出）
+fn padded_area(sprite: &SpriteInput, padding: u32) -> u64 {
+    (sprite.width as u64 + padding as u64).saturating_mul(sprite.height as u64 + padding as u64)
+}
+
+/// 多页打包
+///
+/// 按固定尺寸依次打包，当前页放不下的精灵放入下一页。
+/// 同一亲和分组的精灵总在同一页：当前页放不下整个分组时，分组整体移到后面的页
+///
+/// # Arguments
+/// * `sprites` - 待打包的精灵列表
+/// * `affinity` - 精灵 ID 到亲和分组名称的映射（不在映射中的精灵不受约束）
+/// * `width` / `height` - 每页尺寸
+/// * `options` - 打包器设置
+///
+/// # Returns
+/// * `Option<Vec<Vec<PackedSprite>>>` - 每页的打包结果，None 表示有精灵或分组单独也放不下一页
+pub fn pack_pages(
+    sprites: &[SpriteInput],
+    affinity: &HashMap<String, String>,
+    width: u32,
+    height: u32,
+    options: &PackerOptions,
+) -> Option<Vec<Vec<PackedSprite>>> {
+    let group_of = |id: &str| affinity.get(id).map(String::as_str);
+    let mut pages = Vec::new();
+    let mut remaining = sprites.to_vec();
+    while !remaining.is_empty() {
+        let mut candidates = remaining.clone();
+        let mut isolated = false;
+        let page = loop {
+            let mut packer = MaxRectsPacker::with_options(width, height, options);
+            let page = packer.pack(&candidates);
+            // 只放下一部分的分组
+            let split: HashSet<String> = candidates
+                .iter()
+                .filter(|sprite| !page.iter().any(|packed| packed.id == sprite.id))
+                .filter_map(|sprite| group_of(&sprite.id))
+                .filter(|group| page.iter().any(|packed| group_of(&packed.id) == Some(*group)))
+                .map(str::to_string)
+                .collect();
+            if split.is_empty() {
+                break page;
+            }
+            if isolated {
+                return None;
+            }
+            let first_group = group_of(&candidates[0].id).map(str::to_string);
+            candidates.retain(|sprite| group_of(&sprite.id).is_none_or(|group| !split.contains(group)));
+            if candidates.is_empty() {
+                // 各分组互相挤占时，先单独放入第一个分组
+                isolated = true;
+                candidates = remaining
+                    .iter()
+                    .filter(|sprite| group_of(&sprite.id).map(str::to_string) == first_group)
+                    .cloned()
+                    .collect();
+            }
+        };
+        if page.is_empty() {
+            return None;
+        }
+        remaining.retain(|sprite| !page.iter().any(|packed| packed.id == sprite.id));
+        pages.push(page);
+    }
+    Some(pages)
+}
+
+/// 自动选择最优纹理尺寸
+/// 
+/// # Arguments
+/// * `sprites` - 待打包的精灵列表
+/// * `max_size` - 最大尺寸限制
+/// * `options` - 打包器设置
+/// 
+/// # Returns
+/// * `Option<(u32, u32)>` - 最优尺寸，None 表示无法容纳
+pub fn find_optimal_size(
+    sprites: &[SpriteInput],
+    max_size: u32,
+    options: &PackerOptions,
+) -> Option<(u32, u32)> {
+    // 计算总面积，估算初始尺寸
+    let total_area: u64 = sprites.iter()
+        .map(|s| padded_area(s, options.reserved()))
+        .fold(0, u64::saturating_add);
+    
+    // POT (Power of Two) 尺寸列表
+    let sizes = [128, 256, 512, 1024, 2048, 4096];
+    
+    for &size in &sizes {
+        if size > max_size {
+            break;
+        }
+        
+        // 尝试正方形
+        if size as u64 * size as u64 >= total_area {
+            let mut packer = MaxRectsPacker::with_options(size, size, options);
+            let result = packer.pack(sprites);
+            if result.len() == sprites.len() {
+                return Some((size, size));
+            }
+        }
+    }
+    
+    // 尝试非正方形
+    for &width in &sizes {
+        for &height in &sizes {
+            if width > max_size || height > max_size {
+                continue;
+            }
+            if width as u64 * height as u64 >= total_area {
+                let mut packer = MaxRectsPacker::with_options(width, height, options);
+                let result = packer.pack(sprites);
+                if result.len() == sprites.len() {
+                    return Some((width, height));
+                }
+            }
+        }
+    }
+    
+    None
+}
+
+/// 自动排列全部精灵（合成模式的自动布局）
+///
+/// 用 `find_optimal_size` 选出能放下所有精灵的最小尺寸后打包
+///
+/// # Arguments
+/// * `sprites` - 待排列的精灵列表
+/// * `max_size` - 最大尺寸限制
+/// * `options` - 打包器设置
+///
+/// # Returns
+/// * `Option<(Vec<PackedSprite>, (u32, u32))>` - 各精灵的位置与实际使用的边界，None 表示无法容纳
+pub fn arrange_all(
+    sprites: &[SpriteInput],
+    max_size: u32,
+    options: &PackerOptions,
+) -> Option<(Vec<PackedSprite>, (u32, u32))> {
+    let (width, height) = find_optimal_size(sprites, max_size, options)?;
+    let mut packer = MaxRectsPacker::with_options(width, height, options);
+    let packed = packer.pack(sprites);
+    Some((packed, packer.actual_bounds()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn create_test_sprite(id: &str, width: u32, height: u32) -> SpriteInput {
+        SpriteInput {
+            id: id.to_string(),
+            name: format!("{}.png", id),
+            width,
+            height,
+            original_width: width,
+            original_height: height,
+            offset_x: 0,
+            offset_y: 0,
+            trimmed: false,
+            allow_rotation: true,
+        }
+    }
+    
+    fn options(allow_rotation: bool, padding: u32) -> PackerOptions {
+        PackerOptions { allow_rotation, padding, ..Default::default() }
+    }
+    
+    #[test]
+    fn test_packer_creation() {
+        let packer = MaxRectsPacker::new(512, 512, true, 0);
+        assert_eq!(packer.width, 512);
+        assert_eq!(packer.height, 512);
+        assert_eq!(packer.free_rects.len(), 1);
+    }
+    
+    #[test]
+    fn test_basic_packing() {
+        let sprites = vec![
+            create_test_sprite("a", 100, 100),
+            create_test_sprite("b", 50, 50),
+            create_test_sprite("c", 80, 60),
+        ];
+        
+        let mut packer = MaxRectsPacker::new(512, 512, true, 0);
+        let result = packer.pack(&sprites);
+        
+        assert_eq!(result.len(), 3);
+        
+        // 验证没有重叠
+        for i in 0..result.len() {
+            for j in (i + 1)..result.len() {
+                let r1 = Rect::new(result[i].x, result[i].y, result[i].width, result[i].height);
+                let r2 = Rect::new(result[j].x, result[j].y, result[j].width, result[j].height);
+                assert!(!r1.intersects(&r2), "精灵 {} 和 {} 重叠", i, j);
+            }
+        }
+    }
+    
+    #[test]
+    fn test_fill_rate() {
+        let sprites = vec![
+            create_test_sprite("a", 256, 256),
+            create_test_sprite("b", 256, 256),
+            create_test_sprite("c", 256, 256),
+            create_test_sprite("d", 256, 256),
+        ];
+        
+        let mut packer = MaxRectsPacker::new(512, 512, false, 0);
+        let result = packer.pack(&sprites);
+        
+        assert_eq!(result.len(), 4);
+        assert!((packer.fill_rate() - 100.0).abs() < 0.01);
+    }
+    
+    #[test]
+    fn test_rotation() {
+        // 一个窄长的精灵，需要旋转才能放入
+        let sprites = vec![
+            create_test_sprite("a", 200, 50), // 200x50
+        ];
+        
+        // 150x200 的容器，不旋转无法放入 200 宽的图
+        let mut packer = MaxRectsPacker::new(150, 200, true, 0);
+        let result = packer.pack(&sprites);
+        
+        assert_eq!(result.len(), 1);
+        assert!(result[0].rotated); // 应该被旋转
+    }
+    
+    #[test]
+    fn test_huge_sprites_do_not_overflow() {
+        let sprites = vec![
+            create_test_sprite("a", u32::MAX, u32::MAX),
+            create_test_sprite("b", u32::MAX, 1),
+        ];
+        
+        let mut packer = MaxRectsPacker::new(512, 512, true, 4);
+        assert!(packer.pack(&sprites).is_empty());
+        assert!(find_optimal_size(&sprites, 4096, &options(true, 4)).is_none());
+    }
+    
+    #[test]
+    fn test_optimal_size() {
+        let sprites = vec![
+            create_test_sprite("a", 100, 100),
+            create_test_sprite("b", 100, 100),
+            create_test_sprite("c", 100, 100),
+        ];
+        
+        let size = find_optimal_size(&sprites, 2048, &options(true, 0));
+        assert!(size.is_some());
+        
+        let (w, h) = size.unwrap();
+        assert!(w <= 256 && h <= 256); // 应该选择较小的尺寸
+    }
+    
+    #[test]
+    fn test_arrange_all() {
+        let sprites = vec![
+            create_test_sprite("a", 200, 40),
+            create_test_sprite("b", 40, 40),
+            create_test_sprite("c", 40, 40),
+        ];
+        
+        let (packed, (w, h)) = arrange_all(&sprites, 2048, &options(false, 2)).unwrap();
+        assert_eq!(packed.len(), 3);
+        assert!(packed.iter().all(|p| !p.rotated && p.x + p.width <= w && p.y + p.height <= h));
+        assert!(arrange_all(&sprites, 128, &options(false, 2)).is_none());
+    }
+    
+    #[test]
+    fn test_pack_pages_spills_overflow() {
+        // 每页 128x128 只能放下 4 个 64x64 精灵
+        let sprites: Vec<SpriteInput> = (0..9)
+            .map(|i| create_test_sprite(&format!("s{}", i), 64, 64))
+            .collect();
+        
+        let pages = pack_pages(&sprites, &HashMap::new(), 128, 128, &options(false, 0)).unwrap();
+        assert_eq!(pages.iter().map(Vec::len).collect::<Vec<_>>(), vec![4, 4, 1]);
+        assert_eq!(pages[2][0].id, "s8");
+        
+        let too_big = vec![create_test_sprite("big", 200, 10)];
+        assert!(pack_pages(&too_big, &HashMap::new(), 128, 128, &options(false, 0)).is_none());
+    }
+
+    #[test]
+    fn test_pack_pages_group_affinity() {
+        let sprites: Vec<SpriteInput> = (0..9)
+            .map(|i| create_test_sprite(&format!("s{}", i), 64, 64))
+            .collect();
+        let affinity = |groups: &[(&str, &[usize])]| -> HashMap<String, String> {
+            groups
+                .iter()
+                .flat_map(|(group, ids)| ids.iter().map(move |i| (format!("s{}", i), group.to_string())))
+                .collect()
+        };
+
+        // run 分组放不进第一页剩余的空间，整体移到第二页，空位由未分组的精灵填补
+        let grouped = affinity(&[("walk", &[0, 1, 2]), ("run", &[3, 4, 5])]);
+        let pages = pack_pages(&sprites, &grouped, 128, 128, &options(false, 0)).unwrap();
+        let page_of = |id: &str| pages.iter().position(|page| page.iter().any(|s| s.id == id)).unwrap();
+        assert_eq!(pages.len(), 3);
+        assert!(["s1", "s2"].iter().all(|id| page_of(id) == page_of("s0")));
+        assert!(["s4", "s5"].iter().all(|id| page_of(id) == page_of("s3")));
+        assert_ne!(page_of("s0"), page_of("s3"));
+        assert_eq!(pages[0].len(), 4);
+
+        // 分组单独也放不下一页
+        let too_large = affinity(&[("all", &[0, 1, 2, 3, 4])]);
+        assert!(pack_pages(&sprites, &too_large, 128, 128, &options(false, 0)).is_none());
+    }
+    
+    #[test]
+    fn test_all_heuristics_and_orders_pack_without_overlap() {
+        let sprites: Vec<SpriteInput> = (0..12)
+            .map(|i| create_test_sprite(&format!("s{}", i), 10 + i * 7 % 40, 8 + i * 11 % 30))
+            .collect();
+        for heuristic in PackHeuristic::ALL {
+            for sort in SortOrder::ALL {
+                let options = PackerOptions { allow_rotation: true, padding: 1, heuristic, sort, ..Default::default() };
+                let result = MaxRectsPacker::with_options(256, 256, &options).pack(&sprites);
+                assert_eq!(result.len(), sprites.len(), "{:?} {:?}", heuristic, sort);
+                for (i, a) in result.iter().enumerate() {
+                    for b in &result[i + 1..] {
+                        let ra = Rect::new(a.x, a.y, a.width, a.height);
+                        let rb = Rect::new(b.x, b.y, b.width, b.height);
+                        assert!(!ra.intersects(&rb), "{:?} {:?}: {} / {}", heuristic, sort, a.id, b.id);
+                    }
+                }
+            }
+        }
+    }
+    
+    #[test]
+    fn test_bottom_left_fills_top_row_first() {
+        let sprites: Vec<SpriteInput> = (0..3).map(|i| create_test_sprite(&format!("s{}", i), 20, 20)).collect();
+        let mut packer = MaxRectsPacker::new(100, 100, false, 0).with_heuristic(PackHeuristic::BottomLeft);
+        let result = packer.pack(&sprites);
+        assert!(result.iter().all(|s| s.y == 0));
+    }
+    
+    #[test]
+    fn test_size_constraint() {
+        assert_eq!(SizeConstraint::Pot.apply(130, 60), (256, 64));
+        assert_eq!(SizeConstraint::Square.apply(130, 60), (256, 256));
+        assert_eq!(SizeConstraint::MultipleOf4.apply(130, 60), (132, 60));
+        assert_eq!(SizeConstraint::Any.apply(130, 0), (130, 1));
+        assert_eq!(SizeConstraint::Pot.apply(u32::MAX, 1), (u32::MAX, 1));
+        
+        let mut packer = MaxRectsPacker::new(512, 512, false, 0);
+        packer.pack(&[create_test_sprite("a", 100, 30)]);
+        assert_eq!(packer.constrained_bounds(SizeConstraint::MultipleOf4), (100, 32));
+    }
+    
+    #[test]
+    fn test_extrude_reserves_border() {
+        // 每个 60x60 精灵四周挤出 2 像素后占用 64x64，128x128 恰好放下 4 个
+        let sprites: Vec<SpriteInput> = (0..4)
+            .map(|i| create_test_sprite(&format!("s{}", i), 60, 60))
+            .collect();
+        let mut packer = MaxRectsPacker::new(128, 128, false, 0).with_extrude(2);
+        let result = packer.pack(&sprites);
+        assert_eq!(result.len(), 4);
+        for sprite in &result {
+            // 位置指向内部区域
+            assert_eq!((sprite.x % 64, sprite.y % 64), (2, 2));
+            assert_eq!((sprite.width, sprite.height), (60, 60));
+        }
+        
+        let mut packer = MaxRectsPacker::new(128, 128, false, 0).with_extrude(3);
+        assert_eq!(packer.pack(&sprites).len(), 1);
+    }
+}
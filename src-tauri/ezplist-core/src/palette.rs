@@ -0,0 +1,151 @@
+/// 调色板 (Palette)
+///
+/// 提取精灵使用的颜色，并按颜色映射表生成换色变体（如不同阵营的单位配色）。
+/// 颜色按 RGB 精确匹配，换色时保留原像素的 alpha
+
+use image::RgbaImage;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 调色板中的一种颜色
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaletteEntry {
+    /// RGB 颜色
+    pub color: [u8; 3],
+    /// 使用该颜色的像素数
+    pub pixel_count: u64,
+}
+
+/// 调色板提取结果
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaletteResult {
+    /// 按使用次数降序排列的颜色（最多 `max_colors` 种）
+    pub colors: Vec<PaletteEntry>,
+    /// 实际使用的颜色总数
+    pub total_colors: usize,
+}
+
+/// 颜色映射（旧颜色 -> 新颜色）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct ColorMapping {
+    pub from: [u8; 3],
+    pub to: [u8; 3],
+}
+
+/// 提取调色板
+///
+/// 完全透明的像素不计入
+///
+/// # Arguments
+/// * `images` - 精灵图像
+/// * `max_colors` - 返回的最大颜色数
+///
+/// # Returns
+/// * `PaletteResult` - 调色板
+pub fn extract_palette(images: &[RgbaImage], max_colors: usize) -> PaletteResult {
+    let mut counts: HashMap<[u8; 3], u64> = HashMap::new();
+    for image in images {
+        for pixel in image.pixels().filter(|p| p[3] > 0) {
+            *counts.entry([pixel[0], pixel[1], pixel[2]]).or_default() += 1;
+        }
+    }
+
+    let total_colors = counts.len();
+    let mut colors: Vec<PaletteEntry> = counts
+        .into_iter()
+        .map(|(color, pixel_count)| PaletteEntry { color, pixel_count })
+        .collect();
+    colors.sort_by(|a, b| b.pixel_count.cmp(&a.pixel_count).then(a.color.cmp(&b.color)));
+    colors.truncate(max_colors);
+
+    PaletteResult { colors, total_colors }
+}
+
+/// 校验颜色映射表
+///
+/// # Returns
+/// * `Result<(), String>` - 映射表为空或同一颜色映射到多个目标时返回错误信息
+pub fn validate_mapping(mapping: &[ColorMapping]) -> Result<(), String> {
+    if mapping.is_empty() {
+        return Err("颜色映射表为空".to_string());
+    }
+    let mut targets: HashMap<[u8; 3], [u8; 3]> = HashMap::new();
+    for m in mapping {
+        if let Some(existing) = targets.insert(m.from, m.to) {
+            if existing != m.to {
+                return Err(format!("颜色 {:?} 被映射到多个目标", m.from));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 按映射表换色
+///
+/// # Arguments
+/// * `image` - 原图像
+/// * `mapping` - 颜色映射表
+///
+/// # Returns
+/// * `RgbaImage` - 换色后的图像（未出现在映射表中的颜色保持不变）
+pub fn apply_palette_swap(image: &RgbaImage, mapping: &[ColorMapping]) -> RgbaImage {
+    let table: HashMap<[u8; 3], [u8; 3]> = mapping.iter().map(|m| (m.from, m.to)).collect();
+
+    let mut output = image.clone();
+    for pixel in output.pixels_mut() {
+        if let Some(to) = table.get(&[pixel[0], pixel[1], pixel[2]]) {
+            pixel[0] = to[0];
+            pixel[1] = to[1];
+            pixel[2] = to[2];
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    fn sample() -> RgbaImage {
+        let mut img = RgbaImage::from_pixel(4, 1, Rgba([200, 0, 0, 255]));
+        img.put_pixel(1, 0, Rgba([200, 0, 0, 128]));
+        img.put_pixel(2, 0, Rgba([0, 0, 0, 255]));
+        img.put_pixel(3, 0, Rgba([10, 10, 10, 0]));
+        img
+    }
+
+    #[test]
+    fn test_extract_palette() {
+        let palette = extract_palette(&[sample()], 16);
+        assert_eq!(palette.total_colors, 2);
+        assert_eq!(palette.colors[0], PaletteEntry { color: [200, 0, 0], pixel_count: 2 });
+        assert_eq!(palette.colors[1].color, [0, 0, 0]);
+
+        let limited = extract_palette(&[sample()], 1);
+        assert_eq!(limited.colors.len(), 1);
+        assert_eq!(limited.total_colors, 2);
+    }
+
+    #[test]
+    fn test_palette_swap_keeps_alpha() {
+        let mapping = [ColorMapping { from: [200, 0, 0], to: [0, 0, 200] }];
+        let swapped = apply_palette_swap(&sample(), &mapping);
+
+        assert_eq!(swapped.get_pixel(0, 0), &Rgba([0, 0, 200, 255]));
+        assert_eq!(swapped.get_pixel(1, 0), &Rgba([0, 0, 200, 128]));
+        assert_eq!(swapped.get_pixel(2, 0), &Rgba([0, 0, 0, 255]));
+    }
+
+    #[test]
+    fn test_validate_mapping() {
+        let a = ColorMapping { from: [1, 2, 3], to: [4, 5, 6] };
+        let b = ColorMapping { from: [1, 2, 3], to: [7, 8, 9] };
+        assert!(validate_mapping(&[a]).is_ok());
+        assert!(validate_mapping(&[a, a]).is_ok());
+        assert!(validate_mapping(&[a, b]).is_err());
+        assert!(validate_mapping(&[]).is_err());
+    }
+}
@@ -0,0 +1,412 @@
+/// 物理形状导出 (Physics Shape Export)
+///
+/// 将帧轮廓转换为 Cocos2d-x PhysicsEditor（GB2ShapeCache）格式的 plist。
+/// 轮廓多边形先用耳切法三角化，再用 Hertel-Mehlhorn 算法合并为
+/// 不超过 `max_polygon_vertices` 个顶点的凸多边形（Box2D 要求凸多边形、逆时针、最多 8 个顶点）
+
+use crate::outline::Point;
+use serde::Deserialize;
+
+/// 浮点比较容差
+const EPSILON: f32 = 1e-4;
+
+/// 夹具参数
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FixtureParams {
+    /// 密度
+    pub density: f64,
+    /// 摩擦系数
+    pub friction: f64,
+    /// 弹性系数
+    pub restitution: f64,
+}
+
+impl Default for FixtureParams {
+    /// 与 PhysicsEditor 的默认值一致
+    fn default() -> Self {
+        Self {
+            density: 2.0,
+            friction: 0.0,
+            restitution: 0.0,
+        }
+    }
+}
+
+/// 物理导出选项
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhysicsExportOptions {
+    /// 像素/米比例（写入 metadata，由引擎换算）
+    pub ptm_ratio: f64,
+    /// 锚点（相对帧尺寸，默认中心）
+    pub anchor: (f32, f32),
+    /// 单个凸多边形的最大顶点数
+    pub max_polygon_vertices: usize,
+}
+
+impl Default for PhysicsExportOptions {
+    fn default() -> Self {
+        Self {
+            ptm_ratio: 32.0,
+            anchor: (0.5, 0.5),
+            max_polygon_vertices: 8,
+        }
+    }
+}
+
+/// 物理刚体（一帧对应一个刚体）
+#[derive(Debug, Clone)]
+pub struct PhysicsBody {
+    /// 刚体名称（通常为帧名称）
+    pub name: String,
+    /// 帧宽度
+    pub width: u32,
+    /// 帧高度
+    pub height: u32,
+    /// 帧轮廓（图像坐标，原点左上角，y 向下）
+    pub outline: Vec<Vec<Point>>,
+    /// 夹具参数
+    pub fixture: FixtureParams,
+}
+
+/// 生成 PhysicsEditor 格式的 plist
+///
+/// 每个刚体生成一个多边形夹具，坐标以锚点为原点、y 轴向上
+///
+/// # Arguments
+/// * `bodies` - 刚体列表
+/// * `options` - 导出选项
+///
+/// # Returns
+/// * `Result<String, String>` - plist XML 内容或错误信息
+pub fn generate_physics_plist(bodies: &[PhysicsBody], options: &PhysicsExportOptions) -> Result<String, String> {
+    let mut bodies_dict = plist::Dictionary::new();
+
+    for body in bodies {
+        let anchor_x = options.anchor.0 * body.width as f32;
+        let anchor_y = options.anchor.1 * body.height as f32;
+
+        let polygons: Vec<plist::Value> = body
+            .outline
+            .iter()
+            .flat_map(|outline| {
+                let local: Vec<Point> = outline
+                    .iter()
+                    .map(|p| Point {
+                        x: p.x - anchor_x,
+                        y: (body.height as f32 - p.y) - anchor_y,
+                    })
+                    .collect();
+                decompose_convex(&local, options.max_polygon_vertices)
+            })
+            .map(|polygon| {
+                plist::Value::Array(
+                    polygon
+                        .iter()
+                        .map(|p| plist::Value::String(format!("{{ {:.5},{:.5} }}", p.x, p.y)))
+                        .collect(),
+                )
+            })
+            .collect();
+
+        let mut fixture = plist::Dictionary::new();
+        fixture.insert("density".to_string(), plist::Value::Real(body.fixture.density));
+        fixture.insert("friction".to_string(), plist::Value::Real(body.fixture.friction));
+        fixture.insert("restitution".to_string(), plist::Value::Real(body.fixture.restitution));
+        fixture.insert("filter_categoryBits".to_string(), plist::Value::Integer(1.into()));
+        fixture.insert("filter_groupIndex".to_string(), plist::Value::Integer(0.into()));
+        fixture.insert("filter_maskBits".to_string(), plist::Value::Integer(65535.into()));
+        fixture.insert("isSensor".to_string(), plist::Value::Boolean(false));
+        fixture.insert("id".to_string(), plist::Value::String(String::new()));
+        fixture.insert("fixture_type".to_string(), plist::Value::String("POLYGON".to_string()));
+        fixture.insert("polygons".to_string(), plist::Value::Array(polygons));
+
+        let mut body_dict = plist::Dictionary::new();
+        body_dict.insert(
+            "anchorpoint".to_string(),
+            plist::Value::String(format!("{{ {:.4},{:.4} }}", options.anchor.0, options.anchor.1)),
+        );
+        body_dict.insert(
+            "fixtures".to_string(),
+            plist::Value::Array(vec![plist::Value::Dictionary(fixture)]),
+        );
+
+        bodies_dict.insert(body.name.clone(), plist::Value::Dictionary(body_dict));
+    }
+
+    let mut metadata = plist::Dictionary::new();
+    metadata.insert("format".to_string(), plist::Value::Integer(1.into()));
+    metadata.insert("ptm_ratio".to_string(), plist::Value::Real(options.ptm_ratio));
+
+    let mut root = plist::Dictionary::new();
+    root.insert("bodies".to_string(), plist::Value::Dictionary(bodies_dict));
+    root.insert("metadata".to_string(), plist::Value::Dictionary(metadata));
+
+    let mut buf = Vec::new();
+    plist::to_writer_xml(&mut buf, &plist::Value::Dictionary(root))
+        .map_err(|e| format!("序列化 Plist 失败: {}", e))?;
+
+    String::from_utf8(buf).map_err(|e| format!("转换 Plist 编码失败: {}", e))
+}
+
+/// 将简单多边形分解为凸多边形
+///
+/// 无法三角化的输入（自相交等）退化为使用凸包
+///
+/// # Arguments
+/// * `polygon` - 多边形顶点（任意方向）
+/// * `max_vertices` - 单个凸多边形的最大顶点数（至少为 3）
+///
+/// # Returns
+/// * `Vec<Vec<Point>>` - 逆时针排列的凸多边形（y 轴向上时）
+pub fn decompose_convex(polygon: &[Point], max_vertices: usize) -> Vec<Vec<Point>> {
+    let max_vertices = max_vertices.max(3);
+    let mut points = remove_collinear(polygon);
+    if points.len() < 3 {
+        return Vec::new();
+    }
+    if signed_area(&points) < 0.0 {
+        points.reverse();
+    }
+
+    if is_convex(&points) {
+        return split_convex(points, max_vertices);
+    }
+
+    let Some(triangles) = triangulate(&points) else {
+        return split_convex(convex_hull(&points), max_vertices);
+    };
+
+    let mut pieces: Vec<Vec<usize>> = triangles.iter().map(|t| t.to_vec()).collect();
+    'merge: loop {
+        for i in 0..pieces.len() {
+            for j in (i + 1)..pieces.len() {
+                if let Some(merged) = try_merge(&pieces[i], &pieces[j], &points, max_vertices) {
+                    pieces[i] = merged;
+                    pieces.remove(j);
+                    continue 'merge;
+                }
+            }
+        }
+        break;
+    }
+
+    pieces
+        .into_iter()
+        .map(|piece| remove_collinear(&piece.iter().map(|&i| points[i]).collect::<Vec<_>>()))
+        .filter(|piece| piece.len() >= 3)
+        .collect()
+}
+
+/// 多边形有向面积（逆时针为正）
+pub fn signed_area(points: &[Point]) -> f32 {
+    let n = points.len();
+    (0..n)
+        .map(|i| {
+            let (a, b) = (points[i], points[(i + 1) % n]);
+            a.x * b.y - b.x * a.y
+        })
+        .sum::<f32>()
+        / 2.0
+}
+
+fn cross(o: Point, a: Point, b: Point) -> f32 {
+    (a.x - o.x) * (b.y - o.y) - (a.y - o.y) * (b.x - o.x)
+}
+
+/// 去除重复与共线的顶点
+fn remove_collinear(points: &[Point]) -> Vec<Point> {
+    let mut result: Vec<Point> = points.to_vec();
+    let mut changed = true;
+    while changed && result.len() >= 3 {
+        changed = false;
+        let n = result.len();
+        for i in 0..n {
+            let (prev, cur, next) = (result[(i + n - 1) % n], result[i], result[(i + 1) % n]);
+            if cur == prev || cross(prev, cur, next).abs() < EPSILON {
+                result.remove(i);
+                changed = true;
+                break;
+            }
+        }
+    }
+    result
+}
+
+fn is_convex(points: &[Point]) -> bool {
+    let n = points.len();
+    (0..n).all(|i| cross(points[(i + n - 1) % n], points[i], points[(i + 1) % n]) >= -EPSILON)
+}
+
+/// 将顶点过多的凸多边形按扇形拆分
+fn split_convex(mut points: Vec<Point>, max_vertices: usize) -> Vec<Vec<Point>> {
+    let mut pieces = Vec::new();
+    while points.len() > max_vertices {
+        pieces.push(points[..max_vertices].to_vec());
+        let mut rest = vec![points[0]];
+        rest.extend_from_slice(&points[max_vertices - 1..]);
+        points = rest;
+    }
+    if points.len() >= 3 {
+        pieces.push(points);
+    }
+    pieces
+}
+
+fn point_in_triangle(p: Point, a: Point, b: Point, c: Point) -> bool {
+    cross(a, b, p) >= -EPSILON && cross(b, c, p) >= -EPSILON && cross(c, a, p) >= -EPSILON
+}
+
+/// 耳切法三角化（输入为逆时针），失败时返回 None
+fn triangulate(points: &[Point]) -> Option<Vec<[usize; 3]>> {
+    let mut remaining: Vec<usize> = (0..points.len()).collect();
+    let mut triangles = Vec::new();
+
+    while remaining.len() > 3 {
+        let n = remaining.len();
+        let ear = (0..n).find_map(|i| {
+            let (a, b, c) = (remaining[(i + n - 1) % n], remaining[i], remaining[(i + 1) % n]);
+            let (pa, pb, pc) = (points[a], points[b], points[c]);
+            let turn = cross(pa, pb, pc);
+            if turn.abs() < EPSILON {
+                // 共线顶点直接移除
+                return Some((i, None));
+            }
+            if turn < 0.0 {
+                return None;
+            }
+            let blocked = remaining.iter().any(|&k| {
+                let p = points[k];
+                k != a && k != b && k != c && p != pa && p != pb && p != pc && point_in_triangle(p, pa, pb, pc)
+            });
+            (!blocked).then_some((i, Some([a, b, c])))
+        })?;
+
+        if let Some(triangle) = ear.1 {
+            triangles.push(triangle);
+        }
+        remaining.remove(ear.0);
+    }
+
+    if cross(points[remaining[0]], points[remaining[1]], points[remaining[2]]) > EPSILON {
+        triangles.push([remaining[0], remaining[1], remaining[2]]);
+    }
+    Some(triangles)
+}
+
+/// 尝试沿公共边合并两个凸多边形（Hertel-Mehlhorn）
+fn try_merge(p: &[usize], q: &[usize], points: &[Point], max_vertices: usize) -> Option<Vec<usize>> {
+    if p.len() + q.len() - 2 > max_vertices {
+        return None;
+    }
+
+    for i in 0..p.len() {
+        let (a, b) = (p[i], p[(i + 1) % p.len()]);
+        let Some(j) = (0..q.len()).find(|&j| q[j] == b && q[(j + 1) % q.len()] == a) else {
+            continue;
+        };
+
+        // p 从 b 走到 a，再接上 q 中除 a、b 以外的顶点
+        let mut merged: Vec<usize> = (0..p.len()).map(|k| p[(i + 1 + k) % p.len()]).collect();
+        merged.extend((2..q.len()).map(|k| q[(j + k) % q.len()]));
+
+        let merged_points: Vec<Point> = merged.iter().map(|&k| points[k]).collect();
+        return is_convex(&merged_points).then_some(merged);
+    }
+    None
+}
+
+/// 凸包（Andrew 单调链），逆时针
+fn convex_hull(points: &[Point]) -> Vec<Point> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    let half_hull = |points: &mut dyn Iterator<Item = Point>| {
+        let mut chain: Vec<Point> = Vec::new();
+        for p in points {
+            while chain.len() >= 2 && cross(chain[chain.len() - 2], chain[chain.len() - 1], p) <= 0.0 {
+                chain.pop();
+            }
+            chain.push(p);
+        }
+        chain.pop();
+        chain
+    };
+
+    let mut hull = half_hull(&mut sorted.iter().copied());
+    hull.extend(half_hull(&mut sorted.iter().rev().copied()));
+    hull
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pts(coords: &[(f32, f32)]) -> Vec<Point> {
+        coords.iter().map(|&(x, y)| Point { x, y }).collect()
+    }
+
+    fn total_area(pieces: &[Vec<Point>]) -> f32 {
+        pieces.iter().map(|p| signed_area(p)).sum()
+    }
+
+    #[test]
+    fn test_convex_polygon_is_kept() {
+        // 顺时针输入被翻转为逆时针
+        let square = pts(&[(0.0, 0.0), (0.0, 2.0), (2.0, 2.0), (2.0, 0.0)]);
+        let pieces = decompose_convex(&square, 8);
+        assert_eq!(pieces.len(), 1);
+        assert_eq!(pieces[0].len(), 4);
+        assert!(signed_area(&pieces[0]) > 0.0);
+    }
+
+    #[test]
+    fn test_concave_polygon_is_split() {
+        let l_shape = pts(&[(0.0, 0.0), (3.0, 0.0), (3.0, 1.0), (1.0, 1.0), (1.0, 3.0), (0.0, 3.0)]);
+        let pieces = decompose_convex(&l_shape, 8);
+        assert_eq!(pieces.len(), 2);
+        assert!(pieces.iter().all(|p| is_convex(p)));
+        assert!((total_area(&pieces) - 5.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_vertex_limit() {
+        // 12 边形需要拆分为不超过 8 个顶点的多边形
+        let polygon: Vec<Point> = (0..12)
+            .map(|i| {
+                let angle = i as f32 * std::f32::consts::TAU / 12.0;
+                Point {
+                    x: angle.cos() * 10.0,
+                    y: angle.sin() * 10.0,
+                }
+            })
+            .collect();
+        let pieces = decompose_convex(&polygon, 8);
+        assert!(pieces.len() >= 2);
+        assert!(pieces.iter().all(|p| p.len() <= 8 && is_convex(p)));
+        assert!((total_area(&pieces) - signed_area(&polygon)).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_physics_plist_structure() {
+        let body = PhysicsBody {
+            name: "hero".to_string(),
+            width: 4,
+            height: 4,
+            outline: vec![pts(&[(0.0, 0.0), (0.0, 4.0), (4.0, 4.0), (4.0, 0.0)])],
+            fixture: FixtureParams::default(),
+        };
+        let xml = generate_physics_plist(&[body], &PhysicsExportOptions::default()).unwrap();
+
+        assert!(xml.contains("<key>bodies</key>"));
+        assert!(xml.contains("<key>hero</key>"));
+        assert!(xml.contains("<string>POLYGON</string>"));
+        assert!(xml.contains("<string>{ -2.00000,2.00000 }</string>"));
+        assert!(xml.contains("<key>ptm_ratio</key>"));
+    }
+}
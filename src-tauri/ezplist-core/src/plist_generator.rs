@@ -0,0 +1,446 @@
+/// Plist 生成器
+///
+/// 生成 Cocos2d-x 的 .plist 文件：默认 Format 3 XML，
+/// 也可输出旧版 Format 2 键名或二进制 plist
+
+use crate::atlas_patch::offset_origin;
+use crate::hash::SmartUpdate;
+use crate::texture_format::PixelFormat;
+use crate::types::{NineSlice, PackedSprite};
+use plist::{Dictionary, Value};
+use serde::{Deserialize, Serialize};
+
+/// Plist 输出格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PlistFormat {
+    /// XML，Format 3 键名（`textureRect`、`spriteOffset` 等）
+    #[default]
+    XmlV3,
+    /// XML，Format 2 键名（`frame`、`offset`、`sourceSize` 等），供旧版工具链使用
+    XmlV2,
+    /// 二进制 plist，Format 3 键名
+    Binary,
+}
+
+impl PlistFormat {
+    /// 写入 metadata 的 format 版本号
+    pub fn version(self) -> i64 {
+        match self {
+            PlistFormat::XmlV2 => 2,
+            PlistFormat::XmlV3 | PlistFormat::Binary => 3,
+        }
+    }
+}
+
+/// Plist 中的一帧
+///
+/// 所有生成 Cocos2d-x plist 的命令（打包导出、切分、合成）都先把帧转换为这个结构，
+/// 键名、旋转与格式差异只在 [`generate_plist`] 中处理
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlistFrame {
+    /// 帧名称
+    pub name: String,
+    /// 纹理中的位置与帧尺寸 (x, y, 宽, 高)，尺寸为未旋转时的帧尺寸（即 spriteSize）
+    pub rect: (u32, u32, u32, u32),
+    /// 裁剪偏移（Cocos2d-x Y 轴向上）
+    pub offset: (i32, i32),
+    /// 原始尺寸（spriteSourceSize）
+    pub source_size: (u32, u32),
+    /// 是否在纹理中顺时针旋转了 90 度
+    pub rotated: bool,
+    /// 水平翻转（纹理区域为源帧）
+    pub flip_x: bool,
+    /// 垂直翻转
+    pub flip_y: bool,
+    /// 与该帧共用纹理区域的其他名称
+    pub aliases: Vec<String>,
+    /// 锚点（相对原始尺寸的 0~1 坐标，Y 轴向上）
+    pub anchor: Option<(f32, f32)>,
+    /// 九宫格边距（相对帧尺寸）
+    pub nine_slice: Option<NineSlice>,
+}
+
+impl PlistFrame {
+    /// 未裁剪、未旋转的帧（切分与合成的输出）
+    pub fn untrimmed(name: impl Into<String>, x: u32, y: u32, width: u32, height: u32) -> Self {
+        Self {
+            name: name.into(),
+            rect: (x, y, width, height),
+            offset: (0, 0),
+            source_size: (width, height),
+            rotated: false,
+            flip_x: false,
+            flip_y: false,
+            aliases: Vec::new(),
+            anchor: None,
+            nine_slice: None,
+        }
+    }
+
+    /// 帧尺寸（spriteSize）
+    pub fn size(&self) -> (u32, u32) {
+        (self.rect.2, self.rect.3)
+    }
+}
+
+impl From<&PackedSprite> for PlistFrame {
+    /// `PackedSprite` 的宽高为在纹理中占用的尺寸，旋转帧需要互换
+    fn from(sprite: &PackedSprite) -> Self {
+        let (width, height) = sprite.frame_size();
+        Self {
+            name: sprite.name.clone(),
+            rect: (sprite.x, sprite.y, width, height),
+            offset: (sprite.offset_x, sprite.offset_y),
+            source_size: (sprite.original_width, sprite.original_height),
+            rotated: sprite.rotated,
+            flip_x: sprite.flip_x,
+            flip_y: sprite.flip_y,
+            aliases: Vec::new(),
+            anchor: sprite.anchor,
+            nine_slice: sprite.nine_slice,
+        }
+    }
+}
+
+/// 生成 Plist 文件内容
+///
+/// 帧按传入顺序写入；`aliases`、`flipX`、`flipY`、`anchor`、`capInsets` 只在非空 / 为 true 时写入，
+/// 元数据中的 `pixelFormat` 只在不是 RGBA8888 时写入
+///
+/// # Arguments
+/// * `frames` - 帧列表
+/// * `texture_width` / `texture_height` - 纹理尺寸
+/// * `texture_name` - 纹理文件名
+/// * `format` - 输出格式
+/// * `pixel_format` - 纹理像素格式
+/// * `smartupdate` - SmartUpdate 哈希的纹理像素与模式
+///
+/// # Returns
+/// * `Result<Vec<u8>, String>` - 文件内容（XML 或二进制）；帧名称或别名重复、
+///   九宫格边距超出帧尺寸时返回错误
+pub fn generate_plist(
+    frames: &[PlistFrame],
+    texture_width: u32,
+    texture_height: u32,
+    texture_name: &str,
+    format: PlistFormat,
+    pixel_format: PixelFormat,
+    smartupdate: SmartUpdate,
+) -> Result<Vec<u8>, String> {
+    let mut frames_dict = Dictionary::new();
+    for frame in frames {
+        let mut dict = match format.version() {
+            2 => frame_v2(frame),
+            _ => frame_v3(frame),
+        };
+        if !frame.aliases.is_empty() {
+            let aliases = frame.aliases.iter().map(|alias| Value::String(alias.clone())).collect();
+            dict.insert("aliases".to_string(), Value::Array(aliases));
+        }
+        // 翻转帧的纹理区域为源帧，只在需要翻转时写入（Cocos2d-x 忽略未知键）
+        if frame.flip_x {
+            dict.insert("flipX".to_string(), Value::Boolean(true));
+        }
+        if frame.flip_y {
+            dict.insert("flipY".to_string(), Value::Boolean(true));
+        }
+        // Cocos2d-x 读取帧的 anchor 作为精灵的默认锚点，未设置时不写入
+        if let Some((x, y)) = frame.anchor {
+            dict.insert("anchor".to_string(), pair(x, y));
+        }
+        // 九宫格写为中间可拉伸区域，与 Scale9Sprite 的 capInsets 相同，坐标相对帧尺寸
+        if let Some(nine_slice) = frame.nine_slice {
+            let (width, height) = frame.size();
+            nine_slice.validate(width, height).map_err(|e| format!("帧 {}: {}", frame.name, e))?;
+            let (x, y, center_width, center_height) = nine_slice.center(width, height);
+            dict.insert("capInsets".to_string(), rect(x, y, center_width, center_height));
+        }
+        if frames_dict.insert(frame.name.clone(), Value::Dictionary(dict)).is_some() {
+            return Err(format!("帧名称重复: {}", frame.name));
+        }
+    }
+    if let Some(alias) = frames.iter().flat_map(|f| &f.aliases).find(|alias| frames_dict.contains_key(alias.as_str())) {
+        return Err(format!("别名与帧名称重复: {}", alias));
+    }
+
+    let mut metadata = Dictionary::new();
+    metadata.insert("format".to_string(), Value::Integer(format.version().into()));
+    if pixel_format != PixelFormat::Rgba8888 {
+        metadata.insert("pixelFormat".to_string(), Value::String(pixel_format.name().to_string()));
+    }
+    metadata.insert("realTextureFileName".to_string(), Value::String(texture_name.to_string()));
+    metadata.insert("size".to_string(), pair(texture_width, texture_height));
+    metadata.insert(
+        "smartupdate".to_string(),
+        Value::String(smartupdate.hash(texture_name, &layout_text(frames, texture_width, texture_height, pixel_format))),
+    );
+    metadata.insert("textureFileName".to_string(), Value::String(texture_name.to_string()));
+
+    let mut root = Dictionary::new();
+    root.insert("frames".to_string(), Value::Dictionary(frames_dict));
+    root.insert("metadata".to_string(), Value::Dictionary(metadata));
+
+    let root = Value::Dictionary(root);
+    let mut buf = Vec::new();
+    match format {
+        PlistFormat::Binary => plist::to_writer_binary(&mut buf, &root),
+        PlistFormat::XmlV3 | PlistFormat::XmlV2 => plist::to_writer_xml(&mut buf, &root),
+    }
+    .map_err(|e| format!("序列化 Plist 失败: {}", e))?;
+    Ok(buf)
+}
+
+/// 由已打包的精灵生成 Plist 文件内容
+///
+/// # Arguments
+/// * `sprites` - 已打包的精灵
+/// * `texture_width` / `texture_height` - 纹理尺寸
+/// * `texture_name` - 纹理文件名
+/// * `format` - 输出格式
+/// * `pixel_format` - 纹理像素格式
+/// * `smartupdate` - SmartUpdate 哈希的纹理像素与模式
+///
+/// # Returns
+/// * `Result<Vec<u8>, String>` - 文件内容（XML 或二进制）或错误信息
+pub fn generate_plist_data(
+    sprites: &[PackedSprite],
+    texture_width: u32,
+    texture_height: u32,
+    texture_name: &str,
+    format: PlistFormat,
+    pixel_format: PixelFormat,
+    smartupdate: SmartUpdate,
+) -> Result<Vec<u8>, String> {
+    let frames: Vec<PlistFrame> = sprites.iter().map(PlistFrame::from).collect();
+    generate_plist(&frames, texture_width, texture_height, texture_name, format, pixel_format, smartupdate)
+}
+
+/// Format 3 的单帧字典
+fn frame_v3(frame: &PlistFrame) -> Dictionary {
+    let (x, y, width, height) = frame.rect;
+    let mut dict = Dictionary::new();
+    dict.insert("spriteOffset".to_string(), pair(frame.offset.0, frame.offset.1));
+    dict.insert("spriteSize".to_string(), pair(width, height));
+    dict.insert("spriteSourceSize".to_string(), pair(frame.source_size.0, frame.source_size.1));
+    dict.insert("textureRect".to_string(), rect(x, y, width, height));
+    dict.insert("textureRotated".to_string(), Value::Boolean(frame.rotated));
+    dict
+}
+
+/// Format 2 的单帧字典
+///
+/// `sourceColorRect` 为裁剪后内容在原图中的矩形，按偏移的取整方式反推（Cocos2d-x Y 轴向上）；
+/// 偏移与尺寸不一致时（如手工编辑的帧）退回为居中位置加偏移
+fn frame_v2(frame: &PlistFrame) -> Dictionary {
+    let (x, y, width, height) = frame.rect;
+    let (source_width, source_height) = frame.source_size;
+    let (color_x, color_y) = offset_origin(frame.size(), frame.source_size, frame.offset)
+        .map(|(left, top)| (left as i64, top as i64))
+        .unwrap_or_else(|| {
+            let spare_x = source_width.saturating_sub(width) as i64;
+            let spare_y = source_height.saturating_sub(height) as i64;
+            (
+                (spare_x / 2 + frame.offset.0 as i64).clamp(0, spare_x),
+                (spare_y / 2 - frame.offset.1 as i64).clamp(0, spare_y),
+            )
+        });
+
+    let mut dict = Dictionary::new();
+    dict.insert("frame".to_string(), rect(x, y, width, height));
+    dict.insert("offset".to_string(), pair(frame.offset.0, frame.offset.1));
+    dict.insert("rotated".to_string(), Value::Boolean(frame.rotated));
+    dict.insert("sourceColorRect".to_string(), rect(color_x, color_y, width, height));
+    dict.insert("sourceSize".to_string(), pair(source_width, source_height));
+    dict
+}
+
+/// `{{x,y},{w,h}}` 形式的字符串值
+fn rect(x: impl std::fmt::Display, y: impl std::fmt::Display, width: u32, height: u32) -> Value {
+    Value::String(format!("{{{{{},{}}},{{{},{}}}}}", x, y, width, height))
+}
+
+/// `{a,b}` 形式的字符串值
+fn pair(a: impl std::fmt::Display, b: impl std::fmt::Display) -> Value {
+    Value::String(format!("{{{},{}}}", a, b))
+}
+
+/// SmartUpdate 哈希使用的帧布局描述
+///
+/// 每帧一行，与键名版本和编码无关：同一份图集导出为 Format 2、Format 3 或二进制时哈希相同
+fn layout_text(frames: &[PlistFrame], texture_width: u32, texture_height: u32, pixel_format: PixelFormat) -> String {
+    let mut text = format!("{}x{} {}\n", texture_width, texture_height, pixel_format.name());
+    for frame in frames {
+        let (x, y, width, height) = frame.rect;
+        text.push_str(&format!(
+            "{} {},{},{},{} {},{} {},{} {} {} {} {}",
+            frame.name,
+            x,
+            y,
+            width,
+            height,
+            frame.offset.0,
+            frame.offset.1,
+            frame.source_size.0,
+            frame.source_size.1,
+            frame.rotated,
+            frame.flip_x,
+            frame.flip_y,
+            frame.aliases.join(","),
+        ));
+        if let Some((x, y)) = frame.anchor {
+            text.push_str(&format!(" anchor {},{}", x, y));
+        }
+        if let Some(n) = frame.nine_slice {
+            text.push_str(&format!(" nine-slice {},{},{},{}", n.left, n.right, n.top, n.bottom));
+        }
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::atlas_plist::parse_atlas_plist;
+
+    #[test]
+    fn test_generate_plist_round_trip() {
+        let sprite = PackedSprite {
+            id: "1".to_string(),
+            name: "hero.png".to_string(),
+            x: 4,
+            y: 8,
+            width: 20,
+            height: 10,
+            rotated: true,
+            original_width: 16,
+            original_height: 32,
+            trimmed: true,
+            offset_x: -1,
+            offset_y: 2,
+            flip_x: false,
+            flip_y: false,
+            anchor: Some((0.5, 0.25)),
+            nine_slice: Some(NineSlice { left: 2, right: 3, top: 4, bottom: 5 }),
+        };
+        let xml = generate_plist_data(std::slice::from_ref(&sprite), 64, 128, "ui.png", PlistFormat::XmlV3, PixelFormat::Rgba8888, SmartUpdate::default()).unwrap();
+        let atlas = parse_atlas_plist(&xml).unwrap();
+        let xml = String::from_utf8(xml).unwrap();
+
+        assert_eq!((atlas.format, atlas.texture.as_str(), atlas.width, atlas.height), (3, "ui.png", 64, 128));
+        let hero = atlas.frame("hero.png").unwrap();
+        assert_eq!((hero.x, hero.y, hero.width, hero.height, hero.rotated), (4, 8, 20, 10, true));
+        assert!(xml.contains("{{4,8},{10,20}}"));
+        assert_eq!((hero.offset_x, hero.offset_y, hero.original_width, hero.original_height), (-1, 2, 16, 32));
+        assert!(xml.contains("<key>anchor</key>\n\t\t\t<string>{0.5,0.25}</string>"));
+        assert_eq!(hero.anchor, Some((0.5, 0.25)));
+        // 帧尺寸 10x20，中间区域 (2, 4) 起 5x11
+        assert!(xml.contains("<key>capInsets</key>\n\t\t\t<string>{{2,4},{5,11}}</string>"));
+        assert_eq!(hero.nine_slice, sprite.nine_slice);
+        let oversized = PackedSprite { nine_slice: Some(NineSlice { left: 6, right: 6, top: 0, bottom: 0 }), ..sprite.clone() };
+        let err = generate_plist_data(&[oversized], 64, 128, "ui.png", PlistFormat::XmlV3, PixelFormat::Rgba8888, SmartUpdate::default()).unwrap_err();
+        assert!(err.contains("九宫格"));
+
+        assert!(generate_plist_data(&[sprite.clone(), sprite], 64, 128, "ui.png", PlistFormat::XmlV3, PixelFormat::Rgba8888, SmartUpdate::default()).is_err());
+    }
+
+    #[test]
+    fn test_untrimmed_frames_and_aliases() {
+        let mut idle = PlistFrame::untrimmed("idle_01.png", 0, 0, 16, 24);
+        idle.aliases = vec!["idle_03.png".to_string()];
+        let frames = vec![idle.clone(), PlistFrame::untrimmed("idle_02.png", 16, 0, 16, 24)];
+        let data = generate_plist(&frames, 32, 24, "idle.png", PlistFormat::XmlV3, PixelFormat::Rgba8888, SmartUpdate::default()).unwrap();
+
+        let atlas = parse_atlas_plist(&data).unwrap();
+        let second = atlas.frame("idle_02.png").unwrap();
+        assert_eq!((second.x, second.width, second.trimmed, second.offset_x), (16, 16, false, 0));
+
+        let root = Value::from_reader(std::io::Cursor::new(&data)).unwrap();
+        let aliases = root
+            .as_dictionary()
+            .and_then(|root| root.get("frames")?.as_dictionary()?.get("idle_01.png")?.as_dictionary()?.get("aliases"))
+            .and_then(Value::as_array)
+            .unwrap();
+        assert_eq!(aliases, &vec![Value::String("idle_03.png".to_string())]);
+        // 没有别名的帧不写 aliases 键
+        assert_eq!(String::from_utf8(data).unwrap().matches("<key>aliases</key>").count(), 1);
+
+        idle.aliases = vec!["idle_02.png".to_string()];
+        let clash = generate_plist(&[idle, frames[1].clone()], 32, 24, "idle.png", PlistFormat::XmlV3, PixelFormat::Rgba8888, SmartUpdate::default());
+        assert!(clash.unwrap_err().contains("idle_02.png"));
+    }
+
+    #[test]
+    fn test_format2_and_binary_round_trip() {
+        let sprite = PackedSprite {
+            id: "1".to_string(),
+            name: "hero.png".to_string(),
+            x: 4,
+            y: 8,
+            width: 10,
+            height: 20,
+            rotated: false,
+            original_width: 16,
+            original_height: 32,
+            trimmed: true,
+            offset_x: -1,
+            offset_y: 2,
+            flip_x: false,
+            flip_y: false,
+            anchor: None,
+            nine_slice: None,
+        };
+        for format in [PlistFormat::XmlV2, PlistFormat::Binary] {
+            let data = generate_plist_data(std::slice::from_ref(&sprite), 64, 128, "ui.png", format, PixelFormat::Rgba8888, SmartUpdate::default()).unwrap();
+            let atlas = parse_atlas_plist(&data).unwrap();
+            assert_eq!(atlas.format, format.version());
+            let hero = atlas.frame("hero.png").unwrap();
+            assert_eq!((hero.x, hero.y, hero.width, hero.height), (4, 8, 10, 20));
+            assert_eq!((hero.offset_x, hero.offset_y, hero.original_width, hero.original_height), (-1, 2, 16, 32));
+        }
+
+        let xml = generate_plist_data(&[sprite], 64, 128, "ui.png", PlistFormat::XmlV2, PixelFormat::Rgba4444, SmartUpdate::default()).unwrap();
+        let xml = String::from_utf8(xml).unwrap();
+        assert!(xml.contains("<key>pixelFormat</key>\n\t\t<string>RGBA4444</string>"));
+        // sourceColorRect：居中位置 (3, 6) 按偏移左移 1、上移 2 像素
+        assert!(xml.contains("<string>{{2,4},{10,20}}</string>"));
+        assert!(!xml.contains("textureRect"));
+
+        let binary = generate_plist_data(&[], 1, 1, "ui.png", PlistFormat::Binary, PixelFormat::Rgba8888, SmartUpdate::default()).unwrap();
+        assert!(binary.starts_with(b"bplist00"));
+        let format: PlistFormat = serde_json::from_str("\"xml-v2\"").unwrap();
+        assert_eq!(format, PlistFormat::XmlV2);
+    }
+
+    #[test]
+    fn test_v2_source_color_rect_with_odd_spare() {
+        use crate::atlas_patch::trim_origin;
+        use crate::trim::source_offset;
+
+        // 10x10 原图中裁剪区域为 (2,1)-(7,4)：两侧留白之差为奇数，偏移经过取整
+        let (offset_x, offset_y) = source_offset((2, 1, 7, 4), (10, 10));
+        let sprite = PackedSprite {
+            id: "a".to_string(),
+            name: "a.png".to_string(),
+            x: 0,
+            y: 0,
+            width: 5,
+            height: 3,
+            rotated: false,
+            original_width: 10,
+            original_height: 10,
+            trimmed: true,
+            offset_x,
+            offset_y,
+            flip_x: false,
+            flip_y: false,
+            anchor: None,
+            nine_slice: None,
+        };
+        let data = generate_plist_data(std::slice::from_ref(&sprite), 16, 16, "a.png", PlistFormat::XmlV2, PixelFormat::Rgba8888, SmartUpdate::default()).unwrap();
+        assert!(String::from_utf8(data.clone()).unwrap().contains("<string>{{2,1},{5,3}}</string>"));
+        let atlas = parse_atlas_plist(&data).unwrap();
+        assert_eq!(trim_origin(atlas.frame("a.png").unwrap()), Some((2, 1)));
+    }
+}
@@ -0,0 +1,370 @@
+/// 图集 Plist 校验 (Atlas Plist Lint)
+///
+/// 检查其他工具生成的 plist + 纹理是否自洽：帧超出纹理、帧区域重叠、
+/// 旋转帧尺寸写反、帧名称重复、纹理缺失或尺寸与 metadata 不一致。
+/// 与 `atlas_plist` 不同，单帧解析失败不会中断检查，所有问题一并报告
+
+use crate::atlas_plist::{parse_frame, parse_root};
+use crate::plist_parse::size_field;
+use crate::types::PackedSprite;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// 问题类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LintKind {
+    /// 帧字段缺失或格式错误
+    InvalidFrame,
+    /// 帧名称重复（XML 中后出现的同名帧会覆盖前面的）
+    DuplicateName,
+    /// 帧超出纹理范围
+    OutOfBounds,
+    /// 两帧的纹理区域部分重叠（完全相同的区域视为别名，不报告）
+    Overlap,
+    /// 旋转帧的尺寸按纹理中的占用尺寸写入（宽高写反）
+    RotatedSizeMismatch,
+    /// 帧尺寸与 spriteSize 不一致或大于原始尺寸
+    FrameSizeMismatch,
+    /// 纹理文件不存在
+    MissingTexture,
+    /// 无法读取纹理尺寸
+    UnreadableTexture,
+    /// 纹理尺寸与 metadata 的 size 不一致
+    TextureSizeMismatch,
+}
+
+impl LintKind {
+    /// 是否为错误（否则为警告）
+    pub fn is_error(self) -> bool {
+        !matches!(self, LintKind::FrameSizeMismatch | LintKind::UnreadableTexture)
+    }
+}
+
+/// 一个问题
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintIssue {
+    /// 问题类型
+    pub kind: LintKind,
+    /// 是否为错误
+    pub error: bool,
+    /// 相关的帧名称（纹理相关的问题为空）
+    pub frames: Vec<String>,
+    /// 说明
+    pub message: String,
+}
+
+impl LintIssue {
+    fn new(kind: LintKind, frames: Vec<String>, message: String) -> Self {
+        Self {
+            kind,
+            error: kind.is_error(),
+            frames,
+            message,
+        }
+    }
+}
+
+/// 校验报告
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlistLintReport {
+    /// plist 格式版本（2 或 3）
+    pub format: i64,
+    /// 纹理文件名（相对于 plist 所在目录）
+    pub texture: String,
+    /// metadata 中的纹理宽度
+    pub width: u32,
+    /// metadata 中的纹理高度
+    pub height: u32,
+    /// 帧数（不含解析失败的帧）
+    pub frame_count: usize,
+    /// 按检查顺序排列的问题
+    pub issues: Vec<LintIssue>,
+}
+
+impl PlistLintReport {
+    /// 错误数
+    pub fn error_count(&self) -> usize {
+        self.issues.iter().filter(|issue| issue.error).count()
+    }
+
+    /// 检查纹理文件
+    ///
+    /// # Arguments
+    /// * `texture` - 纹理状态：None 表示文件不存在，`Some(Err)` 表示无法读取尺寸，`Some(Ok)` 为实际尺寸
+    pub fn check_texture(&mut self, texture: Option<Result<(u32, u32), String>>) {
+        let issue = match texture {
+            None => LintIssue::new(LintKind::MissingTexture, Vec::new(), format!("纹理文件不存在: {}", self.texture)),
+            Some(Err(e)) => LintIssue::new(LintKind::UnreadableTexture, Vec::new(), format!("无法读取纹理尺寸: {}", e)),
+            Some(Ok(size)) if size != (self.width, self.height) => LintIssue::new(
+                LintKind::TextureSizeMismatch,
+                Vec::new(),
+                format!("纹理尺寸 {}x{} 与 metadata 的 {}x{} 不一致", size.0, size.1, self.width, self.height),
+            ),
+            Some(Ok(_)) => return,
+        };
+        self.issues.push(issue);
+    }
+}
+
+/// 校验图集 plist（不含纹理文件检查，见 `PlistLintReport::check_texture`）
+///
+/// # Arguments
+/// * `data` - plist 文件内容（XML 或二进制）
+///
+/// # Returns
+/// * `Result<PlistLintReport, String>` - 校验报告；不是图集 plist 或 metadata 无效时返回错误
+pub fn lint_atlas_plist(data: &[u8]) -> Result<PlistLintReport, String> {
+    let root = parse_root(data)?;
+    let mut issues: Vec<LintIssue> = duplicate_frame_names(data)
+        .into_iter()
+        .map(|name| LintIssue::new(LintKind::DuplicateName, vec![name.clone()], format!("帧名称重复: {}", name)))
+        .collect();
+
+    let mut frames = Vec::with_capacity(root.frames.len());
+    for (name, value) in &root.frames {
+        let frame = value
+            .as_dictionary()
+            .ok_or_else(|| "不是字典".to_string())
+            .and_then(|dict| Ok((parse_frame(name, dict, root.format)?, dict)));
+        let (frame, dict) = match frame {
+            Ok(frame) => frame,
+            Err(e) => {
+                issues.push(LintIssue::new(LintKind::InvalidFrame, vec![name.clone()], format!("帧 {}: {}", name, e)));
+                continue;
+            }
+        };
+        // format 2 没有 spriteSize
+        let sprite_size = match dict.get("spriteSize") {
+            Some(_) if root.format == 3 => Some(size_field(dict, "spriteSize")),
+            _ => None,
+        };
+        if let Some(issue) = check_frame_size(&frame, sprite_size) {
+            issues.push(issue);
+        }
+        let (right, bottom) = (frame.x as u64 + frame.width as u64, frame.y as u64 + frame.height as u64);
+        if right > root.width as u64 || bottom > root.height as u64 {
+            issues.push(LintIssue::new(
+                LintKind::OutOfBounds,
+                vec![name.clone()],
+                format!(
+                    "帧 {} 占用 ({}, {})~({}, {})，超出纹理 {}x{}",
+                    name, frame.x, frame.y, right, bottom, root.width, root.height
+                ),
+            ));
+        }
+        frames.push(frame);
+    }
+    issues.extend(find_overlaps(&frames));
+
+    Ok(PlistLintReport {
+        format: root.format,
+        texture: root.texture,
+        width: root.width,
+        height: root.height,
+        frame_count: frames.len(),
+        issues,
+    })
+}
+
+/// 检查帧尺寸与 spriteSize、原始尺寸是否一致
+fn check_frame_size(frame: &PackedSprite, sprite_size: Option<Result<(u32, u32), String>>) -> Option<LintIssue> {
+    let name = &frame.name;
+    let size = frame.frame_size();
+    let swapped = (size.1, size.0);
+    let source = (frame.original_width, frame.original_height);
+    let fits = |(w, h): (u32, u32)| w <= source.0 && h <= source.1;
+
+    let rotated_mismatch = |detail: String| {
+        Some(LintIssue::new(
+            LintKind::RotatedSizeMismatch,
+            vec![name.clone()],
+            format!("旋转帧 {} 的尺寸宽高写反: {}", name, detail),
+        ))
+    };
+    let size_mismatch = |detail: String| {
+        Some(LintIssue::new(LintKind::FrameSizeMismatch, vec![name.clone()], format!("帧 {}: {}", name, detail)))
+    };
+
+    match sprite_size {
+        Some(Err(e)) => return size_mismatch(e),
+        Some(Ok(sprite_size)) if sprite_size != size => {
+            let detail = format!("spriteSize {}x{} 与帧尺寸 {}x{} 不一致", sprite_size.0, sprite_size.1, size.0, size.1);
+            return if frame.rotated && sprite_size == swapped && fits(swapped) {
+                rotated_mismatch(detail)
+            } else {
+                size_mismatch(detail)
+            };
+        }
+        _ => {}
+    }
+    if !fits(size) {
+        let detail = format!("帧尺寸 {}x{} 大于原始尺寸 {}x{}", size.0, size.1, source.0, source.1);
+        return if frame.rotated && fits(swapped) { rotated_mismatch(detail) } else { size_mismatch(detail) };
+    }
+    None
+}
+
+/// 纹理中的矩形 [left, top, right, bottom)
+type Rect = (u64, u64, u64, u64);
+
+/// 查找纹理区域部分重叠的帧对
+fn find_overlaps(frames: &[PackedSprite]) -> Vec<LintIssue> {
+    let rect = |f: &PackedSprite| -> Rect {
+        let (x, y) = (f.x as u64, f.y as u64);
+        (x, y, x + f.width as u64, y + f.height as u64)
+    };
+    let mut sorted: Vec<(&PackedSprite, Rect)> = frames.iter().map(|f| (f, rect(f))).collect();
+    sorted.sort_by_key(|(_, r)| r.0);
+
+    let mut issues = Vec::new();
+    for (i, (frame, a)) in sorted.iter().enumerate() {
+        // 按左边界排序，之后的帧左边界不小于当前帧右边界即可停止
+        for (other, b) in sorted[i + 1..].iter().take_while(|(_, b)| b.0 < a.2) {
+            let overlaps = b.1 < a.3 && a.1 < b.3;
+            if overlaps && a != b {
+                issues.push(LintIssue::new(
+                    LintKind::Overlap,
+                    vec![frame.name.clone(), other.name.clone()],
+                    format!("帧 {} 与 {} 的纹理区域重叠", frame.name, other.name),
+                ));
+            }
+        }
+    }
+    issues
+}
+
+/// 在 XML plist 中查找 `frames` 字典里重复的键（解析为字典后重复的键已被覆盖，只能从原文查找）
+///
+/// 二进制 plist 返回空列表
+fn duplicate_frame_names(data: &[u8]) -> Vec<String> {
+    let Ok(text) = std::str::from_utf8(data) else {
+        return Vec::new();
+    };
+    let Some(start) = text.find("<key>frames</key>") else {
+        return Vec::new();
+    };
+    let token = Regex::new(r"<dict\s*/>|<dict>|</dict>|<key>([^<]*)</key>").expect("正则有效");
+
+    let mut depth = 0;
+    let mut seen = HashSet::new();
+    let mut duplicates = Vec::new();
+    for caps in token.captures_iter(&text[start + "<key>frames</key>".len()..]) {
+        match &caps[0] {
+            "<dict>" => depth += 1,
+            "</dict>" => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            tag if tag.starts_with("<dict") => {}
+            _ if depth == 1 => {
+                let name = unescape_xml(&caps[1]);
+                if !seen.insert(name.clone()) && !duplicates.contains(&name) {
+                    duplicates.push(name);
+                }
+            }
+            _ => {}
+        }
+    }
+    duplicates
+}
+
+/// 还原 XML 预定义实体
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(name: &str, rect: &str, rotated: bool, sprite_size: &str, source_size: &str) -> String {
+        format!(
+            "<key>{}</key><dict>
+    <key>spriteOffset</key><string>{{0,0}}</string>
+    <key>spriteSize</key><string>{}</string>
+    <key>spriteSourceSize</key><string>{}</string>
+    <key>textureRect</key><string>{}</string>
+    <key>textureRotated</key>{}
+  </dict>",
+            name,
+            sprite_size,
+            source_size,
+            rect,
+            if rotated { "<true/>" } else { "<false/>" }
+        )
+    }
+
+    fn plist(frames: &[String]) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0"><dict>
+<key>frames</key><dict>
+  {}
+</dict>
+<key>metadata</key><dict>
+  <key>format</key><integer>3</integer>
+  <key>realTextureFileName</key><string>ui.png</string>
+  <key>size</key><string>{{64,64}}</string>
+  <key>textureFileName</key><string>ui.png</string>
+</dict>
+</dict></plist>"#,
+            frames.join("\n  ")
+        )
+    }
+
+    #[test]
+    fn test_clean_atlas() {
+        let data = plist(&[
+            frame("a.png", "{{0,0},{16,16}}", false, "{16,16}", "{16,16}"),
+            frame("b.png", "{{16,0},{8,32}}", true, "{8,32}", "{8,32}"),
+            // 与 a.png 区域完全相同的别名帧
+            frame("a_copy.png", "{{0,0},{16,16}}", false, "{16,16}", "{16,16}"),
+        ]);
+        let mut report = lint_atlas_plist(data.as_bytes()).unwrap();
+        assert_eq!(report.frame_count, 3);
+        assert!(report.issues.is_empty(), "{:?}", report.issues);
+
+        report.check_texture(Some(Ok((64, 64))));
+        assert!(report.issues.is_empty());
+        report.check_texture(Some(Ok((32, 64))));
+        report.check_texture(None);
+        let kinds: Vec<LintKind> = report.issues.iter().map(|i| i.kind).collect();
+        assert_eq!(kinds, vec![LintKind::TextureSizeMismatch, LintKind::MissingTexture]);
+    }
+
+    #[test]
+    fn test_reports_problems() {
+        let data = plist(&[
+            frame("a.png", "{{0,0},{16,16}}", false, "{16,16}", "{16,16}"),
+            frame("overlap.png", "{{8,8},{16,16}}", false, "{16,16}", "{16,16}"),
+            frame("outside.png", "{{60,0},{8,8}}", false, "{8,8}", "{8,8}"),
+            // 旋转帧的 spriteSize 按占用尺寸写入
+            frame("rotated.png", "{{0,32},{8,16}}", true, "{16,8}", "{16,8}"),
+            frame("broken.png", "{{0,0},{8}}", false, "{8,8}", "{8,8}"),
+            frame("a.png", "{{0,0},{16,16}}", false, "{16,16}", "{16,16}"),
+        ]);
+        let report = lint_atlas_plist(data.as_bytes()).unwrap();
+        let find = |kind: LintKind| report.issues.iter().find(|i| i.kind == kind).unwrap();
+
+        assert_eq!(find(LintKind::DuplicateName).frames, vec!["a.png"]);
+        assert_eq!(find(LintKind::OutOfBounds).frames, vec!["outside.png"]);
+        assert_eq!(find(LintKind::RotatedSizeMismatch).frames, vec!["rotated.png"]);
+        assert_eq!(find(LintKind::InvalidFrame).frames, vec!["broken.png"]);
+        let mut overlap = find(LintKind::Overlap).frames.clone();
+        overlap.sort();
+        assert_eq!(overlap, vec!["a.png", "overlap.png"]);
+        assert_eq!(report.error_count(), report.issues.len());
+
+        assert!(lint_atlas_plist(b"not a plist").is_err());
+    }
+}
@@ -0,0 +1,171 @@
+/// Cocos 坐标字符串解析 (Cocos Geometry String Parsing)
+///
+/// Cocos2d-x / TexturePacker 的 plist 用字符串保存几何信息：矩形 `{{x,y},{w,h}}`、
+/// 尺寸 `{w,h}`、坐标 `{x,y}`。不同工具输出的空白与数字格式不一致（`{ 4, 8 }`、`10.0`、
+/// `-0`），这里统一解析，供图集读取、校验与比较等功能共用
+
+use plist::{Dictionary, Value};
+
+/// 解析 `{a,b}`，返回原始浮点值
+///
+/// # Arguments
+/// * `text` - 坐标或尺寸字符串，允许任意空白与小数
+///
+/// # Returns
+/// * `Result<(f64, f64), String>` - 两个数值；格式错误时返回错误
+pub fn parse_pair_f64(text: &str) -> Result<(f64, f64), String> {
+    let inner = braced(text.trim()).ok_or_else(|| format!("\"{}\" 不是 {{a,b}} 格式", text))?;
+    let mut parts = inner.split(',');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(a), Some(b), None) => Ok((number(a, text)?, number(b, text)?)),
+        _ => Err(format!("\"{}\" 应包含 2 个数值", text)),
+    }
+}
+
+/// 解析 `{{x,y},{w,h}}`，返回原始浮点值
+///
+/// # Arguments
+/// * `text` - 矩形字符串，允许任意空白与小数
+///
+/// # Returns
+/// * `Result<(f64, f64, f64, f64), String>` - x、y、宽、高；格式错误时返回错误
+pub fn parse_rect_f64(text: &str) -> Result<(f64, f64, f64, f64), String> {
+    let invalid = || format!("\"{}\" 不是 {{{{x,y}},{{w,h}}}} 格式", text);
+    let inner = braced(text.trim()).ok_or_else(invalid)?;
+    // 第一个 `}` 结束坐标部分，之后是逗号与尺寸部分
+    let split = inner.find('}').ok_or_else(invalid)? + 1;
+    let (origin, rest) = inner.split_at(split);
+    let size = rest.trim_start().strip_prefix(',').ok_or_else(invalid)?;
+    let (x, y) = parse_pair_f64(origin).map_err(|_| invalid())?;
+    let (w, h) = parse_pair_f64(size).map_err(|_| invalid())?;
+    Ok((x, y, w, h))
+}
+
+/// 解析坐标 `{x,y}`（允许负数，小数四舍五入）
+pub fn parse_point(text: &str) -> Result<(i32, i32), String> {
+    let (x, y) = parse_pair_f64(text)?;
+    Ok((to_i32(x, text)?, to_i32(y, text)?))
+}
+
+/// 解析尺寸 `{w,h}`（小数四舍五入，不允许负数）
+pub fn parse_size(text: &str) -> Result<(u32, u32), String> {
+    let (w, h) = parse_pair_f64(text)?;
+    Ok((to_u32(w, text)?, to_u32(h, text)?))
+}
+
+/// 解析矩形 `{{x,y},{w,h}}`（小数四舍五入，不允许负数）
+pub fn parse_rect(text: &str) -> Result<(u32, u32, u32, u32), String> {
+    let (x, y, w, h) = parse_rect_f64(text)?;
+    Ok((to_u32(x, text)?, to_u32(y, text)?, to_u32(w, text)?, to_u32(h, text)?))
+}
+
+/// 读取字典中的字符串字段
+pub fn string_field<'a>(dict: &'a Dictionary, key: &str) -> Result<&'a str, String> {
+    dict.get(key)
+        .and_then(Value::as_string)
+        .ok_or_else(|| format!("缺少字段 {}", key))
+}
+
+/// 读取字典中的坐标字段 `{x,y}`
+pub fn point_field(dict: &Dictionary, key: &str) -> Result<(i32, i32), String> {
+    parse_point(string_field(dict, key)?).map_err(|e| format!("字段 {}: {}", key, e))
+}
+
+/// 读取字典中的尺寸字段 `{w,h}`
+pub fn size_field(dict: &Dictionary, key: &str) -> Result<(u32, u32), String> {
+    parse_size(string_field(dict, key)?).map_err(|e| format!("字段 {}: {}", key, e))
+}
+
+/// 读取字典中的矩形字段 `{{x,y},{w,h}}`
+pub fn rect_field(dict: &Dictionary, key: &str) -> Result<(u32, u32, u32, u32), String> {
+    parse_rect(string_field(dict, key)?).map_err(|e| format!("字段 {}: {}", key, e))
+}
+
+/// 去掉最外层的一对花括号
+fn braced(text: &str) -> Option<&str> {
+    text.strip_prefix('{')?.strip_suffix('}')
+}
+
+fn number(part: &str, text: &str) -> Result<f64, String> {
+    part.trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite())
+        .ok_or_else(|| format!("\"{}\" 含无效数值 \"{}\"", text, part.trim()))
+}
+
+fn to_i32(value: f64, text: &str) -> Result<i32, String> {
+    let rounded = value.round();
+    if rounded < i32::MIN as f64 || rounded > i32::MAX as f64 {
+        return Err(format!("\"{}\" 超出范围", text));
+    }
+    Ok(rounded as i32)
+}
+
+fn to_u32(value: f64, text: &str) -> Result<u32, String> {
+    let rounded = value.round();
+    if rounded < 0.0 {
+        return Err(format!("\"{}\" 含负数", text));
+    }
+    if rounded > u32::MAX as f64 {
+        return Err(format!("\"{}\" 超出范围", text));
+    }
+    Ok(rounded as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// TexturePacker 4.x / 6.x 与 Zwoptex 的实际输出
+    #[test]
+    fn test_real_tool_output() {
+        // TexturePacker format 3
+        assert_eq!(parse_rect("{{1,1},{62,86}}").unwrap(), (1, 1, 62, 86));
+        assert_eq!(parse_point("{0,-1}").unwrap(), (0, -1));
+        assert_eq!(parse_point("{-0.5,1.5}").unwrap(), (-1, 2));
+        assert_eq!(parse_size("{64,88}").unwrap(), (64, 88));
+        // Zwoptex / 旧版 TexturePacker 带空格
+        assert_eq!(parse_rect("{{0, 0}, {32, 32}}").unwrap(), (0, 0, 32, 32));
+        assert_eq!(parse_size("{ 512 , 256 }").unwrap(), (512, 256));
+        // 小数与指数
+        assert_eq!(parse_rect_f64("{{ 4.25, 8 },{10.0,2e1}}").unwrap(), (4.25, 8.0, 10.0, 20.0));
+        assert_eq!(parse_pair_f64("  {-0,3.5}\n").unwrap(), (0.0, 3.5));
+        assert_eq!(parse_rect("{{\t2,\n2},{4 ,4}}").unwrap(), (2, 2, 4, 4));
+    }
+
+    #[test]
+    fn test_malformed_strings() {
+        for text in ["", "{}", "{1}", "{1,2,3}", "1,2", "{1,2", "{a,2}", "{1,,2}", "{nan,1}", "{inf,1}"] {
+            assert!(parse_pair_f64(text).is_err(), "{}", text);
+        }
+        for text in [
+            "{1,2,3,4}",
+            "{{1,2},{3}}",
+            "{{1,2}{3,4}}",
+            "{{1,2},{3,4},{5,6}}",
+            "{{1,2,3},{4,5}}",
+            "{1,2},{3,4}",
+            "{{1,2},{3,4}",
+            "{{1,2},3,4}",
+        ] {
+            assert!(parse_rect_f64(text).is_err(), "{}", text);
+        }
+        assert!(parse_size("{-1,2}").unwrap_err().contains("负数"));
+        assert!(parse_rect("{{-3,0},{1,1}}").is_err());
+        assert!(parse_point("{1e12,0}").unwrap_err().contains("超出范围"));
+    }
+
+    #[test]
+    fn test_dictionary_fields() {
+        let mut dict = Dictionary::new();
+        dict.insert("frame".to_string(), Value::String("{{2,4},{6,8}}".to_string()));
+        dict.insert("offset".to_string(), Value::String("{1,-1}".to_string()));
+        dict.insert("rotated".to_string(), Value::Boolean(true));
+        assert_eq!(rect_field(&dict, "frame").unwrap(), (2, 4, 6, 8));
+        assert_eq!(point_field(&dict, "offset").unwrap(), (1, -1));
+        assert_eq!(size_field(&dict, "sourceSize").unwrap_err(), "缺少字段 sourceSize");
+        assert!(rect_field(&dict, "offset").unwrap_err().starts_with("字段 offset"));
+        assert!(string_field(&dict, "rotated").is_err());
+    }
+}
@@ -0,0 +1,325 @@
+/// PNG 元数据 (PNG Metadata)
+///
+/// 在已编码的 PNG 字节流中插入 tEXt/iTXt 块，记录生成图集的工具版本、配置哈希与帧数，
+/// 在构建产物中发现一张图集时可以据此追溯到生成它的 EzPlist 设置；
+/// 以及写入 pHYs 块声明物理分辨率（DPI）。只处理块结构，不解码像素
+
+use crc32fast::Hasher;
+use std::ops::Range;
+
+/// PNG 文件签名
+const PNG_SIGNATURE: [u8; 8] = [137, 80, 78, 71, 13, 10, 26, 10];
+
+/// 关键字最大长度（PNG 规范）
+const MAX_KEYWORD_LEN: usize = 79;
+
+/// 允许设置的最大 DPI
+pub const MAX_DPI: u32 = 10_000;
+
+/// 每英寸的米数
+const METERS_PER_INCH: f64 = 0.0254;
+
+/// 写入 PNG 的附加元数据
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PngMetadata {
+    /// 图集来源信息（写入文本块）
+    pub stamp: Option<AtlasStamp>,
+    /// 物理分辨率（写入 pHYs 块）
+    pub dpi: Option<u32>,
+}
+
+impl PngMetadata {
+    /// 把元数据写入已编码的 PNG
+    ///
+    /// # Arguments
+    /// * `png` - 已编码的 PNG 数据
+    ///
+    /// # Returns
+    /// * `Result<Vec<u8>, String>` - 写入后的 PNG 数据
+    pub fn apply(&self, png: &[u8]) -> Result<Vec<u8>, String> {
+        let mut output = png.to_vec();
+        if let Some(dpi) = self.dpi {
+            output = set_dpi(&output, dpi)?;
+        }
+        if let Some(stamp) = &self.stamp {
+            output = insert_text_chunks(&output, &stamp.text_entries())?;
+        }
+        Ok(output)
+    }
+}
+
+/// 图集来源信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AtlasStamp {
+    /// 工具版本
+    pub tool_version: String,
+    /// 生成图集的配置哈希
+    pub config_hash: String,
+    /// 帧数
+    pub frame_count: usize,
+}
+
+impl AtlasStamp {
+    /// 转换为要写入的文本条目（关键字, 内容）
+    pub fn text_entries(&self) -> Vec<(String, String)> {
+        vec![
+            ("Software".to_string(), format!("EzPlist {}", self.tool_version)),
+            ("EzPlist:ConfigHash".to_string(), self.config_hash.clone()),
+            ("EzPlist:FrameCount".to_string(), self.frame_count.to_string()),
+        ]
+    }
+}
+
+/// 在 PNG 中插入文本块
+///
+/// 文本块紧跟在 IHDR 之后。纯 ASCII 内容写为 tEXt，其余写为未压缩的 iTXt（UTF-8）
+///
+/// # Arguments
+/// * `png` - 已编码的 PNG 数据
+/// * `entries` - 文本条目（关键字, 内容）
+///
+/// # Returns
+/// * `Result<Vec<u8>, String>` - 插入后的 PNG 数据；输入不是 PNG 或关键字无效时返回错误
+pub fn insert_text_chunks(png: &[u8], entries: &[(String, String)]) -> Result<Vec<u8>, String> {
+    let ihdr_end = ihdr_end(png)?;
+
+    let mut chunks = Vec::new();
+    for (keyword, text) in entries {
+        validate_keyword(keyword)?;
+        if text.is_ascii() && !text.contains('\0') {
+            let mut data = keyword.as_bytes().to_vec();
+            data.push(0);
+            data.extend_from_slice(text.as_bytes());
+            write_chunk(&mut chunks, b"tEXt", &data);
+        } else {
+            // 关键字、压缩标志、压缩方法、语言标签、翻译后的关键字、内容
+            let mut data = keyword.as_bytes().to_vec();
+            data.extend_from_slice(&[0, 0, 0, 0, 0]);
+            data.extend_from_slice(text.as_bytes());
+            write_chunk(&mut chunks, b"iTXt", &data);
+        }
+    }
+
+    let mut output = Vec::with_capacity(png.len() + chunks.len());
+    output.extend_from_slice(&png[..ihdr_end]);
+    output.extend_from_slice(&chunks);
+    output.extend_from_slice(&png[ihdr_end..]);
+    Ok(output)
+}
+
+/// 设置 PNG 的物理分辨率
+///
+/// 写入以米为单位的 pHYs 块（横纵分辨率相同），替换已有的 pHYs 块
+///
+/// # Arguments
+/// * `png` - 已编码的 PNG 数据
+/// * `dpi` - 每英寸像素数（1 ~ `MAX_DPI`）
+///
+/// # Returns
+/// * `Result<Vec<u8>, String>` - 写入后的 PNG 数据
+pub fn set_dpi(png: &[u8], dpi: u32) -> Result<Vec<u8>, String> {
+    if dpi == 0 || dpi > MAX_DPI {
+        return Err(format!("DPI 必须在 1 ~ {} 之间: {}", MAX_DPI, dpi));
+    }
+    let ihdr_end = ihdr_end(png)?;
+
+    let pixels_per_meter = (dpi as f64 / METERS_PER_INCH).round() as u32;
+    let mut data = Vec::with_capacity(9);
+    data.extend_from_slice(&pixels_per_meter.to_be_bytes());
+    data.extend_from_slice(&pixels_per_meter.to_be_bytes());
+    data.push(1);
+
+    let mut output = Vec::with_capacity(png.len() + 21);
+    output.extend_from_slice(&png[..ihdr_end]);
+    write_chunk(&mut output, b"pHYs", &data);
+    for (kind, range) in chunks(png)? {
+        if range.start >= ihdr_end && kind != b"pHYs" {
+            output.extend_from_slice(&png[range]);
+        }
+    }
+    Ok(output)
+}
+
+/// 读取 PNG 的物理分辨率
+///
+/// # Returns
+/// * `Result<Option<u32>, String>` - 以米为单位的 pHYs 块换算出的横向 DPI；没有时返回 None
+pub fn read_dpi(png: &[u8]) -> Result<Option<u32>, String> {
+    for (kind, range) in chunks(png)? {
+        let data = &png[range.start + 8..range.end - 4];
+        if kind == b"pHYs" && data.len() == 9 && data[8] == 1 {
+            let pixels_per_meter = u32::from_be_bytes([data[0], data[1], data[2], data[3]]);
+            return Ok(Some((pixels_per_meter as f64 * METERS_PER_INCH).round() as u32));
+        }
+    }
+    Ok(None)
+}
+
+/// 读取 PNG 中的 tEXt 与未压缩的 iTXt 块
+///
+/// # Arguments
+/// * `png` - PNG 数据
+///
+/// # Returns
+/// * `Result<Vec<(String, String)>, String>` - 文本条目（关键字, 内容），按出现顺序排列
+pub fn read_text_chunks(png: &[u8]) -> Result<Vec<(String, String)>, String> {
+    let mut entries = Vec::new();
+    for (kind, range) in chunks(png)? {
+        let data = &png[range.start + 8..range.end - 4];
+        match kind {
+            b"tEXt" => {
+                if let Some((keyword, text)) = split_null(data) {
+                    // tEXt 为 Latin-1 编码
+                    entries.push((latin1(keyword), latin1(text)));
+                }
+            }
+            b"iTXt" => {
+                if let Some((keyword, rest)) = split_null(data) {
+                    // 跳过压缩的 iTXt
+                    if rest.len() >= 2 && rest[0] == 0 {
+                        let rest = &rest[2..];
+                        let text = split_null(rest)
+                            .and_then(|(_, rest)| split_null(rest))
+                            .map(|(_, text)| text);
+                        if let Some(text) = text {
+                            entries.push((latin1(keyword), String::from_utf8_lossy(text).into_owned()));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(entries)
+}
+
+/// 数据块类型与包含长度、CRC 的完整字节范围
+type ChunkSpan<'a> = (&'a [u8], Range<usize>);
+
+/// 列出 PNG 的所有数据块，到 IEND 为止
+fn chunks(png: &[u8]) -> Result<Vec<ChunkSpan<'_>>, String> {
+    ihdr_end(png)?;
+
+    let mut chunks = Vec::new();
+    let mut pos = PNG_SIGNATURE.len();
+    while pos + 8 <= png.len() {
+        let len = u32::from_be_bytes([png[pos], png[pos + 1], png[pos + 2], png[pos + 3]]) as usize;
+        let kind = &png[pos + 4..pos + 8];
+        let end = pos
+            .checked_add(12)
+            .and_then(|p| p.checked_add(len))
+            .filter(|&end| end <= png.len())
+            .ok_or_else(|| "PNG 数据块长度超出文件范围".to_string())?;
+        chunks.push((kind, pos..end));
+        if kind == b"IEND" {
+            break;
+        }
+        pos = end;
+    }
+    Ok(chunks)
+}
+
+/// 检查 PNG 签名与 IHDR，返回 IHDR 块结束的位置
+fn ihdr_end(png: &[u8]) -> Result<usize, String> {
+    if !png.starts_with(&PNG_SIGNATURE) {
+        return Err("不是有效的 PNG 数据".to_string());
+    }
+    // 签名 + 长度(4) + 类型(4) + IHDR 数据(13) + CRC(4)
+    let end = PNG_SIGNATURE.len() + 4 + 4 + 13 + 4;
+    if png.len() < end || &png[12..16] != b"IHDR" {
+        return Err("PNG 缺少 IHDR 数据块".to_string());
+    }
+    Ok(end)
+}
+
+/// 关键字必须为 1~79 个可打印 Latin-1 字符，首尾不能是空格，不能有连续空格
+fn validate_keyword(keyword: &str) -> Result<(), String> {
+    let valid = !keyword.is_empty()
+        && keyword.len() <= MAX_KEYWORD_LEN
+        && keyword.bytes().all(|b| (32..=126).contains(&b))
+        && !keyword.starts_with(' ')
+        && !keyword.ends_with(' ')
+        && !keyword.contains("  ");
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("无效的 PNG 文本关键字: {:?}", keyword))
+    }
+}
+
+fn write_chunk(output: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    output.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    output.extend_from_slice(kind);
+    output.extend_from_slice(data);
+    let mut hasher = Hasher::new();
+    hasher.update(kind);
+    hasher.update(data);
+    output.extend_from_slice(&hasher.finalize().to_be_bytes());
+}
+
+fn split_null(data: &[u8]) -> Option<(&[u8], &[u8])> {
+    let pos = data.iter().position(|&b| b == 0)?;
+    Some((&data[..pos], &data[pos + 1..]))
+}
+
+fn latin1(bytes: &[u8]) -> String {
+    bytes.iter().map(|&b| b as char).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 最小的 PNG 结构（签名 + IHDR + IEND），不含像素数据
+    fn minimal_png() -> Vec<u8> {
+        let mut png = PNG_SIGNATURE.to_vec();
+        let mut ihdr = Vec::new();
+        ihdr.extend_from_slice(&1u32.to_be_bytes());
+        ihdr.extend_from_slice(&1u32.to_be_bytes());
+        ihdr.extend_from_slice(&[8, 6, 0, 0, 0]);
+        write_chunk(&mut png, b"IHDR", &ihdr);
+        write_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+
+    #[test]
+    fn test_stamp_round_trip() {
+        let stamp = AtlasStamp {
+            tool_version: "0.1.0".to_string(),
+            config_hash: "d41d8cd98f00b204e9800998ecf8427e".to_string(),
+            frame_count: 12,
+        };
+        let mut entries = stamp.text_entries();
+        entries.push(("Comment".to_string(), "角色图集".to_string()));
+
+        let png = insert_text_chunks(&minimal_png(), &entries).unwrap();
+        assert_eq!(&png[37..41], b"tEXt");
+        assert_eq!(read_text_chunks(&png).unwrap(), entries);
+        assert!(png.ends_with(&minimal_png()[33..]));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let entry = |keyword: &str| vec![(keyword.to_string(), "x".to_string())];
+        assert!(insert_text_chunks(&minimal_png(), &entry("")).is_err());
+        assert!(insert_text_chunks(&minimal_png(), &entry(" Software")).is_err());
+        assert!(insert_text_chunks(&minimal_png(), &entry(&"k".repeat(80))).is_err());
+        assert!(insert_text_chunks(b"GIF89a", &entry("Software")).is_err());
+    }
+
+    #[test]
+    fn test_dpi() {
+        let png = set_dpi(&minimal_png(), 300).unwrap();
+        assert_eq!(&png[37..41], b"pHYs");
+        assert_eq!(read_dpi(&png).unwrap(), Some(300));
+        assert_eq!(read_dpi(&minimal_png()).unwrap(), None);
+
+        // 再次设置时替换而不是追加
+        let png = set_dpi(&png, 72).unwrap();
+        assert_eq!(png.len(), minimal_png().len() + 21);
+        assert_eq!(read_dpi(&png).unwrap(), Some(72));
+
+        assert!(set_dpi(&minimal_png(), 0).is_err());
+        assert!(set_dpi(&minimal_png(), MAX_DPI + 1).is_err());
+    }
+}
@@ -0,0 +1,178 @@
+/// 图像预处理流水线 (Preprocessing Pipeline)
+///
+/// 会话级别配置的一组预处理步骤，在打包时按顺序应用于每个精灵，
+/// 把美术直接导出的原始图像统一规范化（尺寸、边距、颜色）
+
+use image::{imageops, Rgba, RgbaImage};
+use serde::{Deserialize, Serialize};
+
+/// 单个步骤的参数上限（尺寸与倍数）
+const MAX_STEP_SIZE: u32 = 16384;
+
+/// 颜色转换方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ColorConversion {
+    /// 转为灰度（保留 alpha）
+    Grayscale,
+    /// 预乘 alpha
+    PremultiplyAlpha,
+    /// 去掉 alpha（所有像素变为不透明）
+    Opaque,
+}
+
+/// 预处理步骤
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum PreprocessStep {
+    /// 等比缩小到最长边不超过 `max_dimension`（较小的图像不放大）
+    #[serde(rename_all = "camelCase")]
+    ResizeToMax { max_dimension: u32 },
+    /// 四周均匀补透明像素，使宽高为 `multiple` 的倍数（多出的 1 像素补在右侧/下方）
+    PadToMultiple { multiple: u32 },
+    /// 颜色转换
+    ConvertColor { conversion: ColorConversion },
+    /// 清除完全透明像素中残留的颜色数据
+    ///
+    /// 解码时文件元数据（EXIF、ICC、文本块）已丢弃，这里清除的是隐藏在透明像素中的 RGB
+    StripMetadata,
+}
+
+impl PreprocessStep {
+    /// 检查参数
+    pub fn validate(&self) -> Result<(), String> {
+        match *self {
+            PreprocessStep::ResizeToMax { max_dimension } if max_dimension == 0 || max_dimension > MAX_STEP_SIZE => {
+                Err(format!("缩放的最大边长必须在 1 ~ {} 之间: {}", MAX_STEP_SIZE, max_dimension))
+            }
+            PreprocessStep::PadToMultiple { multiple } if multiple == 0 || multiple > MAX_STEP_SIZE => {
+                Err(format!("补边倍数必须在 1 ~ {} 之间: {}", MAX_STEP_SIZE, multiple))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 应用单个步骤
+    fn apply(&self, img: RgbaImage) -> RgbaImage {
+        match *self {
+            PreprocessStep::ResizeToMax { max_dimension } => resize_to_max(img, max_dimension),
+            PreprocessStep::PadToMultiple { multiple } => pad_to_multiple(img, multiple),
+            PreprocessStep::ConvertColor { conversion } => convert_color(img, conversion),
+            PreprocessStep::StripMetadata => {
+                let mut img = img;
+                for pixel in img.pixels_mut().filter(|p| p[3] == 0) {
+                    *pixel = Rgba([0, 0, 0, 0]);
+                }
+                img
+            }
+        }
+    }
+}
+
+/// 检查整条流水线
+pub fn validate_steps(steps: &[PreprocessStep]) -> Result<(), String> {
+    steps.iter().try_for_each(PreprocessStep::validate)
+}
+
+/// 按顺序应用预处理步骤
+///
+/// # Arguments
+/// * `img` - 原始图像
+/// * `steps` - 预处理步骤（调用前应通过 `validate_steps` 检查）
+///
+/// # Returns
+/// * `RgbaImage` - 处理后的图像
+pub fn apply_preprocess(img: RgbaImage, steps: &[PreprocessStep]) -> RgbaImage {
+    steps.iter().fold(img, |img, step| step.apply(img))
+}
+
+fn resize_to_max(img: RgbaImage, max_dimension: u32) -> RgbaImage {
+    let (width, height) = img.dimensions();
+    let longest = width.max(height);
+    if longest <= max_dimension {
+        return img;
+    }
+    let scale = max_dimension as f64 / longest as f64;
+    let new_width = ((width as f64 * scale).round() as u32).max(1);
+    let new_height = ((height as f64 * scale).round() as u32).max(1);
+    imageops::resize(&img, new_width, new_height, imageops::FilterType::Lanczos3)
+}
+
+fn pad_to_multiple(img: RgbaImage, multiple: u32) -> RgbaImage {
+    let (width, height) = img.dimensions();
+    let round = |v: u32| v.div_ceil(multiple).saturating_mul(multiple);
+    let (new_width, new_height) = (round(width), round(height));
+    if (new_width, new_height) == (width, height) {
+        return img;
+    }
+    let mut canvas = RgbaImage::new(new_width, new_height);
+    let x = (new_width - width) / 2;
+    let y = (new_height - height) / 2;
+    imageops::replace(&mut canvas, &img, x as i64, y as i64);
+    canvas
+}
+
+fn convert_color(mut img: RgbaImage, conversion: ColorConversion) -> RgbaImage {
+    for pixel in img.pixels_mut() {
+        let [r, g, b, a] = pixel.0;
+        pixel.0 = match conversion {
+            ColorConversion::Grayscale => {
+                let luma = (0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32).round() as u8;
+                [luma, luma, luma, a]
+            }
+            ColorConversion::PremultiplyAlpha => {
+                let mul = |c: u8| ((c as u32 * a as u32 + 127) / 255) as u8;
+                [mul(r), mul(g), mul(b), a]
+            }
+            ColorConversion::Opaque => [r, g, b, 255],
+        };
+    }
+    img
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pipeline_order() {
+        let img = RgbaImage::from_pixel(100, 50, Rgba([200, 100, 50, 255]));
+        let steps = [
+            PreprocessStep::ResizeToMax { max_dimension: 30 },
+            PreprocessStep::PadToMultiple { multiple: 4 },
+        ];
+        let out = apply_preprocess(img, &steps);
+        // 100x50 -> 30x15 -> 32x16，内容居中
+        assert_eq!(out.dimensions(), (32, 16));
+        assert_eq!(out.get_pixel(0, 0)[3], 0);
+        assert_eq!(out.get_pixel(1, 0)[3], 255);
+
+        let small = RgbaImage::new(8, 8);
+        assert_eq!(apply_preprocess(small, &steps[..1]).dimensions(), (8, 8));
+    }
+
+    #[test]
+    fn test_color_steps() {
+        let mut img = RgbaImage::from_pixel(2, 1, Rgba([200, 100, 50, 128]));
+        img.put_pixel(1, 0, Rgba([9, 9, 9, 0]));
+        let gray = apply_preprocess(img.clone(), &[PreprocessStep::ConvertColor { conversion: ColorConversion::Grayscale }]);
+        assert_eq!(gray.get_pixel(0, 0).0, [124, 124, 124, 128]);
+        let premultiplied =
+            apply_preprocess(img.clone(), &[PreprocessStep::ConvertColor { conversion: ColorConversion::PremultiplyAlpha }]);
+        assert_eq!(premultiplied.get_pixel(0, 0).0, [100, 50, 25, 128]);
+        let stripped = apply_preprocess(img, &[PreprocessStep::StripMetadata]);
+        assert_eq!(stripped.get_pixel(1, 0).0, [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_serde_and_validate() {
+        let steps: Vec<PreprocessStep> = serde_json::from_str(
+            r#"[{"type": "resizeToMax", "maxDimension": 256}, {"type": "stripMetadata"},
+                {"type": "convertColor", "conversion": "opaque"}]"#,
+        )
+        .unwrap();
+        assert_eq!(steps[0], PreprocessStep::ResizeToMax { max_dimension: 256 });
+        assert!(validate_steps(&steps).is_ok());
+        assert!(validate_steps(&[PreprocessStep::PadToMultiple { multiple: 0 }]).is_err());
+    }
+}
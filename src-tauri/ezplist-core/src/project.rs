@@ -0,0 +1,245 @@
+/// 项目文件 (Project File)
+///
+/// 把导入的精灵列表、会话、打包/切分/合成配置与动画区域保存为带版本号的 `.ezplist` JSON 文件，
+/// 下次打开时不必重新导入与设置。配置与区域由前端原样保存和恢复；
+/// 来源路径在项目目录内时保存为相对路径，移动整个项目目录后仍能找到图片
+
+use crate::history::ExportRecord;
+use crate::notes::validate_note;
+use crate::session::{Session, SpriteSource};
+use crate::types::SpriteData;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+/// 当前项目文件版本
+pub const PROJECT_VERSION: u32 = 1;
+
+/// 项目文件扩展名
+pub const PROJECT_EXTENSION: &str = "ezplist";
+
+/// 项目内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Project {
+    /// 文件版本（保存时写入 `PROJECT_VERSION`）
+    #[serde(default)]
+    pub version: u32,
+    /// 导入的独立图片（`import_images` 返回的精灵）
+    #[serde(default)]
+    pub sprites: Vec<SpriteData>,
+    /// 独立图片的备注（按精灵 ID；会话精灵的备注保存在会话中）
+    #[serde(default)]
+    pub notes: HashMap<String, String>,
+    /// 打包会话（精灵来源、元数据与预处理步骤）
+    #[serde(default)]
+    pub session: Option<Session>,
+    /// 切分使用的图集路径
+    #[serde(default)]
+    pub spritesheet_path: Option<String>,
+    /// 打包配置（`PackConfig`）
+    #[serde(default)]
+    pub pack_config: Value,
+    /// 切分配置（`SplitConfig`）
+    #[serde(default)]
+    pub split_config: Value,
+    /// 合成配置（`ComposeConfig`）
+    #[serde(default)]
+    pub compose_config: Value,
+    /// 动画区域定义（`AnimationRegion` 列表）
+    #[serde(default)]
+    pub regions: Value,
+    /// 导出历史（按时间先后）
+    #[serde(default)]
+    pub history: Vec<ExportRecord>,
+}
+
+/// 打开项目时找不到的来源文件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissingSource {
+    /// 精灵或图集名称
+    pub name: String,
+    /// 解析后的路径
+    pub path: String,
+}
+
+impl Project {
+    /// 解析项目文件
+    ///
+    /// # Arguments
+    /// * `text` - 文件内容
+    ///
+    /// # Returns
+    /// * `Result<Project, String>` - 内容无效或版本高于当前支持的版本时返回错误
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let project: Project = serde_json::from_str(text).map_err(|e| format!("项目文件无效: {}", e))?;
+        if project.version == 0 {
+            return Err("项目文件缺少版本号".to_string());
+        }
+        if project.version > PROJECT_VERSION {
+            return Err(format!(
+                "项目文件版本 {} 高于当前支持的版本 {}，请升级 EzPlist",
+                project.version, PROJECT_VERSION
+            ));
+        }
+        for note in project.notes.values() {
+            validate_note(note)?;
+        }
+        Ok(project)
+    }
+
+    /// 所有来源与输出路径（独立图片、会话来源、切分图集、导出历史），供统一改写
+    fn paths_mut(&mut self) -> impl Iterator<Item = &mut String> {
+        let session_paths = self.session.iter_mut().flat_map(|session| {
+            session.sprites.iter_mut().map(|sprite| match &mut sprite.source {
+                SpriteSource::Image { path } => path,
+                SpriteSource::SheetFrame { sheet_path, .. } => sheet_path,
+            })
+        });
+        let history_paths = self.history.iter_mut().flat_map(|record| {
+            record
+                .sprites
+                .iter_mut()
+                .map(|sprite| &mut sprite.path)
+                .chain(std::iter::once(record.export.output_dir_mut()))
+        });
+        self.sprites
+            .iter_mut()
+            .map(|sprite| &mut sprite.path)
+            .chain(session_paths)
+            .chain(self.spritesheet_path.iter_mut())
+            .chain(history_paths)
+    }
+
+    /// 把位于 `base` 目录内的来源路径改写为相对路径（保存前调用）
+    pub fn relativize(&mut self, base: &Path) {
+        for path in self.paths_mut() {
+            if let Ok(relative) = Path::new(path.as_str()).strip_prefix(base) {
+                *path = relative.to_string_lossy().to_string();
+            }
+        }
+    }
+
+    /// 把相对路径解析为相对于 `base` 目录的路径（打开后调用）
+    pub fn resolve(&mut self, base: &Path) {
+        for path in self.paths_mut() {
+            if Path::new(path.as_str()).is_relative() {
+                *path = normalize(&base.join(path.as_str())).to_string_lossy().to_string();
+            }
+        }
+    }
+
+    /// 找出不存在的来源文件（同一文件只报告一次）
+    ///
+    /// # Arguments
+    /// * `exists` - 判断路径是否存在
+    ///
+    /// # Returns
+    /// * `Vec<MissingSource>` - 缺失的来源，按项目中的顺序排列
+    pub fn missing_sources(&self, exists: impl Fn(&str) -> bool) -> Vec<MissingSource> {
+        let sprites = self.sprites.iter().map(|s| (s.name.as_str(), s.path.as_str()));
+        let session = self
+            .session
+            .iter()
+            .flat_map(|session| session.sprites.iter().map(|s| (s.name.as_str(), s.source.path())));
+        let sheet = self.spritesheet_path.iter().map(|path| {
+            let name = Path::new(path).file_name().and_then(|n| n.to_str()).unwrap_or(path);
+            (name, path.as_str())
+        });
+
+        let mut missing: Vec<MissingSource> = Vec::new();
+        for (name, path) in sprites.chain(session).chain(sheet) {
+            if !missing.iter().any(|m| m.path == path) && !exists(path) {
+                missing.push(MissingSource {
+                    name: name.to_string(),
+                    path: path.to_string(),
+                });
+            }
+        }
+        missing
+    }
+}
+
+/// 去掉路径中的 `.` 与 `..`（不访问文件系统）
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push("..");
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::SessionSprite;
+
+    fn sprite(name: &str, path: &str) -> SpriteData {
+        SpriteData {
+            id: name.to_string(),
+            name: name.to_string(),
+            path: path.to_string(),
+            width: 8,
+            height: 8,
+            trimmed_width: 8,
+            trimmed_height: 8,
+            anchor: None,
+            nine_slice: None,
+        }
+    }
+
+    #[test]
+    fn test_project_paths_round_trip() {
+        let mut session = Session::new("s");
+        let frame = SpriteSource::SheetFrame { sheet_path: "/game/art/hero.png".to_string(), x: 0, y: 0 };
+        session.add_sprites(vec![SessionSprite::new("f", "hero_01", frame, 8, 8)]).unwrap();
+        let mut project = Project {
+            version: PROJECT_VERSION,
+            sprites: vec![sprite("coin", "/game/art/coin.png"), sprite("logo", "/shared/logo.png")],
+            notes: HashMap::from([("logo".to_string(), "等待品牌组定稿".to_string())]),
+            session: Some(session),
+            spritesheet_path: Some("/game/art/hero.png".to_string()),
+            pack_config: serde_json::json!({ "padding": 2 }),
+            split_config: Value::Null,
+            compose_config: Value::Null,
+            regions: serde_json::json!([{ "name": "idle" }]),
+            history: Vec::new(),
+        };
+
+        project.relativize(Path::new("/game"));
+        assert_eq!(project.sprites[0].path, Path::new("art").join("coin.png").to_string_lossy());
+        assert_eq!(project.sprites[1].path, "/shared/logo.png");
+        let text = serde_json::to_string(&project).unwrap();
+
+        // 项目目录移动后，相对路径跟随新位置
+        let mut moved = Project::parse(&text).unwrap();
+        moved.resolve(Path::new("/backup/game/./"));
+        assert_eq!(Path::new(&moved.sprites[0].path), Path::new("/backup/game/art/coin.png"));
+        assert_eq!(Path::new(moved.spritesheet_path.as_deref().unwrap()), Path::new("/backup/game/art/hero.png"));
+        assert_eq!(moved.regions, project.regions);
+        assert_eq!(moved.notes, project.notes);
+
+        let missing = moved.missing_sources(|path| path.ends_with("coin.png"));
+        let names: Vec<&str> = missing.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["logo", "hero_01"]);
+    }
+
+    #[test]
+    fn test_project_version() {
+        assert!(Project::parse(r#"{ "version": 1 }"#).is_ok());
+        assert!(Project::parse("{}").unwrap_err().contains("版本号"));
+        assert!(Project::parse(r#"{ "version": 99 }"#).unwrap_err().contains("99"));
+        assert!(Project::parse("not json").is_err());
+        assert!(Project::parse(r#"{ "version": 1, "notes": { "a": "" } }"#).unwrap_err().contains("备注"));
+    }
+}
@@ -0,0 +1,191 @@
+/// 缩放版本导出 (Scale Variants)
+///
+/// Cocos 项目通常同时发布 HD 与 SD 图集。导出全分辨率图集后，按缩放比例缩小纹理，
+/// 并同步缩放每帧的纹理区域、原始尺寸与偏移，一次得到 `name-sd.png` / `name-sd.plist` 等版本
+
+use crate::types::{NineSlice, PackedSprite};
+use image::{imageops, RgbaImage};
+use serde::{Deserialize, Serialize};
+
+/// 一个缩放版本
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScaleVariant {
+    /// 相对于全分辨率图集的缩放比例（0 < scale ≤ 1，如 0.5）
+    pub scale: f32,
+    /// 文件名后缀（如 `-sd`、`@1x`）
+    pub suffix: String,
+}
+
+impl ScaleVariant {
+    /// 该版本的文件名（不含扩展名）
+    pub fn file_stem(&self, stem: &str) -> String {
+        format!("{}{}", stem, self.suffix)
+    }
+}
+
+/// 检查缩放版本列表
+///
+/// # Returns
+/// * `Result<(), String>` - 比例不在 (0, 1] 内、后缀为空、含路径分隔符或重复时返回错误
+pub fn validate_variants(variants: &[ScaleVariant]) -> Result<(), String> {
+    for (index, variant) in variants.iter().enumerate() {
+        if !(variant.scale > 0.0 && variant.scale <= 1.0) {
+            return Err(format!("缩放版本 {} 的比例 {} 无效（应在 0-1 之间）", variant.suffix, variant.scale));
+        }
+        if variant.suffix.is_empty() {
+            return Err("缩放版本的后缀不能为空".to_string());
+        }
+        if variant.suffix.contains(['/', '\\']) || variant.suffix.contains("..") {
+            return Err(format!("缩放版本后缀 {} 不能包含路径", variant.suffix));
+        }
+        if variants[..index].iter().any(|other| other.suffix == variant.suffix) {
+            return Err(format!("缩放版本后缀重复: {}", variant.suffix));
+        }
+    }
+    Ok(())
+}
+
+/// 缩放后的纹理尺寸（向上取整，至少 1 像素）
+pub fn scaled_texture_size(width: u32, height: u32, scale: f32) -> (u32, u32) {
+    let scale_side = |side: u32| ((side as f64 * scale as f64).ceil() as u32).max(1);
+    (scale_side(width), scale_side(height))
+}
+
+/// 缩小纹理
+///
+/// # Arguments
+/// * `texture` - 全分辨率纹理
+/// * `scale` - 缩放比例
+///
+/// # Returns
+/// * `RgbaImage` - 尺寸为 [`scaled_texture_size`] 的纹理
+pub fn scale_texture(texture: &RgbaImage, scale: f32) -> RgbaImage {
+    let (width, height) = scaled_texture_size(texture.width(), texture.height(), scale);
+    if (width, height) == texture.dimensions() {
+        return texture.clone();
+    }
+    imageops::resize(texture, width, height, imageops::FilterType::Triangle)
+}
+
+/// 缩放帧数据
+///
+/// 纹理区域按左上角与右下角分别取整，保证相邻帧缩放后仍不重叠；
+/// 原始尺寸与偏移四舍五入，尺寸至少 1 像素；九宫格边距同样四舍五入并限制在缩放后的帧内
+///
+/// # Arguments
+/// * `sprites` - 全分辨率图集中的帧
+/// * `scale` - 缩放比例
+///
+/// # Returns
+/// * `Vec<PackedSprite>` - 缩放后的帧
+pub fn scale_sprites(sprites: &[PackedSprite], scale: f32) -> Vec<PackedSprite> {
+    let scale = scale as f64;
+    let edge = |value: u32| (value as f64 * scale).round() as u32;
+    let size = |value: u32| ((value as f64 * scale).round() as u32).max(1);
+    let offset = |value: i32| (value as f64 * scale).round() as i32;
+    sprites
+        .iter()
+        .map(|sprite| {
+            let (x, y) = (edge(sprite.x), edge(sprite.y));
+            let scaled = PackedSprite {
+                x,
+                y,
+                width: edge(sprite.x + sprite.width).saturating_sub(x).max(1),
+                height: edge(sprite.y + sprite.height).saturating_sub(y).max(1),
+                original_width: size(sprite.original_width),
+                original_height: size(sprite.original_height),
+                offset_x: offset(sprite.offset_x),
+                offset_y: offset(sprite.offset_y),
+                ..sprite.clone()
+            };
+            let (width, height) = scaled.frame_size();
+            let nine_slice = sprite.nine_slice.map(|insets| {
+                let (left, top) = (edge(insets.left).min(width), edge(insets.top).min(height));
+                NineSlice {
+                    left,
+                    right: edge(insets.right).min(width - left),
+                    top,
+                    bottom: edge(insets.bottom).min(height - top),
+                }
+            });
+            PackedSprite { nine_slice, ..scaled }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    fn sprite(name: &str, x: u32, width: u32) -> PackedSprite {
+        PackedSprite {
+            id: name.to_string(),
+            name: name.to_string(),
+            x,
+            y: 2,
+            width,
+            height: 9,
+            rotated: false,
+            original_width: 15,
+            original_height: 13,
+            trimmed: true,
+            offset_x: -3,
+            offset_y: 1,
+            flip_x: false,
+            flip_y: false,
+            anchor: None,
+            nine_slice: None,
+        }
+    }
+
+    #[test]
+    fn test_scale_sprites() {
+        let sprites = vec![sprite("a", 1, 5), sprite("b", 6, 7)];
+        let half = scale_sprites(&sprites, 0.5);
+        let rects: Vec<_> = half.iter().map(|s| (s.x, s.y, s.width, s.height)).collect();
+        // a: 1..6 -> 1..3，b: 6..13 -> 3..7，相邻帧不重叠
+        assert_eq!(rects, vec![(1, 1, 2, 5), (3, 1, 4, 5)]);
+        assert_eq!((half[0].original_width, half[0].original_height), (8, 7));
+        assert_eq!((half[0].offset_x, half[0].offset_y), (-2, 1));
+
+        let tiny = scale_sprites(&[sprite("c", 0, 1)], 0.1);
+        assert_eq!((tiny[0].width, tiny[0].height, tiny[0].original_width), (1, 1, 2));
+        let same = scale_sprites(&sprites, 1.0);
+        assert_eq!((same[1].x, same[1].width, same[1].offset_x), (6, 7, -3));
+
+        // 九宫格边距随帧缩放，并限制在缩放后的帧内
+        let panel = PackedSprite {
+            nine_slice: Some(NineSlice { left: 3, right: 2, top: 5, bottom: 4 }),
+            ..sprite("a", 1, 5)
+        };
+        let half = scale_sprites(std::slice::from_ref(&panel), 0.5);
+        assert_eq!(half[0].nine_slice, Some(NineSlice { left: 2, right: 0, top: 3, bottom: 2 }));
+        assert!(half[0].nine_slice.unwrap().validate(half[0].width, half[0].height).is_ok());
+        // 旋转帧的边距相对未旋转的帧尺寸
+        let rotated = PackedSprite { rotated: true, ..panel.clone() };
+        let half = scale_sprites(&[rotated], 0.5);
+        assert_eq!(half[0].nine_slice, Some(NineSlice { left: 2, right: 1, top: 2, bottom: 0 }));
+        assert_eq!(scale_sprites(&[panel], 1.0)[0].nine_slice, Some(NineSlice { left: 3, right: 2, top: 5, bottom: 4 }));
+        assert!(scale_sprites(&sprites, 0.5).iter().all(|s| s.nine_slice.is_none()));
+    }
+
+    #[test]
+    fn test_scale_texture_and_validate() {
+        let texture = RgbaImage::from_pixel(7, 4, Rgba([10, 20, 30, 255]));
+        let half = scale_texture(&texture, 0.5);
+        assert_eq!(half.dimensions(), (4, 2));
+        assert_eq!(half.get_pixel(1, 1), &Rgba([10, 20, 30, 255]));
+        assert_eq!(scaled_texture_size(1, 1, 0.25), (1, 1));
+
+        let sd = ScaleVariant { scale: 0.5, suffix: "-sd".to_string() };
+        assert_eq!(sd.file_stem("ui"), "ui-sd");
+        assert!(validate_variants(std::slice::from_ref(&sd)).is_ok());
+        assert!(validate_variants(&[sd.clone(), sd.clone()]).unwrap_err().contains("重复"));
+        assert!(validate_variants(&[ScaleVariant { scale: 2.0, ..sd.clone() }]).is_err());
+        assert!(validate_variants(&[ScaleVariant { scale: f32::NAN, ..sd.clone() }]).is_err());
+        assert!(validate_variants(&[ScaleVariant { suffix: "/../x".to_string(), ..sd.clone() }]).is_err());
+        assert!(validate_variants(&[ScaleVariant { suffix: String::new(), ..sd }]).is_err());
+    }
+}
@@ -0,0 +1,635 @@
+/// 打包会话 (Pack Session)
+///
+/// 一个会话收集来自多个来源的精灵：独立导入的图片，以及从一张或多张图集中切分出的帧，
+/// 最终统一打包到同一张输出图集。会话只记录来源与尺寸，图像在打包时才加载
+
+use crate::hit_area::HitArea;
+use crate::naming::{render_name, FrameNumbering, NameContext};
+use crate::notes::validate_note;
+use crate::preprocess::PreprocessStep;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// 会话精灵来源
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SpriteSource {
+    /// 独立图片文件
+    Image {
+        /// 文件路径
+        path: String,
+    },
+    /// 图集中的一帧
+    #[serde(rename_all = "camelCase")]
+    SheetFrame {
+        /// 图集文件路径
+        sheet_path: String,
+        /// 帧在图集中的 X 坐标
+        x: u32,
+        /// 帧在图集中的 Y 坐标
+        y: u32,
+    },
+}
+
+impl SpriteSource {
+    /// 需要读取的文件路径
+    pub fn path(&self) -> &str {
+        match self {
+            SpriteSource::Image { path } => path,
+            SpriteSource::SheetFrame { sheet_path, .. } => sheet_path,
+        }
+    }
+}
+
+/// 来源文件指纹（加入会话时记录，用于发现导入后被修改或删除的源文件）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceStamp {
+    /// 修改时间（Unix 毫秒）
+    pub modified_ms: u64,
+    /// 文件大小（字节）
+    pub size: u64,
+    /// 文件内容的 MD5
+    pub hash: String,
+}
+
+/// 来源过期原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StaleReason {
+    /// 文件内容已改变
+    Modified,
+    /// 文件已删除或无法读取
+    Missing,
+}
+
+/// 来源文件已过期的精灵
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaleSource {
+    /// 精灵 ID
+    pub id: String,
+    /// 帧名称
+    pub name: String,
+    /// 来源文件路径
+    pub path: String,
+    /// 过期原因
+    pub reason: StaleReason,
+}
+
+/// 会话中的精灵
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionSprite {
+    /// 唯一标识符
+    pub id: String,
+    /// 帧名称
+    pub name: String,
+    /// 来源
+    pub source: SpriteSource,
+    /// 宽度
+    pub width: u32,
+    /// 高度
+    pub height: u32,
+    /// 标签
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// 轴心点（相对帧尺寸的 0~1 坐标，未设置时使用引擎默认值）
+    #[serde(default)]
+    pub pivot: Option<(f32, f32)>,
+    /// 打包时是否允许旋转
+    #[serde(default = "default_true")]
+    pub allow_rotation: bool,
+    /// 是否从打包中排除
+    #[serde(default)]
+    pub excluded: bool,
+    /// 来源文件指纹（未记录时不检查来源是否过期）
+    #[serde(default)]
+    pub stamp: Option<SourceStamp>,
+    /// 备注（如「发布前替换」）
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl SessionSprite {
+    /// 创建没有附加元数据的精灵
+    pub fn new(id: impl Into<String>, name: impl Into<String>, source: SpriteSource, width: u32, height: u32) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            source,
+            width,
+            height,
+            tags: Vec::new(),
+            pivot: None,
+            allow_rotation: true,
+            excluded: false,
+            stamp: None,
+            note: None,
+        }
+    }
+}
+
+/// 批量修改内容，未设置的字段保持不变
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SpritePatch {
+    /// 重命名模板（见 `naming` 模块），序号按传入 ID 的顺序分配
+    pub rename: Option<String>,
+    /// 重命名序号的起始值与步长（可选，默认从 1 开始；补零位数由模板中的 `{index:N}` 决定）
+    pub numbering: Option<FrameNumbering>,
+    /// 添加的标签
+    pub add_tags: Vec<String>,
+    /// 移除的标签
+    pub remove_tags: Vec<String>,
+    /// 设置轴心点
+    pub pivot: Option<(f32, f32)>,
+    /// 清除轴心点（优先于 `pivot`）
+    pub clear_pivot: bool,
+    /// 是否允许旋转
+    pub allow_rotation: Option<bool>,
+    /// 是否排除
+    pub excluded: Option<bool>,
+    /// 设置备注
+    pub note: Option<String>,
+    /// 清除备注（优先于 `note`）
+    pub clear_note: bool,
+}
+
+impl SpritePatch {
+    /// 检查与具体精灵无关的参数
+    pub fn validate(&self) -> Result<(), String> {
+        if self.add_tags.iter().chain(&self.remove_tags).any(|tag| tag.trim().is_empty()) {
+            return Err("标签不能为空".to_string());
+        }
+        if let Some(numbering) = &self.numbering {
+            numbering.validate()?;
+        }
+        if let Some((x, y)) = self.pivot {
+            if !(0.0..=1.0).contains(&x) || !(0.0..=1.0).contains(&y) {
+                return Err(format!("轴心点必须在 0~1 范围内: ({}, {})", x, y));
+            }
+        }
+        if let (Some(note), false) = (&self.note, self.clear_note) {
+            validate_note(note)?;
+        }
+        Ok(())
+    }
+
+    fn apply(&self, sprite: &mut SessionSprite, index: u32) -> Result<(), String> {
+        if let Some(template) = &self.rename {
+            sprite.name = render_name(template, &NameContext { name: &sprite.name, index })?;
+        }
+        for tag in &self.add_tags {
+            if !sprite.tags.contains(tag) {
+                sprite.tags.push(tag.clone());
+            }
+        }
+        sprite.tags.retain(|tag| !self.remove_tags.contains(tag));
+        if self.clear_pivot {
+            sprite.pivot = None;
+        } else if self.pivot.is_some() {
+            sprite.pivot = self.pivot;
+        }
+        if let Some(allow) = self.allow_rotation {
+            sprite.allow_rotation = allow;
+        }
+        if let Some(excluded) = self.excluded {
+            sprite.excluded = excluded;
+        }
+        if self.clear_note {
+            sprite.note = None;
+        } else if self.note.is_some() {
+            sprite.note = self.note.clone();
+        }
+        Ok(())
+    }
+}
+
+/// 精灵移植方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransplantMode {
+    /// 复制（来源会话保持不变，副本使用新 ID）
+    #[default]
+    Copy,
+    /// 移动（从来源会话移除，保留原 ID）
+    Move,
+}
+
+/// 打包会话
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Session {
+    /// 会话 ID
+    pub id: String,
+    /// 精灵列表（按加入顺序）
+    pub sprites: Vec<SessionSprite>,
+    /// 打包时应用于每个精灵的预处理步骤（按顺序执行）
+    #[serde(default)]
+    pub preprocess: Vec<PreprocessStep>,
+    /// 帧的碰撞区域（帧名称 -> 区域列表）
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub hit_areas: BTreeMap<String, Vec<HitArea>>,
+    /// 已注册的导出模板（名称 -> 模板文件路径）
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub export_templates: BTreeMap<String, String>,
+}
+
+impl Session {
+    /// 创建空会话
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            sprites: Vec::new(),
+            preprocess: Vec::new(),
+            hit_areas: BTreeMap::new(),
+            export_templates: BTreeMap::new(),
+        }
+    }
+
+    /// 添加精灵
+    ///
+    /// 全部检查通过后才会加入，任何一个 ID 与已有精灵或本批其他精灵重复时整批拒绝
+    ///
+    /// # Arguments
+    /// * `sprites` - 待添加的精灵
+    ///
+    /// # Returns
+    /// * `Result<(), String>` - 重复 ID 或空尺寸时返回错误信息
+    pub fn add_sprites(&mut self, sprites: Vec<SessionSprite>) -> Result<(), String> {
+        let mut ids: HashSet<&str> =
+            self.sprites.iter().map(|s| s.id.as_str()).collect();
+        for sprite in &sprites {
+            if !ids.insert(&sprite.id) {
+                return Err(format!("精灵 ID 重复: {}", sprite.id));
+            }
+            if sprite.width == 0 || sprite.height == 0 {
+                return Err(format!("精灵 {} 的尺寸为 0", sprite.name));
+            }
+        }
+        self.sprites.extend(sprites);
+        Ok(())
+    }
+
+    /// 移除精灵
+    ///
+    /// # Returns
+    /// * `usize` - 实际移除的数量
+    pub fn remove_sprites(&mut self, ids: &[String]) -> usize {
+        let before = self.sprites.len();
+        self.sprites.retain(|s| !ids.contains(&s.id));
+        before - self.sprites.len()
+    }
+
+    /// 按 ID 查找精灵
+    pub fn sprite(&self, id: &str) -> Option<&SessionSprite> {
+        self.sprites.iter().find(|s| s.id == id)
+    }
+
+    /// 查找来源文件在记录指纹后被修改或删除的精灵
+    ///
+    /// 每个来源文件只检查一次，同一张图集切出的帧共用检查结果
+    ///
+    /// # Arguments
+    /// * `current` - 读取来源文件的当前指纹（参数为路径与记录的指纹，文件不存在时返回 None）
+    ///
+    /// # Returns
+    /// * `Vec<StaleSource>` - 过期的精灵，按会话中的顺序排列；没有记录指纹的精灵不检查
+    pub fn stale_sources(&self, mut current: impl FnMut(&str, &SourceStamp) -> Option<SourceStamp>) -> Vec<StaleSource> {
+        let mut checked: HashMap<&str, Option<SourceStamp>> = HashMap::new();
+        let mut stale = Vec::new();
+        for sprite in &self.sprites {
+            let Some(recorded) = &sprite.stamp else {
+                continue;
+            };
+            let path = sprite.source.path();
+            let now = checked.entry(path).or_insert_with(|| current(path, recorded));
+            let reason = match now {
+                None => StaleReason::Missing,
+                Some(now) if now.hash != recorded.hash => StaleReason::Modified,
+                Some(_) => continue,
+            };
+            stale.push(StaleSource {
+                id: sprite.id.clone(),
+                name: sprite.name.clone(),
+                path: path.to_string(),
+                reason,
+            });
+        }
+        stale
+    }
+
+    /// 按重新读取的来源文件更新精灵的尺寸与指纹
+    ///
+    /// # Arguments
+    /// * `id` - 精灵 ID
+    /// * `width` / `height` - 新尺寸
+    /// * `stamp` - 新指纹
+    ///
+    /// # Returns
+    /// * `Result<(), String>` - ID 不存在或尺寸为 0 时返回错误信息
+    pub fn refresh_source(&mut self, id: &str, width: u32, height: u32, stamp: SourceStamp) -> Result<(), String> {
+        let sprite = self
+            .sprites
+            .iter_mut()
+            .find(|s| s.id == id)
+            .ok_or_else(|| format!("精灵不存在: {}", id))?;
+        if width == 0 || height == 0 {
+            return Err(format!("精灵 {} 的尺寸为 0", sprite.name));
+        }
+        sprite.width = width;
+        sprite.height = height;
+        sprite.stamp = Some(stamp);
+        Ok(())
+    }
+
+    /// 批量修改精灵
+    ///
+    /// 在副本上应用全部修改并检查名称唯一性，任何一步失败时会话保持不变
+    ///
+    /// # Arguments
+    /// * `ids` - 要修改的精灵 ID（顺序决定重命名序号）
+    /// * `patch` - 修改内容
+    ///
+    /// # Returns
+    /// * `Result<(), String>` - ID 不存在、参数无效或名称冲突时返回错误信息
+    pub fn update_sprites(&mut self, ids: &[String], patch: &SpritePatch) -> Result<(), String> {
+        patch.validate()?;
+
+        let numbering = patch.numbering.unwrap_or_default();
+        let mut updated = self.sprites.clone();
+        let mut seen = HashSet::new();
+        for (position, id) in ids.iter().enumerate() {
+            if !seen.insert(id) {
+                return Err(format!("精灵 ID 重复: {}", id));
+            }
+            let sprite = updated
+                .iter_mut()
+                .find(|s| &s.id == id)
+                .ok_or_else(|| format!("精灵不存在: {}", id))?;
+            patch.apply(sprite, numbering.number(position as u32)?)?;
+        }
+
+        if patch.rename.is_some() {
+            let mut names = HashSet::new();
+            if let Some(duplicate) = updated.iter().find(|s| !names.insert(s.name.as_str())) {
+                return Err(format!("重命名后名称冲突: {}", duplicate.name));
+            }
+        }
+
+        self.sprites = updated;
+        Ok(())
+    }
+
+    /// 把另一个会话中的精灵移植到本会话
+    ///
+    /// 名称、来源、标签、轴心点等元数据原样保留；来源帧不变，重新打包时得到相同的裁剪结果。
+    /// 全部检查通过后才会修改两个会话
+    ///
+    /// # Arguments
+    /// * `source` - 来源会话
+    /// * `ids` - 要移植的精灵 ID
+    /// * `mode` - 复制或移动
+    /// * `new_id` - 复制时为副本生成新 ID
+    ///
+    /// # Returns
+    /// * `Result<Vec<String>, String>` - 精灵在本会话中的 ID；来源与目标相同、
+    ///   ID 不存在或与本会话中的帧重名时返回错误
+    pub fn transplant_from(
+        &mut self,
+        source: &mut Session,
+        ids: &[String],
+        mode: TransplantMode,
+        mut new_id: impl FnMut() -> String,
+    ) -> Result<Vec<String>, String> {
+        if source.id == self.id {
+            return Err("来源会话与目标会话相同".to_string());
+        }
+        if ids.is_empty() {
+            return Err("没有选择要移植的精灵".to_string());
+        }
+
+        let mut names: HashSet<&str> = self.sprites.iter().map(|s| s.name.as_str()).collect();
+        let mut moved = Vec::with_capacity(ids.len());
+        for id in ids {
+            let sprite = source.sprite(id).ok_or_else(|| format!("精灵不存在: {}", id))?;
+            if !names.insert(&sprite.name) {
+                return Err(format!("目标会话中已有同名帧: {}", sprite.name));
+            }
+            let mut sprite = sprite.clone();
+            if mode == TransplantMode::Copy {
+                sprite.id = new_id();
+            }
+            moved.push(sprite);
+        }
+
+        let moved_ids = moved.iter().map(|s| s.id.clone()).collect();
+        self.add_sprites(moved)?;
+        if mode == TransplantMode::Move {
+            source.remove_sprites(ids);
+        }
+        Ok(moved_ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sprite(id: &str, source: SpriteSource) -> SessionSprite {
+        SessionSprite::new(id, format!("{}.png", id), source, 16, 16)
+    }
+
+    fn frame(sheet: &str, x: u32) -> SpriteSource {
+        SpriteSource::SheetFrame {
+            sheet_path: sheet.to_string(),
+            x,
+            y: 0,
+        }
+    }
+
+    #[test]
+    fn test_mixed_sources() {
+        let mut session = Session::new("s1");
+        session
+            .add_sprites(vec![
+                sprite("hero", SpriteSource::Image { path: "hero.png".to_string() }),
+                sprite("walk_01", frame("walk.png", 0)),
+                sprite("jump_01", frame("jump.png", 16)),
+            ])
+            .unwrap();
+
+        assert_eq!(session.sprites.len(), 3);
+        assert_eq!(session.sprite("jump_01").unwrap().source.path(), "jump.png");
+        assert_eq!(session.remove_sprites(&["hero".to_string(), "missing".to_string()]), 1);
+    }
+
+    #[test]
+    fn test_add_is_atomic() {
+        let mut session = Session::new("s1");
+        session.add_sprites(vec![sprite("a", frame("walk.png", 0))]).unwrap();
+
+        let err = session
+            .add_sprites(vec![sprite("b", frame("walk.png", 16)), sprite("a", frame("walk.png", 32))])
+            .unwrap_err();
+        assert!(err.contains("a"));
+        assert_eq!(session.sprites.len(), 1);
+    }
+
+    #[test]
+    fn test_source_serialization() {
+        let json = serde_json::to_string(&frame("walk.png", 32)).unwrap();
+        assert_eq!(json, r#"{"type":"sheetFrame","sheetPath":"walk.png","x":32,"y":0}"#);
+    }
+
+    #[test]
+    fn test_update_sprites() {
+        let mut session = Session::new("s1");
+        session
+            .add_sprites(vec![
+                sprite("a", frame("walk.png", 0)),
+                sprite("b", frame("walk.png", 16)),
+                sprite("c", frame("walk.png", 32)),
+            ])
+            .unwrap();
+
+        let patch = SpritePatch {
+            rename: Some("run_{index:02}.{ext}".to_string()),
+            add_tags: vec!["player".to_string()],
+            pivot: Some((0.5, 0.0)),
+            allow_rotation: Some(false),
+            ..Default::default()
+        };
+        session.update_sprites(&["c".to_string(), "a".to_string()], &patch).unwrap();
+
+        let c = session.sprite("c").unwrap();
+        assert_eq!(c.name, "run_01.png");
+        assert_eq!(c.tags, vec!["player".to_string()]);
+        assert_eq!(c.pivot, Some((0.5, 0.0)));
+        assert!(!c.allow_rotation);
+        assert_eq!(session.sprite("a").unwrap().name, "run_02.png");
+        assert_eq!(session.sprite("b").unwrap().name, "b.png");
+
+        let from_zero = SpritePatch {
+            rename: Some("run_{index:03}.{ext}".to_string()),
+            numbering: Some(FrameNumbering { start: 0, step: 10, digits: 0 }),
+            ..Default::default()
+        };
+        session.update_sprites(&["a".to_string(), "c".to_string()], &from_zero).unwrap();
+        assert_eq!(session.sprite("a").unwrap().name, "run_000.png");
+        assert_eq!(session.sprite("c").unwrap().name, "run_010.png");
+
+        let note = SpritePatch { note: Some("发布前替换".to_string()), ..Default::default() };
+        session.update_sprites(&["b".to_string()], &note).unwrap();
+        assert_eq!(session.sprite("b").unwrap().note.as_deref(), Some("发布前替换"));
+        let blank = SpritePatch { note: Some(" ".to_string()), ..Default::default() };
+        assert!(session.update_sprites(&["b".to_string()], &blank).is_err());
+        let clear = SpritePatch { clear_note: true, ..Default::default() };
+        session.update_sprites(&["b".to_string()], &clear).unwrap();
+        assert_eq!(session.sprite("b").unwrap().note, None);
+    }
+
+    #[test]
+    fn test_update_sprites_is_atomic() {
+        let mut session = Session::new("s1");
+        session
+            .add_sprites(vec![sprite("a", frame("walk.png", 0)), sprite("b", frame("walk.png", 16))])
+            .unwrap();
+        let before = session.clone();
+
+        // 没有序号的模板会让两个精灵重名
+        let rename = SpritePatch {
+            rename: Some("idle.png".to_string()),
+            excluded: Some(true),
+            ..Default::default()
+        };
+        assert!(session.update_sprites(&["a".to_string(), "b".to_string()], &rename).is_err());
+
+        let missing = SpritePatch {
+            excluded: Some(true),
+            ..Default::default()
+        };
+        assert!(session.update_sprites(&["a".to_string(), "zzz".to_string()], &missing).is_err());
+
+        let bad_pivot = SpritePatch {
+            pivot: Some((1.5, 0.0)),
+            ..Default::default()
+        };
+        assert!(session.update_sprites(&["a".to_string()], &bad_pivot).is_err());
+        assert_eq!(session, before);
+    }
+
+    #[test]
+    fn test_transplant_between_sessions() {
+        let mut shipped = Session::new("shipped");
+        shipped.add_sprites(vec![sprite("hero", frame("ui.png", 0))]).unwrap();
+        let mut hotfix = Session::new("hotfix");
+        let mut coin = sprite("coin", frame("fx.png", 16));
+        coin.pivot = Some((0.5, 0.0));
+        hotfix.add_sprites(vec![coin, sprite("hero2", frame("fx.png", 32))]).unwrap();
+
+        let ids = shipped
+            .transplant_from(&mut hotfix, &["coin".to_string()], TransplantMode::Copy, || "copy-1".to_string())
+            .unwrap();
+        assert_eq!(ids, vec!["copy-1".to_string()]);
+        assert_eq!(shipped.sprite("copy-1").unwrap().pivot, Some((0.5, 0.0)));
+        assert_eq!(hotfix.sprites.len(), 2);
+
+        // 同名帧整批拒绝，两个会话都不变
+        let before = (shipped.clone(), hotfix.clone());
+        let err = shipped
+            .transplant_from(&mut hotfix, &["hero2".to_string(), "coin".to_string()], TransplantMode::Move, String::new)
+            .unwrap_err();
+        assert!(err.contains("coin.png"));
+        assert_eq!((shipped.clone(), hotfix.clone()), before);
+
+        shipped
+            .transplant_from(&mut hotfix, &["hero2".to_string()], TransplantMode::Move, String::new)
+            .unwrap();
+        assert_eq!(shipped.sprite("hero2").unwrap().source.path(), "fx.png");
+        assert!(hotfix.sprite("hero2").is_none());
+    }
+
+    #[test]
+    fn test_stale_sources() {
+        let stamp = |hash: &str| SourceStamp { modified_ms: 1, size: 4, hash: hash.to_string() };
+        let mut session = Session::new("s");
+        let stamped = |id: &str, source: SpriteSource| SessionSprite { stamp: Some(stamp("a")), ..sprite(id, source) };
+        session
+            .add_sprites(vec![
+                stamped("walk1", frame("hero.png", 0)),
+                stamped("walk2", frame("hero.png", 16)),
+                stamped("coin", SpriteSource::Image { path: "coin.png".to_string() }),
+                stamped("gone", SpriteSource::Image { path: "gone.png".to_string() }),
+                sprite("legacy", SpriteSource::Image { path: "legacy.png".to_string() }),
+            ])
+            .unwrap();
+
+        let mut reads = Vec::new();
+        let stale = session.stale_sources(|path, _| {
+            reads.push(path.to_string());
+            match path {
+                "hero.png" => Some(stamp("b")),
+                "gone.png" => None,
+                _ => Some(stamp("a")),
+            }
+        });
+        // 同一张图集只读取一次，未记录指纹的精灵不检查
+        assert_eq!(reads, vec!["hero.png", "coin.png", "gone.png"]);
+        let summary: Vec<_> = stale.iter().map(|s| (s.id.as_str(), s.reason)).collect();
+        assert_eq!(
+            summary,
+            vec![("walk1", StaleReason::Modified), ("walk2", StaleReason::Modified), ("gone", StaleReason::Missing)]
+        );
+
+        session.refresh_source("coin", 20, 24, stamp("c")).unwrap();
+        assert_eq!((session.sprites[2].width, session.sprites[2].height), (20, 24));
+        assert!(session.refresh_source("coin", 0, 24, stamp("c")).is_err());
+        assert!(session.refresh_source("missing", 1, 1, stamp("c")).is_err());
+    }
+}
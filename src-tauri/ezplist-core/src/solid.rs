@@ -0,0 +1,127 @@
+/// 纯色区域注入 (Solid Region Injection)
+///
+/// 在图集中放入一小块纯白（或指定颜色）的帧，例如 `__white`。引擎绘制线框、血条等
+/// 纯色图元时采样这块区域，就能与精灵共用同一张纹理合批，减少一次 draw call
+///
+/// 区域取中心像素采样时不受线性过滤影响，因此默认尺寸为 4x4 而不是 1x1
+
+use image::{Rgba, RgbaImage};
+use serde::{Deserialize, Serialize};
+
+/// 白色区域的保留帧名
+pub const WHITE_REGION_NAME: &str = "__white";
+
+/// 纯色区域的默认边长
+pub const DEFAULT_SOLID_SIZE: u32 = 4;
+
+/// 纯色区域的最大边长
+pub const MAX_SOLID_SIZE: u32 = 64;
+
+/// 注入图集的纯色区域
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SolidRegion {
+    /// 帧名称（同时作为精灵 ID，不能与用户精灵重名）
+    pub name: String,
+    /// RGBA 颜色
+    pub color: [u8; 4],
+    /// 边长（像素，默认 4）
+    #[serde(default = "default_size")]
+    pub size: u32,
+}
+
+fn default_size() -> u32 {
+    DEFAULT_SOLID_SIZE
+}
+
+impl SolidRegion {
+    /// 默认的 `__white` 区域
+    pub fn white() -> Self {
+        Self {
+            name: WHITE_REGION_NAME.to_string(),
+            color: [255, 255, 255, 255],
+            size: DEFAULT_SOLID_SIZE,
+        }
+    }
+
+    /// 检查名称与尺寸
+    ///
+    /// # Returns
+    /// * `Result<(), String>` - 名称为空或尺寸不在 1..=64 时返回错误
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("纯色区域名称不能为空".to_string());
+        }
+        if !(1..=MAX_SOLID_SIZE).contains(&self.size) {
+            return Err(format!(
+                "纯色区域 {} 的尺寸 {} 无效（应为 1-{}）",
+                self.name, self.size, MAX_SOLID_SIZE
+            ));
+        }
+        Ok(())
+    }
+
+    /// 生成区域图像
+    pub fn image(&self) -> RgbaImage {
+        RgbaImage::from_pixel(self.size, self.size, Rgba(self.color))
+    }
+}
+
+/// 合并纯色区域配置
+///
+/// # Arguments
+/// * `white` - 是否注入默认的 `__white` 区域
+/// * `regions` - 额外的纯色区域
+///
+/// # Returns
+/// * `Result<Vec<SolidRegion>, String>` - 去掉重复 `__white` 后的区域列表；
+///   区域无效或名称重复时返回错误
+pub fn solid_regions(white: bool, regions: &[SolidRegion]) -> Result<Vec<SolidRegion>, String> {
+    let mut result: Vec<SolidRegion> = Vec::with_capacity(regions.len() + 1);
+    // 显式配置的 __white 优先于默认白色区域
+    if white && !regions.iter().any(|r| r.name == WHITE_REGION_NAME) {
+        result.push(SolidRegion::white());
+    }
+    for region in regions {
+        region.validate()?;
+        if result.iter().any(|r| r.name == region.name) {
+            return Err(format!("纯色区域名称重复: {}", region.name));
+        }
+        result.push(region.clone());
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solid_regions() {
+        let red = SolidRegion {
+            name: "__red".to_string(),
+            color: [255, 0, 0, 255],
+            size: 2,
+        };
+        let regions = solid_regions(true, std::slice::from_ref(&red)).unwrap();
+        let names: Vec<_> = regions.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec![WHITE_REGION_NAME, "__red"]);
+        assert_eq!(red.image().get_pixel(1, 1), &Rgba([255, 0, 0, 255]));
+        assert_eq!(regions[0].image().dimensions(), (4, 4));
+
+        // 显式的 __white 覆盖默认区域
+        let gray = SolidRegion { name: WHITE_REGION_NAME.to_string(), color: [200, 200, 200, 255], size: 8 };
+        assert_eq!(solid_regions(true, std::slice::from_ref(&gray)).unwrap(), vec![gray]);
+        assert!(solid_regions(false, &[]).unwrap().is_empty());
+
+        assert!(solid_regions(false, &[red.clone(), red.clone()]).unwrap_err().contains("重复"));
+        assert!(solid_regions(false, &[SolidRegion { size: 0, ..red.clone() }]).is_err());
+        assert!(solid_regions(false, &[SolidRegion { name: " ".to_string(), ..red }]).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_default_size() {
+        let region: SolidRegion = serde_json::from_str(r#"{"name":"__black","color":[0,0,0,255]}"#).unwrap();
+        assert_eq!(region.size, DEFAULT_SOLID_SIZE);
+    }
+}
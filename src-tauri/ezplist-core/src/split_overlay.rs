@@ -0,0 +1,205 @@
+/// 切分预览叠加图 (Split Overlay Preview)
+///
+/// 在缩小后的图集上绘制帧矩形和帧序号，由后端按像素坐标渲染，
+/// 与前端画布的缩放、抗锯齿实现无关，用于确认切分结果是否与像素边界对齐
+
+use crate::types::FrameInfo;
+use image::imageops::{self, FilterType};
+use image::{Rgba, RgbaImage};
+use serde::Deserialize;
+
+/// 默认的预览图最大边长
+pub const DEFAULT_OVERLAY_MAX_SIZE: u32 = 1024;
+
+/// 序号字形宽度（像素，缩放前）
+const GLYPH_WIDTH: u32 = 3;
+
+/// 序号字形高度（像素，缩放前）
+const GLYPH_HEIGHT: u32 = 5;
+
+/// 0-9 的 3x5 点阵，每行 3 位，高位在左
+const DIGITS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b010, 0b010, 0b010],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
+/// 叠加图选项
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SplitOverlayOptions {
+    /// 预览图最大边长（图集更大时等比缩小，不放大）
+    pub max_size: u32,
+    /// 帧边框颜色（RGBA）
+    pub color: [u8; 4],
+    /// 是否绘制帧序号（从 0 开始，按帧列表顺序）
+    pub show_indices: bool,
+}
+
+impl Default for SplitOverlayOptions {
+    fn default() -> Self {
+        Self {
+            max_size: DEFAULT_OVERLAY_MAX_SIZE,
+            color: [255, 0, 255, 255],
+            show_indices: true,
+        }
+    }
+}
+
+/// 生成切分预览叠加图
+///
+/// 帧矩形按缩放后的像素取整：左上角向下取整、右下角向上取整，
+/// 相邻帧的边框不会因缩放出现空隙
+///
+/// # Arguments
+/// * `sheet` - 图集图像
+/// * `frames` - 帧列表
+/// * `options` - 叠加图选项
+///
+/// # Returns
+/// * `Result<RgbaImage, String>` - 叠加图，或选项无效时的错误
+pub fn render_split_overlay(
+    sheet: &RgbaImage,
+    frames: &[FrameInfo],
+    options: &SplitOverlayOptions,
+) -> Result<RgbaImage, String> {
+    if options.max_size == 0 {
+        return Err("预览图最大边长必须大于 0".to_string());
+    }
+    let (width, height) = sheet.dimensions();
+    if width == 0 || height == 0 {
+        return Err("图集尺寸无效".to_string());
+    }
+
+    let scale = (options.max_size as f64 / width.max(height) as f64).min(1.0);
+    let mut canvas = if scale < 1.0 {
+        let scaled_width = ((width as f64 * scale).round() as u32).max(1);
+        let scaled_height = ((height as f64 * scale).round() as u32).max(1);
+        imageops::resize(sheet, scaled_width, scaled_height, FilterType::Triangle)
+    } else {
+        sheet.clone()
+    };
+
+    let color = Rgba(options.color);
+    for (index, frame) in frames.iter().enumerate() {
+        let left = (frame.x as f64 * scale).floor() as u32;
+        let top = (frame.y as f64 * scale).floor() as u32;
+        let right = ((frame.x as f64 + frame.width as f64) * scale).ceil() as u32;
+        let bottom = ((frame.y as f64 + frame.height as f64) * scale).ceil() as u32;
+        if right <= left || bottom <= top {
+            continue;
+        }
+        draw_rect(&mut canvas, left, top, right - 1, bottom - 1, color);
+        if options.show_indices {
+            draw_index(&mut canvas, index, left + 1, top + 1, right - left, bottom - top, color);
+        }
+    }
+
+    Ok(canvas)
+}
+
+/// 绘制矩形边框（坐标含端点，超出画布的部分忽略）
+fn draw_rect(canvas: &mut RgbaImage, left: u32, top: u32, right: u32, bottom: u32, color: Rgba<u8>) {
+    for x in left..=right {
+        put_pixel(canvas, x, top, color);
+        put_pixel(canvas, x, bottom, color);
+    }
+    for y in top..=bottom {
+        put_pixel(canvas, left, y, color);
+        put_pixel(canvas, right, y, color);
+    }
+}
+
+/// 在帧左上角绘制序号（带深色底），帧太小放不下时跳过
+fn draw_index(canvas: &mut RgbaImage, index: usize, x: u32, y: u32, frame_width: u32, frame_height: u32, color: Rgba<u8>) {
+    let digits: Vec<usize> = index
+        .to_string()
+        .bytes()
+        .map(|b| (b - b'0') as usize)
+        .collect();
+    // 帧足够大时放大一倍，便于阅读
+    let zoom = if frame_width >= 48 && frame_height >= 24 { 2 } else { 1 };
+    let label_width = (digits.len() as u32 * (GLYPH_WIDTH + 1) + 1) * zoom;
+    let label_height = (GLYPH_HEIGHT + 2) * zoom;
+    if label_width + 2 > frame_width || label_height + 2 > frame_height {
+        return;
+    }
+
+    for dy in 0..label_height {
+        for dx in 0..label_width {
+            put_pixel(canvas, x + dx, y + dy, Rgba([0, 0, 0, 192]));
+        }
+    }
+    for (i, &digit) in digits.iter().enumerate() {
+        let glyph_x = x + (1 + i as u32 * (GLYPH_WIDTH + 1)) * zoom;
+        let glyph_y = y + zoom;
+        for (row, bits) in DIGITS[digit].iter().enumerate() {
+            for col in 0..GLYPH_WIDTH {
+                if bits & (1 << (GLYPH_WIDTH - 1 - col)) == 0 {
+                    continue;
+                }
+                for zy in 0..zoom {
+                    for zx in 0..zoom {
+                        put_pixel(canvas, glyph_x + col * zoom + zx, glyph_y + row as u32 * zoom + zy, color);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn put_pixel(canvas: &mut RgbaImage, x: u32, y: u32, color: Rgba<u8>) {
+    if x < canvas.width() && y < canvas.height() {
+        let pixel = canvas.get_pixel_mut(x, y);
+        let alpha = color[3] as u32;
+        for c in 0..3 {
+            pixel[c] = ((color[c] as u32 * alpha + pixel[c] as u32 * (255 - alpha)) / 255) as u8;
+        }
+        pixel[3] = pixel[3].max(color[3]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(x: u32, y: u32, width: u32, height: u32) -> FrameInfo {
+        FrameInfo { name: String::new(), x, y, width, height, row: 0, col: 0, anchor: None }
+    }
+
+    #[test]
+    fn test_overlay_draws_frame_edges() {
+        let sheet = RgbaImage::new(64, 32);
+        let options = SplitOverlayOptions { show_indices: false, ..Default::default() };
+        let overlay = render_split_overlay(&sheet, &[frame(0, 0, 32, 32), frame(32, 0, 32, 32)], &options).unwrap();
+
+        assert_eq!(overlay.dimensions(), (64, 32));
+        let magenta = Rgba([255, 0, 255, 255]);
+        assert_eq!(*overlay.get_pixel(31, 10), magenta);
+        assert_eq!(*overlay.get_pixel(32, 10), magenta);
+        assert_eq!(*overlay.get_pixel(63, 31), magenta);
+        assert_eq!(overlay.get_pixel(16, 16)[3], 0);
+    }
+
+    #[test]
+    fn test_overlay_downscales_and_labels() {
+        let sheet = RgbaImage::new(400, 200);
+        let options = SplitOverlayOptions { max_size: 100, ..Default::default() };
+        let overlay = render_split_overlay(&sheet, &[frame(0, 0, 200, 200), frame(200, 0, 200, 200)], &options).unwrap();
+
+        assert_eq!(overlay.dimensions(), (100, 50));
+        // 第二帧左边界缩放后位于 x = 50
+        assert_eq!(overlay.get_pixel(50, 25)[3], 255);
+        // 序号底色
+        assert_eq!(overlay.get_pixel(2, 2)[3], 192);
+
+        assert!(render_split_overlay(&sheet, &[], &SplitOverlayOptions { max_size: 0, ..Default::default() }).is_err());
+    }
+}
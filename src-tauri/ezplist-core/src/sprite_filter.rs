@@ -0,0 +1,208 @@
+/// 精灵搜索与过滤 (Sprite Search & Filtering)
+///
+/// 在会话中按名称（通配符或正则）、尺寸范围、标签、来源文件夹筛选精灵，
+/// 所有条件同时满足才算匹配，未设置的条件不参与筛选
+
+use crate::session::SessionSprite;
+use regex::{Regex, RegexBuilder};
+use serde::Deserialize;
+use std::path::Path;
+
+/// 正则表达式编译后的大小上限（字节），防止恶意输入占用过多内存
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+/// 过滤条件
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SpriteFilter {
+    /// 名称通配符（`*` 匹配任意字符，`?` 匹配单个字符，不区分大小写）
+    pub name_glob: Option<String>,
+    /// 名称正则表达式
+    pub name_regex: Option<String>,
+    /// 最小宽度
+    pub min_width: Option<u32>,
+    /// 最大宽度
+    pub max_width: Option<u32>,
+    /// 最小高度
+    pub min_height: Option<u32>,
+    /// 最大高度
+    pub max_height: Option<u32>,
+    /// 必须包含的标签
+    pub tag: Option<String>,
+    /// 来源文件所在的文件夹（图集帧按图集文件所在文件夹计算）
+    pub source_folder: Option<String>,
+}
+
+impl SpriteFilter {
+    /// 编译名称匹配规则
+    ///
+    /// # Returns
+    /// * `Result<CompiledFilter, String>` - 正则表达式无效时返回错误信息
+    pub fn compile(&self) -> Result<CompiledFilter<'_>, String> {
+        let glob = self
+            .name_glob
+            .as_deref()
+            .map(compile_glob)
+            .transpose()?;
+        let regex = self
+            .name_regex
+            .as_deref()
+            .map(|pattern| build_regex(pattern, false))
+            .transpose()?;
+        Ok(CompiledFilter {
+            filter: self,
+            glob,
+            regex,
+        })
+    }
+}
+
+/// 编译后的过滤条件
+#[derive(Debug)]
+pub struct CompiledFilter<'a> {
+    filter: &'a SpriteFilter,
+    glob: Option<Regex>,
+    regex: Option<Regex>,
+}
+
+impl CompiledFilter<'_> {
+    /// 精灵是否满足全部条件
+    pub fn matches(&self, sprite: &SessionSprite) -> bool {
+        let filter = self.filter;
+        let in_range = |value: u32, min: Option<u32>, max: Option<u32>| {
+            min.is_none_or(|min| value >= min) && max.is_none_or(|max| value <= max)
+        };
+
+        self.glob.as_ref().is_none_or(|re| re.is_match(&sprite.name))
+            && self.regex.as_ref().is_none_or(|re| re.is_match(&sprite.name))
+            && in_range(sprite.width, filter.min_width, filter.max_width)
+            && in_range(sprite.height, filter.min_height, filter.max_height)
+            && filter.tag.as_ref().is_none_or(|tag| sprite.tags.contains(tag))
+            && filter.source_folder.as_deref().is_none_or(|folder| {
+                Path::new(sprite.source.path()).parent() == Some(Path::new(folder))
+            })
+    }
+}
+
+/// 筛选精灵
+///
+/// # Arguments
+/// * `sprites` - 精灵列表
+/// * `filter` - 过滤条件
+///
+/// # Returns
+/// * `Result<Vec<String>, String>` - 匹配的精灵 ID（保持原有顺序）
+pub fn filter_sprites(sprites: &[SessionSprite], filter: &SpriteFilter) -> Result<Vec<String>, String> {
+    let compiled = filter.compile()?;
+    Ok(sprites
+        .iter()
+        .filter(|sprite| compiled.matches(sprite))
+        .map(|sprite| sprite.id.clone())
+        .collect())
+}
+
+/// 编译通配符（完整匹配，不区分大小写）
+///
+/// # Returns
+/// * `Result<Regex, String>` - 通配符过长时返回错误信息
+pub fn compile_glob(glob: &str) -> Result<Regex, String> {
+    build_regex(&glob_to_regex(glob), true)
+}
+
+/// 把通配符转换为完整匹配的正则表达式
+fn glob_to_regex(glob: &str) -> String {
+    let mut pattern = String::from("^");
+    for c in glob.chars() {
+        match c {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            c => pattern.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    pattern.push('$');
+    pattern
+}
+
+fn build_regex(pattern: &str, case_insensitive: bool) -> Result<Regex, String> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(case_insensitive)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .build()
+        .map_err(|e| format!("无效的匹配规则 {}: {}", pattern, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::SpriteSource;
+
+    fn sprite(id: &str, name: &str, path: &str, size: u32, tags: &[&str]) -> SessionSprite {
+        SessionSprite {
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..SessionSprite::new(id, name, SpriteSource::Image { path: path.to_string() }, size, size)
+        }
+    }
+
+    fn sprites() -> Vec<SessionSprite> {
+        vec![
+            sprite("1", "hero_walk_01.png", "/art/hero/hero_walk_01.png", 32, &["player"]),
+            sprite("2", "hero_walk_02.png", "/art/hero/hero_walk_02.png", 32, &["player"]),
+            sprite("3", "coin.png", "/art/items/coin.png", 16, &["pickup"]),
+            sprite("4", "Boss_Idle.png", "/art/boss/Boss_Idle.png", 128, &[]),
+        ]
+    }
+
+    #[test]
+    fn test_name_glob_and_regex() {
+        let filter = SpriteFilter {
+            name_glob: Some("HERO_*".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(filter_sprites(&sprites(), &filter).unwrap(), vec!["1", "2"]);
+
+        let filter = SpriteFilter {
+            name_regex: Some(r"_0[2-9]\.png$".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(filter_sprites(&sprites(), &filter).unwrap(), vec!["2"]);
+
+        // 通配符中的 . 按字面匹配
+        let filter = SpriteFilter {
+            name_glob: Some("coin?png".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(filter_sprites(&sprites(), &filter).unwrap(), vec!["3"]);
+    }
+
+    #[test]
+    fn test_size_tag_and_folder() {
+        let filter = SpriteFilter {
+            min_width: Some(20),
+            max_height: Some(64),
+            ..Default::default()
+        };
+        assert_eq!(filter_sprites(&sprites(), &filter).unwrap(), vec!["1", "2"]);
+
+        let filter = SpriteFilter {
+            tag: Some("pickup".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(filter_sprites(&sprites(), &filter).unwrap(), vec!["3"]);
+
+        let filter = SpriteFilter {
+            source_folder: Some("/art/boss/".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(filter_sprites(&sprites(), &filter).unwrap(), vec!["4"]);
+    }
+
+    #[test]
+    fn test_invalid_regex() {
+        let filter = SpriteFilter {
+            name_regex: Some("(unclosed".to_string()),
+            ..Default::default()
+        };
+        assert!(filter_sprites(&sprites(), &filter).is_err());
+        assert_eq!(filter_sprites(&sprites(), &SpriteFilter::default()).unwrap().len(), 4);
+    }
+}
@@ -0,0 +1,158 @@
+/// 精灵变换 (Sprite Transform)
+///
+/// 合成时单个精灵的翻转、90° 步进旋转与缩放。
+/// 变换按 翻转 → 顺时针旋转 → 缩放 的顺序作用于精灵自身，
+/// 合成坐标指向变换后外接矩形的左上角
+
+use image::{imageops, RgbaImage};
+use serde::{Deserialize, Serialize};
+
+/// 允许的最大缩放倍数
+pub const MAX_SPRITE_SCALE: f32 = 16.0;
+
+/// 单个精灵的变换
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpriteTransform {
+    /// 顺时针旋转角度（0、90、180、270）
+    #[serde(default)]
+    pub rotation: u32,
+    /// 缩放倍数（默认 1）
+    #[serde(default = "default_scale")]
+    pub scale: f32,
+    /// 水平翻转
+    #[serde(default)]
+    pub flip_x: bool,
+    /// 垂直翻转
+    #[serde(default)]
+    pub flip_y: bool,
+}
+
+fn default_scale() -> f32 {
+    1.0
+}
+
+impl Default for SpriteTransform {
+    fn default() -> Self {
+        Self {
+            rotation: 0,
+            scale: 1.0,
+            flip_x: false,
+            flip_y: false,
+        }
+    }
+}
+
+impl SpriteTransform {
+    /// 校验旋转角度与缩放倍数
+    pub fn validate(&self) -> Result<(), String> {
+        if !matches!(self.rotation, 0 | 90 | 180 | 270) {
+            return Err(format!("旋转角度必须为 0、90、180 或 270: {}", self.rotation));
+        }
+        if !self.scale.is_finite() || self.scale <= 0.0 || self.scale > MAX_SPRITE_SCALE {
+            return Err(format!("缩放倍数无效: {}（范围 0~{}）", self.scale, MAX_SPRITE_SCALE));
+        }
+        Ok(())
+    }
+
+    /// 是否为恒等变换
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// 缩放后（旋转前）的尺寸，至少为 1 像素
+    pub fn scaled_size(&self, width: u32, height: u32) -> (u32, u32) {
+        let scale = |v: u32| ((v as f64 * self.scale as f64).round() as u32).max(1);
+        (scale(width), scale(height))
+    }
+
+    /// 变换后在画布中占用的尺寸
+    pub fn placed_size(&self, width: u32, height: u32) -> (u32, u32) {
+        let (w, h) = self.scaled_size(width, height);
+        if self.rotation % 180 == 90 {
+            (h, w)
+        } else {
+            (w, h)
+        }
+    }
+
+    /// 纹理区域能否以 `textureRotated` 描述
+    ///
+    /// Cocos2d-x 的旋转帧为顺时针 90°，运行时自动转回原方向；
+    /// 其他旋转与翻转直接烘焙到像素中，帧按变换后的外观导出
+    pub fn texture_rotated(&self) -> bool {
+        self.rotation == 90 && !self.flip_x && !self.flip_y
+    }
+
+    /// 对图像应用变换
+    pub fn apply(&self, image: &RgbaImage) -> RgbaImage {
+        if self.is_identity() {
+            return image.clone();
+        }
+
+        let mut out = match (self.flip_x, self.flip_y) {
+            (true, true) => imageops::rotate180(image),
+            (true, false) => imageops::flip_horizontal(image),
+            (false, true) => imageops::flip_vertical(image),
+            (false, false) => image.clone(),
+        };
+        out = match self.rotation {
+            90 => imageops::rotate90(&out),
+            180 => imageops::rotate180(&out),
+            270 => imageops::rotate270(&out),
+            _ => out,
+        };
+        if self.scale != 1.0 {
+            let (w, h) = self.placed_size(image.width(), image.height());
+            out = imageops::resize(&out, w, h, imageops::FilterType::Triangle);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn test_validate() {
+        assert!(SpriteTransform::default().validate().is_ok());
+        let transform = |rotation, scale| SpriteTransform { rotation, scale, ..Default::default() };
+        assert!(transform(45, 1.0).validate().is_err());
+        assert!(transform(360, 1.0).validate().is_err());
+        assert!(transform(90, 0.0).validate().is_err());
+        assert!(transform(90, f32::NAN).validate().is_err());
+        assert!(transform(270, 0.5).validate().is_ok());
+
+        let parsed: SpriteTransform = serde_json::from_str(r#"{"rotation":90,"flipX":true}"#).unwrap();
+        assert_eq!(parsed.scale, 1.0);
+        assert!(parsed.flip_x && !parsed.texture_rotated());
+    }
+
+    #[test]
+    fn test_apply_rotation_flip_and_scale() {
+        // 2x1：左红右绿
+        let mut image = RgbaImage::new(2, 1);
+        image.put_pixel(0, 0, Rgba([255, 0, 0, 255]));
+        image.put_pixel(1, 0, Rgba([0, 255, 0, 255]));
+
+        let rotated = SpriteTransform { rotation: 90, ..Default::default() };
+        let out = rotated.apply(&image);
+        assert_eq!(out.dimensions(), (1, 2));
+        assert_eq!(out.get_pixel(0, 0)[0], 255);
+        assert!(rotated.texture_rotated());
+        assert_eq!(rotated.placed_size(2, 1), (1, 2));
+
+        // 先水平翻转再顺时针旋转 90°：绿色在上
+        let flipped = SpriteTransform { rotation: 90, flip_x: true, ..Default::default() };
+        assert_eq!(flipped.apply(&image).get_pixel(0, 0)[1], 255);
+
+        let scaled = SpriteTransform { rotation: 270, scale: 2.0, ..Default::default() };
+        let out = scaled.apply(&image);
+        assert_eq!(out.dimensions(), (2, 4));
+        assert_eq!(scaled.placed_size(2, 1), out.dimensions());
+        assert_eq!(out.get_pixel(0, 0)[1], 255);
+        assert_eq!(out.get_pixel(1, 3)[0], 255);
+    }
+}
@@ -0,0 +1,102 @@
+/// 横向条带图生成 (Horizontal Sprite Strips)
+///
+/// GameMaker 通过 `name_stripN.png` 命名约定导入横向条带图，
+/// 每帧占用相同宽度的单元格，N 为帧数
+
+use image::{imageops, RgbaImage};
+
+/// 生成 GameMaker 条带图文件名
+///
+/// # Arguments
+/// * `name` - 动画名称
+/// * `frame_count` - 帧数
+///
+/// # Returns
+/// * `String` - 如 `"idle_strip8.png"`
+pub fn strip_file_name(name: &str, frame_count: usize) -> String {
+    format!("{}_strip{}.png", name, frame_count)
+}
+
+/// 校验条带图名称（名称直接作为文件名，不能为空或包含路径）
+pub fn validate_strip_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("条带图名称不能为空".to_string());
+    }
+    if name.contains(['/', '\\']) || name.contains("..") {
+        return Err(format!("条带图名称 {} 不能包含路径", name));
+    }
+    Ok(())
+}
+
+/// 计算条带图单元格尺寸（所有帧的最大宽高）
+pub fn strip_cell_size(frames: &[RgbaImage]) -> (u32, u32) {
+    let cell_width = frames.iter().map(|f| f.width()).max().unwrap_or(0);
+    let cell_height = frames.iter().map(|f| f.height()).max().unwrap_or(0);
+    (cell_width, cell_height)
+}
+
+/// 将帧序列合成为横向条带图
+///
+/// 每帧放入等宽的单元格中，尺寸不一致的帧在单元格内居中
+///
+/// # Arguments
+/// * `frames` - 按播放顺序排列的帧图像
+///
+/// # Returns
+/// * `Option<RgbaImage>` - 条带图，帧列表为空时返回 None
+pub fn compose_strip(frames: &[RgbaImage]) -> Option<RgbaImage> {
+    if frames.is_empty() {
+        return None;
+    }
+
+    let (cell_width, cell_height) = strip_cell_size(frames);
+    if cell_width == 0 || cell_height == 0 {
+        return None;
+    }
+
+    let mut strip = RgbaImage::new(cell_width * frames.len() as u32, cell_height);
+
+    for (i, frame) in frames.iter().enumerate() {
+        let x = i as u32 * cell_width + (cell_width - frame.width()) / 2;
+        let y = (cell_height - frame.height()) / 2;
+        imageops::replace(&mut strip, frame, x as i64, y as i64);
+    }
+
+    Some(strip)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn test_strip_file_name() {
+        assert_eq!(strip_file_name("idle", 8), "idle_strip8.png");
+        assert!(validate_strip_name("idle").is_ok());
+        assert!(validate_strip_name("").is_err());
+        assert!(validate_strip_name("../idle").is_err());
+        assert!(validate_strip_name("run/idle").is_err());
+        assert!(validate_strip_name("run\\idle").unwrap_err().contains("路径"));
+    }
+
+    #[test]
+    fn test_compose_strip_uniform_cells() {
+        let a = RgbaImage::from_pixel(4, 4, Rgba([255, 0, 0, 255]));
+        let b = RgbaImage::from_pixel(2, 2, Rgba([0, 255, 0, 255]));
+
+        let strip = compose_strip(&[a, b]).unwrap();
+
+        assert_eq!(strip.dimensions(), (8, 4));
+        // 第一帧填满第一个单元格
+        assert_eq!(strip.get_pixel(0, 0), &Rgba([255, 0, 0, 255]));
+        // 第二帧在第二个单元格中居中
+        assert_eq!(strip.get_pixel(4, 0), &Rgba([0, 0, 0, 0]));
+        assert_eq!(strip.get_pixel(5, 1), &Rgba([0, 255, 0, 255]));
+    }
+
+    #[test]
+    fn test_compose_strip_empty() {
+        assert!(compose_strip(&[]).is_none());
+    }
+}
@@ -0,0 +1,166 @@
+/// 结果摘要与本地化数字格式 (Result Summaries)
+///
+/// 在后端统一生成人类可读的结果摘要（如 `纹理 2048×1024，2.0 MP，约 8 MB RGBA8888，填充率 83.4%`），
+/// 前端各处直接显示，数字、单位与小数分隔符保持一致
+
+use serde::{Deserialize, Serialize};
+
+/// 数字格式地区
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NumberLocale {
+    /// 简体中文（`1,234.5`）
+    #[default]
+    #[serde(rename = "zh-CN")]
+    ZhCn,
+    /// 英语（`1,234.5`）
+    #[serde(rename = "en-US")]
+    EnUs,
+    /// 德语（`1.234,5`）
+    #[serde(rename = "de-DE")]
+    DeDe,
+    /// 法语（`1 234,5`，千位分隔为窄不换行空格）
+    #[serde(rename = "fr-FR")]
+    FrFr,
+}
+
+impl NumberLocale {
+    /// 千位分隔符与小数点
+    fn separators(self) -> (&'static str, &'static str) {
+        match self {
+            NumberLocale::ZhCn | NumberLocale::EnUs => (",", "."),
+            NumberLocale::DeDe => (".", ","),
+            NumberLocale::FrFr => ("\u{202f}", ","),
+        }
+    }
+
+    /// 字节单位（B、KB、MB、GB）
+    fn byte_units(self) -> [&'static str; 4] {
+        match self {
+            NumberLocale::FrFr => ["o", "Ko", "Mo", "Go"],
+            _ => ["B", "KB", "MB", "GB"],
+        }
+    }
+
+    /// 百分比（德语与法语在数字与 % 之间加空格）
+    fn percent(self, value: f64) -> String {
+        match self {
+            NumberLocale::ZhCn | NumberLocale::EnUs => format!("{}%", format_number(value, 1, self)),
+            NumberLocale::DeDe | NumberLocale::FrFr => format!("{}\u{a0}%", format_number(value, 1, self)),
+        }
+    }
+}
+
+/// 按地区格式化数字
+///
+/// # Arguments
+/// * `value` - 数值
+/// * `decimals` - 小数位数
+/// * `locale` - 地区
+///
+/// # Returns
+/// * `String` - 带千位分隔符的数字
+pub fn format_number(value: f64, decimals: usize, locale: NumberLocale) -> String {
+    let (group, decimal) = locale.separators();
+    let fixed = format!("{:.*}", decimals, value.abs());
+    let (integer, fraction) = fixed.split_once('.').unwrap_or((&fixed, ""));
+
+    let mut grouped = String::new();
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            grouped.push_str(group);
+        }
+        grouped.push(digit);
+    }
+    let sign = if value < 0.0 && fixed.chars().any(|c| c.is_ascii_digit() && c != '0') { "-" } else { "" };
+    if fraction.is_empty() {
+        format!("{}{}", sign, grouped)
+    } else {
+        format!("{}{}{}{}", sign, grouped, decimal, fraction)
+    }
+}
+
+/// 按地区格式化字节数（1024 进制，整数值不带小数）
+///
+/// # Arguments
+/// * `bytes` - 字节数
+/// * `locale` - 地区
+///
+/// # Returns
+/// * `String` - 如 `8 MB`、`1.5 KB`
+pub fn format_bytes(bytes: u64, locale: NumberLocale) -> String {
+    let units = locale.byte_units();
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < units.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    let decimals = if value.fract() == 0.0 { 0 } else { 1 };
+    format!("{} {}", format_number(value, decimals, locale), units[unit])
+}
+
+/// 纹理摘要
+///
+/// # Arguments
+/// * `width` / `height` - 纹理尺寸
+/// * `fill_rate` - 填充率（百分比，可选）
+/// * `locale` - 地区
+///
+/// # Returns
+/// * `String` - 尺寸、像素数、按 RGBA8888 估算的显存占用与填充率
+pub fn texture_summary(width: u32, height: u32, fill_rate: Option<f32>, locale: NumberLocale) -> String {
+    let pixels = width as u64 * height as u64;
+    let megapixels = format_number(pixels as f64 / 1_000_000.0, 1, locale);
+    let memory = format_bytes(pixels * 4, locale);
+    let (texture, estimate, fill, separator) = match locale {
+        NumberLocale::ZhCn => ("纹理", "约", "填充率", "，"),
+        NumberLocale::EnUs => ("texture", "est.", "fill", ", "),
+        NumberLocale::DeDe => ("Textur", "ca.", "Füllrate", ", "),
+        NumberLocale::FrFr => ("texture", "env.", "remplissage", ", "),
+    };
+
+    let mut parts = vec![
+        format!("{} {}×{}", texture, width, height),
+        format!("{} MP", megapixels),
+        format!("{} {} RGBA8888", estimate, memory),
+    ];
+    if let Some(rate) = fill_rate {
+        parts.push(format!("{} {}", fill, locale.percent(rate as f64)));
+    }
+    parts.join(separator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_number(1234567.891, 2, NumberLocale::EnUs), "1,234,567.89");
+        assert_eq!(format_number(1234.5, 1, NumberLocale::DeDe), "1.234,5");
+        assert_eq!(format_number(1234.5, 1, NumberLocale::FrFr), "1\u{202f}234,5");
+        assert_eq!(format_number(-999.0, 0, NumberLocale::ZhCn), "-999");
+        assert_eq!(format_number(-0.01, 1, NumberLocale::ZhCn), "0.0");
+        assert_eq!(format_bytes(8 * 1024 * 1024, NumberLocale::EnUs), "8 MB");
+        assert_eq!(format_bytes(1536, NumberLocale::DeDe), "1,5 KB");
+        assert_eq!(format_bytes(512, NumberLocale::FrFr), "512 o");
+    }
+
+    #[test]
+    fn test_texture_summary() {
+        assert_eq!(
+            texture_summary(2048, 1024, Some(83.42), NumberLocale::EnUs),
+            "texture 2048×1024, 2.1 MP, est. 8 MB RGBA8888, fill 83.4%"
+        );
+        assert_eq!(
+            texture_summary(2048, 1024, Some(83.42), NumberLocale::ZhCn),
+            "纹理 2048×1024，2.1 MP，约 8 MB RGBA8888，填充率 83.4%"
+        );
+        assert_eq!(
+            texture_summary(512, 512, None, NumberLocale::DeDe),
+            "Textur 512×512, 0,3 MP, ca. 1 MB RGBA8888"
+        );
+        let locale: NumberLocale = serde_json::from_str("\"fr-FR\"").unwrap();
+        assert_eq!(locale, NumberLocale::FrFr);
+    }
+}
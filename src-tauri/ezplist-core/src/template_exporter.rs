@@ -0,0 +1,204 @@
+/// 模板导出器 (Template-Driven Exporter)
+///
+/// 使用 Handlebars 模板渲染帧数据，用户编写模板文件即可支持自定义引擎格式
+///
+/// 模板可用的数据模型：
+/// - `frames`: 帧列表（name, x, y, width, height, rotated, trimmed,
+///   sourceWidth, sourceHeight, offsetX, offsetY, flipX, flipY, index）
+/// - `meta`: 元数据（app, version, texture, width, height, frameCount）
+
+use crate::types::{FrameInfo, PackedSprite};
+use handlebars::Handlebars;
+use serde::Serialize;
+
+/// 模板中的单帧数据
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateFrame {
+    /// 帧名称
+    pub name: String,
+    /// 在纹理图中的 X 坐标
+    pub x: u32,
+    /// 在纹理图中的 Y 坐标
+    pub y: u32,
+    /// 在纹理图中的宽度
+    pub width: u32,
+    /// 在纹理图中的高度
+    pub height: u32,
+    /// 是否旋转 90 度
+    pub rotated: bool,
+    /// 是否进行了透明裁剪
+    pub trimmed: bool,
+    /// 原始宽度
+    pub source_width: u32,
+    /// 原始高度
+    pub source_height: u32,
+    /// 裁剪偏移量 X
+    pub offset_x: i32,
+    /// 裁剪偏移量 Y
+    pub offset_y: i32,
+    /// 水平翻转（纹理区域为源帧）
+    pub flip_x: bool,
+    /// 垂直翻转（纹理区域为源帧）
+    pub flip_y: bool,
+    /// 帧序号（从 0 开始）
+    pub index: usize,
+}
+
+/// 模板中的元数据
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateMeta {
+    /// 生成工具名称
+    pub app: String,
+    /// 生成工具版本
+    pub version: String,
+    /// 纹理文件名
+    pub texture: String,
+    /// 纹理宽度
+    pub width: u32,
+    /// 纹理高度
+    pub height: u32,
+    /// 帧数
+    pub frame_count: usize,
+}
+
+/// 模板渲染上下文
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateContext {
+    pub frames: Vec<TemplateFrame>,
+    pub meta: TemplateMeta,
+}
+
+impl TemplateContext {
+    /// 从打包结果构建上下文
+    pub fn from_packed(sprites: &[PackedSprite], width: u32, height: u32, texture: &str) -> Self {
+        let frames = sprites
+            .iter()
+            .enumerate()
+            .map(|(index, s)| TemplateFrame {
+                name: s.name.clone(),
+                x: s.x,
+                y: s.y,
+                width: s.width,
+                height: s.height,
+                rotated: s.rotated,
+                trimmed: s.trimmed,
+                source_width: s.original_width,
+                source_height: s.original_height,
+                offset_x: s.offset_x,
+                offset_y: s.offset_y,
+                flip_x: s.flip_x,
+                flip_y: s.flip_y,
+                index,
+            })
+            .collect();
+        Self::new(frames, width, height, texture)
+    }
+
+    /// 从切分帧构建上下文
+    pub fn from_frames(frames: &[FrameInfo], width: u32, height: u32, texture: &str) -> Self {
+        let frames = frames
+            .iter()
+            .enumerate()
+            .map(|(index, f)| TemplateFrame {
+                name: f.name.clone(),
+                x: f.x,
+                y: f.y,
+                width: f.width,
+                height: f.height,
+                rotated: false,
+                trimmed: false,
+                source_width: f.width,
+                source_height: f.height,
+                offset_x: 0,
+                offset_y: 0,
+                flip_x: false,
+                flip_y: false,
+                index,
+            })
+            .collect();
+        Self::new(frames, width, height, texture)
+    }
+
+    fn new(frames: Vec<TemplateFrame>, width: u32, height: u32, texture: &str) -> Self {
+        let frame_count = frames.len();
+        Self {
+            frames,
+            meta: TemplateMeta {
+                app: "EzPlist".to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                texture: texture.to_string(),
+                width,
+                height,
+                frame_count,
+            },
+        }
+    }
+}
+
+/// 创建模板引擎（严格模式：引用不存在的字段视为错误）
+fn create_engine() -> Handlebars<'static> {
+    let mut engine = Handlebars::new();
+    engine.set_strict_mode(true);
+    // 导出的是代码/数据文件而非 HTML，不做转义
+    engine.register_escape_fn(handlebars::no_escape);
+    engine
+}
+
+/// 校验模板语法
+pub fn validate_template(source: &str) -> Result<(), String> {
+    let mut engine = create_engine();
+    engine
+        .register_template_string("template", source)
+        .map_err(|e| format!("模板语法错误: {}", e))
+}
+
+/// 渲染模板
+///
+/// # Arguments
+/// * `source` - 模板内容
+/// * `context` - 帧数据上下文
+///
+/// # Returns
+/// * `Result<String, String>` - 渲染结果或错误信息
+pub fn render_template(source: &str, context: &TemplateContext) -> Result<String, String> {
+    let mut engine = create_engine();
+    engine
+        .register_template_string("template", source)
+        .map_err(|e| format!("模板语法错误: {}", e))?;
+    engine
+        .render("template", context)
+        .map_err(|e| format!("模板渲染失败: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_context() -> TemplateContext {
+        let frames = vec![
+            FrameInfo { name: "a.png".to_string(), x: 0, y: 0, width: 32, height: 16, row: 0, col: 0, anchor: None },
+            FrameInfo { name: "b.png".to_string(), x: 32, y: 0, width: 32, height: 16, row: 0, col: 1, anchor: None },
+        ];
+        TemplateContext::from_frames(&frames, 64, 16, "sheet.png")
+    }
+
+    #[test]
+    fn test_render_frames_and_meta() {
+        let template = "{{meta.texture}} {{meta.frameCount}}\n{{#each frames}}{{name}}={{x}},{{y}},{{width}}x{{height}}\n{{/each}}";
+        let output = render_template(template, &sample_context()).unwrap();
+        assert_eq!(output, "sheet.png 2\na.png=0,0,32x16\nb.png=32,0,32x16\n");
+    }
+
+    #[test]
+    fn test_strict_mode_rejects_unknown_field() {
+        assert!(render_template("{{meta.unknown}}", &sample_context()).is_err());
+    }
+
+    #[test]
+    fn test_validate_template_syntax() {
+        assert!(validate_template("{{#each frames}}{{name}}{{/each}}").is_ok());
+        assert!(validate_template("{{#each frames}}").is_err());
+    }
+}
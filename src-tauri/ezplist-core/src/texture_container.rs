@@ -0,0 +1,202 @@
+/// 纹理容器格式 (Texture Containers)
+///
+/// 除 PNG 外，纹理还可以写成 GPU 可直接上传的未压缩容器：
+/// - PVR: PowerVR 容器第 3 版（Cocos2d-x 可加载，常再用 ccz 包一层 zlib，即 `.pvr.ccz`）
+/// - KTX: Khronos 容器第 1 版
+///
+/// 像素按 `PixelFormat` 写出（RGBA8888 每像素 4 字节，RGBA4444 与 RGB565 每像素一个小端 16 位值）。
+/// zlib 压缩由应用完成，这里只生成 ccz 文件头
+
+use crate::texture_format::PixelFormat;
+use image::RgbaImage;
+use serde::{Deserialize, Serialize};
+
+/// PVR 第 3 版文件头标识（小端 `PVR\x03`）
+const PVR_VERSION: u32 = 0x0352_5650;
+
+/// KTX 第 1 版文件标识
+const KTX_IDENTIFIER: [u8; 12] = [0xAB, 0x4B, 0x54, 0x58, 0x20, 0x31, 0x31, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A];
+
+/// ccz 文件头长度
+pub const CCZ_HEADER_LEN: usize = 16;
+
+/// 纹理容器格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TextureContainer {
+    /// PNG（默认）
+    #[default]
+    Png,
+    /// 未压缩 PVR
+    Pvr,
+    /// zlib 压缩的 PVR（Cocos2d-x `.pvr.ccz`）
+    PvrCcz,
+    /// 未压缩 KTX
+    Ktx,
+}
+
+impl TextureContainer {
+    /// 纹理文件扩展名（不含点）
+    pub fn extension(self) -> &'static str {
+        match self {
+            TextureContainer::Png => "png",
+            TextureContainer::Pvr => "pvr",
+            TextureContainer::PvrCcz => "pvr.ccz",
+            TextureContainer::Ktx => "ktx",
+        }
+    }
+
+    /// 纹理文件名
+    ///
+    /// # Arguments
+    /// * `stem` - 文件名（不含扩展名）
+    pub fn file_name(self, stem: &str) -> String {
+        format!("{}.{}", stem, self.extension())
+    }
+}
+
+/// 生成 PVR 第 3 版纹理（单面、无 mipmap、无元数据）
+///
+/// # Arguments
+/// * `image` - 纹理（已按 `format` 降低色深）
+/// * `format` - 像素格式
+///
+/// # Returns
+/// * `Vec<u8>` - 文件内容
+pub fn encode_pvr(image: &RgbaImage, format: PixelFormat) -> Vec<u8> {
+    // 像素格式低 4 字节为通道名，高 4 字节为各通道位数
+    let (channels, bits, channel_type): (&[u8; 4], [u8; 4], u32) = match format {
+        PixelFormat::Rgba8888 => (b"rgba", [8, 8, 8, 8], 0),
+        PixelFormat::Rgba4444 => (b"rgba", [4, 4, 4, 4], 4),
+        PixelFormat::Rgb565 => (b"rgb\0", [5, 6, 5, 0], 4),
+    };
+    let mut out = Vec::with_capacity(52 + image.as_raw().len());
+    out.extend_from_slice(&PVR_VERSION.to_le_bytes());
+    out.extend_from_slice(&0u32.to_le_bytes()); // 标志：非预乘 alpha
+    out.extend_from_slice(channels);
+    out.extend_from_slice(&bits);
+    out.extend_from_slice(&0u32.to_le_bytes()); // 色彩空间：线性 RGB
+    out.extend_from_slice(&channel_type.to_le_bytes());
+    for value in [image.height(), image.width(), 1, 1, 1, 1, 0] {
+        // 高度、宽度、深度、表面数、面数、mipmap 数、元数据长度
+        out.extend_from_slice(&value.to_le_bytes());
+    }
+    out.extend(pixel_data(image, format, 1));
+    out
+}
+
+/// 生成 KTX 第 1 版纹理（单面、无 mipmap、无键值数据）
+///
+/// # Arguments
+/// * `image` - 纹理（已按 `format` 降低色深）
+/// * `format` - 像素格式
+///
+/// # Returns
+/// * `Vec<u8>` - 文件内容（每行按 4 字节对齐）
+pub fn encode_ktx(image: &RgbaImage, format: PixelFormat) -> Vec<u8> {
+    // glType、glTypeSize、glFormat、glInternalFormat、glBaseInternalFormat
+    let gl: [u32; 5] = match format {
+        PixelFormat::Rgba8888 => [0x1401, 1, 0x1908, 0x8058, 0x1908],
+        PixelFormat::Rgba4444 => [0x8033, 2, 0x1908, 0x8056, 0x1908],
+        PixelFormat::Rgb565 => [0x8363, 2, 0x1907, 0x8D62, 0x1907],
+    };
+    let data = pixel_data(image, format, 4);
+    let mut out = Vec::with_capacity(68 + data.len());
+    out.extend_from_slice(&KTX_IDENTIFIER);
+    out.extend_from_slice(&0x0403_0201u32.to_le_bytes());
+    for value in gl {
+        out.extend_from_slice(&value.to_le_bytes());
+    }
+    for value in [image.width(), image.height(), 0, 0, 1, 1, 0] {
+        // 宽度、高度、深度、数组元素数、面数、mipmap 数、键值数据长度
+        out.extend_from_slice(&value.to_le_bytes());
+    }
+    out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    out.extend(data);
+    out
+}
+
+/// Cocos2d-x ccz 文件头（`CCZ!`、zlib、版本 2，长度为大端的未压缩字节数）
+///
+/// # Arguments
+/// * `uncompressed_len` - 被压缩数据的原始长度
+///
+/// # Returns
+/// * `Result<[u8; 16], String>` - 文件头；超过 4 GB 时返回错误信息
+pub fn ccz_header(uncompressed_len: usize) -> Result<[u8; CCZ_HEADER_LEN], String> {
+    let len = u32::try_from(uncompressed_len).map_err(|_| "纹理过大，无法写入 ccz".to_string())?;
+    let mut header = [0u8; CCZ_HEADER_LEN];
+    header[..4].copy_from_slice(b"CCZ!");
+    header[4..6].copy_from_slice(&0u16.to_be_bytes()); // 压缩方式：zlib
+    header[6..8].copy_from_slice(&2u16.to_be_bytes());
+    header[12..].copy_from_slice(&len.to_be_bytes());
+    Ok(header)
+}
+
+/// 按像素格式排列像素（行尾补零到 `row_align` 字节）
+fn pixel_data(image: &RgbaImage, format: PixelFormat, row_align: usize) -> Vec<u8> {
+    let bytes_per_pixel = if format == PixelFormat::Rgba8888 { 4 } else { 2 };
+    let row_len = image.width() as usize * bytes_per_pixel;
+    let padding = (row_align - row_len % row_align) % row_align;
+    let mut out = Vec::with_capacity((row_len + padding) * image.height() as usize);
+    for row in image.rows() {
+        for pixel in row {
+            let [r, g, b, a] = pixel.0.map(u16::from);
+            match format {
+                PixelFormat::Rgba8888 => out.extend_from_slice(&pixel.0),
+                PixelFormat::Rgba4444 => {
+                    let value = (r >> 4) << 12 | (g >> 4) << 8 | (b >> 4) << 4 | a >> 4;
+                    out.extend_from_slice(&value.to_le_bytes());
+                }
+                PixelFormat::Rgb565 => {
+                    let value = (r >> 3) << 11 | (g >> 2) << 5 | b >> 3;
+                    out.extend_from_slice(&value.to_le_bytes());
+                }
+            }
+        }
+        out.resize(out.len() + padding, 0);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn test_encode_pvr() {
+        let image = RgbaImage::from_pixel(3, 2, Rgba([255, 0, 136, 255]));
+        let pvr = encode_pvr(&image, PixelFormat::Rgba8888);
+        assert_eq!(pvr.len(), 52 + 3 * 2 * 4);
+        assert_eq!(&pvr[..4], b"PVR\x03");
+        assert_eq!(&pvr[8..16], b"rgba\x08\x08\x08\x08");
+        assert_eq!(u32::from_le_bytes(pvr[24..28].try_into().unwrap()), 2);
+        assert_eq!(u32::from_le_bytes(pvr[28..32].try_into().unwrap()), 3);
+        assert_eq!(&pvr[52..56], &[255, 0, 136, 255]);
+
+        let pvr = encode_pvr(&image, PixelFormat::Rgba4444);
+        assert_eq!(pvr.len(), 52 + 3 * 2 * 2);
+        assert_eq!(u16::from_le_bytes([pvr[52], pvr[53]]), 0xF08F);
+        let pvr = encode_pvr(&image, PixelFormat::Rgb565);
+        assert_eq!(&pvr[8..16], b"rgb\0\x05\x06\x05\x00");
+        assert_eq!(u16::from_le_bytes([pvr[52], pvr[53]]), 0xF811);
+
+        assert_eq!(TextureContainer::PvrCcz.file_name("ui"), "ui.pvr.ccz");
+        let header = ccz_header(300).unwrap();
+        assert_eq!(&header[..8], b"CCZ!\0\0\0\x02");
+        assert_eq!(&header[12..], &300u32.to_be_bytes());
+    }
+
+    #[test]
+    fn test_encode_ktx() {
+        // 3 像素宽的 16 位行为 6 字节，补齐到 8 字节
+        let image = RgbaImage::from_pixel(3, 2, Rgba([255, 255, 255, 0]));
+        let ktx = encode_ktx(&image, PixelFormat::Rgba4444);
+        assert_eq!(&ktx[..12], &KTX_IDENTIFIER);
+        assert_eq!(u32::from_le_bytes(ktx[64..68].try_into().unwrap()), 16);
+        assert_eq!(ktx.len(), 68 + 16);
+        assert_eq!(&ktx[68..76], &[0xF0, 0xFF, 0xF0, 0xFF, 0xF0, 0xFF, 0, 0]);
+        assert_eq!(encode_ktx(&image, PixelFormat::Rgba8888).len(), 68 + 24);
+    }
+}
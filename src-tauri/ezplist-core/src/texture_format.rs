@@ -0,0 +1,373 @@
+/// 纹理像素格式与调色板量化 (Texture Pixel Formats)
+///
+/// 导出的纹理默认为 RGBA8888。为减小安装包，可以在写出前降低色深（RGBA4444、RGB565，
+/// 可选 Floyd–Steinberg 抖动），或量化为最多 256 色的索引调色板。
+/// 这里只处理像素，PNG 编码与压缩级别由应用完成
+
+use crate::texture_container::TextureContainer;
+use image::{Rgba, RgbaImage};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 像素格式（写入数据文件的 `pixelFormat` / `format`）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PixelFormat {
+    /// 每通道 8 位
+    #[default]
+    #[serde(rename = "RGBA8888")]
+    Rgba8888,
+    /// 每通道 4 位
+    #[serde(rename = "RGBA4444")]
+    Rgba4444,
+    /// 红 5 位、绿 6 位、蓝 5 位，不含 alpha
+    #[serde(rename = "RGB565")]
+    Rgb565,
+}
+
+impl PixelFormat {
+    /// 格式名称（与 TexturePacker、libGDX 的写法一致）
+    pub fn name(self) -> &'static str {
+        match self {
+            PixelFormat::Rgba8888 => "RGBA8888",
+            PixelFormat::Rgba4444 => "RGBA4444",
+            PixelFormat::Rgb565 => "RGB565",
+        }
+    }
+
+    /// 是否保留 alpha 通道
+    pub fn has_alpha(self) -> bool {
+        self != PixelFormat::Rgb565
+    }
+
+    /// 各通道位数 (R, G, B, A)
+    fn bits(self) -> [u32; 4] {
+        match self {
+            PixelFormat::Rgba8888 => [8, 8, 8, 8],
+            PixelFormat::Rgba4444 => [4, 4, 4, 4],
+            PixelFormat::Rgb565 => [5, 6, 5, 8],
+        }
+    }
+}
+
+/// PNG 压缩级别
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PngCompression {
+    /// 编码最快，文件较大（默认）
+    #[default]
+    Fast,
+    /// 兼顾速度与大小
+    Balanced,
+    /// 文件最小，编码较慢
+    Best,
+}
+
+/// 纹理输出选项
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TextureOptions {
+    /// 像素格式（默认 RGBA8888）
+    pub pixel_format: PixelFormat,
+    /// 降低色深或量化时使用 Floyd–Steinberg 抖动
+    pub dither: bool,
+    /// 量化为索引调色板的最大颜色数（2–256，仅 RGBA8888；完全透明像素共用一个颜色）
+    pub palette_colors: Option<u16>,
+    /// PNG 压缩级别（`.pvr.ccz` 的 zlib 压缩也按此级别）
+    pub compression: PngCompression,
+    /// 纹理容器（默认 PNG；数据文件引用对应扩展名的纹理）
+    pub container: TextureContainer,
+}
+
+impl TextureOptions {
+    /// 校验选项
+    ///
+    /// # Returns
+    /// * `Result<(), String>` - 颜色数超出范围，或与降低色深、非 PNG 容器同时使用时返回错误信息
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(colors) = self.palette_colors {
+            if !(2..=256).contains(&colors) {
+                return Err(format!("调色板颜色数必须在 2 到 256 之间: {}", colors));
+            }
+            if self.pixel_format != PixelFormat::Rgba8888 {
+                return Err(format!("调色板量化不能与 {} 同时使用", self.pixel_format.name()));
+            }
+            if self.container != TextureContainer::Png {
+                return Err(format!("{} 纹理不支持调色板量化", self.container.extension()));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 索引调色板图像
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedImage {
+    /// 宽度
+    pub width: u32,
+    /// 高度
+    pub height: u32,
+    /// 调色板（RGBA）
+    pub palette: Vec<[u8; 4]>,
+    /// 每个像素的调色板索引（按行排列）
+    pub indices: Vec<u8>,
+}
+
+/// 把纹理转换为指定像素格式可表示的颜色
+///
+/// RGB565 没有 alpha，所有像素变为不透明；RGBA4444 中完全透明的像素保持不变，
+/// 不向周围扩散误差
+///
+/// # Arguments
+/// * `image` - 纹理（原地修改）
+/// * `format` - 像素格式
+/// * `dither` - 是否使用 Floyd–Steinberg 抖动
+pub fn reduce_pixel_format(image: &mut RgbaImage, format: PixelFormat, dither: bool) {
+    if format == PixelFormat::Rgba8888 {
+        return;
+    }
+    let bits = format.bits();
+    let has_alpha = format.has_alpha();
+    diffuse(image, dither, |color| {
+        let mut out = [0u8; 4];
+        for channel in 0..4 {
+            out[channel] = quantize_channel(color[channel], bits[channel]);
+        }
+        if !has_alpha {
+            out[3] = 255;
+        }
+        out
+    });
+}
+
+/// 量化为索引调色板（中位切分）
+///
+/// # Arguments
+/// * `image` - 纹理
+/// * `max_colors` - 最大颜色数（2–256；有完全透明像素时其中一个为透明色）
+/// * `dither` - 是否使用 Floyd–Steinberg 抖动
+///
+/// # Returns
+/// * `IndexedImage` - 调色板与索引；颜色数不超过上限时调色板与原图颜色完全一致
+pub fn quantize(image: &RgbaImage, max_colors: usize, dither: bool) -> IndexedImage {
+    let max_colors = max_colors.clamp(2, 256);
+    let mut histogram: HashMap<[u8; 4], u32> = HashMap::new();
+    let mut has_transparent = false;
+    for pixel in image.pixels() {
+        if pixel[3] == 0 {
+            has_transparent = true;
+        } else {
+            *histogram.entry(pixel.0).or_default() += 1;
+        }
+    }
+
+    let mut palette: Vec<[u8; 4]> = Vec::with_capacity(max_colors);
+    if has_transparent {
+        palette.push([0, 0, 0, 0]);
+    }
+    let budget = max_colors - palette.len();
+    let mut colors: Vec<([u8; 4], u32)> = histogram.into_iter().collect();
+    colors.sort_unstable();
+    if colors.len() <= budget {
+        palette.extend(colors.iter().map(|(color, _)| *color));
+    } else {
+        palette.extend(median_cut(colors, budget));
+    }
+
+    let mut work = image.clone();
+    let mut indices = vec![0u8; (image.width() * image.height()) as usize];
+    let mut nearest: HashMap<[u8; 4], u8> = HashMap::new();
+    let width = image.width() as usize;
+    let mut position = 0usize;
+    diffuse(&mut work, dither, |color| {
+        let index = if color[3] == 0 && has_transparent {
+            0
+        } else {
+            *nearest.entry(color).or_insert_with(|| nearest_color(&palette, color, has_transparent))
+        };
+        indices[position] = index;
+        position += 1;
+        palette[index as usize]
+    });
+    debug_assert_eq!(position, width * image.height() as usize);
+    IndexedImage { width: image.width(), height: image.height(), palette, indices }
+}
+
+/// 把一个通道量化到 `bits` 位后再扩展回 8 位
+fn quantize_channel(value: u8, bits: u32) -> u8 {
+    if bits >= 8 {
+        return value;
+    }
+    let levels = (1u32 << bits) - 1;
+    let level = (value as u32 * levels + 127) / 255;
+    ((level * 255 + levels / 2) / levels) as u8
+}
+
+/// 按行扫描纹理，把每个像素替换为 `map` 给出的颜色
+///
+/// 启用抖动时把量化误差按 Floyd–Steinberg 权重分给右侧与下一行的像素；
+/// 完全透明的像素不接收也不扩散误差
+fn diffuse(image: &mut RgbaImage, dither: bool, mut map: impl FnMut([u8; 4]) -> [u8; 4]) {
+    let (width, height) = (image.width() as usize, image.height() as usize);
+    let mut current = vec![[0f32; 4]; width + 2];
+    let mut next = vec![[0f32; 4]; width + 2];
+    for y in 0..height {
+        for x in 0..width {
+            let pixel = image.get_pixel_mut(x as u32, y as u32);
+            let transparent = pixel[3] == 0;
+            let mut wanted = [0u8; 4];
+            for channel in 0..4 {
+                let error = if dither && !transparent { current[x + 1][channel] } else { 0.0 };
+                wanted[channel] = (pixel[channel] as f32 + error).round().clamp(0.0, 255.0) as u8;
+            }
+            let out = map(wanted);
+            if dither && !transparent {
+                for channel in 0..4 {
+                    let error = pixel[channel] as f32 + current[x + 1][channel] - out[channel] as f32;
+                    current[x + 2][channel] += error * 7.0 / 16.0;
+                    next[x][channel] += error * 3.0 / 16.0;
+                    next[x + 1][channel] += error * 5.0 / 16.0;
+                    next[x + 2][channel] += error / 16.0;
+                }
+            }
+            *pixel = Rgba(out);
+        }
+        std::mem::swap(&mut current, &mut next);
+        next.iter_mut().for_each(|error| *error = [0.0; 4]);
+    }
+}
+
+/// 调色板中与 `color` 最接近的颜色（透明色只匹配完全透明的像素）
+fn nearest_color(palette: &[[u8; 4]], color: [u8; 4], skip_transparent: bool) -> u8 {
+    let start = usize::from(skip_transparent);
+    let distance = |entry: &[u8; 4]| -> u32 {
+        (0..4).map(|c| (entry[c] as i32 - color[c] as i32).pow(2) as u32).sum()
+    };
+    let (index, _) = palette
+        .iter()
+        .enumerate()
+        .skip(start)
+        .min_by_key(|(_, entry)| distance(entry))
+        .unwrap_or((0, &palette[0]));
+    index as u8
+}
+
+/// 中位切分：反复把像素数与通道跨度乘积最大的颜色盒沿最宽的通道按像素数中位切开
+///
+/// # Returns
+/// * `Vec<[u8; 4]>` - 每个颜色盒按像素数加权的平均色
+fn median_cut(colors: Vec<([u8; 4], u32)>, count: usize) -> Vec<[u8; 4]> {
+    let mut boxes: Vec<Vec<([u8; 4], u32)>> = vec![colors];
+    while boxes.len() < count {
+        let Some((index, channel)) = boxes
+            .iter()
+            .enumerate()
+            .filter(|(_, colors)| colors.len() > 1)
+            .map(|(index, colors)| {
+                let (channel, range) = widest_channel(colors);
+                let pixels: u64 = colors.iter().map(|(_, n)| *n as u64).sum();
+                (index, channel, range as u64 * pixels)
+            })
+            .max_by_key(|(_, _, score)| *score)
+            .map(|(index, channel, _)| (index, channel))
+        else {
+            break;
+        };
+        let mut colors = boxes.swap_remove(index);
+        colors.sort_unstable_by_key(|(color, _)| color[channel]);
+        let total: u64 = colors.iter().map(|(_, n)| *n as u64).sum();
+        let mut seen = 0u64;
+        let split = colors
+            .iter()
+            .position(|(_, n)| {
+                seen += *n as u64;
+                seen * 2 >= total
+            })
+            .map_or(1, |position| position + 1)
+            .clamp(1, colors.len() - 1);
+        let upper = colors.split_off(split);
+        boxes.push(colors);
+        boxes.push(upper);
+    }
+    boxes
+        .iter()
+        .map(|colors| {
+            let pixels: u64 = colors.iter().map(|(_, n)| *n as u64).sum();
+            let mut average = [0u8; 4];
+            for (channel, value) in average.iter_mut().enumerate() {
+                let sum: u64 = colors.iter().map(|(color, n)| color[channel] as u64 * *n as u64).sum();
+                *value = ((sum + pixels / 2) / pixels) as u8;
+            }
+            average
+        })
+        .collect()
+}
+
+/// 颜色盒中跨度最大的通道及其跨度
+fn widest_channel(colors: &[([u8; 4], u32)]) -> (usize, u8) {
+    (0..4)
+        .map(|channel| {
+            let min = colors.iter().map(|(color, _)| color[channel]).min().unwrap_or(0);
+            let max = colors.iter().map(|(color, _)| color[channel]).max().unwrap_or(0);
+            (channel, max - min)
+        })
+        .max_by_key(|(_, range)| *range)
+        .unwrap_or((0, 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 8x8 横向渐变，左上角 2x2 完全透明
+    fn gradient() -> RgbaImage {
+        RgbaImage::from_fn(8, 8, |x, y| {
+            if x < 2 && y < 2 {
+                Rgba([0, 0, 0, 0])
+            } else {
+                Rgba([(x * 32) as u8, (y * 32) as u8, 200, 255])
+            }
+        })
+    }
+
+    #[test]
+    fn test_reduce_pixel_format() {
+        let mut image = gradient();
+        reduce_pixel_format(&mut image, PixelFormat::Rgba4444, false);
+        // 4 位通道只有 16 个取值，都是 17 的倍数
+        assert!(image.pixels().all(|p| p.0.iter().all(|c| c % 17 == 0)));
+        assert_eq!(image.get_pixel(0, 0)[3], 0);
+
+        let mut dithered = gradient();
+        reduce_pixel_format(&mut dithered, PixelFormat::Rgb565, true);
+        assert!(dithered.pixels().all(|p| p[3] == 255 && quantize_channel(p[0], 5) == p[0] && quantize_channel(p[1], 6) == p[1]));
+        assert_eq!(quantize_channel(255, 5), 255);
+        assert_eq!(quantize_channel(0, 6), 0);
+
+        let mut untouched = gradient();
+        reduce_pixel_format(&mut untouched, PixelFormat::Rgba8888, true);
+        assert_eq!(untouched, gradient());
+    }
+
+    #[test]
+    fn test_quantize() {
+        // 颜色数不超过上限时无损
+        let exact = quantize(&gradient(), 256, false);
+        assert_eq!(exact.palette[0], [0, 0, 0, 0]);
+        assert_eq!(exact.palette.len(), 1 + 60);
+        let restored: Vec<[u8; 4]> = exact.indices.iter().map(|&i| exact.palette[i as usize]).collect();
+        let original: Vec<[u8; 4]> = gradient().pixels().map(|p| p.0).collect();
+        assert_eq!(restored, original);
+
+        let reduced = quantize(&gradient(), 8, true);
+        assert_eq!(reduced.palette.len(), 8);
+        assert_eq!(reduced.indices.len(), 64);
+        let source = gradient();
+        assert!(source.pixels().zip(&reduced.indices).all(|(p, &i)| (p[3] == 0) == (i == 0)));
+
+        assert!(TextureOptions { palette_colors: Some(1), ..Default::default() }.validate().is_err());
+        let mixed = TextureOptions { palette_colors: Some(16), pixel_format: PixelFormat::Rgb565, ..Default::default() };
+        assert!(mixed.validate().unwrap_err().contains("RGB565"));
+        let json = serde_json::to_value(TextureOptions { pixel_format: PixelFormat::Rgba4444, ..Default::default() }).unwrap();
+        assert_eq!(json["pixelFormat"], "RGBA4444");
+    }
+}
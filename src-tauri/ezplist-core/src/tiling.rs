@@ -0,0 +1,184 @@
+/// 大图集分页 (Large Sheet Tiling)
+///
+/// 超出目标平台纹理尺寸上限的图集（如 16384 像素宽的条带图）按帧边界切成多页，
+/// 每页是原图中的一个矩形窗口，不会把任何一帧切开。
+/// 多页输出统一命名为 `{base}_{page}`（页号从 0 开始），与多页打包导出保持一致
+
+use crate::types::FrameInfo;
+use serde::{Deserialize, Serialize};
+
+/// 默认的页面尺寸上限（多数移动 GPU 支持的最大纹理边长）
+pub const DEFAULT_MAX_PAGE_SIZE: u32 = 4096;
+
+/// 分页后的一页
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TilePage {
+    /// 页号（从 0 开始）
+    pub index: usize,
+    /// 该页在原图中的 X 坐标
+    pub x: u32,
+    /// 该页在原图中的 Y 坐标
+    pub y: u32,
+    /// 页宽度
+    pub width: u32,
+    /// 页高度
+    pub height: u32,
+    /// 该页包含的帧（坐标相对于该页）
+    pub frames: Vec<FrameInfo>,
+}
+
+/// 多页输出的文件名（不含扩展名）
+///
+/// # Arguments
+/// * `base` - 基础名称（如 `atlas`）
+/// * `page` - 页号（从 0 开始）
+///
+/// # Returns
+/// * `String` - 如 `atlas_0`
+pub fn page_file_stem(base: &str, page: usize) -> String {
+    format!("{}_{}", base, page)
+}
+
+/// 按页面尺寸上限规划分页
+///
+/// 先沿 X 方向在不穿过任何帧的位置切成若干列，再在每列内沿 Y 方向切分，
+/// 每次都取不超过上限的最远切割位置；每页裁剪到其中帧的外接矩形
+///
+/// # Arguments
+/// * `frames` - 原图中的帧
+/// * `max_width` - 页宽上限
+/// * `max_height` - 页高上限
+///
+/// # Returns
+/// * `Result<Vec<TilePage>, String>` - 分页结果；某帧本身超过上限、
+///   或帧相互交错导致无法在上限内切开时返回错误
+pub fn plan_tiles(frames: &[FrameInfo], max_width: u32, max_height: u32) -> Result<Vec<TilePage>, String> {
+    if max_width == 0 || max_height == 0 {
+        return Err("页面尺寸上限必须大于 0".to_string());
+    }
+    if frames.is_empty() {
+        return Err("没有帧可分页".to_string());
+    }
+    if let Some(frame) = frames.iter().find(|f| f.width > max_width || f.height > max_height) {
+        return Err(format!(
+            "帧 {} 的尺寸 {}x{} 超过页面上限 {}x{}",
+            frame.name, frame.width, frame.height, max_width, max_height
+        ));
+    }
+
+    let horizontal = |f: &FrameInfo| (f.x as u64, f.x as u64 + f.width as u64);
+    let vertical = |f: &FrameInfo| (f.y as u64, f.y as u64 + f.height as u64);
+
+    let all: Vec<&FrameInfo> = frames.iter().collect();
+    let mut pages = Vec::new();
+    for column in split_axis(&all, max_width, horizontal)? {
+        for cell in split_axis(&column, max_height, vertical)? {
+            let left = cell.iter().map(|f| f.x).min().unwrap_or(0);
+            let top = cell.iter().map(|f| f.y).min().unwrap_or(0);
+            let right = cell.iter().map(|f| f.x + f.width).max().unwrap_or(0);
+            let bottom = cell.iter().map(|f| f.y + f.height).max().unwrap_or(0);
+            pages.push(TilePage {
+                index: pages.len(),
+                x: left,
+                y: top,
+                width: right - left,
+                height: bottom - top,
+                frames: cell
+                    .iter()
+                    .map(|f| FrameInfo {
+                        x: f.x - left,
+                        y: f.y - top,
+                        ..(*f).clone()
+                    })
+                    .collect(),
+            });
+        }
+    }
+    Ok(pages)
+}
+
+/// 沿一个方向把帧分成若干组，每组的跨度不超过 `limit`
+///
+/// `span` 返回帧在该方向上的 [起点, 终点)
+fn split_axis<'a>(
+    frames: &[&'a FrameInfo],
+    limit: u32,
+    span: impl Fn(&FrameInfo) -> (u64, u64),
+) -> Result<Vec<Vec<&'a FrameInfo>>, String> {
+    let mut sorted = frames.to_vec();
+    sorted.sort_by_key(|f| span(f));
+
+    let mut groups: Vec<Vec<&FrameInfo>> = Vec::new();
+    let mut current: Vec<&FrameInfo> = Vec::new();
+    // 当前组的起点与终点；下一帧起点不小于组终点时才能在两者之间切开
+    let mut group_start = 0;
+    let mut group_end = 0;
+    for frame in sorted {
+        let (start, end) = span(frame);
+        if current.is_empty() {
+            group_start = start;
+            group_end = end;
+        } else if start >= group_end && end - group_start > limit as u64 {
+            groups.push(std::mem::take(&mut current));
+            group_start = start;
+            group_end = end;
+        } else {
+            group_end = group_end.max(end);
+            if group_end - group_start > limit as u64 {
+                return Err(format!("帧 {} 与相邻帧交错，无法在 {} 像素内切开", frame.name, limit));
+            }
+        }
+        current.push(frame);
+    }
+    if !current.is_empty() {
+        groups.push(current);
+    }
+    Ok(groups)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(name: &str, x: u32, y: u32, width: u32, height: u32) -> FrameInfo {
+        FrameInfo { name: name.to_string(), x, y, width, height, row: 0, col: 0, anchor: None }
+    }
+
+    #[test]
+    fn test_wide_strip_splits_into_pages() {
+        let frames: Vec<FrameInfo> = (0..256)
+            .map(|i| frame(&format!("run_{:03}.png", i), i * 64, 0, 64, 64))
+            .collect();
+        let pages = plan_tiles(&frames, 4096, 4096).unwrap();
+
+        assert_eq!(pages.len(), 4);
+        assert_eq!(pages[1].x, 4096);
+        assert_eq!((pages[1].width, pages[1].height), (4096, 64));
+        assert_eq!(pages[1].frames.len(), 64);
+        assert_eq!(pages[1].frames[0].name, "run_064.png");
+        assert_eq!(pages[1].frames[0].x, 0);
+        assert_eq!(page_file_stem("atlas", 1), "atlas_1");
+    }
+
+    #[test]
+    fn test_grid_splits_both_axes() {
+        let frames: Vec<FrameInfo> = (0..4)
+            .flat_map(|row| (0..4).map(move |col| frame("f", col * 100, row * 100, 100, 100)))
+            .collect();
+        let pages = plan_tiles(&frames, 250, 250).unwrap();
+
+        assert_eq!(pages.len(), 4);
+        assert!(pages.iter().all(|p| p.width == 200 && p.height == 200 && p.frames.len() == 4));
+        assert_eq!((pages[1].x, pages[1].y), (0, 200));
+    }
+
+    #[test]
+    fn test_plan_tiles_errors() {
+        assert!(plan_tiles(&[frame("big", 0, 0, 300, 10)], 256, 256).is_err());
+        // 交错的帧无法在上限内切开
+        let staggered = vec![frame("a", 0, 0, 200, 10), frame("b", 150, 20, 200, 10)];
+        assert!(plan_tiles(&staggered, 256, 256).is_err());
+        assert!(plan_tiles(&[], 256, 256).is_err());
+    }
+}
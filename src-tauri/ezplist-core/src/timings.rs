@@ -0,0 +1,110 @@
+/// 命令耗时统计 (Command Timings)
+///
+/// 打包、导出、切分、合成等命令在结果中附带各阶段耗时，
+/// 方便用户查看时间花在哪里并提交可操作的性能报告
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// 耗时阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// 解码图像
+    Decode,
+    /// 透明裁剪
+    Trim,
+    /// 排布精灵
+    Pack,
+    /// 渲染纹理与数据文件
+    Render,
+    /// 写入文件
+    Write,
+}
+
+/// 各阶段耗时（毫秒，同一阶段多次执行时累加）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Timings {
+    /// 解码图像
+    pub decode_ms: f64,
+    /// 透明裁剪
+    pub trim_ms: f64,
+    /// 排布精灵
+    pub pack_ms: f64,
+    /// 渲染纹理与数据文件
+    pub render_ms: f64,
+    /// 写入文件
+    pub write_ms: f64,
+}
+
+impl Timings {
+    /// 执行 `f` 并把耗时累加到指定阶段
+    pub fn time<T>(&mut self, phase: Phase, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = f();
+        self.add(phase, start.elapsed());
+        result
+    }
+
+    /// 把一段耗时累加到指定阶段
+    pub fn add(&mut self, phase: Phase, elapsed: Duration) {
+        let ms = elapsed.as_secs_f64() * 1000.0;
+        match phase {
+            Phase::Decode => self.decode_ms += ms,
+            Phase::Trim => self.trim_ms += ms,
+            Phase::Pack => self.pack_ms += ms,
+            Phase::Render => self.render_ms += ms,
+            Phase::Write => self.write_ms += ms,
+        }
+    }
+
+    /// 合并另一组耗时
+    pub fn merge(&mut self, other: &Timings) {
+        self.decode_ms += other.decode_ms;
+        self.trim_ms += other.trim_ms;
+        self.pack_ms += other.pack_ms;
+        self.render_ms += other.render_ms;
+        self.write_ms += other.write_ms;
+    }
+
+    /// 总耗时
+    pub fn total_ms(&self) -> f64 {
+        self.decode_ms + self.trim_ms + self.pack_ms + self.render_ms + self.write_ms
+    }
+}
+
+impl fmt::Display for Timings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "共 {:.1}ms (解码 {:.1}, 裁剪 {:.1}, 打包 {:.1}, 渲染 {:.1}, 写入 {:.1})",
+            self.total_ms(),
+            self.decode_ms,
+            self.trim_ms,
+            self.pack_ms,
+            self.render_ms,
+            self.write_ms
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phases_accumulate() {
+        let mut timings = Timings::default();
+        timings.add(Phase::Decode, Duration::from_millis(3));
+        timings.add(Phase::Decode, Duration::from_millis(2));
+        let value = timings.time(Phase::Write, || 42);
+        assert_eq!(value, 42);
+        assert!((timings.decode_ms - 5.0).abs() < 1e-9);
+
+        let mut total = Timings { pack_ms: 1.5, ..Default::default() };
+        total.merge(&timings);
+        assert!((total.total_ms() - (6.5 + timings.write_ms)).abs() < 1e-9);
+        assert!(total.to_string().starts_with("共 "));
+    }
+}
@@ -0,0 +1,497 @@
+/// 透明像素裁剪工具 (Transparent Pixel Trimming)
+/// 
+/// 从四个方向扫描透明边框，裁剪不必要的透明区域；
+/// 没有 alpha 通道的图像可以改用颜色键，按背景色裁剪
+
+use image::{Rgba, RgbaImage, imageops};
+use serde::{Deserialize, Serialize};
+
+/// 默认 alpha 阈值（不大于此值的像素视为透明）
+pub const DEFAULT_ALPHA_THRESHOLD: u8 = 1;
+
+/// 裁剪模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TrimMode {
+    /// 裁剪透明边框，精灵缩小为内容尺寸（spriteSourceSize 为裁剪后尺寸，偏移为 0）
+    Trim,
+    /// 裁剪透明边框但保留原始尺寸（spriteSourceSize 为原图尺寸，spriteOffset 还原内容位置）
+    #[default]
+    CropKeepSize,
+    /// 不裁剪
+    None,
+}
+
+/// 裁剪结果
+#[derive(Debug, Clone)]
+pub struct TrimResult {
+    /// 裁剪后的图像
+    pub trimmed_image: RgbaImage,
+    /// 裁剪偏移量 (相对于原始中心的偏移)
+    pub offset_x: i32,
+    pub offset_y: i32,
+    /// 原始尺寸
+    pub original_width: u32,
+    pub original_height: u32,
+    /// 裁剪后尺寸
+    pub trimmed_width: u32,
+    pub trimmed_height: u32,
+    /// 裁剪边界 (left, top, right, bottom)
+    pub trim_bounds: (u32, u32, u32, u32),
+}
+
+impl TrimResult {
+    /// 不裁剪的结果（整张图像，偏移为 0）
+    pub fn untrimmed(image: RgbaImage) -> Self {
+        let (width, height) = image.dimensions();
+        Self {
+            trimmed_image: image,
+            offset_x: 0,
+            offset_y: 0,
+            original_width: width,
+            original_height: height,
+            trimmed_width: width,
+            trimmed_height: height,
+            trim_bounds: (0, 0, width, height),
+        }
+    }
+
+    /// 按裁剪模式调整结果：`Trim` 模式下精灵缩小为裁剪后的内容，不再记录原图尺寸与偏移
+    pub fn with_mode(self, mode: TrimMode) -> Self {
+        match mode {
+            TrimMode::Trim => Self::untrimmed(self.trimmed_image),
+            TrimMode::CropKeepSize | TrimMode::None => self,
+        }
+    }
+
+    /// 把原图居中放入更大的虚拟源尺寸（sourceSize 覆盖），重新计算偏移
+    ///
+    /// 裁剪后的图像不变，只改变 `original_width/height` 与偏移，
+    /// 使不同尺寸的帧在同一虚拟格子中保持一致的锚点
+    ///
+    /// # Arguments
+    /// * `width` / `height` - 虚拟源尺寸
+    ///
+    /// # Returns
+    /// * `Option<TrimResult>` - 新的裁剪结果；虚拟尺寸小于原图时返回 None
+    pub fn with_source_size(self, width: u32, height: u32) -> Option<Self> {
+        let (offset_x, offset_y) = centered_source_offset(
+            self.trim_bounds,
+            (self.original_width, self.original_height),
+            (width, height),
+        )?;
+        Some(Self {
+            offset_x,
+            offset_y,
+            original_width: width,
+            original_height: height,
+            ..self
+        })
+    }
+}
+
+/// 计算帧偏移（裁剪区域中心相对于源图中心，Cocos2d-x Y 轴向上）
+///
+/// # Arguments
+/// * `bounds` - 裁剪区域 (left, top, right, bottom)，源图坐标
+/// * `source` - 源图尺寸
+///
+/// # Returns
+/// * `(i32, i32)` - spriteOffset
+pub fn source_offset(bounds: (u32, u32, u32, u32), source: (u32, u32)) -> (i32, i32) {
+    let (left, top, right, bottom) = bounds;
+    let source_center_x = source.0 as f32 / 2.0;
+    let source_center_y = source.1 as f32 / 2.0;
+    let trimmed_center_x = (left + right) as f32 / 2.0;
+    let trimmed_center_y = (top + bottom) as f32 / 2.0;
+
+    let offset_x = (trimmed_center_x - source_center_x).round() as i32;
+    // Cocos2d-x Y 轴向上，所以取反
+    let offset_y = -((trimmed_center_y - source_center_y).round() as i32);
+    (offset_x, offset_y)
+}
+
+/// 原图居中放入虚拟源尺寸后的帧偏移（两侧留白不等时多出的 1 像素放在右/下侧）
+///
+/// # Arguments
+/// * `bounds` - 裁剪区域，原图坐标
+/// * `original` - 原图尺寸
+/// * `source` - 虚拟源尺寸
+///
+/// # Returns
+/// * `Option<(i32, i32)>` - spriteOffset；虚拟尺寸小于原图时返回 None
+pub fn centered_source_offset(
+    bounds: (u32, u32, u32, u32),
+    original: (u32, u32),
+    source: (u32, u32),
+) -> Option<(i32, i32)> {
+    if source.0 < original.0 || source.1 < original.1 {
+        return None;
+    }
+    let pad_x = (source.0 - original.0) / 2;
+    let pad_y = (source.1 - original.1) / 2;
+    let (left, top, right, bottom) = bounds;
+    Some(source_offset(
+        (left + pad_x, top + pad_y, right + pad_x, bottom + pad_y),
+        source,
+    ))
+}
+
+/// 颜色键：没有 alpha 通道的图像（JPEG、BMP 等）用一种背景色代替透明
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColorKey {
+    /// 背景色（RGB）
+    pub color: [u8; 3],
+    /// 容差（每个通道允许的最大差值）
+    #[serde(default)]
+    pub tolerance: u8,
+    /// 是否把裁剪结果中的背景色转为透明
+    #[serde(default)]
+    pub to_transparent: bool,
+}
+
+impl ColorKey {
+    /// 像素是否为背景色（忽略 alpha）
+    pub fn matches(&self, pixel: &Rgba<u8>) -> bool {
+        (0..3).all(|c| pixel[c].abs_diff(self.color[c]) <= self.tolerance)
+    }
+
+    /// 像素是否视为背景（alpha 不大于阈值或与背景色一致）
+    fn is_background(&self, pixel: &Rgba<u8>, alpha_threshold: u8) -> bool {
+        pixel[3] <= alpha_threshold || self.matches(pixel)
+    }
+}
+
+/// 裁剪图像的透明边框
+/// 
+/// # Arguments
+/// * `img` - 输入的 RGBA 图像
+/// 
+/// # Returns
+/// * `TrimResult` - 包含裁剪后图像和偏移信息
+pub fn trim_transparent(img: &RgbaImage) -> TrimResult {
+    trim_by(img, 0, is_transparent)
+}
+
+/// 裁剪透明边框，但在内容四周保留最多 `margin` 像素的透明边
+///
+/// 供需要在不透明区域外少量采样的着色器/特效使用；保留的边不超出原图
+///
+/// # Arguments
+/// * `img` - 输入的 RGBA 图像
+/// * `margin` - 每侧保留的透明像素数
+///
+/// # Returns
+/// * `TrimResult` - 包含裁剪后图像和偏移信息
+pub fn trim_transparent_with_margin(img: &RgbaImage, margin: u32) -> TrimResult {
+    trim_alpha(img, margin, DEFAULT_ALPHA_THRESHOLD)
+}
+
+/// 按自定义 alpha 阈值裁剪透明边框
+///
+/// 半透明的阴影或抗锯齿边缘可以通过提高阈值一并裁掉
+///
+/// # Arguments
+/// * `img` - 输入的 RGBA 图像
+/// * `margin` - 每侧保留的透明像素数
+/// * `alpha_threshold` - alpha 不大于此值的像素视为透明
+///
+/// # Returns
+/// * `TrimResult` - 包含裁剪后图像和偏移信息
+pub fn trim_alpha(img: &RgbaImage, margin: u32, alpha_threshold: u8) -> TrimResult {
+    trim_by(img, margin, |pixel| pixel[3] <= alpha_threshold)
+}
+
+/// 按颜色键裁剪背景色边框
+///
+/// 透明像素同样视为背景，带 alpha 的图像也可以使用。
+/// 设置 `to_transparent` 时，裁剪结果中所有与背景色一致的像素都转为完全透明
+///
+/// # Arguments
+/// * `img` - 输入的 RGBA 图像
+/// * `key` - 颜色键
+///
+/// # Returns
+/// * `TrimResult` - 包含裁剪后图像和偏移信息
+pub fn trim_color_key(img: &RgbaImage, key: &ColorKey) -> TrimResult {
+    trim_color_key_with_margin(img, key, 0)
+}
+
+/// 按颜色键裁剪背景色边框，在内容四周保留最多 `margin` 像素的背景
+pub fn trim_color_key_with_margin(img: &RgbaImage, key: &ColorKey, margin: u32) -> TrimResult {
+    trim_color_key_alpha(img, key, margin, DEFAULT_ALPHA_THRESHOLD)
+}
+
+/// 按颜色键裁剪背景色边框，alpha 不大于 `alpha_threshold` 的像素同样视为背景
+pub fn trim_color_key_alpha(img: &RgbaImage, key: &ColorKey, margin: u32, alpha_threshold: u8) -> TrimResult {
+    let mut result = trim_by(img, margin, |pixel| key.is_background(pixel, alpha_threshold));
+    if key.to_transparent {
+        for pixel in result.trimmed_image.pixels_mut() {
+            if key.matches(pixel) {
+                *pixel = Rgba([0, 0, 0, 0]);
+            }
+        }
+    }
+    result
+}
+
+/// 按背景判定裁剪边框（`margin` 为每侧保留的背景像素数）
+fn trim_by(img: &RgbaImage, margin: u32, is_background: impl Fn(&Rgba<u8>) -> bool) -> TrimResult {
+    let (width, height) = img.dimensions();
+    
+    // 如果图片为空，返回原图
+    if width == 0 || height == 0 {
+        return TrimResult::untrimmed(img.clone());
+    }
+    
+    // 从四个方向扫描
+    let row_empty = |y| is_row_background(img, y, &is_background);
+    let col_empty = |x| is_col_background(img, x, &is_background);
+    let top = (0..height).find(|&y| !row_empty(y)).unwrap_or(height);
+    let bottom = (0..height).rev().find(|&y| !row_empty(y)).map_or(0, |y| y + 1);
+    let left = (0..width).find(|&x| !col_empty(x)).unwrap_or(width);
+    let right = (0..width).rev().find(|&x| !col_empty(x)).map_or(0, |x| x + 1);
+    
+    // 如果整张图片都是透明的
+    if top >= bottom || left >= right {
+        return TrimResult {
+            trimmed_image: RgbaImage::new(1, 1), // 最小 1x1
+            offset_x: 0,
+            offset_y: 0,
+            original_width: width,
+            original_height: height,
+            trimmed_width: 1,
+            trimmed_height: 1,
+            trim_bounds: (0, 0, 1, 1),
+        };
+    }
+    
+    // 保留安全边距，不超出原图
+    let (left, top) = (left.saturating_sub(margin), top.saturating_sub(margin));
+    let (right, bottom) = (right.saturating_add(margin).min(width), bottom.saturating_add(margin).min(height));
+    let trimmed_width = right - left;
+    let trimmed_height = bottom - top;
+    
+    // 计算偏移量（相对于原始图像中心的偏移）
+    // Cocos2d-x 使用的 spriteOffset 计算方式
+    let (offset_x, offset_y) = source_offset((left, top, right, bottom), (width, height));
+    
+    // 裁剪图像
+    let trimmed_image = imageops::crop_imm(img, left, top, trimmed_width, trimmed_height).to_image();
+    
+    TrimResult {
+        trimmed_image,
+        offset_x,
+        offset_y,
+        original_width: width,
+        original_height: height,
+        trimmed_width,
+        trimmed_height,
+        trim_bounds: (left, top, right, bottom),
+    }
+}
+
+/// 检查图像是否完全透明（裁剪后只剩 1x1 占位）
+pub fn is_fully_transparent(img: &RgbaImage) -> bool {
+    find_first_opaque_row(img, 0, img.height()) == img.height()
+}
+
+/// 检查图像是否所有像素的 alpha 都不大于阈值（按该阈值裁剪后只剩 1x1 占位）
+pub fn is_below_alpha(img: &RgbaImage, alpha_threshold: u8) -> bool {
+    img.pixels().all(|pixel| pixel[3] <= alpha_threshold)
+}
+
+/// 检查图像中的矩形区域是否完全透明（超出图像的部分视为透明）
+pub fn is_region_transparent(img: &RgbaImage, x: u32, y: u32, width: u32, height: u32) -> bool {
+    (y..y.saturating_add(height)).all(|py| {
+        (x..x.saturating_add(width)).all(|px| img.get_pixel_checked(px, py).is_none_or(is_transparent))
+    })
+}
+
+/// 检查图像是否全部为背景色或透明（按颜色键裁剪后只剩 1x1 占位）
+///
+/// # Arguments
+/// * `img` - 输入的 RGBA 图像
+/// * `key` - 颜色键
+/// * `alpha_threshold` - alpha 不大于此值的像素视为透明
+pub fn is_fully_keyed(img: &RgbaImage, key: &ColorKey, alpha_threshold: u8) -> bool {
+    img.pixels().all(|pixel| key.is_background(pixel, alpha_threshold))
+}
+
+/// 从上方扫描第一个不透明行
+pub fn find_first_opaque_row(img: &RgbaImage, start: u32, end: u32) -> u32 {
+    for y in start..end {
+        if !is_row_transparent(img, y) {
+            return y;
+        }
+    }
+    end
+}
+
+/// 从下方扫描最后一个不透明行
+pub fn find_last_opaque_row(img: &RgbaImage, start: u32, end: u32) -> u32 {
+    for y in (start..end).rev() {
+        if !is_row_transparent(img, y) {
+            return y + 1;
+        }
+    }
+    start
+}
+
+/// 像素是否透明
+fn is_transparent(pixel: &Rgba<u8>) -> bool {
+    pixel[3] <= DEFAULT_ALPHA_THRESHOLD
+}
+
+/// 检查一行是否完全透明
+fn is_row_transparent(img: &RgbaImage, y: u32) -> bool {
+    is_row_background(img, y, &is_transparent)
+}
+
+/// 检查一行是否全部为背景
+fn is_row_background(img: &RgbaImage, y: u32, is_background: &impl Fn(&Rgba<u8>) -> bool) -> bool {
+    (0..img.width()).all(|x| img.get_pixel_checked(x, y).is_none_or(is_background))
+}
+
+/// 从左侧扫描第一个不透明列
+pub fn find_first_opaque_col(img: &RgbaImage, start: u32, end: u32) -> u32 {
+    for x in start..end {
+        if !is_col_transparent(img, x) {
+            return x;
+        }
+    }
+    end
+}
+
+/// 从右侧扫描最后一个不透明列
+pub fn find_last_opaque_col(img: &RgbaImage, start: u32, end: u32) -> u32 {
+    for x in (start..end).rev() {
+        if !is_col_transparent(img, x) {
+            return x + 1;
+        }
+    }
+    start
+}
+
+/// 检查一列是否完全透明
+fn is_col_transparent(img: &RgbaImage, x: u32) -> bool {
+    is_col_background(img, x, &is_transparent)
+}
+
+/// 检查一列是否全部为背景
+fn is_col_background(img: &RgbaImage, x: u32, is_background: &impl Fn(&Rgba<u8>) -> bool) -> bool {
+    (0..img.height()).all(|y| img.get_pixel_checked(x, y).is_none_or(is_background))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 白底 JPEG 风格的图像：8x6 白色背景，中间 (2,1)-(5,4) 为红色
+    fn keyed_image() -> RgbaImage {
+        let mut img = RgbaImage::from_pixel(8, 6, Rgba([250, 252, 255, 255]));
+        for y in 1..4 {
+            for x in 2..5 {
+                img.put_pixel(x, y, Rgba([200, 0, 0, 255]));
+            }
+        }
+        img.put_pixel(3, 2, Rgba([255, 255, 255, 255]));
+        img
+    }
+
+    #[test]
+    fn test_trim_color_key() {
+        let white = ColorKey { color: [255, 255, 255], tolerance: 8, to_transparent: false };
+        let result = trim_color_key(&keyed_image(), &white);
+        assert_eq!(result.trim_bounds, (2, 1, 5, 4));
+        assert_eq!(*result.trimmed_image.get_pixel(1, 1), Rgba([255, 255, 255, 255]));
+        // 按 alpha 裁剪时不透明的白底不会被裁掉
+        assert_eq!(trim_transparent(&keyed_image()).trimmed_width, 8);
+
+        // 容差不足时背景不匹配
+        let strict = ColorKey { tolerance: 0, ..white };
+        assert_eq!(trim_color_key(&keyed_image(), &strict).trimmed_width, 8);
+    }
+
+    #[test]
+    fn test_with_source_size() {
+        // 10x10 图像，内容在 (2,2)-(6,6)，偏移 (-1, 1)
+        let mut img = RgbaImage::new(10, 10);
+        for y in 2..6 {
+            for x in 2..6 {
+                img.put_pixel(x, y, Rgba([255, 0, 0, 255]));
+            }
+        }
+        let result = trim_transparent(&img);
+        assert_eq!((result.offset_x, result.offset_y), (-1, 1));
+
+        // 居中放入 20x16：留白为 5 与 3，偏移不变
+        let expanded = result.clone().with_source_size(20, 16).unwrap();
+        assert_eq!((expanded.original_width, expanded.original_height), (20, 16));
+        assert_eq!((expanded.offset_x, expanded.offset_y), (-1, 1));
+        assert_eq!(expanded.trimmed_width, 4);
+
+        // 留白为奇数时多出的像素在右/下侧
+        let odd = result.clone().with_source_size(11, 13).unwrap();
+        assert_eq!((odd.offset_x, odd.offset_y), (-2, 2));
+
+        assert!(result.with_source_size(8, 16).is_none());
+    }
+
+    #[test]
+    fn test_trim_margin() {
+        // 10x10 图像，内容在 (1,2)-(5,6)
+        let mut img = RgbaImage::new(10, 10);
+        for y in 2..6 {
+            for x in 1..5 {
+                img.put_pixel(x, y, Rgba([255, 0, 0, 255]));
+            }
+        }
+        // 左侧只剩 1 像素可保留，其余三侧各保留 2 像素
+        let result = trim_transparent_with_margin(&img, 2);
+        assert_eq!(result.trim_bounds, (0, 0, 7, 8));
+        assert_eq!((result.trimmed_width, result.trimmed_height), (7, 8));
+        assert_eq!(result.trimmed_image.get_pixel(0, 0)[3], 0);
+        assert_eq!(result.trimmed_image.get_pixel(1, 2), &Rgba([255, 0, 0, 255]));
+        assert_eq!((result.offset_x, result.offset_y), (-2, 1));
+
+        assert_eq!(trim_transparent_with_margin(&img, 0).trim_bounds, trim_transparent(&img).trim_bounds);
+        // 完全透明的图像仍为 1x1 占位
+        assert_eq!(trim_transparent_with_margin(&RgbaImage::new(4, 4), 3).trimmed_width, 1);
+    }
+
+    #[test]
+    fn test_color_key_to_transparent() {
+        let white = ColorKey { color: [255, 255, 255], tolerance: 8, to_transparent: true };
+        let result = trim_color_key(&keyed_image(), &white);
+        assert_eq!(result.trimmed_image.get_pixel(1, 1)[3], 0);
+        assert_eq!(result.trimmed_image.get_pixel(0, 0)[3], 255);
+
+        assert!(is_fully_keyed(&RgbaImage::from_pixel(4, 4, Rgba([255, 255, 255, 255])), &white, DEFAULT_ALPHA_THRESHOLD));
+        assert!(!is_fully_keyed(&keyed_image(), &white, DEFAULT_ALPHA_THRESHOLD));
+    }
+
+    #[test]
+    fn test_alpha_threshold_and_modes() {
+        // 10x10 图像：中间 4x4 不透明，外圈到 (1,1)-(9,9) 为 alpha 40 的阴影
+        let mut img = RgbaImage::new(10, 10);
+        for y in 1..9 {
+            for x in 1..9 {
+                let alpha = if (3..7).contains(&x) && (3..7).contains(&y) { 255 } else { 40 };
+                img.put_pixel(x, y, Rgba([0, 0, 0, alpha]));
+            }
+        }
+        assert_eq!(trim_alpha(&img, 0, DEFAULT_ALPHA_THRESHOLD).trim_bounds, (1, 1, 9, 9));
+        let result = trim_alpha(&img, 0, 40);
+        assert_eq!(result.trim_bounds, (3, 3, 7, 7));
+        assert!(!is_below_alpha(&img, 40));
+        assert!(is_below_alpha(&RgbaImage::from_pixel(2, 2, Rgba([9, 9, 9, 40])), 40));
+
+        let kept = result.clone().with_mode(TrimMode::CropKeepSize);
+        assert_eq!((kept.original_width, kept.trimmed_width), (10, 4));
+        let shrunk = result.with_mode(TrimMode::Trim);
+        assert_eq!((shrunk.original_width, shrunk.original_height), (4, 4));
+        assert_eq!((shrunk.offset_x, shrunk.offset_y, shrunk.trim_bounds), (0, 0, (0, 0, 4, 4)));
+    }
+}
@@ -0,0 +1,584 @@
+/// 共享类型定义
+/// 
+/// 定义前后端交互的数据结构
+
+use crate::alpha_stats::AlphaStats;
+use crate::animated_image::ImportedAnimation;
+use crate::animation::PreviewFormat;
+use crate::exporters::SheetFormat;
+use crate::frame_check::{FrameConstraints, FrameWarning};
+use crate::frame_groups::FrameGrouping;
+use crate::frame_names::FrameRename;
+use crate::grid::{GridGutter, SplitRounding};
+use crate::hash::SmartUpdateMode;
+use crate::image_processor::ImageAdjustments;
+use crate::naming::FrameNumbering;
+use crate::plist_generator::PlistFormat;
+use crate::scale_variant::ScaleVariant;
+use crate::texture_format::TextureOptions;
+use crate::timings::Timings;
+use serde::{Deserialize, Serialize};
+
+/// 精灵数据（原始图片信息）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpriteData {
+    /// 唯一标识符
+    pub id: String,
+    /// 文件名
+    pub name: String,
+    /// 文件路径
+    pub path: String,
+    /// 原始宽度
+    pub width: u32,
+    /// 原始高度
+    pub height: u32,
+    /// 裁剪后宽度
+    pub trimmed_width: u32,
+    /// 裁剪后高度
+    pub trimmed_height: u32,
+    /// 锚点（可选，相对原始尺寸的 0~1 坐标，Y 轴向上；未设置时使用引擎默认值）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<(f32, f32)>,
+    /// 九宫格边距（可选，相对原始尺寸）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nine_slice: Option<NineSlice>,
+}
+
+/// 九宫格边距 (9-patch / cap insets)
+///
+/// 四边不随缩放拉伸的像素宽度，中间区域拉伸
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NineSlice {
+    /// 左边距
+    pub left: u32,
+    /// 右边距
+    pub right: u32,
+    /// 上边距
+    pub top: u32,
+    /// 下边距
+    pub bottom: u32,
+}
+
+impl NineSlice {
+    /// 校验边距不超出图像尺寸
+    pub fn validate(&self, width: u32, height: u32) -> Result<(), String> {
+        if self.left as u64 + self.right as u64 > width as u64 || self.top as u64 + self.bottom as u64 > height as u64 {
+            return Err(format!(
+                "九宫格边距 左{} 右{} 上{} 下{} 超出图像尺寸 {}x{}",
+                self.left, self.right, self.top, self.bottom, width, height
+            ));
+        }
+        Ok(())
+    }
+
+    /// 换算为裁剪区域内的边距
+    ///
+    /// 被裁掉的透明边缘从对应边距中扣除，落在边距内的部分保持不拉伸
+    ///
+    /// # Arguments
+    /// * `source` - 原始尺寸
+    /// * `bounds` - 裁剪区域在原图中的位置与尺寸 (x, y, 宽, 高)
+    pub fn trimmed(&self, source: (u32, u32), bounds: (u32, u32, u32, u32)) -> Self {
+        let (x, y, width, height) = bounds;
+        let right_cut = source.0.saturating_sub(x.saturating_add(width));
+        let bottom_cut = source.1.saturating_sub(y.saturating_add(height));
+        let left = self.left.saturating_sub(x).min(width);
+        let top = self.top.saturating_sub(y).min(height);
+        Self {
+            left,
+            right: self.right.saturating_sub(right_cut).min(width - left),
+            top,
+            bottom: self.bottom.saturating_sub(bottom_cut).min(height - top),
+        }
+    }
+
+    /// 由裁剪区域内的边距还原为相对原始尺寸的边距（`trimmed` 的逆运算）
+    pub fn untrimmed(&self, source: (u32, u32), bounds: (u32, u32, u32, u32)) -> Self {
+        let (x, y, width, height) = bounds;
+        Self {
+            left: self.left.saturating_add(x),
+            right: self.right.saturating_add(source.0.saturating_sub(x.saturating_add(width))),
+            top: self.top.saturating_add(y),
+            bottom: self.bottom.saturating_add(source.1.saturating_sub(y.saturating_add(height))),
+        }
+    }
+
+    /// 中间可拉伸区域 (x, y, 宽, 高)
+    pub fn center(&self, width: u32, height: u32) -> (u32, u32, u32, u32) {
+        (
+            self.left,
+            self.top,
+            width.saturating_sub(self.left.saturating_add(self.right)),
+            height.saturating_sub(self.top.saturating_add(self.bottom)),
+        )
+    }
+}
+
+/// 已打包的精灵（布局信息）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackedSprite {
+    /// 精灵 ID
+    pub id: String,
+    /// 精灵名称
+    pub name: String,
+    /// 在纹理图中的 X 坐标
+    pub x: u32,
+    /// 在纹理图中的 Y 坐标
+    pub y: u32,
+    /// 在纹理图中的宽度
+    pub width: u32,
+    /// 在纹理图中的高度
+    pub height: u32,
+    /// 是否旋转 90 度
+    pub rotated: bool,
+    /// 原始宽度（未裁剪前）
+    #[serde(alias = "originalWidth")]
+    pub original_width: u32,
+    /// 原始高度（未裁剪前）
+    #[serde(alias = "originalHeight")]
+    pub original_height: u32,
+    /// 是否进行了透明裁剪
+    pub trimmed: bool,
+    /// 裁剪偏移量 X
+    #[serde(alias = "offsetX")]
+    pub offset_x: i32,
+    /// 裁剪偏移量 Y
+    #[serde(alias = "offsetY")]
+    pub offset_y: i32,
+    /// 水平翻转（纹理区域为源帧，显示时水平镜像）
+    #[serde(default, alias = "flipX")]
+    pub flip_x: bool,
+    /// 垂直翻转（纹理区域为源帧，显示时垂直镜像）
+    #[serde(default, alias = "flipY")]
+    pub flip_y: bool,
+    /// 锚点（可选，相对 sourceSize 的 0~1 坐标，Y 轴向上）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<(f32, f32)>,
+    /// 九宫格边距（可选，相对裁剪后的帧尺寸，未旋转）
+    #[serde(default, skip_serializing_if = "Option::is_none", alias = "nineSlice")]
+    pub nine_slice: Option<NineSlice>,
+}
+
+impl PackedSprite {
+    /// 未旋转时的帧尺寸（裁剪后；旋转帧在纹理中宽高互换）
+    pub fn frame_size(&self) -> (u32, u32) {
+        if self.rotated {
+            (self.height, self.width)
+        } else {
+            (self.width, self.height)
+        }
+    }
+
+    /// 由切分帧构建（未裁剪、未旋转，ID 与名称相同）
+    pub fn from_frame(frame: &FrameInfo) -> Self {
+        Self {
+            id: frame.name.clone(),
+            name: frame.name.clone(),
+            x: frame.x,
+            y: frame.y,
+            width: frame.width,
+            height: frame.height,
+            rotated: false,
+            original_width: frame.width,
+            original_height: frame.height,
+            trimmed: false,
+            offset_x: 0,
+            offset_y: 0,
+            flip_x: false,
+            flip_y: false,
+            anchor: frame.anchor,
+            nine_slice: None,
+        }
+    }
+
+    /// 是否为翻转帧（与源帧共用纹理区域，运行时翻转显示）
+    pub fn is_flipped(&self) -> bool {
+        self.flip_x || self.flip_y
+    }
+}
+
+/// 导入结果
+#[derive(Debug, Serialize)]
+pub struct ImportResult {
+    /// 成功导入的精灵列表
+    pub sprites: Vec<SpriteData>,
+    /// 失败的文件列表（带错误信息）
+    pub failed: Vec<String>,
+    /// 总数
+    pub total: usize,
+    /// 动图展开的动画（帧的精灵 ID 与时长，可写入动画描述文件）
+    pub animations: Vec<ImportedAnimation>,
+}
+
+/// 打包结果
+#[derive(Debug, Serialize)]
+pub struct PackResult {
+    /// 打包后的精灵列表
+    pub packed_sprites: Vec<PackedSprite>,
+    /// 纹理图宽度
+    pub texture_width: u32,
+    /// 纹理图高度
+    pub texture_height: u32,
+    /// 填充率（百分比）
+    pub fill_rate: f32,
+    /// 不影响打包结果的警告
+    pub warnings: Vec<String>,
+    /// 完全透明的精灵 ID（按空精灵策略处理）
+    pub empty_sprites: Vec<String>,
+    /// 每个精灵的 alpha 统计（按精灵 ID 索引，仅包含加载了图像的精灵）
+    pub alpha_stats: std::collections::HashMap<String, AlphaStats>,
+    /// 打包结果 ID（由应用填写，导出时传回以使用本次打包的裁剪结果）
+    pub job_id: String,
+    /// 各阶段耗时（多页打包时每页相同，为整个任务的耗时）
+    pub timings: Timings,
+    /// 人类可读的结果摘要（由应用按当前数字格式地区填写）
+    pub summary: String,
+    /// 因重名自动改名的帧（由应用填写，`packed_sprites` 中已是新名称）
+    pub renamed_frames: Vec<FrameRename>,
+}
+
+/// 导出配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportConfig {
+    /// 已打包的精灵
+    pub packed_sprites: Vec<PackedSprite>,
+    /// 纹理尺寸
+    pub texture_width: u32,
+    pub texture_height: u32,
+    /// 输出目录
+    pub output_dir: String,
+    /// 输出文件名（不含扩展名）
+    pub output_name: String,
+    /// 是否打包为 ZIP
+    pub zip_output: bool,
+    /// 导出时的图像调整（可选）
+    pub adjustments: Option<ImageAdjustments>,
+    /// 只调整这些精灵（可选，按 ID；不设置则调整整张纹理）
+    pub adjust_sprite_ids: Option<Vec<String>>,
+    /// 数据文件格式（可选，默认 plist）
+    #[serde(default)]
+    pub format: SheetFormat,
+    /// plist 键名版本与编码（可选，默认 Format 3 XML）
+    #[serde(default)]
+    pub plist_format: PlistFormat,
+    /// 边缘挤出像素（可选，应与打包时的 extrude 一致）
+    #[serde(default)]
+    pub extrude: u32,
+    /// 透明像素渗色的迭代次数（可选，0 为关闭），消除缩放时的暗边
+    #[serde(default)]
+    pub bleed: u32,
+    /// 额外导出的缩放版本（可选，如 0.5 → `-sd`，与全分辨率文件写在一起）
+    #[serde(default)]
+    pub variants: Vec<ScaleVariant>,
+    /// 额外按文件夹或标签分组写出数据文件（可选，各分组文件引用同一张纹理）
+    #[serde(default)]
+    pub frame_groups: Option<FrameGrouping>,
+    /// 帧备注（可选，按精灵 ID；有备注时额外写出 `{outputName}.notes.json`）
+    #[serde(default)]
+    pub frame_notes: std::collections::HashMap<String, String>,
+    /// 纹理像素格式、调色板量化与 PNG 压缩级别（可选，默认 RGBA8888）
+    #[serde(default)]
+    pub texture: TextureOptions,
+    /// plist 中 smartupdate 哈希的计算方式（可选，默认按纹理文件名、帧布局与像素计算）
+    #[serde(default)]
+    pub smartupdate: SmartUpdateMode,
+    /// 打包结果 ID（`PackResult.job_id`）
+    pub job_id: String,
+}
+
+/// 多页导出中的一页
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportPage {
+    /// 该页已打包的精灵
+    pub packed_sprites: Vec<PackedSprite>,
+    /// 纹理尺寸
+    pub texture_width: u32,
+    pub texture_height: u32,
+}
+
+/// 多页导出配置（每页输出 `{outputName}_{page}.png` 与对应格式的数据文件）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PagedExportConfig {
+    /// 各页（顺序即页号，从 0 开始）
+    pub pages: Vec<ExportPage>,
+    /// 输出目录
+    pub output_dir: String,
+    /// 输出文件名（不含扩展名与页号）
+    pub output_name: String,
+    /// 是否把所有页打包为一个 ZIP
+    pub zip_output: bool,
+    /// 导出时的图像调整（可选）
+    pub adjustments: Option<ImageAdjustments>,
+    /// 只调整这些精灵（可选，按 ID；不设置则调整整张纹理）
+    pub adjust_sprite_ids: Option<Vec<String>>,
+    /// 数据文件格式（可选，默认 plist）
+    #[serde(default)]
+    pub format: SheetFormat,
+    /// plist 键名版本与编码（可选，默认 Format 3 XML）
+    #[serde(default)]
+    pub plist_format: PlistFormat,
+    /// 边缘挤出像素（可选，应与打包时的 extrude 一致）
+    #[serde(default)]
+    pub extrude: u32,
+    /// 透明像素渗色的迭代次数（可选，0 为关闭），消除缩放时的暗边
+    #[serde(default)]
+    pub bleed: u32,
+    /// 帧备注（可选，按精灵 ID；有备注的页额外写出 `{outputName}_{page}.notes.json`）
+    #[serde(default)]
+    pub frame_notes: std::collections::HashMap<String, String>,
+    /// 纹理像素格式、调色板量化与 PNG 压缩级别（可选，各页相同）
+    #[serde(default)]
+    pub texture: TextureOptions,
+    /// plist 中 smartupdate 哈希的计算方式（可选，各页相同）
+    #[serde(default)]
+    pub smartupdate: SmartUpdateMode,
+    /// 打包结果 ID（各页共用的 `PackResult.job_id`）
+    pub job_id: String,
+}
+
+/// Plist 元数据
+#[derive(Debug, Serialize)]
+pub struct PlistMetadata {
+    pub format: i32,
+    pub real_texture_file_name: String,
+    pub size: String,
+    pub texture_file_name: String,
+    pub smartupdate: String,
+}
+
+/// Plist 帧数据
+#[derive(Debug, Serialize)]
+pub struct PlistFrameData {
+    #[serde(rename = "spriteOffset")]
+    pub sprite_offset: String,
+    
+    #[serde(rename = "spriteSize")]
+    pub sprite_size: String,
+    
+    #[serde(rename = "spriteSourceSize")]
+    pub sprite_source_size: String,
+    
+    #[serde(rename = "textureRect")]
+    pub texture_rect: String,
+    
+    #[serde(rename = "textureRotated")]
+    pub texture_rotated: bool,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aliases: Option<Vec<String>>,
+}
+
+// ========== 拆分图集相关类型 ==========
+
+/// 精灵图集信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpritesheetInfo {
+    /// 文件路径
+    pub path: String,
+    /// 文件名
+    pub name: String,
+    /// 图集宽度
+    pub width: u32,
+    /// 图集高度
+    pub height: u32,
+}
+
+/// 切分帧信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrameInfo {
+    /// 帧名称（如 "idle_01.png"）
+    pub name: String,
+    /// 在图集中的 X 坐标
+    pub x: u32,
+    /// 在图集中的 Y 坐标
+    pub y: u32,
+    /// 帧宽度
+    pub width: u32,
+    /// 帧高度
+    pub height: u32,
+    /// 行索引
+    pub row: u32,
+    /// 列索引
+    pub col: u32,
+    /// 锚点（可选，相对帧尺寸的 0~1 坐标，Y 轴向上）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<(f32, f32)>,
+}
+
+/// 切分配置
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SplitConfig {
+    /// 行数
+    pub rows: u32,
+    /// 列数
+    pub cols: u32,
+    /// 固定帧宽（可选，不设置则自动计算）
+    pub frame_width: Option<u32>,
+    /// 固定帧高（可选，不设置则自动计算）
+    pub frame_height: Option<u32>,
+    /// 帧名称前缀
+    pub name_prefix: String,
+    /// 起始编号（可选，设置时覆盖 `numbering.start`）
+    pub start_index: Option<u32>,
+    /// 帧序号规则（可选，默认从 1 开始、步长 1、补零到 2 位）
+    pub numbering: Option<FrameNumbering>,
+    /// 帧尺寸约束（可选，不满足时在结果中返回警告）
+    pub constraints: Option<FrameConstraints>,
+    /// 自动计算帧尺寸时不能整除的取整策略（可选，默认截断）
+    pub rounding: Option<SplitRounding>,
+    /// 左右外边距（可选，默认 0）
+    pub margin_x: Option<u32>,
+    /// 上下外边距（可选，默认 0）
+    pub margin_y: Option<u32>,
+    /// 相邻两列之间的间距（可选，默认 0）
+    pub spacing_x: Option<u32>,
+    /// 相邻两行之间的间距（可选，默认 0）
+    pub spacing_y: Option<u32>,
+    /// 网格整体向右的偏移（可选，默认 0）
+    pub offset_x: Option<u32>,
+    /// 网格整体向下的偏移（可选，默认 0）
+    pub offset_y: Option<u32>,
+    /// 跳过完全透明的格子（可选，默认 false；启用时需要读取图集图像）
+    pub skip_empty: Option<bool>,
+    /// 所有帧的锚点（可选，相对帧尺寸的 0~1 坐标，Y 轴向上）
+    pub anchor: Option<(f32, f32)>,
+}
+
+impl SplitConfig {
+    /// 水平方向的边距、间距与偏移
+    pub fn gutter_x(&self) -> GridGutter {
+        GridGutter {
+            margin: self.margin_x.unwrap_or(0),
+            spacing: self.spacing_x.unwrap_or(0),
+            offset: self.offset_x.unwrap_or(0),
+        }
+    }
+
+    /// 垂直方向的边距、间距与偏移
+    pub fn gutter_y(&self) -> GridGutter {
+        GridGutter {
+            margin: self.margin_y.unwrap_or(0),
+            spacing: self.spacing_y.unwrap_or(0),
+            offset: self.offset_y.unwrap_or(0),
+        }
+    }
+}
+
+/// 切分结果
+#[derive(Debug, Serialize)]
+pub struct SplitResult {
+    /// 切分后的帧列表
+    pub frames: Vec<FrameInfo>,
+    /// 每帧宽度（分摊余数时为截断后的基准宽度，各帧精确尺寸见 `frames`）
+    pub frame_width: u32,
+    /// 每帧高度（分摊余数时为截断后的基准高度）
+    pub frame_height: u32,
+    /// 总帧数
+    pub total_frames: u32,
+    /// 因完全透明而跳过的格子数
+    pub skipped_empty: u32,
+    /// 帧尺寸约束警告
+    pub warnings: Vec<FrameWarning>,
+}
+
+/// 动画区域定义（用于多区域导出）
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimationRegion {
+    /// 区域名称（如 "idle"）
+    pub name: String,
+    /// 起始行（0-indexed）
+    pub start_row: u32,
+    /// 起始列（0-indexed）
+    pub start_col: u32,
+    /// 帧数
+    pub frame_count: u32,
+    /// 帧宽度
+    pub frame_width: u32,
+    /// 帧高度
+    pub frame_height: u32,
+    /// 帧序号规则（可选，默认从 1 开始、步长 1、补零到 2 位）
+    pub numbering: Option<FrameNumbering>,
+    /// 区域内所有帧的锚点（可选，相对帧尺寸的 0~1 坐标，Y 轴向上）
+    pub anchor: Option<(f32, f32)>,
+}
+
+// ========== GameMaker 条带图相关类型 ==========
+
+/// 条带图分组（一个动画对应一张条带图）
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StripGroup {
+    /// 动画名称（如 "idle"）
+    pub name: String,
+    /// 按播放顺序排列的精灵
+    pub sprites: Vec<SpriteData>,
+}
+
+/// 条带图导出结果
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StripExportResult {
+    /// 成功导出的条带图路径列表
+    pub exported_files: Vec<String>,
+    /// 失败的分组名称及错误信息
+    pub failed: Vec<(String, String)>,
+    /// 总分组数
+    pub total: usize,
+}
+
+// ========== 动画预览相关类型 ==========
+
+/// 动画预览帧
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewFrame {
+    /// 帧名称
+    pub name: String,
+    /// 裁剪后帧图像的临时文件路径
+    pub path: String,
+    /// 帧宽度
+    pub width: u32,
+    /// 帧高度
+    pub height: u32,
+    /// 播放时长（毫秒）
+    pub duration_ms: u32,
+}
+
+/// 动画预览数据
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimationPreview {
+    /// 按播放顺序排列的帧
+    pub frames: Vec<PreviewFrame>,
+    /// 帧率
+    pub fps: f32,
+    /// 总时长（毫秒）
+    pub total_duration_ms: u32,
+}
+
+/// 动图预览导出结果
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimationPreviewExport {
+    /// 文件格式
+    pub format: PreviewFormat,
+    /// 写出的文件路径（写到 Plist 旁时）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// base64 data URL（未指定 Plist 路径时，供应用内预览）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_url: Option<String>,
+    /// 帧数
+    pub frame_count: usize,
+    /// 画布宽度
+    pub width: u32,
+    /// 画布高度
+    pub height: u32,
+    /// 总时长（毫秒）
+    pub total_duration_ms: u32,
+}
@@ -0,0 +1,230 @@
+/// 像素画放大 (Pixel-Art Upscaling)
+///
+/// 为高 DPI 目标生成 2x/3x 资源。提供两种整数倍放大算法：
+/// - nearest: 最近邻，每个像素复制为 N×N 色块
+/// - epx: Scale2x/Scale3x（EPX/AdvMAME），根据相邻像素的相等关系平滑对角边缘，
+///   不引入新颜色，适合调色板受限的像素画
+///
+/// 颜色比较时所有完全透明的像素视为相同
+
+use image::{Rgba, RgbaImage};
+use serde::{Deserialize, Serialize};
+
+/// 放大算法
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UpscaleFilter {
+    /// 最近邻
+    #[default]
+    Nearest,
+    /// Scale2x/Scale3x 边缘平滑
+    Epx,
+}
+
+/// 放大选项
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct UpscaleOptions {
+    /// 放大算法
+    pub filter: UpscaleFilter,
+    /// 放大倍数（2 或 3）
+    pub factor: u32,
+}
+
+impl Default for UpscaleOptions {
+    fn default() -> Self {
+        Self {
+            filter: UpscaleFilter::Nearest,
+            factor: 2,
+        }
+    }
+}
+
+impl UpscaleOptions {
+    /// 检查放大倍数
+    pub fn validate(&self) -> Result<(), String> {
+        if !(2..=3).contains(&self.factor) {
+            return Err(format!("放大倍数只支持 2 或 3，当前为 {}", self.factor));
+        }
+        Ok(())
+    }
+}
+
+/// 放大图像
+///
+/// # Arguments
+/// * `image` - 原始图像
+/// * `options` - 放大选项（调用前应通过 `validate` 检查）
+///
+/// # Returns
+/// * `RgbaImage` - 宽高各乘以放大倍数的图像
+pub fn upscale_image(image: &RgbaImage, options: &UpscaleOptions) -> RgbaImage {
+    match (options.filter, options.factor) {
+        (UpscaleFilter::Epx, 2) => scale2x(image),
+        (UpscaleFilter::Epx, 3) => scale3x(image),
+        (_, factor) => nearest(image, factor),
+    }
+}
+
+fn nearest(image: &RgbaImage, factor: u32) -> RgbaImage {
+    RgbaImage::from_fn(image.width() * factor, image.height() * factor, |x, y| {
+        *image.get_pixel(x / factor, y / factor)
+    })
+}
+
+/// 读取邻域像素，越界时取最近的边缘像素
+struct Neighborhood<'a> {
+    image: &'a RgbaImage,
+}
+
+impl Neighborhood<'_> {
+    fn at(&self, x: u32, y: u32, dx: i32, dy: i32) -> Rgba<u8> {
+        let nx = (x as i64 + dx as i64).clamp(0, self.image.width() as i64 - 1) as u32;
+        let ny = (y as i64 + dy as i64).clamp(0, self.image.height() as i64 - 1) as u32;
+        *self.image.get_pixel(nx, ny)
+    }
+}
+
+fn same(a: Rgba<u8>, b: Rgba<u8>) -> bool {
+    a == b || (a[3] == 0 && b[3] == 0)
+}
+
+fn scale2x(image: &RgbaImage) -> RgbaImage {
+    let n = Neighborhood { image };
+    let mut out = RgbaImage::new(image.width() * 2, image.height() * 2);
+
+    for (x, y, &p) in image.enumerate_pixels() {
+        let a = n.at(x, y, 0, -1);
+        let b = n.at(x, y, 1, 0);
+        let c = n.at(x, y, -1, 0);
+        let d = n.at(x, y, 0, 1);
+
+        let block = [
+            if same(c, a) && !same(c, d) && !same(a, b) { a } else { p },
+            if same(a, b) && !same(a, c) && !same(b, d) { b } else { p },
+            if same(d, c) && !same(d, b) && !same(c, a) { c } else { p },
+            if same(b, d) && !same(b, a) && !same(d, c) { d } else { p },
+        ];
+        for (i, pixel) in block.into_iter().enumerate() {
+            out.put_pixel(x * 2 + i as u32 % 2, y * 2 + i as u32 / 2, pixel);
+        }
+    }
+    out
+}
+
+fn scale3x(image: &RgbaImage) -> RgbaImage {
+    let n = Neighborhood { image };
+    let mut out = RgbaImage::new(image.width() * 3, image.height() * 3);
+
+    for (x, y, &e) in image.enumerate_pixels() {
+        let a = n.at(x, y, -1, -1);
+        let b = n.at(x, y, 0, -1);
+        let c = n.at(x, y, 1, -1);
+        let d = n.at(x, y, -1, 0);
+        let f = n.at(x, y, 1, 0);
+        let g = n.at(x, y, -1, 1);
+        let h = n.at(x, y, 0, 1);
+        let i = n.at(x, y, 1, 1);
+
+        // 四个方向的对角边缘
+        let top_left = same(d, b) && !same(b, f) && !same(d, h);
+        let top_right = same(b, f) && !same(b, d) && !same(f, h);
+        let bottom_left = same(d, h) && !same(d, b) && !same(h, f);
+        let bottom_right = same(h, f) && !same(d, h) && !same(b, f);
+
+        let block = [
+            if top_left { d } else { e },
+            if (top_left && !same(e, c)) || (top_right && !same(e, a)) { b } else { e },
+            if top_right { f } else { e },
+            if (top_left && !same(e, g)) || (bottom_left && !same(e, a)) { d } else { e },
+            e,
+            if (top_right && !same(e, i)) || (bottom_right && !same(e, c)) { f } else { e },
+            if bottom_left { d } else { e },
+            if (bottom_left && !same(e, i)) || (bottom_right && !same(e, g)) { h } else { e },
+            if bottom_right { f } else { e },
+        ];
+        for (index, pixel) in block.into_iter().enumerate() {
+            out.put_pixel(x * 3 + index as u32 % 3, y * 3 + index as u32 / 3, pixel);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INK: Rgba<u8> = Rgba([0, 0, 0, 255]);
+    const PAPER: Rgba<u8> = Rgba([255, 255, 255, 255]);
+
+    fn image_from(rows: &[&str]) -> RgbaImage {
+        RgbaImage::from_fn(rows[0].len() as u32, rows.len() as u32, |x, y| {
+            if rows[y as usize].as_bytes()[x as usize] == b'#' {
+                INK
+            } else {
+                PAPER
+            }
+        })
+    }
+
+    fn options(filter: UpscaleFilter, factor: u32) -> UpscaleOptions {
+        UpscaleOptions { filter, factor }
+    }
+
+    #[test]
+    fn test_nearest_blocks() {
+        let img = image_from(&["#.", ".#"]);
+        let out = upscale_image(&img, &options(UpscaleFilter::Nearest, 3));
+        assert_eq!(out.dimensions(), (6, 6));
+        assert_eq!(*out.get_pixel(2, 2), INK);
+        assert_eq!(*out.get_pixel(3, 2), PAPER);
+        assert_eq!(*out.get_pixel(5, 5), INK);
+    }
+
+    #[test]
+    fn test_scale2x_smooths_diagonal() {
+        // 对角线上的像素之间补出阶梯
+        let img = image_from(&["#..", ".#.", "..#"]);
+        let out = upscale_image(&img, &options(UpscaleFilter::Epx, 2));
+        assert_eq!(out.dimensions(), (6, 6));
+        assert_eq!(*out.get_pixel(2, 1), INK);
+        assert_eq!(*out.get_pixel(1, 2), INK);
+        // 远离对角线的区域保持不变
+        assert_eq!(*out.get_pixel(5, 0), PAPER);
+        assert_eq!(*out.get_pixel(0, 5), PAPER);
+    }
+
+    #[test]
+    fn test_scale3x_flat_and_dimensions() {
+        let flat = RgbaImage::from_pixel(4, 2, PAPER);
+        let out = upscale_image(&flat, &options(UpscaleFilter::Epx, 3));
+        assert_eq!(out.dimensions(), (12, 6));
+        assert!(out.pixels().all(|p| *p == PAPER));
+
+        let img = image_from(&["#..", ".#.", "..#"]);
+        let out = upscale_image(&img, &options(UpscaleFilter::Epx, 3));
+        assert_eq!(*out.get_pixel(4, 4), INK);
+        assert_eq!(*out.get_pixel(3, 2), INK);
+        assert_eq!(*out.get_pixel(8, 0), PAPER);
+    }
+
+    #[test]
+    fn test_transparent_pixels_compare_equal() {
+        // RGB 不同但都完全透明的像素不应产生边缘
+        let mut img = RgbaImage::new(2, 2);
+        img.put_pixel(0, 0, Rgba([255, 0, 0, 0]));
+        img.put_pixel(1, 1, Rgba([0, 0, 255, 0]));
+        let out = upscale_image(&img, &options(UpscaleFilter::Epx, 2));
+        assert!(out.pixels().all(|p| p[3] == 0));
+    }
+
+    #[test]
+    fn test_validate_factor() {
+        assert!(options(UpscaleFilter::Nearest, 2).validate().is_ok());
+        assert!(options(UpscaleFilter::Epx, 4).validate().is_err());
+        assert!(options(UpscaleFilter::Nearest, 1).validate().is_err());
+
+        let parsed: UpscaleOptions = serde_json::from_str(r#"{"filter":"epx"}"#).unwrap();
+        assert_eq!(parsed, options(UpscaleFilter::Epx, 2));
+    }
+}
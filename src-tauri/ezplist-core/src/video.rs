@@ -0,0 +1,123 @@
+/// 视频帧采样 (Video Frame Sampling)
+///
+/// 从 mp4/webm 等视频中按固定帧率采样帧的参数校验与解码器命令行生成，
+/// 实际解码由随应用分发的 ffmpeg 完成（见 `utils::video_decoder`）
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// 支持导入的视频扩展名
+pub const VIDEO_EXTENSIONS: &[&str] = &["mp4", "webm", "mov", "mkv", "avi"];
+
+/// 最大采样帧率
+pub const MAX_VIDEO_FPS: f64 = 120.0;
+
+/// 单次导入的最大帧数
+pub const MAX_VIDEO_FRAMES: u32 = 2000;
+
+/// 采样的时间范围（秒）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct VideoRange {
+    /// 起始时间
+    pub start: f64,
+    /// 结束时间（可选，不设置时采样到视频结尾）
+    pub end: Option<f64>,
+}
+
+impl VideoRange {
+    /// 检查时间范围
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.start.is_finite() || self.start < 0.0 {
+            return Err(format!("起始时间无效: {}", self.start));
+        }
+        match self.end {
+            Some(end) if !end.is_finite() || end <= self.start => {
+                Err(format!("结束时间 {} 必须大于起始时间 {}", end, self.start))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// 检查采样帧率
+pub fn validate_fps(fps: f64) -> Result<(), String> {
+    if fps.is_finite() && fps > 0.0 && fps <= MAX_VIDEO_FPS {
+        Ok(())
+    } else {
+        Err(format!("采样帧率必须在 0 ~ {} 之间: {}", MAX_VIDEO_FPS, fps))
+    }
+}
+
+/// 是否为支持的视频文件（按扩展名判断）
+pub fn is_video_path(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| VIDEO_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+/// 生成 ffmpeg 采样命令行参数
+///
+/// 按帧率采样并输出为 RGBA PNG 序列（保留 webm 的透明通道），
+/// 帧数不超过 `MAX_VIDEO_FRAMES`
+///
+/// # Arguments
+/// * `input` - 视频路径
+/// * `output_pattern` - 输出文件模板（如 `walk_%04d.png`）
+/// * `fps` - 采样帧率（调用前应通过 `validate_fps` 检查）
+/// * `range` - 时间范围
+///
+/// # Returns
+/// * `Vec<String>` - 参数列表（不含程序名）
+pub fn decoder_args(input: &str, output_pattern: &str, fps: f64, range: &VideoRange) -> Vec<String> {
+    let mut args: Vec<String> = ["-hide_banner", "-loglevel", "error", "-nostdin", "-y"]
+        .into_iter()
+        .map(String::from)
+        .collect();
+    if range.start > 0.0 {
+        args.extend(["-ss".into(), range.start.to_string()]);
+    }
+    args.extend(["-i".into(), input.to_string()]);
+    if let Some(end) = range.end {
+        args.extend(["-t".into(), (end - range.start).to_string()]);
+    }
+    args.extend([
+        "-vf".into(),
+        format!("fps={}", fps),
+        "-frames:v".into(),
+        MAX_VIDEO_FRAMES.to_string(),
+        "-pix_fmt".into(),
+        "rgba".into(),
+        output_pattern.to_string(),
+    ]);
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decoder_args() {
+        let range = VideoRange { start: 1.5, end: Some(3.0) };
+        let args = decoder_args("in.webm", "out_%04d.png", 12.0, &range);
+        let joined = args.join(" ");
+        assert!(joined.contains("-ss 1.5 -i in.webm -t 1.5"));
+        assert!(joined.ends_with("-vf fps=12 -frames:v 2000 -pix_fmt rgba out_%04d.png"));
+
+        let args = decoder_args("in.mp4", "out_%04d.png", 24.0, &VideoRange::default());
+        assert!(!args.contains(&"-ss".to_string()) && !args.contains(&"-t".to_string()));
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(validate_fps(30.0).is_ok());
+        assert!(validate_fps(0.0).is_err());
+        assert!(validate_fps(f64::NAN).is_err());
+        assert!(VideoRange { start: 2.0, end: Some(1.0) }.validate().is_err());
+        assert!(VideoRange { start: -1.0, end: None }.validate().is_err());
+        assert!(is_video_path("clip/Walk.MP4"));
+        assert!(!is_video_path("walk.png"));
+    }
+}
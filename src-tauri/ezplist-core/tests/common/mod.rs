@@ -0,0 +1,66 @@
+#![allow(dead_code)]
+
+/// 测试公共工具
+///
+/// golden 文件比对：输出与 `tests/golden/` 下的基准文件逐字节比较，
+/// 设置环境变量 `UPDATE_GOLDEN=1` 运行测试可重新生成基准文件；
+/// 合成精灵生成器见 `synthetic`
+
+pub mod synthetic;
+
+use ezplist_core::packer::SpriteInput;
+use std::path::PathBuf;
+
+/// golden 基准文件路径
+pub fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden").join(name)
+}
+
+/// 测试夹具文件路径
+pub fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name)
+}
+
+/// 读取测试夹具
+pub fn read_fixture(name: &str) -> String {
+    std::fs::read_to_string(fixture_path(name))
+        .unwrap_or_else(|e| panic!("无法读取夹具 {}: {}", name, e))
+}
+
+/// 与 golden 基准文件比对
+pub fn assert_golden(name: &str, actual: &[u8]) {
+    let path = golden_path(name);
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read(&path)
+        .unwrap_or_else(|e| panic!("缺少 golden 文件 {} ({})，使用 UPDATE_GOLDEN=1 生成", name, e));
+
+    if expected != actual {
+        panic!(
+            "输出与 golden 文件 {} 不一致\n--- 期望 ---\n{}\n--- 实际 ---\n{}",
+            name,
+            String::from_utf8_lossy(&expected),
+            String::from_utf8_lossy(actual)
+        );
+    }
+}
+
+/// 创建未裁剪的打包输入
+pub fn sprite_input(id: &str, width: u32, height: u32) -> SpriteInput {
+    SpriteInput {
+        id: id.to_string(),
+        name: format!("{}.png", id),
+        width,
+        height,
+        original_width: width,
+        original_height: height,
+        offset_x: 0,
+        offset_y: 0,
+        trimmed: false,
+        allow_rotation: true,
+    }
+}
@@ -0,0 +1,89 @@
+/// 合成精灵生成器
+///
+/// 用固定种子的伪随机数生成尺寸随机的渐变精灵，可加透明边框或复制出内容相同的帧，
+/// 供端到端测试构造可重复的输入（同一种子总是生成相同的精灵）
+
+use image::{Rgba, RgbaImage};
+
+/// 合成精灵
+#[derive(Debug, Clone)]
+pub struct SyntheticSprite {
+    /// 帧名称（`sprite_{序号}.png`，复制帧为 `sprite_{序号}_copy.png`）
+    pub name: String,
+    /// 原始图像
+    pub image: RgbaImage,
+}
+
+/// 合成精灵生成器（xorshift 伪随机数）
+#[derive(Debug, Clone)]
+pub struct SpriteGenerator {
+    state: u64,
+}
+
+impl SpriteGenerator {
+    /// 创建生成器
+    pub fn new(seed: u64) -> Self {
+        // 种子为 0 时 xorshift 只会输出 0
+        Self { state: seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1 }
+    }
+
+    /// 下一个伪随机数
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    /// `[min, max]` 内的伪随机数
+    pub fn range(&mut self, min: u32, max: u32) -> u32 {
+        min + (self.next_u64() % (max - min + 1) as u64) as u32
+    }
+
+    /// 渐变图像：颜色随坐标变化，alpha 在 2–255 之间（不会被默认阈值裁掉）
+    pub fn gradient(&mut self, width: u32, height: u32) -> RgbaImage {
+        let base = [self.range(0, 255), self.range(0, 255), self.range(0, 255)];
+        let translucent = self.range(0, 3) == 0;
+        RgbaImage::from_fn(width, height, |x, y| {
+            let r = (base[0] + x * 255 / width.max(2)) % 256;
+            let g = (base[1] + y * 255 / height.max(2)) % 256;
+            let b = (base[2] + (x + y) * 7) % 256;
+            let a = if translucent { 2 + (x * 31 + y * 17) % 254 } else { 255 };
+            Rgba([r as u8, g as u8, b as u8, a as u8])
+        })
+    }
+
+    /// 在四周加随机宽度（0–`max`）的完全透明边框
+    pub fn transparent_border(&mut self, content: &RgbaImage, max: u32) -> RgbaImage {
+        let (left, top) = (self.range(0, max), self.range(0, max));
+        let (right, bottom) = (self.range(0, max), self.range(0, max));
+        let mut image = RgbaImage::new(content.width() + left + right, content.height() + top + bottom);
+        image::imageops::replace(&mut image, content, left as i64, top as i64);
+        image
+    }
+
+    /// 生成一组精灵
+    ///
+    /// # Arguments
+    /// * `count` - 不含复制帧的精灵数
+    /// * `max_size` - 内容的最大边长
+    ///
+    /// # Returns
+    /// * `Vec<SyntheticSprite>` - 约一半带透明边框，约四分之一之后跟一个内容相同的复制帧
+    pub fn sprites(&mut self, count: usize, max_size: u32) -> Vec<SyntheticSprite> {
+        let mut sprites = Vec::with_capacity(count * 2);
+        for index in 0..count {
+            let (width, height) = (self.range(1, max_size), self.range(1, max_size));
+            let mut image = self.gradient(width, height);
+            if self.range(0, 1) == 0 {
+                image = self.transparent_border(&image, 6);
+            }
+            let duplicate = self.range(0, 3) == 0;
+            sprites.push(SyntheticSprite { name: format!("sprite_{}.png", index), image: image.clone() });
+            if duplicate {
+                sprites.push(SyntheticSprite { name: format!("sprite_{}_copy.png", index), image });
+            }
+        }
+        sprites
+    }
+}
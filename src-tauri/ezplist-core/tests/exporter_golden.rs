@@ -0,0 +1,294 @@
+/// 导出器 golden 测试 (Exporter Golden Tests)
+///
+/// 固定输入渲染后与 `tests/golden/` 基准文件比对，并验证可解析格式的往返一致性
+
+mod common;
+
+use common::{assert_golden, read_fixture};
+use ezplist_core::exporters::{SheetData, SheetFormat};
+use ezplist_core::hash::SmartUpdate;
+use ezplist_core::hit_area::{generate_hit_area_json, HitArea, HitShape};
+use ezplist_core::outline::{extract_outline, OutlineOptions, Point};
+use ezplist_core::physics::{generate_physics_plist, FixtureParams, PhysicsBody, PhysicsExportOptions};
+use ezplist_core::plist_generator::PlistFormat;
+use ezplist_core::strip::{compose_strip, strip_cell_size};
+use ezplist_core::template_exporter::{render_template, TemplateContext};
+use ezplist_core::texture_format::PixelFormat;
+use ezplist_core::types::{FrameInfo, PackedSprite};
+use image::{Rgba, RgbaImage};
+use proptest::prelude::*;
+
+/// 固定的打包结果（包含旋转和裁剪帧）
+fn sample_packed() -> Vec<PackedSprite> {
+    vec![
+        PackedSprite {
+            id: "1".to_string(),
+            name: "hero_idle.png".to_string(),
+            x: 0,
+            y: 0,
+            width: 64,
+            height: 96,
+            rotated: false,
+            original_width: 80,
+            original_height: 100,
+            trimmed: true,
+            offset_x: -2,
+            offset_y: 1,
+            flip_x: false,
+            flip_y: false,
+            anchor: None,
+            nine_slice: None,
+        },
+        PackedSprite {
+            id: "2".to_string(),
+            name: "sword.png".to_string(),
+            x: 65,
+            y: 0,
+            width: 16,
+            height: 48,
+            rotated: true,
+            original_width: 48,
+            original_height: 16,
+            trimmed: false,
+            offset_x: 0,
+            offset_y: 0,
+            flip_x: false,
+            flip_y: false,
+            anchor: None,
+            nine_slice: None,
+        },
+    ]
+}
+
+/// 固定的切分帧
+fn sample_frames() -> Vec<FrameInfo> {
+    (0..3)
+        .map(|i| FrameInfo {
+            name: format!("walk_{:02}.png", i + 1),
+            x: i * 32,
+            y: 0,
+            width: 32,
+            height: 32,
+            row: 0,
+            col: i,
+            anchor: None,
+        })
+        .collect()
+}
+
+#[test]
+fn template_json_hash_golden() {
+    let context = TemplateContext::from_packed(&sample_packed(), 128, 128, "atlas.png");
+    let output = render_template(&read_fixture("templates/json_hash.hbs"), &context).unwrap();
+    assert_golden("template_json_hash.json", output.as_bytes());
+}
+
+#[test]
+fn template_json_hash_round_trip() {
+    let packed = sample_packed();
+    let context = TemplateContext::from_packed(&packed, 128, 128, "atlas.png");
+    let output = render_template(&read_fixture("templates/json_hash.hbs"), &context).unwrap();
+
+    // 导出的 JSON 重新解析后与输入一致
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    for sprite in &packed {
+        let frame = &parsed["frames"][&sprite.name];
+        assert_eq!(frame["frame"]["x"], sprite.x);
+        assert_eq!(frame["frame"]["y"], sprite.y);
+        assert_eq!(frame["frame"]["w"], sprite.width);
+        assert_eq!(frame["frame"]["h"], sprite.height);
+        assert_eq!(frame["rotated"], sprite.rotated);
+        assert_eq!(frame["sourceSize"]["w"], sprite.original_width);
+        assert_eq!(frame["offset"]["x"], sprite.offset_x);
+    }
+    assert_eq!(parsed["meta"]["frameCount"], packed.len());
+}
+
+#[test]
+fn template_xml_golden() {
+    let context = TemplateContext::from_frames(&sample_frames(), 96, 32, "walk.png");
+    let output = render_template(&read_fixture("templates/texture_atlas.xml.hbs"), &context).unwrap();
+    assert_golden("template_texture_atlas.xml", output.as_bytes());
+}
+
+/// 用指定格式导出 `sample_packed`
+fn export_sheet(format: SheetFormat) -> String {
+    String::from_utf8(export_sheet_bytes(format, PlistFormat::default())).unwrap()
+}
+
+fn export_sheet_bytes(format: SheetFormat, plist_format: PlistFormat) -> Vec<u8> {
+    let packed = sample_packed();
+    let sheet = SheetData { sprites: &packed, texture_width: 128, texture_height: 128, texture_name: "atlas.png", pixel_format: PixelFormat::Rgba8888, smartupdate: SmartUpdate::default() };
+    format.exporter(plist_format).export(&sheet).unwrap()
+}
+
+#[test]
+fn cocos_plist_golden() {
+    assert_golden("cocos_v3.plist", &export_sheet_bytes(SheetFormat::Plist, PlistFormat::XmlV3));
+    assert_golden("cocos_v2.plist", &export_sheet_bytes(SheetFormat::Plist, PlistFormat::XmlV2));
+
+    // 二进制输出与 XML 描述同一份数据
+    let binary = plist::Value::from_reader(std::io::Cursor::new(export_sheet_bytes(SheetFormat::Plist, PlistFormat::Binary))).unwrap();
+    let xml = plist::Value::from_reader(std::io::Cursor::new(export_sheet_bytes(SheetFormat::Plist, PlistFormat::XmlV3))).unwrap();
+    assert_eq!(binary, xml);
+}
+
+#[test]
+fn texture_packer_json_golden() {
+    assert_golden("texture_packer_hash.json", export_sheet(SheetFormat::JsonHash).as_bytes());
+    assert_golden("texture_packer_array.json", export_sheet(SheetFormat::JsonArray).as_bytes());
+}
+
+#[test]
+fn texture_packer_json_round_trip() {
+    let packed = sample_packed();
+    let hash: serde_json::Value = serde_json::from_str(&export_sheet(SheetFormat::JsonHash)).unwrap();
+    let array: serde_json::Value = serde_json::from_str(&export_sheet(SheetFormat::JsonArray)).unwrap();
+
+    for (index, sprite) in packed.iter().enumerate() {
+        let frame = &hash["frames"][&sprite.name];
+        // 两种格式除 filename 外内容相同
+        let mut entry = array["frames"][index].clone();
+        assert_eq!(entry.as_object_mut().unwrap().remove("filename").unwrap(), sprite.name.as_str());
+        assert_eq!(&entry, frame);
+
+        // frame 的宽高为未旋转时的帧尺寸
+        let (width, height) = sprite.frame_size();
+        assert_eq!((frame["frame"]["w"].as_u64(), frame["frame"]["h"].as_u64()), (Some(width as u64), Some(height as u64)));
+        assert_eq!(frame["frame"]["x"], sprite.x);
+        assert_eq!(frame["rotated"], sprite.rotated);
+        assert_eq!(frame["sourceSize"]["w"], sprite.original_width);
+    }
+    assert_eq!(hash["meta"]["image"], "atlas.png");
+}
+
+/// 固定的物理刚体：L 形帧与矩形帧
+fn sample_bodies() -> Vec<PhysicsBody> {
+    let mut l_shape = RgbaImage::new(8, 8);
+    for y in 0..8 {
+        for x in 0..8 {
+            if x < 3 || y >= 5 {
+                l_shape.put_pixel(x, y, Rgba([255, 255, 255, 255]));
+            }
+        }
+    }
+    let crate_box = RgbaImage::from_pixel(6, 4, Rgba([120, 80, 40, 255]));
+
+    vec![
+        PhysicsBody {
+            name: "bracket".to_string(),
+            width: 8,
+            height: 8,
+            outline: extract_outline(&l_shape, &OutlineOptions::default()),
+            fixture: FixtureParams::default(),
+        },
+        PhysicsBody {
+            name: "crate".to_string(),
+            width: 6,
+            height: 4,
+            outline: extract_outline(&crate_box, &OutlineOptions::default()),
+            fixture: FixtureParams {
+                density: 1.0,
+                friction: 0.5,
+                restitution: 0.1,
+            },
+        },
+    ]
+}
+
+#[test]
+fn physics_plist_golden() {
+    let output = generate_physics_plist(&sample_bodies(), &PhysicsExportOptions::default()).unwrap();
+    assert_golden("physics_editor.plist", output.as_bytes());
+}
+
+#[test]
+fn physics_plist_round_trip() {
+    let output = generate_physics_plist(&sample_bodies(), &PhysicsExportOptions::default()).unwrap();
+    let value = plist::Value::from_reader_xml(output.as_bytes()).unwrap();
+
+    let bodies = value.as_dictionary().unwrap()["bodies"].as_dictionary().unwrap();
+    assert_eq!(bodies.len(), 2);
+
+    let fixtures = bodies["bracket"].as_dictionary().unwrap()["fixtures"].as_array().unwrap();
+    let polygons = fixtures[0].as_dictionary().unwrap()["polygons"].as_array().unwrap();
+    // L 形需要分解为两个凸多边形
+    assert_eq!(polygons.len(), 2);
+    assert!(polygons.iter().all(|p| p.as_array().unwrap().len() <= 8));
+}
+
+#[test]
+fn hit_area_json_golden() {
+    let mut frames = std::collections::BTreeMap::new();
+    frames.insert(
+        "punch_02.png".to_string(),
+        vec![
+            HitArea {
+                name: "hitbox".to_string(),
+                shape: HitShape::Rect {
+                    x: 40.0,
+                    y: 12.0,
+                    width: 18.0,
+                    height: 10.0,
+                },
+            },
+            HitArea {
+                name: "hurtbox".to_string(),
+                shape: HitShape::Polygon {
+                    points: vec![
+                        Point { x: 8.0, y: 4.0 },
+                        Point { x: 30.0, y: 4.0 },
+                        Point { x: 26.0, y: 60.0 },
+                        Point { x: 10.0, y: 60.0 },
+                    ],
+                },
+            },
+        ],
+    );
+    frames.insert(
+        "idle_01.png".to_string(),
+        vec![HitArea {
+            name: "hurtbox".to_string(),
+            shape: HitShape::Rect {
+                x: 8.0,
+                y: 4.0,
+                width: 20.0,
+                height: 56.0,
+            },
+        }],
+    );
+
+    let output = generate_hit_area_json(&frames).unwrap();
+    assert_golden("hit_areas.json", output.as_bytes());
+
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let round_trip: Vec<HitArea> = serde_json::from_value(parsed["frames"]["punch_02.png"].clone()).unwrap();
+    assert_eq!(round_trip, frames["punch_02.png"]);
+}
+
+proptest! {
+    #[test]
+    fn strip_cells_hold_every_frame(
+        sizes in prop::collection::vec((1u32..=24, 1u32..=24), 1..=8),
+    ) {
+        // 每帧填充唯一颜色，便于在条带图中定位
+        let frames: Vec<RgbaImage> = sizes
+            .iter()
+            .enumerate()
+            .map(|(i, (w, h))| RgbaImage::from_pixel(*w, *h, Rgba([i as u8 + 1, 0, 0, 255])))
+            .collect();
+
+        let strip = compose_strip(&frames).unwrap();
+        let (cell_width, cell_height) = strip_cell_size(&frames);
+        prop_assert_eq!(strip.dimensions(), (cell_width * frames.len() as u32, cell_height));
+
+        for (i, frame) in frames.iter().enumerate() {
+            let cell_x = i as u32 * cell_width;
+            let count = (cell_x..cell_x + cell_width)
+                .flat_map(|x| (0..cell_height).map(move |y| (x, y)))
+                .filter(|&(x, y)| strip.get_pixel(x, y)[0] == i as u8 + 1)
+                .count();
+            prop_assert_eq!(count as u32, frame.width() * frame.height());
+        }
+    }
+}
@@ -0,0 +1,127 @@
+/// 打包器属性测试 (Packer Property Tests)
+///
+/// 对随机精灵集合验证打包不变量：无重叠、不越界、尺寸与旋转一致、顺序保持
+
+mod common;
+
+use common::sprite_input;
+use ezplist_core::job::PackJob;
+use ezplist_core::packer::{find_optimal_size, MaxRectsPacker, PackHeuristic, PackerOptions, Rect, SortOrder, SpriteInput};
+use ezplist_core::types::PackedSprite;
+use image::{Rgba, RgbaImage};
+use proptest::prelude::*;
+
+/// 随机精灵集合（1~24 个，边长 1~128）
+fn sprite_set() -> impl Strategy<Value = Vec<SpriteInput>> {
+    prop::collection::vec((1u32..=128, 1u32..=128), 1..=24).prop_map(|sizes| {
+        sizes
+            .into_iter()
+            .enumerate()
+            .map(|(i, (w, h))| sprite_input(&format!("s{}", i), w, h))
+            .collect()
+    })
+}
+
+/// 验证打包结果的通用不变量
+fn assert_layout_invariants(
+    inputs: &[SpriteInput],
+    packed: &[PackedSprite],
+    width: u32,
+    height: u32,
+) -> Result<(), TestCaseError> {
+    for sprite in packed {
+        let input = inputs.iter().find(|s| s.id == sprite.id).expect("未知的精灵 ID");
+
+        // 帧在纹理范围内
+        prop_assert!(sprite.x + sprite.width <= width, "{} 超出纹理宽度", sprite.name);
+        prop_assert!(sprite.y + sprite.height <= height, "{} 超出纹理高度", sprite.name);
+
+        // 旋转时宽高互换
+        if sprite.rotated {
+            prop_assert_eq!((sprite.width, sprite.height), (input.height, input.width));
+        } else {
+            prop_assert_eq!((sprite.width, sprite.height), (input.width, input.height));
+        }
+    }
+
+    // 帧之间无重叠
+    for (i, a) in packed.iter().enumerate() {
+        let ra = Rect::new(a.x, a.y, a.width, a.height);
+        for b in &packed[i + 1..] {
+            let rb = Rect::new(b.x, b.y, b.width, b.height);
+            prop_assert!(!ra.intersects(&rb), "{} 与 {} 重叠", a.name, b.name);
+        }
+    }
+
+    // 结果保持输入顺序
+    let order: Vec<usize> = packed
+        .iter()
+        .map(|s| inputs.iter().position(|i| i.id == s.id).unwrap())
+        .collect();
+    prop_assert!(order.windows(2).all(|w| w[0] < w[1]), "结果未保持输入顺序");
+
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn packed_frames_never_overlap_or_leave_bounds(
+        sprites in sprite_set(),
+        allow_rotation in any::<bool>(),
+        padding in 0u32..=4,
+        heuristic in prop::sample::select(PackHeuristic::ALL.to_vec()),
+        sort in prop::sample::select(SortOrder::ALL.to_vec()),
+    ) {
+        let options = PackerOptions { allow_rotation, padding, heuristic, sort, ..Default::default() };
+        let mut packer = MaxRectsPacker::with_options(512, 512, &options);
+        let packed = packer.pack(&sprites);
+        assert_layout_invariants(&sprites, &packed, 512, 512)?;
+    }
+
+    #[test]
+    fn optimal_size_fits_every_sprite(
+        sprites in sprite_set(),
+        allow_rotation in any::<bool>(),
+        padding in 0u32..=2,
+    ) {
+        let options = PackerOptions { allow_rotation, padding, ..Default::default() };
+        if let Some((width, height)) = find_optimal_size(&sprites, 2048, &options) {
+            let mut packer = MaxRectsPacker::new(width, height, allow_rotation, padding);
+            let packed = packer.pack(&sprites);
+            prop_assert_eq!(packed.len(), sprites.len());
+            assert_layout_invariants(&sprites, &packed, width, height)?;
+        }
+    }
+
+    #[test]
+    fn trimmed_frames_stay_within_source(
+        boxes in prop::collection::vec((1u32..=32, 1u32..=32, 0u32..32, 0u32..32), 1..=12),
+    ) {
+        // 每个精灵为 32x32 画布上的一个不透明矩形
+        let mut job = PackJob::new().max_size(1024);
+        for (i, (w, h, x, y)) in boxes.iter().enumerate() {
+            let mut image = RgbaImage::new(32, 32);
+            for py in *y..(*y + *h).min(32) {
+                for px in *x..(*x + *w).min(32) {
+                    image.put_pixel(px, py, Rgba([255, 255, 255, 255]));
+                }
+            }
+            job = job.add_sprite(format!("s{}", i), format!("s{}.png", i), image);
+        }
+
+        let output = job.run().unwrap();
+        for sprite in &output.result.packed_sprites {
+            let trim = &output.trims[&sprite.id];
+            prop_assert!(trim.trimmed_width <= trim.original_width);
+            prop_assert!(trim.trimmed_height <= trim.original_height);
+            prop_assert!(sprite.x + sprite.width <= output.result.texture_width);
+            prop_assert!(sprite.y + sprite.height <= output.result.texture_height);
+
+            // 偏移量不会把裁剪区域移出原图
+            let max_dx = (trim.original_width - trim.trimmed_width) as i32 / 2 + 1;
+            let max_dy = (trim.original_height - trim.trimmed_height) as i32 / 2 + 1;
+            prop_assert!(sprite.offset_x.abs() <= max_dx);
+            prop_assert!(sprite.offset_y.abs() <= max_dy);
+        }
+    }
+}
@@ -0,0 +1,138 @@
+/// 端到端往返测试 (Pipeline Round-trip Tests)
+///
+/// 用合成精灵走完整条导出流程：PNG 导入 → 裁剪 → 打包 → 渲染纹理 → 生成 plist，
+/// 再解析 plist 并从纹理中还原每一帧，还原结果必须与原图逐像素一致
+
+mod common;
+
+use common::synthetic::{SpriteGenerator, SyntheticSprite};
+use ezplist_core::atlas_plist::parse_atlas_plist;
+use ezplist_core::atlas_unpack::extract_frame;
+use ezplist_core::hash::{SmartUpdate, SmartUpdateMode};
+use ezplist_core::image_processor::render_texture;
+use ezplist_core::job::PackJob;
+use ezplist_core::plist_generator::{generate_plist_data, PlistFormat};
+use ezplist_core::texture_format::PixelFormat;
+use ezplist_core::trim::{TrimMode, TrimResult};
+use ezplist_core::types::PackResult;
+use image::{ImageFormat, RgbaImage};
+use proptest::prelude::*;
+use std::collections::HashMap;
+use std::io::Cursor;
+
+/// 编码为 PNG
+fn encode(image: &RgbaImage) -> Vec<u8> {
+    let mut data = Vec::new();
+    image.write_to(&mut Cursor::new(&mut data), ImageFormat::Png).unwrap();
+    data
+}
+
+/// 解码 PNG
+fn decode(data: &[u8]) -> RgbaImage {
+    image::load_from_memory_with_format(data, ImageFormat::Png).unwrap().to_rgba8()
+}
+
+/// 以 PNG 文件内容导入精灵（模拟从磁盘导入）
+fn import(sprites: &[SyntheticSprite]) -> Vec<(String, RgbaImage)> {
+    sprites.iter().map(|sprite| (sprite.name.clone(), decode(&encode(&sprite.image)))).collect()
+}
+
+/// 渲染一页并生成 plist，纹理经 PNG 编码再解码后与解析出的 plist 一起返回
+fn export_page(
+    result: &PackResult,
+    trims: &HashMap<String, TrimResult>,
+    format: PlistFormat,
+) -> (RgbaImage, ezplist_core::atlas_plist::AtlasPlist) {
+    let texture = render_texture(&result.packed_sprites, result.texture_width, result.texture_height, |sprite| {
+        trims.get(&sprite.id).map(|trim| &trim.trimmed_image)
+    })
+    .unwrap();
+    let plist = generate_plist_data(
+        &result.packed_sprites,
+        result.texture_width,
+        result.texture_height,
+        "atlas.png",
+        format,
+        PixelFormat::Rgba8888,
+        SmartUpdate::new(&texture, SmartUpdateMode::Content),
+    )
+    .unwrap();
+    (decode(&encode(&texture)), parse_atlas_plist(&plist).unwrap())
+}
+
+/// 从导出的纹理与 plist 还原所有帧并与原图比对
+fn assert_round_trip(sprites: &[SyntheticSprite], pages: &[(RgbaImage, ezplist_core::atlas_plist::AtlasPlist)]) {
+    let frame_count: usize = pages.iter().map(|(_, atlas)| atlas.frames.len()).sum();
+    assert_eq!(frame_count, sprites.len());
+    for sprite in sprites {
+        let (texture, frame) = pages
+            .iter()
+            .find_map(|(texture, atlas)| atlas.frame(&sprite.name).map(|frame| (texture, frame)))
+            .unwrap_or_else(|| panic!("plist 中缺少帧 {}", sprite.name));
+        let restored = extract_frame(texture, frame).unwrap();
+        assert_eq!(restored.dimensions(), sprite.image.dimensions(), "帧 {} 的尺寸", sprite.name);
+        assert!(restored == sprite.image, "帧 {} 的像素与原图不一致", sprite.name);
+    }
+}
+
+/// 由导入的精灵创建打包任务
+fn pack_job(imported: Vec<(String, RgbaImage)>) -> PackJob {
+    imported
+        .into_iter()
+        .enumerate()
+        .fold(PackJob::new(), |job, (index, (name, image))| job.add_sprite(index.to_string(), name, image))
+}
+
+#[test]
+fn round_trip_trimmed_and_rotated() {
+    for seed in 1..=8 {
+        let sprites = SpriteGenerator::new(seed).sprites(24, 48);
+        let output = pack_job(import(&sprites)).run().unwrap();
+        assert!(output.result.packed_sprites.iter().any(|s| s.trimmed), "种子 {} 没有裁剪帧", seed);
+        let page = export_page(&output.result, &output.trims, PlistFormat::XmlV3);
+        assert_round_trip(&sprites, &[page]);
+    }
+}
+
+#[test]
+fn round_trip_plist_formats_and_trim_modes() {
+    let sprites = SpriteGenerator::new(42).sprites(12, 32);
+    for mode in [TrimMode::CropKeepSize, TrimMode::None] {
+        let output = pack_job(import(&sprites)).trim_mode(mode).allow_rotation(false).run().unwrap();
+        for format in [PlistFormat::XmlV3, PlistFormat::XmlV2, PlistFormat::Binary] {
+            let page = export_page(&output.result, &output.trims, format);
+            assert_round_trip(&sprites, &[page]);
+        }
+    }
+}
+
+#[test]
+fn round_trip_multiple_pages() {
+    let sprites = SpriteGenerator::new(7).sprites(30, 40);
+    let output = pack_job(import(&sprites)).max_size(96).run_pages().unwrap();
+    assert!(output.pages.len() > 1);
+    let pages: Vec<_> = output.pages.iter().map(|page| export_page(page, &output.trims, PlistFormat::XmlV3)).collect();
+    assert_round_trip(&sprites, &pages);
+}
+
+#[test]
+fn generator_is_deterministic() {
+    let first = SpriteGenerator::new(3).sprites(10, 16);
+    let second = SpriteGenerator::new(3).sprites(10, 16);
+    assert_eq!(first.len(), second.len());
+    assert!(first.iter().zip(&second).all(|(a, b)| a.name == b.name && a.image == b.image));
+    assert!(first.iter().any(|s| s.name.ends_with("_copy.png")));
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn round_trip_random_sets(seed in any::<u64>(), count in 1usize..=16, padding in 0u32..=2) {
+        let sprites = SpriteGenerator::new(seed).sprites(count, 24);
+        let output = pack_job(import(&sprites)).padding(padding).run().unwrap();
+        let page = export_page(&output.result, &output.trims, PlistFormat::XmlV3);
+        assert_round_trip(&sprites, &[page]);
+    }
+}
+
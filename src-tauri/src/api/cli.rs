@@ -0,0 +1,621 @@
+/// 命令行批处理 (Headless CLI)
+///
+/// 以 `ezplist --cli <命令> ...` 启动时不打开窗口，按参数或任务文件调用与界面相同的命令，
+/// 构建服务器可以在 CI 中复现美术保存的打包设置：
+/// - `pack <图片或目录...> -o <输出目录> [-n 名称] [--config 打包配置.json] [--export 导出配置.json] [--format 格式]`
+/// - `split <图集> [--rows 行数 --cols 列数] [--prefix 前缀] [--config 切分配置.json] [-n 名称] [--format 格式]`
+/// - `unpack <图集.plist> -o <输出目录>`
+/// - `run <任务.json>`：依次执行任务文件中的任务（单个任务或数组，相对路径相对于任务文件所在目录）
+///
+/// 配置文件与前端 invoke 的参数格式相同（camelCase）。命令日志写到标准错误，
+/// 标准输出每个任务一行 JSON 结果。`--quiet` 丢弃日志只保留错误，`--json` 改为在结束时
+/// 输出一个包含每个任务结果与错误类别的 JSON 报告；失败时的退出码见 `CliErrorKind`
+
+use crate::api::dispatch;
+use crate::commands::pack::PackSessionStore;
+use crate::core::folder_scan::IMAGE_EXTENSIONS;
+use crate::core::limits::MAX_TEXT_INPUT_BYTES;
+use crate::utils::stdio::{redirect_stdout_to_null, redirect_stdout_to_stderr};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// 用法说明
+pub const USAGE: &str = "用法:
+  ezplist --cli pack <图片或目录...> -o <输出目录> [-n 名称] [--config 打包配置.json] [--export 导出配置.json] [--format 格式]
+  ezplist --cli split <图集> [--rows 行数 --cols 列数] [--prefix 前缀] [--config 切分配置.json] [-n 名称] [--format 格式]
+  ezplist --cli unpack <图集.plist> -o <输出目录>
+  ezplist --cli run <任务.json>
+
+选项: -q/--quiet 不输出日志，--json 结束时输出 JSON 报告
+格式: plist、jsonHash、jsonArray、libgdxAtlas、spineAtlas
+退出码: 0 成功，2 参数无效，3 输入无法读取，4 打包或切分失败，5 导出失败";
+
+/// 错误类别（决定进程退出码）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CliErrorKind {
+    /// 参数、配置或任务文件无效
+    Usage,
+    /// 输入图片、图集或 plist 无法读取
+    Input,
+    /// 打包、切分或拆分失败
+    Process,
+    /// 写出文件失败
+    Output,
+}
+
+impl CliErrorKind {
+    /// 进程退出码
+    pub fn exit_code(self) -> i32 {
+        match self {
+            CliErrorKind::Usage => 2,
+            CliErrorKind::Input => 3,
+            CliErrorKind::Process => 4,
+            CliErrorKind::Output => 5,
+        }
+    }
+
+    /// 把错误信息标记为该类别
+    fn error(self, message: impl Into<String>) -> CliError {
+        CliError { kind: self, message: message.into() }
+    }
+}
+
+/// 命令行错误
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CliError {
+    /// 错误类别
+    pub kind: CliErrorKind,
+    /// 错误信息
+    pub message: String,
+}
+
+impl std::fmt::Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// 输出选项（可以出现在参数中的任何位置）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CliOutput {
+    /// 丢弃命令日志，只在标准错误输出失败信息
+    pub quiet: bool,
+    /// 结束时在标准输出写出一个 JSON 报告，代替每个任务一行结果
+    pub json: bool,
+}
+
+/// 取出输出选项
+///
+/// # Returns
+/// * `(CliOutput, Vec<String>)` - 输出选项与其余参数
+pub fn split_output_flags(args: &[String]) -> (CliOutput, Vec<String>) {
+    let mut output = CliOutput::default();
+    let rest = args
+        .iter()
+        .filter(|arg| match arg.as_str() {
+            "-q" | "--quiet" => {
+                output.quiet = true;
+                false
+            }
+            "--json" => {
+                output.json = true;
+                false
+            }
+            _ => true,
+        })
+        .cloned()
+        .collect();
+    (output, rest)
+}
+
+/// 一个批处理任务（任务文件中的 `command` 字段区分类型）
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "command", rename_all = "camelCase")]
+pub enum CliTask {
+    /// 导入图片，打包并导出纹理与数据文件
+    #[serde(rename_all = "camelCase")]
+    Pack {
+        /// 图片文件或目录（目录只取第一层的图片，按文件名排序）
+        inputs: Vec<String>,
+        /// 输出目录
+        output_dir: String,
+        /// 输出文件名（不含扩展名，默认 `atlas`）
+        #[serde(default = "default_output_name")]
+        output_name: String,
+        /// 打包配置（`PackConfig`）
+        #[serde(default)]
+        config: Value,
+        /// 导出配置中的可选项（`ExportConfig`，如 format、plistFormat、zipOutput、variants）
+        #[serde(default)]
+        export: Value,
+    },
+    /// 按网格切分图集并在图集旁写出数据文件
+    #[serde(rename_all = "camelCase")]
+    Split {
+        /// 图集图片
+        sheet: String,
+        /// 数据文件名（不含扩展名，默认与图集同名）
+        #[serde(default)]
+        output_name: Option<String>,
+        /// 切分配置（`SplitConfig`，未设置的网格参数使用自动检测结果）
+        #[serde(default)]
+        config: Value,
+        /// 导出配置（`ExportSplitConfig`）
+        #[serde(default)]
+        export: Value,
+    },
+    /// 把已有图集拆分为单帧 PNG
+    #[serde(rename_all = "camelCase")]
+    Unpack {
+        /// 图集 plist
+        plist: String,
+        /// 输出目录
+        output_dir: String,
+    },
+}
+
+fn default_output_name() -> String {
+    "atlas".to_string()
+}
+
+impl CliTask {
+    /// 命令名称
+    fn command(&self) -> &'static str {
+        match self {
+            CliTask::Pack { .. } => "pack",
+            CliTask::Split { .. } => "split",
+            CliTask::Unpack { .. } => "unpack",
+        }
+    }
+
+    /// 把相对路径解析为相对于 `base` 的路径
+    fn resolve_paths(&mut self, base: &Path) {
+        let resolve = |path: &mut String| {
+            if Path::new(path.as_str()).is_relative() {
+                *path = base.join(path.as_str()).to_string_lossy().to_string();
+            }
+        };
+        match self {
+            CliTask::Pack { inputs, output_dir, .. } => {
+                inputs.iter_mut().for_each(resolve);
+                resolve(output_dir);
+            }
+            CliTask::Split { sheet, .. } => resolve(sheet),
+            CliTask::Unpack { plist, output_dir } => {
+                resolve(plist);
+                resolve(output_dir);
+            }
+        }
+    }
+}
+
+/// 运行命令行模式
+///
+/// # Arguments
+/// * `args` - `--cli` 之后的参数
+///
+/// # Returns
+/// * `Result<(), CliError>` - 参数无效或有任务失败时返回错误（类别为第一个失败任务的类别）
+pub fn run(args: &[String]) -> Result<(), CliError> {
+    let (flags, args) = split_output_flags(args);
+    if args.is_empty() || args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        eprintln!("{}", USAGE);
+        return if args.is_empty() { Err(CliErrorKind::Usage.error("缺少命令")) } else { Ok(()) };
+    }
+    let redirect = if flags.quiet { redirect_stdout_to_null() } else { redirect_stdout_to_stderr() };
+    let mut output = redirect.map_err(|e| CliErrorKind::Output.error(e))?;
+    let mut write = |line: &Value| {
+        writeln!(output, "{}", line).map_err(|e| CliErrorKind::Output.error(format!("写出结果失败: {}", e)))
+    };
+    let tasks = match parse_args(&args) {
+        Ok(tasks) => tasks,
+        Err(e) => {
+            let error = CliErrorKind::Usage.error(e);
+            if flags.json {
+                write(&report(&[], Some(&error)))?;
+            }
+            return Err(error);
+        }
+    };
+
+    let store = PackSessionStore::default();
+    let mut outcomes = Vec::with_capacity(tasks.len());
+    for (index, task) in tasks.iter().enumerate() {
+        let outcome = run_task(&store, task);
+        match &outcome {
+            Ok(result) if !flags.json => write(result)?,
+            Ok(_) => {}
+            Err(e) => eprintln!("✗ 任务 {} 失败: {}", index + 1, e),
+        }
+        outcomes.push((task.command(), outcome));
+    }
+    let failed = outcomes.iter().filter(|(_, outcome)| outcome.is_err()).count();
+    let error = outcomes
+        .iter()
+        .find_map(|(_, outcome)| outcome.as_ref().err())
+        .map(|first| first.kind.error(format!("{}/{} 个任务失败", failed, tasks.len())));
+    if flags.json {
+        write(&report(&outcomes, error.as_ref()))?;
+    }
+    match error {
+        Some(error) => Err(error),
+        None => {
+            if !flags.quiet {
+                eprintln!("✓ {} 个任务完成", tasks.len());
+            }
+            Ok(())
+        }
+    }
+}
+
+/// 生成 `--json` 报告
+///
+/// # Arguments
+/// * `outcomes` - 每个任务的命令名称与结果
+/// * `error` - 整体错误（没有时表示全部成功）
+///
+/// # Returns
+/// * `Value` - `{ ok, exitCode, error?, tasks: [{ command, ok, result | error }] }`
+fn report(outcomes: &[(&str, Result<Value, CliError>)], error: Option<&CliError>) -> Value {
+    let tasks: Vec<Value> = outcomes
+        .iter()
+        .map(|(command, outcome)| match outcome {
+            Ok(result) => json!({ "command": command, "ok": true, "result": result }),
+            Err(e) => json!({ "command": command, "ok": false, "error": e }),
+        })
+        .collect();
+    let mut report = json!({
+        "ok": error.is_none(),
+        "exitCode": error.map_or(0, |e| e.kind.exit_code()),
+        "tasks": tasks,
+    });
+    if let Some(error) = error {
+        merge(&mut report, &json!({ "error": error }));
+    }
+    report
+}
+
+/// 解析命令行参数
+///
+/// # Arguments
+/// * `args` - `--cli` 之后的参数
+///
+/// # Returns
+/// * `Result<Vec<CliTask>, String>` - 要执行的任务；参数或配置文件无效时返回错误
+pub fn parse_args(args: &[String]) -> Result<Vec<CliTask>, String> {
+    let (command, rest) = args.split_first().ok_or("缺少命令")?;
+    let mut positional = Vec::new();
+    let mut options = Map::new();
+    let mut iter = rest.iter();
+    while let Some(arg) = iter.next() {
+        let key = match arg.as_str() {
+            "-o" | "--output" => "output",
+            "-n" | "--name" => "name",
+            "--config" | "--export" | "--format" | "--rows" | "--cols" | "--prefix" => &arg[2..],
+            flag if flag.starts_with('-') => return Err(format!("未知的参数: {}", flag)),
+            _ => {
+                positional.push(arg.clone());
+                continue;
+            }
+        };
+        let value = iter.next().ok_or_else(|| format!("参数 {} 缺少值", arg))?;
+        options.insert(key.to_string(), Value::String(value.clone()));
+    }
+    let option = |key: &str| options.get(key).and_then(Value::as_str).map(str::to_string);
+    let config = option("config").map(|path| read_json(Path::new(&path))).transpose()?.unwrap_or(Value::Null);
+    let mut export = option("export").map(|path| read_json(Path::new(&path))).transpose()?.unwrap_or(Value::Null);
+    if let Some(format) = option("format") {
+        merge(&mut export, &json!({ "format": format }));
+    }
+
+    let task = match command.as_str() {
+        "pack" => {
+            if positional.is_empty() {
+                return Err("pack 需要至少一个图片或目录".to_string());
+            }
+            CliTask::Pack {
+                inputs: positional,
+                output_dir: option("output").ok_or("pack 需要 -o <输出目录>")?,
+                output_name: option("name").unwrap_or_else(default_output_name),
+                config,
+                export,
+            }
+        }
+        "split" => {
+            let [sheet] = <[String; 1]>::try_from(positional).map_err(|_| "split 需要一个图集图片".to_string())?;
+            let mut config = config;
+            for key in ["rows", "cols"] {
+                if let Some(value) = option(key) {
+                    let count: u32 = value.parse().map_err(|_| format!("--{} 必须是正整数: {}", key, value))?;
+                    merge(&mut config, &json!({ key: count }));
+                }
+            }
+            if let Some(prefix) = option("prefix") {
+                merge(&mut config, &json!({ "namePrefix": prefix }));
+            }
+            CliTask::Split { sheet, output_name: option("name"), config, export }
+        }
+        "unpack" => {
+            let [plist] = <[String; 1]>::try_from(positional).map_err(|_| "unpack 需要一个 plist 文件".to_string())?;
+            CliTask::Unpack { plist, output_dir: option("output").ok_or("unpack 需要 -o <输出目录>")? }
+        }
+        "run" => {
+            let [path] = <[String; 1]>::try_from(positional).map_err(|_| "run 需要一个任务文件".to_string())?;
+            return read_job_file(Path::new(&path));
+        }
+        _ => return Err(format!("未知的命令: {}\n{}", command, USAGE)),
+    };
+    Ok(vec![task])
+}
+
+/// 读取任务文件（单个任务对象或任务数组）
+fn read_job_file(path: &Path) -> Result<Vec<CliTask>, String> {
+    let value = read_json(path)?;
+    let mut tasks: Vec<CliTask> = match value {
+        Value::Array(_) => serde_json::from_value(value),
+        _ => serde_json::from_value(value).map(|task| vec![task]),
+    }
+    .map_err(|e| format!("任务文件 {} 无效: {}", path.display(), e))?;
+    if tasks.is_empty() {
+        return Err(format!("任务文件 {} 中没有任务", path.display()));
+    }
+    let base = path.parent().unwrap_or(Path::new("."));
+    for task in &mut tasks {
+        task.resolve_paths(base);
+    }
+    Ok(tasks)
+}
+
+/// 读取 JSON 配置文件
+fn read_json(path: &Path) -> Result<Value, String> {
+    let size = std::fs::metadata(path).map_err(|e| format!("无法读取 {}: {}", path.display(), e))?.len();
+    if size > MAX_TEXT_INPUT_BYTES {
+        return Err(format!("{} 超过 {} 字节", path.display(), MAX_TEXT_INPUT_BYTES));
+    }
+    let text = std::fs::read_to_string(path).map_err(|e| format!("无法读取 {}: {}", path.display(), e))?;
+    serde_json::from_str(&text).map_err(|e| format!("{} 不是有效的 JSON: {}", path.display(), e))
+}
+
+/// 把 `overlay` 的顶层字段写入 `base`（`base` 不是对象时替换为对象）
+fn merge(base: &mut Value, overlay: &Value) {
+    let Some(overlay) = overlay.as_object() else {
+        return;
+    };
+    if !base.is_object() {
+        *base = Value::Object(Map::new());
+    }
+    if let Some(base) = base.as_object_mut() {
+        for (key, value) in overlay {
+            base.insert(key.clone(), value.clone());
+        }
+    }
+}
+
+/// 展开输入：目录替换为其中的图片（按文件名排序）
+fn expand_inputs(inputs: &[String]) -> Result<Vec<String>, String> {
+    let mut paths = Vec::new();
+    for input in inputs {
+        let path = Path::new(input);
+        if !path.is_dir() {
+            paths.push(input.clone());
+            continue;
+        }
+        let mut images: Vec<PathBuf> = std::fs::read_dir(path)
+            .map_err(|e| format!("无法读取目录 {}: {}", input, e))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|file| {
+                file.is_file()
+                    && file
+                        .extension()
+                        .and_then(|ext| ext.to_str())
+                        .is_some_and(|ext| IMAGE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+            })
+            .collect();
+        images.sort();
+        if images.is_empty() {
+            println!("警告: 目录 {} 中没有图片", input);
+        }
+        paths.extend(images.into_iter().map(|image| image.to_string_lossy().to_string()));
+    }
+    Ok(paths)
+}
+
+/// 执行一个任务
+///
+/// # Returns
+/// * `Result<Value, CliError>` - 最后一步命令的返回值（导出、切分导出或拆分结果），
+///   错误按失败的步骤分类
+pub fn run_task(store: &PackSessionStore, task: &CliTask) -> Result<Value, CliError> {
+    let input = |e: String| CliErrorKind::Input.error(e);
+    let process = |e: String| CliErrorKind::Process.error(e);
+    let output = |e: String| CliErrorKind::Output.error(e);
+    match task {
+        CliTask::Pack { inputs, output_dir, output_name, config, export } => {
+            let paths = expand_inputs(inputs).map_err(input)?;
+            if paths.is_empty() {
+                return Err(input("没有可打包的图片".to_string()));
+            }
+            let imported = dispatch(store, "import_images", json!({ "paths": paths })).map_err(input)?;
+            for failure in imported["failed"].as_array().into_iter().flatten() {
+                println!("警告: {}", failure.as_str().unwrap_or_default());
+            }
+            if imported["sprites"].as_array().is_none_or(Vec::is_empty) {
+                return Err(input("没有成功导入的图片".to_string()));
+            }
+            let packed = dispatch(store, "pack_sprites", json!({ "sprites": imported["sprites"], "config": config }))
+                .map_err(process)?;
+
+            // 导出默认沿用打包时的挤出像素与目标格式
+            let mut export_config = json!({ "zipOutput": false });
+            if let Some(extrude) = config.get("extrude").filter(|v| !v.is_null()) {
+                merge(&mut export_config, &json!({ "extrude": extrude }));
+            }
+            if let Some(format) = config.get("targetFormat").filter(|v| !v.is_null()) {
+                merge(&mut export_config, &json!({ "format": format }));
+            }
+            merge(&mut export_config, export);
+            merge(
+                &mut export_config,
+                &json!({
+                    "packedSprites": packed["packed_sprites"],
+                    "textureWidth": packed["texture_width"],
+                    "textureHeight": packed["texture_height"],
+                    "jobId": packed["job_id"],
+                    "outputDir": output_dir,
+                    "outputName": output_name,
+                }),
+            );
+            dispatch(store, "export_sprite_sheet", json!({ "config": export_config })).map_err(output)
+        }
+        CliTask::Split { sheet, output_name, config, export } => {
+            let spritesheet = dispatch(store, "import_spritesheet", json!({ "path": sheet })).map_err(input)?;
+            let stem = Path::new(sheet)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(default_output_name);
+
+            // 未指定的网格参数使用自动检测结果
+            let mut split_config = json!({ "namePrefix": format!("{}_", stem) });
+            if let Some(detected) = spritesheet.get("autoDetect").and_then(Value::as_object) {
+                for key in ["rows", "cols", "marginX", "marginY", "spacingX", "spacingY", "offsetX", "offsetY"] {
+                    if let Some(value) = detected.get(key) {
+                        merge(&mut split_config, &json!({ key: value }));
+                    }
+                }
+            }
+            merge(&mut split_config, config);
+            if split_config.get("rows").is_none() || split_config.get("cols").is_none() {
+                return Err(CliErrorKind::Usage.error("未能自动检测帧网格，请指定 --rows 与 --cols"));
+            }
+            let split = dispatch(
+                store,
+                "calculate_split_frames",
+                json!({ "spritesheet": spritesheet, "config": split_config }),
+            )
+            .map_err(process)?;
+
+            let mut export_config = json!({ "renamePng": false });
+            merge(&mut export_config, export);
+            dispatch(
+                store,
+                "export_split_plist",
+                json!({
+                    "spritesheet": spritesheet,
+                    "frames": split["frames"],
+                    "outputName": output_name.clone().unwrap_or(stem),
+                    "config": export_config,
+                }),
+            )
+            .map_err(output)
+        }
+        CliTask::Unpack { plist, output_dir } => {
+            if !Path::new(plist).is_file() {
+                return Err(input(format!("找不到 plist 文件: {}", plist)));
+            }
+            dispatch(
+                store,
+                "unpack_atlas",
+                json!({ "config": { "plistPath": plist, "outputDir": output_dir } }),
+            )
+            .map_err(process)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let tasks = parse_args(&args(&["pack", "sprites", "extra.png", "-o", "out", "--format", "jsonHash"])).unwrap();
+        assert_eq!(
+            tasks,
+            vec![CliTask::Pack {
+                inputs: args(&["sprites", "extra.png"]),
+                output_dir: "out".to_string(),
+                output_name: "atlas".to_string(),
+                config: Value::Null,
+                export: json!({ "format": "jsonHash" }),
+            }]
+        );
+
+        let tasks = parse_args(&args(&["split", "hero.png", "--rows", "2", "--cols", "4", "-n", "hero_run"])).unwrap();
+        assert!(matches!(&tasks[0], CliTask::Split { config, output_name: Some(name), .. }
+            if config == &json!({ "rows": 2, "cols": 4 }) && name == "hero_run"));
+
+        assert!(parse_args(&args(&["pack", "sprites"])).unwrap_err().contains("-o"));
+        assert!(parse_args(&args(&["split", "a.png", "--rows", "x"])).is_err());
+        assert!(parse_args(&args(&["unpack", "a.plist", "-o"])).unwrap_err().contains("缺少值"));
+        assert!(parse_args(&args(&["pack", "a.png", "--zip"])).unwrap_err().contains("未知的参数"));
+        assert!(parse_args(&args(&["deploy"])).unwrap_err().contains("未知的命令"));
+    }
+
+    #[test]
+    fn test_output_flags_and_error_kinds() {
+        let (flags, rest) = split_output_flags(&args(&["--json", "unpack", "a.plist", "-q", "-o", "out"]));
+        assert_eq!(flags, CliOutput { quiet: true, json: true });
+        assert_eq!(rest, args(&["unpack", "a.plist", "-o", "out"]));
+
+        let store = PackSessionStore::default();
+        let missing = CliTask::Unpack { plist: "/nonexistent/a.plist".to_string(), output_dir: "out".to_string() };
+        let error = run_task(&store, &missing).unwrap_err();
+        assert_eq!((error.kind, error.kind.exit_code()), (CliErrorKind::Input, 3));
+
+        let report = report(&[("unpack", Err(error.clone()))], Some(&CliErrorKind::Input.error("1/1 个任务失败")));
+        assert_eq!(report["ok"], false);
+        assert_eq!(report["exitCode"], 3);
+        assert_eq!(report["tasks"][0]["error"]["kind"], "input");
+        assert_eq!(report["tasks"][0]["error"]["message"], error.message.as_str());
+    }
+
+    #[test]
+    fn test_job_file_and_pack() {
+        let dir = std::env::temp_dir().join(format!("ezplist_cli_test_{}", std::process::id()));
+        let sprites = dir.join("sprites");
+        std::fs::create_dir_all(&sprites).unwrap();
+        RgbaImage::from_pixel(8, 6, Rgba([255, 0, 0, 255])).save(sprites.join("b.png")).unwrap();
+        RgbaImage::from_pixel(4, 4, Rgba([0, 255, 0, 255])).save(sprites.join("a.png")).unwrap();
+        std::fs::write(sprites.join("notes.txt"), "not an image").unwrap();
+        let job = dir.join("job.json");
+        std::fs::write(
+            &job,
+            r#"[{ "command": "pack", "inputs": ["sprites"], "outputDir": "out", "outputName": "ui",
+                  "config": { "padding": 0 }, "export": { "format": "jsonArray" } },
+                { "command": "unpack", "plist": "ui.plist", "outputDir": "frames" }]"#,
+        )
+        .unwrap();
+
+        let tasks = parse_args(&args(&["run", &job.to_string_lossy()])).unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert!(matches!(&tasks[1], CliTask::Unpack { plist, .. } if Path::new(plist) == dir.join("ui.plist")));
+        // 目录展开为其中的图片，按文件名排序
+        let CliTask::Pack { inputs, .. } = &tasks[0] else { panic!("应为打包任务") };
+        let expanded = expand_inputs(inputs).unwrap();
+        assert_eq!(expanded, [sprites.join("a.png"), sprites.join("b.png")].iter().map(|p| p.to_string_lossy().to_string()).collect::<Vec<_>>());
+
+        let result = run_task(&PackSessionStore::default(), &tasks[0]).unwrap();
+        assert!(result["outputPath"].as_str().unwrap().ends_with("ui.json"));
+        assert!(dir.join("out").join("ui.png").exists());
+
+        // 放不下时归为打包失败
+        let CliTask::Pack { inputs, output_dir, .. } = tasks[0].clone() else { unreachable!() };
+        let tiny = CliTask::Pack {
+            inputs,
+            output_dir,
+            output_name: "tiny".to_string(),
+            config: json!({ "maxWidth": 2, "maxHeight": 2, "autoSize": false }),
+            export: Value::Null,
+        };
+        assert_eq!(run_task(&PackSessionStore::default(), &tiny).unwrap_err().kind, CliErrorKind::Process);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,401 @@
+/// 本地 HTTP 服务 (Local HTTP API)
+///
+/// 默认关闭，通过 `start_http_api` 命令开启。只监听 127.0.0.1，
+/// 每次启动生成随机令牌，请求须携带 `Authorization: Bearer <令牌>` 与
+/// `Content-Type: application/json`（浏览器跨域请求无法在不预检的情况下携带这两个头，
+/// 服务不响应预检请求，网页无法借用户的浏览器调用本地接口）。
+///
+/// 请求：`POST /api/<方法名>`，请求体为参数对象；`GET /api` 列出支持的方法。
+/// 响应：成功为 `{"ok":true,"result":...}`，失败为 `{"ok":false,"error":"..."}`
+
+use crate::api::{dispatch, METHODS};
+use crate::commands::pack::PackSessionStore;
+use crate::core::limits::MAX_TEXT_INPUT_BYTES;
+use crate::utils::job_lock::BUSY_PREFIX;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// 默认端口
+pub const DEFAULT_PORT: u16 = 47821;
+
+/// 请求头的最大总长度
+const MAX_HEADER_BYTES: usize = 16 * 1024;
+
+/// 读取请求的超时
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 检查停止标志的间隔
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// 同时处理的最大连接数（超出时直接返回 503）
+const MAX_CONNECTIONS: usize = 16;
+
+/// 服务信息（返回给前端，用于配置编辑器插件）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpApiInfo {
+    /// 实际监听的端口
+    pub port: u16,
+    /// 访问令牌
+    pub token: String,
+}
+
+struct RunningServer {
+    info: HttpApiInfo,
+    stop: Arc<AtomicBool>,
+    thread: JoinHandle<()>,
+}
+
+/// 当前运行的服务
+static SERVER: LazyLock<Mutex<Option<RunningServer>>> = LazyLock::new(|| Mutex::new(None));
+
+/// 启动服务
+///
+/// # Arguments
+/// * `port` - 端口，0 表示由系统分配
+/// * `store` - 打包结果存储
+///
+/// # Returns
+/// * `Result<HttpApiInfo, String>` - 端口与令牌；服务已在运行时返回其信息
+pub fn start(port: u16, store: PackSessionStore) -> Result<HttpApiInfo, String> {
+    let mut server = SERVER.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(running) = server.as_ref() {
+        return Ok(running.info.clone());
+    }
+
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+        .map_err(|e| format!("无法监听端口 {}: {}", port, e))?;
+    listener
+        .set_nonblocking(true)
+        .map_err(|e| format!("无法设置监听模式: {}", e))?;
+    let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+
+    let info = HttpApiInfo {
+        port,
+        token: uuid::Uuid::new_v4().simple().to_string(),
+    };
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread = {
+        let stop = stop.clone();
+        let token = info.token.clone();
+        std::thread::spawn(move || accept_loop(listener, &token, &stop, &store))
+    };
+
+    println!("✓ HTTP 接口已启动: http://127.0.0.1:{}/api", port);
+    *server = Some(RunningServer {
+        info: info.clone(),
+        stop,
+        thread,
+    });
+    Ok(info)
+}
+
+/// 停止服务
+///
+/// # Returns
+/// * `bool` - 服务之前是否在运行
+pub fn stop() -> bool {
+    let running = SERVER.lock().unwrap_or_else(|e| e.into_inner()).take();
+    match running {
+        Some(running) => {
+            running.stop.store(true, Ordering::SeqCst);
+            let _ = running.thread.join();
+            println!("✓ HTTP 接口已停止");
+            true
+        }
+        None => false,
+    }
+}
+
+/// 当前服务信息
+pub fn info() -> Option<HttpApiInfo> {
+    SERVER
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .map(|running| running.info.clone())
+}
+
+/// 连接计数，处理线程结束时释放
+struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl ConnectionSlot {
+    /// 占用一个连接名额，已达上限时返回 None
+    fn acquire(active: &Arc<AtomicUsize>) -> Option<Self> {
+        active
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| (count < MAX_CONNECTIONS).then_some(count + 1))
+            .ok()
+            .map(|_| Self(active.clone()))
+    }
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+fn accept_loop(listener: TcpListener, token: &str, stop: &AtomicBool, store: &PackSessionStore) {
+    let active = Arc::new(AtomicUsize::new(0));
+    while !stop.load(Ordering::SeqCst) {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                let Some(slot) = ConnectionSlot::acquire(&active) else {
+                    if let Err(e) = reject_connection(stream) {
+                        println!("警告: HTTP 请求处理失败: {}", e);
+                    }
+                    continue;
+                };
+                let token = token.to_string();
+                let store = store.clone();
+                std::thread::spawn(move || {
+                    let _slot = slot;
+                    if let Err(e) = handle_connection(stream, &token, &store) {
+                        println!("警告: HTTP 请求处理失败: {}", e);
+                    }
+                });
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => std::thread::sleep(ACCEPT_POLL_INTERVAL),
+            Err(e) => println!("警告: HTTP 接口接受连接失败: {}", e),
+        }
+    }
+}
+
+/// 解析后的请求
+struct Request {
+    method: String,
+    path: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// 连接数已达上限时直接返回 503，不读取请求
+fn reject_connection(stream: TcpStream) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_write_timeout(Some(READ_TIMEOUT))?;
+    write_response(stream, 503, &json!({ "ok": false, "error": "同时连接数过多，请稍后重试" }))
+}
+
+fn handle_connection(stream: TcpStream, token: &str, store: &PackSessionStore) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let (status, body) = match read_request(&mut reader) {
+        Ok(request) => route(&request, token, store),
+        Err((status, message)) => (status, json!({ "ok": false, "error": message })),
+    };
+    write_response(stream, status, &body)
+}
+
+fn read_request(reader: &mut impl BufRead) -> Result<Request, (u16, String)> {
+    let bad_request = |message: &str| (400, message.to_string());
+
+    let mut header_bytes = 0;
+    let mut read_line = |reader: &mut dyn BufRead| -> Result<String, (u16, String)> {
+        let mut line = String::new();
+        let len = Read::take(&mut *reader, (MAX_HEADER_BYTES - header_bytes) as u64)
+            .read_line(&mut line)
+            .map_err(|_| bad_request("无法读取请求"))?;
+        header_bytes += len;
+        if header_bytes >= MAX_HEADER_BYTES {
+            return Err((431, "请求头过大".to_string()));
+        }
+        Ok(line.trim_end_matches(['\r', '\n']).to_string())
+    };
+
+    let request_line = read_line(reader)?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Err(bad_request("请求行无效"));
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+
+    let mut headers = Vec::new();
+    loop {
+        let line = read_line(reader)?;
+        if line.is_empty() {
+            break;
+        }
+        let (key, value) = line.split_once(':').ok_or_else(|| bad_request("请求头无效"))?;
+        headers.push((key.trim().to_string(), value.trim().to_string()));
+    }
+
+    let mut request = Request {
+        method,
+        path,
+        headers,
+        body: Vec::new(),
+    };
+    let length: u64 = match request.header("Content-Length") {
+        Some(value) => value.parse().map_err(|_| bad_request("Content-Length 无效"))?,
+        None => 0,
+    };
+    if length > MAX_TEXT_INPUT_BYTES {
+        return Err((413, "请求体过大".to_string()));
+    }
+    reader
+        .take(length)
+        .read_to_end(&mut request.body)
+        .map_err(|_| bad_request("无法读取请求体"))?;
+    if request.body.len() as u64 != length {
+        return Err(bad_request("请求体不完整"));
+    }
+    Ok(request)
+}
+
+fn route(request: &Request, token: &str, store: &PackSessionStore) -> (u16, Value) {
+    let error = |status: u16, message: &str| (status, json!({ "ok": false, "error": message }));
+
+    let authorized = request
+        .header("Authorization")
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|value| token_matches(value, token));
+    if !authorized {
+        return error(401, "缺少或错误的访问令牌");
+    }
+
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/api") => (200, json!({ "ok": true, "result": METHODS })),
+        ("POST", path) => {
+            let Some(method) = path.strip_prefix("/api/") else {
+                return error(404, "未知的接口");
+            };
+            let is_json = request
+                .header("Content-Type")
+                .is_some_and(|value| value.split(';').next().is_some_and(|v| v.trim() == "application/json"));
+            if !is_json {
+                return error(415, "请求体必须为 application/json");
+            }
+            let params: Value = if request.body.is_empty() {
+                Value::Null
+            } else {
+                match serde_json::from_slice(&request.body) {
+                    Ok(params) => params,
+                    Err(e) => return error(400, &format!("JSON 无效: {}", e)),
+                }
+            };
+            if !METHODS.contains(&method) {
+                return error(404, &format!("未知的方法: {}", method));
+            }
+            match dispatch(store, method, params) {
+                Ok(result) => (200, json!({ "ok": true, "result": result })),
+                Err(e) if e.starts_with(BUSY_PREFIX) => error(409, &e),
+                Err(e) => error(400, &e),
+            }
+        }
+        _ => error(405, "不支持的请求方法"),
+    }
+}
+
+/// 按常数时间比较令牌（长度不同时直接返回 false，令牌长度固定，不泄露内容）
+fn token_matches(value: &str, token: &str) -> bool {
+    let (value, token) = (value.as_bytes(), token.as_bytes());
+    value.len() == token.len() && value.iter().zip(token).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+fn write_response(mut stream: TcpStream, status: u16, body: &Value) -> std::io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        415 => "Unsupported Media Type",
+        431 => "Request Header Fields Too Large",
+        503 => "Service Unavailable",
+        _ => "Error",
+    };
+    let body = body.to_string();
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn send(port: u16, request: &str) -> String {
+        let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port)).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn test_token_matches() {
+        assert!(token_matches("abc123", "abc123"));
+        assert!(!token_matches("abc124", "abc123"));
+        assert!(!token_matches("abc12", "abc123"));
+        assert!(!token_matches("", "abc123"));
+    }
+
+    #[test]
+    fn test_connection_slots() {
+        let active = Arc::new(AtomicUsize::new(0));
+        let slots: Vec<ConnectionSlot> = (0..MAX_CONNECTIONS).filter_map(|_| ConnectionSlot::acquire(&active)).collect();
+        assert_eq!(slots.len(), MAX_CONNECTIONS);
+        assert!(ConnectionSlot::acquire(&active).is_none());
+        drop(slots);
+        assert_eq!(active.load(Ordering::SeqCst), 0);
+        assert!(ConnectionSlot::acquire(&active).is_some());
+    }
+
+    #[test]
+    fn test_http_api() {
+        let info = start(0, PackSessionStore::default()).unwrap();
+        assert_eq!(start(0, PackSessionStore::default()).unwrap(), info);
+
+        let list = format!("GET /api HTTP/1.1\r\nAuthorization: Bearer {}\r\n\r\n", info.token);
+        let response = send(info.port, &list);
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert!(response.contains("pack_sprites"));
+
+        assert!(send(info.port, "GET /api HTTP/1.1\r\n\r\n").starts_with("HTTP/1.1 401"));
+
+        let body = r#"{"spritesheet":{"path":"a.png","name":"a.png","width":32,"height":16},"config":{"rows":1,"cols":2,"namePrefix":"f_"}}"#;
+        let split = format!(
+            "POST /api/calculate_split_frames HTTP/1.1\r\nAuthorization: Bearer {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            info.token,
+            body.len(),
+            body
+        );
+        let response = send(info.port, &split);
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        let (_, body) = response.split_once("\r\n\r\n").unwrap();
+        let body: Value = serde_json::from_str(body).unwrap();
+        assert_eq!(body["result"]["frames"].as_array().unwrap().len(), 2);
+
+        // 非 JSON 请求体被拒绝
+        let plain = split.replace("application/json", "text/plain");
+        assert!(send(info.port, &plain).starts_with("HTTP/1.1 415"));
+
+        assert!(stop());
+        assert!(!stop());
+        assert!(super::info().is_none());
+    }
+}
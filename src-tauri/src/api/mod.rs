@@ -0,0 +1,208 @@
+/// 自动化接口 (Automation API)
+///
+/// 把导入、打包、切分、导出命令按名称暴露给 Tauri 之外的调用方（本地 HTTP 服务、JSON-RPC），
+/// 方法名与 Tauri 命令同名，参数与返回值的 JSON 格式与前端 invoke 相同
+/// - http: 本地 HTTP 服务
+/// - rpc: 标准输入输出上的 JSON-RPC
+/// - cli: 命令行批处理（打包、切分、拆分图集）
+
+pub mod cli;
+pub mod http;
+pub mod rpc;
+
+use crate::commands::export::export_sprite_sheet_in;
+use crate::commands::pack::{pack_sprites_in, PackSessionStore};
+use crate::commands::unpack::repack_atlas_in;
+use crate::commands::{
+    self, ComposeArrangeOptions, ComposeConfig, ComposeSpritePosition, ExportSplitConfig, ImportOptions, PackConfig,
+    RepackAtlasConfig, UnpackAtlasConfig,
+};
+use crate::core::folder_scan::FolderImportOptions;
+use crate::core::types::{ExportConfig, FrameInfo, SplitConfig, SpriteData, SpritesheetInfo};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::future::Future;
+
+/// 支持的方法
+pub const METHODS: &[&str] = &[
+    "import_images",
+    "import_folder",
+    "pack_sprites",
+    "export_sprite_sheet",
+    "import_spritesheet",
+    "calculate_split_frames",
+    "export_split_plist",
+    "compose_sprites",
+    "compose_auto_arrange",
+    "unpack_atlas",
+    "repack_atlas",
+];
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ImportParams {
+    paths: Vec<String>,
+    #[serde(default)]
+    options: Option<ImportOptions>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FolderParams {
+    dir: String,
+    #[serde(default)]
+    folder: Option<FolderImportOptions>,
+    #[serde(default)]
+    options: Option<ImportOptions>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PackParams {
+    sprites: Vec<SpriteData>,
+    #[serde(default)]
+    config: Option<PackConfig>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportParams {
+    config: ExportConfig,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PathParams {
+    path: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SplitParams {
+    spritesheet: SpritesheetInfo,
+    config: SplitConfig,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportSplitParams {
+    spritesheet: SpritesheetInfo,
+    frames: Vec<FrameInfo>,
+    output_name: String,
+    #[serde(default)]
+    config: Option<ExportSplitConfig>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UnpackParams {
+    config: UnpackAtlasConfig,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RepackParams {
+    config: RepackAtlasConfig,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ComposeParams {
+    sprites: Vec<ComposeSpritePosition>,
+    config: ComposeConfig,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ArrangeParams {
+    sprites: Vec<ComposeSpritePosition>,
+    #[serde(default)]
+    options: Option<ComposeArrangeOptions>,
+}
+
+/// 按名称调用命令
+///
+/// # Arguments
+/// * `store` - 打包结果存储（`pack_sprites` 写入、`export_sprite_sheet` 按 `jobId` 读取）
+/// * `method` - 方法名（见 `METHODS`）
+/// * `params` - 参数对象（字段名与前端 invoke 的参数相同）
+///
+/// # Returns
+/// * `Result<Value, String>` - 命令返回值或错误信息
+pub fn dispatch(store: &PackSessionStore, method: &str, params: Value) -> Result<Value, String> {
+    match method {
+        "import_images" => call(params, |p: ImportParams| commands::import_images(p.paths, p.options)),
+        "import_folder" => call(params, |p: FolderParams| commands::import_folder(p.dir, p.folder, p.options)),
+        "pack_sprites" => call(params, |p: PackParams| pack_sprites_in(store.clone(), p.sprites, p.config)),
+        "export_sprite_sheet" => call(params, |p: ExportParams| export_sprite_sheet_in(store.clone(), p.config)),
+        "import_spritesheet" => call(params, |p: PathParams| commands::import_spritesheet(p.path)),
+        "calculate_split_frames" => {
+            call(params, |p: SplitParams| commands::calculate_split_frames(p.spritesheet, p.config))
+        }
+        "export_split_plist" => call(params, |p: ExportSplitParams| {
+            commands::export_split_plist(p.spritesheet, p.frames, p.output_name, p.config)
+        }),
+        "compose_sprites" => call(params, |p: ComposeParams| commands::compose_sprites(p.sprites, p.config)),
+        "compose_auto_arrange" => {
+            call(params, |p: ArrangeParams| commands::compose_auto_arrange(p.sprites, p.options))
+        }
+        "unpack_atlas" => call(params, |p: UnpackParams| commands::unpack_atlas(p.config)),
+        "repack_atlas" => call(params, |p: RepackParams| repack_atlas_in(store.clone(), p.config)),
+        _ => Err(format!("未知的方法: {}", method)),
+    }
+}
+
+/// 解析参数、同步执行命令并序列化结果
+fn call<P, T, F>(params: Value, command: impl FnOnce(P) -> F) -> Result<Value, String>
+where
+    P: DeserializeOwned,
+    T: Serialize,
+    F: Future<Output = Result<T, String>>,
+{
+    // 没有参数的请求按空对象处理
+    let params = if params.is_null() { Value::Object(Default::default()) } else { params };
+    let params: P = serde_json::from_value(params).map_err(|e| format!("参数无效: {}", e))?;
+    let result = tauri::async_runtime::block_on(command(params))?;
+    serde_json::to_value(result).map_err(|e| format!("序列化结果失败: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_dispatch() {
+        let store = PackSessionStore::default();
+        let result = dispatch(
+            &store,
+            "calculate_split_frames",
+            json!({
+                "spritesheet": { "path": "sheet.png", "name": "sheet.png", "width": 64, "height": 32 },
+                "config": { "rows": 1, "cols": 2, "namePrefix": "frame_" }
+            }),
+        )
+        .unwrap();
+        assert_eq!(result["frames"].as_array().unwrap().len(), 2);
+
+        let arranged = dispatch(
+            &store,
+            "compose_auto_arrange",
+            json!({
+                "sprites": [
+                    { "id": "a", "name": "a.png", "path": "a.png", "width": 30, "height": 10, "x": 500, "y": 500 },
+                    { "id": "a", "name": "a.png", "path": "a.png", "width": 30, "height": 10, "x": 0, "y": 0, "rotation": 90 }
+                ],
+                "options": { "padding": 0 }
+            }),
+        )
+        .unwrap();
+        let sprites = arranged["sprites"].as_array().unwrap();
+        assert_eq!(sprites[1]["rotation"], 90);
+        assert!(sprites[0]["x"].as_i64().unwrap() < 30 && arranged["width"].as_u64().unwrap() <= 40);
+
+        assert!(dispatch(&store, "pack_sprites", json!({ "sprites": "oops" })).unwrap_err().starts_with("参数无效"));
+        assert!(dispatch(&store, "rm_rf", Value::Null).is_err());
+    }
+}
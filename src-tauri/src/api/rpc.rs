@@ -0,0 +1,132 @@
+/// JSON-RPC 自动化接口 (JSON-RPC over stdio)
+///
+/// 以 `ezplist --rpc` 启动时不打开窗口，从标准输入逐行读取 JSON-RPC 2.0 请求，
+/// 每个响应占标准输出的一行，构建脚本与自动化工具可以不经界面完成导入→打包→导出。
+/// 方法与 HTTP 接口相同（见 `METHODS`），另有 `list_methods` 列出所有方法。
+/// 命令日志改写到标准错误，标准输出只包含响应
+///
+/// 错误码：-32700 JSON 无效、-32600 请求无效、-32601 方法不存在、-32602 参数无效、
+/// -32000 命令执行失败、-32001 资源忙碌（稍后重试）
+
+use crate::api::{dispatch, METHODS};
+use crate::commands::pack::PackSessionStore;
+use crate::utils::job_lock::BUSY_PREFIX;
+use crate::utils::stdio::redirect_stdout_to_stderr;
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+
+/// 在标准输入/输出上运行 JSON-RPC 服务，直到输入结束
+pub fn run_stdio() -> Result<(), String> {
+    let output = redirect_stdout_to_stderr()?;
+    eprintln!("EzPlist JSON-RPC 已启动，等待标准输入中的请求");
+    serve(std::io::stdin().lock(), output, &PackSessionStore::default()).map_err(|e| format!("JSON-RPC 读写失败: {}", e))
+}
+
+/// 处理输入中的所有请求
+///
+/// # Arguments
+/// * `input` - 每行一个 JSON-RPC 请求
+/// * `output` - 每行写入一个响应（通知请求没有响应）
+/// * `store` - 打包结果存储（同一输入中的打包与导出共用）
+pub fn serve(input: impl BufRead, mut output: impl Write, store: &PackSessionStore) -> std::io::Result<()> {
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = handle_line(&line, store) {
+            writeln!(output, "{}", response)?;
+            output.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// 处理单行请求，返回要写出的响应
+fn handle_line(line: &str, store: &PackSessionStore) -> Option<Value> {
+    let request: Value = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(e) => return Some(error_response(Value::Null, -32700, &format!("JSON 无效: {}", e))),
+    };
+    let Some(object) = request.as_object() else {
+        return Some(error_response(Value::Null, -32600, "请求必须是 JSON 对象（不支持批量请求）"));
+    };
+
+    // 没有 id 的请求是通知，不返回响应
+    let id = object.get("id").cloned();
+    let reply = |response: Value| id.as_ref().map(|_| response);
+    let id_value = id.clone().unwrap_or(Value::Null);
+
+    if object.get("jsonrpc").and_then(Value::as_str) != Some("2.0") {
+        return reply(error_response(id_value, -32600, "jsonrpc 必须为 \"2.0\""));
+    }
+    let Some(method) = object.get("method").and_then(Value::as_str) else {
+        return reply(error_response(id_value, -32600, "缺少 method"));
+    };
+    let params = object.get("params").cloned().unwrap_or(Value::Null);
+
+    let result = match method {
+        "list_methods" => Ok(json!(METHODS)),
+        method if METHODS.contains(&method) => dispatch(store, method, params),
+        _ => return reply(error_response(id_value, -32601, &format!("方法不存在: {}", method))),
+    };
+    reply(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id_value, "result": result }),
+        Err(e) if e.starts_with("参数无效") => error_response(id_value, -32602, &e),
+        Err(e) if e.starts_with(BUSY_PREFIX) => error_response(id_value, -32001, &e),
+        Err(e) => error_response(id_value, -32000, &e),
+    })
+}
+
+fn error_response(id: Value, code: i32, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(input: &str) -> Vec<Value> {
+        let mut output = Vec::new();
+        serve(input.as_bytes(), &mut output, &PackSessionStore::default()).unwrap();
+        String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_requests() {
+        let input = [
+            r#"{"jsonrpc":"2.0","id":1,"method":"list_methods"}"#,
+            r#"{"jsonrpc":"2.0","id":2,"method":"calculate_split_frames","params":{"spritesheet":{"path":"a.png","name":"a.png","width":32,"height":32},"config":{"rows":2,"cols":2,"namePrefix":"f_"}}}"#,
+            r#"{"jsonrpc":"2.0","method":"list_methods"}"#,
+            "",
+        ]
+        .join("\n");
+        let responses = run(&input);
+
+        assert_eq!(responses.len(), 2);
+        assert!(responses[0]["result"].as_array().unwrap().contains(&json!("pack_sprites")));
+        assert_eq!(responses[1]["id"], 2);
+        assert_eq!(responses[1]["result"]["frames"].as_array().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_errors() {
+        let input = [
+            "not json",
+            r#"[1, 2]"#,
+            r#"{"jsonrpc":"2.0","id":"a","method":"format_disk"}"#,
+            r#"{"jsonrpc":"2.0","id":"b","method":"pack_sprites","params":{"sprites":5}}"#,
+            r#"{"id":"c","method":"list_methods"}"#,
+        ]
+        .join("\n");
+        let codes: Vec<i64> = run(&input)
+            .iter()
+            .map(|response| response["error"]["code"].as_i64().unwrap())
+            .collect();
+        assert_eq!(codes, vec![-32700, -32600, -32601, -32602, -32600]);
+    }
+}
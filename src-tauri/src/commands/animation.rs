@@ -0,0 +1,230 @@
+/// 动画预览命令 (Animation Preview Commands)
+///
+/// 为前端播放器预先裁剪好每一帧，前端只需按时长切换图片；
+/// 生成洋葱皮叠加预览，用于检查动作轨迹；
+/// 以及把帧序列导出为 GIF / APNG 动图
+
+use crate::core::animation::{
+    compose_onion_skin, frame_durations_ms, pad_frames, preview_file_name, AnimationExportOptions, OnionSkinOptions,
+};
+use crate::core::limits::check_texture_size;
+use crate::core::types::{AnimationPreview, AnimationPreviewExport, FrameInfo, PreviewFrame, SpritesheetInfo};
+use crate::utils::animation_writer::encode_animation;
+use crate::utils::guard::run_blocking;
+use crate::utils::image_loader::{crop_frame, load_image, ImageLoadLimits};
+use base64::Engine;
+use image::DynamicImage;
+use std::path::{Path, PathBuf};
+
+/// 预览帧临时目录名称
+const PREVIEW_DIR_NAME: &str = "ezplist_preview";
+
+/// 洋葱皮预览临时目录名称
+const ONION_SKIN_DIR_NAME: &str = "ezplist_onion_skin";
+
+/// 生成动画预览数据
+///
+/// 帧图像保存为临时 PNG 文件，前端通过 `getAssetUrl` 加载。
+/// 每次生成会清理之前的预览文件
+///
+/// # Arguments
+/// * `spritesheet` - 图集信息
+/// * `frames` - 按播放顺序排列的帧
+/// * `fps` - 播放帧率
+///
+/// # Returns
+/// * `Result<AnimationPreview, String>` - 预览帧与时长，或错误信息
+#[tauri::command]
+pub async fn build_animation_preview(
+    spritesheet: SpritesheetInfo,
+    frames: Vec<FrameInfo>,
+    fps: f32,
+) -> Result<AnimationPreview, String> {
+    run_blocking("build_animation_preview", move || build_animation_preview_impl(spritesheet, frames, fps)).await
+}
+
+fn build_animation_preview_impl(
+    spritesheet: SpritesheetInfo,
+    frames: Vec<FrameInfo>,
+    fps: f32,
+) -> Result<AnimationPreview, String> {
+    if frames.is_empty() {
+        return Err("没有帧可预览".to_string());
+    }
+    let durations = frame_durations_ms(frames.len(), fps)?;
+
+    let source_img = load_image(&spritesheet.path, &ImageLoadLimits::default())?;
+
+    let preview_dir = fresh_temp_dir(PREVIEW_DIR_NAME)?;
+
+    let mut preview_frames = Vec::with_capacity(frames.len());
+    for (index, (frame, duration_ms)) in frames.iter().zip(durations).enumerate() {
+        let path = save_preview_frame(&source_img, frame, index, &preview_dir)?;
+        preview_frames.push(PreviewFrame {
+            name: frame.name.clone(),
+            path: path.to_string_lossy().to_string(),
+            width: frame.width,
+            height: frame.height,
+            duration_ms,
+        });
+    }
+
+    let total_duration_ms = preview_frames.iter().map(|f| f.duration_ms).sum();
+    println!("✓ 动画预览生成完成: {} 帧, {} fps", preview_frames.len(), fps);
+
+    Ok(AnimationPreview {
+        frames: preview_frames,
+        fps,
+        total_duration_ms,
+    })
+}
+
+/// 生成洋葱皮预览图
+///
+/// # Arguments
+/// * `spritesheet` - 图集信息
+/// * `frames` - 连续的帧，最后一帧为当前帧，之前的帧以递减的不透明度叠加
+/// * `options` - 合成选项（可选，默认不透明度 0.5、不着色）
+///
+/// # Returns
+/// * `Result<String, String>` - 预览图临时文件路径或错误信息
+#[tauri::command]
+pub async fn render_onion_skin(
+    spritesheet: SpritesheetInfo,
+    frames: Vec<FrameInfo>,
+    options: Option<OnionSkinOptions>,
+) -> Result<String, String> {
+    run_blocking("render_onion_skin", move || render_onion_skin_impl(spritesheet, frames, options)).await
+}
+
+fn render_onion_skin_impl(
+    spritesheet: SpritesheetInfo,
+    frames: Vec<FrameInfo>,
+    options: Option<OnionSkinOptions>,
+) -> Result<String, String> {
+    if frames.is_empty() {
+        return Err("没有帧可预览".to_string());
+    }
+
+    let source_img = load_image(&spritesheet.path, &ImageLoadLimits::default())?;
+    let frame_images = frames
+        .iter()
+        .map(|frame| crop_frame(&source_img, frame))
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let composite = compose_onion_skin(&frame_images, &options.unwrap_or_default())
+        .ok_or("没有有效帧可合成")?;
+
+    let path = fresh_temp_dir(ONION_SKIN_DIR_NAME)?.join("onion_skin.png");
+    composite
+        .save(&path)
+        .map_err(|e| format!("保存洋葱皮预览失败: {}", e))?;
+
+    println!("✓ 洋葱皮预览生成完成: {} 帧", frames.len());
+
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// 导出动图预览
+///
+/// 按帧率计算各帧时长，把帧居中放入统一尺寸的画布后编码为循环播放的 GIF 或 APNG。
+/// 指定 Plist 路径时写到 Plist 旁（如 `hero_preview.gif`），否则以 base64 data URL 返回供应用内预览
+///
+/// # Arguments
+/// * `spritesheet` - 图集信息
+/// * `frames` - 按播放顺序排列的帧
+/// * `fps` - 播放帧率
+/// * `options` - 导出选项（可选，默认 GIF 并返回 data URL）
+///
+/// # Returns
+/// * `Result<AnimationPreviewExport, String>` - 文件路径或 data URL，以及画布尺寸与时长
+#[tauri::command]
+pub async fn export_animation_preview(
+    spritesheet: SpritesheetInfo,
+    frames: Vec<FrameInfo>,
+    fps: f32,
+    options: Option<AnimationExportOptions>,
+) -> Result<AnimationPreviewExport, String> {
+    run_blocking("export_animation_preview", move || {
+        export_animation_preview_impl(spritesheet, frames, fps, options.unwrap_or_default())
+    })
+    .await
+}
+
+fn export_animation_preview_impl(
+    spritesheet: SpritesheetInfo,
+    frames: Vec<FrameInfo>,
+    fps: f32,
+    options: AnimationExportOptions,
+) -> Result<AnimationPreviewExport, String> {
+    if frames.is_empty() {
+        return Err("没有帧可预览".to_string());
+    }
+    let durations = frame_durations_ms(frames.len(), fps)?;
+
+    let source_img = load_image(&spritesheet.path, &ImageLoadLimits::default())?;
+    let frame_images = frames
+        .iter()
+        .map(|frame| crop_frame(&source_img, frame))
+        .collect::<Result<Vec<_>, String>>()?;
+    let canvas_frames = pad_frames(&frame_images).ok_or("没有有效帧可合成")?;
+    let (width, height) = canvas_frames[0].dimensions();
+    check_texture_size(width, height)?;
+
+    let format = options.format;
+    let data = encode_animation(&canvas_frames, &durations, format)?;
+
+    let (path, data_url) = match &options.plist_path {
+        Some(plist_path) => {
+            let plist_path = Path::new(plist_path);
+            let stem = plist_path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .ok_or_else(|| format!("Plist 路径无效: {}", plist_path.display()))?;
+            let path = plist_path.with_file_name(preview_file_name(stem, format));
+            std::fs::write(&path, &data).map_err(|e| format!("写入动图失败 {}: {}", path.display(), e))?;
+            (Some(path.to_string_lossy().to_string()), None)
+        }
+        None => {
+            let encoded = base64::engine::general_purpose::STANDARD.encode(&data);
+            (None, Some(format!("data:{};base64,{}", format.mime_type(), encoded)))
+        }
+    };
+
+    let total_duration_ms = durations.iter().sum();
+    println!("✓ 动图预览导出完成: {} 帧, {} fps, {}x{}", frames.len(), fps, width, height);
+
+    Ok(AnimationPreviewExport {
+        format,
+        path,
+        data_url,
+        frame_count: frames.len(),
+        width,
+        height,
+        total_duration_ms,
+    })
+}
+
+/// 创建新的临时预览目录，并清理同类的旧预览文件（清理失败不影响本次生成）
+pub(crate) fn fresh_temp_dir(name: &str) -> Result<PathBuf, String> {
+    let base_dir = std::env::temp_dir().join(name);
+    let _ = std::fs::remove_dir_all(&base_dir);
+    let dir = base_dir.join(uuid::Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("无法创建预览目录: {}", e))?;
+    Ok(dir)
+}
+
+/// 裁剪单帧并保存为临时 PNG
+fn save_preview_frame(
+    source_img: &DynamicImage,
+    frame: &FrameInfo,
+    index: usize,
+    dir: &Path,
+) -> Result<PathBuf, String> {
+    let path = dir.join(format!("frame_{:04}.png", index));
+    crop_frame(source_img, frame)?
+        .save(&path)
+        .map_err(|e| format!("保存预览帧失败 {}: {}", frame.name, e))?;
+    Ok(path)
+}
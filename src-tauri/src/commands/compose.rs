@@ -1,14 +1,33 @@
 /// 精灵图合成命令 (Sprite Compose Commands)
 /// 
-/// 将多张独立图片按手动布局合成为一张 PNG + Plist
+/// 将多张独立图片按手动布局合成为一张 PNG + Plist。
+/// 精灵可单独旋转、缩放与翻转，按 z 顺序以 alpha 混合叠加；
+/// 也可以用 MaxRects 自动排列位置，前端确认后再合成
 
-use image::{ImageReader, RgbaImage, GenericImage};
-use std::collections::HashMap;
+use crate::core::hooks::{ExportHook, HookContext, HookOutput, HookStage};
+use crate::core::image_processor::{apply_adjustments, bleed_alpha, validate_bleed, ImageAdjustments};
+use crate::core::limits::{check_texture_size, MAX_TEXTURE_SIZE};
+use crate::core::packer::{arrange_all, PackHeuristic, PackerOptions, SpriteInput};
+use crate::core::plist_generator::{generate_plist, PlistFormat, PlistFrame};
+use crate::core::sprite_transform::SpriteTransform;
+use crate::core::png_meta::{AtlasStamp, PngMetadata, MAX_DPI};
+use crate::core::summary::texture_summary;
+use crate::core::texture_format::PixelFormat;
+use crate::core::timings::{Phase, Timings};
+use crate::core::upscale::{upscale_image, UpscaleOptions};
+use crate::utils::guard::{catch_panic, run_blocking};
+use crate::utils::hash::{calculate_md5, SmartUpdate, SmartUpdateMode};
+use crate::utils::hook_runner::run_hooks;
+use crate::utils::image_loader::{load_image, ImageLoadLimits};
+use crate::utils::job_lock::{output_dir, try_lock};
+use crate::utils::number_locale::number_locale;
+use crate::utils::png_writer::{save_png, TOOL_VERSION};
+use image::{imageops, RgbaImage};
 use std::path::Path;
-use md5::{Md5, Digest};
+use std::time::Instant;
 
 /// 合成精灵位置信息
-#[derive(Debug, Clone, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ComposeSpritePosition {
     /// 精灵 ID
@@ -23,15 +42,29 @@ pub struct ComposeSpritePosition {
     pub height: u32,
     /// 在画布中的 X 坐标
     pub x: i32,
-    /// 在画布中的 Y 坐标
+    /// 在画布中的 Y 坐标（与 X 一起指向变换后外接矩形的左上角）
     pub y: i32,
+    /// 旋转、缩放与翻转（可选，默认不变换）
+    #[serde(flatten)]
+    pub transform: SpriteTransform,
+    /// 叠放顺序（可选，默认 0；大的绘制在上层，相同时按列表顺序）
+    #[serde(default)]
+    pub z_index: i32,
+}
+
+impl ComposeSpritePosition {
+    /// 变换后在画布中占用的尺寸（未整体放大）
+    fn placed_size(&self) -> (u32, u32) {
+        self.transform.placed_size(self.width, self.height)
+    }
 }
 
 /// 合成配置
-#[derive(Debug, Clone, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ComposeConfig {
-    /// 输出目录
+    /// 输出目录（与机器相关，不参与配置哈希）
+    #[serde(skip_serializing)]
     pub output_dir: String,
     /// 输出文件名（不含扩展名）
     pub output_name: String,
@@ -39,6 +72,22 @@ pub struct ComposeConfig {
     pub padding: Option<u32>,
     /// 是否裁剪到最小边界（可选，默认 true）
     pub trim_to_bounds: Option<bool>,
+    /// 导出时的图像调整（可选）
+    pub adjustments: Option<ImageAdjustments>,
+    /// 只调整这些精灵（可选，按 ID；不设置则调整整张合成图）
+    pub adjust_sprite_ids: Option<Vec<String>>,
+    /// 整体放大（可选）：逐个精灵放大后按放大后的坐标合成，Plist 同步缩放
+    pub upscale: Option<UpscaleOptions>,
+    /// 透明像素渗色的迭代次数（可选，默认 0 为关闭）
+    pub bleed: Option<u32>,
+    /// 是否在 PNG 中写入工具版本、配置哈希与帧数（可选，默认 false）
+    pub embed_metadata: Option<bool>,
+    /// PNG 的物理分辨率（可选，设置时写入 pHYs 块）
+    pub dpi: Option<u32>,
+    /// 导出前后执行的外部命令（可选）
+    pub hooks: Option<Vec<ExportHook>>,
+    /// Plist 中 smartupdate 哈希的计算方式（可选，默认按纹理文件名、帧布局与像素计算）
+    pub smartupdate: Option<SmartUpdateMode>,
 }
 
 /// 合成结果
@@ -55,6 +104,12 @@ pub struct ComposeResult {
     pub texture_height: u32,
     /// 精灵数量
     pub sprite_count: usize,
+    /// 导出钩子的执行结果
+    pub hook_outputs: Vec<HookOutput>,
+    /// 各阶段耗时
+    pub timings: Timings,
+    /// 人类可读的结果摘要（按当前数字格式地区）
+    pub summary: String,
 }
 
 /// 合成精灵图命令
@@ -71,6 +126,13 @@ pub struct ComposeResult {
 pub async fn compose_sprites(
     sprites: Vec<ComposeSpritePosition>,
     config: ComposeConfig,
+) -> Result<ComposeResult, String> {
+    run_blocking("compose_sprites", move || compose_sprites_impl(sprites, config)).await
+}
+
+fn compose_sprites_impl(
+    sprites: Vec<ComposeSpritePosition>,
+    config: ComposeConfig,
 ) -> Result<ComposeResult, String> {
     println!("开始合成 {} 个精灵", sprites.len());
     
@@ -80,94 +142,168 @@ pub async fn compose_sprites(
     
     let padding = config.padding.unwrap_or(0);
     let trim_to_bounds = config.trim_to_bounds.unwrap_or(true);
+    if let Some(adjustments) = &config.adjustments {
+        adjustments.validate()?;
+    }
+    if let Some(upscale) = &config.upscale {
+        upscale.validate()?;
+    }
+    for sprite in &sprites {
+        sprite.transform.validate().map_err(|e| format!("精灵 {}: {}", sprite.name, e))?;
+    }
+    let bleed = config.bleed.unwrap_or(0);
+    validate_bleed(bleed)?;
+    if config.dpi.is_some_and(|dpi| dpi == 0 || dpi > MAX_DPI) {
+        return Err(format!("DPI 必须在 1 ~ {} 之间", MAX_DPI));
+    }
+    let hooks = config.hooks.clone().unwrap_or_default();
+    for hook in &hooks {
+        hook.validate()?;
+    }
+    let scale = config.upscale.map_or(1, |upscale| upscale.factor);
     
     // 计算画布边界
     let (min_x, min_y, max_x, max_y) = calculate_bounds(&sprites);
     
-    // 计算最终纹理尺寸
+    // 计算最终纹理尺寸（偏移量使用 i64，避免极端坐标溢出）
     let (texture_width, texture_height, offset_x, offset_y) = if trim_to_bounds {
         // 裁剪到最小边界
-        let width = (max_x - min_x) as u32 + padding * 2;
-        let height = (max_y - min_y) as u32 + padding * 2;
-        (width, height, -min_x + padding as i32, -min_y + padding as i32)
+        let width = max_x.abs_diff(min_x).saturating_add(padding.saturating_mul(2));
+        let height = max_y.abs_diff(min_y).saturating_add(padding.saturating_mul(2));
+        (width, height, padding as i64 - min_x as i64, padding as i64 - min_y as i64)
     } else {
         // 保留原始位置（从 0,0 开始）
-        let width = max_x as u32 + padding;
-        let height = max_y as u32 + padding;
-        (width, height, padding as i32, padding as i32)
+        if min_x < 0 || min_y < 0 {
+            return Err("未启用裁剪到边界时，精灵坐标不能为负数".to_string());
+        }
+        let width = (max_x as u32).saturating_add(padding);
+        let height = (max_y as u32).saturating_add(padding);
+        (width, height, padding as i64, padding as i64)
     };
     
+    let texture_width = texture_width.saturating_mul(scale);
+    let texture_height = texture_height.saturating_mul(scale);
     println!("纹理尺寸: {}x{}", texture_width, texture_height);
+    check_texture_size(texture_width, texture_height)?;
     
     // 创建目标图像
     let mut output_image = RgbaImage::new(texture_width, texture_height);
     
-    // 加载并绘制每个精灵
-    let mut frame_infos: Vec<FrameComposeInfo> = Vec::new();
+    // 按 z 顺序加载并绘制每个精灵，Plist 中的帧保持列表顺序
+    let mut frame_infos: Vec<Option<FrameComposeInfo>> = sprites.iter().map(|_| None).collect();
+    let mut draw_order: Vec<usize> = (0..sprites.len()).collect();
+    draw_order.sort_by_key(|&index| sprites[index].z_index);
     
-    for sprite in &sprites {
+    let mut timings = Timings::default();
+    for index in draw_order {
+        let sprite = &sprites[index];
         // 加载图像
-        let img = ImageReader::open(&sprite.path)
-            .map_err(|e| format!("无法打开图像 {}: {}", sprite.path, e))?
-            .decode()
-            .map_err(|e| format!("无法解码图像 {}: {}", sprite.path, e))?
-            .to_rgba8();
+        let mut img = timings.time(Phase::Decode, || load_image(&sprite.path, &ImageLoadLimits::default()))?.to_rgba8();
+        let render_start = Instant::now();
+        if let (Some(adjustments), Some(ids)) = (&config.adjustments, &config.adjust_sprite_ids) {
+            if ids.contains(&sprite.id) {
+                apply_adjustments(&mut img, adjustments);
+            }
+        }
+        img = sprite.transform.apply(&img);
+        if let Some(upscale) = &config.upscale {
+            img = upscale_image(&img, upscale);
+        }
         
-        // 计算在输出图像中的位置
-        let dest_x = (sprite.x + offset_x) as u32;
-        let dest_y = (sprite.y + offset_y) as u32;
+        // 计算在输出图像中的位置（边界已包含所有精灵，结果必然非负）
+        let dest_x = u32::try_from((sprite.x as i64 + offset_x) * scale as i64)
+            .map_err(|_| format!("精灵 {} 的坐标超出画布范围", sprite.name))?;
+        let dest_y = u32::try_from((sprite.y as i64 + offset_y) * scale as i64)
+            .map_err(|_| format!("精灵 {} 的坐标超出画布范围", sprite.name))?;
         
-        // 绘制到输出图像
-        if let Err(e) = output_image.copy_from(&img, dest_x, dest_y) {
-            println!("警告: 绘制精灵 {} 时出错: {}", sprite.name, e);
-            // 继续处理其他精灵
-        }
+        // 以 alpha 混合绘制到输出图像（超出画布的部分被裁掉）
+        imageops::overlay(&mut output_image, &img, dest_x as i64, dest_y as i64);
         
-        // 记录帧信息
-        frame_infos.push(FrameComposeInfo {
+        // 记录帧信息：顺时针旋转 90° 的帧以 textureRotated 描述，尺寸为未旋转时的尺寸
+        let rotated = sprite.transform.texture_rotated();
+        let (width, height) = if rotated {
+            sprite.transform.scaled_size(sprite.width, sprite.height)
+        } else {
+            sprite.placed_size()
+        };
+        frame_infos[index] = Some(FrameComposeInfo {
             name: sprite.name.clone(),
             x: dest_x,
             y: dest_y,
-            width: sprite.width,
-            height: sprite.height,
+            width: width.saturating_mul(scale),
+            height: height.saturating_mul(scale),
+            rotated,
         });
         
+        timings.add(Phase::Render, render_start.elapsed());
         println!("  - 绘制 {} 到 ({}, {})", sprite.name, dest_x, dest_y);
     }
     
+    let frame_infos: Vec<FrameComposeInfo> = frame_infos.into_iter().flatten().collect();
+    
+    // 未指定精灵时调整整张合成图
+    if let (Some(adjustments), None) = (&config.adjustments, &config.adjust_sprite_ids) {
+        timings.time(Phase::Render, || apply_adjustments(&mut output_image, adjustments));
+    }
+    timings.time(Phase::Render, || bleed_alpha(&mut output_image, bleed));
+    
     // 确保输出目录存在
+    let _guard = try_lock("compose_sprites", [output_dir(&config.output_dir)])?;
     let output_dir = Path::new(&config.output_dir);
     std::fs::create_dir_all(output_dir)
         .map_err(|e| format!("无法创建输出目录: {}", e))?;
     
-    // 保存 PNG
     let png_path = output_dir.join(format!("{}.png", config.output_name));
-    output_image.save(&png_path)
+    let plist_path = output_dir.join(format!("{}.plist", config.output_name));
+    let hook_context = HookContext {
+        png: png_path.to_string_lossy().to_string(),
+        plist: plist_path.to_string_lossy().to_string(),
+        dir: output_dir.to_string_lossy().to_string(),
+        name: config.output_name.clone(),
+    };
+    let mut hook_outputs = run_hooks(&hooks, HookStage::Pre, &hook_context)?;
+    
+    // 保存 PNG
+    let stamp = if config.embed_metadata.unwrap_or(false) {
+        let config_json = serde_json::to_vec(&config).map_err(|e| format!("序列化配置失败: {}", e))?;
+        Some(AtlasStamp {
+            tool_version: TOOL_VERSION.to_string(),
+            config_hash: calculate_md5(&config_json),
+            frame_count: frame_infos.len(),
+        })
+    } else {
+        None
+    };
+    let metadata = PngMetadata { stamp, dpi: config.dpi };
+    timings.time(Phase::Write, || save_png(&output_image, &png_path, &metadata))
         .map_err(|e| format!("保存 PNG 失败: {}", e))?;
     
     println!("PNG 保存成功: {}", png_path.display());
     
     // 生成并保存 Plist
     let texture_name = format!("{}.png", config.output_name);
-    let plist_content = generate_compose_plist(
-        &frame_infos,
-        texture_width,
-        texture_height,
-        &texture_name,
-    )?;
+    let plist_content = timings.time(Phase::Render, || {
+        let smartupdate = SmartUpdate::new(&output_image, config.smartupdate.unwrap_or_default());
+        generate_compose_plist(&frame_infos, texture_width, texture_height, &texture_name, smartupdate)
+    })?;
     
-    let plist_path = output_dir.join(format!("{}.plist", config.output_name));
-    std::fs::write(&plist_path, plist_content)
+    timings.time(Phase::Write, || std::fs::write(&plist_path, plist_content))
         .map_err(|e| format!("保存 Plist 失败: {}", e))?;
     
     println!("Plist 保存成功: {}", plist_path.display());
     
+    hook_outputs.extend(run_hooks(&hooks, HookStage::Post, &hook_context)?);
+    println!("✓ 合成完成, {}", timings);
+    
     Ok(ComposeResult {
         png_path: png_path.to_string_lossy().to_string(),
         plist_path: plist_path.to_string_lossy().to_string(),
         texture_width,
         texture_height,
         sprite_count: sprites.len(),
+        hook_outputs,
+        timings,
+        summary: texture_summary(texture_width, texture_height, None, number_locale()),
     })
 }
 
@@ -176,11 +312,14 @@ struct FrameComposeInfo {
     name: String,
     x: u32,
     y: u32,
+    /// 帧尺寸（旋转帧为未旋转时的尺寸）
     width: u32,
     height: u32,
+    /// 在纹理中顺时针旋转了 90 度
+    rotated: bool,
 }
 
-/// 计算所有精灵的边界
+/// 计算所有精灵的边界（按变换后的尺寸）
 fn calculate_bounds(sprites: &[ComposeSpritePosition]) -> (i32, i32, i32, i32) {
     let mut min_x = i32::MAX;
     let mut min_y = i32::MAX;
@@ -188,10 +327,11 @@ fn calculate_bounds(sprites: &[ComposeSpritePosition]) -> (i32, i32, i32, i32) {
     let mut max_y = i32::MIN;
     
     for sprite in sprites {
+        let (width, height) = sprite.placed_size();
         min_x = min_x.min(sprite.x);
         min_y = min_y.min(sprite.y);
-        max_x = max_x.max(sprite.x + sprite.width as i32);
-        max_y = max_y.max(sprite.y + sprite.height as i32);
+        max_x = max_x.max(sprite.x.saturating_add_unsigned(width));
+        max_y = max_y.max(sprite.y.saturating_add_unsigned(height));
     }
     
     (min_x, min_y, max_x, max_y)
@@ -203,90 +343,121 @@ fn generate_compose_plist(
     texture_width: u32,
     texture_height: u32,
     texture_name: &str,
-) -> Result<String, String> {
-    // 构建 frames 字典
-    let mut frames_dict: HashMap<String, plist::Value> = HashMap::new();
-    
-    for frame in frames {
-        let mut frame_data: HashMap<String, plist::Value> = HashMap::new();
-        
-        // Cocos2d-x Format 3 格式
-        frame_data.insert(
-            "spriteOffset".to_string(),
-            plist::Value::String("{0,0}".to_string()),
-        );
-        
-        frame_data.insert(
-            "spriteSize".to_string(),
-            plist::Value::String(format!("{{{},{}}}", frame.width, frame.height)),
-        );
-        
-        frame_data.insert(
-            "spriteSourceSize".to_string(),
-            plist::Value::String(format!("{{{},{}}}", frame.width, frame.height)),
-        );
-        
-        frame_data.insert(
-            "textureRect".to_string(),
-            plist::Value::String(format!(
-                "{{{{{},{}}},{{{},{}}}}}",
-                frame.x, frame.y, frame.width, frame.height
-            )),
-        );
-        
-        frame_data.insert(
-            "textureRotated".to_string(),
-            plist::Value::Boolean(false),
-        );
-        
-        frames_dict.insert(
-            frame.name.clone(),
-            plist::Value::Dictionary(frame_data.into_iter().collect()),
-        );
+    smartupdate: SmartUpdate,
+) -> Result<Vec<u8>, String> {
+    let frames: Vec<PlistFrame> = frames
+        .iter()
+        .map(|frame| PlistFrame {
+            rotated: frame.rotated,
+            ..PlistFrame::untrimmed(frame.name.clone(), frame.x, frame.y, frame.width, frame.height)
+        })
+        .collect();
+    generate_plist(&frames, texture_width, texture_height, texture_name, PlistFormat::XmlV3, PixelFormat::Rgba8888, smartupdate)
+}
+
+/// 自动排列选项
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComposeArrangeOptions {
+    /// 精灵间距（可选，默认 2）
+    pub padding: Option<u32>,
+    /// 是否允许旋转 90°（可选，默认 false；旋转记入精灵的 `rotation`）
+    pub allow_rotation: Option<bool>,
+    /// 启发式策略（可选，默认 BSSF）
+    pub heuristic: Option<PackHeuristic>,
+    /// 最大尺寸（可选，默认 4096）
+    pub max_size: Option<u32>,
+}
+
+/// 自动排列结果
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComposeArrangeResult {
+    /// 按输入顺序排列、已更新坐标与旋转的精灵（可直接传给 `compose_sprites`）
+    pub sprites: Vec<ComposeSpritePosition>,
+    /// 排列后的宽度
+    pub width: u32,
+    /// 排列后的高度
+    pub height: u32,
+}
+
+/// 自动排列合成精灵
+///
+/// 按变换后的尺寸用 MaxRects 打包，只计算位置不合成，
+/// 前端展示结果后再调用 `compose_sprites`
+///
+/// # Arguments
+/// * `sprites` - 精灵位置信息列表（坐标会被替换）
+/// * `options` - 排列选项（可选）
+///
+/// # Returns
+/// * `Result<ComposeArrangeResult, String>` - 排列后的精灵与尺寸
+#[tauri::command]
+pub async fn compose_auto_arrange(
+    sprites: Vec<ComposeSpritePosition>,
+    options: Option<ComposeArrangeOptions>,
+) -> Result<ComposeArrangeResult, String> {
+    catch_panic("compose_auto_arrange", compose_auto_arrange_impl(sprites, options.unwrap_or_default())).await
+}
+
+async fn compose_auto_arrange_impl(
+    mut sprites: Vec<ComposeSpritePosition>,
+    options: ComposeArrangeOptions,
+) -> Result<ComposeArrangeResult, String> {
+    if sprites.is_empty() {
+        return Err("没有精灵可排列".to_string());
     }
+    for sprite in &sprites {
+        sprite.transform.validate().map_err(|e| format!("精灵 {}: {}", sprite.name, e))?;
+    }
+    let max_size = options.max_size.unwrap_or(4096);
+    if max_size == 0 || max_size > MAX_TEXTURE_SIZE {
+        return Err(format!("最大尺寸必须在 1 ~ {} 之间", MAX_TEXTURE_SIZE));
+    }
+    let allow_rotation = options.allow_rotation.unwrap_or(false);
+    let packer_options = PackerOptions {
+        allow_rotation,
+        padding: options.padding.unwrap_or(2),
+        heuristic: options.heuristic.unwrap_or_default(),
+        ..Default::default()
+    };
     
-    // 构建 metadata
-    let mut metadata: HashMap<String, plist::Value> = HashMap::new();
-    metadata.insert("format".to_string(), plist::Value::Integer(3.into()));
-    metadata.insert(
-        "realTextureFileName".to_string(),
-        plist::Value::String(texture_name.to_string()),
-    );
-    metadata.insert(
-        "size".to_string(),
-        plist::Value::String(format!("{{{},{}}}", texture_width, texture_height)),
-    );
-    metadata.insert(
-        "textureFileName".to_string(),
-        plist::Value::String(texture_name.to_string()),
-    );
-    
-    // 计算 smartupdate hash
-    let mut hasher = Md5::new();
-    hasher.update(format!("{}_{}", texture_name, frames.len()).as_bytes());
-    let hash = format!("{:x}", hasher.finalize());
-    metadata.insert("smartupdate".to_string(), plist::Value::String(hash));
-    
-    // 构建根字典
-    let mut root: HashMap<String, plist::Value> = HashMap::new();
-    root.insert(
-        "frames".to_string(),
-        plist::Value::Dictionary(frames_dict.into_iter().collect()),
-    );
-    root.insert(
-        "metadata".to_string(),
-        plist::Value::Dictionary(metadata.into_iter().collect()),
-    );
-    
-    let plist_value = plist::Value::Dictionary(root.into_iter().collect());
+    // 同一张图片可以在画布中出现多次，以下标作为 ID
+    let inputs: Vec<SpriteInput> = sprites
+        .iter()
+        .enumerate()
+        .map(|(index, sprite)| {
+            let (width, height) = sprite.placed_size();
+            SpriteInput {
+                id: index.to_string(),
+                name: sprite.name.clone(),
+                width,
+                height,
+                original_width: width,
+                original_height: height,
+                offset_x: 0,
+                offset_y: 0,
+                trimmed: false,
+                allow_rotation,
+            }
+        })
+        .collect();
     
-    // 序列化为 XML
-    let mut buf = Vec::new();
-    plist::to_writer_xml(&mut buf, &plist_value)
-        .map_err(|e| format!("序列化 Plist 失败: {}", e))?;
+    let (packed, (width, height)) = arrange_all(&inputs, max_size, &packer_options)
+        .ok_or_else(|| format!("精灵无法在 {}x{} 内排列", max_size, max_size))?;
+    for placed in packed {
+        let index: usize = placed.id.parse().map_err(|_| format!("排列结果无效: {}", placed.id))?;
+        let sprite = &mut sprites[index];
+        sprite.x = placed.x as i32;
+        sprite.y = placed.y as i32;
+        // 打包器的旋转为顺时针 90°，叠加在精灵已有的旋转之后
+        if placed.rotated {
+            sprite.transform.rotation = (sprite.transform.rotation + 90) % 360;
+        }
+    }
     
-    String::from_utf8(buf)
-        .map_err(|e| format!("转换 Plist 编码失败: {}", e))
+    println!("✓ 自动排列完成: {} 个精灵, {}x{}", sprites.len(), width, height);
+    Ok(ComposeArrangeResult { sprites, width, height })
 }
 
 /// 预览合成边界（不实际合成，只计算尺寸）
@@ -299,6 +470,12 @@ fn generate_compose_plist(
 #[tauri::command]
 pub async fn preview_compose_bounds(
     sprites: Vec<ComposeSpritePosition>,
+) -> Result<ComposeBoundsInfo, String> {
+    catch_panic("preview_compose_bounds", preview_compose_bounds_impl(sprites)).await
+}
+
+async fn preview_compose_bounds_impl(
+    sprites: Vec<ComposeSpritePosition>,
 ) -> Result<ComposeBoundsInfo, String> {
     if sprites.is_empty() {
         return Ok(ComposeBoundsInfo {
@@ -319,8 +496,8 @@ pub async fn preview_compose_bounds(
         min_y,
         max_x,
         max_y,
-        width: (max_x - min_x) as u32,
-        height: (max_y - min_y) as u32,
+        width: max_x.abs_diff(min_x),
+        height: max_y.abs_diff(min_y),
         sprite_count: sprites.len(),
     })
 }
@@ -360,6 +537,8 @@ mod tests {
                 height: 100,
                 x: 0,
                 y: 0,
+                transform: SpriteTransform::default(),
+                z_index: 0,
             },
             ComposeSpritePosition {
                 id: "2".to_string(),
@@ -369,6 +548,8 @@ mod tests {
                 height: 50,
                 x: 100,
                 y: 100,
+                transform: SpriteTransform::default(),
+                z_index: 0,
             },
         ];
         
@@ -379,4 +560,17 @@ mod tests {
         assert_eq!(max_x, 150);
         assert_eq!(max_y, 150);
     }
+    
+    #[test]
+    fn test_transformed_bounds() {
+        let sprite: ComposeSpritePosition = serde_json::from_value(serde_json::json!({
+            "id": "1", "name": "a.png", "path": "a.png", "width": 40, "height": 10,
+            "x": 10, "y": 0, "rotation": 90, "scale": 2.0, "flipY": true, "zIndex": 3
+        }))
+        .unwrap();
+        assert_eq!(sprite.placed_size(), (20, 80));
+        assert_eq!(sprite.z_index, 3);
+        assert!(sprite.transform.flip_y && !sprite.transform.texture_rotated());
+        assert_eq!(calculate_bounds(&[sprite]), (10, 0, 30, 80));
+    }
 }
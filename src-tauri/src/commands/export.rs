@@ -1,34 +1,735 @@
 /// 导出命令
 /// 
-/// 生成 Plist 文件和纹理图，可选 ZIP 打包
+/// 生成纹理图与数据文件（Plist 或 TexturePacker JSON），可选 ZIP 打包
 
-use crate::core::types::ExportConfig;
+use crate::commands::history::{record_export, ExportHistoryStore};
+use crate::commands::pack::{PackSessionStore, PackTrims};
+use crate::core::image_processor::{
+    apply_adjustments, bleed_alpha, extrude_edges, render_texture, validate_bleed, ImageAdjustments,
+};
+use crate::core::limits::check_texture_size;
+use crate::core::notes::notes_sidecar;
+use crate::core::exporters::{SheetData, SheetExporter};
+use crate::core::frame_groups::{group_frames, FrameGrouping};
+use crate::core::frame_names::check_unique_names;
+use crate::core::history::ExportSnapshot;
+use crate::core::scale_variant::{scale_sprites, scale_texture, validate_variants, ScaleVariant};
+use crate::core::strip::{compose_strip, strip_file_name, validate_strip_name};
+use crate::core::texture_format::TextureOptions;
+use crate::core::tiling::page_file_stem;
+use crate::core::timings::{Phase, Timings};
+use crate::core::types::{ExportConfig, PackedSprite, PagedExportConfig, StripExportResult, StripGroup};
+use crate::utils::guard::run_blocking;
+use crate::utils::hash::{SmartUpdate, SmartUpdateMode};
+use crate::utils::image_loader::{load_image, ImageLoadLimits};
+use crate::utils::job_lock::{self, output_dir, try_lock};
+use crate::utils::texture_writer::encode_texture_file;
+use image::RgbaImage;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+use tauri::State;
+
+/// 导出结果
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportResult {
+    /// 数据文件路径（ZIP 模式下为 ZIP 路径）
+    pub output_path: String,
+    /// 各缩放版本的数据文件路径（顺序与 `variants` 一致，ZIP 模式下为空）
+    pub variant_paths: Vec<String>,
+    /// 分组数据文件路径（未设置分组或 ZIP 模式下为空）
+    pub group_paths: Vec<String>,
+    /// 各阶段耗时
+    pub timings: Timings,
+}
+
+impl ExportResult {
+    /// 写出的所有数据文件（主文件、缩放版本与分组文件）
+    pub fn output_files(&self) -> Vec<String> {
+        std::iter::once(&self.output_path)
+            .chain(&self.variant_paths)
+            .chain(&self.group_paths)
+            .cloned()
+            .collect()
+    }
+}
+
+/// 多页导出结果
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PagedExportResult {
+    /// 各页的数据文件路径（ZIP 模式下只有 ZIP 路径）
+    pub output_paths: Vec<String>,
+    /// 各阶段耗时（所有页合计）
+    pub timings: Timings,
+}
 
 /// 导出精灵图命令
 /// 
 /// # Arguments
-/// * `config` - 导出配置
+/// * `config` - 导出配置（`job_id` 指定使用哪次打包的裁剪结果）
 /// 
 /// # Returns
-/// * `Result<String, String>` - 导出路径或错误信息
+/// * `Result<ExportResult, String>` - 导出路径与耗时或错误信息
 #[tauri::command]
-pub async fn export_sprite_sheet(config: ExportConfig) -> Result<String, String> {
+pub async fn export_sprite_sheet(
+    store: State<'_, PackSessionStore>,
+    history: State<'_, ExportHistoryStore>,
+    config: ExportConfig,
+) -> Result<ExportResult, String> {
+    let (store, history) = (store.inner().clone(), history.inner().clone());
+    run_blocking("export_sprite_sheet", move || {
+        let (snapshot, job_id) = (ExportSnapshot::single(&config), config.job_id.clone());
+        let result = export_sprite_sheet_impl(&store, config)?;
+        record_export(&store, &history, &job_id, snapshot, result.output_files());
+        Ok(result)
+    })
+    .await
+}
+
+/// 使用指定存储中的打包结果导出（自动化接口使用）
+pub(crate) async fn export_sprite_sheet_in(store: PackSessionStore, config: ExportConfig) -> Result<ExportResult, String> {
+    run_blocking("export_sprite_sheet", move || export_sprite_sheet_impl(&store, config)).await
+}
+
+pub(crate) fn export_sprite_sheet_impl(store: &PackSessionStore, config: ExportConfig) -> Result<ExportResult, String> {
     println!("开始导出精灵图: {}", config.output_name);
     println!("  - 输出路径: {}", config.output_dir);
     println!("  - ZIP 打包: {}", config.zip_output);
+    println!("  - 数据格式: {:?} ({:?})", config.format, config.plist_format);
     
-    // TODO: 实现实际的导出逻辑
-    // 1. 生成 Plist XML
-    // 2. 渲染纹理图
-    // 3. 保存文件或创建 ZIP
+    if let Some(adjustments) = &config.adjustments {
+        adjustments.validate()?;
+    }
+    validate_variants(&config.variants)?;
+    validate_bleed(config.bleed)?;
+    config.texture.validate()?;
+    if config.packed_sprites.is_empty() {
+        return Err("没有精灵可导出".to_string());
+    }
+    check_unique_names(config.packed_sprites.iter().map(|s| s.name.as_str()))?;
+    validate_nine_slices(&config.packed_sprites)?;
+    check_texture_size(config.texture_width, config.texture_height)?;
+    let _guard = try_lock("export_sprite_sheet", [output_dir(&config.output_dir)])?;
     
-    let output_path = if config.zip_output {
-        format!("{}/{}.zip", config.output_dir, config.output_name)
+    // 1. 渲染纹理图（图像来自该次打包的裁剪结果）并生成数据文件
+    let trims = store.get(&config.job_id)?;
+    let renderer = PageRenderer {
+        cache: &trims,
+        adjustments: config.adjustments.as_ref(),
+        adjust_ids: config.adjust_sprite_ids.as_ref(),
+        exporter: config.format.exporter(config.plist_format),
+        extrude: config.extrude,
+        bleed: config.bleed,
+        grouping: config.frame_groups.as_ref(),
+        notes: &config.frame_notes,
+        texture: config.texture,
+        smartupdate: config.smartupdate,
+    };
+    let mut timings = Timings::default();
+    let pages = timings.time(Phase::Render, || {
+        let page = renderer.render(
+            &config.packed_sprites,
+            config.texture_width,
+            config.texture_height,
+            &config.output_name,
+        )?;
+        // 缩放版本由全分辨率纹理缩小得到
+        let mut pages = Vec::with_capacity(config.variants.len() + 1);
+        for variant in &config.variants {
+            pages.push(renderer.render_variant(&page, &config.packed_sprites, &config.output_name, variant)?);
+            println!("  - 缩放版本 {} (x{})", variant.suffix, variant.scale);
+        }
+        pages.insert(0, page);
+        Ok::<_, String>(pages)
+    })?;
+    
+    // 2. 保存文件或创建 ZIP
+    let mut paths = timings.time(Phase::Write, || {
+        write_pages(Path::new(&config.output_dir), &config.output_name, config.zip_output, &config.texture, &pages)
+    })?;
+    let output_path = paths.remove(0);
+    let group_paths = if config.zip_output {
+        Vec::new()
     } else {
-        format!("{}/{}.plist", config.output_dir, config.output_name)
+        pages
+            .iter()
+            .flat_map(|page| &page.groups)
+            .map(|(name, _)| Path::new(&config.output_dir).join(name).to_string_lossy().to_string())
+            .collect()
+    };
+    
+    println!("✓ 导出成功: {}, {}", output_path, timings);
+    
+    Ok(ExportResult { output_path, variant_paths: paths, group_paths, timings })
+}
+
+/// 多页导出精灵图命令
+///
+/// 每页输出 `{outputName}_{page}.png` 与同名数据文件（页号从 0 开始），
+/// ZIP 模式下所有页写入同一个 `{outputName}.zip`
+///
+/// # Arguments
+/// * `config` - 多页导出配置
+///
+/// # Returns
+/// * `Result<PagedExportResult, String>` - 各页的数据文件路径（ZIP 模式下为 ZIP 路径）与耗时或错误信息
+#[tauri::command]
+pub async fn export_sprite_sheet_pages(
+    store: State<'_, PackSessionStore>,
+    history: State<'_, ExportHistoryStore>,
+    config: PagedExportConfig,
+) -> Result<PagedExportResult, String> {
+    let (store, history) = (store.inner().clone(), history.inner().clone());
+    run_blocking("export_sprite_sheet_pages", move || {
+        let (snapshot, job_id) = (ExportSnapshot::pages(&config), config.job_id.clone());
+        let result = export_sprite_sheet_pages_impl(&store, config)?;
+        record_export(&store, &history, &job_id, snapshot, result.output_paths.clone());
+        Ok(result)
+    })
+    .await
+}
+
+pub(crate) fn export_sprite_sheet_pages_impl(
+    store: &PackSessionStore,
+    config: PagedExportConfig,
+) -> Result<PagedExportResult, String> {
+    println!("开始多页导出精灵图: {} ({} 页)", config.output_name, config.pages.len());
+    
+    if let Some(adjustments) = &config.adjustments {
+        adjustments.validate()?;
+    }
+    validate_bleed(config.bleed)?;
+    config.texture.validate()?;
+    if config.pages.iter().all(|page| page.packed_sprites.is_empty()) {
+        return Err("没有精灵可导出".to_string());
+    }
+    for (index, page) in config.pages.iter().enumerate() {
+        check_unique_names(page.packed_sprites.iter().map(|s| s.name.as_str())).map_err(|e| format!("第 {} 页: {}", index, e))?;
+        validate_nine_slices(&page.packed_sprites).map_err(|e| format!("第 {} 页: {}", index, e))?;
+        check_texture_size(page.texture_width, page.texture_height)?;
+    }
+    let _guard = try_lock("export_sprite_sheet_pages", [output_dir(&config.output_dir)])?;
+    
+    let trims = store.get(&config.job_id)?;
+    let renderer = PageRenderer {
+        cache: &trims,
+        adjustments: config.adjustments.as_ref(),
+        adjust_ids: config.adjust_sprite_ids.as_ref(),
+        exporter: config.format.exporter(config.plist_format),
+        extrude: config.extrude,
+        bleed: config.bleed,
+        grouping: None,
+        notes: &config.frame_notes,
+        texture: config.texture,
+        smartupdate: config.smartupdate,
     };
+    let mut timings = Timings::default();
+    let pages = timings.time(Phase::Render, || {
+        config
+            .pages
+            .iter()
+            .enumerate()
+            .map(|(index, page)| {
+                renderer
+                    .render(
+                        &page.packed_sprites,
+                        page.texture_width,
+                        page.texture_height,
+                        &page_file_stem(&config.output_name, index),
+                    )
+                    .map_err(|e| format!("第 {} 页: {}", index, e))
+            })
+            .collect::<Result<Vec<_>, String>>()
+    })?;
     
-    println!("✓ 导出成功: {}", output_path);
+    let output_paths = timings.time(Phase::Write, || {
+        write_pages(Path::new(&config.output_dir), &config.output_name, config.zip_output, &config.texture, &pages)
+    })?;
+    println!("✓ 多页导出成功: {} 页, {}", pages.len(), timings);
     
-    Ok(output_path)
+    Ok(PagedExportResult { output_paths, timings })
+}
+
+/// 渲染完成、等待写入的一页
+struct RenderedPage {
+    /// 纹理文件名
+    texture_name: String,
+    /// 纹理
+    texture: RgbaImage,
+    /// 数据文件名
+    data_name: String,
+    /// 数据文件内容
+    data: Vec<u8>,
+    /// 分组数据文件 (文件名, 内容)，都引用同一张纹理
+    groups: Vec<(String, Vec<u8>)>,
+    /// 帧备注附属文件 (文件名, 内容)，该页没有备注时为 None
+    notes: Option<(String, Vec<u8>)>,
+}
+
+/// 各页共用的渲染设置
+struct PageRenderer<'a> {
+    /// 打包时的裁剪结果
+    cache: &'a PackTrims,
+    /// 图像调整（可选）
+    adjustments: Option<&'a ImageAdjustments>,
+    /// 只调整这些精灵（可选，不设置则调整整张纹理）
+    adjust_ids: Option<&'a Vec<String>>,
+    /// 数据文件导出器
+    exporter: Box<dyn SheetExporter>,
+    /// 边缘挤出像素
+    extrude: u32,
+    /// 透明像素渗色的迭代次数
+    bleed: u32,
+    /// 分组数据文件（可选）
+    grouping: Option<&'a FrameGrouping>,
+    /// 帧备注（按精灵 ID）
+    notes: &'a HashMap<String, String>,
+    /// 纹理输出选项（决定纹理扩展名与数据文件中的像素格式）
+    texture: TextureOptions,
+    /// smartupdate 哈希模式
+    smartupdate: SmartUpdateMode,
+}
+
+impl PageRenderer<'_> {
+    /// 渲染一页纹理并生成对应的数据文件
+    ///
+    /// # Arguments
+    /// * `sprites` - 该页已打包的精灵
+    /// * `width` / `height` - 纹理尺寸
+    /// * `stem` - 输出文件名（不含扩展名）
+    fn render(&self, sprites: &[PackedSprite], width: u32, height: u32, stem: &str) -> Result<RenderedPage, String> {
+        let (cache, adjustments, adjust_ids) = (self.cache, self.adjustments, self.adjust_ids);
+        let mut adjusted: HashMap<&str, RgbaImage> = HashMap::new();
+        if let (Some(adjustments), Some(ids)) = (adjustments, adjust_ids) {
+            for sprite in sprites.iter().filter(|s| ids.contains(&s.id)) {
+                if let Some(trim) = cache.get(&sprite.id) {
+                    let mut img = trim.trimmed_image.clone();
+                    apply_adjustments(&mut img, adjustments);
+                    adjusted.insert(&sprite.id, img);
+                }
+            }
+        }
+        let image_for = |sprite: &PackedSprite| {
+            adjusted
+                .get(sprite.id.as_str())
+                .or_else(|| cache.get(&sprite.id).map(|trim| &trim.trimmed_image))
+        };
+        if let Some(sprite) = sprites.iter().find(|s| image_for(s).is_none()) {
+            return Err(format!("精灵 {} 不在本次打包结果中，请重新打包后再导出", sprite.name));
+        }
+        let mut texture = render_texture(sprites, width, height, image_for)?;
+        // 未指定精灵时调整整张纹理
+        if let (Some(adjustments), None) = (adjustments, adjust_ids) {
+            apply_adjustments(&mut texture, adjustments);
+        }
+        extrude_edges(&mut texture, sprites, self.extrude);
+        bleed_alpha(&mut texture, self.bleed);
+    
+        let texture_name = self.texture.container.file_name(stem);
+        let data = self.exporter.export(&SheetData {
+            sprites,
+            texture_width: width,
+            texture_height: height,
+            texture_name: &texture_name,
+            pixel_format: self.texture.pixel_format,
+            smartupdate: SmartUpdate::new(&texture, self.smartupdate),
+        })?;
+        let notes = notes_sidecar(sprites, self.notes, &texture_name)?.map(|data| (format!("{}.notes.json", stem), data));
+        Ok(RenderedPage {
+            groups: self.group_files(sprites, &texture, &texture_name, stem)?,
+            notes,
+            texture_name,
+            texture,
+            data_name: format!("{}.{}", stem, self.exporter.extension()),
+            data,
+        })
+    }
+
+    /// 生成分组数据文件 `{stem}_{分组}.{扩展名}`
+    fn group_files(
+        &self,
+        sprites: &[PackedSprite],
+        texture: &RgbaImage,
+        texture_name: &str,
+        stem: &str,
+    ) -> Result<Vec<(String, Vec<u8>)>, String> {
+        let Some(grouping) = self.grouping else {
+            return Ok(Vec::new());
+        };
+        let (groups, ungrouped) = group_frames(sprites, grouping)?;
+        if ungrouped > 0 {
+            println!("警告: {} 个帧不属于任何分组，只写入完整的数据文件", ungrouped);
+        }
+        groups
+            .iter()
+            .map(|group| {
+                let data = self.exporter.export(&SheetData {
+                    sprites: &group.sprites,
+                    texture_width: texture.width(),
+                    texture_height: texture.height(),
+                    texture_name,
+                    pixel_format: self.texture.pixel_format,
+                    smartupdate: SmartUpdate::new(texture, self.smartupdate),
+                })?;
+                Ok((format!("{}_{}.{}", stem, group.name, self.exporter.extension()), data))
+            })
+            .collect()
+    }
+
+    /// 由已渲染的全分辨率页生成缩放版本（文件名为 `{stem}{suffix}`）
+    ///
+    /// # Arguments
+    /// * `page` - 全分辨率页
+    /// * `sprites` - 全分辨率页中的精灵
+    /// * `stem` - 全分辨率页的文件名（不含扩展名）
+    /// * `variant` - 缩放版本
+    fn render_variant(
+        &self,
+        page: &RenderedPage,
+        sprites: &[PackedSprite],
+        stem: &str,
+        variant: &ScaleVariant,
+    ) -> Result<RenderedPage, String> {
+        let texture = scale_texture(&page.texture, variant.scale);
+        let sprites = scale_sprites(sprites, variant.scale);
+        let stem = variant.file_stem(stem);
+        let texture_name = self.texture.container.file_name(&stem);
+        let data = self.exporter.export(&SheetData {
+            sprites: &sprites,
+            texture_width: texture.width(),
+            texture_height: texture.height(),
+            texture_name: &texture_name,
+            pixel_format: self.texture.pixel_format,
+            smartupdate: SmartUpdate::new(&texture, self.smartupdate),
+        })?;
+        // 备注只随全分辨率页写出
+        Ok(RenderedPage {
+            groups: self.group_files(&sprites, &texture, &texture_name, &stem)?,
+            notes: None,
+            texture_name,
+            texture,
+            data_name: format!("{}.{}", stem, self.exporter.extension()),
+            data,
+        })
+    }
+}
+
+/// 校验前端传回的九宫格边距不超出帧尺寸
+fn validate_nine_slices(sprites: &[PackedSprite]) -> Result<(), String> {
+    for sprite in sprites {
+        if let Some(nine_slice) = &sprite.nine_slice {
+            let (width, height) = sprite.frame_size();
+            nine_slice.validate(width, height).map_err(|e| format!("帧 {}: {}", sprite.name, e))?;
+        }
+    }
+    Ok(())
+}
+
+/// 保存各页文件，或把所有页写入 `{name}.zip`
+///
+/// 纹理在这里按 `texture` 降低色深或量化后编码，缩放版本因此仍由全质量纹理缩小得到
+///
+/// # Returns
+/// * `Result<Vec<String>, String>` - 各页的数据文件路径，ZIP 模式下只有 ZIP 路径
+fn write_pages(
+    output_dir: &Path,
+    name: &str,
+    zip_output: bool,
+    texture: &TextureOptions,
+    pages: &[RenderedPage],
+) -> Result<Vec<String>, String> {
+    std::fs::create_dir_all(output_dir)
+        .map_err(|e| format!("无法创建输出目录: {}", e))?;
+    
+    if zip_output {
+        let mut entries: Vec<(&str, Vec<u8>)> = Vec::new();
+        for page in pages {
+            entries.push((&page.texture_name, encode_texture_file(&page.texture, texture)?));
+            entries.push((&page.data_name, page.data.clone()));
+            for (name, data) in page.groups.iter().chain(&page.notes) {
+                entries.push((name, data.clone()));
+            }
+        }
+        let zip_path = output_dir.join(format!("{}.zip", name));
+        let entries: Vec<(&str, &[u8])> = entries.iter().map(|(name, data)| (*name, data.as_slice())).collect();
+        write_zip(&zip_path, &entries)?;
+        return Ok(vec![zip_path.to_string_lossy().to_string()]);
+    }
+    
+    let mut paths = Vec::new();
+    for page in pages {
+        std::fs::write(output_dir.join(&page.texture_name), encode_texture_file(&page.texture, texture)?)
+            .map_err(|e| format!("写入纹理失败: {}", e))?;
+        let data_path = output_dir.join(&page.data_name);
+        std::fs::write(&data_path, &page.data)
+            .map_err(|e| format!("保存数据文件失败: {}", e))?;
+        for (name, data) in &page.groups {
+            std::fs::write(output_dir.join(name), data)
+                .map_err(|e| format!("保存分组数据文件 {} 失败: {}", name, e))?;
+        }
+        if let Some((name, data)) = &page.notes {
+            std::fs::write(output_dir.join(name), data)
+                .map_err(|e| format!("保存备注文件 {} 失败: {}", name, e))?;
+        }
+        paths.push(data_path.to_string_lossy().to_string());
+    }
+    Ok(paths)
+}
+
+/// 把若干文件写入 ZIP
+///
+/// # Arguments
+/// * `path` - ZIP 文件路径
+/// * `entries` - (文件名, 内容)
+fn write_zip(path: &Path, entries: &[(&str, &[u8])]) -> Result<(), String> {
+    let file = std::fs::File::create(path).map_err(|e| format!("无法创建 ZIP: {}", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, data) in entries {
+        zip.start_file(*name, options)
+            .map_err(|e| format!("写入 ZIP 失败: {}", e))?;
+        zip.write_all(data).map_err(|e| format!("写入 ZIP 失败: {}", e))?;
+    }
+    zip.finish().map_err(|e| format!("写入 ZIP 失败: {}", e))?;
+    Ok(())
+}
+
+/// 导出 GameMaker 横向条带图命令
+///
+/// 每个分组生成一张 `name_stripN.png`，帧按分组内精灵顺序排列
+///
+/// # Arguments
+/// * `groups` - 条带图分组列表
+/// * `output_dir` - 输出目录
+///
+/// # Returns
+/// * `Result<StripExportResult, String>` - 导出结果或错误信息
+#[tauri::command]
+pub async fn export_gamemaker_strips(
+    groups: Vec<StripGroup>,
+    output_dir: String,
+) -> Result<StripExportResult, String> {
+    run_blocking("export_gamemaker_strips", move || export_gamemaker_strips_impl(groups, output_dir)).await
+}
+
+fn export_gamemaker_strips_impl(
+    groups: Vec<StripGroup>,
+    output_dir: String,
+) -> Result<StripExportResult, String> {
+    println!("开始导出 {} 个 GameMaker 条带图", groups.len());
+
+    if groups.is_empty() {
+        return Err("没有分组可导出".to_string());
+    }
+    for group in &groups {
+        validate_strip_name(&group.name)?;
+    }
+
+    let _guard = try_lock("export_gamemaker_strips", [job_lock::output_dir(&output_dir)])?;
+    let output_dir = Path::new(&output_dir);
+    std::fs::create_dir_all(output_dir)
+        .map_err(|e| format!("无法创建输出目录: {}", e))?;
+
+    // 各分组互不依赖，并行导出，结果保持分组顺序
+    let results: Vec<Result<String, String>> = groups
+        .par_iter()
+        .map(|group| export_strip(group, output_dir))
+        .collect();
+
+    let mut exported_files = Vec::new();
+    let mut failed: Vec<(String, String)> = Vec::new();
+    let total = groups.len();
+    for (group, result) in groups.iter().zip(results) {
+        match result {
+            Ok(path) => {
+                println!("  - 条带图: {}", path);
+                exported_files.push(path);
+            }
+            Err(e) => failed.push((group.name.clone(), e)),
+        }
+    }
+    println!("✓ 条带图导出完成: {}/{} 个分组", exported_files.len(), total);
+
+    Ok(StripExportResult {
+        exported_files,
+        failed,
+        total,
+    })
+}
+
+/// 导出单个分组的条带图
+///
+/// # Returns
+/// * `Result<String, String>` - 条带图路径或错误信息
+fn export_strip(group: &StripGroup, output_dir: &Path) -> Result<String, String> {
+    let frames = group
+        .sprites
+        .iter()
+        .map(|sprite| load_image(&sprite.path, &ImageLoadLimits::default()).map(|img| img.to_rgba8()))
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let strip = compose_strip(&frames).ok_or("分组没有有效帧")?;
+
+    let strip_path = output_dir.join(strip_file_name(&group.name, frames.len()));
+    strip
+        .save(&strip_path)
+        .map_err(|e| format!("保存 PNG 失败: {}", e))?;
+    Ok(strip_path.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::exporters::SheetFormat;
+    use crate::core::plist_generator::PlistFormat;
+
+    #[test]
+    fn test_export_config_from_frontend() {
+        // 前端把打包结果转换为 camelCase 后原样传回
+        let json = r#"{
+            "packedSprites": [{
+                "id": "1", "name": "hero.png", "x": 0, "y": 0, "width": 8, "height": 4,
+                "rotated": false, "originalWidth": 10, "originalHeight": 6, "trimmed": true,
+                "offsetX": 1, "offsetY": -1
+            }],
+            "textureWidth": 16,
+            "textureHeight": 16,
+            "outputDir": "out",
+            "outputName": "atlas",
+            "zipOutput": false,
+            "jobId": "job"
+        }"#;
+        let config: ExportConfig = serde_json::from_str(json).unwrap();
+        let sprite = &config.packed_sprites[0];
+        assert_eq!((sprite.original_width, sprite.offset_y), (10, -1));
+        assert!(config.adjustments.is_none());
+        assert_eq!((config.format, config.extrude), (SheetFormat::Plist, 0));
+
+        assert_eq!(config.plist_format, PlistFormat::XmlV3);
+
+        let json = json.replace(r#""zipOutput": false"#, r#""zipOutput": false, "format": "jsonHash", "plistFormat": "binary""#);
+        let config: ExportConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!((config.format, config.plist_format), (SheetFormat::JsonHash, PlistFormat::Binary));
+        assert!(config.variants.is_empty());
+
+        let json = json.replace(r#""zipOutput": false"#, r#""zipOutput": false, "variants": [{"scale": 0.5, "suffix": "-sd"}]"#);
+        let config: ExportConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(config.variants, vec![ScaleVariant { scale: 0.5, suffix: "-sd".to_string() }]);
+    }
+
+    #[test]
+    fn test_write_pages() {
+        let dir = std::env::temp_dir().join(format!("ezplist_pages_{}", std::process::id()));
+        let cache = PackTrims::new();
+        let notes = HashMap::new();
+        let renderer = |format: SheetFormat| PageRenderer {
+            cache: &cache,
+            adjustments: None,
+            adjust_ids: None,
+            exporter: format.exporter(PlistFormat::default()),
+            extrude: 0,
+            bleed: 0,
+            grouping: None,
+            notes: &notes,
+            texture: TextureOptions::default(),
+            smartupdate: SmartUpdateMode::default(),
+        };
+        let plist = renderer(SheetFormat::Plist);
+        let page = |index| plist.render(&[], 4, 4, &page_file_stem("atlas", index)).unwrap();
+        let pages = vec![page(0), page(1)];
+
+        let paths = write_pages(&dir, "atlas", false, &TextureOptions::default(), &pages).unwrap();
+        assert_eq!(paths.len(), 2);
+        assert!(paths[1].ends_with("atlas_1.plist"));
+        assert!(dir.join("atlas_1.png").exists());
+
+        let paths = write_pages(&dir, "atlas", true, &TextureOptions::default(), &pages).unwrap();
+        let archive = zip::ZipArchive::new(std::fs::File::open(&paths[0]).unwrap()).unwrap();
+        let mut names: Vec<&str> = archive.file_names().collect();
+        names.sort();
+        assert_eq!(names, vec!["atlas_0.plist", "atlas_0.png", "atlas_1.plist", "atlas_1.png"]);
+
+        let json = renderer(SheetFormat::JsonArray).render(&[], 4, 4, "atlas").unwrap();
+        let paths = write_pages(&dir, "atlas", false, &TextureOptions::default(), &[json]).unwrap();
+        assert!(paths[0].ends_with("atlas.json"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_render_variant() {
+        let mut cache = PackTrims::new();
+        cache.insert("a".to_string(), crate::utils::trim::TrimResult::untrimmed(RgbaImage::from_pixel(6, 8, image::Rgba([255, 0, 0, 255]))));
+        let sprite = PackedSprite {
+            id: "a".to_string(),
+            name: "a.png".to_string(),
+            x: 2,
+            y: 0,
+            width: 6,
+            height: 8,
+            rotated: false,
+            original_width: 6,
+            original_height: 8,
+            trimmed: false,
+            offset_x: 0,
+            offset_y: 0,
+            flip_x: false,
+            flip_y: false,
+            anchor: None,
+            nine_slice: None,
+        };
+        let notes = HashMap::from([("a".to_string(), "发布前替换".to_string())]);
+        let renderer = PageRenderer {
+            cache: &cache,
+            adjustments: None,
+            adjust_ids: None,
+            exporter: SheetFormat::Plist.exporter(PlistFormat::default()),
+            extrude: 0,
+            bleed: 0,
+            grouping: None,
+            notes: &notes,
+            texture: TextureOptions::default(),
+            smartupdate: SmartUpdateMode::default(),
+        };
+        let page = renderer.render(std::slice::from_ref(&sprite), 8, 8, "ui").unwrap();
+        assert_eq!(page.notes.as_ref().map(|(name, _)| name.as_str()), Some("ui.notes.json"));
+        let sd = ScaleVariant { scale: 0.5, suffix: "-sd".to_string() };
+        let variant = renderer.render_variant(&page, std::slice::from_ref(&sprite), "ui", &sd).unwrap();
+        assert!(variant.notes.is_none());
+
+        assert_eq!((variant.texture_name.as_str(), variant.data_name.as_str()), ("ui-sd.png", "ui-sd.plist"));
+        assert_eq!(variant.texture.dimensions(), (4, 4));
+        let atlas = crate::core::atlas_plist::parse_atlas_plist(&variant.data).unwrap();
+        assert_eq!((atlas.texture.as_str(), atlas.width, atlas.height), ("ui-sd.png", 4, 4));
+        let frame = atlas.frame("a.png").unwrap();
+        assert_eq!((frame.x, frame.y, frame.width, frame.height), (1, 0, 3, 4));
+        assert_eq!(variant.texture.get_pixel(2, 2), &image::Rgba([255, 0, 0, 255]));
+
+        // 分组文件与全分辨率页引用同一张纹理，缩放版本同样分组
+        let grouped = PageRenderer { grouping: Some(&FrameGrouping::Folder), ..renderer };
+        let hero = PackedSprite { name: "hero/a.png".to_string(), ..sprite.clone() };
+        let page = grouped.render(std::slice::from_ref(&hero), 8, 8, "ui").unwrap();
+        assert_eq!(page.groups.len(), 1);
+        assert_eq!(page.groups[0].0, "ui_hero.plist");
+        let atlas = crate::core::atlas_plist::parse_atlas_plist(&page.groups[0].1).unwrap();
+        assert_eq!((atlas.texture.as_str(), atlas.frames.len()), ("ui.png", 1));
+        let variant = grouped.render_variant(&page, &[hero], "ui", &sd).unwrap();
+        assert_eq!(variant.groups[0].0, "ui-sd_hero.plist");
+
+        // 数据文件引用容器纹理并写入像素格式
+        let texture = TextureOptions {
+            pixel_format: crate::core::texture_format::PixelFormat::Rgba4444,
+            container: crate::core::texture_container::TextureContainer::PvrCcz,
+            ..Default::default()
+        };
+        let pvr = PageRenderer { texture, grouping: None, ..grouped };
+        let page = pvr.render(std::slice::from_ref(&sprite), 8, 8, "ui").unwrap();
+        assert_eq!(page.texture_name, "ui.pvr.ccz");
+        let atlas = crate::core::atlas_plist::parse_atlas_plist(&page.data).unwrap();
+        assert_eq!(atlas.texture, "ui.pvr.ccz");
+        assert!(String::from_utf8(page.data).unwrap().contains("<string>RGBA4444</string>"));
+    }
 }
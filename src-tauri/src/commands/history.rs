@@ -0,0 +1,248 @@
+/// 导出历史命令 (Export History Commands)
+///
+/// 记录每次成功的导出，并按记录重新打包、导出，源图修改后一键生成同样配置的图集
+
+use crate::commands::export::{export_sprite_sheet_impl, export_sprite_sheet_pages_impl};
+use crate::commands::pack::{load_sprite_image, run_pack, PackConfig, PackSessionStore};
+use crate::core::history::{inputs_hash, push_record, ExportRecord, ExportSnapshot};
+use crate::core::types::{ExportPage, PackResult, SpriteData};
+use crate::utils::guard::{catch_panic, run_blocking};
+use crate::utils::source_stamp::stamp_file;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::State;
+
+/// 导出历史存储（Tauri 托管状态）
+///
+/// 导出命令成功后写入记录；保存项目时写入项目文件，打开项目时整体替换。
+/// 克隆后共享同一份数据
+#[derive(Clone, Default)]
+pub struct ExportHistoryStore {
+    inner: Arc<Mutex<Vec<ExportRecord>>>,
+}
+
+impl ExportHistoryStore {
+    /// 所有记录（按时间先后）
+    pub fn list(&self) -> Vec<ExportRecord> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// 用项目中的记录替换全部历史
+    pub fn replace(&self, records: Vec<ExportRecord>) {
+        *self.inner.lock().unwrap_or_else(|e| e.into_inner()) = records;
+    }
+
+    /// 加入一条记录
+    pub fn push(&self, record: ExportRecord) {
+        push_record(&mut self.inner.lock().unwrap_or_else(|e| e.into_inner()), record);
+    }
+
+    /// 按 ID 取回记录
+    fn get(&self, id: &str) -> Result<ExportRecord, String> {
+        let records = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        records
+            .iter()
+            .find(|record| record.id == id)
+            .cloned()
+            .ok_or_else(|| format!("导出记录不存在: {}", id))
+    }
+}
+
+/// 重新导出的结果
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReExportResult {
+    /// 写出的数据文件或 ZIP 路径
+    pub output_paths: Vec<String>,
+    /// 源图或精灵列表是否与记录时不同
+    pub inputs_changed: bool,
+    /// 重新打包时的警告
+    pub warnings: Vec<String>,
+}
+
+/// 按当前来源文件计算输入指纹
+fn current_inputs_hash(sprites: &[SpriteData], pack_config: &serde_json::Value) -> String {
+    inputs_hash(sprites, pack_config, |path| stamp_file(path).ok().map(|stamp| stamp.hash))
+}
+
+/// 记录一次成功的导出
+///
+/// 只有由独立图片打包（`pack_sprites` / `pack_sprites_pages`）的结果能够重新打包，
+/// 其他打包结果（如会话打包）不记入历史
+///
+/// # Arguments
+/// * `store` - 打包结果存储
+/// * `history` - 导出历史存储
+/// * `job_id` - 导出使用的打包结果 ID
+/// * `export` - 导出配置快照
+/// * `outputs` - 写出的文件
+pub(crate) fn record_export(
+    store: &PackSessionStore,
+    history: &ExportHistoryStore,
+    job_id: &str,
+    export: ExportSnapshot,
+    outputs: Vec<String>,
+) {
+    let Some(inputs) = store.inputs(job_id) else {
+        println!("导出历史: 打包结果 {} 没有记录打包输入，本次导出不记入历史", job_id);
+        return;
+    };
+    let created_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_millis() as u64);
+    let record = ExportRecord {
+        id: uuid::Uuid::new_v4().to_string(),
+        created_ms,
+        sprites: inputs.sprites.clone(),
+        pack_config: inputs.config.clone(),
+        export,
+        inputs_hash: current_inputs_hash(&inputs.sprites, &inputs.config),
+        outputs,
+    };
+    println!("✓ 记入导出历史: {} ({} 个输出文件)", record.id, record.outputs.len());
+    history.push(record);
+}
+
+/// 获取导出历史命令
+///
+/// # Returns
+/// * `Result<Vec<ExportRecord>, String>` - 所有记录（按时间先后）
+#[tauri::command]
+pub async fn list_export_history(history: State<'_, ExportHistoryStore>) -> Result<Vec<ExportRecord>, String> {
+    catch_panic("list_export_history", async { Ok(history.list()) }).await
+}
+
+/// 重新导出命令
+///
+/// 用记录中的精灵列表与打包配置重新读取源图并打包，再按记录的导出配置写出文件
+///
+/// # Arguments
+/// * `history_id` - 导出记录 ID
+///
+/// # Returns
+/// * `Result<ReExportResult, String>` - 输出路径、输入是否变化与打包警告
+#[tauri::command]
+pub async fn re_export(
+    store: State<'_, PackSessionStore>,
+    history: State<'_, ExportHistoryStore>,
+    history_id: String,
+) -> Result<ReExportResult, String> {
+    let (store, history) = (store.inner().clone(), history.inner().clone());
+    run_blocking("re_export", move || re_export_impl(&store, &history, &history_id)).await
+}
+
+fn re_export_impl(
+    store: &PackSessionStore,
+    history: &ExportHistoryStore,
+    history_id: &str,
+) -> Result<ReExportResult, String> {
+    let record = history.get(history_id)?;
+    println!("开始重新导出: {} ({} 个精灵)", history_id, record.sprites.len());
+    let inputs_changed = current_inputs_hash(&record.sprites, &record.pack_config) != record.inputs_hash;
+    let config: Option<PackConfig> = serde_json::from_value(record.pack_config.clone())
+        .map_err(|e| format!("导出记录中的打包配置无效: {}", e))?;
+
+    let pages = run_pack(store, &record.sprites, config, record.export.multi_page(), load_sprite_image)?;
+    let job_id = pages[0].job_id.clone();
+    let warnings: Vec<String> = pages.iter().flat_map(|page| page.warnings.clone()).collect();
+    let output_paths = replay_export(store, record.export, pages);
+    store.release(&job_id);
+    let output_paths = output_paths?;
+
+    println!("✓ 重新导出成功: {} 个输出文件, 输入{}", output_paths.len(), if inputs_changed { "已变化" } else { "未变化" });
+    Ok(ReExportResult { output_paths, inputs_changed, warnings })
+}
+
+/// 把新的打包结果填入导出配置快照并导出
+fn replay_export(store: &PackSessionStore, export: ExportSnapshot, pages: Vec<PackResult>) -> Result<Vec<String>, String> {
+    match export {
+        ExportSnapshot::Single(mut config) => {
+            let page = pages.into_iter().next().ok_or("重新打包没有结果")?;
+            config.packed_sprites = page.packed_sprites;
+            config.texture_width = page.texture_width;
+            config.texture_height = page.texture_height;
+            config.job_id = page.job_id;
+            Ok(export_sprite_sheet_impl(store, config)?.output_files())
+        }
+        ExportSnapshot::Pages(mut config) => {
+            config.job_id = pages.first().map(|page| page.job_id.clone()).unwrap_or_default();
+            config.pages = pages
+                .into_iter()
+                .map(|page| ExportPage {
+                    packed_sprites: page.packed_sprites,
+                    texture_width: page.texture_width,
+                    texture_height: page.texture_height,
+                })
+                .collect();
+            Ok(export_sprite_sheet_pages_impl(store, config)?.output_paths)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::pack::pack_sprites_impl;
+    use crate::core::types::ExportConfig;
+    use image::{Rgba, RgbaImage};
+
+    #[test]
+    fn test_record_and_re_export() {
+        let dir = std::env::temp_dir().join(format!("ezplist_history_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("hero.png");
+        RgbaImage::from_pixel(6, 6, Rgba([255, 0, 0, 255])).save(&path).unwrap();
+        let sprite = SpriteData {
+            id: "hero".to_string(),
+            name: "hero.png".to_string(),
+            path: path.to_string_lossy().to_string(),
+            width: 6,
+            height: 6,
+            trimmed_width: 6,
+            trimmed_height: 6,
+            anchor: None,
+            nine_slice: None,
+        };
+
+        let (store, history) = (PackSessionStore::default(), ExportHistoryStore::default());
+        let packed = pack_sprites_impl(&store, vec![sprite], None).unwrap();
+        let config = ExportConfig {
+            packed_sprites: packed.packed_sprites,
+            texture_width: packed.texture_width,
+            texture_height: packed.texture_height,
+            output_dir: dir.join("out").to_string_lossy().to_string(),
+            output_name: "sheet".to_string(),
+            zip_output: false,
+            adjustments: None,
+            adjust_sprite_ids: None,
+            format: Default::default(),
+            plist_format: Default::default(),
+            extrude: 0,
+            bleed: 0,
+            variants: Vec::new(),
+            frame_groups: None,
+            frame_notes: Default::default(),
+            texture: Default::default(),
+            smartupdate: Default::default(),
+            job_id: packed.job_id.clone(),
+        };
+        let snapshot = ExportSnapshot::single(&config);
+        let exported = export_sprite_sheet_impl(&store, config).unwrap();
+        record_export(&store, &history, &packed.job_id, snapshot, exported.output_files());
+        let record = history.list().remove(0);
+        assert_eq!(record.outputs, vec![exported.output_path.clone()]);
+        // 未记录打包输入的结果不记入历史
+        record_export(&store, &history, "unknown", record.export.clone(), Vec::new());
+        assert_eq!(history.list().len(), 1);
+
+        // 修改源图后重新导出：输出覆盖原文件，并报告输入变化
+        std::fs::remove_file(&exported.output_path).unwrap();
+        RgbaImage::from_pixel(6, 6, Rgba([0, 0, 255, 255])).save(&path).unwrap();
+        let result = re_export_impl(&store, &history, &record.id).unwrap();
+        assert_eq!(result.output_paths, record.outputs);
+        assert!(result.inputs_changed);
+        assert!(std::path::Path::new(&result.output_paths[0]).is_file());
+        assert!(re_export_impl(&store, &history, "missing").is_err());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
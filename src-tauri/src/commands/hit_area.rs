@@ -0,0 +1,160 @@
+/// 碰撞区域命令 (Hit-Area Commands)
+///
+/// 为帧定义命名的碰撞区域（hitbox、hurtbox 等），并导出为 JSON 附属文件
+
+use crate::commands::session::SessionStore;
+use crate::core::hit_area::{generate_hit_area_json, validate_hit_areas, HitArea};
+use crate::utils::guard::{catch_panic, run_blocking};
+use std::collections::BTreeMap;
+use std::path::Path;
+use tauri::State;
+
+/// 设置帧的碰撞区域命令
+///
+/// 替换该帧已有的全部区域；传入空列表则删除该帧的区域
+///
+/// # Arguments
+/// * `session_id` - 会话 ID
+/// * `frame_name` - 帧名称
+/// * `areas` - 碰撞区域列表
+///
+/// # Returns
+/// * `Result<(), String>` - 校验失败时返回错误信息
+#[tauri::command]
+pub async fn set_hit_areas(
+    sessions: State<'_, SessionStore>,
+    session_id: String,
+    frame_name: String,
+    areas: Vec<HitArea>,
+) -> Result<(), String> {
+    catch_panic("set_hit_areas", set_hit_areas_impl(&sessions, session_id, frame_name, areas)).await
+}
+
+async fn set_hit_areas_impl(
+    sessions: &SessionStore,
+    session_id: String,
+    frame_name: String,
+    areas: Vec<HitArea>,
+) -> Result<(), String> {
+    if frame_name.is_empty() {
+        return Err("帧名称不能为空".to_string());
+    }
+    validate_hit_areas(&areas)?;
+
+    sessions.with_session(&session_id, |session| {
+        if areas.is_empty() {
+            session.hit_areas.remove(&frame_name);
+        } else {
+            session.hit_areas.insert(frame_name, areas);
+        }
+        Ok(())
+    })
+}
+
+/// 获取碰撞区域命令
+///
+/// # Arguments
+/// * `session_id` - 会话 ID
+///
+/// # Returns
+/// * `Result<BTreeMap<String, Vec<HitArea>>, String>` - 会话中所有帧的碰撞区域
+#[tauri::command]
+pub async fn get_hit_areas(
+    sessions: State<'_, SessionStore>,
+    session_id: String,
+) -> Result<BTreeMap<String, Vec<HitArea>>, String> {
+    catch_panic("get_hit_areas", get_hit_areas_impl(&sessions, session_id)).await
+}
+
+async fn get_hit_areas_impl(
+    sessions: &SessionStore,
+    session_id: String,
+) -> Result<BTreeMap<String, Vec<HitArea>>, String> {
+    sessions.with_session(&session_id, |session| Ok(session.hit_areas.clone()))
+}
+
+/// 导出碰撞区域 JSON 附属文件命令
+///
+/// # Arguments
+/// * `session_id` - 会话 ID
+/// * `output_path` - 输出文件路径（.json）
+/// * `frame_names` - 要导出的帧（可选，默认导出全部）
+///
+/// # Returns
+/// * `Result<String, String>` - 输出文件路径或错误信息
+#[tauri::command]
+pub async fn export_hit_areas(
+    sessions: State<'_, SessionStore>,
+    session_id: String,
+    output_path: String,
+    frame_names: Option<Vec<String>>,
+) -> Result<String, String> {
+    let sessions = sessions.inner().clone();
+    run_blocking("export_hit_areas", move || {
+        export_hit_areas_impl(&sessions, session_id, output_path, frame_names)
+    })
+    .await
+}
+
+fn export_hit_areas_impl(
+    sessions: &SessionStore,
+    session_id: String,
+    output_path: String,
+    frame_names: Option<Vec<String>>,
+) -> Result<String, String> {
+    let frames: BTreeMap<String, Vec<HitArea>> = sessions.with_session(&session_id, |session| {
+        Ok(match &frame_names {
+            Some(names) => session
+                .hit_areas
+                .iter()
+                .filter(|(name, _)| names.contains(name))
+                .map(|(name, areas)| (name.clone(), areas.clone()))
+                .collect(),
+            None => session.hit_areas.clone(),
+        })
+    })?;
+
+    if frames.is_empty() {
+        return Err("没有定义碰撞区域的帧".to_string());
+    }
+
+    let content = generate_hit_area_json(&frames)?;
+
+    if let Some(parent) = Path::new(&output_path).parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("无法创建输出目录: {}", e))?;
+    }
+    std::fs::write(&output_path, content)
+        .map_err(|e| format!("写入文件失败: {}", e))?;
+
+    println!("✓ 碰撞区域导出成功: {} ({} 帧)", output_path, frames.len());
+
+    Ok(output_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::hit_area::HitShape;
+
+    #[test]
+    fn test_hit_areas_are_per_session() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let sessions = SessionStore::default();
+        let a = sessions.create();
+        let b = sessions.create();
+        let area = HitArea {
+            name: "hitbox".to_string(),
+            shape: HitShape::Rect { x: 0.0, y: 0.0, width: 8.0, height: 8.0 },
+        };
+
+        rt.block_on(set_hit_areas_impl(&sessions, a.id.clone(), "walk_01".to_string(), vec![area.clone()]))
+            .unwrap();
+        assert_eq!(rt.block_on(get_hit_areas_impl(&sessions, a.id.clone())).unwrap()["walk_01"], vec![area]);
+        assert!(rt.block_on(get_hit_areas_impl(&sessions, b.id.clone())).unwrap().is_empty());
+
+        // 空列表删除该帧的区域
+        rt.block_on(set_hit_areas_impl(&sessions, a.id.clone(), "walk_01".to_string(), Vec::new())).unwrap();
+        assert!(rt.block_on(get_hit_areas_impl(&sessions, a.id)).unwrap().is_empty());
+    }
+}
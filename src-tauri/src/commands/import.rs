@@ -2,61 +2,214 @@
 /// 
 /// 处理用户导入图片的请求
 
+use crate::core::animated_image::{animation_frame_name, ImportedAnimation};
+use crate::core::folder_scan::{select_folder_files, FolderImportOptions};
+use crate::core::image_format::SourceFormat;
+use crate::core::image_processor::{apply_chroma_key, ChromaKey};
 use crate::core::types::{SpriteData, ImportResult};
-use std::path::Path;
+use crate::utils::guard::run_blocking;
+use crate::utils::image_loader::{load_animation, load_image, load_image_bytes, AnimatedFrame, ImageLoadLimits};
+use crate::utils::png_writer::encode_png;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::ipc::{InvokeBody, Request};
+
+/// 前端传入图像数据时携带精灵名的请求头（值需经 `encodeURIComponent` 编码）
+pub const SPRITE_NAME_HEADER: &str = "ezplist-sprite-name";
+
+/// 导入选项
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportOptions {
+    /// 允许导入的格式（如 ["png", "webp"]），默认允许所有支持的格式
+    pub allowed_formats: Option<Vec<SourceFormat>>,
+    /// 单张图片的最大像素数，默认 `limits::MAX_IMAGE_PIXELS`
+    pub max_pixels: Option<u64>,
+    /// 绿幕/蓝幕抠像（可选，抠像后的帧保存到临时目录，精灵路径指向该文件）
+    pub chroma_key: Option<ChromaKey>,
+}
+
+impl ImportOptions {
+    fn to_limits(&self) -> ImageLoadLimits {
+        let defaults = ImageLoadLimits::default();
+        ImageLoadLimits {
+            allowed_formats: self.allowed_formats.clone().unwrap_or(defaults.allowed_formats),
+            max_pixels: self.max_pixels.unwrap_or(defaults.max_pixels),
+        }
+    }
+}
 
 /// 导入图片命令
 /// 
+/// GIF、APNG 与动画 WebP 按帧展开为 `{文件名}_0001.png` 起的精灵（帧保存到临时目录），
+/// 各帧时长记录在结果的 `animations` 中
+/// 
 /// # Arguments
 /// * `paths` - 图片文件路径列表
+/// * `options` - 导入选项（可选，格式允许列表与像素上限）
 /// 
 /// # Returns
 /// * `Result<ImportResult, String>` - 导入结果或错误信息
 #[tauri::command]
-pub async fn import_images(paths: Vec<String>) -> Result<ImportResult, String> {
+pub async fn import_images(
+    paths: Vec<String>,
+    options: Option<ImportOptions>,
+) -> Result<ImportResult, String> {
+    run_blocking("import_images", move || import_images_impl(paths, options)).await
+}
+
+pub(crate) fn import_images_impl(
+    paths: Vec<String>,
+    options: Option<ImportOptions>,
+) -> Result<ImportResult, String> {
+    let limits = options.as_ref().map(ImportOptions::to_limits).unwrap_or_default();
+    let ExpandedImports { items, animations } = expand_animations(paths, &limits);
+
+    // 展开失败的动图直接记为失败，其余文件照常导入
+    let files = items.iter().filter_map(|item| item.as_ref().ok().cloned()).collect();
+    let mut imported = import_each(files, options)?.into_iter();
+    let results: Vec<Result<SpriteData, String>> = items
+        .into_iter()
+        .map(|item| item.and_then(|path| imported.next().unwrap_or_else(|| Err(format!("导入结果缺失: {}", path)))))
+        .collect();
+
+    let animations = animations
+        .into_iter()
+        .map(|(start, mut animation)| {
+            for (frame, result) in animation.frames.iter_mut().zip(&results[start..]) {
+                frame.sprite_id = result.as_ref().ok().map(|sprite| sprite.id.clone());
+            }
+            animation
+        })
+        .collect();
+    Ok(ImportResult { animations, ..collect_results(results) })
+}
+
+/// 动图展开后的导入列表
+struct ExpandedImports {
+    /// 展开后的文件路径（动图解码失败时为错误信息）
+    items: Vec<Result<String, String>>,
+    /// 每个动图的描述与其首帧在 `items` 中的位置
+    animations: Vec<(usize, ImportedAnimation)>,
+}
+
+/// 动图的帧文件路径与动画描述
+pub(crate) type AnimationFiles = (Vec<String>, ImportedAnimation);
+
+/// 把动图展开为逐帧 PNG
+///
+/// # Returns
+/// * `ExpandedImports` - 展开后的文件路径与每个动图的描述
+fn expand_animations(paths: Vec<String>, limits: &ImageLoadLimits) -> ExpandedImports {
+    let expanded: Vec<Result<Option<AnimationFiles>, String>> = paths
+        .par_iter()
+        .map(|path| match load_animation(path, limits) {
+            Ok(Some(frames)) => write_animation_frames(path, &frames).map(Some),
+            Ok(None) => Ok(None),
+            Err(e) => Err(e),
+        })
+        .collect();
+
+    let mut items = Vec::with_capacity(paths.len());
+    let mut animations = Vec::new();
+    for (path, result) in paths.into_iter().zip(expanded) {
+        match result {
+            Ok(Some((frames, animation))) => {
+                println!("✓ 动图 {} 展开为 {} 帧 ({} ms)", path, frames.len(), animation.duration_ms);
+                animations.push((items.len(), animation));
+                items.extend(frames.into_iter().map(Ok));
+            }
+            Ok(None) => items.push(Ok(path)),
+            Err(e) => {
+                println!("✗ 导入失败: {}", e);
+                items.push(Err(e));
+            }
+        }
+    }
+    ExpandedImports { items, animations }
+}
+
+/// 把动图的帧保存到临时目录
+///
+/// # Returns
+/// * `Result<AnimationFiles, String>` - 帧文件路径与动画描述
+pub(crate) fn write_animation_frames(path: &str, frames: &[AnimatedFrame]) -> Result<AnimationFiles, String> {
+    let name = animation_name(path);
+    let dir = animation_temp_dir()?;
+    let paths = frames
+        .iter()
+        .enumerate()
+        .map(|(index, frame)| {
+            let frame_path = dir.join(animation_frame_name(&name, index));
+            std::fs::write(&frame_path, encode_png(&frame.image)?)
+                .map_err(|e| format!("保存动图帧失败 {}: {}", frame_path.display(), e))?;
+            Ok(frame_path.to_string_lossy().to_string())
+        })
+        .collect::<Result<Vec<String>, String>>()?;
+    let delays: Vec<u32> = frames.iter().map(|frame| frame.delay_ms).collect();
+    Ok((paths, ImportedAnimation::new(&name, path, &delays)))
+}
+
+/// 动图帧的临时目录（打包时仍从文件读取，因此每次导入使用独立目录且不清理旧目录）
+pub(crate) fn animation_temp_dir() -> Result<PathBuf, String> {
+    let dir = std::env::temp_dir()
+        .join("ezplist_animation")
+        .join(uuid::Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&dir).map_err(|e| format!("无法创建临时目录: {}", e))?;
+    Ok(dir)
+}
+
+/// 动画名称（源文件名，不含扩展名）
+pub(crate) fn animation_name(path: &str) -> String {
+    Path::new(path)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .filter(|s| !s.is_empty())
+        .unwrap_or("animation")
+        .to_string()
+}
+
+/// 并行导入每个文件，结果与输入顺序一一对应
+fn import_each(
+    paths: Vec<String>,
+    options: Option<ImportOptions>,
+) -> Result<Vec<Result<SpriteData, String>>, String> {
     println!("开始导入 {} 张图片", paths.len());
     
+    let options = options.unwrap_or_default();
+    let limits = options.to_limits();
+    if limits.allowed_formats.is_empty() {
+        return Err("允许导入的格式列表为空".to_string());
+    }
+    let chroma = match &options.chroma_key {
+        Some(key) => {
+            key.validate()?;
+            // 打包时仍从文件读取，因此每次导入使用独立目录且不清理旧目录
+            let dir = std::env::temp_dir()
+                .join("ezplist_chroma")
+                .join(uuid::Uuid::new_v4().to_string());
+            std::fs::create_dir_all(&dir).map_err(|e| format!("无法创建临时目录: {}", e))?;
+            Some((key, dir))
+        }
+        None => None,
+    };
+    
+    // 并行解码，结果保持输入顺序
+    Ok(paths
+        .into_par_iter()
+        .map(|path_str| import_one(path_str, &limits, chroma.as_ref().map(|(key, dir)| (*key, dir))))
+        .collect())
+}
+
+/// 汇总每个文件的导入结果
+fn collect_results(results: Vec<Result<SpriteData, String>>) -> ImportResult {
     let mut sprites = Vec::new();
     let mut failed = Vec::new();
-    
-    for path_str in paths {
-        let path = Path::new(&path_str);
-        
-        // 检查文件是否存在
-        if !path.exists() {
-            failed.push(format!("文件不存在: {}", path_str));
-            continue;
-        }
-        
-        // 尝试加载图片
-        match image::open(path) {
-            Ok(img) => {
-                let rgba = img.to_rgba8();
-                let (width, height) = rgba.dimensions();
-                
-                let sprite = SpriteData {
-                    id: uuid::Uuid::new_v4().to_string(),
-                    name: path
-                        .file_name()
-                        .and_then(|n| n.to_str())
-                        .unwrap_or("unknown.png")
-                        .to_string(),
-                    path: path_str,
-                    width,
-                    height,
-                    // 后续添加裁剪后的尺寸
-                    trimmed_width: width,
-                    trimmed_height: height,
-                };
-                
-                println!("✓ 成功导入: {}", sprite.name);
-                sprites.push(sprite);
-            }
-            Err(e) => {
-                let err_msg = format!("无法加载图片 {}: {}", path_str, e);
-                failed.push(err_msg);
-                println!("✗ 导入失败: {}", path_str);
-            }
+    for result in results {
+        match result {
+            Ok(sprite) => sprites.push(sprite),
+            Err(e) => failed.push(e),
         }
     }
     
@@ -64,9 +217,307 @@ pub async fn import_images(paths: Vec<String>) -> Result<ImportResult, String> {
     let failed_count = failed.len();
     println!("导入完成: 成功 {}, 失败 {}", success_count, failed_count);
     
-    Ok(ImportResult {
+    ImportResult {
         sprites,
         failed,
         total: success_count + failed_count,
-    })
+        animations: Vec::new(),
+    }
+}
+
+/// 文件夹导入结果
+#[derive(Debug, Serialize)]
+pub struct FolderImportResult {
+    /// 导入结果（精灵名称按选项带子文件夹前缀）
+    #[serde(flatten)]
+    pub result: ImportResult,
+    /// 帧名称重名等警告
+    pub warnings: Vec<String>,
+}
+
+/// 导入文件夹命令
+///
+/// 扫描文件夹（默认包含子文件夹，跳过隐藏文件与符号链接目录），按扩展名与通配符筛选后导入，
+/// 图片按相对路径排序
+///
+/// # Arguments
+/// * `dir` - 文件夹路径
+/// * `folder` - 筛选与命名选项（可选）
+/// * `options` - 导入选项（可选，与 `import_images` 相同）
+///
+/// # Returns
+/// * `Result<FolderImportResult, String>` - 导入结果或错误信息
+#[tauri::command]
+pub async fn import_folder(
+    dir: String,
+    folder: Option<FolderImportOptions>,
+    options: Option<ImportOptions>,
+) -> Result<FolderImportResult, String> {
+    run_blocking("import_folder", move || import_folder_impl(dir, folder, options)).await
+}
+
+pub(crate) fn import_folder_impl(
+    dir: String,
+    folder: Option<FolderImportOptions>,
+    options: Option<ImportOptions>,
+) -> Result<FolderImportResult, String> {
+    let folder = folder.unwrap_or_default();
+    let root = Path::new(&dir);
+    if !root.is_dir() {
+        return Err(format!("文件夹不存在: {}", dir));
+    }
+    let mut files = Vec::new();
+    collect_files(root, "", folder.recursive, &mut files)?;
+    let (entries, warnings) = select_folder_files(&files, &folder)?;
+    if entries.is_empty() {
+        return Err(format!("文件夹中没有符合条件的图片: {}", dir));
+    }
+    for warning in &warnings {
+        println!("警告: {}", warning);
+    }
+
+    let paths = entries
+        .iter()
+        .map(|entry| root.join(&entry.relative_path).to_string_lossy().to_string())
+        .collect();
+    let results = import_each(paths, options)?
+        .into_iter()
+        .zip(&entries)
+        .map(|(result, entry)| {
+            result.map(|sprite| SpriteData { name: entry.frame_name.clone(), ..sprite })
+        })
+        .collect();
+    Ok(FolderImportResult { result: collect_results(results), warnings })
+}
+
+/// 递归列出文件夹中的文件（相对路径以 `/` 分隔）
+fn collect_files(dir: &Path, prefix: &str, recursive: bool, files: &mut Vec<String>) -> Result<(), String> {
+    let entries = std::fs::read_dir(dir).map_err(|e| format!("无法读取文件夹 {}: {}", dir.display(), e))?;
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with('.') {
+            continue;
+        }
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let relative = if prefix.is_empty() { name } else { format!("{}/{}", prefix, name) };
+        if file_type.is_dir() {
+            if recursive {
+                collect_files(&entry.path(), &relative, recursive, files)?;
+            }
+        } else if entry.path().is_file() {
+            files.push(relative);
+        }
+    }
+    Ok(())
+}
+
+/// 导入图像数据命令
+///
+/// 前端把画布生成或编辑后的图像直接以二进制请求体发送
+/// （`invoke('import_image_buffer', bytes, { headers: { 'ezplist-sprite-name': encodeURIComponent(name) } })`），
+/// 后端解码后写入临时目录，之后与普通导入的精灵一样按路径参与打包
+///
+/// # Arguments
+/// * `request` - 原始请求：请求体为编码后的图像数据，`SPRITE_NAME_HEADER` 请求头为精灵名
+///
+/// # Returns
+/// * `Result<SpriteData, String>` - 导入的精灵或错误信息
+#[tauri::command]
+pub async fn import_image_buffer(request: Request<'_>) -> Result<SpriteData, String> {
+    let name = request
+        .headers()
+        .get(SPRITE_NAME_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| format!("缺少请求头 {}", SPRITE_NAME_HEADER))?;
+    let name = decode_component(name)?;
+    let bytes = match request.body() {
+        InvokeBody::Raw(bytes) => bytes.clone(),
+        InvokeBody::Json(_) => return Err("图像数据必须以二进制请求体发送".to_string()),
+    };
+    run_blocking("import_image_buffer", move || import_image_buffer_impl(&name, &bytes)).await
+}
+
+pub(crate) fn import_image_buffer_impl(name: &str, bytes: &[u8]) -> Result<SpriteData, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("精灵名称不能为空".to_string());
+    }
+    if bytes.is_empty() {
+        return Err(format!("图像数据为空: {}", name));
+    }
+
+    let img = load_image_bytes(bytes, name, &ImageLoadLimits::default())
+        .inspect_err(|_| println!("✗ 导入失败: {}", name))?;
+    let rgba = img.to_rgba8();
+    let (width, height) = rgba.dimensions();
+
+    // 打包时仍从文件读取，因此统一保存为 PNG，每次导入使用独立目录
+    let id = uuid::Uuid::new_v4().to_string();
+    let dir = std::env::temp_dir().join("ezplist_buffers").join(&id);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("无法创建临时目录: {}", e))?;
+    let path = dir.join(format!("{}.png", id));
+    std::fs::write(&path, encode_png(&rgba)?).map_err(|e| format!("保存图像数据失败 {}: {}", name, e))?;
+
+    let sprite = SpriteData {
+        id,
+        name: name.to_string(),
+        path: path.to_string_lossy().to_string(),
+        width,
+        height,
+        trimmed_width: width,
+        trimmed_height: height,
+        anchor: None,
+        nine_slice: None,
+    };
+    println!("✓ 成功导入图像数据: {} ({}x{})", sprite.name, width, height);
+    Ok(sprite)
+}
+
+/// 解码 `encodeURIComponent` 编码的请求头值（请求头只能携带 ASCII）
+fn decode_component(value: &str) -> Result<String, String> {
+    let invalid = || format!("请求头 {} 编码无效: {}", SPRITE_NAME_HEADER, value);
+    let raw = value.as_bytes();
+    let mut bytes = Vec::with_capacity(raw.len());
+    let mut i = 0;
+    while i < raw.len() {
+        if raw[i] == b'%' {
+            let hex = raw
+                .get(i + 1..i + 3)
+                .filter(|h| h.iter().all(u8::is_ascii_hexdigit))
+                .and_then(|h| std::str::from_utf8(h).ok())
+                .ok_or_else(invalid)?;
+            bytes.push(u8::from_str_radix(hex, 16).map_err(|_| invalid())?);
+            i += 3;
+        } else {
+            bytes.push(raw[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| invalid())
+}
+
+/// 导入单张图片（设置抠像时把结果写入 `chroma` 中的目录）
+fn import_one(
+    path_str: String,
+    limits: &ImageLoadLimits,
+    chroma: Option<(&ChromaKey, &PathBuf)>,
+) -> Result<SpriteData, String> {
+    let path = Path::new(&path_str);
+    
+    // 检查文件是否存在
+    if !path.exists() {
+        return Err(format!("文件不存在: {}", path_str));
+    }
+    
+    // 尝试加载图片（按文件头识别格式并检查尺寸）
+    let img = load_image(&path_str, limits).inspect_err(|_| println!("✗ 导入失败: {}", path_str))?;
+    let mut rgba = img.to_rgba8();
+    let (width, height) = rgba.dimensions();
+    let id = uuid::Uuid::new_v4().to_string();
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("unknown.png")
+        .to_string();
+    
+    let path_str = match chroma {
+        Some((key, dir)) => {
+            apply_chroma_key(&mut rgba, key);
+            let keyed_path = dir.join(format!("{}.png", id));
+            std::fs::write(&keyed_path, encode_png(&rgba)?)
+                .map_err(|e| format!("保存抠像结果失败 {}: {}", name, e))?;
+            keyed_path.to_string_lossy().to_string()
+        }
+        None => path_str,
+    };
+    
+    let sprite = SpriteData {
+        id,
+        name,
+        path: path_str,
+        width,
+        height,
+        // 后续添加裁剪后的尺寸
+        trimmed_width: width,
+        trimmed_height: height,
+        anchor: None,
+        nine_slice: None,
+    };
+    
+    println!("✓ 成功导入: {}", sprite.name);
+    Ok(sprite)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+
+    #[test]
+    fn test_import_with_chroma_key() {
+        let dir = std::env::temp_dir().join(format!("ezplist_import_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("hero.png");
+        let mut img = RgbaImage::from_pixel(2, 1, Rgba([0, 255, 0, 255]));
+        img.put_pixel(1, 0, Rgba([200, 50, 50, 255]));
+        img.save(&source).unwrap();
+
+        let options = ImportOptions { chroma_key: Some(ChromaKey::default()), ..Default::default() };
+        let result = import_images_impl(vec![source.to_string_lossy().to_string()], Some(options)).unwrap();
+        let sprite = &result.sprites[0];
+        // 名称保持原文件名，路径指向抠像后的临时文件
+        assert_eq!(sprite.name, "hero.png");
+        assert_ne!(Path::new(&sprite.path), source);
+        let keyed = image::open(&sprite.path).unwrap().to_rgba8();
+        assert_eq!((keyed.get_pixel(0, 0)[3], keyed.get_pixel(1, 0)[3]), (0, 255));
+
+        std::fs::remove_file(&sprite.path).ok();
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_import_folder() {
+        let dir = std::env::temp_dir().join(format!("ezplist_folder_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("enemies").join(".cache")).unwrap();
+        let save = |relative: &str| {
+            RgbaImage::from_pixel(2, 2, Rgba([255, 0, 0, 255])).save(dir.join(relative)).unwrap();
+        };
+        save("hero.png");
+        save("enemies/slime_01.png");
+        save("enemies/.cache/slime_01.png");
+        std::fs::write(dir.join("enemies").join("readme.txt"), "notes").unwrap();
+
+        let folder = FolderImportOptions { subfolder_names: true, ..Default::default() };
+        let imported = import_folder_impl(dir.to_string_lossy().to_string(), Some(folder), None).unwrap();
+        let names: Vec<&str> = imported.result.sprites.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["enemies/slime_01.png", "hero.png"]);
+        assert!(imported.warnings.is_empty());
+
+        let flat = FolderImportOptions { recursive: false, ..Default::default() };
+        let imported = import_folder_impl(dir.to_string_lossy().to_string(), Some(flat), None).unwrap();
+        assert_eq!(imported.result.total, 1);
+        let none = FolderImportOptions { include: Some("boss_*".to_string()), ..Default::default() };
+        assert!(import_folder_impl(dir.to_string_lossy().to_string(), Some(none), None).is_err());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_import_image_buffer() {
+        let img = RgbaImage::from_pixel(3, 2, Rgba([10, 20, 30, 255]));
+        let mut bytes = Vec::new();
+        img.write_to(&mut std::io::Cursor::new(&mut bytes), image::ImageFormat::Png).unwrap();
+
+        let sprite = import_image_buffer_impl("canvas/brush.png", &bytes).unwrap();
+        assert_eq!(sprite.name, "canvas/brush.png");
+        assert_eq!((sprite.width, sprite.height), (3, 2));
+        assert_eq!(image::open(&sprite.path).unwrap().to_rgba8(), img);
+        std::fs::remove_dir_all(Path::new(&sprite.path).parent().unwrap()).ok();
+
+        assert!(import_image_buffer_impl(" ", &bytes).is_err());
+        assert!(import_image_buffer_impl("bad.png", b"not an image").is_err());
+        assert_eq!(decode_component("%E8%8B%B1%E9%9B%84%20a.png").unwrap(), "英雄 a.png");
+        assert!(decode_component("%E8%8").is_err());
+    }
 }
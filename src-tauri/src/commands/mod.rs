@@ -7,12 +7,42 @@ pub mod pack;
 pub mod export;
 pub mod split;
 pub mod compose;
+pub mod template;
+pub mod animation;
+pub mod outline;
+pub mod physics;
+pub mod hit_area;
+pub mod palette;
+pub mod session;
+pub mod settings;
+pub mod patch;
+pub mod unpack;
+pub mod video;
+pub mod profile;
+pub mod window;
+pub mod project;
+pub mod history;
 
 pub use import::*;
 pub use pack::*;
 pub use export::*;
 pub use split::*;
 pub use compose::*;
+pub use template::*;
+pub use animation::*;
+pub use outline::*;
+pub use physics::*;
+pub use hit_area::*;
+pub use palette::*;
+pub use session::*;
+pub use settings::*;
+pub use patch::*;
+pub use unpack::*;
+pub use video::*;
+pub use profile::*;
+pub use window::*;
+pub use project::*;
+pub use history::*;
 
 /// 测试命令：问候
 #[tauri::command]
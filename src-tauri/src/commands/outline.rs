@@ -0,0 +1,48 @@
+/// 轮廓提取命令 (Outline Extraction Commands)
+///
+/// 返回帧不透明区域的轮廓多边形，供前端编辑碰撞区域使用
+
+use crate::core::outline::{self, OutlineOptions, Point};
+use crate::core::types::FrameInfo;
+use crate::utils::guard::run_blocking;
+use crate::utils::image_loader::{crop_frame, load_image, ImageLoadLimits};
+
+/// 提取帧轮廓命令
+///
+/// # Arguments
+/// * `path` - 图像文件路径（单张精灵或图集）
+/// * `frame` - 图集中的帧（可选，不设置则使用整张图像）
+/// * `options` - 提取选项（可选）
+///
+/// # Returns
+/// * `Result<Vec<Vec<Point>>, String>` - 每个不透明区域的轮廓多边形（坐标相对帧左上角）
+#[tauri::command]
+pub async fn extract_outline(
+    path: String,
+    frame: Option<FrameInfo>,
+    options: Option<OutlineOptions>,
+) -> Result<Vec<Vec<Point>>, String> {
+    run_blocking("extract_outline", move || extract_outline_impl(path, frame, options)).await
+}
+
+fn extract_outline_impl(
+    path: String,
+    frame: Option<FrameInfo>,
+    options: Option<OutlineOptions>,
+) -> Result<Vec<Vec<Point>>, String> {
+    let source_img = load_image(&path, &ImageLoadLimits::default())?;
+    let image = match &frame {
+        Some(frame) => crop_frame(&source_img, frame)?,
+        None => source_img.to_rgba8(),
+    };
+
+    let polygons = outline::extract_outline(&image, &options.unwrap_or_default());
+
+    println!(
+        "✓ 轮廓提取完成: {} 个区域, {} 个顶点",
+        polygons.len(),
+        polygons.iter().map(|p| p.len()).sum::<usize>()
+    );
+
+    Ok(polygons)
+}
@@ -2,62 +2,255 @@
 /// 
 /// 使用 MaxRects 算法打包精灵图，支持透明裁剪和旋转优化
 
-use crate::core::packer::{MaxRectsPacker, SpriteInput, find_optimal_size};
+use crate::core::effects::{bake_effects, validate_effects, SpriteEffect};
+use crate::core::frame_align::{normalize_frames, NormalizeOptions};
+use crate::core::frame_groups::{sprite_groups, FrameGrouping};
+use crate::core::frame_names::{resolve_collisions, NameCollisionPolicy};
+use crate::core::job::{EmptySpritePolicy, PackJob};
+use crate::core::mipmap::{mip_levels, validate_mipmap_levels};
+use crate::core::exporters::SheetFormat;
+use crate::core::mirror::{find_mirrors, MirrorMatch};
+use crate::core::packer::{PackHeuristic, SizeConstraint};
+use crate::core::solid::{solid_regions, SolidRegion};
+use crate::core::upscale::{upscale_image, UpscaleOptions};
+use crate::core::summary::texture_summary;
+use crate::core::timings::{Phase, Timings};
 use crate::core::types::{SpriteData, PackResult};
-use crate::utils::trim::{trim_transparent, TrimResult};
-use image::ImageReader;
-use serde::Deserialize;
-use std::collections::HashMap;
-use std::sync::Mutex;
-use std::sync::LazyLock;
+use crate::utils::guard::{catch_panic, run_blocking};
+use crate::utils::image_loader::{load_image, ImageLoadLimits};
+use crate::utils::number_locale::number_locale;
+use crate::utils::trim::{ColorKey, TrimMode, TrimResult, DEFAULT_ALPHA_THRESHOLD};
+use image::RgbaImage;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tauri::State;
+
+/// 未设置最大尺寸时的默认纹理边长
+pub(crate) const DEFAULT_MAX_TEXTURE_SIZE: u32 = 2048;
 
 /// 打包配置
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PackConfig {
     /// 最大宽度
     pub max_width: Option<u32>,
     /// 最大高度
     pub max_height: Option<u32>,
-    /// 是否启用透明裁剪
+    /// 是否启用透明裁剪（未设置 `trim_mode` 时生效：启用为 `crop-keep-size`，关闭为 `none`）
     pub trim_transparent: Option<bool>,
+    /// 裁剪模式（可选，优先于 `trim_transparent`）：`trim` 缩小为内容尺寸，
+    /// `crop-keep-size` 保留原始尺寸，`none` 不裁剪
+    pub trim_mode: Option<TrimMode>,
+    /// 裁剪时的 alpha 阈值（可选，默认 1）：不大于此值的像素视为透明
+    pub alpha_threshold: Option<u8>,
+    /// 裁剪时在内容四周保留的透明像素数（可选，默认 0；供在不透明区域外采样的着色器使用）
+    pub trim_margin: Option<u32>,
     /// 是否允许旋转
     pub allow_rotation: Option<bool>,
     /// 精灵间距
     pub padding: Option<u32>,
+    /// 边缘挤出像素（可选，导出时用精灵边缘像素填充，避免线性过滤时的渗色）
+    pub extrude: Option<u32>,
     /// 是否自动选择最优尺寸
     pub auto_size: Option<bool>,
+    /// 纹理尺寸约束（可选，默认 2 的幂；自动尺寸时纹理按约束紧贴内容）
+    pub size_constraint: Option<SizeConstraint>,
+    /// 强制使用最大尺寸作为纹理尺寸（可选，优先于自动尺寸）
+    pub force_max_size: Option<bool>,
+    /// 打包启发式策略（可选，默认 BSSF）
+    pub heuristic: Option<PackHeuristic>,
+    /// 尝试所有启发式策略与放置顺序并保留最优结果（可选，启用后忽略 `heuristic`）
+    pub auto_heuristic: Option<bool>,
+    /// 打包前烘焙的投影/描边效果
+    pub effects: Option<Vec<SpriteEffect>>,
+    /// 应用效果的精灵 ID（为空时应用于全部精灵）
+    pub effect_sprite_ids: Option<Vec<String>>,
+    /// 打包前的像素画放大（在烘焙效果之前执行）
+    pub upscale: Option<UpscaleOptions>,
+    /// 放大的精灵 ID（为空时放大全部精灵）
+    pub upscale_sprite_ids: Option<Vec<String>>,
+    /// 图集将生成的 mipmap 级数（可选，自动增大间距）
+    pub mipmap_levels: Option<u32>,
+    /// 完全透明精灵的处理策略（可选，默认保留为 1x1 占位）
+    pub empty_sprites: Option<EmptySpritePolicy>,
+    /// 禁止旋转的精灵 ID（可选，全局允许旋转时逐个锁定）
+    pub rotation_locked_ids: Option<Vec<String>>,
+    /// 按颜色键而不是 alpha 裁剪（可选，用于没有透明通道的 JPEG/BMP，仅在启用裁剪时生效）
+    pub color_key: Option<ColorKey>,
+    /// 使用颜色键的精灵 ID（为空时应用于全部精灵）
+    pub color_key_sprite_ids: Option<Vec<String>>,
+    /// 打包前把帧对齐到统一画布（可选，在放大与效果之后执行，用于消除动画抖动）
+    pub normalize: Option<NormalizeOptions>,
+    /// 参与对齐的精灵 ID（为空时对齐全部精灵）
+    pub normalize_sprite_ids: Option<Vec<String>>,
+    /// 按精灵 ID 指定的虚拟源尺寸（可选，原图居中放入，不小于图像尺寸）
+    pub source_sizes: Option<HashMap<String, SourceSize>>,
+    /// 把翻转帧合并到源帧（可选，仅在启用裁剪时生效；翻转帧不占纹理空间，输出 flipX/flipY）
+    pub alias_mirrors: Option<bool>,
+    /// 在每页图集中注入 4x4 的纯白区域 `__white`（可选，供纯色图元与精灵合批绘制）
+    pub white_pixel: Option<bool>,
+    /// 额外注入的纯色区域（可选，名称不能与精灵重名）
+    pub solid_regions: Option<Vec<SolidRegion>>,
+    /// 导出的数据格式（可选）：格式无法表示旋转或翻转帧时自动关闭旋转与翻转帧合并，并给出警告
+    pub target_format: Option<SheetFormat>,
+    /// 页面亲和分组（可选，仅多页打包）：同一文件夹或自定义分组的帧总在同一页，动画不会跨纹理
+    pub page_affinity: Option<FrameGrouping>,
+    /// 帧名称重复时的处理（可选，默认报错；自动改名的结果在 `renamed_frames` 中返回）
+    pub name_collision: Option<NameCollisionPolicy>,
+}
+
+/// 虚拟源尺寸（sourceSize 覆盖）
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceSize {
+    /// 宽度
+    pub width: u32,
+    /// 高度
+    pub height: u32,
 }
 
 impl Default for PackConfig {
     fn default() -> Self {
         Self {
-            max_width: Some(2048),
-            max_height: Some(2048),
+            max_width: Some(DEFAULT_MAX_TEXTURE_SIZE),
+            max_height: Some(DEFAULT_MAX_TEXTURE_SIZE),
             trim_transparent: Some(true),
+            trim_mode: None,
+            alpha_threshold: None,
+            trim_margin: None,
             allow_rotation: Some(true),
             padding: Some(1),
+            extrude: None,
             auto_size: Some(true),
+            size_constraint: None,
+            force_max_size: None,
+            heuristic: None,
+            auto_heuristic: None,
+            effects: None,
+            effect_sprite_ids: None,
+            upscale: None,
+            upscale_sprite_ids: None,
+            mipmap_levels: None,
+            empty_sprites: None,
+            rotation_locked_ids: None,
+            color_key: None,
+            color_key_sprite_ids: None,
+            normalize: None,
+            normalize_sprite_ids: None,
+            source_sizes: None,
+            alias_mirrors: None,
+            white_pixel: None,
+            solid_regions: None,
+            page_affinity: None,
+            target_format: None,
+            name_collision: None,
         }
     }
 }
 
-/// 裁剪缓存（用于后续导出）- 使用线程安全的 Mutex
-static TRIM_CACHE: LazyLock<Mutex<HashMap<String, TrimResult>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+/// 保留的打包结果数量（超出时丢弃最早的结果）
+const MAX_PACK_SESSIONS: usize = 8;
+
+/// 一次打包的裁剪结果（按精灵 ID 索引）
+pub type PackTrims = HashMap<String, TrimResult>;
 
-/// 获取裁剪缓存（克隆）
-pub fn get_trim_cache() -> HashMap<String, TrimResult> {
-    TRIM_CACHE.lock().unwrap().clone()
+/// 一次打包的输入（导出时记入导出历史，供重新导出）
+#[derive(Debug, Clone)]
+pub struct PackInputs {
+    /// 参与打包的精灵
+    pub sprites: Vec<SpriteData>,
+    /// 打包配置（`Option<PackConfig>` 的 JSON）
+    pub config: serde_json::Value,
 }
 
-/// 清空裁剪缓存
-fn clear_trim_cache() {
-    TRIM_CACHE.lock().unwrap().clear();
+impl PackInputs {
+    fn new(sprites: &[SpriteData], config: Option<&PackConfig>) -> Self {
+        Self {
+            sprites: sprites.to_vec(),
+            config: serde_json::to_value(config).unwrap_or_default(),
+        }
+    }
 }
 
-/// 存入裁剪缓存
-fn cache_trim_result(id: String, result: TrimResult) {
-    TRIM_CACHE.lock().unwrap().insert(id, result);
+/// 打包结果存储（Tauri 托管状态）
+///
+/// 每次打包的裁剪结果以返回给前端的 `job_id` 为键保存，导出时按 ID 取回，
+/// 并发的打包与导出各自使用自己的结果。克隆后共享同一份数据，供自动化接口使用
+#[derive(Clone, Default)]
+pub struct PackSessionStore {
+    inner: Arc<Mutex<PackSessions>>,
+}
+
+#[derive(Default)]
+struct PackSessions {
+    trims: HashMap<String, Arc<PackTrims>>,
+    /// 打包输入（只记录由独立图片打包的结果）
+    inputs: HashMap<String, Arc<PackInputs>>,
+    /// 按存入顺序排列的 ID
+    order: VecDeque<String>,
+}
+
+impl PackSessionStore {
+    /// 存入一次打包的裁剪结果
+    ///
+    /// # Returns
+    /// * `String` - 新的打包结果 ID
+    pub fn insert(&self, trims: PackTrims) -> String {
+        let job_id = uuid::Uuid::new_v4().to_string();
+        let mut sessions = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        sessions.trims.insert(job_id.clone(), Arc::new(trims));
+        sessions.order.push_back(job_id.clone());
+        while sessions.order.len() > MAX_PACK_SESSIONS {
+            if let Some(oldest) = sessions.order.pop_front() {
+                sessions.trims.remove(&oldest);
+                sessions.inputs.remove(&oldest);
+            }
+        }
+        job_id
+    }
+
+    /// 取回打包结果
+    ///
+    /// # Returns
+    /// * `Result<Arc<PackTrims>, String>` - 裁剪结果；ID 不存在或已被丢弃时返回错误
+    pub fn get(&self, job_id: &str) -> Result<Arc<PackTrims>, String> {
+        let sessions = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        sessions
+            .trims
+            .get(job_id)
+            .cloned()
+            .ok_or_else(|| format!("打包结果不存在或已过期: {}，请重新打包后再导出", job_id))
+    }
+
+    /// 释放打包结果
+    ///
+    /// # Returns
+    /// * `bool` - 该 ID 之前是否存在
+    pub fn release(&self, job_id: &str) -> bool {
+        let mut sessions = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        sessions.order.retain(|id| id != job_id);
+        sessions.inputs.remove(job_id);
+        sessions.trims.remove(job_id).is_some()
+    }
+
+    /// 记录打包输入（打包结果已被丢弃时忽略）
+    pub fn set_inputs(&self, job_id: &str, inputs: PackInputs) {
+        let mut sessions = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        if sessions.trims.contains_key(job_id) {
+            sessions.inputs.insert(job_id.to_string(), Arc::new(inputs));
+        }
+    }
+
+    /// 取回打包输入
+    ///
+    /// # Returns
+    /// * `Option<Arc<PackInputs>>` - 打包输入；结果不存在或不是由独立图片打包时为 None
+    pub fn inputs(&self, job_id: &str) -> Option<Arc<PackInputs>> {
+        let sessions = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        sessions.inputs.get(job_id).cloned()
+    }
 }
 
 /// 打包精灵图命令
@@ -70,159 +263,431 @@ fn cache_trim_result(id: String, result: TrimResult) {
 /// * `Result<PackResult, String>` - 打包结果或错误信息
 #[tauri::command]
 pub async fn pack_sprites(
+    store: State<'_, PackSessionStore>,
+    sprites: Vec<SpriteData>,
+    config: Option<PackConfig>,
+) -> Result<PackResult, String> {
+    pack_sprites_in(store.inner().clone(), sprites, config).await
+}
+
+/// 打包并把裁剪结果存入指定存储（自动化接口使用）
+pub(crate) async fn pack_sprites_in(
+    store: PackSessionStore,
+    sprites: Vec<SpriteData>,
+    config: Option<PackConfig>,
+) -> Result<PackResult, String> {
+    run_blocking("pack_sprites", move || pack_sprites_impl(&store, sprites, config)).await
+}
+
+pub(crate) fn pack_sprites_impl(
+    store: &PackSessionStore,
+    sprites: Vec<SpriteData>,
+    config: Option<PackConfig>,
+) -> Result<PackResult, String> {
+    let inputs = PackInputs::new(&sprites, config.as_ref());
+    let result = pack_with_loader(store, &sprites, config, load_sprite_image)?;
+    store.set_inputs(&result.job_id, inputs);
+    Ok(result)
+}
+
+/// 多页打包精灵图命令
+///
+/// 一张纹理放不下时把剩余精灵放入新的一页，每页都不超过最大尺寸
+///
+/// # Arguments
+/// * `sprites` - 待打包的精灵数据列表
+/// * `config` - 打包配置
+///
+/// # Returns
+/// * `Result<Vec<PackResult>, String>` - 每页的打包结果（页号从 0 开始，共用同一个 `job_id`）或错误信息
+#[tauri::command]
+pub async fn pack_sprites_pages(
+    store: State<'_, PackSessionStore>,
     sprites: Vec<SpriteData>,
     config: Option<PackConfig>,
+) -> Result<Vec<PackResult>, String> {
+    let store = store.inner().clone();
+    run_blocking("pack_sprites_pages", move || {
+        let inputs = PackInputs::new(&sprites, config.as_ref());
+        let pages = run_pack(&store, &sprites, config, true, load_sprite_image)?;
+        store.set_inputs(&pages[0].job_id, inputs);
+        Ok(pages)
+    })
+    .await
+}
+
+/// 释放打包结果命令
+///
+/// 导出完成或放弃某次打包结果后调用，释放其占用的图像内存
+///
+/// # Arguments
+/// * `job_id` - 打包结果 ID
+///
+/// # Returns
+/// * `Result<bool, String>` - 该结果之前是否存在
+#[tauri::command]
+pub async fn release_pack_result(store: State<'_, PackSessionStore>, job_id: String) -> Result<bool, String> {
+    catch_panic("release_pack_result", async { Ok(store.release(&job_id)) }).await
+}
+
+/// 查找翻转帧命令
+///
+/// 在某次打包的裁剪结果中查找是其他精灵水平/垂直翻转结果的精灵，
+/// 供用户确认后启用 `aliasMirrors` 重新打包
+///
+/// # Arguments
+/// * `job_id` - 打包结果 ID
+/// * `sprite_ids` - 参与检测的精灵 ID（顺序靠前的作为源帧）
+///
+/// # Returns
+/// * `Result<Vec<MirrorMatch>, String>` - 翻转帧及其源帧
+#[tauri::command]
+pub async fn find_mirrored_sprites(
+    store: State<'_, PackSessionStore>,
+    job_id: String,
+    sprite_ids: Vec<String>,
+) -> Result<Vec<MirrorMatch>, String> {
+    let store = store.inner().clone();
+    run_blocking("find_mirrored_sprites", move || find_mirrored_sprites_impl(&store, &job_id, &sprite_ids)).await
+}
+
+fn find_mirrored_sprites_impl(
+    store: &PackSessionStore,
+    job_id: &str,
+    sprite_ids: &[String],
+) -> Result<Vec<MirrorMatch>, String> {
+    let trims = store.get(job_id)?;
+    let images: Vec<(&str, &RgbaImage)> = sprite_ids
+        .iter()
+        .filter_map(|id| trims.get(id).map(|trim| (id.as_str(), &trim.trimmed_image)))
+        .collect();
+    let matches = find_mirrors(&images);
+    println!("✓ 翻转帧检测完成: {} 个精灵中有 {} 个翻转帧", images.len(), matches.len());
+    Ok(matches)
+}
+
+/// 执行打包流程，并把裁剪结果存入 `store`
+///
+/// # Arguments
+/// * `store` - 打包结果存储
+/// * `sprites` - 待打包的精灵
+/// * `config` - 打包配置
+/// * `load` - 加载精灵图像（独立图片或图集中的帧）
+///
+/// # Returns
+/// * `Result<PackResult, String>` - 打包结果或错误信息
+pub(crate) fn pack_with_loader(
+    store: &PackSessionStore,
+    sprites: &[SpriteData],
+    config: Option<PackConfig>,
+    load: impl FnMut(&SpriteData) -> Result<RgbaImage, String>,
 ) -> Result<PackResult, String> {
+    let mut pages = run_pack(store, sprites, config, false, load)?;
+    Ok(pages.remove(0))
+}
+
+/// 执行单页或多页打包，并把裁剪结果存入 `store`
+///
+/// # Returns
+/// * `Result<Vec<PackResult>, String>` - 每页的打包结果（单页时只有一项）
+pub(crate) fn run_pack(
+    store: &PackSessionStore,
+    sprites: &[SpriteData],
+    config: Option<PackConfig>,
+    multi_page: bool,
+    mut load: impl FnMut(&SpriteData) -> Result<RgbaImage, String>,
+) -> Result<Vec<PackResult>, String> {
     let config = config.unwrap_or_default();
-    let max_width = config.max_width.unwrap_or(2048);
-    let max_height = config.max_height.unwrap_or(2048);
-    let do_trim = config.trim_transparent.unwrap_or(true);
+    let max_width = config.max_width.unwrap_or(DEFAULT_MAX_TEXTURE_SIZE);
+    let max_height = config.max_height.unwrap_or(DEFAULT_MAX_TEXTURE_SIZE);
+    let trim_mode = config.trim_mode.unwrap_or(match config.trim_transparent.unwrap_or(true) {
+        true => TrimMode::CropKeepSize,
+        false => TrimMode::None,
+    });
+    let alpha_threshold = config.alpha_threshold.unwrap_or(DEFAULT_ALPHA_THRESHOLD);
+    // 目标格式无法表示的帧属性不参与打包
+    let target_format = config.target_format.unwrap_or_default();
+    let capabilities = target_format.capabilities();
+    let mut format_warnings = Vec::new();
     let allow_rotation = config.allow_rotation.unwrap_or(true);
+    if allow_rotation && !capabilities.rotation {
+        format_warnings.push(format!("{:?} 格式不支持旋转的帧，已关闭旋转", target_format));
+    }
+    let allow_rotation = allow_rotation && capabilities.rotation;
+    let alias_mirrors = config.alias_mirrors.unwrap_or(false);
+    if alias_mirrors && !capabilities.flip {
+        format_warnings.push(format!("{:?} 格式不支持翻转帧，已关闭翻转帧合并", target_format));
+    }
+    let alias_mirrors = alias_mirrors && capabilities.flip;
     let padding = config.padding.unwrap_or(1);
-    let auto_size = config.auto_size.unwrap_or(true);
+    let extrude = config.extrude.unwrap_or(0);
+    let auto_size = config.auto_size.unwrap_or(true) && !config.force_max_size.unwrap_or(false);
+    let size_constraint = config.size_constraint.unwrap_or_default();
     
     println!("开始打包 {} 个精灵", sprites.len());
-    println!("配置: 最大尺寸 {}x{}, 裁剪={}, 旋转={}, 间距={}", 
-             max_width, max_height, do_trim, allow_rotation, padding);
+    println!("配置: 最大尺寸 {}x{}, 裁剪={:?} (alpha 阈值 {}), 旋转={}, 间距={}, 挤出={}, 自动尺寸={} ({:?})", 
+             max_width, max_height, trim_mode, alpha_threshold, allow_rotation, padding, extrude, auto_size, size_constraint);
     
     if sprites.is_empty() {
         return Err("没有精灵可打包".to_string());
     }
+    if let Some(sprite) = sprites.iter().find(|s| s.anchor.is_some_and(|(x, y)| !x.is_finite() || !y.is_finite())) {
+        return Err(format!("精灵 {} 的锚点无效", sprite.name));
+    }
+    // 重名的帧按策略报错或改名，改名后的名称用于打包与导出
+    let renamed_frames = resolve_collisions(sprites, config.name_collision.unwrap_or_default())?;
+    let renamed_sprites: Vec<SpriteData>;
+    let sprites = if renamed_frames.is_empty() {
+        sprites
+    } else {
+        let names: HashMap<&str, &str> = renamed_frames.iter().map(|r| (r.id.as_str(), r.to.as_str())).collect();
+        renamed_sprites = sprites
+            .iter()
+            .map(|sprite| match names.get(sprite.id.as_str()) {
+                Some(name) => SpriteData { name: name.to_string(), ..sprite.clone() },
+                None => sprite.clone(),
+            })
+            .collect();
+        for rename in &renamed_frames {
+            println!("  重名帧改名: {} -> {}", rename.from, rename.to);
+        }
+        &renamed_sprites
+    };
+    for sprite in sprites {
+        if let Some(nine_slice) = &sprite.nine_slice {
+            nine_slice.validate(sprite.width, sprite.height).map_err(|e| format!("精灵 {}: {}", sprite.name, e))?;
+        }
+    }
     
-    // 清空之前的裁剪缓存
-    clear_trim_cache();
+    let effects = config.effects.unwrap_or_default();
+    validate_effects(&effects)?;
+    let has_effect = |sprite: &SpriteData| {
+        !effects.is_empty() && is_selected(&config.effect_sprite_ids, sprite)
+    };
+    if let Some(upscale) = &config.upscale {
+        upscale.validate()?;
+    }
+    if let Some(normalize) = &config.normalize {
+        normalize.validate()?;
+    }
+    let solids = solid_regions(
+        config.white_pixel.unwrap_or(false),
+        config.solid_regions.as_deref().unwrap_or_default(),
+    )?;
+    let mipmap_levels = config.mipmap_levels.unwrap_or(0);
+    validate_mipmap_levels(mipmap_levels)?;
+    for level in mip_levels(mipmap_levels) {
+        println!("  mipmap 第 {} 级: 间距 {}px, 每侧扩展 {}px", level.level, level.padding, level.extrusion);
+    }
+    let upscale_for = |sprite: &SpriteData| {
+        config
+            .upscale
+            .filter(|_| is_selected(&config.upscale_sprite_ids, sprite))
+    };
     
-    // 处理精灵：加载图像并进行透明裁剪
-    let mut sprite_inputs: Vec<SpriteInput> = Vec::with_capacity(sprites.len());
+    // 构建打包任务：加载图像用于裁剪与导出，加载失败时只使用原始尺寸
+    let mut job = PackJob::new()
+        .max_dimensions(max_width, max_height)
+        .trim_mode(trim_mode)
+        .trim_margin(config.trim_margin.unwrap_or(0))
+        .alpha_threshold(alpha_threshold)
+        .allow_rotation(allow_rotation)
+        .padding(padding)
+        .extrude(extrude)
+        .auto_size(auto_size)
+        .size_constraint(size_constraint)
+        .heuristic(config.heuristic.unwrap_or_default())
+        .auto_heuristic(config.auto_heuristic.unwrap_or(false))
+        .mipmap_levels(mipmap_levels)
+        .empty_sprites(config.empty_sprites.unwrap_or_default())
+        .alias_mirrors(alias_mirrors);
     
-    for sprite in &sprites {
-        let input = if do_trim {
-            // 加载图像进行透明裁剪
-            match load_and_trim_sprite(sprite) {
-                Ok((input, trim_result)) => {
-                    // 缓存裁剪结果用于后续导出
-                    cache_trim_result(sprite.id.clone(), trim_result);
-                    input
-                }
-                Err(e) => {
-                    println!("警告: 处理精灵 {} 失败: {}", sprite.name, e);
-                    // 使用原始尺寸
-                    SpriteInput {
-                        id: sprite.id.clone(),
-                        name: sprite.name.clone(),
-                        width: sprite.width,
-                        height: sprite.height,
-                        original_width: sprite.width,
-                        original_height: sprite.height,
-                        offset_x: 0,
-                        offset_y: 0,
-                        trimmed: false,
-                    }
-                }
+    // 加载并预处理图像
+    let mut timings = Timings::default();
+    let decode_start = Instant::now();
+    let mut images: Vec<Result<RgbaImage, String>> = sprites
+        .iter()
+        .map(|sprite| {
+            let mut img = load(sprite)?;
+            if let Some(upscale) = &upscale_for(sprite) {
+                img = upscale_image(&img, upscale);
             }
-        } else {
-            // 不裁剪，使用原始尺寸
-            SpriteInput {
-                id: sprite.id.clone(),
-                name: sprite.name.clone(),
-                width: sprite.width,
-                height: sprite.height,
-                original_width: sprite.width,
-                original_height: sprite.height,
-                offset_x: 0,
-                offset_y: 0,
-                trimmed: false,
+            if has_effect(sprite) {
+                img = bake_effects(&img, &effects);
             }
-        };
-        
-        sprite_inputs.push(input);
+            Ok(img)
+        })
+        .collect();
+    if let Some(options) = &config.normalize {
+        normalize_selected(sprites, &mut images, options, &config.normalize_sprite_ids)?;
     }
+    timings.add(Phase::Decode, decode_start.elapsed());
     
-    // 确定纹理尺寸
-    let (tex_width, tex_height) = if auto_size {
-        // 自动选择最优尺寸
-        match find_optimal_size(&sprite_inputs, max_width.max(max_height), allow_rotation, padding) {
-            Some(size) => size,
-            None => {
-                println!("自动尺寸失败，使用最大尺寸 {}x{}", max_width, max_height);
-                (max_width, max_height)
+    for (sprite, image) in sprites.iter().zip(images) {
+        job = match image {
+            Ok(img) => job.add_sprite(sprite.id.clone(), sprite.name.clone(), img),
+            Err(e) => {
+                println!("警告: 处理精灵 {} 失败: {}", sprite.name, e);
+                // 使用原始尺寸
+                job.add_sprite_size(sprite.id.clone(), sprite.name.clone(), sprite.width, sprite.height)
             }
+        };
+    }
+    for id in config.rotation_locked_ids.iter().flatten() {
+        job = job.allow_sprite_rotation(id, false);
+    }
+    if let Some(key) = config.color_key {
+        for sprite in sprites.iter().filter(|s| is_selected(&config.color_key_sprite_ids, s)) {
+            job = job.sprite_color_key(&sprite.id, key);
+        }
+    }
+    for (id, size) in config.source_sizes.iter().flatten() {
+        job = job.sprite_source_size(id, size.width, size.height);
+    }
+    // 锚点与九宫格边距相对原图，打包时按实际裁剪区域换算到帧上
+    for sprite in sprites {
+        if let Some(anchor) = sprite.anchor {
+            job = job.sprite_anchor(&sprite.id, anchor);
+        }
+        if let Some(nine_slice) = sprite.nine_slice {
+            job = job.sprite_nine_slice(&sprite.id, nine_slice);
         }
+    }
+    if let (Some(grouping), true) = (&config.page_affinity, multi_page) {
+        let groups = sprite_groups(sprites.iter().map(|s| (s.id.as_str(), s.name.as_str())), grouping)?;
+        for (id, group) in groups {
+            job = job.sprite_page_group(&id, group);
+        }
+    }
+    for region in solids {
+        println!("  注入纯色区域 {} ({}x{}, {:?})", region.name, region.size, region.size, region.color);
+        job = job.solid_region(region);
+    }
+    
+    // 执行打包
+    let (mut pages, trims) = if multi_page {
+        let output = job.run_pages().map_err(|e| e.to_string())?;
+        (output.pages, output.trims)
     } else {
-        (max_width, max_height)
+        let output = job.run().map_err(|e| e.to_string())?;
+        (vec![output.result], output.trims)
     };
     
-    println!("使用纹理尺寸: {}x{}", tex_width, tex_height);
     
-    // 执行打包
-    let mut packer = MaxRectsPacker::new(tex_width, tex_height, allow_rotation, padding);
-    let packed_sprites = packer.pack(&sprite_inputs);
-    
-    // 检查是否所有精灵都已打包
-    if packed_sprites.len() != sprite_inputs.len() {
-        return Err(format!(
-            "纹理尺寸不足：只打包了 {}/{} 个精灵。请增大最大尺寸或减少精灵数量。",
-            packed_sprites.len(),
-            sprite_inputs.len()
-        ));
+    // 保存裁剪结果用于后续导出，各页共用同一个 ID
+    let job_id = store.insert(trims);
+    let locale = number_locale();
+    for page in &mut pages {
+        page.job_id = job_id.clone();
+        page.timings.merge(&timings);
+        page.summary = texture_summary(page.texture_width, page.texture_height, Some(page.fill_rate), locale);
+    }
+    if let Some(first) = pages.first_mut() {
+        first.warnings.splice(0..0, format_warnings);
+        first.renamed_frames = renamed_frames;
     }
     
-    // 计算实际边界和填充率
-    let (actual_width, actual_height) = packer.actual_bounds();
-    let fill_rate = calculate_fill_rate(&packed_sprites, actual_width, actual_height);
-    
-    println!("打包完成: 实际尺寸 {}x{}, 填充率 {:.1}%", actual_width, actual_height, fill_rate);
+    for (index, result) in pages.iter().enumerate() {
+        for warning in &result.warnings {
+            println!("警告: {}", warning);
+        }
+        if !result.empty_sprites.is_empty() {
+            println!("警告: {} 个精灵完全透明", result.empty_sprites.len());
+        }
+        let page = if multi_page { format!("（第 {} 页）", index) } else { String::new() };
+        println!(
+            "打包完成{}: 纹理尺寸 {}x{}, 填充率 {:.1}%, {}",
+            page, result.texture_width, result.texture_height, result.fill_rate, result.timings
+        );
+    }
     
-    Ok(PackResult {
-        packed_sprites,
-        texture_width: tex_width,
-        texture_height: tex_height,
-        fill_rate,
-    })
+    Ok(pages)
 }
 
-/// 加载并裁剪精灵
-fn load_and_trim_sprite(sprite: &SpriteData) -> Result<(SpriteInput, TrimResult), String> {
-    // 加载图像
-    let img = ImageReader::open(&sprite.path)
-        .map_err(|e| format!("无法打开图像 {}: {}", sprite.path, e))?
-        .decode()
-        .map_err(|e| format!("无法解码图像 {}: {}", sprite.path, e))?
-        .to_rgba8();
-    
-    // 透明裁剪
-    let trim_result = trim_transparent(&img);
-    
-    let input = SpriteInput {
-        id: sprite.id.clone(),
-        name: sprite.name.clone(),
-        width: trim_result.trimmed_width,
-        height: trim_result.trimmed_height,
-        original_width: trim_result.original_width,
-        original_height: trim_result.original_height,
-        offset_x: trim_result.offset_x,
-        offset_y: trim_result.offset_y,
-        trimmed: trim_result.trimmed_width != trim_result.original_width 
-                 || trim_result.trimmed_height != trim_result.original_height,
-    };
-    
-    Ok((input, trim_result))
+/// 把选中的精灵对齐到统一画布（加载失败的精灵不参与；选项应已通过 `validate` 检查）
+fn normalize_selected(
+    sprites: &[SpriteData],
+    images: &mut [Result<RgbaImage, String>],
+    options: &NormalizeOptions,
+    ids: &Option<Vec<String>>,
+) -> Result<(), String> {
+    let selected: Vec<usize> = (0..sprites.len())
+        .filter(|&i| images[i].is_ok() && is_selected(ids, &sprites[i]))
+        .collect();
+    let frames: Vec<(&str, &RgbaImage)> = selected
+        .iter()
+        .filter_map(|&i| Some((sprites[i].name.as_str(), images[i].as_ref().ok()?)))
+        .collect();
+    let aligned = normalize_frames(&frames, options)?;
+    if let Some(first) = aligned.first() {
+        println!("帧对齐: {} 帧统一为 {}x{}", aligned.len(), first.width(), first.height());
+    }
+    for (i, image) in selected.into_iter().zip(aligned) {
+        images[i] = Ok(image);
+    }
+    Ok(())
 }
 
-/// 计算填充率
-fn calculate_fill_rate(sprites: &[crate::core::types::PackedSprite], width: u32, height: u32) -> f32 {
-    let total_area = width as u64 * height as u64;
-    if total_area == 0 {
-        return 0.0;
+/// 精灵是否在 ID 列表中（未指定列表时视为全部选中）
+fn is_selected(ids: &Option<Vec<String>>, sprite: &SpriteData) -> bool {
+    ids.as_ref().is_none_or(|ids| ids.contains(&sprite.id))
+}
+
+/// 精灵图像来源
+///
+/// 独立图片按路径读取；从已有图集中裁出的帧只存在于内存中，
+/// 打包后导出使用保存的裁剪结果，两种来源的精灵可以混合打包
+#[derive(Debug, Clone)]
+pub enum ImageSource {
+    /// 图片文件路径
+    File(String),
+    /// 内存中的图像
+    Buffer(Arc<RgbaImage>),
+}
+
+impl ImageSource {
+    /// 读取为 RGBA 图像
+    pub fn load(&self) -> Result<RgbaImage, String> {
+        match self {
+            ImageSource::File(path) => Ok(load_image(path, &ImageLoadLimits::default())?.to_rgba8()),
+            ImageSource::Buffer(image) => Ok(image.as_ref().clone()),
+        }
     }
-    
-    let used_area: u64 = sprites.iter().map(|s| s.width as u64 * s.height as u64).sum();
-    (used_area as f32 / total_area as f32) * 100.0
+}
+
+/// 按精灵 ID 查找图像来源的加载函数（未登记的精灵从 `SpriteData.path` 读取）
+pub(crate) fn source_loader(
+    sources: &HashMap<String, ImageSource>,
+) -> impl FnMut(&SpriteData) -> Result<RgbaImage, String> + '_ {
+    move |sprite| match sources.get(&sprite.id) {
+        Some(source) => source.load(),
+        None => load_sprite_image(sprite),
+    }
+}
+
+/// 加载精灵图像
+pub(crate) fn load_sprite_image(sprite: &SpriteData) -> Result<RgbaImage, String> {
+    ImageSource::File(sprite.path.clone()).load()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::types::NineSlice;
+
+    fn sprite(id: &str, path: &str) -> SpriteData {
+        SpriteData {
+            id: id.to_string(),
+            name: format!("{}.png", id),
+            path: path.to_string(),
+            width: 4,
+            height: 4,
+            trimmed_width: 4,
+            trimmed_height: 4,
+            anchor: None,
+            nine_slice: None,
+        }
+    }
     
     #[test]
     fn test_pack_config_default() {
@@ -231,4 +696,197 @@ mod tests {
         assert_eq!(config.trim_transparent, Some(true));
         assert_eq!(config.allow_rotation, Some(true));
     }
+
+    #[test]
+    fn test_find_mirrored_sprites() {
+        let store = PackSessionStore::default();
+        let sprite = |id: &str| SpriteData { height: 2, trimmed_height: 2, ..sprite(id, "") };
+        let mut right = RgbaImage::from_pixel(4, 2, image::Rgba([0, 0, 255, 255]));
+        right.put_pixel(0, 0, image::Rgba([255, 0, 0, 255]));
+        let left = image::imageops::flip_horizontal(&right);
+        let load = |s: &SpriteData| Ok(if s.id == "r" { right.clone() } else { left.clone() });
+        let result = pack_with_loader(&store, &[sprite("r"), sprite("l")], None, load).unwrap();
+
+        let ids = vec!["r".to_string(), "l".to_string()];
+        let matches = find_mirrored_sprites_impl(&store, &result.job_id, &ids).unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!((matches[0].id.as_str(), matches[0].source_id.as_str(), matches[0].flip_x), ("l", "r", true));
+        assert!(find_mirrored_sprites_impl(&store, "missing", &ids).is_err());
+
+        // 目标格式不支持翻转帧与旋转时自动关闭，并在第一页给出警告
+        let config = PackConfig {
+            alias_mirrors: Some(true),
+            target_format: Some(SheetFormat::SpineAtlas),
+            ..Default::default()
+        };
+        let result = pack_with_loader(&store, &[sprite("r"), sprite("l")], Some(config), load).unwrap();
+        assert!(result.packed_sprites.iter().all(|s| !s.is_flipped() && !s.rotated));
+        assert_eq!(result.warnings.len(), 2);
+        assert!(result.warnings[0].contains("旋转") && result.warnings[1].contains("翻转"));
+    }
+
+    #[test]
+    fn test_white_pixel_injection() {
+        let store = PackSessionStore::default();
+        let sprite = SpriteData {
+            width: 8,
+            height: 8,
+            trimmed_width: 8,
+            trimmed_height: 8,
+            anchor: Some((0.5, 0.0)),
+            ..sprite("a", "")
+        };
+        let load = |_: &SpriteData| Ok(RgbaImage::from_pixel(8, 8, image::Rgba([0, 0, 255, 255])));
+        let config = PackConfig {
+            trim_transparent: Some(false),
+            white_pixel: Some(true),
+            ..Default::default()
+        };
+        let result = pack_with_loader(&store, std::slice::from_ref(&sprite), Some(config), load).unwrap();
+        assert!(result.packed_sprites.iter().any(|s| s.name == "__white" && s.width == 4 && s.anchor.is_none()));
+        assert!(result.packed_sprites.iter().any(|s| s.id == "a" && s.anchor == Some((0.5, 0.0))));
+        // 未裁剪时纯色区域同样保存在打包结果中，导出时可以渲染
+        assert!(store.get(&result.job_id).unwrap().contains_key("__white"));
+
+        let clashing = SpriteData { name: "__white".to_string(), ..sprite };
+        let config = PackConfig { white_pixel: Some(true), ..Default::default() };
+        let err = pack_with_loader(&store, &[clashing], Some(config), load).unwrap_err();
+        assert!(err.contains("重名"));
+    }
+
+    #[test]
+    fn test_anchor_follows_trim_mode() {
+        let store = PackSessionStore::default();
+        let sprite = SpriteData { width: 8, height: 8, anchor: Some((0.5, 0.5)), ..sprite("a", "") };
+        // 8x8 中只有 (4, 4) 起 4x4 的区域不透明，锚点在原图中心即内容的左上角
+        let load = |_: &SpriteData| {
+            let mut img = RgbaImage::new(8, 8);
+            for y in 4..8 {
+                for x in 4..8 {
+                    img.put_pixel(x, y, image::Rgba([255, 0, 0, 255]));
+                }
+            }
+            Ok(img)
+        };
+        let anchor = |trim_mode: TrimMode| {
+            let config = PackConfig { trim_mode: Some(trim_mode), ..Default::default() };
+            let result = pack_with_loader(&store, std::slice::from_ref(&sprite), Some(config), load).unwrap();
+            result.packed_sprites[0].anchor
+        };
+        assert_eq!(anchor(TrimMode::CropKeepSize), Some((0.5, 0.5)));
+        assert_eq!(anchor(TrimMode::Trim), Some((0.0, 1.0)));
+    }
+
+    #[test]
+    fn test_nine_slice_follows_trim() {
+        let store = PackSessionStore::default();
+        let sprite = SpriteData {
+            name: "panel.png".to_string(),
+            width: 10,
+            height: 10,
+            trimmed_width: 10,
+            trimmed_height: 10,
+            nine_slice: Some(NineSlice { left: 3, right: 3, top: 1, bottom: 4 }),
+            ..sprite("a", "")
+        };
+        // 10x10 中只有 (2, 2) 起 6x6 的区域不透明
+        let load = |_: &SpriteData| {
+            let mut img = RgbaImage::new(10, 10);
+            for y in 2..8 {
+                for x in 2..8 {
+                    img.put_pixel(x, y, image::Rgba([255, 0, 0, 255]));
+                }
+            }
+            Ok(img)
+        };
+        let config = PackConfig { trim_transparent: Some(true), allow_rotation: Some(false), ..Default::default() };
+        let result = pack_with_loader(&store, std::slice::from_ref(&sprite), Some(config), load).unwrap();
+        let packed = &result.packed_sprites[0];
+        assert_eq!((packed.width, packed.height), (6, 6));
+        // 四边各裁掉 2 像素，边距相应扣除，不足的按 0 计
+        let trimmed = Some(NineSlice { left: 1, right: 1, top: 0, bottom: 2 });
+        assert_eq!(packed.nine_slice, trimmed);
+
+        // Trim 模式下帧缩小为内容，边距同样按裁剪区域扣除
+        let config = PackConfig { trim_mode: Some(TrimMode::Trim), allow_rotation: Some(false), ..Default::default() };
+        let result = pack_with_loader(&store, std::slice::from_ref(&sprite), Some(config), load).unwrap();
+        assert_eq!(result.packed_sprites[0].nine_slice, trimmed);
+        // 虚拟格子居中的留白不属于原图，不从边距中扣除
+        let source_sizes = HashMap::from([("a".to_string(), SourceSize { width: 16, height: 16 })]);
+        let config = PackConfig { source_sizes: Some(source_sizes), allow_rotation: Some(false), ..Default::default() };
+        let result = pack_with_loader(&store, std::slice::from_ref(&sprite), Some(config), load).unwrap();
+        assert_eq!(result.packed_sprites[0].original_width, 16);
+        assert_eq!(result.packed_sprites[0].nine_slice, trimmed);
+
+        let oversized = SpriteData { nine_slice: Some(NineSlice { left: 6, right: 6, top: 0, bottom: 0 }), ..sprite };
+        let err = pack_with_loader(&store, &[oversized], None, load).unwrap_err();
+        assert!(err.contains("九宫格"));
+    }
+
+    #[test]
+    fn test_name_collisions() {
+        let store = PackSessionStore::default();
+        let sprite = |id: &str, path: &str| SpriteData { name: "01.png".to_string(), ..sprite(id, path) };
+        let sprites = [sprite("a", "art/run/01.png"), sprite("b", "art/jump/01.png")];
+        let load = |_: &SpriteData| Ok(RgbaImage::from_pixel(4, 4, image::Rgba([0, 0, 255, 255])));
+
+        let err = pack_with_loader(&store, &sprites, None, load).unwrap_err();
+        assert!(err.contains("art/run/01.png") && err.contains("art/jump/01.png"));
+
+        let config = PackConfig { name_collision: Some(NameCollisionPolicy::FolderPrefix), ..Default::default() };
+        let result = pack_with_loader(&store, &sprites, Some(config), load).unwrap();
+        let mut names: Vec<&str> = result.packed_sprites.iter().map(|s| s.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, ["jump/01.png", "run/01.png"]);
+        assert_eq!(result.renamed_frames.len(), 2);
+        assert_eq!((result.renamed_frames[1].id.as_str(), result.renamed_frames[1].to.as_str()), ("b", "jump/01.png"));
+    }
+
+    #[test]
+    fn test_trim_mode_overrides_trim_transparent() {
+        let store = PackSessionStore::default();
+        let sprite = SpriteData { width: 8, height: 8, trimmed_width: 8, trimmed_height: 8, ..sprite("a", "") };
+        let load = |_: &SpriteData| {
+            let mut img = RgbaImage::from_pixel(8, 8, image::Rgba([0, 0, 0, 20]));
+            img.put_pixel(6, 6, image::Rgba([255, 0, 0, 255]));
+            Ok(img)
+        };
+        let pack = |config: PackConfig| {
+            let result = pack_with_loader(&store, std::slice::from_ref(&sprite), Some(config), load).unwrap();
+            let packed = &result.packed_sprites[0];
+            (packed.width, packed.original_width)
+        };
+        assert_eq!(pack(PackConfig::default()), (8, 8));
+        assert_eq!(pack(PackConfig { alpha_threshold: Some(20), ..Default::default() }), (1, 8));
+        let shrink = PackConfig {
+            trim_transparent: Some(false),
+            trim_mode: Some(TrimMode::Trim),
+            alpha_threshold: Some(20),
+            ..Default::default()
+        };
+        assert_eq!(pack(shrink), (1, 1));
+    }
+
+    #[test]
+    fn test_pack_session_store() {
+        let store = PackSessionStore::default();
+        let sprites = |id: &str| vec![sprite(id, "")];
+        let load = |color: u8| move |_: &SpriteData| Ok(RgbaImage::from_pixel(4, 4, image::Rgba([color, 0, 0, 255])));
+
+        // 两次打包的结果互不覆盖
+        let first = pack_with_loader(&store, &sprites("a"), None, load(10)).unwrap();
+        let second = pack_with_loader(&store, &sprites("b"), None, load(20)).unwrap();
+        assert_ne!(first.job_id, second.job_id);
+        assert!(store.get(&first.job_id).unwrap().contains_key("a"));
+        assert!(!store.get(&first.job_id).unwrap().contains_key("b"));
+
+        assert!(store.release(&first.job_id));
+        assert!(store.get(&first.job_id).is_err());
+
+        // 超出保留数量时丢弃最早的结果
+        for _ in 0..MAX_PACK_SESSIONS {
+            store.insert(PackTrims::new());
+        }
+        assert!(store.get(&second.job_id).is_err());
+    }
 }
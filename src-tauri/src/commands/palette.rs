@@ -0,0 +1,150 @@
+/// 调色板命令 (Palette Commands)
+///
+/// 提取精灵调色板，并生成换色变体。变体保存为新的 PNG 并以精灵数据返回，
+/// 前端可将其作为额外帧加入当前图集，或单独打包为新图集
+
+use crate::core::palette::{apply_palette_swap, extract_palette, validate_mapping, ColorMapping, PaletteResult};
+use crate::core::types::{ImportResult, SpriteData};
+use crate::utils::guard::run_blocking;
+use crate::utils::image_loader::{load_image, ImageLoadLimits};
+use crate::utils::job_lock::{self, try_lock};
+use serde::Deserialize;
+use std::path::Path;
+
+/// 默认返回的最大颜色数
+const DEFAULT_MAX_COLORS: usize = 256;
+
+/// 换色变体
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaletteVariant {
+    /// 变体名称（作为文件名后缀，如 "blue"）
+    pub name: String,
+    /// 颜色映射表
+    pub mapping: Vec<ColorMapping>,
+}
+
+/// 提取调色板命令
+///
+/// # Arguments
+/// * `sprites` - 精灵列表
+/// * `max_colors` - 返回的最大颜色数（可选，默认 256）
+///
+/// # Returns
+/// * `Result<PaletteResult, String>` - 调色板或错误信息
+#[tauri::command]
+pub async fn extract_sprite_palette(
+    sprites: Vec<SpriteData>,
+    max_colors: Option<usize>,
+) -> Result<PaletteResult, String> {
+    run_blocking("extract_sprite_palette", move || extract_sprite_palette_impl(sprites, max_colors)).await
+}
+
+fn extract_sprite_palette_impl(
+    sprites: Vec<SpriteData>,
+    max_colors: Option<usize>,
+) -> Result<PaletteResult, String> {
+    if sprites.is_empty() {
+        return Err("没有精灵可提取调色板".to_string());
+    }
+
+    let images = sprites
+        .iter()
+        .map(|sprite| load_image(&sprite.path, &ImageLoadLimits::default()).map(|img| img.to_rgba8()))
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let palette = extract_palette(&images, max_colors.unwrap_or(DEFAULT_MAX_COLORS));
+    println!("✓ 调色板提取完成: {} 种颜色", palette.total_colors);
+
+    Ok(palette)
+}
+
+/// 生成换色变体命令
+///
+/// 每个精灵的每个变体保存为 `{原文件名}_{变体名}.png`
+///
+/// # Arguments
+/// * `sprites` - 原精灵列表
+/// * `variants` - 换色变体列表
+/// * `output_dir` - 输出目录
+///
+/// # Returns
+/// * `Result<ImportResult, String>` - 生成的变体精灵（可直接用于打包）
+#[tauri::command]
+pub async fn generate_palette_variants(
+    sprites: Vec<SpriteData>,
+    variants: Vec<PaletteVariant>,
+    output_dir: String,
+) -> Result<ImportResult, String> {
+    run_blocking("generate_palette_variants", move || generate_palette_variants_impl(sprites, variants, output_dir)).await
+}
+
+fn generate_palette_variants_impl(
+    sprites: Vec<SpriteData>,
+    variants: Vec<PaletteVariant>,
+    output_dir: String,
+) -> Result<ImportResult, String> {
+    if sprites.is_empty() || variants.is_empty() {
+        return Err("没有精灵或变体可生成".to_string());
+    }
+    for variant in &variants {
+        if variant.name.trim().is_empty() {
+            return Err("变体名称不能为空".to_string());
+        }
+        validate_mapping(&variant.mapping).map_err(|e| format!("变体 {}: {}", variant.name, e))?;
+    }
+
+    let _guard = try_lock("generate_palette_variants", [job_lock::output_dir(&output_dir)])?;
+    let out_dir = Path::new(&output_dir);
+    std::fs::create_dir_all(out_dir)
+        .map_err(|e| format!("无法创建输出目录: {}", e))?;
+
+    let mut generated = Vec::new();
+    let mut failed = Vec::new();
+
+    for sprite in &sprites {
+        let image = match load_image(&sprite.path, &ImageLoadLimits::default()) {
+            Ok(img) => img.to_rgba8(),
+            Err(e) => {
+                failed.push(e);
+                continue;
+            }
+        };
+        let stem = Path::new(&sprite.name)
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or(&sprite.name);
+
+        for variant in &variants {
+            let name = format!("{}_{}.png", stem, variant.name);
+            let path = out_dir.join(&name);
+
+            if let Err(e) = apply_palette_swap(&image, &variant.mapping).save(&path) {
+                failed.push(format!("保存变体 {} 失败: {}", name, e));
+                continue;
+            }
+
+            generated.push(SpriteData {
+                id: uuid::Uuid::new_v4().to_string(),
+                name,
+                path: path.to_string_lossy().to_string(),
+                width: sprite.width,
+                height: sprite.height,
+                trimmed_width: sprite.trimmed_width,
+                trimmed_height: sprite.trimmed_height,
+                anchor: sprite.anchor,
+                nine_slice: sprite.nine_slice,
+            });
+        }
+    }
+
+    println!("✓ 换色变体生成完成: 成功 {}, 失败 {}", generated.len(), failed.len());
+
+    let total = generated.len() + failed.len();
+    Ok(ImportResult {
+        sprites: generated,
+        failed,
+        total,
+        animations: Vec::new(),
+    })
+}
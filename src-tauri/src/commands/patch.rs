@@ -0,0 +1,342 @@
+/// 图集就地修补命令 (Atlas Patch Commands)
+///
+/// 加载已有的 plist + PNG 图集，保持所有帧的位置不变，只重绘被替换的帧。
+/// plist 原样保留，PNG 仅在内容变化时重写，并保留原文件中的文本块与 DPI，
+/// 适合线上版本的美术热更新。另提供对已有图集的渗色检查与一致性校验
+
+use crate::core::atlas_patch::patch_frame;
+use crate::core::atlas_plist::{parse_atlas_plist, AtlasPlist};
+use crate::core::bleed_check::{find_bleed_pairs, min_bleed_distance, BleedPair};
+use crate::core::mipmap::validate_mipmap_levels;
+use crate::core::limits::check_text_input_size;
+use crate::core::plist_lint::{lint_atlas_plist, PlistLintReport};
+use crate::core::png_meta::{insert_text_chunks, read_dpi, read_text_chunks, set_dpi};
+use crate::utils::guard::run_blocking;
+use crate::utils::image_loader::{load_image, ImageLoadLimits};
+use crate::utils::job_lock::{self, try_lock};
+use crate::utils::png_writer::encode_png;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// 替换一帧
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameReplacement {
+    /// 帧名称（与 plist 中一致）
+    pub frame_name: String,
+    /// 新图像路径（原始尺寸或裁剪后的帧尺寸）
+    pub path: String,
+}
+
+/// 就地修补配置
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchAtlasConfig {
+    /// 已有图集的 plist 路径
+    pub plist_path: String,
+    /// 要替换的帧
+    pub replacements: Vec<FrameReplacement>,
+    /// 输出目录（可选，不设置时覆盖原文件）
+    pub output_dir: Option<String>,
+}
+
+/// 就地修补结果
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchAtlasResult {
+    /// 纹理路径
+    pub texture_path: String,
+    /// plist 路径
+    pub plist_path: String,
+    /// 内容发生变化并已重绘的帧
+    pub patched_frames: Vec<String>,
+    /// 替换图像与原内容相同的帧
+    pub unchanged_frames: Vec<String>,
+    /// 是否重写了纹理
+    pub texture_written: bool,
+}
+
+/// 锁定布局修补已有图集命令
+///
+/// # Arguments
+/// * `config` - 修补配置
+///
+/// # Returns
+/// * `Result<PatchAtlasResult, String>` - 修补结果；任何一帧无法在原位置放下时不写入任何文件
+#[tauri::command]
+pub async fn patch_atlas(config: PatchAtlasConfig) -> Result<PatchAtlasResult, String> {
+    run_blocking("patch_atlas", move || patch_atlas_impl(config)).await
+}
+
+fn patch_atlas_impl(config: PatchAtlasConfig) -> Result<PatchAtlasResult, String> {
+    if config.replacements.is_empty() {
+        return Err("没有要替换的帧".to_string());
+    }
+
+    let plist_path = Path::new(&config.plist_path);
+    let (plist_data, atlas) = read_atlas_plist(plist_path)?;
+
+    let source_dir = plist_path.parent().unwrap_or(Path::new("."));
+    let texture_path = source_dir.join(&atlas.texture);
+    let is_png = texture_path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("png"));
+    if !is_png {
+        return Err(format!("仅支持 PNG 纹理: {}", atlas.texture));
+    }
+    let texture_data = std::fs::read(&texture_path)
+        .map_err(|e| format!("无法读取纹理 {}: {}", texture_path.display(), e))?;
+    let mut texture = load_image(&texture_path.to_string_lossy(), &ImageLoadLimits::default())?.to_rgba8();
+    if texture.dimensions() != (atlas.width, atlas.height) {
+        return Err(format!(
+            "纹理尺寸 {}x{} 与 Plist 中的 {}x{} 不一致",
+            texture.width(),
+            texture.height(),
+            atlas.width,
+            atlas.height
+        ));
+    }
+
+    let mut seen = HashSet::new();
+    let mut patched_frames = Vec::new();
+    let mut unchanged_frames = Vec::new();
+    for replacement in &config.replacements {
+        if !seen.insert(&replacement.frame_name) {
+            return Err(format!("帧重复替换: {}", replacement.frame_name));
+        }
+        let frame = atlas
+            .frame(&replacement.frame_name)
+            .ok_or_else(|| format!("图集中没有帧: {}", replacement.frame_name))?;
+        let image = load_image(&replacement.path, &ImageLoadLimits::default())?.to_rgba8();
+        if patch_frame(&mut texture, frame, &image)? {
+            patched_frames.push(frame.name.clone());
+        } else {
+            unchanged_frames.push(frame.name.clone());
+        }
+    }
+
+    let output_dir = config
+        .output_dir
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| source_dir.to_path_buf());
+    std::fs::create_dir_all(&output_dir)
+        .map_err(|e| format!("无法创建输出目录: {}", e))?;
+    let _guard = try_lock("patch_atlas", [job_lock::output_dir(&output_dir)])?;
+
+    let output_texture = output_dir.join(&atlas.texture);
+    let output_plist = output_dir.join(plist_path.file_name().ok_or("Plist 路径无效")?);
+    let in_place = job_lock::output_dir(&output_dir) == job_lock::output_dir(source_dir);
+
+    let texture_written = !patched_frames.is_empty();
+    if texture_written {
+        let data = encode_preserving_metadata(&texture, &texture_data)?;
+        // 先写临时文件再替换，写入中途失败不会损坏原纹理
+        let temp_path = output_texture.with_extension("png.tmp");
+        std::fs::write(&temp_path, data).map_err(|e| format!("写入 PNG 失败: {}", e))?;
+        std::fs::rename(&temp_path, &output_texture).map_err(|e| format!("写入 PNG 失败: {}", e))?;
+    } else if !in_place {
+        std::fs::write(&output_texture, &texture_data).map_err(|e| format!("写入 PNG 失败: {}", e))?;
+    }
+    if !in_place {
+        std::fs::write(&output_plist, &plist_data).map_err(|e| format!("写入 Plist 失败: {}", e))?;
+    }
+
+    println!(
+        "✓ 图集修补完成: {} 帧重绘, {} 帧未变化",
+        patched_frames.len(),
+        unchanged_frames.len()
+    );
+
+    Ok(PatchAtlasResult {
+        texture_path: output_texture.to_string_lossy().to_string(),
+        plist_path: output_plist.to_string_lossy().to_string(),
+        patched_frames,
+        unchanged_frames,
+        texture_written,
+    })
+}
+
+/// 图集渗色检查结果
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AtlasBleedReport {
+    /// 帧数
+    pub frame_count: usize,
+    /// 不渗色所需的最小间隔
+    pub min_distance: u32,
+    /// 间隔不足的帧对（按间隔从小到大）
+    pub pairs: Vec<BleedPair>,
+}
+
+/// 检查已有图集中相邻帧是否会在双线性过滤下渗色
+///
+/// # Arguments
+/// * `plist_path` - 图集 plist 路径
+/// * `mipmap_levels` - 计划生成的 mipmap 级数（0 表示不使用 mipmap）
+///
+/// # Returns
+/// * `Result<AtlasBleedReport, String>` - 检查结果；`pairs` 非空时建议开启边缘扩展后重新打包
+#[tauri::command]
+pub async fn check_atlas_bleed(plist_path: String, mipmap_levels: u32) -> Result<AtlasBleedReport, String> {
+    run_blocking("check_atlas_bleed", move || check_atlas_bleed_impl(&plist_path, mipmap_levels)).await
+}
+
+fn check_atlas_bleed_impl(plist_path: &str, mipmap_levels: u32) -> Result<AtlasBleedReport, String> {
+    validate_mipmap_levels(mipmap_levels)?;
+    let (_, atlas) = read_atlas_plist(Path::new(plist_path))?;
+    let min_distance = min_bleed_distance(mipmap_levels);
+    let pairs = find_bleed_pairs(&atlas.frames, min_distance);
+
+    if pairs.is_empty() {
+        println!("✓ 渗色检查通过: {} 帧间隔均不小于 {} 像素", atlas.frames.len(), min_distance);
+    } else {
+        println!(
+            "警告: {} 对相邻帧间隔小于 {} 像素，双线性过滤下会渗色，建议开启边缘扩展后重新打包",
+            pairs.len(),
+            min_distance
+        );
+    }
+
+    Ok(AtlasBleedReport {
+        frame_count: atlas.frames.len(),
+        min_distance,
+        pairs,
+    })
+}
+
+/// 校验已有图集的 plist 与纹理
+///
+/// 报告帧超出纹理、帧区域重叠、旋转帧尺寸写反、帧名称重复、
+/// 纹理缺失以及纹理尺寸与 metadata 不一致等问题，适合检查其他工具生成的图集
+///
+/// # Arguments
+/// * `plist_path` - 图集 plist 路径
+///
+/// # Returns
+/// * `Result<PlistLintReport, String>` - 校验报告；文件不是图集 plist 时返回错误
+#[tauri::command]
+pub async fn validate_plist(plist_path: String) -> Result<PlistLintReport, String> {
+    run_blocking("validate_plist", move || validate_plist_impl(Path::new(&plist_path))).await
+}
+
+fn validate_plist_impl(plist_path: &Path) -> Result<PlistLintReport, String> {
+    let mut report = lint_atlas_plist(&read_plist_data(plist_path)?)?;
+
+    // 只读取文件头中的尺寸，纹理可能是 PVR 等无法解码的格式
+    let texture_path = plist_path.parent().unwrap_or(Path::new("")).join(&report.texture);
+    let texture = texture_path.is_file().then(|| {
+        image::ImageReader::open(&texture_path)
+            .and_then(|reader| reader.with_guessed_format())
+            .map_err(|e| e.to_string())
+            .and_then(|reader| reader.into_dimensions().map_err(|e| e.to_string()))
+    });
+    report.check_texture(texture);
+
+    let errors = report.error_count();
+    if report.issues.is_empty() {
+        println!("✓ Plist 校验通过: {} 帧", report.frame_count);
+    } else {
+        println!(
+            "警告: Plist 校验发现 {} 个错误、{} 个警告",
+            errors,
+            report.issues.len() - errors
+        );
+    }
+    Ok(report)
+}
+
+/// 读取并解析图集 plist，返回原始内容与解析结果
+pub(crate) fn read_atlas_plist(plist_path: &Path) -> Result<(Vec<u8>, AtlasPlist), String> {
+    let plist_data = read_plist_data(plist_path)?;
+    let atlas = parse_atlas_plist(&plist_data)?;
+    Ok((plist_data, atlas))
+}
+
+/// 读取 plist 文件内容（检查文件大小上限）
+fn read_plist_data(plist_path: &Path) -> Result<Vec<u8>, String> {
+    let plist_size = std::fs::metadata(plist_path)
+        .map_err(|e| format!("无法读取 Plist {}: {}", plist_path.display(), e))?
+        .len();
+    check_text_input_size(plist_size)?;
+    std::fs::read(plist_path).map_err(|e| format!("无法读取 Plist {}: {}", plist_path.display(), e))
+}
+
+/// 编码 PNG，并保留原文件中的文本块与 DPI
+fn encode_preserving_metadata(texture: &image::RgbaImage, original: &[u8]) -> Result<Vec<u8>, String> {
+    let mut data = encode_png(texture)?;
+    let text = read_text_chunks(original)?;
+    if !text.is_empty() {
+        data = insert_text_chunks(&data, &text)?;
+    }
+    if let Some(dpi) = read_dpi(original)? {
+        data = set_dpi(&data, dpi)?;
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+
+    #[test]
+    fn test_patch_atlas_in_place() {
+        let dir = std::env::temp_dir().join(format!("ezplist_patch_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let plist = r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0"><dict>
+<key>frames</key><dict>
+  <key>coin.png</key><dict>
+    <key>spriteOffset</key><string>{0,0}</string>
+    <key>spriteSize</key><string>{4,4}</string>
+    <key>spriteSourceSize</key><string>{4,4}</string>
+    <key>textureRect</key><string>{{2,2},{4,4}}</string>
+    <key>textureRotated</key><false/>
+  </dict>
+</dict>
+<key>metadata</key><dict>
+  <key>format</key><integer>3</integer>
+  <key>realTextureFileName</key><string>ui.png</string>
+  <key>size</key><string>{8,8}</string>
+  <key>textureFileName</key><string>ui.png</string>
+</dict>
+</dict></plist>"#;
+        let plist_path = dir.join("ui.plist");
+        std::fs::write(&plist_path, plist).unwrap();
+        let texture_path = dir.join("ui.png");
+        let mut texture = RgbaImage::new(8, 8);
+        image::imageops::replace(&mut texture, &RgbaImage::from_pixel(4, 4, Rgba([255, 0, 0, 255])), 2, 2);
+        let original = set_dpi(&encode_png(&texture).unwrap(), 144).unwrap();
+        std::fs::write(&texture_path, &original).unwrap();
+
+        let replacement = dir.join("coin.png");
+        let config = |path: &Path| PatchAtlasConfig {
+            plist_path: plist_path.to_string_lossy().to_string(),
+            replacements: vec![FrameReplacement {
+                frame_name: "coin.png".to_string(),
+                path: path.to_string_lossy().to_string(),
+            }],
+            output_dir: None,
+        };
+
+        // 内容相同时不重写纹理
+        RgbaImage::from_pixel(4, 4, Rgba([255, 0, 0, 255])).save(&replacement).unwrap();
+        let result = patch_atlas_impl(config(&replacement)).unwrap();
+        assert!(!result.texture_written);
+        assert_eq!(std::fs::read(&texture_path).unwrap(), original);
+
+        RgbaImage::from_pixel(4, 4, Rgba([0, 255, 0, 255])).save(&replacement).unwrap();
+        let result = patch_atlas_impl(config(&replacement)).unwrap();
+        assert_eq!(result.patched_frames, vec!["coin.png".to_string()]);
+        let written = std::fs::read(&texture_path).unwrap();
+        assert_eq!(read_dpi(&written).unwrap(), Some(144));
+        let patched = image::load_from_memory(&written).unwrap().to_rgba8();
+        assert_eq!(*patched.get_pixel(3, 3), Rgba([0, 255, 0, 255]));
+        assert_eq!(std::fs::read_to_string(&plist_path).unwrap(), plist);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,102 @@
+/// 物理形状导出命令 (Physics Shape Export Commands)
+///
+/// 提取每帧轮廓并导出 PhysicsEditor 格式的 plist，与精灵帧一起交付
+
+use crate::core::outline::{extract_outline, OutlineOptions};
+use crate::core::physics::{generate_physics_plist, FixtureParams, PhysicsBody, PhysicsExportOptions};
+use crate::core::types::FrameInfo;
+use crate::utils::guard::run_blocking;
+use crate::utils::image_loader::{crop_frame, load_image, ImageLoadLimits};
+use image::DynamicImage;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// 物理形状来源（一帧）
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhysicsShapeSource {
+    /// 刚体名称（通常为帧名称）
+    pub name: String,
+    /// 图像文件路径（单张精灵或图集）
+    pub path: String,
+    /// 图集中的帧（可选，不设置则使用整张图像）
+    pub frame: Option<FrameInfo>,
+    /// 该帧的夹具参数（可选，默认使用 `default_fixture`）
+    pub fixture: Option<FixtureParams>,
+}
+
+/// 物理形状导出配置
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhysicsExportConfig {
+    /// 要导出的帧
+    pub shapes: Vec<PhysicsShapeSource>,
+    /// 输出文件路径（.plist）
+    pub output_path: String,
+    /// 默认夹具参数（可选，默认密度 2、摩擦 0、弹性 0）
+    pub default_fixture: Option<FixtureParams>,
+    /// 导出选项（可选）
+    pub options: Option<PhysicsExportOptions>,
+    /// 轮廓提取选项（可选）
+    pub outline: Option<OutlineOptions>,
+}
+
+/// 导出物理形状 plist 命令
+///
+/// # Arguments
+/// * `config` - 导出配置
+///
+/// # Returns
+/// * `Result<String, String>` - 输出文件路径或错误信息
+#[tauri::command]
+pub async fn export_physics_plist(config: PhysicsExportConfig) -> Result<String, String> {
+    run_blocking("export_physics_plist", move || export_physics_plist_impl(config)).await
+}
+
+fn export_physics_plist_impl(config: PhysicsExportConfig) -> Result<String, String> {
+    if config.shapes.is_empty() {
+        return Err("没有帧可导出".to_string());
+    }
+
+    let default_fixture = config.default_fixture.unwrap_or_default();
+    let outline_options = config.outline.unwrap_or_default();
+
+    // 同一图集只加载一次
+    let mut images: HashMap<String, DynamicImage> = HashMap::new();
+    let mut bodies = Vec::with_capacity(config.shapes.len());
+
+    for shape in &config.shapes {
+        if !images.contains_key(&shape.path) {
+            let img = load_image(&shape.path, &ImageLoadLimits::default())?;
+            images.insert(shape.path.clone(), img);
+        }
+        let source_img = &images[&shape.path];
+
+        let image = match &shape.frame {
+            Some(frame) => crop_frame(source_img, frame)?,
+            None => source_img.to_rgba8(),
+        };
+
+        bodies.push(PhysicsBody {
+            name: shape.name.clone(),
+            width: image.width(),
+            height: image.height(),
+            outline: extract_outline(&image, &outline_options),
+            fixture: shape.fixture.unwrap_or(default_fixture),
+        });
+    }
+
+    let content = generate_physics_plist(&bodies, &config.options.unwrap_or_default())?;
+
+    if let Some(parent) = Path::new(&config.output_path).parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("无法创建输出目录: {}", e))?;
+    }
+    std::fs::write(&config.output_path, content)
+        .map_err(|e| format!("写入文件失败: {}", e))?;
+
+    println!("✓ 物理形状导出成功: {} ({} 个刚体)", config.output_path, bodies.len());
+
+    Ok(config.output_path)
+}
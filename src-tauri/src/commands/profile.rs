@@ -0,0 +1,89 @@
+/// 引擎配置检查命令 (Engine Profile Commands)
+///
+/// 按目标引擎的限制检查当前的打包/导出配置
+
+use crate::commands::pack::{PackConfig, DEFAULT_MAX_TEXTURE_SIZE};
+use crate::core::engine_profile::{self, EngineProfile, ProfileIssue, ProfileSpec, ProfileTarget};
+use crate::core::exporters::SheetFormat;
+use crate::utils::guard::catch_panic;
+use serde::Serialize;
+
+/// 引擎配置信息
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineProfileInfo {
+    /// 引擎
+    pub profile: EngineProfile,
+    /// 显示名称
+    pub name: &'static str,
+    /// 引擎限制
+    pub spec: ProfileSpec,
+}
+
+/// 列出引擎配置命令
+///
+/// # Returns
+/// * `Result<Vec<EngineProfileInfo>, String>` - 所有引擎配置及其限制
+#[tauri::command]
+pub async fn list_engine_profiles() -> Result<Vec<EngineProfileInfo>, String> {
+    catch_panic("list_engine_profiles", list_engine_profiles_impl()).await
+}
+
+async fn list_engine_profiles_impl() -> Result<Vec<EngineProfileInfo>, String> {
+    Ok(EngineProfile::ALL
+        .into_iter()
+        .map(|profile| EngineProfileInfo {
+            profile,
+            name: profile.display_name(),
+            spec: profile.spec(),
+        })
+        .collect())
+}
+
+/// 按引擎检查配置命令
+///
+/// # Arguments
+/// * `profile` - 目标引擎
+/// * `config` - 打包配置（可选，未设置的字段按打包时的默认值处理）
+/// * `format` - 导出的数据格式（可选，默认 plist）
+/// * `sprite_names` - 帧名（可选，用于检查帧名规则）
+///
+/// # Returns
+/// * `Result<Vec<ProfileIssue>, String>` - 发现的问题，全部满足时为空
+#[tauri::command]
+pub async fn validate_for_profile(
+    profile: EngineProfile,
+    config: Option<PackConfig>,
+    format: Option<SheetFormat>,
+    sprite_names: Option<Vec<String>>,
+) -> Result<Vec<ProfileIssue>, String> {
+    catch_panic(
+        "validate_for_profile",
+        validate_for_profile_impl(profile, config.unwrap_or_default(), format, sprite_names),
+    )
+    .await
+}
+
+async fn validate_for_profile_impl(
+    profile: EngineProfile,
+    config: PackConfig,
+    format: Option<SheetFormat>,
+    sprite_names: Option<Vec<String>>,
+) -> Result<Vec<ProfileIssue>, String> {
+    let target = ProfileTarget {
+        max_width: config.max_width.unwrap_or(DEFAULT_MAX_TEXTURE_SIZE),
+        max_height: config.max_height.unwrap_or(DEFAULT_MAX_TEXTURE_SIZE),
+        auto_size: config.auto_size.unwrap_or(true) && !config.force_max_size.unwrap_or(false),
+        size_constraint: config.size_constraint.unwrap_or_default(),
+        allow_rotation: config.allow_rotation.unwrap_or(true),
+        format: format.unwrap_or_default(),
+        sprite_names: sprite_names.unwrap_or_default(),
+    };
+    let issues = engine_profile::validate_for_profile(profile, &target);
+    if issues.is_empty() {
+        println!("✓ 配置符合 {} 的要求", profile.display_name());
+    } else {
+        println!("警告: 配置有 {} 处不符合 {} 的要求", issues.len(), profile.display_name());
+    }
+    Ok(issues)
+}
@@ -0,0 +1,176 @@
+/// 项目文件命令 (Project File Commands)
+///
+/// 保存与打开 `.ezplist` 项目文件，恢复精灵列表、会话与各项配置
+
+use crate::commands::history::ExportHistoryStore;
+use crate::commands::session::SessionStore;
+use crate::core::limits::MAX_TEXT_INPUT_BYTES;
+use crate::core::notes::validate_note;
+use crate::core::project::{MissingSource, Project, PROJECT_EXTENSION, PROJECT_VERSION};
+use crate::utils::guard::run_blocking;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+/// 打开项目的结果
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadProjectResult {
+    /// 项目内容（来源路径已解析为绝对路径；会话已以新 ID 加入会话存储）
+    pub project: Project,
+    /// 找不到的来源文件（为空表示全部存在）
+    pub missing: Vec<MissingSource>,
+}
+
+/// 保存项目命令
+///
+/// 导出历史由后端记录，保存时写入当前的全部记录
+///
+/// # Arguments
+/// * `path` - 项目文件路径（没有扩展名时补上 `.ezplist`）
+/// * `project` - 项目内容
+///
+/// # Returns
+/// * `Result<String, String>` - 实际写入的路径或错误信息
+#[tauri::command]
+pub async fn save_project(
+    history: State<'_, ExportHistoryStore>,
+    path: String,
+    mut project: Project,
+) -> Result<String, String> {
+    project.history = history.list();
+    run_blocking("save_project", move || save_project_impl(path, project)).await
+}
+
+fn save_project_impl(path: String, mut project: Project) -> Result<String, String> {
+    for note in project.notes.values() {
+        validate_note(note)?;
+    }
+    let mut path = PathBuf::from(path);
+    if path.extension().is_none() {
+        path.set_extension(PROJECT_EXTENSION);
+    }
+    let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    std::fs::create_dir_all(dir).map_err(|e| format!("无法创建目录 {}: {}", dir.display(), e))?;
+
+    project.version = PROJECT_VERSION;
+    project.relativize(dir);
+    let text = serde_json::to_string_pretty(&project).map_err(|e| format!("序列化项目失败: {}", e))?;
+
+    // 先写临时文件再替换，写入中断时不会损坏已有项目
+    let temp = dir.join(format!(".{}.tmp", uuid::Uuid::new_v4()));
+    std::fs::write(&temp, text).map_err(|e| format!("无法写入项目文件: {}", e))?;
+    std::fs::rename(&temp, &path).map_err(|e| {
+        let _ = std::fs::remove_file(&temp);
+        format!("无法写入项目文件 {}: {}", path.display(), e)
+    })?;
+
+    println!("✓ 保存项目: {} ({} 个精灵)", path.display(), project.sprites.len());
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// 打开项目命令
+///
+/// 相对路径按项目文件所在目录解析，并检查每个来源文件是否仍然存在；
+/// 缺失的文件只在结果中报告，不阻止打开。项目中的导出历史替换当前历史
+///
+/// # Arguments
+/// * `path` - 项目文件路径
+///
+/// # Returns
+/// * `Result<LoadProjectResult, String>` - 项目内容与缺失的来源；文件无效或版本过高时返回错误
+#[tauri::command]
+pub async fn load_project(
+    sessions: State<'_, SessionStore>,
+    history: State<'_, ExportHistoryStore>,
+    path: String,
+) -> Result<LoadProjectResult, String> {
+    let (sessions, history) = (sessions.inner().clone(), history.inner().clone());
+    run_blocking("load_project", move || load_project_impl(&sessions, &history, path)).await
+}
+
+fn load_project_impl(
+    sessions: &SessionStore,
+    history: &ExportHistoryStore,
+    path: String,
+) -> Result<LoadProjectResult, String> {
+    let size = std::fs::metadata(&path).map_err(|e| format!("无法读取项目文件 {}: {}", path, e))?.len();
+    if size > MAX_TEXT_INPUT_BYTES {
+        return Err(format!("项目文件超过 {} 字节: {}", MAX_TEXT_INPUT_BYTES, path));
+    }
+    let text = std::fs::read_to_string(&path).map_err(|e| format!("无法读取项目文件 {}: {}", path, e))?;
+    let mut project = Project::parse(&text)?;
+
+    let dir = Path::new(&path).parent().unwrap_or(Path::new("."));
+    project.resolve(dir);
+    let missing = project.missing_sources(|source| Path::new(source).is_file());
+    for source in &missing {
+        println!("警告: 找不到来源文件 {}: {}", source.name, source.path);
+    }
+    project.session = project.session.take().map(|session| sessions.restore(session));
+    history.replace(project.history.clone());
+
+    println!("✓ 打开项目: {} ({} 个精灵, {} 个来源缺失)", path, project.sprites.len(), missing.len());
+    Ok(LoadProjectResult { project, missing })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::SpriteData;
+    use image::{Rgba, RgbaImage};
+
+    #[test]
+    fn test_save_and_load_project() {
+        let dir = std::env::temp_dir().join(format!("ezplist_project_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("art")).unwrap();
+        let image = |name: &str| {
+            let path = dir.join("art").join(name);
+            RgbaImage::from_pixel(4, 4, Rgba([255, 0, 0, 255])).save(&path).unwrap();
+            SpriteData {
+                id: name.to_string(),
+                name: name.to_string(),
+                path: path.to_string_lossy().to_string(),
+                width: 4,
+                height: 4,
+                trimmed_width: 4,
+                trimmed_height: 4,
+                anchor: None,
+                nine_slice: None,
+            }
+        };
+        let sessions = SessionStore::default();
+        let mut session = sessions.create();
+        session.preprocess = vec![crate::core::preprocess::PreprocessStep::PadToMultiple { multiple: 4 }];
+        let project = Project {
+            version: 0,
+            sprites: vec![image("a.png"), image("b.png")],
+            notes: Default::default(),
+            session: Some(session.clone()),
+            spritesheet_path: None,
+            pack_config: serde_json::json!({ "padding": 2, "allowRotation": false }),
+            split_config: serde_json::Value::Null,
+            compose_config: serde_json::Value::Null,
+            regions: serde_json::json!([]),
+            history: Vec::new(),
+        };
+
+        let saved = save_project_impl(dir.join("level1").to_string_lossy().to_string(), project).unwrap();
+        assert!(saved.ends_with("level1.ezplist"));
+        let text = std::fs::read_to_string(&saved).unwrap();
+        assert!(text.contains("\"version\": 1"));
+        assert!(!text.contains(dir.to_string_lossy().as_ref()));
+
+        std::fs::remove_file(dir.join("art").join("b.png")).unwrap();
+        let history = ExportHistoryStore::default();
+        let loaded = load_project_impl(&sessions, &history, saved).unwrap();
+        assert_eq!(loaded.project.pack_config["padding"], 2);
+        assert!(Path::new(&loaded.project.sprites[0].path).is_file());
+        assert_eq!(loaded.missing.len(), 1);
+        assert_eq!(loaded.missing[0].name, "b.png");
+        // 会话以新 ID 恢复，预处理步骤保留
+        let restored = loaded.project.session.unwrap();
+        assert_ne!(restored.id, session.id);
+        assert_eq!(restored.preprocess, session.preprocess);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
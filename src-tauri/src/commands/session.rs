@@ -0,0 +1,945 @@
+/// 打包会话命令 (Pack Session Commands)
+///
+/// 会话把独立图片与多张图集中切分出的帧放在一起，统一打包成一张图集，
+/// 打通导入与切分两条流程
+
+use crate::commands::pack::{pack_with_loader, PackConfig, PackSessionStore};
+use crate::core::anchor::{estimate_anchors as estimate_content_anchors, AnchorStrategy};
+use crate::core::preprocess::{apply_preprocess, validate_steps, PreprocessStep};
+use crate::core::session::{Session, SessionSprite, SourceStamp, SpritePatch, SpriteSource, StaleReason, StaleSource, TransplantMode};
+use crate::core::sprite_filter::{filter_sprites, SpriteFilter};
+use crate::core::types::{FrameInfo, PackResult, PackedSprite, SpriteData, SpritesheetInfo};
+use crate::utils::guard::{catch_panic, run_blocking};
+use crate::utils::frame_cache::{default_cache_dir, CachedAtlas};
+use crate::utils::image_loader::{load_image, ImageLoadLimits};
+use crate::utils::source_stamp::{current_stamp, stamp_file};
+use image::RgbaImage;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tauri::State;
+
+/// 会话存储（Tauri 托管状态）
+///
+/// 保存所有打开的会话以及窗口与会话的绑定，每个项目窗口使用自己的会话。
+/// 克隆后共享同一份数据
+#[derive(Clone, Default)]
+pub struct SessionStore {
+    inner: Arc<Mutex<Sessions>>,
+}
+
+#[derive(Default)]
+struct Sessions {
+    /// 会话 ID -> 会话
+    sessions: HashMap<String, Session>,
+    /// 窗口标签 -> 会话 ID
+    windows: HashMap<String, String>,
+}
+
+impl SessionStore {
+    fn lock(&self) -> MutexGuard<'_, Sessions> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 新建空会话
+    pub fn create(&self) -> Session {
+        let session = Session::new(uuid::Uuid::new_v4().to_string());
+        self.lock().sessions.insert(session.id.clone(), session.clone());
+        session
+    }
+
+    /// 以新 ID 加入已保存的会话（如项目文件中的会话）
+    pub fn restore(&self, mut session: Session) -> Session {
+        session.id = uuid::Uuid::new_v4().to_string();
+        self.lock().sessions.insert(session.id.clone(), session.clone());
+        session
+    }
+
+    /// 关闭会话，同时解除绑定到它的窗口
+    pub fn close(&self, session_id: &str) -> Result<(), String> {
+        let mut inner = self.lock();
+        inner
+            .sessions
+            .remove(session_id)
+            .ok_or_else(|| format!("会话不存在: {}", session_id))?;
+        inner.windows.retain(|_, bound| bound != session_id);
+        Ok(())
+    }
+
+    /// 在锁内修改指定会话
+    pub(crate) fn with_session<T>(
+        &self,
+        session_id: &str,
+        f: impl FnOnce(&mut Session) -> Result<T, String>,
+    ) -> Result<T, String> {
+        let mut inner = self.lock();
+        let session = inner
+            .sessions
+            .get_mut(session_id)
+            .ok_or_else(|| format!("会话不存在: {}", session_id))?;
+        f(session)
+    }
+
+    /// 写回在副本上修改过的多个会话（任一会话已关闭时全部不写入）
+    fn commit(&self, updated: &[&Session]) -> Result<(), String> {
+        let mut inner = self.lock();
+        if let Some(closed) = updated.iter().find(|s| !inner.sessions.contains_key(&s.id)) {
+            return Err(format!("会话在操作期间被关闭: {}", closed.id));
+        }
+        for session in updated {
+            inner.sessions.insert(session.id.clone(), (*session).clone());
+        }
+        Ok(())
+    }
+
+    /// 把窗口绑定到会话
+    pub fn bind_window(&self, label: &str, session_id: &str) -> Result<(), String> {
+        let mut inner = self.lock();
+        if !inner.sessions.contains_key(session_id) {
+            return Err(format!("会话不存在: {}", session_id));
+        }
+        inner.windows.insert(label.to_string(), session_id.to_string());
+        Ok(())
+    }
+
+    /// 窗口绑定的会话；尚未绑定时新建会话并绑定
+    pub fn window_session(&self, label: &str) -> Session {
+        let mut inner = self.lock();
+        let bound = inner.windows.get(label).and_then(|id| inner.sessions.get(id)).cloned();
+        if let Some(session) = bound {
+            return session;
+        }
+        let session = Session::new(uuid::Uuid::new_v4().to_string());
+        inner.sessions.insert(session.id.clone(), session.clone());
+        inner.windows.insert(label.to_string(), session.id.clone());
+        session
+    }
+
+    /// 解除窗口绑定；没有其他窗口使用该会话时一并关闭会话
+    ///
+    /// # Returns
+    /// * `Option<String>` - 被关闭的会话 ID
+    pub fn release_window(&self, label: &str) -> Option<String> {
+        let mut inner = self.lock();
+        let session_id = inner.windows.remove(label)?;
+        if inner.windows.values().any(|bound| *bound == session_id) {
+            return None;
+        }
+        inner.sessions.remove(&session_id).map(|_| session_id)
+    }
+}
+
+/// 创建会话命令
+///
+/// # Returns
+/// * `Result<Session, String>` - 新建的空会话
+#[tauri::command]
+pub async fn create_session(sessions: State<'_, SessionStore>) -> Result<Session, String> {
+    catch_panic("create_session", create_session_impl(&sessions)).await
+}
+
+async fn create_session_impl(sessions: &SessionStore) -> Result<Session, String> {
+    let session = sessions.create();
+    println!("✓ 创建会话: {}", session.id);
+    Ok(session)
+}
+
+/// 获取会话命令
+///
+/// # Arguments
+/// * `session_id` - 会话 ID
+///
+/// # Returns
+/// * `Result<Session, String>` - 会话内容或错误信息
+#[tauri::command]
+pub async fn get_session(sessions: State<'_, SessionStore>, session_id: String) -> Result<Session, String> {
+    catch_panic("get_session", get_session_impl(&sessions, session_id)).await
+}
+
+async fn get_session_impl(sessions: &SessionStore, session_id: String) -> Result<Session, String> {
+    sessions.with_session(&session_id, |session| Ok(session.clone()))
+}
+
+/// 关闭会话命令
+///
+/// # Arguments
+/// * `session_id` - 会话 ID
+#[tauri::command]
+pub async fn close_session(sessions: State<'_, SessionStore>, session_id: String) -> Result<(), String> {
+    catch_panic("close_session", close_session_impl(&sessions, session_id)).await
+}
+
+async fn close_session_impl(sessions: &SessionStore, session_id: String) -> Result<(), String> {
+    sessions.close(&session_id)
+}
+
+/// 向会话添加独立图片命令
+///
+/// # Arguments
+/// * `session_id` - 会话 ID
+/// * `sprites` - `import_images` 返回的精灵
+///
+/// # Returns
+/// * `Result<Session, String>` - 更新后的会话
+#[tauri::command]
+pub async fn add_session_images(sessions: State<'_, SessionStore>, session_id: String, sprites: Vec<SpriteData>) -> Result<Session, String> {
+    catch_panic("add_session_images", add_session_images_impl(&sessions, session_id, sprites)).await
+}
+
+async fn add_session_images_impl(sessions: &SessionStore, session_id: String, sprites: Vec<SpriteData>) -> Result<Session, String> {
+    let sprites = sprites
+        .into_iter()
+        .map(|sprite| SessionSprite {
+            stamp: record_stamp(&sprite.path),
+            ..SessionSprite::new(
+                sprite.id,
+                sprite.name,
+                SpriteSource::Image { path: sprite.path },
+                sprite.width,
+                sprite.height,
+            )
+        })
+        .collect();
+
+    sessions.with_session(&session_id, |session| {
+        session.add_sprites(sprites)?;
+        Ok(session.clone())
+    })
+}
+
+/// 向会话添加图集帧命令
+///
+/// # Arguments
+/// * `session_id` - 会话 ID
+/// * `spritesheet` - 图集信息
+/// * `frames` - 切分得到的帧（如 `calculate_split_frames` 的结果）
+///
+/// # Returns
+/// * `Result<Session, String>` - 更新后的会话；帧超出图集范围时整批拒绝
+#[tauri::command]
+pub async fn add_session_frames(
+    sessions: State<'_, SessionStore>,
+    session_id: String,
+    spritesheet: SpritesheetInfo,
+    frames: Vec<FrameInfo>,
+) -> Result<Session, String> {
+    catch_panic("add_session_frames", add_session_frames_impl(&sessions, session_id, spritesheet, frames)).await
+}
+
+async fn add_session_frames_impl(
+    sessions: &SessionStore,
+    session_id: String,
+    spritesheet: SpritesheetInfo,
+    frames: Vec<FrameInfo>,
+) -> Result<Session, String> {
+    let stamp = record_stamp(&spritesheet.path);
+    let mut sprites = Vec::with_capacity(frames.len());
+    for frame in frames {
+        let inside = frame.x.checked_add(frame.width).is_some_and(|r| r <= spritesheet.width)
+            && frame.y.checked_add(frame.height).is_some_and(|b| b <= spritesheet.height);
+        if !inside {
+            return Err(format!("帧 {} 超出图集范围 {}x{}", frame.name, spritesheet.width, spritesheet.height));
+        }
+        let source = SpriteSource::SheetFrame {
+            sheet_path: spritesheet.path.clone(),
+            x: frame.x,
+            y: frame.y,
+        };
+        sprites.push(SessionSprite {
+            stamp: stamp.clone(),
+            ..SessionSprite::new(uuid::Uuid::new_v4().to_string(), frame.name, source, frame.width, frame.height)
+        });
+    }
+
+    sessions.with_session(&session_id, |session| {
+        session.add_sprites(sprites)?;
+        Ok(session.clone())
+    })
+}
+
+/// 记录来源文件指纹（读取失败时只给出警告，该精灵不参与过期检查）
+fn record_stamp(path: &str) -> Option<SourceStamp> {
+    stamp_file(path)
+        .map_err(|e| println!("警告: 无法记录来源指纹: {}", e))
+        .ok()
+}
+
+/// 从会话移除精灵命令
+///
+/// # Arguments
+/// * `session_id` - 会话 ID
+/// * `ids` - 要移除的精灵 ID
+///
+/// # Returns
+/// * `Result<Session, String>` - 更新后的会话
+#[tauri::command]
+pub async fn remove_session_sprites(sessions: State<'_, SessionStore>, session_id: String, ids: Vec<String>) -> Result<Session, String> {
+    catch_panic("remove_session_sprites", remove_session_sprites_impl(&sessions, session_id, ids)).await
+}
+
+async fn remove_session_sprites_impl(sessions: &SessionStore, session_id: String, ids: Vec<String>) -> Result<Session, String> {
+    sessions.with_session(&session_id, |session| {
+        session.remove_sprites(&ids);
+        Ok(session.clone())
+    })
+}
+
+/// 搜索会话精灵命令
+///
+/// # Arguments
+/// * `session_id` - 会话 ID
+/// * `filter` - 过滤条件（名称通配符/正则、尺寸范围、标签、来源文件夹）
+///
+/// # Returns
+/// * `Result<Vec<String>, String>` - 匹配的精灵 ID，按会话中的顺序排列
+#[tauri::command]
+pub async fn search_session_sprites(sessions: State<'_, SessionStore>, session_id: String, filter: SpriteFilter) -> Result<Vec<String>, String> {
+    catch_panic("search_session_sprites", search_session_sprites_impl(&sessions, session_id, filter)).await
+}
+
+async fn search_session_sprites_impl(sessions: &SessionStore, session_id: String, filter: SpriteFilter) -> Result<Vec<String>, String> {
+    sessions.with_session(&session_id, |session| filter_sprites(&session.sprites, &filter))
+}
+
+/// 批量修改会话精灵命令
+///
+/// 一次调用修改多个精灵的名称、标签、轴心点、旋转许可与排除状态，
+/// 所有修改通过校验后才会生效
+///
+/// # Arguments
+/// * `session_id` - 会话 ID
+/// * `ids` - 要修改的精灵 ID（顺序决定重命名序号）
+/// * `patch` - 修改内容
+///
+/// # Returns
+/// * `Result<Session, String>` - 更新后的会话
+#[tauri::command]
+pub async fn update_sprites(sessions: State<'_, SessionStore>, session_id: String, ids: Vec<String>, patch: SpritePatch) -> Result<Session, String> {
+    catch_panic("update_sprites", update_sprites_impl(&sessions, session_id, ids, patch)).await
+}
+
+async fn update_sprites_impl(sessions: &SessionStore, session_id: String, ids: Vec<String>, patch: SpritePatch) -> Result<Session, String> {
+    sessions.with_session(&session_id, |session| {
+        session.update_sprites(&ids, &patch)?;
+        println!("✓ 批量修改 {} 个精灵", ids.len());
+        Ok(session.clone())
+    })
+}
+
+/// 设置会话预处理流水线命令
+///
+/// 步骤在打包（以及锚点估算）加载图像时按顺序应用于每个精灵，不修改源文件
+///
+/// # Arguments
+/// * `session_id` - 会话 ID
+/// * `steps` - 预处理步骤（为空时关闭预处理）
+///
+/// # Returns
+/// * `Result<Session, String>` - 更新后的会话
+#[tauri::command]
+pub async fn set_session_preprocess(sessions: State<'_, SessionStore>, session_id: String, steps: Vec<PreprocessStep>) -> Result<Session, String> {
+    catch_panic("set_session_preprocess", set_session_preprocess_impl(&sessions, session_id, steps)).await
+}
+
+async fn set_session_preprocess_impl(sessions: &SessionStore, session_id: String, steps: Vec<PreprocessStep>) -> Result<Session, String> {
+    validate_steps(&steps)?;
+    sessions.with_session(&session_id, |session| {
+        println!("✓ 会话预处理: {} 个步骤", steps.len());
+        session.preprocess = steps;
+        Ok(session.clone())
+    })
+}
+
+/// 打包会话命令
+///
+/// 会话中未排除的精灵（包括来自不同图集的帧）打包到同一张图集，
+/// 裁剪结果与 `pack_sprites` 一样存入打包结果存储，导出时传回返回的 `job_id`
+///
+/// # Arguments
+/// * `session_id` - 会话 ID
+/// * `config` - 打包配置
+///
+/// # Returns
+/// * `Result<PackResult, String>` - 打包结果或错误信息
+#[tauri::command]
+pub async fn pack_session(
+    sessions: State<'_, SessionStore>,
+    store: State<'_, PackSessionStore>,
+    session_id: String,
+    config: Option<PackConfig>,
+) -> Result<PackResult, String> {
+    let sessions = sessions.inner().clone();
+    let store = store.inner().clone();
+    run_blocking("pack_session", move || pack_session_impl(&sessions, &store, session_id, config)).await
+}
+
+fn pack_session_impl(
+    sessions: &SessionStore,
+    store: &PackSessionStore,
+    session_id: String,
+    config: Option<PackConfig>,
+) -> Result<PackResult, String> {
+    // 复制会话后释放锁，加载图像期间不阻塞其他命令
+    let session = sessions.with_session(&session_id, |session| Ok(session.clone()))?;
+    pack_session_sprites(store, &session, config)
+}
+
+/// 打包会话中未排除的精灵
+fn pack_session_sprites(
+    store: &PackSessionStore,
+    session: &Session,
+    config: Option<PackConfig>,
+) -> Result<PackResult, String> {
+    let included: Vec<&SessionSprite> = session.sprites.iter().filter(|s| !s.excluded).collect();
+    let sprites: Vec<SpriteData> = included
+        .iter()
+        .map(|sprite| SpriteData {
+            id: sprite.id.clone(),
+            name: sprite.name.clone(),
+            path: sprite.source.path().to_string(),
+            width: sprite.width,
+            height: sprite.height,
+            trimmed_width: sprite.width,
+            trimmed_height: sprite.height,
+            anchor: sprite.pivot,
+            nine_slice: None,
+        })
+        .collect();
+
+    // 会话中禁止旋转的精灵与配置中的锁定列表合并
+    let mut config = config.unwrap_or_default();
+    let locked = included.iter().filter(|s| !s.allow_rotation).map(|s| s.id.clone());
+    config.rotation_locked_ids = Some(config.rotation_locked_ids.take().into_iter().flatten().chain(locked).collect());
+
+    let mut sheets: HashMap<String, CachedAtlas> = HashMap::new();
+    let mut result = pack_with_loader(store, &sprites, Some(config), |sprite| {
+        let source = session.sprite(&sprite.id).ok_or("精灵不在会话中")?;
+        load_session_sprite(source, &session.preprocess, &mut sheets)
+    })?;
+
+    // 来源在导入后被修改的精灵按新内容打包，但记录的尺寸可能已过期
+    for stale in session.stale_sources(current_stamp) {
+        if included.iter().any(|s| s.id == stale.id) {
+            result.warnings.push(stale_warning(&stale));
+        }
+    }
+    Ok(result)
+}
+
+/// 来源过期的警告文本
+fn stale_warning(stale: &StaleSource) -> String {
+    match stale.reason {
+        StaleReason::Modified => format!("{} 的来源文件在导入后已修改: {}，请重新加载", stale.name, stale.path),
+        StaleReason::Missing => format!("{} 的来源文件已删除或无法读取: {}", stale.name, stale.path),
+    }
+}
+
+/// 检查过期来源命令
+///
+/// 导出前调用，找出来源文件在加入会话后被修改或删除的精灵
+///
+/// # Arguments
+/// * `session_id` - 会话 ID
+///
+/// # Returns
+/// * `Result<Vec<StaleSource>, String>` - 过期的精灵（为空表示全部来源未变）
+#[tauri::command]
+pub async fn check_stale_sources(sessions: State<'_, SessionStore>, session_id: String) -> Result<Vec<StaleSource>, String> {
+    let sessions = sessions.inner().clone();
+    run_blocking("check_stale_sources", move || check_stale_sources_impl(&sessions, session_id)).await
+}
+
+fn check_stale_sources_impl(sessions: &SessionStore, session_id: String) -> Result<Vec<StaleSource>, String> {
+    let session = sessions.with_session(&session_id, |session| Ok(session.clone()))?;
+    let stale = session.stale_sources(current_stamp);
+    if !stale.is_empty() {
+        println!("警告: {} 个精灵的来源文件已过期", stale.len());
+    }
+    Ok(stale)
+}
+
+/// 重新加载结果
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReloadResult {
+    /// 更新后的会话
+    pub session: Session,
+    /// 已刷新的精灵 ID
+    pub reloaded: Vec<String>,
+    /// 无法重新加载的原因（来源缺失、帧超出新图集范围等）
+    pub failed: Vec<String>,
+}
+
+/// 重新加载精灵命令
+///
+/// 重新读取来源文件，刷新精灵的尺寸与指纹；之后打包时使用新的像素。
+/// 图集帧保持原区域，只检查区域仍在新图集范围内
+///
+/// # Arguments
+/// * `session_id` - 会话 ID
+/// * `ids` - 要重新加载的精灵 ID（可选，默认为所有来源已修改的精灵）
+///
+/// # Returns
+/// * `Result<ReloadResult, String>` - 更新后的会话与每个精灵的结果
+#[tauri::command]
+pub async fn reload_sprites(sessions: State<'_, SessionStore>, session_id: String, ids: Option<Vec<String>>) -> Result<ReloadResult, String> {
+    let sessions = sessions.inner().clone();
+    run_blocking("reload_sprites", move || reload_sprites_impl(&sessions, session_id, ids)).await
+}
+
+fn reload_sprites_impl(sessions: &SessionStore, session_id: String, ids: Option<Vec<String>>) -> Result<ReloadResult, String> {
+    let session = sessions.with_session(&session_id, |session| Ok(session.clone()))?;
+    let ids = match ids {
+        Some(ids) => ids,
+        None => session
+            .stale_sources(current_stamp)
+            .into_iter()
+            .filter(|stale| stale.reason == StaleReason::Modified)
+            .map(|stale| stale.id)
+            .collect(),
+    };
+
+    // 每个来源文件只读取一次：(宽, 高, 指纹)
+    let mut files: HashMap<String, Result<(u32, u32, SourceStamp), String>> = HashMap::new();
+    let mut updates = Vec::new();
+    let mut failed = Vec::new();
+    for id in &ids {
+        let Some(sprite) = session.sprite(id) else {
+            failed.push(format!("精灵不存在: {}", id));
+            continue;
+        };
+        let path = sprite.source.path();
+        let file = files.entry(path.to_string()).or_insert_with(|| {
+            let stamp = stamp_file(path)?;
+            let image = load_image(path, &ImageLoadLimits::default())?;
+            Ok((image.width(), image.height(), stamp))
+        });
+        let (width, height, stamp) = match file {
+            Ok(file) => file.clone(),
+            Err(e) => {
+                failed.push(format!("{}: {}", sprite.name, e));
+                continue;
+            }
+        };
+        let size = match &sprite.source {
+            SpriteSource::Image { .. } => (width, height),
+            SpriteSource::SheetFrame { x, y, .. } => {
+                let inside = x.checked_add(sprite.width).is_some_and(|r| r <= width)
+                    && y.checked_add(sprite.height).is_some_and(|b| b <= height);
+                if !inside {
+                    failed.push(format!("{}: 帧超出新图集范围 {}x{}", sprite.name, width, height));
+                    continue;
+                }
+                (sprite.width, sprite.height)
+            }
+        };
+        updates.push((id.clone(), size, stamp));
+    }
+
+    // 读取期间精灵可能已被移除，全部更新成功后才替换会话
+    let session = sessions.with_session(&session_id, |session| {
+        let mut updated = session.clone();
+        for (id, (width, height), stamp) in &updates {
+            updated.refresh_source(id, *width, *height, stamp.clone())?;
+        }
+        *session = updated;
+        Ok(session.clone())
+    })?;
+    println!("✓ 重新加载 {} 个精灵, 失败 {}", updates.len(), failed.len());
+    Ok(ReloadResult {
+        session,
+        reloaded: updates.into_iter().map(|(id, _, _)| id).collect(),
+        failed,
+    })
+}
+
+/// 单个精灵的锚点建议
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpriteAnchor {
+    /// 精灵 ID
+    pub id: String,
+    /// 帧名称
+    pub name: String,
+    /// 建议的轴心点（完全透明的帧为 None）
+    pub pivot: Option<(f32, f32)>,
+}
+
+/// 锚点估算结果
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnchorEstimateResult {
+    /// 实际使用的策略
+    pub strategy: AnchorStrategy,
+    /// 每个精灵的建议（与传入 ID 顺序一致）
+    pub anchors: Vec<SpriteAnchor>,
+}
+
+/// 估算锚点命令
+///
+/// 根据帧内容为一组精灵建议轴心点（角色取底边中点，特效取质心），
+/// 可直接写入会话中精灵的轴心点，供导出器使用
+///
+/// # Arguments
+/// * `session_id` - 会话 ID
+/// * `ids` - 同一组动画的精灵 ID
+/// * `strategy` - 估算策略（可选，默认按内容自动选择）
+/// * `apply` - 是否写入精灵的轴心点（完全透明的帧保持不变）
+///
+/// # Returns
+/// * `Result<AnchorEstimateResult, String>` - 估算结果或错误信息
+#[tauri::command]
+pub async fn estimate_anchors(
+    sessions: State<'_, SessionStore>,
+    session_id: String,
+    ids: Vec<String>,
+    strategy: Option<AnchorStrategy>,
+    apply: bool,
+) -> Result<AnchorEstimateResult, String> {
+    let sessions = sessions.inner().clone();
+    run_blocking("estimate_anchors", move || estimate_anchors_impl(&sessions, session_id, ids, strategy, apply)).await
+}
+
+fn estimate_anchors_impl(
+    sessions: &SessionStore,
+    session_id: String,
+    ids: Vec<String>,
+    strategy: Option<AnchorStrategy>,
+    apply: bool,
+) -> Result<AnchorEstimateResult, String> {
+    if ids.is_empty() {
+        return Err("没有要估算锚点的精灵".to_string());
+    }
+    let session = sessions.with_session(&session_id, |session| Ok(session.clone()))?;
+    let mut sheets: HashMap<String, CachedAtlas> = HashMap::new();
+    let mut sprites = Vec::with_capacity(ids.len());
+    let mut frames = Vec::with_capacity(ids.len());
+    for id in &ids {
+        let sprite = session.sprite(id).ok_or_else(|| format!("精灵不存在: {}", id))?;
+        frames.push(load_session_sprite(sprite, &session.preprocess, &mut sheets)?);
+        sprites.push(sprite);
+    }
+
+    let estimate = estimate_content_anchors(&frames, strategy.unwrap_or_default());
+    let anchors: Vec<SpriteAnchor> = sprites
+        .iter()
+        .zip(estimate.pivots)
+        .map(|(sprite, pivot)| SpriteAnchor {
+            id: sprite.id.clone(),
+            name: sprite.name.clone(),
+            pivot,
+        })
+        .collect();
+
+    if apply {
+        // 在副本上逐个写入，全部成功后才替换会话
+        sessions.with_session(&session_id, |session| {
+            let mut updated = session.clone();
+            for anchor in &anchors {
+                if let Some(pivot) = anchor.pivot {
+                    let patch = SpritePatch { pivot: Some(pivot), ..Default::default() };
+                    updated.update_sprites(std::slice::from_ref(&anchor.id), &patch)?;
+                }
+            }
+            *session = updated;
+            Ok(())
+        })?;
+    }
+    println!("✓ 锚点估算完成: {} 个精灵, 策略 {:?}", anchors.len(), estimate.strategy);
+
+    Ok(AnchorEstimateResult {
+        strategy: estimate.strategy,
+        anchors,
+    })
+}
+
+/// 精灵移植结果
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransplantResult {
+    /// 更新后的来源会话
+    pub source: Session,
+    /// 更新后的目标会话
+    pub target: Session,
+    /// 精灵在目标会话中的 ID
+    pub transplanted_ids: Vec<String>,
+    /// 目标会话重新打包的结果
+    pub pack_result: PackResult,
+}
+
+/// 在会话之间移植精灵命令
+///
+/// 把选中的帧复制或移动到另一个会话（如已发布的图集），保留名称与元数据，
+/// 并重新打包目标会话；打包失败时两个会话都保持不变
+///
+/// # Arguments
+/// * `source_session_id` - 来源会话 ID
+/// * `target_session_id` - 目标会话 ID
+/// * `ids` - 要移植的精灵 ID
+/// * `mode` - 复制或移动（可选，默认复制）
+/// * `config` - 目标会话的打包配置
+///
+/// # Returns
+/// * `Result<TransplantResult, String>` - 移植与打包结果或错误信息
+#[tauri::command]
+pub async fn transplant_sprites(
+    sessions: State<'_, SessionStore>,
+    store: State<'_, PackSessionStore>,
+    source_session_id: String,
+    target_session_id: String,
+    ids: Vec<String>,
+    mode: Option<TransplantMode>,
+    config: Option<PackConfig>,
+) -> Result<TransplantResult, String> {
+    let sessions = sessions.inner().clone();
+    let store = store.inner().clone();
+    run_blocking("transplant_sprites", move || {
+        transplant_sprites_impl(&sessions, &store, source_session_id, target_session_id, ids, mode.unwrap_or_default(), config)
+    })
+    .await
+}
+
+fn transplant_sprites_impl(
+    sessions: &SessionStore,
+    store: &PackSessionStore,
+    source_session_id: String,
+    target_session_id: String,
+    ids: Vec<String>,
+    mode: TransplantMode,
+    config: Option<PackConfig>,
+) -> Result<TransplantResult, String> {
+    // 在副本上移植并打包，成功后再写回
+    let mut source = sessions.with_session(&source_session_id, |session| Ok(session.clone()))?;
+    let mut target = sessions.with_session(&target_session_id, |session| Ok(session.clone()))?;
+    let transplanted_ids = target.transplant_from(&mut source, &ids, mode, || uuid::Uuid::new_v4().to_string())?;
+
+    let pack_result = pack_session_sprites(store, &target, config)?;
+
+    sessions.commit(&[&source, &target])?;
+
+    println!("✓ 移植 {} 个精灵: {} -> {}", transplanted_ids.len(), source.id, target.id);
+
+    Ok(TransplantResult {
+        source,
+        target,
+        transplanted_ids,
+        pack_result,
+    })
+}
+
+/// 加载会话精灵图像并应用预处理，同一图集只解码一次
+fn load_session_sprite(
+    sprite: &SessionSprite,
+    steps: &[PreprocessStep],
+    sheets: &mut HashMap<String, CachedAtlas>,
+) -> Result<RgbaImage, String> {
+    let image = match &sprite.source {
+        SpriteSource::Image { path } => load_image(path, &ImageLoadLimits::default())?.to_rgba8(),
+        SpriteSource::SheetFrame { sheet_path, x, y } => {
+            if !sheets.contains_key(sheet_path) {
+                let sheet = CachedAtlas::open(&default_cache_dir(), sheet_path)?;
+                sheets.insert(sheet_path.clone(), sheet);
+            }
+            let frame = PackedSprite {
+                id: sprite.id.clone(),
+                name: sprite.name.clone(),
+                x: *x,
+                y: *y,
+                width: sprite.width,
+                height: sprite.height,
+                rotated: false,
+                original_width: sprite.width,
+                original_height: sprite.height,
+                trimmed: false,
+                offset_x: 0,
+                offset_y: 0,
+                flip_x: false,
+                flip_y: false,
+                anchor: None,
+                nine_slice: None,
+            };
+            sheets.get_mut(sheet_path).expect("图集已打开").frame(&frame)?
+        }
+    };
+    Ok(apply_preprocess(image, steps))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn test_pack_frames_from_two_sheets() {
+        let dir = std::env::temp_dir().join(format!("ezplist_session_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let sheet = |name: &str, color: [u8; 4]| {
+            let path = dir.join(name);
+            RgbaImage::from_pixel(32, 16, Rgba(color)).save(&path).unwrap();
+            SpritesheetInfo {
+                path: path.to_string_lossy().to_string(),
+                name: name.to_string(),
+                width: 32,
+                height: 16,
+            }
+        };
+        let frames = |prefix: &str| {
+            (0..2)
+                .map(|i| FrameInfo {
+                    name: format!("{}_{:02}.png", prefix, i + 1),
+                    x: i * 16,
+                    y: 0,
+                    width: 16,
+                    height: 16,
+                    row: 0,
+                    col: i,
+                    anchor: None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let sessions = SessionStore::default();
+        let store = PackSessionStore::default();
+        let session = rt.block_on(create_session_impl(&sessions)).unwrap();
+        let walk = sheet("walk.png", [255, 0, 0, 255]);
+        let jump = sheet("jump.png", [0, 0, 255, 255]);
+        rt.block_on(add_session_frames_impl(&sessions, session.id.clone(), walk.clone(), frames("walk"))).unwrap();
+        let updated = rt.block_on(add_session_frames_impl(&sessions, session.id.clone(), jump, frames("jump"))).unwrap();
+        assert_eq!(updated.sprites.len(), 4);
+
+        // 超出图集范围的帧整批拒绝
+        let mut bad = frames("bad");
+        bad[1].x = 30;
+        assert!(rt.block_on(add_session_frames_impl(&sessions, session.id.clone(), walk, bad)).is_err());
+
+        let result = pack_session_impl(&sessions, &store, session.id.clone(), None).unwrap();
+        assert_eq!(result.packed_sprites.len(), 4);
+
+        // 预处理流水线在打包时缩小每一帧
+        let resize = vec![PreprocessStep::ResizeToMax { max_dimension: 8 }];
+        rt.block_on(set_session_preprocess_impl(&sessions, session.id.clone(), resize)).unwrap();
+        let result = pack_session_impl(&sessions, &store, session.id.clone(), None).unwrap();
+        assert!(result.packed_sprites.iter().all(|s| (s.original_width, s.original_height) == (8, 8)));
+        let invalid = vec![PreprocessStep::PadToMultiple { multiple: 0 }];
+        assert!(rt.block_on(set_session_preprocess_impl(&sessions, session.id.clone(), invalid)).is_err());
+        rt.block_on(set_session_preprocess_impl(&sessions, session.id.clone(), Vec::new())).unwrap();
+
+        // 排除的精灵不参与打包
+        let excluded: Vec<String> = updated.sprites.iter().take(2).map(|s| s.id.clone()).collect();
+        let patch = SpritePatch {
+            excluded: Some(true),
+            ..Default::default()
+        };
+        rt.block_on(update_sprites_impl(&sessions, session.id.clone(), excluded, patch)).unwrap();
+        let result = pack_session_impl(&sessions, &store, session.id.clone(), None).unwrap();
+        assert_eq!(result.packed_sprites.len(), 2);
+
+        // 把一帧复制到另一个会话并重新打包
+        let other = rt.block_on(create_session_impl(&sessions)).unwrap();
+        let moved = vec![updated.sprites[3].id.clone()];
+        let result =
+            transplant_sprites_impl(&sessions, &store, session.id.clone(), other.id.clone(), moved, TransplantMode::default(), None)
+                .unwrap();
+        assert_eq!(result.target.sprites[0].name, "jump_02.png");
+        assert_eq!(result.pack_result.packed_sprites.len(), 1);
+        assert_eq!(result.source.sprites.len(), 4);
+        rt.block_on(close_session_impl(&sessions, other.id)).unwrap();
+
+        rt.block_on(close_session_impl(&sessions, session.id.clone())).unwrap();
+        assert!(rt.block_on(get_session_impl(&sessions, session.id)).is_err());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_reload_stale_sources() {
+        let dir = std::env::temp_dir().join(format!("ezplist_stale_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("hero.png");
+        RgbaImage::from_pixel(8, 8, Rgba([255, 0, 0, 255])).save(&path).unwrap();
+        let sprite = SpriteData {
+            id: "hero".to_string(),
+            name: "hero.png".to_string(),
+            path: path.to_string_lossy().to_string(),
+            width: 8,
+            height: 8,
+            trimmed_width: 8,
+            trimmed_height: 8,
+            anchor: None,
+            nine_slice: None,
+        };
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let sessions = SessionStore::default();
+        let store = PackSessionStore::default();
+        let session = rt.block_on(create_session_impl(&sessions)).unwrap();
+        rt.block_on(add_session_images_impl(&sessions, session.id.clone(), vec![sprite])).unwrap();
+        assert!(check_stale_sources_impl(&sessions, session.id.clone()).unwrap().is_empty());
+
+        // 美术覆盖了源文件：打包给出警告，重新加载后尺寸更新
+        RgbaImage::from_pixel(12, 10, Rgba([0, 255, 0, 255])).save(&path).unwrap();
+        let stale = check_stale_sources_impl(&sessions, session.id.clone()).unwrap();
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].reason, StaleReason::Modified);
+        let result = pack_session_impl(&sessions, &store, session.id.clone(), None).unwrap();
+        assert!(result.warnings.iter().any(|w| w.contains("已修改")));
+
+        let reloaded = reload_sprites_impl(&sessions, session.id.clone(), None).unwrap();
+        assert_eq!(reloaded.reloaded, vec!["hero".to_string()]);
+        assert_eq!((reloaded.session.sprites[0].width, reloaded.session.sprites[0].height), (12, 10));
+        assert!(check_stale_sources_impl(&sessions, session.id.clone()).unwrap().is_empty());
+
+        // 删除的源文件无法重新加载，会话保持不变
+        std::fs::remove_file(&path).unwrap();
+        let stale = check_stale_sources_impl(&sessions, session.id.clone()).unwrap();
+        assert_eq!(stale[0].reason, StaleReason::Missing);
+        let reloaded = reload_sprites_impl(&sessions, session.id.clone(), Some(vec!["hero".to_string()])).unwrap();
+        assert!(reloaded.reloaded.is_empty());
+        assert_eq!(reloaded.failed.len(), 1);
+        assert_eq!(reloaded.session.sprites[0].width, 12);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_estimate_anchors_writes_pivots() {
+        let dir = std::env::temp_dir().join(format!("ezplist_anchor_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        // 10x10 的角色帧，内容 (2,4)-(6,10) 站在底边上
+        let mut hero = RgbaImage::new(10, 10);
+        for y in 4..10 {
+            for x in 2..6 {
+                hero.put_pixel(x, y, Rgba([255, 0, 0, 255]));
+            }
+        }
+        let path = dir.join("hero.png");
+        hero.save(&path).unwrap();
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let sessions = SessionStore::default();
+        let session = rt.block_on(create_session_impl(&sessions)).unwrap();
+        let sprites = vec![SpriteData {
+            id: "hero".to_string(),
+            name: "hero.png".to_string(),
+            path: path.to_string_lossy().to_string(),
+            width: 10,
+            height: 10,
+            trimmed_width: 10,
+            trimmed_height: 10,
+            anchor: None,
+            nine_slice: None,
+        }];
+        rt.block_on(add_session_images_impl(&sessions, session.id.clone(), sprites)).unwrap();
+
+        let ids = vec!["hero".to_string()];
+        let result = estimate_anchors_impl(&sessions, session.id.clone(), ids.clone(), None, false).unwrap();
+        assert_eq!(result.strategy, AnchorStrategy::BottomCenter);
+        assert_eq!(result.anchors[0].pivot, Some((0.4, 0.0)));
+        assert_eq!(rt.block_on(get_session_impl(&sessions, session.id.clone())).unwrap().sprites[0].pivot, None);
+
+        estimate_anchors_impl(&sessions, session.id.clone(), ids, Some(AnchorStrategy::Centroid), true).unwrap();
+        let updated = rt.block_on(get_session_impl(&sessions, session.id.clone())).unwrap();
+        assert_eq!(updated.sprites[0].pivot, Some((0.4, 0.3)));
+
+        rt.block_on(close_session_impl(&sessions, session.id)).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
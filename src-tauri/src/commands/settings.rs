@@ -0,0 +1,109 @@
+/// 设置命令 (Settings Commands)
+///
+/// 运行时可调整的全局设置
+
+use crate::api::http::{self, HttpApiInfo, DEFAULT_PORT};
+use crate::commands::pack::PackSessionStore;
+use crate::core::summary::NumberLocale;
+use crate::utils::guard::catch_panic;
+use crate::utils::{number_locale, workers};
+use tauri::State;
+
+/// 设置工作线程数命令
+///
+/// # Arguments
+/// * `threads` - 线程数（可选，不设置或为 0 时使用全部 CPU 核心）
+///
+/// # Returns
+/// * `Result<usize, String>` - 实际的线程数
+#[tauri::command]
+pub async fn set_worker_threads(threads: Option<usize>) -> Result<usize, String> {
+    catch_panic("set_worker_threads", set_worker_threads_impl(threads)).await
+}
+
+async fn set_worker_threads_impl(threads: Option<usize>) -> Result<usize, String> {
+    workers::set_worker_threads(threads.unwrap_or(0))
+}
+
+/// 获取工作线程数命令
+///
+/// # Returns
+/// * `Result<usize, String>` - 当前的线程数
+#[tauri::command]
+pub async fn get_worker_threads() -> Result<usize, String> {
+    catch_panic("get_worker_threads", get_worker_threads_impl()).await
+}
+
+async fn get_worker_threads_impl() -> Result<usize, String> {
+    Ok(workers::worker_threads())
+}
+
+/// 设置数字格式地区命令
+///
+/// # Arguments
+/// * `locale` - 地区（如 `zh-CN`、`en-US`），决定结果摘要中的数字与单位格式
+#[tauri::command]
+pub async fn set_number_locale(locale: NumberLocale) -> Result<(), String> {
+    catch_panic("set_number_locale", set_number_locale_impl(locale)).await
+}
+
+async fn set_number_locale_impl(locale: NumberLocale) -> Result<(), String> {
+    number_locale::set_number_locale(locale);
+    Ok(())
+}
+
+/// 获取数字格式地区命令
+///
+/// # Returns
+/// * `Result<NumberLocale, String>` - 当前地区
+#[tauri::command]
+pub async fn get_number_locale() -> Result<NumberLocale, String> {
+    catch_panic("get_number_locale", get_number_locale_impl()).await
+}
+
+async fn get_number_locale_impl() -> Result<NumberLocale, String> {
+    Ok(number_locale::number_locale())
+}
+
+/// 启动本地 HTTP 接口命令
+///
+/// # Arguments
+/// * `port` - 端口（可选，默认 `DEFAULT_PORT`；0 表示由系统分配）
+///
+/// # Returns
+/// * `Result<HttpApiInfo, String>` - 实际端口与访问令牌
+#[tauri::command]
+pub async fn start_http_api(store: State<'_, PackSessionStore>, port: Option<u16>) -> Result<HttpApiInfo, String> {
+    catch_panic("start_http_api", start_http_api_impl(store.inner().clone(), port)).await
+}
+
+async fn start_http_api_impl(store: PackSessionStore, port: Option<u16>) -> Result<HttpApiInfo, String> {
+    // 与界面共用打包结果存储，界面中的打包结果也可以通过接口导出
+    http::start(port.unwrap_or(DEFAULT_PORT), store)
+}
+
+/// 停止本地 HTTP 接口命令
+///
+/// # Returns
+/// * `Result<bool, String>` - 接口之前是否在运行
+#[tauri::command]
+pub async fn stop_http_api() -> Result<bool, String> {
+    catch_panic("stop_http_api", stop_http_api_impl()).await
+}
+
+async fn stop_http_api_impl() -> Result<bool, String> {
+    Ok(http::stop())
+}
+
+/// 获取本地 HTTP 接口状态命令
+///
+/// # Returns
+/// * `Result<Option<HttpApiInfo>, String>` - 运行中时返回端口与令牌
+#[tauri::command]
+pub async fn get_http_api_info() -> Result<Option<HttpApiInfo>, String> {
+    catch_panic("get_http_api_info", get_http_api_info_impl()).await
+}
+
+async fn get_http_api_info_impl() -> Result<Option<HttpApiInfo>, String> {
+    Ok(http::info())
+}
@@ -2,15 +2,40 @@
 /// 
 /// 将单张精灵图集按网格切分，生成帧信息和 Plist
 
-use crate::core::types::{SpritesheetInfo, FrameInfo, SplitConfig, SplitResult};
-use image::{ImageReader, GenericImageView, Pixel};
+use crate::commands::import::{animation_name, animation_temp_dir};
+use crate::core::animated_image::{compose_frame_sheet, ImportedAnimation};
+use crate::core::blobs::{detect_blobs, BlobOptions};
+use crate::core::exporters::{SheetData, SheetFormat};
+use crate::core::frame_check::check_frame_sizes;
+use crate::core::grid::{detect_gutter, grid_edges, uniform_edges, GridGutter};
+use crate::core::limits::check_texture_size;
+use crate::core::naming::FrameNumbering;
+use crate::core::plist_generator::{generate_plist, PlistFormat, PlistFrame};
+use crate::core::png_meta::PngMetadata;
+use crate::core::split_overlay::{render_split_overlay as render_overlay, SplitOverlayOptions};
+use crate::core::strip::{compose_strip, strip_file_name, validate_strip_name};
+use crate::core::texture_format::PixelFormat;
+use crate::core::tiling::{page_file_stem, plan_tiles, DEFAULT_MAX_PAGE_SIZE};
+use crate::core::timings::{Phase, Timings};
+use crate::core::types::{SpritesheetInfo, FrameInfo, PackedSprite, SplitConfig, SplitResult, StripExportResult};
+use crate::utils::guard::{catch_panic, run_blocking};
+use crate::utils::hash::{SmartUpdate, SmartUpdateMode};
+use crate::utils::image_loader::{load_animation, load_image, AnimatedFrame, ImageLoadLimits};
+use crate::utils::job_lock::{self, try_lock};
+use crate::utils::png_writer::save_png;
+use crate::utils::trim::is_region_transparent;
+use super::animation::fresh_temp_dir;
+use image::{GenericImageView, Pixel};
 use std::path::Path;
 
+/// 切分预览叠加图临时目录名称
+const SPLIT_OVERLAY_DIR_NAME: &str = "ezplist_split_overlay";
+
 /// 常见的像素帧尺寸（按优先级排序）
 const COMMON_FRAME_SIZES: [u32; 10] = [128, 64, 96, 48, 32, 256, 16, 192, 512, 24];
 
 /// 自动检测结果
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, Default, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AutoDetectResult {
     /// 推荐帧宽
@@ -23,6 +48,18 @@ pub struct AutoDetectResult {
     pub cols: u32,
     /// 检测置信度 (0-100)
     pub confidence: u32,
+    /// 推荐左右外边距
+    pub margin_x: u32,
+    /// 推荐上下外边距
+    pub margin_y: u32,
+    /// 推荐列间距
+    pub spacing_x: u32,
+    /// 推荐行间距
+    pub spacing_y: u32,
+    /// 推荐水平偏移
+    pub offset_x: u32,
+    /// 推荐垂直偏移
+    pub offset_y: u32,
 }
 
 /// 扩展的图集信息（包含自动检测结果）
@@ -34,13 +71,16 @@ pub struct SpritesheetInfoEx {
     pub info: SpritesheetInfo,
     /// 自动检测结果
     pub auto_detect: Option<AutoDetectResult>,
+    /// 动图的帧时长（导入 GIF、APNG 或动画 WebP 时，帧按行排入网格图集）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub animation: Option<ImportedAnimation>,
 }
 
 /// 基于透明度检测帧边界
 /// 
-/// 扫描图像的每一列/行，检测全透明的列/行作为帧边界
-/// 返回 (帧宽, 帧高, 行数, 列数)
-fn detect_frames_by_transparency(img: &image::DynamicImage) -> Option<(u32, u32, u32, u32)> {
+/// 扫描图像的每一列/行，检测全透明的列/行作为帧边界，
+/// 并识别帧之间透明的外边距与间距
+fn detect_frames_by_transparency(img: &image::DynamicImage) -> Option<AutoDetectResult> {
     let (width, height) = img.dimensions();
     
     // 检测透明度阈值（alpha < 10 视为透明）
@@ -77,34 +117,34 @@ fn detect_frames_by_transparency(img: &image::DynamicImage) -> Option<(u32, u32,
     let rows = count_content_regions(&row_transparent);
     
     // 需要至少 2 个帧或有明显的透明分隔才有意义
-    if cols >= 2 || rows >= 2 {
-        // 计算帧尺寸：图像尺寸 / 帧数（取平均）
-        let frame_width = width / cols;
-        let frame_height = height / rows;
-        
-        // 合理性检查
-        if frame_width >= 16 && frame_height >= 16 {
-            return Some((frame_width, frame_height, rows, cols));
-        }
+    if cols < 2 && rows < 2 {
+        return None;
     }
     
-    // 如果只有 1 行，检查是否有明显的列分隔
-    if rows == 1 && cols >= 2 {
-        let frame_width = width / cols;
-        if frame_width >= 16 {
-            return Some((frame_width, height, 1, cols));
-        }
-    }
+    // 按透明的外边距与帧间距推断帧尺寸，找不到时按图像尺寸 / 帧数（取平均）
+    let (frame_width, gutter_x) = detect_gutter(&col_transparent, cols).unwrap_or((width / cols, GridGutter::default()));
+    let (frame_height, gutter_y) = detect_gutter(&row_transparent, rows).unwrap_or((height / rows, GridGutter::default()));
     
-    // 如果只有 1 列，检查是否有明显的行分隔
-    if cols == 1 && rows >= 2 {
-        let frame_height = height / rows;
-        if frame_height >= 16 {
-            return Some((width, frame_height, rows, 1));
-        }
+    // 合理性检查（单行/单列时只检查分隔方向）
+    let wide_enough = frame_width >= 16 || cols == 1;
+    let tall_enough = frame_height >= 16 || rows == 1;
+    if !wide_enough || !tall_enough {
+        return None;
     }
     
-    None
+    Some(AutoDetectResult {
+        frame_width,
+        frame_height,
+        rows,
+        cols,
+        confidence: 95, // 最高置信度：基于实际图像内容
+        margin_x: gutter_x.margin,
+        margin_y: gutter_y.margin,
+        spacing_x: gutter_x.spacing,
+        spacing_y: gutter_y.spacing,
+        offset_x: gutter_x.offset,
+        offset_y: gutter_y.offset,
+    })
 }
 
 /// 统计连续的非透明（内容）区域数量
@@ -137,6 +177,10 @@ fn count_content_regions(transparent: &[bool]) -> u32 {
 /// 3. 常见尺寸整除检测
 /// 4. GCD 推断
 fn auto_detect_frame_size(width: u32, height: u32) -> Option<AutoDetectResult> {
+    if width == 0 || height == 0 {
+        return None;
+    }
+    
     // 注意：基于透明度的检测在 auto_detect_frame_size_with_image 中实现
     // 这里只做数学推断作为后备
     
@@ -152,6 +196,7 @@ fn auto_detect_frame_size(width: u32, height: u32) -> Option<AutoDetectResult> {
                 rows: 1,
                 cols,
                 confidence: 75,
+                ..Default::default()
             });
         }
     }
@@ -168,6 +213,7 @@ fn auto_detect_frame_size(width: u32, height: u32) -> Option<AutoDetectResult> {
                     rows,
                     cols,
                     confidence: 70,
+                    ..Default::default()
                 });
             }
         }
@@ -189,6 +235,7 @@ fn auto_detect_frame_size(width: u32, height: u32) -> Option<AutoDetectResult> {
                         rows: 1,
                         cols,
                         confidence: 65,
+                        ..Default::default()
                     });
                 }
             }
@@ -207,6 +254,7 @@ fn auto_detect_frame_size(width: u32, height: u32) -> Option<AutoDetectResult> {
                 rows,
                 cols,
                 confidence: 50,
+                ..Default::default()
             });
         }
     }
@@ -235,15 +283,13 @@ fn find_factors(n: u32) -> Vec<u32> {
 /// 带图像的自动检测（使用透明度检测）
 fn auto_detect_with_image(img: &image::DynamicImage, width: u32, height: u32) -> Option<AutoDetectResult> {
     // 首先尝试透明度检测
-    if let Some((fw, fh, rows, cols)) = detect_frames_by_transparency(img) {
-        println!("透明度检测成功: {}x{}, {}行{}列", fw, fh, rows, cols);
-        return Some(AutoDetectResult {
-            frame_width: fw,
-            frame_height: fh,
-            rows,
-            cols,
-            confidence: 95, // 最高置信度：基于实际图像内容
-        });
+    if let Some(detect) = detect_frames_by_transparency(img) {
+        println!(
+            "透明度检测成功: {}x{}, {}行{}列, 边距 {}x{}, 间距 {}x{}",
+            detect.frame_width, detect.frame_height, detect.rows, detect.cols,
+            detect.margin_x, detect.margin_y, detect.spacing_x, detect.spacing_y
+        );
+        return Some(detect);
     }
     
     // 透明度检测失败，使用数学推断
@@ -257,6 +303,9 @@ fn gcd(a: u32, b: u32) -> u32 {
 
 /// 导入精灵图集（带自动检测）
 /// 
+/// 动图的所有帧按行排入接近正方形的网格，保存到临时目录作为图集（`path` 指向该 PNG），
+/// 自动检测结果即为该网格，各帧时长见 `animation`
+/// 
 /// # Arguments
 /// * `path` - 图集文件路径
 /// 
@@ -264,6 +313,10 @@ fn gcd(a: u32, b: u32) -> u32 {
 /// * `Result<SpritesheetInfoEx, String>` - 图集信息（含自动检测结果）或错误
 #[tauri::command]
 pub async fn import_spritesheet(path: String) -> Result<SpritesheetInfoEx, String> {
+    run_blocking("import_spritesheet", move || import_spritesheet_impl(path)).await
+}
+
+fn import_spritesheet_impl(path: String) -> Result<SpritesheetInfoEx, String> {
     println!("导入精灵图集: {}", path);
     
     // 检查文件是否存在
@@ -271,11 +324,12 @@ pub async fn import_spritesheet(path: String) -> Result<SpritesheetInfoEx, Strin
         return Err(format!("文件不存在: {}", path));
     }
     
+    if let Some(frames) = load_animation(&path, &ImageLoadLimits::default())? {
+        return import_animation_sheet(&path, &frames);
+    }
+
     // 加载图像获取尺寸
-    let img = ImageReader::open(&path)
-        .map_err(|e| format!("无法打开图像: {}", e))?
-        .decode()
-        .map_err(|e| format!("无法解码图像: {}", e))?;
+    let img = load_image(&path, &ImageLoadLimits::default())?;
     
     let (width, height) = img.dimensions();
     
@@ -309,6 +363,48 @@ pub async fn import_spritesheet(path: String) -> Result<SpritesheetInfoEx, Strin
             height,
         },
         auto_detect,
+        animation: None,
+    })
+}
+
+/// 把动图的帧排成网格图集导入
+fn import_animation_sheet(path: &str, frames: &[AnimatedFrame]) -> Result<SpritesheetInfoEx, String> {
+    let images: Vec<image::RgbaImage> = frames.iter().map(|frame| frame.image.clone()).collect();
+    let (sheet, cols, rows) = compose_frame_sheet(&images).ok_or_else(|| format!("动图没有帧: {}", path))?;
+    check_texture_size(sheet.width(), sheet.height())?;
+    let name = format!("{}.png", animation_name(path));
+    let dir = animation_temp_dir()?;
+    let sheet_path = dir.join(&name);
+    save_png(&sheet, &sheet_path, &PngMetadata::default())?;
+
+    let (frame_width, frame_height) = (sheet.width() / cols, sheet.height() / rows);
+    let delays: Vec<u32> = frames.iter().map(|frame| frame.delay_ms).collect();
+    let animation = ImportedAnimation::new(&animation_name(path), path, &delays);
+    println!(
+        "动图加载成功: {} 帧 {}x{}, 排成 {}行{}列, 共 {} ms",
+        frames.len(), frame_width, frame_height, rows, cols, animation.duration_ms
+    );
+    Ok(SpritesheetInfoEx {
+        info: SpritesheetInfo {
+            path: sheet_path.to_string_lossy().to_string(),
+            name,
+            width: sheet.width(),
+            height: sheet.height(),
+        },
+        auto_detect: Some(AutoDetectResult {
+            frame_width,
+            frame_height,
+            rows,
+            cols,
+            confidence: 100,
+            margin_x: 0,
+            margin_y: 0,
+            spacing_x: 0,
+            spacing_y: 0,
+            offset_x: 0,
+            offset_y: 0,
+        }),
+        animation: Some(animation),
     })
 }
 
@@ -324,77 +420,273 @@ pub async fn import_spritesheet(path: String) -> Result<SpritesheetInfoEx, Strin
 pub async fn calculate_split_frames(
     spritesheet: SpritesheetInfo,
     config: SplitConfig,
+) -> Result<SplitResult, String> {
+    run_blocking("calculate_split_frames", move || calculate_split_frames_impl(spritesheet, config)).await
+}
+
+fn calculate_split_frames_impl(
+    spritesheet: SpritesheetInfo,
+    config: SplitConfig,
 ) -> Result<SplitResult, String> {
     if config.rows == 0 || config.cols == 0 {
         return Err("行数和列数必须大于0".to_string());
     }
     
+    // 扣除外边距、偏移与帧间距后剩下的才是帧
+    let gutter_x = config.gutter_x();
+    let gutter_y = config.gutter_y();
+    let content_width = gutter_x.content_size(spritesheet.width, config.cols);
+    let content_height = gutter_y.content_size(spritesheet.height, config.rows);
+    
     // 计算每帧尺寸
-    let frame_width = config.frame_width.unwrap_or(spritesheet.width / config.cols);
-    let frame_height = config.frame_height.unwrap_or(spritesheet.height / config.rows);
+    let frame_width = config.frame_width.unwrap_or(content_width / config.cols);
+    let frame_height = config.frame_height.unwrap_or(content_height / config.rows);
     
     if frame_width == 0 || frame_height == 0 {
         return Err("帧尺寸计算结果为0，请检查配置".to_string());
     }
     
-    let start_index = config.start_index.unwrap_or(1);
+    // 计算网格线坐标：固定帧尺寸时均匀排列（超出图集像素数的行列不可能有帧），
+    // 否则按取整策略处理余数；再按外边距与帧间距得到每帧的起点
+    let rounding = config.rounding.unwrap_or_default();
+    let col_edges = match config.frame_width {
+        Some(width) => uniform_edges(width, config.cols.min(spritesheet.width)),
+        None => grid_edges(content_width, config.cols, rounding)
+            .map_err(|e| format!("图集宽度 {}", e))?,
+    };
+    let row_edges = match config.frame_height {
+        Some(height) => uniform_edges(height, config.rows.min(spritesheet.height)),
+        None => grid_edges(content_height, config.rows, rounding)
+            .map_err(|e| format!("图集高度 {}", e))?,
+    };
+    let row_cells = gutter_y.cells(&row_edges);
+    let col_cells = gutter_x.cells(&col_edges);
+    
+    // 跳过空白格子时需要读取图集像素
+    let sheet_image = if config.skip_empty.unwrap_or(false) {
+        Some(load_image(&spritesheet.path, &ImageLoadLimits::default())?.to_rgba8())
+    } else {
+        None
+    };
+    let mut skipped_empty = 0;
+    
+    let mut numbering = config.numbering.unwrap_or_default();
+    if let Some(start) = config.start_index {
+        numbering.start = start;
+    }
+    numbering.validate()?;
     let mut frames = Vec::new();
-    let mut index = start_index;
+    let mut position = 0;
     
-    for row in 0..config.rows {
-        for col in 0..config.cols {
-            let x = col * frame_width;
-            let y = row * frame_height;
+    for (row, &(y, height)) in (0..config.rows).zip(&row_cells) {
+        if y >= spritesheet.height {
+            break;
+        }
+        
+        for (col, &(x, width)) in (0..config.cols).zip(&col_cells) {
+            if x >= spritesheet.width {
+                break;
+            }
             
             // 检查是否超出图集边界
-            if x + frame_width > spritesheet.width || y + frame_height > spritesheet.height {
+            if !fits_within(x, width, spritesheet.width)
+                || !fits_within(y, height, spritesheet.height)
+            {
+                continue;
+            }
+            
+            // 完全透明的格子（如最后一行末尾的空格）不生成帧，也不占用序号
+            if sheet_image.as_ref().is_some_and(|sheet| is_region_transparent(sheet, x, y, width, height)) {
+                skipped_empty += 1;
                 continue;
             }
             
-            let name = format!("{}_{:02}.png", config.name_prefix, index);
+            let name = numbering.frame_name(&config.name_prefix, position)?;
             
             frames.push(FrameInfo {
                 name,
                 x,
                 y,
-                width: frame_width,
-                height: frame_height,
+                width,
+                height,
                 row,
                 col,
+                anchor: config.anchor,
             });
             
-            index += 1;
+            position += 1;
         }
     }
     
     let total_frames = frames.len() as u32;
+    let warnings = config
+        .constraints
+        .as_ref()
+        .map(|constraints| check_frame_sizes(&frames, constraints))
+        .unwrap_or_default();
     
     println!("切分计算完成: {}帧 ({}x{})", total_frames, frame_width, frame_height);
+    if skipped_empty > 0 {
+        println!("  跳过 {} 个完全透明的格子", skipped_empty);
+    }
+    for warning in &warnings {
+        println!("警告: {}", warning.message);
+    }
     
     Ok(SplitResult {
         frames,
         frame_width,
         frame_height,
         total_frames,
+        skipped_empty,
+        warnings,
     })
 }
 
-/// 导出配置
+/// 连通区域切分配置
 #[derive(Debug, Clone, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct BlobSplitConfig {
+    /// 帧名称前缀
+    pub name_prefix: String,
+    /// 帧序号规则（可选，默认从 1 开始、补零到 2 位）
+    pub numbering: Option<FrameNumbering>,
+    /// 检测选项（可选）
+    pub options: Option<BlobOptions>,
+    /// 所有帧的锚点（可选，相对帧尺寸的 0~1 坐标，Y 轴向上）
+    pub anchor: Option<(f32, f32)>,
+}
+
+/// 按不透明连通区域切分图集
+///
+/// 适用于紧密排布、没有网格也没有帧数据的图集：每个不透明区域（合并相距很近的碎片后）
+/// 成为一帧，帧按阅读顺序编号，帧尺寸各不相同
+///
+/// # Arguments
+/// * `spritesheet` - 图集信息
+/// * `config` - 连通区域切分配置
+///
+/// # Returns
+/// * `Result<SplitResult, String>` - 切分结果（`frame_width`/`frame_height` 为最大帧尺寸）
+#[tauri::command]
+pub async fn detect_blob_frames(
+    spritesheet: SpritesheetInfo,
+    config: BlobSplitConfig,
+) -> Result<SplitResult, String> {
+    run_blocking("detect_blob_frames", move || detect_blob_frames_impl(spritesheet, config)).await
+}
+
+fn detect_blob_frames_impl(
+    spritesheet: SpritesheetInfo,
+    config: BlobSplitConfig,
+) -> Result<SplitResult, String> {
+    let numbering = config.numbering.unwrap_or_default();
+    numbering.validate()?;
+    
+    let img = load_image(&spritesheet.path, &ImageLoadLimits::default())?.to_rgba8();
+    let blobs = detect_blobs(&img, &config.options.unwrap_or_default());
+    if blobs.is_empty() {
+        return Err("未检测到任何不透明区域".to_string());
+    }
+    
+    let frames = blobs
+        .iter()
+        .enumerate()
+        .map(|(position, blob)| {
+            Ok(FrameInfo {
+                name: numbering.frame_name(&config.name_prefix, position as u32)?,
+                x: blob.x,
+                y: blob.y,
+                width: blob.width,
+                height: blob.height,
+                row: blob.row,
+                col: blob.col,
+                anchor: config.anchor,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    
+    let frame_width = frames.iter().map(|f| f.width).max().unwrap_or(0);
+    let frame_height = frames.iter().map(|f| f.height).max().unwrap_or(0);
+    let rows = blobs.last().map_or(0, |blob| blob.row + 1);
+    println!("连通区域切分完成: {}帧, {}行, 最大帧 {}x{}", frames.len(), rows, frame_width, frame_height);
+    
+    Ok(SplitResult {
+        total_frames: frames.len() as u32,
+        frames,
+        frame_width,
+        frame_height,
+        skipped_empty: 0,
+        warnings: Vec::new(),
+    })
+}
+
+/// 生成切分预览叠加图
+///
+/// 在缩小后的图集上绘制帧矩形与序号，保存为临时 PNG，前端通过 `getAssetUrl` 加载。
+/// 每次生成会清理之前的叠加图
+///
+/// # Arguments
+/// * `spritesheet` - 图集信息
+/// * `frames` - 切分得到的帧
+/// * `options` - 叠加图选项（可选，默认最大边长 1024、洋红色边框、显示序号）
+///
+/// # Returns
+/// * `Result<String, String>` - 叠加图临时文件路径或错误信息
+#[tauri::command]
+pub async fn render_split_overlay(
+    spritesheet: SpritesheetInfo,
+    frames: Vec<FrameInfo>,
+    options: Option<SplitOverlayOptions>,
+) -> Result<String, String> {
+    run_blocking("render_split_overlay", move || render_split_overlay_impl(spritesheet, frames, options)).await
+}
+
+fn render_split_overlay_impl(
+    spritesheet: SpritesheetInfo,
+    frames: Vec<FrameInfo>,
+    options: Option<SplitOverlayOptions>,
+) -> Result<String, String> {
+    let source_img = load_image(&spritesheet.path, &ImageLoadLimits::default())?.to_rgba8();
+    let overlay = render_overlay(&source_img, &frames, &options.unwrap_or_default())?;
+
+    let path = fresh_temp_dir(SPLIT_OVERLAY_DIR_NAME)?.join("split_overlay.png");
+    overlay
+        .save(&path)
+        .map_err(|e| format!("保存切分预览失败: {}", e))?;
+
+    println!("✓ 切分预览生成完成: {} 帧, {}x{}", frames.len(), overlay.width(), overlay.height());
+
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// 导出配置
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ExportSplitConfig {
     /// 是否重命名 PNG 文件（使其与 Plist 同名）
     pub rename_png: bool,
+    /// 数据文件格式（可选，默认 plist）
+    #[serde(default)]
+    pub format: SheetFormat,
+    /// plist 键名版本与编码（可选，默认 Format 3 XML）
+    #[serde(default)]
+    pub plist_format: PlistFormat,
+    /// plist 中 smartupdate 哈希的计算方式（可选，默认按纹理文件名、帧布局与像素计算）
+    #[serde(default)]
+    pub smartupdate: SmartUpdateMode,
 }
 
 /// 导出结果
 #[derive(Debug, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ExportSplitResult {
-    /// Plist 文件路径
+    /// 数据文件路径（Plist 或 JSON）
     pub plist_path: String,
     /// PNG 文件路径（如果重命名了）
     pub png_path: Option<String>,
+    /// 各阶段耗时
+    pub timings: Timings,
 }
 
 /// 导出切分后的 Plist 文件（自动保存到 PNG 同目录）
@@ -414,60 +706,26 @@ pub async fn export_split_plist(
     output_name: String,
     config: Option<ExportSplitConfig>,
 ) -> Result<ExportSplitResult, String> {
-    let config = config.unwrap_or(ExportSplitConfig { rename_png: false });
-    use std::collections::HashMap;
+    run_blocking("export_split_plist", move || export_split_plist_impl(spritesheet, frames, output_name, config)).await
+}
+
+fn export_split_plist_impl(
+    spritesheet: SpritesheetInfo,
+    frames: Vec<FrameInfo>,
+    output_name: String,
+    config: Option<ExportSplitConfig>,
+) -> Result<ExportSplitResult, String> {
+    let config = config.unwrap_or_default();
     use std::fs;
     
     if frames.is_empty() {
         return Err("没有帧可导出".to_string());
     }
     
-    // 构建 Plist 数据
-    let mut frames_dict: HashMap<String, plist::Value> = HashMap::new();
-    
-    for frame in &frames {
-        let mut frame_data: HashMap<String, plist::Value> = HashMap::new();
-        
-        // Cocos2d-x Format 3 格式
-        // spriteOffset: 裁剪偏移（这里没有裁剪，所以是 {0,0}）
-        frame_data.insert(
-            "spriteOffset".to_string(),
-            plist::Value::String("{0,0}".to_string()),
-        );
-        
-        // spriteSize: 帧尺寸
-        frame_data.insert(
-            "spriteSize".to_string(),
-            plist::Value::String(format!("{{{},{}}}", frame.width, frame.height)),
-        );
-        
-        // spriteSourceSize: 原始尺寸（同 spriteSize）
-        frame_data.insert(
-            "spriteSourceSize".to_string(),
-            plist::Value::String(format!("{{{},{}}}", frame.width, frame.height)),
-        );
-        
-        // textureRect: {{x,y},{width,height}}
-        frame_data.insert(
-            "textureRect".to_string(),
-            plist::Value::String(format!(
-                "{{{{{},{}}},{{{},{}}}}}",
-                frame.x, frame.y, frame.width, frame.height
-            )),
-        );
-        
-        // textureRotated: 是否旋转
-        frame_data.insert(
-            "textureRotated".to_string(),
-            plist::Value::Boolean(false),
-        );
-        
-        frames_dict.insert(frame.name.clone(), plist::Value::Dictionary(frame_data.into_iter().collect()));
-    }
-    
     // 获取 PNG 文件所在目录
     let png_path = Path::new(&spritesheet.path);
     let png_dir = png_path.parent().unwrap_or(Path::new("."));
+    let _guard = try_lock("export_split_plist", [job_lock::output_dir(png_dir)])?;
     let png_ext = png_path.extension().and_then(|e| e.to_str()).unwrap_or("png");
     
     // 决定最终的纹理文件名
@@ -477,45 +735,32 @@ pub async fn export_split_plist(
         spritesheet.name.clone()
     };
     
-    // 构建 metadata
-    let mut metadata: HashMap<String, plist::Value> = HashMap::new();
-    metadata.insert("format".to_string(), plist::Value::Integer(3.into()));
-    metadata.insert(
-        "realTextureFileName".to_string(),
-        plist::Value::String(final_texture_name.clone()),
-    );
-    metadata.insert(
-        "size".to_string(),
-        plist::Value::String(format!("{{{},{}}}", spritesheet.width, spritesheet.height)),
-    );
-    metadata.insert(
-        "textureFileName".to_string(),
-        plist::Value::String(final_texture_name.clone()),
-    );
-    
-    // 计算简单的 smartupdate hash
-    use md5::{Md5, Digest};
-    let mut hasher = Md5::new();
-    hasher.update(format!("{}_{}", final_texture_name, frames.len()).as_bytes());
-    let hash = format!("{:x}", hasher.finalize());
-    metadata.insert("smartupdate".to_string(), plist::Value::String(hash));
-    
-    // 构建根字典
-    let mut root: HashMap<String, plist::Value> = HashMap::new();
-    root.insert("frames".to_string(), plist::Value::Dictionary(frames_dict.into_iter().collect()));
-    root.insert("metadata".to_string(), plist::Value::Dictionary(metadata.into_iter().collect()));
-    
-    let plist_value = plist::Value::Dictionary(root.into_iter().collect());
-    
-    // 保存 Plist 到 PNG 同目录
-    let plist_path = png_dir.join(format!("{}.plist", output_name));
-    let mut file = fs::File::create(&plist_path)
-        .map_err(|e| format!("无法创建文件: {}", e))?;
-    
-    plist::to_writer_xml(&mut file, &plist_value)
-        .map_err(|e| format!("写入 Plist 失败: {}", e))?;
+    let mut timings = Timings::default();
+    // 数据文件保存到 PNG 同目录
+    let exporter = config.format.exporter(config.plist_format);
+    let sprites: Vec<PackedSprite> = frames.iter().map(PackedSprite::from_frame).collect();
+    // smartupdate 按纹理像素计算，只有 plist 需要解码纹理
+    let texture = match config.format {
+        SheetFormat::Plist => Some(
+            timings.time(Phase::Decode, || load_image(&spritesheet.path, &ImageLoadLimits::default()))?.to_rgba8(),
+        ),
+        _ => None,
+    };
+    let data = timings.time(Phase::Render, || {
+        exporter.export(&SheetData {
+            sprites: &sprites,
+            texture_width: spritesheet.width,
+            texture_height: spritesheet.height,
+            texture_name: &final_texture_name,
+            pixel_format: PixelFormat::Rgba8888,
+            smartupdate: SmartUpdate { texture: texture.as_ref(), mode: config.smartupdate },
+        })
+    })?;
+    let plist_path = png_dir.join(format!("{}.{}", output_name, exporter.extension()));
+    timings.time(Phase::Write, || fs::write(&plist_path, data))
+        .map_err(|e| format!("写入数据文件失败: {}", e))?;
     
-    println!("Plist 导出成功: {}", plist_path.display());
+    println!("数据文件导出成功: {}", plist_path.display());
     
     // 如果需要重命名 PNG 文件
     let renamed_png_path = if config.rename_png {
@@ -523,7 +768,7 @@ pub async fn export_split_plist(
         
         // 只有当新旧路径不同时才重命名
         if new_png_path != png_path {
-            fs::copy(&spritesheet.path, &new_png_path)
+            timings.time(Phase::Write, || fs::copy(&spritesheet.path, &new_png_path))
                 .map_err(|e| format!("复制 PNG 文件失败: {}", e))?;
             println!("PNG 复制成功: {}", new_png_path.display());
             Some(new_png_path.to_string_lossy().to_string())
@@ -534,195 +779,345 @@ pub async fn export_split_plist(
         None
     };
     
+    println!("✓ 切分导出完成, {}", timings);
+    
     Ok(ExportSplitResult {
         plist_path: plist_path.to_string_lossy().to_string(),
         png_path: renamed_png_path,
+        timings,
     })
 }
 
+/// 分页导出配置
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TiledExportConfig {
+    /// 页宽上限（可选，默认 4096）
+    pub max_page_width: Option<u32>,
+    /// 页高上限（可选，默认 4096）
+    pub max_page_height: Option<u32>,
+}
+
+/// 分页导出的一页
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TiledPageOutput {
+    /// 页号（从 0 开始）
+    pub index: usize,
+    /// 该页 PNG 文件路径
+    pub png_path: String,
+    /// 该页 Plist 文件路径
+    pub plist_path: String,
+    /// 页宽度
+    pub width: u32,
+    /// 页高度
+    pub height: u32,
+    /// 该页的帧数
+    pub frame_count: usize,
+}
+
+/// 分页导出结果
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TiledExportResult {
+    /// 各页输出
+    pub pages: Vec<TiledPageOutput>,
+}
+
+/// 把超出纹理尺寸上限的图集按帧边界切成多页导出（自动保存到 PNG 同目录）
+///
+/// 每页输出 `{output_name}_{页号}.png` 与同名 Plist，Plist 中的帧坐标相对于所在页
+///
+/// # Arguments
+/// * `spritesheet` - 图集信息
+/// * `frames` - 帧信息列表
+/// * `output_name` - 输出文件名（不含扩展名与页号）
+/// * `config` - 分页配置（可选）
+///
+/// # Returns
+/// * `Result<TiledExportResult, String>` - 各页输出或错误
+#[tauri::command]
+pub async fn export_tiled_sheet(
+    spritesheet: SpritesheetInfo,
+    frames: Vec<FrameInfo>,
+    output_name: String,
+    config: Option<TiledExportConfig>,
+) -> Result<TiledExportResult, String> {
+    run_blocking("export_tiled_sheet", move || export_tiled_sheet_impl(spritesheet, frames, output_name, config)).await
+}
+
+fn export_tiled_sheet_impl(
+    spritesheet: SpritesheetInfo,
+    frames: Vec<FrameInfo>,
+    output_name: String,
+    config: Option<TiledExportConfig>,
+) -> Result<TiledExportResult, String> {
+    let config = config.unwrap_or_default();
+    let max_width = config.max_page_width.unwrap_or(DEFAULT_MAX_PAGE_SIZE);
+    let max_height = config.max_page_height.unwrap_or(DEFAULT_MAX_PAGE_SIZE);
+
+    if let Some(frame) = frames
+        .iter()
+        .find(|f| !fits_within(f.x, f.width, spritesheet.width) || !fits_within(f.y, f.height, spritesheet.height))
+    {
+        return Err(format!("帧 {} 超出图集范围", frame.name));
+    }
+    let pages = plan_tiles(&frames, max_width, max_height)?;
+
+    let source_img = load_image(&spritesheet.path, &ImageLoadLimits::default())?;
+    let png_dir = Path::new(&spritesheet.path).parent().unwrap_or(Path::new("."));
+    let _guard = try_lock("export_tiled_sheet", [job_lock::output_dir(png_dir)])?;
+
+    let mut outputs = Vec::with_capacity(pages.len());
+    for page in &pages {
+        let stem = page_file_stem(&output_name, page.index);
+        let texture_name = format!("{}.png", stem);
+        let png_path = png_dir.join(&texture_name);
+        let page_img = source_img.crop_imm(page.x, page.y, page.width, page.height).to_rgba8();
+        page_img
+            .save(&png_path)
+            .map_err(|e| format!("保存第 {} 页 PNG 失败: {}", page.index, e))?;
+
+        let plist_path = png_dir.join(format!("{}.plist", stem));
+        let frames: Vec<PlistFrame> = page
+            .frames
+            .iter()
+            .map(|frame| PlistFrame {
+                anchor: frame.anchor,
+                ..PlistFrame::untrimmed(frame.name.clone(), frame.x, frame.y, frame.width, frame.height)
+            })
+            .collect();
+        let smartupdate = SmartUpdate::new(&page_img, SmartUpdateMode::default());
+        let data = generate_plist(&frames, page.width, page.height, &texture_name, PlistFormat::XmlV3, PixelFormat::Rgba8888, smartupdate)?;
+        std::fs::write(&plist_path, data).map_err(|e| format!("写入 Plist 失败: {}", e))?;
+
+        println!("✓ 第 {} 页导出成功: {} ({}x{}, {} 帧)", page.index, png_path.display(), page.width, page.height, page.frames.len());
+        outputs.push(TiledPageOutput {
+            index: page.index,
+            png_path: png_path.to_string_lossy().to_string(),
+            plist_path: plist_path.to_string_lossy().to_string(),
+            width: page.width,
+            height: page.height,
+            frame_count: page.frames.len(),
+        });
+    }
+
+    println!("✓ 分页导出完成: {} 帧, {} 页", frames.len(), outputs.len());
+
+    Ok(TiledExportResult { pages: outputs })
+}
+
+/// 区域导出失败的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RegionFailureCode {
+    /// 区域配置无效（如序号规则错误）
+    InvalidRegion,
+    /// 区域没有有效帧
+    NoFrames,
+    /// 保存 PNG 失败
+    WritePng,
+    /// 保存 Plist 失败
+    WritePlist,
+}
+
+/// 单个区域的导出失败
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegionFailure {
+    /// 区域名称
+    pub region: String,
+    /// 错误代码
+    pub code: RegionFailureCode,
+    /// 错误信息
+    pub message: String,
+}
+
 /// 多区域批量导出结果
 #[derive(Debug, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MultiExportResult {
     /// 成功导出的 Plist 文件路径列表
     pub exported_files: Vec<String>,
-    /// 导出的 PNG 文件路径列表
+    /// 导出的 PNG 文件路径列表（与 `exported_files` 一一对应）
     pub exported_pngs: Vec<String>,
-    /// 失败的区域名称及错误信息
-    pub failed: Vec<(String, String)>,
+    /// 失败的区域（其已写入的文件会被删除）
+    pub failed: Vec<RegionFailure>,
     /// 总区域数
     pub total: usize,
+    /// 部分区域失败时的汇总警告
+    pub warning: Option<String>,
 }
 
 /// 批量导出多个动画区域的 Plist 和裁剪后的 PNG
+///
+/// 单个区域失败不影响其他区域，失败区域已写入的文件会被清理；
+/// 所有区域都失败时返回错误，部分失败时在结果中附带汇总警告
 /// 
 /// # Arguments
 /// * `spritesheet` - 图集信息
 /// * `regions` - 动画区域列表
 /// 
 /// # Returns
-/// * `Result<MultiExportResult, String>` - 批量导出结果
+/// * `Result<MultiExportResult, String>` - 批量导出结果；全部区域失败时返回错误
 #[tauri::command]
 pub async fn export_multi_plist(
     spritesheet: SpritesheetInfo,
     regions: Vec<crate::core::types::AnimationRegion>,
 ) -> Result<MultiExportResult, String> {
-    use std::collections::HashMap;
-    use std::fs;
-    use image::GenericImageView;
-    
+    run_blocking("export_multi_plist", move || export_multi_plist_impl(spritesheet, regions)).await
+}
+
+fn export_multi_plist_impl(
+    spritesheet: SpritesheetInfo,
+    regions: Vec<crate::core::types::AnimationRegion>,
+) -> Result<MultiExportResult, String> {
     if regions.is_empty() {
         return Err("没有区域可导出".to_string());
     }
     
     // 加载原图
-    let source_img = ImageReader::open(&spritesheet.path)
-        .map_err(|e| format!("无法打开图像: {}", e))?
-        .decode()
-        .map_err(|e| format!("无法解码图像: {}", e))?;
+    let source_img = load_image(&spritesheet.path, &ImageLoadLimits::default())?;
     
     // 获取 PNG 文件所在目录
     let png_path = Path::new(&spritesheet.path);
     let png_dir = png_path.parent().unwrap_or(Path::new("."));
+    let _guard = try_lock("export_multi_plist", [job_lock::output_dir(png_dir)])?;
     
     let mut exported_files = Vec::new();
     let mut exported_pngs = Vec::new();
-    let mut failed: Vec<(String, String)> = Vec::new();
+    let mut failed: Vec<RegionFailure> = Vec::new();
     let total = regions.len();
     
     for region in &regions {
-        // 计算该区域的帧信息
-        let frames = calculate_region_frames(&spritesheet, region);
-        
-        if frames.is_empty() {
-            failed.push((region.name.clone(), "区域没有有效帧".to_string()));
-            continue;
-        }
-        
-        // 计算区域边界（所有帧的最小外接矩形）
-        let min_x = frames.iter().map(|f| f.x).min().unwrap_or(0);
-        let min_y = frames.iter().map(|f| f.y).min().unwrap_or(0);
-        let max_x = frames.iter().map(|f| f.x + f.width).max().unwrap_or(0);
-        let max_y = frames.iter().map(|f| f.y + f.height).max().unwrap_or(0);
-        
-        let crop_width = max_x - min_x;
-        let crop_height = max_y - min_y;
-        
-        // 裁剪区域图像
-        let cropped_img = source_img.crop_imm(min_x, min_y, crop_width, crop_height);
-        
-        // 保存裁剪后的 PNG
-        let cropped_png_name = format!("{}.png", region.name);
-        let cropped_png_path = png_dir.join(&cropped_png_name);
-        
-        if let Err(e) = cropped_img.save(&cropped_png_path) {
-            failed.push((region.name.clone(), format!("保存 PNG 失败: {}", e)));
-            continue;
-        }
-        
-        println!("PNG 导出成功: {}", cropped_png_path.display());
-        exported_pngs.push(cropped_png_path.to_string_lossy().to_string());
-        
-        // 构建 Plist 数据（坐标相对于裁剪后的图像）
-        let mut frames_dict: HashMap<String, plist::Value> = HashMap::new();
-        
-        for frame in &frames {
-            let mut frame_data: HashMap<String, plist::Value> = HashMap::new();
-            
-            // 相对于裁剪后图像的坐标
-            let rel_x = frame.x - min_x;
-            let rel_y = frame.y - min_y;
-            
-            frame_data.insert(
-                "spriteOffset".to_string(),
-                plist::Value::String("{0,0}".to_string()),
-            );
-            
-            frame_data.insert(
-                "spriteSize".to_string(),
-                plist::Value::String(format!("{{{},{}}}", frame.width, frame.height)),
-            );
-            
-            frame_data.insert(
-                "spriteSourceSize".to_string(),
-                plist::Value::String(format!("{{{},{}}}", frame.width, frame.height)),
-            );
-            
-            frame_data.insert(
-                "textureRect".to_string(),
-                plist::Value::String(format!(
-                    "{{{{{},{}}},{{{},{}}}}}",
-                    rel_x, rel_y, frame.width, frame.height
-                )),
-            );
-            
-            frame_data.insert(
-                "textureRotated".to_string(),
-                plist::Value::Boolean(false),
-            );
-            
-            frames_dict.insert(frame.name.clone(), plist::Value::Dictionary(frame_data.into_iter().collect()));
-        }
-        
-        // 构建 metadata（指向裁剪后的 PNG）
-        let mut metadata: HashMap<String, plist::Value> = HashMap::new();
-        metadata.insert("format".to_string(), plist::Value::Integer(3.into()));
-        metadata.insert(
-            "realTextureFileName".to_string(),
-            plist::Value::String(cropped_png_name.clone()),
-        );
-        metadata.insert(
-            "size".to_string(),
-            plist::Value::String(format!("{{{},{}}}", crop_width, crop_height)),
-        );
-        metadata.insert(
-            "textureFileName".to_string(),
-            plist::Value::String(cropped_png_name),
-        );
-        
-        // 计算 smartupdate hash
-        use md5::{Md5, Digest};
-        let mut hasher = Md5::new();
-        hasher.update(format!("{}_{}", region.name, frames.len()).as_bytes());
-        let hash = format!("{:x}", hasher.finalize());
-        metadata.insert("smartupdate".to_string(), plist::Value::String(hash));
-        
-        // 构建根字典
-        let mut root: HashMap<String, plist::Value> = HashMap::new();
-        root.insert("frames".to_string(), plist::Value::Dictionary(frames_dict.into_iter().collect()));
-        root.insert("metadata".to_string(), plist::Value::Dictionary(metadata.into_iter().collect()));
-        
-        let plist_value = plist::Value::Dictionary(root.into_iter().collect());
-        
-        // 保存 Plist
-        let plist_path = png_dir.join(format!("{}.plist", region.name));
-        
-        match fs::File::create(&plist_path) {
-            Ok(mut file) => {
-                match plist::to_writer_xml(&mut file, &plist_value) {
-                    Ok(_) => {
-                        println!("Plist 导出成功: {}", plist_path.display());
-                        exported_files.push(plist_path.to_string_lossy().to_string());
-                    }
-                    Err(e) => {
-                        failed.push((region.name.clone(), format!("写入 Plist 失败: {}", e)));
-                    }
-                }
+        match export_region(&source_img, png_dir, &spritesheet, region) {
+            Ok((png, plist)) => {
+                exported_pngs.push(png);
+                exported_files.push(plist);
             }
-            Err(e) => {
-                failed.push((region.name.clone(), format!("创建 Plist 文件失败: {}", e)));
+            Err(failure) => {
+                println!("✗ 区域 {} 导出失败: {}", failure.region, failure.message);
+                failed.push(failure);
             }
         }
     }
     
+    let summary = || {
+        failed
+            .iter()
+            .map(|f| format!("{} ({})", f.region, f.message))
+            .collect::<Vec<_>>()
+            .join("; ")
+    };
+    if failed.len() == total {
+        return Err(format!("全部 {} 个区域导出失败: {}", total, summary()));
+    }
+    let warning = (!failed.is_empty()).then(|| format!("{}/{} 个区域导出失败: {}", failed.len(), total, summary()));
+    if let Some(warning) = &warning {
+        println!("警告: {}", warning);
+    }
+    println!("✓ 批量导出完成: {}/{} 个区域", exported_files.len(), total);
+    
     Ok(MultiExportResult {
         exported_files,
         exported_pngs,
         failed,
         total,
+        warning,
     })
 }
 
+/// 导出单个区域的 PNG 与 Plist，失败时删除已写入的文件
+///
+/// # Returns
+/// * `Result<(String, String), RegionFailure>` - (PNG 路径, Plist 路径) 或失败原因
+fn export_region(
+    source_img: &image::DynamicImage,
+    png_dir: &Path,
+    spritesheet: &SpritesheetInfo,
+    region: &crate::core::types::AnimationRegion,
+) -> Result<(String, String), RegionFailure> {
+    let fail = |code, message: String| RegionFailure {
+        region: region.name.clone(),
+        code,
+        message,
+    };
+    
+    // 计算该区域的帧信息
+    let frames = calculate_region_frames(spritesheet, region).map_err(|e| fail(RegionFailureCode::InvalidRegion, e))?;
+    if frames.is_empty() {
+        return Err(fail(RegionFailureCode::NoFrames, "区域没有有效帧".to_string()));
+    }
+    
+    // 计算区域边界（所有帧的最小外接矩形）
+    let min_x = frames.iter().map(|f| f.x).min().unwrap_or(0);
+    let min_y = frames.iter().map(|f| f.y).min().unwrap_or(0);
+    let max_x = frames.iter().map(|f| f.x + f.width).max().unwrap_or(0);
+    let max_y = frames.iter().map(|f| f.y + f.height).max().unwrap_or(0);
+    let crop_width = max_x - min_x;
+    let crop_height = max_y - min_y;
+    
+    // 裁剪并保存区域图像
+    let cropped_png_name = format!("{}.png", region.name);
+    let cropped_png_path = png_dir.join(&cropped_png_name);
+    let cropped_img = source_img.crop_imm(min_x, min_y, crop_width, crop_height).to_rgba8();
+    if let Err(e) = cropped_img.save(&cropped_png_path) {
+        remove_partial(&cropped_png_path);
+        return Err(fail(RegionFailureCode::WritePng, format!("保存 PNG 失败: {}", e)));
+    }
+    println!("PNG 导出成功: {}", cropped_png_path.display());
+    
+    // 保存 Plist（坐标相对于裁剪后的图像）
+    let plist_path = png_dir.join(format!("{}.plist", region.name));
+    let written = build_region_plist(&frames, (min_x, min_y), &cropped_img, &cropped_png_name)
+        .and_then(|data| std::fs::write(&plist_path, data).map_err(|e| format!("写入 Plist 失败: {}", e)));
+    if let Err(e) = written {
+        remove_partial(&plist_path);
+        remove_partial(&cropped_png_path);
+        return Err(fail(RegionFailureCode::WritePlist, e));
+    }
+    println!("Plist 导出成功: {}", plist_path.display());
+    
+    Ok((
+        cropped_png_path.to_string_lossy().to_string(),
+        plist_path.to_string_lossy().to_string(),
+    ))
+}
+
+/// 删除失败区域写了一部分的文件
+fn remove_partial(path: &Path) {
+    if path.exists() {
+        if let Err(e) = std::fs::remove_file(path) {
+            println!("警告: 无法删除未完成的文件 {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// 构建单个区域的 Plist（坐标相对于区域图像）
+///
+/// # Arguments
+/// * `frames` - 区域中的帧（图集坐标）
+/// * `origin` - 区域图像左上角在图集中的位置
+/// * `texture` - 区域图像
+/// * `png_name` - 区域图像文件名
+fn build_region_plist(
+    frames: &[FrameInfo],
+    origin: (u32, u32),
+    texture: &image::RgbaImage,
+    png_name: &str,
+) -> Result<Vec<u8>, String> {
+    let (min_x, min_y) = origin;
+    let frames: Vec<PlistFrame> = frames
+        .iter()
+        .map(|frame| PlistFrame {
+            anchor: frame.anchor,
+            ..PlistFrame::untrimmed(frame.name.clone(), frame.x - min_x, frame.y - min_y, frame.width, frame.height)
+        })
+        .collect();
+    let smartupdate = SmartUpdate::new(texture, SmartUpdateMode::default());
+    generate_plist(&frames, texture.width(), texture.height(), png_name, PlistFormat::XmlV3, PixelFormat::Rgba8888, smartupdate)
+}
+
 /// 计算单个区域的帧信息
 /// 
 /// # Arguments
@@ -730,15 +1125,24 @@ pub async fn export_multi_plist(
 /// * `region` - 动画区域定义
 /// 
 /// # Returns
-/// * `Vec<FrameInfo>` - 该区域的帧列表
+/// * `Result<Vec<FrameInfo>, String>` - 该区域的帧列表；序号规则无效时返回错误
 fn calculate_region_frames(
     spritesheet: &SpritesheetInfo,
     region: &crate::core::types::AnimationRegion,
-) -> Vec<FrameInfo> {
+) -> Result<Vec<FrameInfo>, String> {
     let mut frames = Vec::new();
+    let numbering = region.numbering.unwrap_or_default();
+    numbering.validate()?;
+    
+    if region.frame_width == 0 || region.frame_height == 0 {
+        return Ok(frames);
+    }
     
     // 计算图集的列数（用于换行计算）
     let cols_in_sheet = spritesheet.width / region.frame_width;
+    if cols_in_sheet == 0 {
+        return Ok(frames);
+    }
     
     for i in 0..region.frame_count {
         // 计算当前帧在区域中的行列位置
@@ -746,19 +1150,26 @@ fn calculate_region_frames(
         let row_offset = i / cols_in_sheet;
         
         // 计算全局行列
-        let global_col = region.start_col + col_offset;
-        let global_row = region.start_row + row_offset;
+        let global_col = region.start_col.saturating_add(col_offset);
+        let global_row = region.start_row.saturating_add(row_offset);
         
         // 计算像素坐标
-        let x = global_col * region.frame_width;
-        let y = global_row * region.frame_height;
+        let x = global_col.saturating_mul(region.frame_width);
+        let y = global_row.saturating_mul(region.frame_height);
+        
+        // 已超出图集底部，后续帧都无效
+        if y >= spritesheet.height {
+            break;
+        }
         
         // 检查是否超出边界
-        if x + region.frame_width > spritesheet.width || y + region.frame_height > spritesheet.height {
+        if !fits_within(x, region.frame_width, spritesheet.width)
+            || !fits_within(y, region.frame_height, spritesheet.height)
+        {
             continue;
         }
         
-        let name = format!("{}_{:02}.png", region.name, i + 1);
+        let name = numbering.frame_name(&region.name, i)?;
         
         frames.push(FrameInfo {
             name,
@@ -768,10 +1179,16 @@ fn calculate_region_frames(
             height: region.frame_height,
             row: global_row,
             col: global_col,
+            anchor: region.anchor,
         });
     }
     
-    frames
+    Ok(frames)
+}
+
+/// 检查 [start, start + len) 是否位于 [0, limit) 内（避免加法溢出）
+fn fits_within(start: u32, len: u32, limit: u32) -> bool {
+    start.checked_add(len).is_some_and(|end| end <= limit)
 }
 
 /// 计算区域帧信息（预览用，前端调用）
@@ -787,7 +1204,14 @@ pub async fn calculate_region_preview(
     spritesheet: SpritesheetInfo,
     region: crate::core::types::AnimationRegion,
 ) -> Result<SplitResult, String> {
-    let frames = calculate_region_frames(&spritesheet, &region);
+    catch_panic("calculate_region_preview", calculate_region_preview_impl(spritesheet, region)).await
+}
+
+async fn calculate_region_preview_impl(
+    spritesheet: SpritesheetInfo,
+    region: crate::core::types::AnimationRegion,
+) -> Result<SplitResult, String> {
+    let frames = calculate_region_frames(&spritesheet, &region)?;
     
     if frames.is_empty() {
         return Err("区域配置无效，没有生成帧".to_string());
@@ -798,12 +1222,100 @@ pub async fn calculate_region_preview(
         frame_width: region.frame_width,
         frame_height: region.frame_height,
         total_frames: frames.len() as u32,
+        skipped_empty: 0,
+        warnings: Vec::new(),
+    })
+}
+
+/// 将动画区域导出为 GameMaker 横向条带图
+///
+/// 每个区域生成一张 `name_stripN.png`，默认保存到图集同目录
+///
+/// # Arguments
+/// * `spritesheet` - 图集信息
+/// * `regions` - 动画区域列表
+/// * `output_dir` - 输出目录（可选，默认图集所在目录）
+///
+/// # Returns
+/// * `Result<StripExportResult, String>` - 导出结果
+#[tauri::command]
+pub async fn export_region_strips(
+    spritesheet: SpritesheetInfo,
+    regions: Vec<crate::core::types::AnimationRegion>,
+    output_dir: Option<String>,
+) -> Result<StripExportResult, String> {
+    run_blocking("export_region_strips", move || export_region_strips_impl(spritesheet, regions, output_dir)).await
+}
+
+fn export_region_strips_impl(
+    spritesheet: SpritesheetInfo,
+    regions: Vec<crate::core::types::AnimationRegion>,
+    output_dir: Option<String>,
+) -> Result<StripExportResult, String> {
+    if regions.is_empty() {
+        return Err("没有区域可导出".to_string());
+    }
+    for region in &regions {
+        validate_strip_name(&region.name)?;
+    }
+
+    let source_img = load_image(&spritesheet.path, &ImageLoadLimits::default())?;
+
+    let png_path = Path::new(&spritesheet.path);
+    let out_dir = match &output_dir {
+        Some(dir) => Path::new(dir),
+        None => png_path.parent().unwrap_or(Path::new(".")),
+    };
+    let _guard = try_lock("export_region_strips", [job_lock::output_dir(out_dir)])?;
+    std::fs::create_dir_all(out_dir)
+        .map_err(|e| format!("无法创建输出目录: {}", e))?;
+
+    let mut exported_files = Vec::new();
+    let mut failed: Vec<(String, String)> = Vec::new();
+    let total = regions.len();
+
+    for region in &regions {
+        let frames = match calculate_region_frames(&spritesheet, region) {
+            Ok(frames) => frames,
+            Err(e) => {
+                failed.push((region.name.clone(), e));
+                continue;
+            }
+        };
+        let frame_images: Vec<_> = frames
+            .iter()
+            .map(|f| source_img.crop_imm(f.x, f.y, f.width, f.height).to_rgba8())
+            .collect();
+
+        let Some(strip) = compose_strip(&frame_images) else {
+            failed.push((region.name.clone(), "区域没有有效帧".to_string()));
+            continue;
+        };
+
+        let strip_path = out_dir.join(strip_file_name(&region.name, frame_images.len()));
+        match strip.save(&strip_path) {
+            Ok(_) => {
+                println!("条带图导出成功: {}", strip_path.display());
+                exported_files.push(strip_path.to_string_lossy().to_string());
+            }
+            Err(e) => {
+                failed.push((region.name.clone(), format!("保存 PNG 失败: {}", e)));
+            }
+        }
+    }
+
+    Ok(StripExportResult {
+        exported_files,
+        failed,
+        total,
     })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::frame_check::{FrameConstraints, FrameWarningKind};
+    use crate::core::grid::SplitRounding;
     
     #[test]
     fn test_split_calculation() {
@@ -821,6 +1333,17 @@ mod tests {
             frame_height: None,
             name_prefix: "frame".to_string(),
             start_index: Some(1),
+            numbering: None,
+            constraints: None,
+            rounding: None,
+            margin_x: None,
+            margin_y: None,
+            spacing_x: None,
+            spacing_y: None,
+            offset_x: None,
+            offset_y: None,
+            skip_empty: None,
+            anchor: None,
         };
         
         // 模拟异步调用
@@ -832,5 +1355,334 @@ mod tests {
         assert_eq!(split.total_frames, 8);
         assert_eq!(split.frame_width, 128);
         assert_eq!(split.frame_height, 128);
+        assert!(split.warnings.is_empty());
+    }
+    
+    #[test]
+    fn test_split_constraint_warnings() {
+        let spritesheet = SpritesheetInfo {
+            path: "test.png".to_string(),
+            name: "test.png".to_string(),
+            width: 300,
+            height: 100,
+        };
+        
+        let config = SplitConfig {
+            rows: 1,
+            cols: 4,
+            frame_width: None,
+            frame_height: None,
+            name_prefix: "frame".to_string(),
+            start_index: None,
+            numbering: None,
+            constraints: Some(FrameConstraints {
+                power_of_two: true,
+                ..Default::default()
+            }),
+            rounding: None,
+            margin_x: None,
+            margin_y: None,
+            spacing_x: None,
+            spacing_y: None,
+            offset_x: None,
+            offset_y: None,
+            skip_empty: None,
+            anchor: None,
+        };
+        
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let split = rt.block_on(calculate_split_frames(spritesheet, config)).unwrap();
+        assert_eq!(split.warnings.len(), 1);
+        assert_eq!(split.warnings[0].kind, FrameWarningKind::NotPowerOfTwo);
+        assert_eq!(split.warnings[0].frame_count, 4);
+    }
+    
+    #[test]
+    fn test_split_rounding_distribute() {
+        let spritesheet = SpritesheetInfo {
+            path: "test.png".to_string(),
+            name: "test.png".to_string(),
+            width: 725,
+            height: 100,
+        };
+        let config = |rounding| SplitConfig {
+            rows: 1,
+            cols: 6,
+            frame_width: None,
+            frame_height: None,
+            name_prefix: "frame".to_string(),
+            start_index: None,
+            numbering: None,
+            constraints: None,
+            rounding: Some(rounding),
+            margin_x: None,
+            margin_y: None,
+            spacing_x: None,
+            spacing_y: None,
+            offset_x: None,
+            offset_y: None,
+            skip_empty: None,
+            anchor: None,
+        };
+        
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let split = rt
+            .block_on(calculate_split_frames(spritesheet.clone(), config(SplitRounding::Distribute)))
+            .unwrap();
+        assert_eq!(split.total_frames, 6);
+        let last = split.frames.last().unwrap();
+        assert_eq!(last.x + last.width, 725);
+        assert!(split.frames.iter().all(|f| f.width == 120 || f.width == 121));
+        
+        let truncated = rt
+            .block_on(calculate_split_frames(spritesheet.clone(), config(SplitRounding::Truncate)))
+            .unwrap();
+        assert_eq!(truncated.frames.last().unwrap().x, 600);
+        
+        assert!(rt
+            .block_on(calculate_split_frames(spritesheet, config(SplitRounding::Error)))
+            .is_err());
+    }
+    
+    #[test]
+    fn test_split_margin_spacing_offset() {
+        // 4 列 16 像素帧，外边距 1、列间距 2：1 + 4*16 + 3*2 + 1 = 72
+        // 2 行 16 像素帧，向下偏移 2、行间距 2：2 + 2*16 + 2 = 36
+        let spritesheet = SpritesheetInfo {
+            path: "test.png".to_string(),
+            name: "test.png".to_string(),
+            width: 72,
+            height: 36,
+        };
+        let config = SplitConfig {
+            rows: 2,
+            cols: 4,
+            frame_width: None,
+            frame_height: None,
+            name_prefix: "frame".to_string(),
+            start_index: None,
+            numbering: None,
+            constraints: None,
+            rounding: Some(SplitRounding::Error),
+            margin_x: Some(1),
+            margin_y: None,
+            spacing_x: Some(2),
+            spacing_y: Some(2),
+            offset_x: None,
+            offset_y: Some(2),
+            skip_empty: None,
+            anchor: None,
+        };
+        
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let split = rt.block_on(calculate_split_frames(spritesheet.clone(), config.clone())).unwrap();
+        assert_eq!(split.total_frames, 8);
+        assert_eq!((split.frame_width, split.frame_height), (16, 16));
+        let rects: Vec<(u32, u32)> = split.frames.iter().take(5).map(|f| (f.x, f.y)).collect();
+        assert_eq!(rects, vec![(1, 2), (19, 2), (37, 2), (55, 2), (1, 20)]);
+        assert!(split.frames.iter().all(|f| f.width == 16 && f.height == 16));
+        
+        // 固定帧尺寸时同样按间距排列
+        let fixed = SplitConfig { frame_width: Some(16), frame_height: Some(16), ..config };
+        let split = rt.block_on(calculate_split_frames(spritesheet, fixed)).unwrap();
+        assert_eq!(split.frames[3].x, 55);
+        assert_eq!(split.frames[7].y, 20);
+    }
+    
+    #[test]
+    fn test_split_skip_empty() {
+        // 2x3 网格，最后一行只有第一格有内容
+        let dir = std::env::temp_dir().join(format!("ezplist_split_empty_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("sheet.png");
+        let mut img = image::RgbaImage::new(48, 32);
+        for (col, row) in [(0, 0), (1, 0), (2, 0), (0, 1)] {
+            img.put_pixel(col * 16 + 8, row * 16 + 8, image::Rgba([255, 255, 255, 255]));
+        }
+        img.save(&path).unwrap();
+        let spritesheet = SpritesheetInfo {
+            path: path.to_string_lossy().to_string(),
+            name: "sheet.png".to_string(),
+            width: 48,
+            height: 32,
+        };
+        let config = |skip_empty| SplitConfig {
+            rows: 2,
+            cols: 3,
+            frame_width: None,
+            frame_height: None,
+            name_prefix: "frame".to_string(),
+            start_index: None,
+            numbering: None,
+            constraints: None,
+            rounding: None,
+            margin_x: None,
+            margin_y: None,
+            spacing_x: None,
+            spacing_y: None,
+            offset_x: None,
+            offset_y: None,
+            skip_empty: Some(skip_empty),
+            anchor: None,
+        };
+        
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let all = rt.block_on(calculate_split_frames(spritesheet.clone(), config(false))).unwrap();
+        assert_eq!((all.total_frames, all.skipped_empty), (6, 0));
+        
+        let split = rt.block_on(calculate_split_frames(spritesheet, config(true))).unwrap();
+        assert_eq!((split.total_frames, split.skipped_empty), (4, 2));
+        // 序号保持连续
+        assert_eq!(split.frames[3].name, "frame_04.png");
+        assert_eq!((split.frames[3].row, split.frames[3].col), (1, 0));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+    
+    #[test]
+    fn test_auto_detect_gutters() {
+        // 外边距 1、间距 2 的 4x1 网格，帧内容铺满整帧
+        let mut img = image::RgbaImage::new(72, 16);
+        for col in 0..4 {
+            for y in 0..16 {
+                for x in 0..16 {
+                    img.put_pixel(1 + col * 18 + x, y, image::Rgba([255, 0, 0, 255]));
+                }
+            }
+        }
+        let detect = detect_frames_by_transparency(&image::DynamicImage::ImageRgba8(img)).unwrap();
+        assert_eq!((detect.frame_width, detect.frame_height, detect.cols, detect.rows), (16, 16, 4, 1));
+        assert_eq!((detect.margin_x, detect.spacing_x), (1, 2));
+    }
+    
+    #[test]
+    fn test_region_frames_with_invalid_frame_size() {
+        let spritesheet = SpritesheetInfo {
+            path: "test.png".to_string(),
+            name: "test.png".to_string(),
+            width: 100,
+            height: 100,
+        };
+        
+        let region = |frame_width, frame_height| crate::core::types::AnimationRegion {
+            name: "idle".to_string(),
+            start_row: 0,
+            start_col: 0,
+            frame_count: 4,
+            frame_width,
+            frame_height,
+            numbering: None,
+            anchor: None,
+        };
+        
+        // 帧尺寸为 0 或大于图集时不应 panic
+        assert!(calculate_region_frames(&spritesheet, &region(0, 10)).unwrap().is_empty());
+        assert!(calculate_region_frames(&spritesheet, &region(200, 10)).unwrap().is_empty());
+        let frames = calculate_region_frames(&spritesheet, &region(50, 50)).unwrap();
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames[0].name, "idle_01.png");
+    }
+    
+    #[test]
+    fn test_multi_export_partial_failure() {
+        let dir = std::env::temp_dir().join(format!("ezplist_multi_export_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("sheet.png");
+        image::RgbaImage::new(64, 32).save(&path).unwrap();
+        let spritesheet = SpritesheetInfo {
+            path: path.to_string_lossy().to_string(),
+            name: "sheet.png".to_string(),
+            width: 64,
+            height: 32,
+        };
+        let region = |name: &str, frame_width| crate::core::types::AnimationRegion {
+            name: name.to_string(),
+            start_row: 0,
+            start_col: 0,
+            frame_count: 2,
+            frame_width,
+            frame_height: 16,
+            numbering: None,
+            anchor: None,
+        };
+        // 与 Plist 同名的目录使写入失败
+        std::fs::create_dir_all(dir.join("blocked.plist")).unwrap();
+        
+        let regions = vec![region("idle", 16), region("empty", 0), region("blocked", 16)];
+        let result = export_multi_plist_impl(spritesheet.clone(), regions).unwrap();
+        assert_eq!(result.exported_files.len(), 1);
+        let codes: Vec<RegionFailureCode> = result.failed.iter().map(|f| f.code).collect();
+        assert_eq!(codes, vec![RegionFailureCode::NoFrames, RegionFailureCode::WritePlist]);
+        assert!(result.warning.unwrap().starts_with("2/3"));
+        // 失败区域已写入的 PNG 被清理
+        assert!(dir.join("idle.png").exists());
+        assert!(!dir.join("blocked.png").exists());
+        
+        let err = export_multi_plist_impl(spritesheet, vec![region("empty", 0)]).unwrap_err();
+        assert!(err.contains("全部 1 个区域导出失败"));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_region_strips_reject_path_names() {
+        let dir = std::env::temp_dir().join(format!("ezplist_region_strips_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("sheet.png");
+        image::RgbaImage::new(32, 16).save(&path).unwrap();
+        let spritesheet = SpritesheetInfo {
+            path: path.to_string_lossy().to_string(),
+            name: "sheet.png".to_string(),
+            width: 32,
+            height: 16,
+        };
+        let region = |name: &str| crate::core::types::AnimationRegion {
+            name: name.to_string(),
+            start_row: 0,
+            start_col: 0,
+            frame_count: 2,
+            frame_width: 16,
+            frame_height: 16,
+            numbering: None,
+            anchor: None,
+        };
+        let out = dir.join("out");
+
+        // 任一区域名称包含路径时整个导出失败，不写入任何文件
+        let regions = vec![region("idle"), region("../evil")];
+        let err = export_region_strips_impl(spritesheet, regions, Some(out.to_string_lossy().to_string())).unwrap_err();
+        assert!(err.contains("路径"));
+        assert!(!out.exists());
+        assert!(!dir.join("evil_strip2.png").exists());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_split_exports_keep_anchor() {
+        use crate::core::atlas_plist::parse_atlas_plist;
+
+        let dir = std::env::temp_dir().join(format!("ezplist_split_anchor_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("sheet.png");
+        image::RgbaImage::new(32, 16).save(&path).unwrap();
+        let spritesheet = SpritesheetInfo {
+            path: path.to_string_lossy().to_string(),
+            name: "sheet.png".to_string(),
+            width: 32,
+            height: 16,
+        };
+        let frame = |name: &str, x, anchor| FrameInfo { name: name.to_string(), x, y: 0, width: 16, height: 16, row: 0, col: x / 16, anchor };
+        let frames = vec![frame("a.png", 0, Some((0.5, 0.0))), frame("b.png", 16, None)];
+
+        // 分页导出
+        let result = export_tiled_sheet_impl(spritesheet, frames.clone(), "tiled".to_string(), None).unwrap();
+        let atlas = parse_atlas_plist(&std::fs::read(&result.pages[0].plist_path).unwrap()).unwrap();
+        assert_eq!(atlas.frame("a.png").unwrap().anchor, Some((0.5, 0.0)));
+        assert_eq!(atlas.frame("b.png").unwrap().anchor, None);
+
+        // 区域导出（坐标相对区域图像）
+        let data = build_region_plist(&frames, (0, 0), &image::RgbaImage::new(32, 16), "region.png").unwrap();
+        let atlas = parse_atlas_plist(&data).unwrap();
+        assert_eq!(atlas.frame("a.png").unwrap().anchor, Some((0.5, 0.0)));
+        assert_eq!(atlas.frame("b.png").unwrap().anchor, None);
+        std::fs::remove_dir_all(&dir).ok();
     }
 }
@@ -0,0 +1,239 @@
+/// 模板导出命令 (Template Export Commands)
+///
+/// 注册用户编写的导出模板，并用其渲染打包/切分结果
+
+use crate::commands::session::SessionStore;
+use crate::core::codegen::{generate_frame_index, CodegenLanguage, FrameIndexAtlas};
+use crate::core::limits::check_text_input_size;
+use crate::core::template_exporter::{render_template, validate_template, TemplateContext};
+use crate::core::types::{FrameInfo, PackedSprite};
+use crate::utils::guard::{catch_panic, run_blocking};
+use std::path::Path;
+use tauri::State;
+
+/// 导出模板信息
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportTemplateInfo {
+    /// 模板名称
+    pub name: String,
+    /// 模板文件路径
+    pub path: String,
+}
+
+/// 模板导出配置
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateExportConfig {
+    /// 模板名称（需先注册）
+    pub template_name: String,
+    /// 打包结果中的精灵（与 frames 二选一）
+    pub packed_sprites: Option<Vec<PackedSprite>>,
+    /// 切分得到的帧（与 packed_sprites 二选一）
+    pub frames: Option<Vec<FrameInfo>>,
+    /// 纹理宽度
+    pub texture_width: u32,
+    /// 纹理高度
+    pub texture_height: u32,
+    /// 纹理文件名
+    pub texture_name: String,
+    /// 输出文件路径（含扩展名）
+    pub output_path: String,
+}
+
+/// 帧索引代码导出配置
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameIndexExportConfig {
+    /// 输出语言
+    pub language: CodegenLanguage,
+    /// 生成的类型名称（如 `HeroFrame`）
+    pub type_name: String,
+    /// 图集及其帧（多页图集按页序排列）
+    pub atlases: Vec<FrameIndexAtlas>,
+    /// 输出文件路径（不含扩展名时按语言补全）
+    pub output_path: String,
+}
+
+/// 注册导出模板命令
+///
+/// 模板在注册时校验语法，导出时重新读取文件，因此修改模板后无需重新注册
+///
+/// # Arguments
+/// * `session_id` - 会话 ID
+/// * `name` - 模板名称
+/// * `path` - 模板文件路径
+///
+/// # Returns
+/// * `Result<ExportTemplateInfo, String>` - 注册的模板信息或错误
+#[tauri::command]
+pub async fn register_export_template(
+    sessions: State<'_, SessionStore>,
+    session_id: String,
+    name: String,
+    path: String,
+) -> Result<ExportTemplateInfo, String> {
+    catch_panic(
+        "register_export_template",
+        register_export_template_impl(&sessions, session_id, name, path),
+    )
+    .await
+}
+
+async fn register_export_template_impl(
+    sessions: &SessionStore,
+    session_id: String,
+    name: String,
+    path: String,
+) -> Result<ExportTemplateInfo, String> {
+    if name.trim().is_empty() {
+        return Err("模板名称不能为空".to_string());
+    }
+
+    let source = read_template_file(&path)?;
+    validate_template(&source)?;
+
+    sessions.with_session(&session_id, |session| {
+        session.export_templates.insert(name.clone(), path.clone());
+        Ok(())
+    })?;
+
+    println!("✓ 注册导出模板: {} ({})", name, path);
+
+    Ok(ExportTemplateInfo { name, path })
+}
+
+/// 列出会话中已注册的导出模板（按名称排序）
+///
+/// # Arguments
+/// * `session_id` - 会话 ID
+#[tauri::command]
+pub async fn list_export_templates(
+    sessions: State<'_, SessionStore>,
+    session_id: String,
+) -> Result<Vec<ExportTemplateInfo>, String> {
+    catch_panic("list_export_templates", list_export_templates_impl(&sessions, session_id)).await
+}
+
+async fn list_export_templates_impl(
+    sessions: &SessionStore,
+    session_id: String,
+) -> Result<Vec<ExportTemplateInfo>, String> {
+    sessions.with_session(&session_id, |session| {
+        Ok(session
+            .export_templates
+            .iter()
+            .map(|(name, path)| ExportTemplateInfo {
+                name: name.clone(),
+                path: path.clone(),
+            })
+            .collect())
+    })
+}
+
+/// 使用会话中已注册的模板导出帧数据
+///
+/// # Arguments
+/// * `session_id` - 会话 ID
+/// * `config` - 模板导出配置
+///
+/// # Returns
+/// * `Result<String, String>` - 输出文件路径或错误信息
+#[tauri::command]
+pub async fn export_with_template(
+    sessions: State<'_, SessionStore>,
+    session_id: String,
+    config: TemplateExportConfig,
+) -> Result<String, String> {
+    let sessions = sessions.inner().clone();
+    run_blocking("export_with_template", move || export_with_template_impl(&sessions, session_id, config)).await
+}
+
+fn export_with_template_impl(
+    sessions: &SessionStore,
+    session_id: String,
+    config: TemplateExportConfig,
+) -> Result<String, String> {
+    let template_path = sessions.with_session(&session_id, |session| {
+        session
+            .export_templates
+            .get(&config.template_name)
+            .cloned()
+            .ok_or_else(|| format!("未注册的模板: {}", config.template_name))
+    })?;
+
+    let source = read_template_file(&template_path)?;
+
+    let context = match (&config.packed_sprites, &config.frames) {
+        (Some(sprites), _) => TemplateContext::from_packed(
+            sprites,
+            config.texture_width,
+            config.texture_height,
+            &config.texture_name,
+        ),
+        (None, Some(frames)) => TemplateContext::from_frames(
+            frames,
+            config.texture_width,
+            config.texture_height,
+            &config.texture_name,
+        ),
+        (None, None) => return Err("没有帧可导出".to_string()),
+    };
+
+    let output = render_template(&source, &context)?;
+
+    if let Some(parent) = Path::new(&config.output_path).parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("无法创建输出目录: {}", e))?;
+    }
+    std::fs::write(&config.output_path, output)
+        .map_err(|e| format!("写入文件失败: {}", e))?;
+
+    println!("✓ 模板导出成功: {}", config.output_path);
+
+    Ok(config.output_path)
+}
+
+/// 导出帧索引代码命令
+///
+/// 生成 TypeScript 枚举、C++ 头文件或 Lua 表，供游戏代码以常量引用帧
+///
+/// # Arguments
+/// * `config` - 导出配置
+///
+/// # Returns
+/// * `Result<String, String>` - 输出文件路径或错误信息
+#[tauri::command]
+pub async fn export_frame_index(config: FrameIndexExportConfig) -> Result<String, String> {
+    run_blocking("export_frame_index", move || export_frame_index_impl(config)).await
+}
+
+fn export_frame_index_impl(config: FrameIndexExportConfig) -> Result<String, String> {
+    let code = generate_frame_index(&config.type_name, &config.atlases, config.language)?;
+
+    let mut output_path = std::path::PathBuf::from(&config.output_path);
+    if output_path.extension().is_none() {
+        output_path.set_extension(config.language.extension());
+    }
+    if let Some(parent) = output_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("无法创建输出目录: {}", e))?;
+    }
+    std::fs::write(&output_path, code)
+        .map_err(|e| format!("写入文件失败: {}", e))?;
+
+    let output_path = output_path.to_string_lossy().to_string();
+    println!("✓ 帧索引代码导出成功: {}", output_path);
+
+    Ok(output_path)
+}
+
+/// 读取模板文件（先检查文件大小）
+fn read_template_file(path: &str) -> Result<String, String> {
+    let metadata = std::fs::metadata(path)
+        .map_err(|e| format!("无法读取模板文件 {}: {}", path, e))?;
+    check_text_input_size(metadata.len())?;
+
+    std::fs::read_to_string(path)
+        .map_err(|e| format!("无法读取模板文件 {}: {}", path, e))
+}
@@ -0,0 +1,428 @@
+/// 图集拆分命令 (Atlas Unpack Commands)
+///
+/// 读取已有的 Cocos2d-x 图集（plist format 2 与 3，含旋转帧），
+/// 把每一帧还原为原始尺寸的单独 PNG，便于修改后重新打包；
+/// 也可以直接在内存中增删帧后重新打包整个图集。
+/// 还原出的帧经 `frame_cache` 缓存在磁盘上
+
+use crate::commands::pack::{pack_with_loader, source_loader, ImageSource, PackConfig, PackSessionStore};
+use crate::commands::patch::read_atlas_plist;
+use crate::core::atlas_patch::trim_origin;
+use crate::core::atlas_unpack::frame_file_path;
+use crate::core::types::{PackResult, PackedSprite, SpriteData};
+use crate::utils::frame_cache::{clear_cache, default_cache_dir, CachedAtlas};
+use crate::utils::guard::run_blocking;
+use crate::utils::job_lock::{output_dir, try_lock};
+use crate::utils::png_writer::encode_png;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::State;
+
+/// 已导入的图集
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedAtlas {
+    /// plist 路径
+    pub plist_path: String,
+    /// 纹理路径
+    pub texture_path: String,
+    /// plist 格式版本（2 或 3）
+    pub format: i64,
+    /// 纹理宽度
+    pub width: u32,
+    /// 纹理高度
+    pub height: u32,
+    /// 帧（宽高为在纹理中占用的尺寸）
+    pub frames: Vec<PackedSprite>,
+}
+
+/// 拆分配置
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnpackAtlasConfig {
+    /// 图集 plist 路径
+    pub plist_path: String,
+    /// 输出目录
+    pub output_dir: String,
+    /// 只拆分这些帧（可选，为空时拆分全部帧）
+    pub frame_names: Option<Vec<String>>,
+}
+
+/// 重新打包配置
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepackAtlasConfig {
+    /// 图集 plist 路径
+    pub plist_path: String,
+    /// 移除的帧名称（可选）
+    pub removed_frames: Option<Vec<String>>,
+    /// 新增的图片（可选，与已有帧同名时替换该帧）
+    pub added_sprites: Option<Vec<SpriteData>>,
+    /// 打包配置（可选，默认与 `pack_sprites` 相同）
+    pub pack_config: Option<PackConfig>,
+}
+
+/// 拆分结果
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnpackAtlasResult {
+    /// 输出目录
+    pub output_dir: String,
+    /// 已写入的帧文件
+    pub files: Vec<String>,
+    /// 失败的帧及原因
+    pub failed: Vec<String>,
+}
+
+/// 导入已有图集命令（只读取 plist，不裁切纹理）
+///
+/// # Arguments
+/// * `plist_path` - 图集 plist 路径
+///
+/// # Returns
+/// * `Result<ImportedAtlas, String>` - 图集描述；纹理文件不存在时返回错误
+#[tauri::command]
+pub async fn import_plist(plist_path: String) -> Result<ImportedAtlas, String> {
+    run_blocking("import_plist", move || import_plist_impl(&plist_path)).await
+}
+
+fn import_plist_impl(plist_path: &str) -> Result<ImportedAtlas, String> {
+    let plist_path = Path::new(plist_path);
+    let (_, atlas) = read_atlas_plist(plist_path)?;
+    let texture_path = texture_path(plist_path, &atlas.texture);
+    if !texture_path.is_file() {
+        return Err(format!("找不到图集纹理: {}", texture_path.display()));
+    }
+
+    println!("✓ 导入图集: {} 帧, 纹理 {}x{}", atlas.frames.len(), atlas.width, atlas.height);
+
+    Ok(ImportedAtlas {
+        plist_path: plist_path.to_string_lossy().to_string(),
+        texture_path: texture_path.to_string_lossy().to_string(),
+        format: atlas.format,
+        width: atlas.width,
+        height: atlas.height,
+        frames: atlas.frames,
+    })
+}
+
+/// 拆分已有图集为单帧 PNG 命令
+///
+/// 帧名称中的子目录会保留；单帧失败不影响其他帧
+///
+/// # Arguments
+/// * `config` - 拆分配置
+///
+/// # Returns
+/// * `Result<UnpackAtlasResult, String>` - 拆分结果
+#[tauri::command]
+pub async fn unpack_atlas(config: UnpackAtlasConfig) -> Result<UnpackAtlasResult, String> {
+    run_blocking("unpack_atlas", move || unpack_atlas_impl(config)).await
+}
+
+fn unpack_atlas_impl(config: UnpackAtlasConfig) -> Result<UnpackAtlasResult, String> {
+    let plist_path = Path::new(&config.plist_path);
+    let (_, atlas) = read_atlas_plist(plist_path)?;
+    let texture_path = texture_path(plist_path, &atlas.texture);
+    // 已拆分过的帧从缓存读取，纹理只在需要时解码
+    let mut texture = CachedAtlas::open(&default_cache_dir(), &texture_path.to_string_lossy())?;
+    if let Ok((width, height)) = image::image_dimensions(&texture_path) {
+        if (width, height) != (atlas.width, atlas.height) {
+            println!("警告: 纹理尺寸 {}x{} 与 Plist 中的 {}x{} 不一致", width, height, atlas.width, atlas.height);
+        }
+    }
+
+    let frames: Vec<&PackedSprite> = match &config.frame_names {
+        Some(names) => names
+            .iter()
+            .map(|name| atlas.frame(name).ok_or_else(|| format!("图集中没有帧: {}", name)))
+            .collect::<Result<_, _>>()?,
+        None => atlas.frames.iter().collect(),
+    };
+
+    let out_dir = PathBuf::from(&config.output_dir);
+    std::fs::create_dir_all(&out_dir).map_err(|e| format!("无法创建输出目录: {}", e))?;
+    let _guard = try_lock("unpack_atlas", [output_dir(&out_dir)])?;
+
+    let mut files = Vec::new();
+    let mut failed = Vec::new();
+    let mut written = HashSet::new();
+    for frame in frames {
+        match unpack_frame(&mut texture, frame, &out_dir, &mut written) {
+            Ok(path) => files.push(path.to_string_lossy().to_string()),
+            Err(e) => {
+                println!("✗ 拆分帧 {} 失败: {}", frame.name, e);
+                failed.push(format!("{}: {}", frame.name, e));
+            }
+        }
+    }
+
+    println!("✓ 图集拆分完成: {} 帧写入, {} 帧失败", files.len(), failed.len());
+
+    Ok(UnpackAtlasResult {
+        output_dir: config.output_dir,
+        files,
+        failed,
+    })
+}
+
+/// 重新打包已有图集命令
+///
+/// 图集中的帧还原为原始尺寸后作为内存中的精灵，与新增图片一起重新打包；
+/// 结果与 `pack_sprites` 相同，按 `jobId` 用 `export_sprite_sheet` 导出
+///
+/// # Arguments
+/// * `config` - 重新打包配置
+///
+/// # Returns
+/// * `Result<PackResult, String>` - 打包结果或错误信息
+#[tauri::command]
+pub async fn repack_atlas(store: State<'_, PackSessionStore>, config: RepackAtlasConfig) -> Result<PackResult, String> {
+    repack_atlas_in(store.inner().clone(), config).await
+}
+
+/// 重新打包并把裁剪结果存入指定存储（自动化接口使用）
+pub(crate) async fn repack_atlas_in(store: PackSessionStore, config: RepackAtlasConfig) -> Result<PackResult, String> {
+    run_blocking("repack_atlas", move || repack_atlas_impl(&store, config)).await
+}
+
+fn repack_atlas_impl(store: &PackSessionStore, config: RepackAtlasConfig) -> Result<PackResult, String> {
+    let plist_path = Path::new(&config.plist_path);
+    let (_, atlas) = read_atlas_plist(plist_path)?;
+    let texture_path = texture_path(plist_path, &atlas.texture).to_string_lossy().to_string();
+
+    let removed = config.removed_frames.unwrap_or_default();
+    if let Some(name) = removed.iter().find(|name| atlas.frame(name).is_none()) {
+        return Err(format!("图集中没有帧: {}", name));
+    }
+    let added = config.added_sprites.unwrap_or_default();
+    let replaced: HashSet<&str> = added.iter().map(|sprite| sprite.name.as_str()).collect();
+
+    let mut texture = CachedAtlas::open(&default_cache_dir(), &texture_path)?;
+    let mut sprites = Vec::new();
+    let mut sources = HashMap::new();
+    for frame in &atlas.frames {
+        if removed.contains(&frame.name) || replaced.contains(frame.name.as_str()) {
+            continue;
+        }
+        let image = texture.frame(frame)?;
+        sprites.push(atlas_sprite(frame, &texture_path));
+        sources.insert(frame.id.clone(), ImageSource::Buffer(Arc::new(image)));
+    }
+    let kept = sprites.len();
+    for sprite in added {
+        if sources.contains_key(&sprite.id) {
+            return Err(format!("新增图片的 ID 与图集中的帧重复: {}", sprite.id));
+        }
+        sources.insert(sprite.id.clone(), ImageSource::File(sprite.path.clone()));
+        sprites.push(sprite);
+    }
+
+    println!(
+        "重新打包图集: 保留 {} 帧, 移除或替换 {} 帧, 新增 {} 张图片",
+        kept,
+        atlas.frames.len() - kept,
+        sprites.len() - kept
+    );
+    // 帧图像只在内存中，不记录打包输入（导出历史无法从路径重新加载）
+    pack_with_loader(store, &sprites, config.pack_config, source_loader(&sources))
+}
+
+/// 清空图集帧缓存命令
+///
+/// # Returns
+/// * `Result<u64, String>` - 释放的字节数
+#[tauri::command]
+pub async fn clear_frame_cache() -> Result<u64, String> {
+    run_blocking("clear_frame_cache", || {
+        let freed = clear_cache(&default_cache_dir())?;
+        println!("✓ 已清空帧缓存，释放 {} 字节", freed);
+        Ok(freed)
+    })
+    .await
+}
+
+/// 还原并写入一帧，返回输出路径
+fn unpack_frame(
+    texture: &mut CachedAtlas,
+    frame: &PackedSprite,
+    out_dir: &Path,
+    written: &mut HashSet<PathBuf>,
+) -> Result<PathBuf, String> {
+    let path = out_dir.join(frame_file_path(&frame.name)?);
+    if !written.insert(path.clone()) {
+        return Err(format!("输出文件重名: {}", path.display()));
+    }
+    let image = texture.frame(frame)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("无法创建目录: {}", e))?;
+    }
+    std::fs::write(&path, encode_png(&image)?).map_err(|e| format!("写入 PNG 失败: {}", e))?;
+    Ok(path)
+}
+
+/// 把图集中的帧转换为精灵（路径为图集纹理，尺寸为原始尺寸）
+fn atlas_sprite(frame: &PackedSprite, texture_path: &str) -> SpriteData {
+    let (trimmed_width, trimmed_height) = if frame.rotated {
+        (frame.height, frame.width)
+    } else {
+        (frame.width, frame.height)
+    };
+    // 九宫格边距在 plist 中相对帧尺寸，还原为相对原始尺寸，重新打包时再按新的裁剪换算
+    let nine_slice = frame.nine_slice.zip(trim_origin(frame)).map(|(nine_slice, (left, top))| {
+        nine_slice.untrimmed(
+            (frame.original_width, frame.original_height),
+            (left, top, trimmed_width, trimmed_height),
+        )
+    });
+    SpriteData {
+        id: frame.id.clone(),
+        name: frame.name.clone(),
+        path: texture_path.to_string(),
+        width: frame.original_width,
+        height: frame.original_height,
+        trimmed_width,
+        trimmed_height,
+        anchor: frame.anchor,
+        nine_slice,
+    }
+}
+
+/// 纹理路径（相对于 plist 所在目录）
+fn texture_path(plist_path: &Path, texture: &str) -> PathBuf {
+    plist_path.parent().unwrap_or(Path::new(".")).join(texture)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::image_processor::render_texture;
+    use crate::core::plist_generator::{generate_plist_data, PlistFormat};
+    use crate::core::texture_format::PixelFormat;
+    use crate::utils::hash::SmartUpdate;
+    use image::{Rgba, RgbaImage};
+
+    #[test]
+    fn test_unpack_atlas() {
+        let dir = std::env::temp_dir().join(format!("ezplist_unpack_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // 6x4 的帧裁剪为 (1,1)-(4,3)，旋转后放入纹理
+        let mut original = RgbaImage::new(6, 4);
+        for x in 1..4 {
+            original.put_pixel(x, 1, Rgba([255, 0, 0, 255]));
+            original.put_pixel(x, 2, Rgba([0, 0, 255, 255]));
+        }
+        let trimmed = image::imageops::crop_imm(&original, 1, 1, 3, 2).to_image();
+        let frame = |name: &str, x: u32, rotated: bool| PackedSprite {
+            id: name.to_string(),
+            name: name.to_string(),
+            x,
+            y: 0,
+            width: if rotated { 2 } else { 3 },
+            height: if rotated { 3 } else { 2 },
+            rotated,
+            original_width: 6,
+            original_height: 4,
+            trimmed: true,
+            offset_x: -1,
+            offset_y: 0,
+            flip_x: false,
+            flip_y: false,
+            anchor: None,
+            nine_slice: None,
+        };
+        let frames = vec![frame("walk/a.png", 0, false), frame("b", 4, true)];
+        let texture = render_texture(&frames, 8, 4, |_| Some(&trimmed)).unwrap();
+        texture.save(dir.join("atlas.png")).unwrap();
+        let plist_path = dir.join("atlas.plist");
+        std::fs::write(&plist_path, generate_plist_data(&frames, 8, 4, "atlas.png", PlistFormat::XmlV3, PixelFormat::Rgba8888, SmartUpdate::default()).unwrap()).unwrap();
+
+        let imported = import_plist_impl(&plist_path.to_string_lossy()).unwrap();
+        assert_eq!((imported.format, imported.frames.len()), (3, 2));
+
+        let out = dir.join("frames");
+        let result = unpack_atlas_impl(UnpackAtlasConfig {
+            plist_path: plist_path.to_string_lossy().to_string(),
+            output_dir: out.to_string_lossy().to_string(),
+            frame_names: None,
+        })
+        .unwrap();
+        assert!(result.failed.is_empty());
+        for name in ["walk/a.png", "b.png"] {
+            assert_eq!(image::open(out.join(name)).unwrap().to_rgba8(), original, "{}", name);
+        }
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_repack_atlas() {
+        let dir = std::env::temp_dir().join(format!("ezplist_repack_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let red = RgbaImage::from_pixel(4, 4, Rgba([255, 0, 0, 255]));
+        let frame = |name: &str, x: u32| PackedSprite {
+            id: name.to_string(),
+            name: name.to_string(),
+            x,
+            y: 0,
+            width: 4,
+            height: 4,
+            rotated: false,
+            original_width: 4,
+            original_height: 4,
+            trimmed: false,
+            offset_x: 0,
+            offset_y: 0,
+            flip_x: false,
+            flip_y: false,
+            anchor: None,
+            nine_slice: None,
+        };
+        let frames = vec![frame("a.png", 0), frame("b.png", 4), frame("c.png", 8)];
+        let texture = render_texture(&frames, 12, 4, |_| Some(&red)).unwrap();
+        texture.save(dir.join("atlas.png")).unwrap();
+        let plist_path = dir.join("atlas.plist");
+        std::fs::write(&plist_path, generate_plist_data(&frames, 12, 4, "atlas.png", PlistFormat::XmlV3, PixelFormat::Rgba8888, SmartUpdate::default()).unwrap()).unwrap();
+
+        // 新增图片 d.png，并用蓝色图片替换 c.png
+        let blue = RgbaImage::from_pixel(2, 6, Rgba([0, 0, 255, 255]));
+        let blue_path = dir.join("blue.png");
+        blue.save(&blue_path).unwrap();
+        let added = |id: &str, name: &str| SpriteData {
+            id: id.to_string(),
+            name: name.to_string(),
+            path: blue_path.to_string_lossy().to_string(),
+            width: 2,
+            height: 6,
+            trimmed_width: 2,
+            trimmed_height: 6,
+            anchor: None,
+            nine_slice: None,
+        };
+        let config = |removed: &[&str]| RepackAtlasConfig {
+            plist_path: plist_path.to_string_lossy().to_string(),
+            removed_frames: Some(removed.iter().map(|s| s.to_string()).collect()),
+            added_sprites: Some(vec![added("new-c", "c.png"), added("new-d", "d.png")]),
+            pack_config: None,
+        };
+
+        let store = PackSessionStore::default();
+        let result = repack_atlas_impl(&store, config(&["b.png"])).unwrap();
+        let mut names: Vec<&str> = result.packed_sprites.iter().map(|s| s.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, ["a.png", "c.png", "d.png"]);
+        let trims = store.get(&result.job_id).unwrap();
+        assert_eq!(trims["a.png"].trimmed_image, red);
+        assert_eq!(trims["new-c"].trimmed_image, blue);
+        // 内存中的帧无法从路径重新加载，不记录打包输入
+        assert!(store.inputs(&result.job_id).is_none());
+
+        assert!(repack_atlas_impl(&store, config(&["missing.png"])).unwrap_err().contains("missing.png"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,91 @@
+/// 视频导入命令 (Video Import Commands)
+///
+/// 从参考视频按帧率采样帧并作为精灵导入，用于逐帧描摹等工作流
+
+use crate::commands::import::{import_images_impl, ImportOptions};
+use crate::core::types::ImportResult;
+use crate::core::video::{is_video_path, validate_fps, VideoRange};
+use crate::utils::guard::run_blocking;
+use crate::utils::video_decoder::decode_frames;
+use std::path::Path;
+
+/// 从视频导入帧命令
+///
+/// 帧被解码到临时目录，精灵名为 `{视频文件名}_0001.png` 起
+///
+/// # Arguments
+/// * `path` - 视频路径（mp4、webm、mov 等）
+/// * `fps` - 采样帧率
+/// * `range` - 时间范围（可选，默认整个视频）
+/// * `options` - 导入选项（可选，如绿幕/蓝幕抠像）
+///
+/// # Returns
+/// * `Result<ImportResult, String>` - 导入结果；解码器不可用时返回错误
+#[tauri::command]
+pub async fn import_video_frames(
+    path: String,
+    fps: f64,
+    range: Option<VideoRange>,
+    options: Option<ImportOptions>,
+) -> Result<ImportResult, String> {
+    run_blocking("import_video_frames", move || {
+        import_video_frames_impl(&path, fps, range.unwrap_or_default(), options)
+    })
+    .await
+}
+
+fn import_video_frames_impl(
+    path: &str,
+    fps: f64,
+    range: VideoRange,
+    options: Option<ImportOptions>,
+) -> Result<ImportResult, String> {
+    validate_fps(fps)?;
+    range.validate()?;
+    if !is_video_path(path) {
+        return Err(format!("不支持的视频格式: {}", path));
+    }
+    if !Path::new(path).is_file() {
+        return Err(format!("文件不存在: {}", path));
+    }
+
+    println!("开始从视频导入帧: {} ({} fps)", path, fps);
+
+    // 导入的精灵在打包时仍从这些文件读取，因此每次导入使用独立目录且不清理旧目录
+    let out_dir = std::env::temp_dir()
+        .join("ezplist_video")
+        .join(uuid::Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&out_dir).map_err(|e| format!("无法创建临时目录: {}", e))?;
+    let frames = decode_frames(path, &out_dir, &frame_stem(path), fps, &range).inspect_err(|_| {
+        let _ = std::fs::remove_dir_all(&out_dir);
+    })?;
+
+    println!("✓ 视频解码完成: {} 帧", frames.len());
+
+    let paths = frames.iter().map(|p| p.to_string_lossy().to_string()).collect();
+    import_images_impl(paths, options)
+}
+
+/// 帧文件名前缀（视频文件名，去掉解码器模板中有特殊含义的 `%`）
+fn frame_stem(path: &str) -> String {
+    Path::new(path)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .filter(|s| !s.is_empty())
+        .unwrap_or("video")
+        .replace('%', "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejects_invalid_input() {
+        let import = |path: &str, fps: f64| import_video_frames_impl(path, fps, VideoRange::default(), None);
+        assert!(import("clip.mp4", 0.0).is_err());
+        assert!(import("clip.png", 12.0).unwrap_err().contains("不支持"));
+        assert!(import("missing.webm", 12.0).unwrap_err().contains("不存在"));
+        assert_eq!(frame_stem("/tmp/run 100%.mp4"), "run 100_");
+    }
+}
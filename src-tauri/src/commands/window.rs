@@ -0,0 +1,119 @@
+/// 项目窗口命令 (Project Window Commands)
+///
+/// 每个应用窗口绑定一个后端会话（项目），可以同时打开多个窗口并排比较两张图集，
+/// 或分别处理两个角色；窗口关闭时释放它的会话
+
+use crate::commands::session::SessionStore;
+use crate::core::session::Session;
+use crate::utils::guard::catch_panic;
+use serde::Serialize;
+use tauri::{AppHandle, State, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
+
+/// 项目窗口标签前缀（与 capabilities 中的窗口匹配规则一致）
+pub const PROJECT_WINDOW_PREFIX: &str = "project-";
+
+/// 新打开的项目窗口
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectWindow {
+    /// 窗口标签
+    pub label: String,
+    /// 窗口绑定的会话 ID
+    pub session_id: String,
+}
+
+/// 打开项目窗口命令
+///
+/// # Arguments
+/// * `session_id` - 要在新窗口中打开的已有会话（可选，默认新建空会话）
+/// * `title` - 窗口标题（可选）
+///
+/// # Returns
+/// * `Result<ProjectWindow, String>` - 新窗口的标签与会话 ID
+#[tauri::command]
+pub async fn open_project_window(
+    app: AppHandle,
+    sessions: State<'_, SessionStore>,
+    session_id: Option<String>,
+    title: Option<String>,
+) -> Result<ProjectWindow, String> {
+    catch_panic("open_project_window", open_project_window_impl(app, &sessions, session_id, title)).await
+}
+
+async fn open_project_window_impl(
+    app: AppHandle,
+    sessions: &SessionStore,
+    session_id: Option<String>,
+    title: Option<String>,
+) -> Result<ProjectWindow, String> {
+    let label = format!("{}{}", PROJECT_WINDOW_PREFIX, uuid::Uuid::new_v4().simple());
+    let session_id = match session_id {
+        Some(id) => id,
+        None => sessions.create().id,
+    };
+    sessions.bind_window(&label, &session_id)?;
+
+    let title = title.unwrap_or_else(|| "EzPlist - 精灵图管理工具".to_string());
+    let built = WebviewWindowBuilder::new(&app, &label, WebviewUrl::App("index.html".into()))
+        .title(title)
+        .inner_size(1280.0, 800.0)
+        .min_inner_size(1024.0, 700.0)
+        .build();
+    if let Err(e) = built {
+        sessions.release_window(&label);
+        return Err(format!("无法打开项目窗口: {}", e));
+    }
+
+    println!("✓ 打开项目窗口: {} (会话 {})", label, session_id);
+    Ok(ProjectWindow { label, session_id })
+}
+
+/// 获取当前窗口的会话命令
+///
+/// 窗口第一次调用时新建会话并绑定，之后始终返回同一会话
+///
+/// # Returns
+/// * `Result<Session, String>` - 当前窗口绑定的会话
+#[tauri::command]
+pub async fn get_window_session(window: WebviewWindow, sessions: State<'_, SessionStore>) -> Result<Session, String> {
+    catch_panic("get_window_session", get_window_session_impl(&sessions, window.label().to_string())).await
+}
+
+async fn get_window_session_impl(sessions: &SessionStore, label: String) -> Result<Session, String> {
+    Ok(sessions.window_session(&label))
+}
+
+/// 窗口销毁时释放它绑定的会话（由 `on_window_event` 调用）
+pub fn release_window_session(sessions: &SessionStore, label: &str) {
+    if let Some(session_id) = sessions.release_window(label) {
+        println!("✓ 窗口 {} 已关闭，释放会话: {}", label, session_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_sessions_are_isolated() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let sessions = SessionStore::default();
+        let main = rt.block_on(get_window_session_impl(&sessions, "main".to_string())).unwrap();
+        let again = rt.block_on(get_window_session_impl(&sessions, "main".to_string())).unwrap();
+        assert_eq!(main.id, again.id);
+
+        // 第二个窗口有自己的会话
+        let other = rt.block_on(get_window_session_impl(&sessions, "project-a".to_string())).unwrap();
+        assert_ne!(other.id, main.id);
+
+        // 共享会话的窗口关闭时，会话保留到最后一个窗口关闭
+        sessions.bind_window("project-b", &other.id).unwrap();
+        assert_eq!(sessions.release_window("project-a"), None);
+        assert!(sessions.with_session(&other.id, |_| Ok(())).is_ok());
+        assert_eq!(sessions.release_window("project-b"), Some(other.id.clone()));
+        assert!(sessions.with_session(&other.id, |_| Ok(())).is_err());
+
+        assert!(sessions.bind_window("project-c", "missing").is_err());
+        assert!(sessions.with_session(&main.id, |_| Ok(())).is_ok());
+    }
+}
@@ -1,39 +0,0 @@
-/// 图像处理模块
-/// 
-/// 包含：
-/// - 透明像素检测
-/// - 图像裁剪
-/// - 纹理图渲染
-
-use image::RgbaImage;
-
-/// 透明裁剪结果
-#[derive(Debug)]
-pub struct TrimResult {
-    /// 裁剪后的图像
-    pub trimmed_image: RgbaImage,
-    /// 偏移量 (x, y)
-    pub offset: (i32, i32),
-    /// 原始尺寸 (width, height)
-    pub original_size: (u32, u32),
-}
-
-/// 透明像素裁剪
-/// 
-/// TODO: Phase 3 实现
-pub fn trim_transparent(_img: &RgbaImage) -> Option<TrimResult> {
-    // 占位实现
-    None
-}
-
-/// 渲染纹理图
-/// 
-/// TODO: Phase 4 实现
-pub fn render_texture(
-    _sprites: &[crate::core::types::PackedSprite],
-    _width: u32,
-    _height: u32,
-) -> Result<RgbaImage, Box<dyn std::error::Error>> {
-    // 占位实现
-    Err("未实现".into())
-}
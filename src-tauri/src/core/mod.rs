@@ -1,12 +1,123 @@
 /// 核心业务逻辑模块
 /// 
+/// 算法实现位于独立的 `ezplist-core` crate，这里重新导出以保持
+/// `crate::core::*` 路径不变
+/// 
 /// 包含：
 /// - types: 共享类型定义
 /// - packer: MaxRects 打包算法
+/// - job: 打包任务构建器
 /// - image_processor: 图像处理
 /// - plist_generator: Plist 文件生成
+/// - strip: GameMaker 横向条带图生成
+/// - template_exporter: 模板驱动的自定义格式导出
+/// - limits: 外部输入大小限制
+/// - image_format: 图像格式识别
+/// - dds: 未压缩 DDS 解码
+/// - animation: 动画预览（帧时长、洋葱皮、GIF / APNG 预览）
+/// - outline: 不透明区域轮廓提取
+/// - physics: PhysicsEditor 物理形状导出
+/// - hit_area: 帧碰撞区域元数据
+/// - palette: 调色板提取与换色
+/// - effects: 投影与描边效果烘焙
+/// - upscale: 像素画放大
+/// - mipmap: mipmap 安全间距
+/// - frame_check: 帧尺寸约束检查
+/// - grid: 网格切分坐标
+/// - session: 多来源打包会话
+/// - sprite_filter: 精灵搜索与过滤
+/// - naming: 命名模板
+/// - png_meta: PNG 元数据（文本块、DPI）
+/// - hooks: 导出钩子
+/// - codegen: 帧索引代码生成
+/// - split_overlay: 切分预览叠加图
+/// - tiling: 大图集分页
+/// - atlas_plist: 已有图集 Plist 读取
+/// - atlas_patch: 锁定布局的就地修补
+/// - alpha_stats: 透明度统计与警告
+/// - bleed_check: 已有图集的帧渗色检查
+/// - atlas_unpack: 从已有图集还原单帧图像
+/// - frame_align: 动画帧统一画布与质心/基线对齐
+/// - exporters: 图集数据文件导出格式（plist、TexturePacker JSON）
+/// - anchor: 根据帧内容估算轴心点
+/// - video: 视频帧采样参数
+/// - preprocess: 图像预处理流水线
+/// - engine_profile: 引擎目标配置与检查
+/// - timings: 命令各阶段耗时统计
+/// - blobs: 连通区域帧检测（不规则图集切分）
+/// - plist_parse: Cocos 矩形/尺寸/坐标字符串解析
+/// - mirror: 翻转帧检测与别名
+/// - solid: 图集纯色区域（`__white`）注入
+/// - scale_variant: HD/SD 缩放版本导出
+/// - frame_groups: 按文件夹/标签分组导出多个数据文件
+/// - summary: 结果摘要与本地化数字格式
+/// - project: `.ezplist` 项目文件
+/// - notes: 帧备注与导出附属文件
+/// - folder_scan: 文件夹导入的筛选与帧命名
+/// - history: 导出历史记录与重新导出
+/// - texture_format: 纹理像素格式转换与调色板量化
+/// - texture_container: PVR / KTX 纹理容器
+/// - animated_image: GIF / APNG / WebP 动图的帧名称、时长与网格图集
+/// - sprite_transform: 合成时精灵的旋转、缩放与翻转
+/// - plist_lint: 已有图集 plist 与纹理的一致性校验
+/// - frame_names: 帧名称重名检测与自动改名
 
-pub mod types;
-pub mod packer;
-pub mod image_processor;
-pub mod plist_generator;
+pub use ezplist_core::{
+    types,
+    packer,
+    job,
+    image_processor,
+    plist_generator,
+    strip,
+    template_exporter,
+    limits,
+    image_format,
+    dds,
+    animation,
+    outline,
+    physics,
+    hit_area,
+    palette,
+    effects,
+    upscale,
+    mipmap,
+    frame_check,
+    grid,
+    session,
+    sprite_filter,
+    naming,
+    png_meta,
+    hooks,
+    codegen,
+    split_overlay,
+    tiling,
+    atlas_plist,
+    atlas_patch,
+    alpha_stats,
+    bleed_check,
+    atlas_unpack,
+    frame_align,
+    exporters,
+    anchor,
+    video,
+    preprocess,
+    engine_profile,
+    timings,
+    blobs,
+    plist_parse,
+    mirror,
+    solid,
+    scale_variant,
+    frame_groups,
+    summary,
+    project,
+    notes,
+    folder_scan,
+    history,
+    texture_format,
+    texture_container,
+    animated_image,
+    sprite_transform,
+    plist_lint,
+    frame_names,
+};
@@ -1,24 +0,0 @@
-/// Plist 生成器
-/// 
-/// 生成符合 Cocos2d-x Format 3 标准的 .plist 文件
-
-use crate::core::types::PackedSprite;
-
-/// 生成 Plist XML 内容
-/// 
-/// TODO: Phase 4 实现
-pub fn generate_plist(
-    _sprites: &[PackedSprite],
-    _texture_width: u32,
-    _texture_height: u32,
-    _texture_name: &str,
-) -> Result<String, Box<dyn std::error::Error>> {
-    // 占位实现
-    Ok(String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!DOCTYPE plist PUBLIC \"-//Apple Computer//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n<plist version=\"1.0\">\n</plist>"))
-}
-
-/// 计算 SmartUpdate 哈希
-fn _calculate_hash(_data: &[u8]) -> String {
-    // TODO: 使用 md-5 crate 计算 MD5
-    String::from("placeholder_hash")
-}
@@ -5,7 +5,9 @@
 /// - commands: Tauri 命令处理
 /// - core: 核心业务逻辑（打包算法、图像处理、Plist 生成）
 /// - utils: 工具函数（裁剪、哈希等）
+/// - api: 自动化接口（本地 HTTP 服务、JSON-RPC）
 
+pub mod api;
 pub mod commands;
 pub mod core;
 pub mod utils;
@@ -18,32 +20,124 @@ pub fn run() {
         // 注册插件
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
+        // 打包结果存储（导出按 job_id 取回裁剪结果）
+        .manage(commands::PackSessionStore::default())
+        // 打包会话存储（每个项目窗口绑定自己的会话）
+        .manage(commands::SessionStore::default())
+        // 导出历史（导出命令写入，随项目文件保存）
+        .manage(commands::ExportHistoryStore::default())
         // 注册命令
         .invoke_handler(tauri::generate_handler![
             commands::greet,
             commands::import_images,
+            commands::import_folder,
+            commands::import_image_buffer,
             commands::pack_sprites,
+            commands::pack_sprites_pages,
+            commands::release_pack_result,
+            commands::find_mirrored_sprites,
             commands::export_sprite_sheet,
+            commands::export_sprite_sheet_pages,
+            // 导出历史命令
+            commands::list_export_history,
+            commands::re_export,
             // 拆分图集命令
             commands::import_spritesheet,
             commands::calculate_split_frames,
+            commands::detect_blob_frames,
+            commands::render_split_overlay,
             commands::export_split_plist,
+            commands::export_tiled_sheet,
             // 多区域导出命令
             commands::export_multi_plist,
             commands::calculate_region_preview,
             // 合成图集命令
             commands::compose_sprites,
             commands::preview_compose_bounds,
+            commands::compose_auto_arrange,
+            // GameMaker 条带图导出命令
+            commands::export_gamemaker_strips,
+            commands::export_region_strips,
+            // 模板导出命令
+            commands::register_export_template,
+            commands::list_export_templates,
+            commands::export_with_template,
+            commands::export_frame_index,
+            // 动画预览命令
+            commands::build_animation_preview,
+            commands::render_onion_skin,
+            commands::export_animation_preview,
+            // 轮廓提取命令
+            commands::extract_outline,
+            commands::export_physics_plist,
+            // 碰撞区域命令
+            commands::set_hit_areas,
+            commands::get_hit_areas,
+            commands::export_hit_areas,
+            // 调色板命令
+            commands::extract_sprite_palette,
+            commands::generate_palette_variants,
+            // 打包会话命令
+            commands::create_session,
+            commands::get_session,
+            commands::close_session,
+            commands::add_session_images,
+            commands::add_session_frames,
+            commands::remove_session_sprites,
+            commands::search_session_sprites,
+            commands::update_sprites,
+            commands::transplant_sprites,
+            commands::set_session_preprocess,
+            commands::pack_session,
+            commands::check_stale_sources,
+            commands::reload_sprites,
+            commands::estimate_anchors,
+            // 项目文件命令
+            commands::save_project,
+            commands::load_project,
+            // 项目窗口命令
+            commands::open_project_window,
+            commands::get_window_session,
+            // 图集修补命令
+            commands::patch_atlas,
+            commands::check_atlas_bleed,
+            commands::validate_plist,
+            // 图集拆分命令
+            commands::import_plist,
+            commands::unpack_atlas,
+            commands::repack_atlas,
+            commands::clear_frame_cache,
+            // 视频导入命令
+            commands::import_video_frames,
+            // 引擎配置检查命令
+            commands::list_engine_profiles,
+            commands::validate_for_profile,
+            // 设置命令
+            commands::set_worker_threads,
+            commands::get_worker_threads,
+            commands::set_number_locale,
+            commands::get_number_locale,
+            commands::start_http_api,
+            commands::stop_http_api,
+            commands::get_http_api_info,
         ])
         // 设置初始化回调
         .setup(|app| {
             #[cfg(debug_assertions)]
             {
-                let window = app.get_webview_window("main").unwrap();
-                window.open_devtools();
+                if let Some(window) = app.get_webview_window("main") {
+                    window.open_devtools();
+                }
             }
             Ok(())
         })
+        // 窗口销毁时释放它绑定的会话
+        .on_window_event(|window, event| {
+            if let tauri::WindowEvent::Destroyed = event {
+                let sessions = window.state::<commands::SessionStore>();
+                commands::release_window_session(&sessions, window.label());
+            }
+        })
         // 运行应用
         .run(tauri::generate_context!())
         .expect("运行 Tauri 应用时出错");
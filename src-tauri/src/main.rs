@@ -2,5 +2,22 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    // 无界面的 JSON-RPC 模式，供构建脚本与自动化工具调用
+    if std::env::args().any(|arg| arg == "--rpc") {
+        if let Err(e) = ezplist::api::rpc::run_stdio() {
+            eprintln!("✗ {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+    // 命令行批处理模式，`--cli` 之后的参数为命令与选项
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(pos) = args.iter().position(|arg| arg == "--cli") {
+        if let Err(e) = ezplist::api::cli::run(&args[pos + 1..]) {
+            eprintln!("✗ {}", e);
+            std::process::exit(e.kind.exit_code());
+        }
+        return;
+    }
     ezplist::run()
 }
@@ -0,0 +1,66 @@
+/// 动图写入 (Animation Writer)
+///
+/// 把同尺寸的帧序列编码为循环播放的 GIF 或 APNG（见 `core::animation::PreviewFormat`）
+
+use crate::core::animation::PreviewFormat;
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame, RgbaImage};
+
+/// 编码动图
+///
+/// # Arguments
+/// * `frames` - 按播放顺序排列、尺寸相同的帧（见 `core::animation::pad_frames`）
+/// * `durations_ms` - 各帧时长（毫秒）
+/// * `format` - 文件格式
+///
+/// # Returns
+/// * `Result<Vec<u8>, String>` - 文件数据或错误信息
+pub fn encode_animation(frames: &[RgbaImage], durations_ms: &[u32], format: PreviewFormat) -> Result<Vec<u8>, String> {
+    if frames.is_empty() || frames.len() != durations_ms.len() {
+        return Err("帧数与帧时长数量不一致".to_string());
+    }
+    match format {
+        PreviewFormat::Gif => encode_gif(frames, durations_ms),
+        PreviewFormat::Apng => encode_apng(frames, durations_ms),
+    }
+}
+
+fn encode_gif(frames: &[RgbaImage], durations_ms: &[u32]) -> Result<Vec<u8>, String> {
+    let mut data = Vec::new();
+    {
+        let mut encoder = GifEncoder::new_with_speed(&mut data, 10);
+        encoder
+            .set_repeat(Repeat::Infinite)
+            .map_err(|e| format!("GIF 编码失败: {}", e))?;
+        encoder
+            .encode_frames(frames.iter().zip(durations_ms).map(|(frame, &ms)| {
+                Frame::from_parts(frame.clone(), 0, 0, Delay::from_numer_denom_ms(ms, 1))
+            }))
+            .map_err(|e| format!("GIF 编码失败: {}", e))?;
+    }
+    Ok(data)
+}
+
+fn encode_apng(frames: &[RgbaImage], durations_ms: &[u32]) -> Result<Vec<u8>, String> {
+    let error = |e: png::EncodingError| format!("APNG 编码失败: {}", e);
+    let (width, height) = frames[0].dimensions();
+    let mut data = Vec::new();
+    let mut encoder = png::Encoder::new(&mut data, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    // 播放次数 0 表示无限循环
+    encoder.set_animated(frames.len() as u32, 0).map_err(error)?;
+    let mut writer = encoder.write_header().map_err(error)?;
+    for (frame, &ms) in frames.iter().zip(durations_ms) {
+        // fcTL 的时长为 u16 分数，超长的帧以 1/100 秒为单位写入
+        let (numer, denom) = if ms <= u16::MAX as u32 {
+            (ms as u16, 1000)
+        } else {
+            ((ms / 10).min(u16::MAX as u32) as u16, 100)
+        };
+        writer.set_frame_delay(numer, denom).map_err(error)?;
+        writer.write_image_data(frame.as_raw()).map_err(error)?;
+    }
+    writer.finish().map_err(error)?;
+    Ok(data)
+}
@@ -0,0 +1,181 @@
+/// 图集帧磁盘缓存 (Atlas Frame Cache)
+///
+/// 以「纹理文件哈希 + 帧区域」为键把还原出的帧保存为 PNG，
+/// 预览、重新导出等操作再次读取同一帧时直接读取缓存，不必重新解码整张大纹理
+
+use crate::core::atlas_unpack::extract_frame;
+use crate::core::types::PackedSprite;
+use crate::utils::hash::calculate_md5;
+use crate::utils::image_loader::{load_image, ImageLoadLimits};
+use crate::utils::png_writer::encode_png;
+use image::RgbaImage;
+use std::path::{Path, PathBuf};
+
+/// 默认缓存目录
+pub fn default_cache_dir() -> PathBuf {
+    std::env::temp_dir().join("ezplist_frame_cache")
+}
+
+/// 一张图集纹理的帧缓存（纹理只在缓存未命中时才解码）
+pub struct CachedAtlas {
+    texture_path: String,
+    dir: PathBuf,
+    texture: Option<RgbaImage>,
+}
+
+impl CachedAtlas {
+    /// 打开图集纹理
+    ///
+    /// # Arguments
+    /// * `cache_root` - 缓存根目录
+    /// * `texture_path` - 图集纹理路径
+    ///
+    /// # Returns
+    /// * `Result<CachedAtlas, String>` - 读取纹理文件失败时返回错误
+    pub fn open(cache_root: &Path, texture_path: &str) -> Result<Self, String> {
+        let bytes = std::fs::read(texture_path).map_err(|e| format!("无法读取图集纹理 {}: {}", texture_path, e))?;
+        Ok(Self {
+            texture_path: texture_path.to_string(),
+            dir: cache_root.join(calculate_md5(&bytes)),
+            texture: None,
+        })
+    }
+
+    /// 已解码的纹理（未命中缓存时才会加载）
+    pub fn texture(&mut self) -> Result<&RgbaImage, String> {
+        if self.texture.is_none() {
+            let texture = load_image(&self.texture_path, &ImageLoadLimits::default())?.to_rgba8();
+            self.texture = Some(texture);
+        }
+        Ok(self.texture.as_ref().expect("纹理已加载"))
+    }
+
+    /// 读取一帧的原始图像，优先使用缓存
+    ///
+    /// # Arguments
+    /// * `frame` - 帧（宽高为在纹理中占用的尺寸）
+    ///
+    /// # Returns
+    /// * `Result<RgbaImage, String>` - 原始尺寸的帧图像
+    pub fn frame(&mut self, frame: &PackedSprite) -> Result<RgbaImage, String> {
+        let path = self.dir.join(frame_key(frame));
+        // 缓存文件损坏时视为未命中
+        if let Ok(cached) = image::open(&path) {
+            return Ok(cached.to_rgba8());
+        }
+
+        let image = extract_frame(self.texture()?, frame)?;
+        if let Err(e) = write_atomic(&path, &image) {
+            println!("警告: 写入帧缓存失败 {}: {}", path.display(), e);
+        }
+        Ok(image)
+    }
+}
+
+/// 帧区域对应的缓存文件名（包含还原帧所需的全部字段）
+fn frame_key(frame: &PackedSprite) -> String {
+    format!(
+        "{}_{}_{}x{}{}_{}x{}_{}_{}.png",
+        frame.x,
+        frame.y,
+        frame.width,
+        frame.height,
+        if frame.rotated { "r" } else { "" },
+        frame.original_width,
+        frame.original_height,
+        frame.offset_x,
+        frame.offset_y
+    )
+}
+
+/// 先写入临时文件再重命名，避免并发读取到写了一半的缓存
+fn write_atomic(path: &Path, image: &RgbaImage) -> Result<(), String> {
+    let dir = path.parent().ok_or("缓存路径无效")?;
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let temp = dir.join(format!(".{}.tmp", uuid::Uuid::new_v4()));
+    std::fs::write(&temp, encode_png(image)?).map_err(|e| e.to_string())?;
+    std::fs::rename(&temp, path).map_err(|e| {
+        let _ = std::fs::remove_file(&temp);
+        e.to_string()
+    })
+}
+
+/// 清空缓存目录
+///
+/// # Returns
+/// * `Result<u64, String>` - 释放的字节数
+pub fn clear_cache(cache_root: &Path) -> Result<u64, String> {
+    if !cache_root.exists() {
+        return Ok(0);
+    }
+    let freed = dir_size(cache_root);
+    std::fs::remove_dir_all(cache_root).map_err(|e| format!("无法清空帧缓存: {}", e))?;
+    Ok(freed)
+}
+
+fn dir_size(dir: &Path) -> u64 {
+    std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(meta) if meta.is_dir() => dir_size(&entry.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn test_frame_cache_hit_skips_texture() {
+        let dir = std::env::temp_dir().join(format!("ezplist_frame_cache_test_{}", std::process::id()));
+        let cache_root = dir.join("cache");
+        std::fs::create_dir_all(&dir).unwrap();
+        let texture_path = dir.join("atlas.png");
+        let mut texture = RgbaImage::new(8, 4);
+        texture.put_pixel(5, 1, Rgba([255, 0, 0, 255]));
+        texture.save(&texture_path).unwrap();
+        let texture_path = texture_path.to_string_lossy().to_string();
+
+        let frame = PackedSprite {
+            id: "a".to_string(),
+            name: "a.png".to_string(),
+            x: 4,
+            y: 0,
+            width: 4,
+            height: 4,
+            rotated: false,
+            original_width: 4,
+            original_height: 4,
+            trimmed: false,
+            offset_x: 0,
+            offset_y: 0,
+            flip_x: false,
+            flip_y: false,
+            anchor: None,
+            nine_slice: None,
+        };
+        let mut atlas = CachedAtlas::open(&cache_root, &texture_path).unwrap();
+        let first = atlas.frame(&frame).unwrap();
+        assert_eq!(first.get_pixel(1, 1).0, [255, 0, 0, 255]);
+
+        // 第二次读取命中缓存，不解码纹理
+        let mut atlas = CachedAtlas::open(&cache_root, &texture_path).unwrap();
+        assert_eq!(atlas.frame(&frame).unwrap(), first);
+        assert!(atlas.texture.is_none());
+
+        // 纹理内容变化后哈希不同，不会读到旧缓存
+        RgbaImage::new(8, 4).save(&texture_path).unwrap();
+        let mut atlas = CachedAtlas::open(&cache_root, &texture_path).unwrap();
+        assert_eq!(atlas.frame(&frame).unwrap().get_pixel(1, 1)[3], 0);
+
+        assert!(clear_cache(&cache_root).unwrap() > 0);
+        assert!(!cache_root.exists());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,130 @@
+/// 命令 panic 防护 (Command Panic Guard)
+///
+/// 异步命令中的 panic 会终止任务，前端的 invoke 永远不会返回。
+/// 所有命令通过 `catch_panic` 执行，panic 会转换为普通的错误结果。
+/// 图像解码/编码与文件读写等耗时的命令改用 `run_blocking`，在阻塞线程池中执行同步的命令主体，
+/// 不占用异步运行时的线程，其他命令在大批量导出期间仍能及时响应；panic 同样转换为错误结果
+
+use crate::utils::workers;
+use std::any::Any;
+use std::future::Future;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// 执行命令并捕获 panic
+///
+/// # Arguments
+/// * `command` - 命令名称（用于日志和错误信息）
+/// * `future` - 命令主体
+///
+/// # Returns
+/// * `Result<T, String>` - 命令结果；发生 panic 时返回错误信息
+pub async fn catch_panic<T, F>(command: &str, future: F) -> Result<T, String>
+where
+    F: Future<Output = Result<T, String>>,
+{
+    match (CatchUnwind { inner: Box::pin(future) }).await {
+        Ok(result) => result,
+        Err(payload) => {
+            let message = panic_message(payload.as_ref());
+            println!("✗ 命令 {} 发生 panic: {}", command, message);
+            Err(format!("命令 {} 执行时发生内部错误: {}", command, message))
+        }
+    }
+}
+
+/// 在阻塞线程池中执行同步任务
+///
+/// 任务在工作线程池中运行（见 `workers`），其中的并行迭代受线程数设置限制
+///
+/// # Arguments
+/// * `command` - 命令名称（用于日志和错误信息）
+/// * `task` - 命令主体（同步函数）
+///
+/// # Returns
+/// * `Result<T, String>` - 任务结果；发生 panic 或线程池异常时返回错误信息
+pub async fn run_blocking<T, F>(command: &str, task: F) -> Result<T, String>
+where
+    F: FnOnce() -> Result<T, String> + Send + 'static,
+    T: Send + 'static,
+{
+    let blocking = move || workers::install(|| catch_unwind(AssertUnwindSafe(task)));
+    match tauri::async_runtime::spawn_blocking(blocking).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(payload)) => {
+            let message = panic_message(payload.as_ref());
+            println!("✗ 命令 {} 发生 panic: {}", command, message);
+            Err(format!("命令 {} 执行时发生内部错误: {}", command, message))
+        }
+        Err(e) => Err(format!("命令 {} 的后台任务执行失败: {}", command, e)),
+    }
+}
+
+/// 提取 panic 信息
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "未知错误".to_string()
+    }
+}
+
+/// 在每次 poll 时捕获 panic 的 Future 包装
+struct CatchUnwind<F> {
+    inner: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for CatchUnwind<F> {
+    type Output = Result<F::Output, Box<dyn Any + Send>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let inner = self.inner.as_mut();
+        match catch_unwind(AssertUnwindSafe(|| inner.poll(cx))) {
+            Ok(Poll::Pending) => Poll::Pending,
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Err(payload) => Poll::Ready(Err(payload)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block_on<T>(future: impl Future<Output = T>) -> T {
+        tokio::runtime::Runtime::new().unwrap().block_on(future)
+    }
+
+    #[test]
+    fn test_passes_through_results() {
+        assert_eq!(block_on(catch_panic("ok", async { Ok::<_, String>(1) })), Ok(1));
+        assert_eq!(
+            block_on(catch_panic("err", async { Err::<i32, _>("失败".to_string()) })),
+            Err("失败".to_string())
+        );
+    }
+
+    #[test]
+    fn test_converts_panic_to_error() {
+        let result = block_on(catch_panic("boom", async {
+            let values: Vec<u32> = Vec::new();
+            Ok::<_, String>(values[1])
+        }));
+
+        let err = result.unwrap_err();
+        assert!(err.contains("boom"));
+        assert!(err.contains("index out of bounds"));
+    }
+
+    #[test]
+    fn test_run_blocking() {
+        assert_eq!(block_on(run_blocking("ok", || Ok::<_, String>(2))), Ok(2));
+
+        let err = block_on(run_blocking("boom", || -> Result<(), String> { panic!("解码失败") })).unwrap_err();
+        assert!(err.contains("boom"));
+        assert!(err.contains("解码失败"));
+    }
+}
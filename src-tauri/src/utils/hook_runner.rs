@@ -0,0 +1,177 @@
+/// 导出钩子执行 (Export Hook Runner)
+///
+/// 启动 `core::hooks` 中配置的外部命令，捕获输出并在超时后终止进程
+
+use crate::core::hooks::{render_hook_arg, ExportHook, HookContext, HookOutput, HookStage};
+use std::io::Read;
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// 每个输出流保留的最大字节数（超出部分截断，避免大量日志占满界面）
+const MAX_CAPTURED_OUTPUT: usize = 64 * 1024;
+
+/// 检查进程是否结束的间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// 执行某一阶段的所有钩子
+///
+/// 按配置顺序依次执行，某个钩子失败且未设置 `continue_on_error` 时中止，
+/// 返回的错误信息包含该钩子的输出
+///
+/// # Arguments
+/// * `hooks` - 钩子配置
+/// * `stage` - 要执行的阶段
+/// * `context` - 参数模板变量
+///
+/// # Returns
+/// * `Result<Vec<HookOutput>, String>` - 已执行钩子的结果
+pub fn run_hooks(hooks: &[ExportHook], stage: HookStage, context: &HookContext) -> Result<Vec<HookOutput>, String> {
+    let mut outputs = Vec::new();
+    for hook in hooks.iter().filter(|h| h.stage == stage) {
+        let output = run_hook(hook, context)?;
+        if output.success {
+            println!("✓ 钩子执行成功: {}", output.command_line);
+        } else {
+            println!("✗ 钩子执行失败: {}", output.command_line);
+            if !hook.continue_on_error {
+                return Err(format!(
+                    "钩子执行失败: {}（退出码 {:?}）\n{}",
+                    output.command_line, output.exit_code, output.stderr
+                ));
+            }
+        }
+        outputs.push(output);
+    }
+    Ok(outputs)
+}
+
+/// 执行单个钩子
+fn run_hook(hook: &ExportHook, context: &HookContext) -> Result<HookOutput, String> {
+    let args = hook
+        .args
+        .iter()
+        .map(|arg| render_hook_arg(arg, context))
+        .collect::<Result<Vec<_>, String>>()?;
+    let command_line = std::iter::once(hook.command.as_str())
+        .chain(args.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let working_dir = hook.working_dir.as_deref().unwrap_or(&context.dir);
+    let mut child = Command::new(&hook.command)
+        .args(&args)
+        .current_dir(working_dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("无法启动钩子 {}: {}", hook.command, e))?;
+
+    // 两个输出流分别在线程中读取，避免管道写满导致子进程阻塞
+    let stdout = child.stdout.take().map(read_in_background);
+    let stderr = child.stderr.take().map(read_in_background);
+    let exit_code = wait_with_timeout(&mut child, Duration::from_secs(hook.timeout()));
+
+    let collect = |handle: Option<std::thread::JoinHandle<String>>| {
+        handle.and_then(|h| h.join().ok()).unwrap_or_default()
+    };
+    let stdout = collect(stdout);
+    let mut stderr = collect(stderr);
+    let exit_code = match exit_code {
+        Some(code) => code,
+        None => {
+            stderr.push_str(&format!("\n钩子超时（{} 秒），已终止", hook.timeout()));
+            None
+        }
+    };
+
+    Ok(HookOutput {
+        command_line,
+        stage: hook.stage,
+        success: exit_code == Some(0),
+        exit_code,
+        stdout,
+        stderr,
+    })
+}
+
+/// 等待进程结束
+///
+/// # Returns
+/// * `Option<Option<i32>>` - 超时返回 None；否则为退出码（被信号终止时为 None）
+pub(crate) fn wait_with_timeout(child: &mut Child, timeout: Duration) -> Option<Option<i32>> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return Some(status.code()),
+            Ok(None) if Instant::now() < deadline => std::thread::sleep(POLL_INTERVAL),
+            Ok(None) | Err(_) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    }
+}
+
+/// 在后台线程读取输出流（最多保留 `MAX_CAPTURED_OUTPUT` 字节）
+pub(crate) fn read_in_background(mut stream: impl Read + Send + 'static) -> std::thread::JoinHandle<String> {
+    std::thread::spawn(move || {
+        let mut data = Vec::new();
+        let _ = stream.read_to_end(&mut data);
+        data.truncate(MAX_CAPTURED_OUTPUT);
+        String::from_utf8_lossy(&data).into_owned()
+    })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn hook(command: &str, args: &[&str]) -> ExportHook {
+        ExportHook {
+            command: command.to_string(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            stage: HookStage::Post,
+            working_dir: None,
+            timeout_secs: Some(5),
+            continue_on_error: false,
+        }
+    }
+
+    fn context() -> HookContext {
+        HookContext {
+            png: "hero.png".to_string(),
+            plist: "hero.plist".to_string(),
+            dir: std::env::temp_dir().to_string_lossy().to_string(),
+            name: "hero".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_run_hooks_captures_output() {
+        let hooks = vec![hook("echo", &["packed", "{png}"])];
+        let outputs = run_hooks(&hooks, HookStage::Post, &context()).unwrap();
+        assert_eq!(outputs.len(), 1);
+        assert!(outputs[0].success);
+        assert_eq!(outputs[0].stdout.trim(), "packed hero.png");
+
+        // 其他阶段的钩子不执行
+        assert!(run_hooks(&hooks, HookStage::Pre, &context()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_failed_hook() {
+        let failing = hook("sh", &["-c", "echo oops >&2; exit 3"]);
+        let err = run_hooks(std::slice::from_ref(&failing), HookStage::Post, &context()).unwrap_err();
+        assert!(err.contains("oops"));
+
+        let tolerated = ExportHook {
+            continue_on_error: true,
+            ..failing
+        };
+        let outputs = run_hooks(&[tolerated], HookStage::Post, &context()).unwrap();
+        assert_eq!(outputs[0].exit_code, Some(3));
+        assert!(!outputs[0].success);
+    }
+}
@@ -0,0 +1,342 @@
+/// 图像加载 (Image Loading)
+///
+/// 从用户文件解码图像的统一入口：先根据文件头魔数识别格式并检查允许列表，
+/// 再读取头部中的尺寸检查像素数，最后才完整解码。
+/// BMP/TGA/DDS 等旧格式解码后统一转换为 RGBA8 并修正未使用的 alpha 通道。
+/// GIF、APNG 与动画 WebP 可用 `load_animation` 解码所有帧
+
+use crate::core::animated_image::{frame_delay_ms, is_animated_png, is_animated_webp, MAX_ANIMATION_FRAMES};
+use crate::core::dds::{decode_uncompressed, DdsHeader, DdsLayout, DDS_HEADER_LEN};
+use crate::core::image_format::{check_format, normalize_legacy_alpha, SourceFormat, SNIFF_LEN};
+use crate::core::limits::{check_image_pixels, MAX_IMAGE_PIXELS};
+use crate::core::types::FrameInfo;
+use image::codecs::gif::GifDecoder;
+use image::codecs::png::PngDecoder;
+use image::codecs::webp::WebPDecoder;
+use image::{AnimationDecoder, DynamicImage, Frames, ImageDecoder, ImageFormat, ImageReader, RgbaImage};
+use std::fs::File;
+use std::io::{BufReader, Cursor, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// DX10 扩展头在内的 DDS 文件头最大长度
+const DDS_MAX_HEADER_LEN: usize = DDS_HEADER_LEN + 20;
+
+/// 识别 APNG 时读取的文件头长度（`acTL` 块位于第一个 `IDAT` 之前）
+const ANIMATION_SNIFF_LEN: usize = 64 * 1024;
+
+/// 图像加载限制
+#[derive(Debug, Clone)]
+pub struct ImageLoadLimits {
+    /// 允许解码的格式
+    pub allowed_formats: Vec<SourceFormat>,
+    /// 允许的最大像素数
+    pub max_pixels: u64,
+}
+
+impl Default for ImageLoadLimits {
+    fn default() -> Self {
+        Self {
+            allowed_formats: SourceFormat::ALL.to_vec(),
+            max_pixels: MAX_IMAGE_PIXELS,
+        }
+    }
+}
+
+/// 加载图像
+///
+/// # Arguments
+/// * `path` - 图像文件路径
+/// * `limits` - 格式与尺寸限制
+///
+/// # Returns
+/// * `Result<DynamicImage, String>` - 解码后的图像或错误信息
+pub fn load_image(path: &str, limits: &ImageLoadLimits) -> Result<DynamicImage, String> {
+    let file = File::open(path)
+        .map_err(|e| format!("无法打开图像 {}: {}", path, e))?;
+    decode_source(file, path, limits)
+}
+
+/// 从内存数据加载图像
+///
+/// 与 `load_image` 使用相同的格式识别与尺寸检查，用于前端直接传来的图像数据
+///
+/// # Arguments
+/// * `bytes` - 编码后的图像数据（PNG、JPEG 等）
+/// * `name` - 图像名称（用于扩展名提示与错误信息）
+/// * `limits` - 格式与尺寸限制
+///
+/// # Returns
+/// * `Result<DynamicImage, String>` - 解码后的图像或错误信息
+pub fn load_image_bytes(bytes: &[u8], name: &str, limits: &ImageLoadLimits) -> Result<DynamicImage, String> {
+    decode_source(Cursor::new(bytes), name, limits)
+}
+
+/// 识别格式、检查尺寸并解码（`path` 仅用于扩展名提示与错误信息）
+fn decode_source<R: Read + Seek>(mut source: R, path: &str, limits: &ImageLoadLimits) -> Result<DynamicImage, String> {
+    // 1. 根据魔数识别格式，不信任扩展名
+    let header = read_head(&mut source, SNIFF_LEN, path)?;
+    let extension = Path::new(path).extension().and_then(|ext| ext.to_str());
+    let format = check_format(&header, extension, &limits.allowed_formats)
+        .map_err(|e| format!("{}: {}", e, path))?;
+    rewind(&mut source, path)?;
+
+    let img = if format == SourceFormat::Dds {
+        load_dds(source, path, limits)?
+    } else {
+        // 2. 只读取头部获取尺寸，在分配像素内存前检查
+        let (width, height) = ImageReader::with_format(BufReader::new(&mut source), format.image_format())
+            .into_dimensions()
+            .map_err(|e| format!("无法读取图像尺寸 {}: {}", path, e))?;
+        check_image_pixels(width, height, limits.max_pixels)
+            .map_err(|e| format!("{}: {}", e, path))?;
+
+        // 3. 完整解码
+        rewind(&mut source, path)?;
+        ImageReader::with_format(BufReader::new(source), format.image_format())
+            .decode()
+            .map_err(|e| format!("无法解码图像 {}: {}", path, e))?
+    };
+
+    if !format.has_unreliable_alpha() {
+        return Ok(img);
+    }
+
+    let mut rgba = img.to_rgba8();
+    if normalize_legacy_alpha(&mut rgba) {
+        println!("  {} 的 alpha 通道全为 0，按不透明图像处理", path);
+    }
+    Ok(DynamicImage::ImageRgba8(rgba))
+}
+
+/// 动图的一帧
+#[derive(Debug, Clone)]
+pub struct AnimatedFrame {
+    /// 合成后的整帧图像（画布尺寸）
+    pub image: RgbaImage,
+    /// 播放时长（毫秒）
+    pub delay_ms: u32,
+}
+
+/// 加载动图的所有帧
+///
+/// 支持 GIF、APNG 与动画 WebP；所有帧合计的像素数不得超过 `limits.max_pixels`
+///
+/// # Arguments
+/// * `path` - 图像文件路径
+/// * `limits` - 格式与尺寸限制
+///
+/// # Returns
+/// * `Result<Option<Vec<AnimatedFrame>>, String>` - 按播放顺序排列的帧；
+///   不是动图、只有一帧或格式不被允许时返回 None（交给 `load_image` 处理），解码失败时返回错误
+pub fn load_animation(path: &str, limits: &ImageLoadLimits) -> Result<Option<Vec<AnimatedFrame>>, String> {
+    let mut file = File::open(path).map_err(|e| format!("无法打开图像 {}: {}", path, e))?;
+    let header = read_head(&mut file, ANIMATION_SNIFF_LEN, path)?;
+    let extension = Path::new(path).extension().and_then(|ext| ext.to_str());
+    let Ok(format) = check_format(&header, extension, &limits.allowed_formats) else {
+        return Ok(None);
+    };
+    let animated = match format {
+        SourceFormat::Gif => true,
+        SourceFormat::Png => is_animated_png(&header),
+        SourceFormat::Webp => is_animated_webp(&header),
+        _ => false,
+    };
+    if !animated {
+        return Ok(None);
+    }
+
+    rewind(&mut file, path)?;
+    let reader = BufReader::new(file);
+    let error = |e: image::ImageError| format!("无法解码动图 {}: {}", path, e);
+    let frames = match format {
+        SourceFormat::Gif => {
+            let decoder = GifDecoder::new(reader).map_err(error)?;
+            check_canvas(decoder.dimensions(), path, limits)?;
+            collect_frames(decoder.into_frames(), path, limits)?
+        }
+        SourceFormat::Png => {
+            let decoder = PngDecoder::new(reader).map_err(error)?;
+            check_canvas(decoder.dimensions(), path, limits)?;
+            collect_frames(decoder.apng().map_err(error)?.into_frames(), path, limits)?
+        }
+        _ => {
+            let decoder = WebPDecoder::new(reader).map_err(error)?;
+            check_canvas(decoder.dimensions(), path, limits)?;
+            collect_frames(decoder.into_frames(), path, limits)?
+        }
+    };
+    Ok((frames.len() > 1).then_some(frames))
+}
+
+/// 检查动图画布的像素数
+fn check_canvas((width, height): (u32, u32), path: &str, limits: &ImageLoadLimits) -> Result<(), String> {
+    check_image_pixels(width, height, limits.max_pixels).map_err(|e| format!("{}: {}", e, path))
+}
+
+/// 逐帧解码，帧数与合计像素数超出上限时返回错误
+fn collect_frames(frames: Frames, path: &str, limits: &ImageLoadLimits) -> Result<Vec<AnimatedFrame>, String> {
+    let mut out = Vec::new();
+    let mut total_pixels = 0u64;
+    for frame in frames {
+        let frame = frame.map_err(|e| format!("无法解码动图 {}: {}", path, e))?;
+        if out.len() == MAX_ANIMATION_FRAMES {
+            return Err(format!("动图帧数超出上限 {}: {}", MAX_ANIMATION_FRAMES, path));
+        }
+        let (numer, denom) = frame.delay().numer_denom_ms();
+        let image = frame.into_buffer();
+        total_pixels += image.width() as u64 * image.height() as u64;
+        if total_pixels > limits.max_pixels {
+            return Err(format!("动图所有帧合计像素数超出上限 {}: {}", limits.max_pixels, path));
+        }
+        out.push(AnimatedFrame { image, delay_ms: frame_delay_ms(numer, denom) });
+    }
+    Ok(out)
+}
+
+/// 从图集中裁剪一帧
+///
+/// # Arguments
+/// * `source` - 图集图像
+/// * `frame` - 帧信息
+///
+/// # Returns
+/// * `Result<RgbaImage, String>` - 帧图像；帧为空或超出图集范围时返回错误
+pub fn crop_frame(source: &DynamicImage, frame: &FrameInfo) -> Result<RgbaImage, String> {
+    let (width, height) = (source.width(), source.height());
+    let inside = frame.width > 0
+        && frame.height > 0
+        && frame.x.checked_add(frame.width).is_some_and(|right| right <= width)
+        && frame.y.checked_add(frame.height).is_some_and(|bottom| bottom <= height);
+    if !inside {
+        return Err(format!("帧 {} 超出图集范围 {}x{}", frame.name, width, height));
+    }
+    Ok(source.crop_imm(frame.x, frame.y, frame.width, frame.height).to_rgba8())
+}
+
+/// 加载 DDS：未压缩数据自行解码，DXT 压缩数据交给 image crate
+fn load_dds<R: Read + Seek>(mut file: R, path: &str, limits: &ImageLoadLimits) -> Result<DynamicImage, String> {
+    let head = read_head(&mut file, DDS_MAX_HEADER_LEN, path)?;
+    let header = DdsHeader::parse(&head).map_err(|e| format!("{}: {}", e, path))?;
+    check_image_pixels(header.width, header.height, limits.max_pixels)
+        .map_err(|e| format!("{}: {}", e, path))?;
+    rewind(&mut file, path)?;
+
+    match header.layout {
+        DdsLayout::Compressed => ImageReader::with_format(BufReader::new(file), ImageFormat::Dds)
+            .decode()
+            .map_err(|e| format!("无法解码图像 {}: {}", path, e)),
+        DdsLayout::Masked { .. } => {
+            // 像素数已检查，最多读取 4 字节/像素的数据
+            let max_len = header.data_offset as u64 + header.width as u64 * header.height as u64 * 4;
+            let data = read_head(&mut file, max_len as usize, path)?;
+            decode_uncompressed(&data)
+                .map(DynamicImage::ImageRgba8)
+                .map_err(|e| format!("无法解码图像 {}: {}", path, e))
+        }
+    }
+}
+
+/// 读取数据开头最多 `len` 字节
+fn read_head<R: Read>(file: &mut R, len: usize, path: &str) -> Result<Vec<u8>, String> {
+    let mut head = Vec::new();
+    file.take(len as u64)
+        .read_to_end(&mut head)
+        .map_err(|e| format!("无法读取图像 {}: {}", path, e))?;
+    Ok(head)
+}
+
+fn rewind<R: Seek>(file: &mut R, path: &str) -> Result<(), String> {
+    file.seek(SeekFrom::Start(0))
+        .map(|_| ())
+        .map_err(|e| format!("无法读取图像 {}: {}", path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::RgbaImage;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("ezplist_loader_{}_{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn test_load_ignores_extension() {
+        // PNG 内容但扩展名为 .jpg
+        let path = temp_path("sprite.jpg");
+        RgbaImage::new(4, 3)
+            .save_with_format(&path, image::ImageFormat::Png)
+            .unwrap();
+
+        let img = load_image(path.to_str().unwrap(), &ImageLoadLimits::default()).unwrap();
+        assert_eq!((img.width(), img.height()), (4, 3));
+
+        let png_only = ImageLoadLimits {
+            allowed_formats: vec![SourceFormat::Jpeg],
+            ..Default::default()
+        };
+        assert!(load_image(path.to_str().unwrap(), &png_only).is_err());
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_rejects_oversized_and_unknown() {
+        let path = temp_path("big.png");
+        RgbaImage::new(64, 64).save(&path).unwrap();
+        let small = ImageLoadLimits {
+            max_pixels: 1000,
+            ..Default::default()
+        };
+        let err = load_image(path.to_str().unwrap(), &small).unwrap_err();
+        assert!(err.contains("超出上限"));
+        std::fs::remove_file(&path).ok();
+
+        let text = temp_path("notes.png");
+        std::fs::write(&text, "not an image").unwrap();
+        assert!(load_image(text.to_str().unwrap(), &ImageLoadLimits::default()).is_err());
+        std::fs::remove_file(&text).ok();
+    }
+
+    #[test]
+    fn test_crop_frame_bounds() {
+        let frame = |x: u32, width: u32| FrameInfo {
+            name: "walk_01.png".to_string(),
+            x,
+            y: 0,
+            width,
+            height: 32,
+            row: 0,
+            col: 0,
+            anchor: None,
+        };
+        let sheet = DynamicImage::ImageRgba8(RgbaImage::new(64, 32));
+
+        assert_eq!(crop_frame(&sheet, &frame(32, 32)).unwrap().dimensions(), (32, 32));
+        assert!(crop_frame(&sheet, &frame(40, 32)).is_err());
+        assert!(crop_frame(&sheet, &frame(0, 0)).is_err());
+        assert!(crop_frame(&sheet, &frame(u32::MAX, 2)).is_err());
+    }
+
+    #[test]
+    fn test_load_tga_with_empty_alpha() {
+        // 32 位未压缩 TGA，alpha 全为 0
+        let mut data = vec![0u8; 18];
+        data[2] = 2;
+        data[12] = 2;
+        data[14] = 1;
+        data[16] = 32;
+        data[17] = 8;
+        data.extend_from_slice(&[0x30, 0x20, 0x10, 0x00, 0x30, 0x20, 0x10, 0x00]);
+        let path = temp_path("legacy.tga");
+        std::fs::write(&path, &data).unwrap();
+
+        let img = load_image(path.to_str().unwrap(), &ImageLoadLimits::default())
+            .unwrap()
+            .to_rgba8();
+        assert_eq!(img.dimensions(), (2, 1));
+        assert_eq!(img.get_pixel(0, 0).0, [0x10, 0x20, 0x30, 0xFF]);
+
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,86 @@
+/// 任务锁 (Job Locks)
+///
+/// 导出会向输出目录写入多个文件，
+/// 同一资源上的两个并发调用会互相覆盖或读到一半的结果。
+/// 命令开始前获取所需资源的锁，资源被占用时立即返回以 `BUSY_PREFIX` 开头的错误，
+/// 不排队等待，由前端决定是否稍后重试
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::{LazyLock, Mutex};
+
+/// 忙碌错误的前缀，前端据此区分“稍后重试”与普通失败
+pub const BUSY_PREFIX: &str = "忙碌:";
+
+/// 当前被占用的资源
+static ACTIVE_JOBS: LazyLock<Mutex<HashSet<String>>> = LazyLock::new(|| Mutex::new(HashSet::new()));
+
+/// 已获取的锁，离开作用域时释放（命令返回或 panic 时都会释放）
+#[derive(Debug)]
+pub struct JobGuard {
+    keys: Vec<String>,
+}
+
+impl Drop for JobGuard {
+    fn drop(&mut self) {
+        let mut active = ACTIVE_JOBS.lock().unwrap_or_else(|e| e.into_inner());
+        for key in &self.keys {
+            active.remove(key);
+        }
+    }
+}
+
+/// 输出目录对应的资源名
+///
+/// 转换为绝对路径，避免同一目录的不同写法获取到不同的锁
+pub fn output_dir(dir: impl AsRef<Path>) -> String {
+    let dir = dir.as_ref();
+    let dir = std::fs::canonicalize(dir)
+        .or_else(|_| std::path::absolute(dir))
+        .unwrap_or_else(|_| dir.to_path_buf());
+    format!("output:{}", dir.display())
+}
+
+/// 获取一组资源的锁
+///
+/// 全部资源空闲时才会获取，任何一个被占用时不占用任何资源
+///
+/// # Arguments
+/// * `command` - 命令名称（用于错误信息）
+/// * `keys` - 资源名
+///
+/// # Returns
+/// * `Result<JobGuard, String>` - 锁；资源被占用时返回忙碌错误
+pub fn try_lock<I>(command: &str, keys: I) -> Result<JobGuard, String>
+where
+    I: IntoIterator,
+    I::Item: Into<String>,
+{
+    let keys: Vec<String> = keys.into_iter().map(Into::into).collect();
+    let mut active = ACTIVE_JOBS.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(busy) = keys.iter().find(|key| active.contains(*key)) {
+        println!("✗ 命令 {} 被拒绝: 资源 {} 正在使用", command, busy);
+        return Err(format!("{} 资源 {} 正在被其他任务使用，请稍后重试", BUSY_PREFIX, busy));
+    }
+    active.extend(keys.iter().cloned());
+    Ok(JobGuard { keys })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_is_exclusive_and_released() {
+        let dir = std::env::temp_dir().join(format!("ezplist_lock_{}", std::process::id()));
+        let guard = try_lock("first", [output_dir(&dir)]).unwrap();
+
+        // 同一目录的不同写法视为同一资源，部分占用时整体拒绝
+        let err = try_lock("second", ["test-free".to_string(), output_dir(dir.join("."))]).unwrap_err();
+        assert!(err.starts_with(BUSY_PREFIX));
+        assert!(try_lock("third", ["test-free"]).is_ok());
+
+        drop(guard);
+        assert!(try_lock("second", [output_dir(&dir)]).is_ok());
+    }
+}
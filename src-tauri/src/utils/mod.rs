@@ -1,7 +1,35 @@
 /// 工具函数模块
+/// 
+/// 裁剪与哈希实现位于 `ezplist-core` crate
+/// - guard: 命令 panic 防护
+/// - image_loader: 带格式与尺寸校验的图像加载
+/// - job_lock: 共享资源的任务锁
+/// - hook_runner: 导出钩子执行
+/// - stdio: 标准输出重定向
+/// - png_writer: PNG 写入（可附带图集来源信息）
+/// - texture_writer: 纹理文件写入（PNG、PVR、ccz、KTX）
+/// - animation_writer: GIF / APNG 动图写入
+/// - workers: 可调线程数的工作线程池
+/// - video_decoder: 调用 ffmpeg 采样视频帧
+/// - frame_cache: 图集帧磁盘缓存
+/// - source_stamp: 来源文件指纹
+/// - number_locale: 数字格式地区设置
 
-pub mod trim;
-pub mod hash;
+pub use ezplist_core::{trim, hash};
+
+pub mod guard;
+pub mod image_loader;
+pub mod job_lock;
+pub mod hook_runner;
+pub mod stdio;
+pub mod png_writer;
+pub mod texture_writer;
+pub mod animation_writer;
+pub mod workers;
+pub mod video_decoder;
+pub mod frame_cache;
+pub mod source_stamp;
+pub mod number_locale;
 
 pub use trim::*;
 pub use hash::*;
@@ -0,0 +1,20 @@
+/// 数字格式地区设置
+///
+/// 命令结果中的摘要按此地区格式化，前端切换语言时同步设置
+
+use crate::core::summary::NumberLocale;
+use std::sync::RwLock;
+
+/// 当前地区
+static LOCALE: RwLock<NumberLocale> = RwLock::new(NumberLocale::ZhCn);
+
+/// 设置数字格式地区
+pub fn set_number_locale(locale: NumberLocale) {
+    *LOCALE.write().unwrap_or_else(|e| e.into_inner()) = locale;
+    println!("✓ 数字格式地区: {:?}", locale);
+}
+
+/// 当前数字格式地区
+pub fn number_locale() -> NumberLocale {
+    *LOCALE.read().unwrap_or_else(|e| e.into_inner())
+}
@@ -0,0 +1,159 @@
+/// PNG 写入 (PNG Writer)
+///
+/// 编码 PNG 并按需写入图集来源信息与 DPI（见 `core::png_meta`）
+
+use crate::core::png_meta::PngMetadata;
+use crate::core::texture_format::{quantize, reduce_pixel_format, PixelFormat, PngCompression, TextureOptions};
+use image::codecs::png::PngEncoder;
+use image::{ImageEncoder, RgbaImage};
+use std::path::Path;
+
+/// 当前工具版本
+pub const TOOL_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// 保存 PNG
+///
+/// # Arguments
+/// * `image` - 图像
+/// * `path` - 输出路径
+/// * `metadata` - 附加元数据（来源信息、DPI）
+///
+/// # Returns
+/// * `Result<(), String>` - 编码或写入失败时返回错误信息
+pub fn save_png(image: &RgbaImage, path: &Path, metadata: &PngMetadata) -> Result<(), String> {
+    let mut data = encode_png(image)?;
+
+    if metadata != &PngMetadata::default() {
+        data = metadata.apply(&data)?;
+    }
+
+    std::fs::write(path, data).map_err(|e| format!("写入 PNG 失败: {}", e))
+}
+
+/// 编码 PNG（不附加元数据）
+pub fn encode_png(image: &RgbaImage) -> Result<Vec<u8>, String> {
+    let mut data = Vec::new();
+    PngEncoder::new(&mut data)
+        .write_image(image.as_raw(), image.width(), image.height(), image::ExtendedColorType::Rgba8)
+        .map_err(|e| format!("PNG 编码失败: {}", e))?;
+    Ok(data)
+}
+
+/// 按纹理输出选项编码 PNG（导出的写出阶段）
+///
+/// 依次降低色深或量化为索引调色板，再按压缩级别编码：
+/// RGB565 写为不含 alpha 的 RGB 图，设置调色板颜色数时写为索引 PNG（透明色写入 tRNS）
+///
+/// # Arguments
+/// * `image` - 渲染好的纹理（不修改）
+/// * `options` - 纹理输出选项
+///
+/// # Returns
+/// * `Result<Vec<u8>, String>` - PNG 数据或错误信息
+pub fn encode_texture(image: &RgbaImage, options: &TextureOptions) -> Result<Vec<u8>, String> {
+    options.validate()?;
+    let (width, height) = image.dimensions();
+    let mut encoded = Vec::new();
+    let mut encoder = png::Encoder::new(&mut encoded, width, height);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_compression(match options.compression {
+        PngCompression::Fast => png::Compression::Fast,
+        PngCompression::Balanced => png::Compression::Balanced,
+        PngCompression::Best => png::Compression::High,
+    });
+
+    let data = if let Some(colors) = options.palette_colors {
+        let indexed = quantize(image, colors as usize, options.dither);
+        encoder.set_color(png::ColorType::Indexed);
+        encoder.set_palette(indexed.palette.iter().flat_map(|c| [c[0], c[1], c[2]]).collect::<Vec<u8>>());
+        // tRNS 只需写到最后一个不透明以外的颜色
+        let alpha: Vec<u8> = indexed.palette.iter().map(|c| c[3]).collect();
+        if let Some(last) = alpha.iter().rposition(|&a| a != 255) {
+            encoder.set_trns(alpha[..=last].to_vec());
+        }
+        indexed.indices
+    } else {
+        let mut reduced = image.clone();
+        reduce_pixel_format(&mut reduced, options.pixel_format, options.dither);
+        if options.pixel_format.has_alpha() {
+            encoder.set_color(png::ColorType::Rgba);
+            reduced.into_raw()
+        } else {
+            encoder.set_color(png::ColorType::Rgb);
+            reduced.pixels().flat_map(|p| [p[0], p[1], p[2]]).collect()
+        }
+    };
+
+    let mut writer = encoder.write_header().map_err(|e| format!("PNG 编码失败: {}", e))?;
+    writer.write_image_data(&data).map_err(|e| format!("PNG 编码失败: {}", e))?;
+    writer.finish().map_err(|e| format!("PNG 编码失败: {}", e))?;
+    if options.pixel_format != PixelFormat::Rgba8888 || options.palette_colors.is_some() {
+        println!("  - 纹理格式: {} {}x{}, {} 字节", texture_label(options), width, height, encoded.len());
+    }
+    Ok(encoded)
+}
+
+/// 日志中的纹理格式说明
+fn texture_label(options: &TextureOptions) -> String {
+    match options.palette_colors {
+        Some(colors) => format!("索引调色板 ({} 色)", colors),
+        None => options.pixel_format.name().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::png_meta::{read_dpi, read_text_chunks, AtlasStamp};
+
+    #[test]
+    fn test_save_with_metadata() {
+        let path = std::env::temp_dir().join(format!("ezplist_stamp_{}.png", std::process::id()));
+        let stamp = AtlasStamp {
+            tool_version: TOOL_VERSION.to_string(),
+            config_hash: "abc".to_string(),
+            frame_count: 3,
+        };
+        let metadata = PngMetadata {
+            stamp: Some(stamp.clone()),
+            dpi: Some(144),
+        };
+        save_png(&RgbaImage::new(4, 4), &path, &metadata).unwrap();
+
+        let data = std::fs::read(&path).unwrap();
+        assert_eq!(read_text_chunks(&data).unwrap(), stamp.text_entries());
+        assert_eq!(read_dpi(&data).unwrap(), Some(144));
+        assert_eq!(image::load_from_memory(&data).unwrap().width(), 4);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_encode_texture() {
+        let image = RgbaImage::from_fn(16, 16, |x, y| {
+            image::Rgba([(x * 16) as u8, (y * 16) as u8, 128, if x == 0 { 0 } else { 255 }])
+        });
+        let decode = |data: &[u8]| {
+            let decoder = png::Decoder::new(std::io::Cursor::new(data.to_vec()));
+            let reader = decoder.read_info().unwrap();
+            let info = reader.info();
+            (info.color_type, info.trns.is_some())
+        };
+
+        let rgba = encode_texture(&image, &TextureOptions::default()).unwrap();
+        assert_eq!(decode(&rgba), (png::ColorType::Rgba, false));
+        assert_eq!(image::load_from_memory(&rgba).unwrap().to_rgba8(), image);
+
+        let options = TextureOptions { pixel_format: PixelFormat::Rgb565, dither: true, ..Default::default() };
+        assert_eq!(decode(&encode_texture(&image, &options).unwrap()), (png::ColorType::Rgb, false));
+
+        let options = TextureOptions { palette_colors: Some(16), compression: PngCompression::Best, ..Default::default() };
+        let indexed = encode_texture(&image, &options).unwrap();
+        assert_eq!(decode(&indexed), (png::ColorType::Indexed, true));
+        let decoded = image::load_from_memory(&indexed).unwrap().to_rgba8();
+        assert_eq!(decoded.get_pixel(0, 5)[3], 0);
+        assert_eq!(decoded.get_pixel(3, 5)[3], 255);
+
+        let invalid = TextureOptions { palette_colors: Some(300), ..Default::default() };
+        assert!(encode_texture(&image, &invalid).is_err());
+    }
+}
@@ -0,0 +1,53 @@
+/// 来源文件指纹
+///
+/// 读取文件的修改时间、大小与内容哈希，供会话检测导入后被修改或删除的源文件
+
+use crate::core::session::SourceStamp;
+use crate::utils::hash::calculate_md5;
+use std::time::UNIX_EPOCH;
+
+/// 读取文件的修改时间（Unix 毫秒）与大小
+fn file_meta(path: &str) -> Result<(u64, u64), String> {
+    let meta = std::fs::metadata(path).map_err(|e| format!("无法读取 {}: {}", path, e))?;
+    let modified_ms = meta
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |duration| duration.as_millis() as u64);
+    Ok((modified_ms, meta.len()))
+}
+
+/// 计算文件指纹
+///
+/// # Arguments
+/// * `path` - 文件路径
+///
+/// # Returns
+/// * `Result<SourceStamp, String>` - 文件不存在或无法读取时返回错误
+pub fn stamp_file(path: &str) -> Result<SourceStamp, String> {
+    let (modified_ms, size) = file_meta(path)?;
+    let bytes = std::fs::read(path).map_err(|e| format!("无法读取 {}: {}", path, e))?;
+    Ok(SourceStamp {
+        modified_ms,
+        size,
+        hash: calculate_md5(&bytes),
+    })
+}
+
+/// 读取文件的当前指纹
+///
+/// 修改时间与大小都和记录一致时直接沿用记录，不重新计算哈希
+///
+/// # Arguments
+/// * `path` - 文件路径
+/// * `recorded` - 之前记录的指纹
+///
+/// # Returns
+/// * `Option<SourceStamp>` - 当前指纹；文件不存在或无法读取时为 None
+pub fn current_stamp(path: &str, recorded: &SourceStamp) -> Option<SourceStamp> {
+    match file_meta(path) {
+        Ok((modified_ms, size)) if modified_ms == recorded.modified_ms && size == recorded.size => Some(recorded.clone()),
+        Ok(_) => stamp_file(path).ok(),
+        Err(_) => None,
+    }
+}
@@ -0,0 +1,96 @@
+/// 标准输出重定向 (Stdout Redirection)
+///
+/// 命令通过 `println!` 输出日志。以 JSON-RPC 等机器可读的方式运行时，
+/// 把标准输出重定向到标准错误（或在静默模式下丢弃），日志不会混进协议数据；
+/// 返回的文件仍写入原来的标准输出
+
+use std::fs::File;
+use std::io::Write;
+
+/// 把标准输出重定向到标准错误
+///
+/// # Returns
+/// * `Result<File, String>` - 指向原标准输出的文件
+#[cfg(unix)]
+pub fn redirect_stdout_to_stderr() -> Result<File, String> {
+    redirect_stdout_to(libc::STDERR_FILENO)
+}
+
+/// 丢弃标准输出中的日志
+///
+/// # Returns
+/// * `Result<File, String>` - 指向原标准输出的文件
+#[cfg(unix)]
+pub fn redirect_stdout_to_null() -> Result<File, String> {
+    use std::os::fd::AsRawFd;
+
+    let null = File::options().write(true).open("/dev/null").map_err(|e| format!("无法打开 /dev/null: {}", e))?;
+    // dup2 复制描述符后 null 可以关闭
+    redirect_stdout_to(null.as_raw_fd())
+}
+
+/// 把标准输出指向 `target` 描述符
+#[cfg(unix)]
+fn redirect_stdout_to(target: std::os::fd::RawFd) -> Result<File, String> {
+    use std::os::fd::FromRawFd;
+
+    let _ = std::io::stdout().flush();
+    // SAFETY: 只复制与替换进程自己的标准描述符，dup 返回的新描述符由 File 独占
+    unsafe {
+        let saved = libc::dup(libc::STDOUT_FILENO);
+        if saved < 0 {
+            return Err("无法复制标准输出".to_string());
+        }
+        if libc::dup2(target, libc::STDOUT_FILENO) < 0 {
+            libc::close(saved);
+            return Err("无法重定向标准输出".to_string());
+        }
+        Ok(File::from_raw_fd(saved))
+    }
+}
+
+/// 把标准输出重定向到标准错误
+///
+/// # Returns
+/// * `Result<File, String>` - 指向原标准输出的文件
+#[cfg(windows)]
+pub fn redirect_stdout_to_stderr() -> Result<File, String> {
+    use windows_sys::Win32::System::Console::{GetStdHandle, STD_ERROR_HANDLE};
+
+    // SAFETY: 只读取进程自己的标准错误句柄
+    redirect_stdout_to(unsafe { GetStdHandle(STD_ERROR_HANDLE) })
+}
+
+/// 丢弃标准输出中的日志
+///
+/// # Returns
+/// * `Result<File, String>` - 指向原标准输出的文件
+#[cfg(windows)]
+pub fn redirect_stdout_to_null() -> Result<File, String> {
+    use std::os::windows::io::IntoRawHandle;
+
+    let null = File::options().write(true).open("NUL").map_err(|e| format!("无法打开 NUL: {}", e))?;
+    // 句柄替换为标准输出后在进程结束前一直使用，不再关闭
+    redirect_stdout_to(null.into_raw_handle())
+}
+
+/// 把标准输出指向 `target` 句柄
+#[cfg(windows)]
+fn redirect_stdout_to(target: windows_sys::Win32::Foundation::HANDLE) -> Result<File, String> {
+    use std::os::windows::io::FromRawHandle;
+    use windows_sys::Win32::Foundation::INVALID_HANDLE_VALUE;
+    use windows_sys::Win32::System::Console::{GetStdHandle, SetStdHandle, STD_OUTPUT_HANDLE};
+
+    let _ = std::io::stdout().flush();
+    // SAFETY: 标准库每次写入时都会重新获取标准输出句柄，替换后原句柄只由返回的 File 使用
+    unsafe {
+        let stdout = GetStdHandle(STD_OUTPUT_HANDLE);
+        if stdout.is_null() || stdout == INVALID_HANDLE_VALUE {
+            return Err("没有可用的标准输出".to_string());
+        }
+        if SetStdHandle(STD_OUTPUT_HANDLE, target) == 0 {
+            return Err("无法重定向标准输出".to_string());
+        }
+        Ok(File::from_raw_handle(stdout))
+    }
+}
@@ -0,0 +1,78 @@
+/// 纹理文件写入 (Texture Writer)
+///
+/// 按 `TextureOptions.container` 把渲染好的纹理编码为 PNG、PVR、`.pvr.ccz` 或 KTX
+
+use crate::core::texture_container::{ccz_header, encode_ktx, encode_pvr, TextureContainer};
+use crate::core::texture_format::{reduce_pixel_format, PngCompression, TextureOptions};
+use crate::utils::png_writer::encode_texture;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use image::RgbaImage;
+use std::io::Write;
+
+/// 编码纹理文件
+///
+/// # Arguments
+/// * `image` - 渲染好的纹理（不修改）
+/// * `options` - 纹理输出选项
+///
+/// # Returns
+/// * `Result<Vec<u8>, String>` - 文件内容或错误信息
+pub fn encode_texture_file(image: &RgbaImage, options: &TextureOptions) -> Result<Vec<u8>, String> {
+    if options.container == TextureContainer::Png {
+        return encode_texture(image, options);
+    }
+    options.validate()?;
+    let mut reduced = image.clone();
+    reduce_pixel_format(&mut reduced, options.pixel_format, options.dither);
+    let data = match options.container {
+        TextureContainer::Ktx => encode_ktx(&reduced, options.pixel_format),
+        _ => encode_pvr(&reduced, options.pixel_format),
+    };
+    if options.container != TextureContainer::PvrCcz {
+        return Ok(data);
+    }
+
+    let level = match options.compression {
+        PngCompression::Fast => Compression::fast(),
+        PngCompression::Balanced => Compression::default(),
+        PngCompression::Best => Compression::best(),
+    };
+    let mut out = ccz_header(data.len())?.to_vec();
+    let mut encoder = ZlibEncoder::new(&mut out, level);
+    encoder.write_all(&data).map_err(|e| format!("ccz 压缩失败: {}", e))?;
+    encoder.finish().map_err(|e| format!("ccz 压缩失败: {}", e))?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::texture_container::CCZ_HEADER_LEN;
+    use crate::core::texture_format::PixelFormat;
+    use flate2::read::ZlibDecoder;
+    use std::io::Read;
+
+    #[test]
+    fn test_encode_pvr_ccz() {
+        let image = RgbaImage::from_pixel(8, 4, image::Rgba([10, 200, 30, 255]));
+        let options = TextureOptions {
+            pixel_format: PixelFormat::Rgba4444,
+            container: TextureContainer::PvrCcz,
+            ..Default::default()
+        };
+        let ccz = encode_texture_file(&image, &options).unwrap();
+        assert_eq!(&ccz[..4], b"CCZ!");
+        let mut pvr = Vec::new();
+        ZlibDecoder::new(&ccz[CCZ_HEADER_LEN..]).read_to_end(&mut pvr).unwrap();
+        let mut reduced = image.clone();
+        reduce_pixel_format(&mut reduced, PixelFormat::Rgba4444, false);
+        assert_eq!(pvr, encode_pvr(&reduced, PixelFormat::Rgba4444));
+        assert_eq!(u32::from_be_bytes(ccz[12..16].try_into().unwrap()) as usize, pvr.len());
+
+        let png = encode_texture_file(&image, &TextureOptions::default()).unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+        let indexed = TextureOptions { palette_colors: Some(16), container: TextureContainer::Pvr, ..Default::default() };
+        assert!(encode_texture_file(&image, &indexed).is_err());
+    }
+}
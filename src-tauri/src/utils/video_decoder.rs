@@ -0,0 +1,80 @@
+/// 视频解码 (Video Decoder)
+///
+/// 调用随应用分发的 ffmpeg 把视频按帧率采样为 PNG 序列。
+/// 优先使用可执行文件同目录下的 ffmpeg（Tauri sidecar 的安装位置），
+/// 找不到时回退到 PATH 中的 ffmpeg
+
+use crate::core::video::{decoder_args, VideoRange};
+use crate::utils::hook_runner::{read_in_background, wait_with_timeout};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// 解码超时
+const DECODE_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// 查找 ffmpeg 可执行文件
+fn decoder_path() -> PathBuf {
+    let name = format!("ffmpeg{}", std::env::consts::EXE_SUFFIX);
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(&name)))
+        .filter(|path| path.is_file())
+        .unwrap_or_else(|| PathBuf::from(name))
+}
+
+/// 采样视频帧
+///
+/// # Arguments
+/// * `input` - 视频路径
+/// * `out_dir` - 输出目录（应为空目录）
+/// * `stem` - 输出帧的文件名前缀，帧文件为 `{stem}_0001.png` 起
+/// * `fps` - 采样帧率
+/// * `range` - 时间范围
+///
+/// # Returns
+/// * `Result<Vec<PathBuf>, String>` - 按时间顺序排列的帧文件
+pub fn decode_frames(
+    input: &str,
+    out_dir: &Path,
+    stem: &str,
+    fps: f64,
+    range: &VideoRange,
+) -> Result<Vec<PathBuf>, String> {
+    let decoder = decoder_path();
+    let pattern = out_dir.join(format!("{}_%04d.png", stem));
+    let args = decoder_args(input, &pattern.to_string_lossy(), fps, range);
+
+    let mut child = Command::new(&decoder)
+        .args(&args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("无法启动视频解码器 {}: {}", decoder.display(), e))?;
+    let stderr = child.stderr.take().map(read_in_background);
+    let exit_code = wait_with_timeout(&mut child, DECODE_TIMEOUT);
+    let stderr = stderr.and_then(|h| h.join().ok()).unwrap_or_default();
+    match exit_code {
+        Some(Some(0)) => {}
+        Some(code) => return Err(format!("视频解码失败（退出码 {:?}）: {}", code, stderr.trim())),
+        None => return Err(format!("视频解码超时（{} 秒），已终止", DECODE_TIMEOUT.as_secs())),
+    }
+
+    let prefix = format!("{}_", stem);
+    let mut frames: Vec<PathBuf> = std::fs::read_dir(out_dir)
+        .map_err(|e| format!("无法读取解码输出目录: {}", e))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(&prefix) && n.ends_with(".png"))
+        })
+        .collect();
+    // 帧数不超过 MAX_VIDEO_FRAMES，4 位帧号按文件名排序即按时间排序
+    frames.sort();
+    if frames.is_empty() {
+        return Err("视频在指定范围内没有可采样的帧".to_string());
+    }
+    Ok(frames)
+}
@@ -0,0 +1,73 @@
+/// 工作线程池 (Worker Thread Pool)
+///
+/// 导入、裁剪、打包与导出中的并行计算都在同一个 rayon 线程池中执行（见 `guard::run_blocking`），
+/// 线程数可调：笔记本上限制线程数可在批量任务运行时保持机器可用，CI 上可以用满所有核心
+
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::sync::{Arc, LazyLock, RwLock};
+
+/// 允许设置的最大线程数
+pub const MAX_WORKER_THREADS: usize = 256;
+
+/// 当前线程池（替换时正在执行的任务继续使用旧线程池直到结束）
+static POOL: LazyLock<RwLock<Arc<ThreadPool>>> =
+    LazyLock::new(|| RwLock::new(Arc::new(build_pool(0).expect("无法创建工作线程池"))));
+
+fn build_pool(threads: usize) -> Result<ThreadPool, String> {
+    ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .thread_name(|i| format!("ezplist-worker-{}", i))
+        .build()
+        .map_err(|e| format!("无法创建工作线程池: {}", e))
+}
+
+fn current_pool() -> Arc<ThreadPool> {
+    POOL.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// 设置工作线程数
+///
+/// # Arguments
+/// * `threads` - 线程数，0 表示使用全部 CPU 核心
+///
+/// # Returns
+/// * `Result<usize, String>` - 实际的线程数
+pub fn set_worker_threads(threads: usize) -> Result<usize, String> {
+    if threads > MAX_WORKER_THREADS {
+        return Err(format!("线程数不能超过 {}", MAX_WORKER_THREADS));
+    }
+    let pool = Arc::new(build_pool(threads)?);
+    let actual = pool.current_num_threads();
+    *POOL.write().unwrap_or_else(|e| e.into_inner()) = pool;
+    println!("✓ 工作线程数: {}", actual);
+    Ok(actual)
+}
+
+/// 当前工作线程数
+pub fn worker_threads() -> usize {
+    current_pool().current_num_threads()
+}
+
+/// 在工作线程池中执行任务，任务内的 rayon 并行迭代使用该线程池
+pub fn install<T, F>(task: F) -> T
+where
+    F: FnOnce() -> T + Send,
+    T: Send,
+{
+    current_pool().install(task)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_worker_threads() {
+        assert_eq!(set_worker_threads(2).unwrap(), 2);
+        assert_eq!(worker_threads(), 2);
+        assert_eq!(install(rayon::current_num_threads), 2);
+        assert!(set_worker_threads(MAX_WORKER_THREADS + 1).is_err());
+
+        assert!(set_worker_threads(0).unwrap() >= 1);
+    }
+}
@@ -8,6 +8,7 @@ import { Download, Settings, Archive, Package, Loader2 } from 'lucide-react'
 import { Button } from './ui/Button'
 import { useSpriteStore } from '../store/spriteStore'
 import { packSprites } from '../lib/tauri'
+import type { NameCollisionPolicy } from '../types/sprite'
 
 /**
  * 导出配置类型
@@ -19,6 +20,7 @@ interface ExportOptions {
   trimTransparent: boolean
   allowRotation: boolean
   zipOutput: boolean
+  nameCollision: NameCollisionPolicy
 }
 
 /**
@@ -34,6 +36,7 @@ export function ExportPanel() {
     trimTransparent: true,
     allowRotation: true,
     zipOutput: false,
+    nameCollision: 'error',
   })
 
   const canExport = sprites.length > 0
@@ -56,6 +59,7 @@ export function ExportPanel() {
         allowRotation: options.allowRotation,
         padding: options.padding,
         autoSize: true,
+        nameCollision: options.nameCollision,
       })
       
       setPackResult(result)
@@ -125,6 +129,20 @@ export function ExportPanel() {
           />
         </div>
 
+        {/* 重名帧 */}
+        <div>
+          <label className="text-xs font-medium text-gray-600">重名帧</label>
+          <select
+            value={options.nameCollision}
+            onChange={(e) => setOptions({ ...options, nameCollision: e.target.value as NameCollisionPolicy })}
+            className="w-full px-2 py-1.5 text-xs rounded-lg border border-gray-200 bg-white/80 focus:ring-1 focus:ring-blue-400 focus:border-blue-400"
+          >
+            <option value="error">报错</option>
+            <option value="folder-prefix">加文件夹前缀</option>
+            <option value="numeric-suffix">加数字后缀</option>
+          </select>
+        </div>
+
         {/* 选项开关 */}
         <div className="space-y-2">
           <ToggleOption
@@ -176,6 +194,14 @@ export function ExportPanel() {
             {packResult.textureWidth}×{packResult.textureHeight} | 填充率 {packResult.fillRate.toFixed(1)}%
           </p>
         )}
+        {isPacked && packResult.renamedFrames.length > 0 && (
+          <p
+            className="text-xs text-center text-amber-600"
+            title={packResult.renamedFrames.map((r) => `${r.from} → ${r.to}`).join('\n')}
+          >
+            {packResult.renamedFrames.length} 个重名帧已自动改名
+          </p>
+        )}
       </div>
     </div>
   )
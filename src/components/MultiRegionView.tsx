@@ -222,8 +222,8 @@ export function MultiRegionView() {
     try {
       const result = await exportMultiPlist(spritesheet, regions)
       
-      if (result.failed.length > 0) {
-        console.warn('部分导出失败:', result.failed)
+      if (result.warning) {
+        console.warn(result.warning, result.failed)
       }
       
       console.log('导出成功:', result.exportedFiles)
@@ -10,14 +10,20 @@ import type {
   ImportResult, 
   PackResult, 
   ExportConfig, 
+  ExportResult,
+  Timings,
   SpriteData,
   SpritesheetInfo,
   SpritesheetInfoEx,
   SplitConfig,
+  BlobSplitConfig,
   SplitResult,
   FrameInfo,
   AnimationRegion,
   MultiExportResult,
+  SheetFormat,
+  SmartUpdateMode,
+  NameCollisionPolicy,
 } from '../types/sprite'
 
 /**
@@ -30,7 +36,7 @@ export async function selectImageFiles(): Promise<string[] | null> {
     filters: [
       {
         name: 'Images',
-        extensions: ['png', 'jpg', 'jpeg', 'gif', 'webp', 'bmp'],
+        extensions: ['png', 'jpg', 'jpeg', 'gif', 'webp', 'bmp', 'tga', 'dds'],
       },
     ],
     title: '选择图片文件 (Select Image Files)',
@@ -63,6 +69,72 @@ export async function importImages(paths: string[]): Promise<ImportResult> {
     })),
     failed: result.failed,
     total: result.total,
+    animations: result.animations ?? [],
+  }
+}
+
+/**
+ * 文件夹导入选项
+ */
+export interface FolderImportOptions {
+  /** 是否扫描子文件夹（默认 true） */
+  recursive?: boolean
+  /** 导入的扩展名（默认常见图片格式） */
+  extensions?: string[]
+  /** 只导入匹配的文件（通配符，包含 / 时匹配相对路径，否则匹配文件名） */
+  include?: string
+  /** 排除匹配的文件 */
+  exclude?: string
+  /** 帧名称带相对子文件夹前缀（如 enemies/slime_01.png） */
+  subfolderNames?: boolean
+}
+
+/**
+ * 导入文件夹中的图片
+ * @param dir 文件夹路径
+ * @param folder 筛选与命名选项
+ * @returns 导入结果与重名警告
+ */
+export async function importFolder(
+  dir: string,
+  folder: FolderImportOptions = {}
+): Promise<ImportResult & { warnings: string[] }> {
+  const result = await invoke<any>('import_folder', { dir, folder })
+  return {
+    sprites: result.sprites.map((sprite: any) => ({
+      id: sprite.id,
+      name: sprite.name,
+      path: sprite.path,
+      width: sprite.width,
+      height: sprite.height,
+      trimmedWidth: sprite.trimmed_width ?? sprite.width,
+      trimmedHeight: sprite.trimmed_height ?? sprite.height,
+    })),
+    failed: result.failed,
+    total: result.total,
+    animations: result.animations ?? [],
+    warnings: result.warnings,
+  }
+}
+
+/**
+ * 导入内存中的图像数据（如画布导出的 PNG），无需先写入临时文件
+ * @param name 精灵名
+ * @param bytes 编码后的图像数据
+ * @returns 导入的精灵
+ */
+export async function importImageBuffer(name: string, bytes: Uint8Array): Promise<SpriteData> {
+  const sprite = await invoke<any>('import_image_buffer', bytes, {
+    headers: { 'ezplist-sprite-name': encodeURIComponent(name) },
+  })
+  return {
+    id: sprite.id,
+    name: sprite.name,
+    path: sprite.path,
+    width: sprite.width,
+    height: sprite.height,
+    trimmedWidth: sprite.trimmed_width ?? sprite.width,
+    trimmedHeight: sprite.trimmed_height ?? sprite.height,
   }
 }
 
@@ -76,12 +148,45 @@ export interface PackConfig {
   maxHeight?: number
   /** 是否启用透明裁剪 */
   trimTransparent?: boolean
+  /** 裁剪模式（优先于 trimTransparent）：trim 缩小为内容尺寸，crop-keep-size 保留原始尺寸，none 不裁剪 */
+  trimMode?: TrimMode
+  /** 裁剪时的 alpha 阈值（默认 1，不大于此值的像素视为透明） */
+  alphaThreshold?: number
+  /** 裁剪时在内容四周保留的透明像素数（默认 0） */
+  trimMargin?: number
   /** 是否允许旋转 */
   allowRotation?: boolean
   /** 精灵间距 */
   padding?: number
   /** 是否自动选择最优尺寸 */
   autoSize?: boolean
+  /** 把翻转帧合并到源帧（翻转帧不占纹理空间） */
+  aliasMirrors?: boolean
+  /** 在每页图集中注入 4x4 的纯白区域 `__white`（纯色图元与精灵合批绘制） */
+  whitePixel?: boolean
+  /** 额外注入的纯色区域（名称不能与精灵重名） */
+  solidRegions?: SolidRegion[]
+  /** 导出的数据格式：格式无法表示旋转或翻转帧时自动关闭对应选项并给出警告 */
+  targetFormat?: SheetFormat
+  /** 帧名称重复时的处理（默认报错并列出重名的帧） */
+  nameCollision?: NameCollisionPolicy
+}
+
+/**
+ * 裁剪模式
+ */
+export type TrimMode = 'trim' | 'crop-keep-size' | 'none'
+
+/**
+ * 注入图集的纯色区域
+ */
+export interface SolidRegion {
+  /** 帧名称 */
+  name: string
+  /** RGBA 颜色 */
+  color: [number, number, number, number]
+  /** 边长（默认 4） */
+  size?: number
 }
 
 /**
@@ -103,6 +208,8 @@ export async function packSprites(
     height: s.height,
     trimmed_width: s.trimmedWidth,
     trimmed_height: s.trimmedHeight,
+    anchor: s.anchor,
+    nine_slice: s.nineSlice,
   }))
   
   const result = await invoke<any>('pack_sprites', {
@@ -111,9 +218,17 @@ export async function packSprites(
       maxWidth: config.maxWidth ?? 2048,
       maxHeight: config.maxHeight ?? 2048,
       trimTransparent: config.trimTransparent ?? true,
+      trimMode: config.trimMode,
+      alphaThreshold: config.alphaThreshold,
+      trimMargin: config.trimMargin ?? 0,
       allowRotation: config.allowRotation ?? true,
       padding: config.padding ?? 1,
       autoSize: config.autoSize ?? true,
+      aliasMirrors: config.aliasMirrors ?? false,
+      whitePixel: config.whitePixel ?? false,
+      solidRegions: config.solidRegions,
+      targetFormat: config.targetFormat,
+      nameCollision: config.nameCollision,
     },
   })
   
@@ -132,20 +247,52 @@ export async function packSprites(
       trimmed: s.trimmed,
       offsetX: s.offset_x,
       offsetY: s.offset_y,
+      flipX: s.flip_x,
+      flipY: s.flip_y,
+      anchor: s.anchor,
+      nineSlice: s.nine_slice,
     })),
     textureWidth: result.texture_width,
     textureHeight: result.texture_height,
     fillRate: result.fill_rate,
+    jobId: result.job_id,
+    timings: result.timings,
+    summary: result.summary,
+    renamedFrames: result.renamed_frames ?? [],
   }
 }
 
+/**
+ * 翻转帧：源帧翻转后与该帧相同
+ */
+export interface MirrorMatch {
+  /** 翻转帧 ID */
+  id: string
+  /** 源帧 ID */
+  sourceId: string
+  /** 水平翻转 */
+  flipX: boolean
+  /** 垂直翻转 */
+  flipY: boolean
+}
+
+/**
+ * 在打包结果中查找翻转帧
+ * @param jobId 打包结果 ID
+ * @param spriteIds 参与检测的精灵 ID（靠前的作为源帧）
+ * @returns 翻转帧列表
+ */
+export async function findMirroredSprites(jobId: string, spriteIds: string[]): Promise<MirrorMatch[]> {
+  return await invoke<MirrorMatch[]>('find_mirrored_sprites', { jobId, spriteIds })
+}
+
 /**
  * 导出精灵表
  * @param config 导出配置
- * @returns 导出路径
+ * @returns 导出路径与各阶段耗时
  */
-export async function exportSpriteSheet(config: ExportConfig): Promise<string> {
-  return await invoke<string>('export_sprite_sheet', { config })
+export async function exportSpriteSheet(config: ExportConfig): Promise<ExportResult> {
+  return await invoke<ExportResult>('export_sprite_sheet', { config })
 }
 
 /**
@@ -169,7 +316,7 @@ export async function selectSpritesheetFile(): Promise<string | null> {
     filters: [
       {
         name: 'Images',
-        extensions: ['png', 'jpg', 'jpeg', 'webp'],
+        extensions: ['png', 'jpg', 'jpeg', 'webp', 'bmp', 'tga', 'dds'],
       },
     ],
     title: '选择精灵图集 (Select Spritesheet)',
@@ -202,6 +349,12 @@ export async function importSpritesheet(path: string): Promise<SpritesheetInfoEx
       rows: result.autoDetect.rows,
       cols: result.autoDetect.cols,
       confidence: result.autoDetect.confidence,
+      marginX: result.autoDetect.marginX,
+      marginY: result.autoDetect.marginY,
+      spacingX: result.autoDetect.spacingX,
+      spacingY: result.autoDetect.spacingY,
+      offsetX: result.autoDetect.offsetX,
+      offsetY: result.autoDetect.offsetY,
     } : null,
   }
 }
@@ -225,9 +378,35 @@ export async function calculateSplitFrames(
       frameHeight: config.frameHeight,
       namePrefix: config.namePrefix,
       startIndex: config.startIndex ?? 1,
+      marginX: config.marginX,
+      marginY: config.marginY,
+      spacingX: config.spacingX,
+      spacingY: config.spacingY,
+      offsetX: config.offsetX,
+      offsetY: config.offsetY,
+      skipEmpty: config.skipEmpty,
+      anchor: config.anchor,
     },
   })
 
+  return toSplitResult(result)
+}
+
+/**
+ * 按不透明连通区域切分图集（适用于没有网格的紧密排布图集）
+ * @param spritesheet 图集信息
+ * @param config 连通区域切分配置
+ * @returns 切分结果（frameWidth/frameHeight 为最大帧尺寸）
+ */
+export async function detectBlobFrames(
+  spritesheet: SpritesheetInfo,
+  config: BlobSplitConfig
+): Promise<SplitResult> {
+  const result = await invoke<any>('detect_blob_frames', { spritesheet, config })
+  return toSplitResult(result)
+}
+
+function toSplitResult(result: any): SplitResult {
   return {
     frames: result.frames.map((f: any) => ({
       name: f.name,
@@ -237,10 +416,12 @@ export async function calculateSplitFrames(
       height: f.height,
       row: f.row,
       col: f.col,
+      anchor: f.anchor,
     })),
     frameWidth: result.frame_width,
     frameHeight: result.frame_height,
     totalFrames: result.total_frames,
+    skippedEmpty: result.skipped_empty,
   }
 }
 
@@ -270,6 +451,8 @@ export async function selectSavePath(defaultName: string): Promise<string | null
 export interface ExportSplitConfig {
   /** 是否重命名 PNG 文件 */
   renamePng: boolean
+  /** plist 中 smartupdate 哈希的计算方式（默认 content） */
+  smartupdate?: SmartUpdateMode
 }
 
 /**
@@ -280,6 +463,8 @@ export interface ExportSplitResult {
   plistPath: string
   /** PNG 文件路径（如果重命名了） */
   pngPath: string | null
+  /** 各阶段耗时 */
+  timings: Timings
 }
 
 /**
@@ -308,6 +493,7 @@ export async function exportSplitPlist(
   return {
     plistPath: result.plist_path,
     pngPath: result.png_path,
+    timings: result.timings,
   }
 }
 
@@ -332,6 +518,7 @@ export async function calculateRegionPreview(
       frameCount: region.frameCount,
       frameWidth: region.frameWidth,
       frameHeight: region.frameHeight,
+      anchor: region.anchor,
     },
   })
 
@@ -344,6 +531,7 @@ export async function calculateRegionPreview(
       height: f.height,
       row: f.row,
       col: f.col,
+      anchor: f.anchor,
     })),
     frameWidth: result.frame_width,
     frameHeight: result.frame_height,
@@ -370,14 +558,16 @@ export async function exportMultiPlist(
       frameCount: r.frameCount,
       frameWidth: r.frameWidth,
       frameHeight: r.frameHeight,
+      anchor: r.anchor,
     })),
   })
 
   return {
-    exportedFiles: result.exported_files,
-    exportedPngs: result.exported_pngs,
+    exportedFiles: result.exportedFiles,
+    exportedPngs: result.exportedPngs,
     failed: result.failed,
     total: result.total,
+    warning: result.warning,
   }
 }
 
@@ -399,8 +589,18 @@ export interface ComposeSpritePosition {
   height: number
   /** 在画布中的 X 坐标 */
   x: number
-  /** 在画布中的 Y 坐标 */
+  /** 在画布中的 Y 坐标（与 X 一起指向变换后外接矩形的左上角） */
   y: number
+  /** 顺时针旋转角度（0、90、180、270） */
+  rotation?: number
+  /** 缩放倍数（默认 1） */
+  scale?: number
+  /** 水平翻转 */
+  flipX?: boolean
+  /** 垂直翻转 */
+  flipY?: boolean
+  /** 叠放顺序（大的绘制在上层） */
+  zIndex?: number
 }
 
 /**
@@ -415,6 +615,10 @@ export interface ComposeConfig {
   padding?: number
   /** 是否裁剪到最小边界 */
   trimToBounds?: boolean
+  /** 透明像素渗色的迭代次数（0 为关闭） */
+  bleed?: number
+  /** plist 中 smartupdate 哈希的计算方式（默认 content） */
+  smartupdate?: SmartUpdateMode
 }
 
 /**
@@ -431,6 +635,10 @@ export interface ComposeResult {
   textureHeight: number
   /** 精灵数量 */
   spriteCount: number
+  /** 各阶段耗时 */
+  timings: Timings
+  /** 结果摘要（按当前数字格式地区） */
+  summary: string
 }
 
 /**
@@ -459,9 +667,48 @@ export async function composeSprites(
     textureWidth: result.texture_width,
     textureHeight: result.texture_height,
     spriteCount: result.sprite_count,
+    timings: result.timings,
+    summary: result.summary,
   }
 }
 
+/**
+ * 自动排列选项
+ */
+export interface ComposeArrangeOptions {
+  /** 精灵间距（默认 2） */
+  padding?: number
+  /** 是否允许旋转 90°（默认 false） */
+  allowRotation?: boolean
+  /** 最大尺寸（默认 4096） */
+  maxSize?: number
+}
+
+/**
+ * 自动排列结果
+ */
+export interface ComposeArrangeResult {
+  /** 按输入顺序排列、已更新坐标与旋转的精灵 */
+  sprites: ComposeSpritePosition[]
+  /** 排列后的宽度 */
+  width: number
+  /** 排列后的高度 */
+  height: number
+}
+
+/**
+ * 自动排列合成精灵（只计算位置，不合成）
+ * @param sprites 精灵位置信息列表
+ * @param options 排列选项
+ * @returns 排列后的精灵与尺寸
+ */
+export async function composeAutoArrange(
+  sprites: ComposeSpritePosition[],
+  options: ComposeArrangeOptions = {}
+): Promise<ComposeArrangeResult> {
+  return invoke<ComposeArrangeResult>('compose_auto_arrange', { sprites, options })
+}
+
 /**
  * 合成边界信息
  */
@@ -513,3 +760,176 @@ export async function selectExportDirectory(): Promise<string | null> {
   }
   return result
 }
+
+/**
+ * 新打开的项目窗口
+ */
+export interface ProjectWindow {
+  /** 窗口标签 */
+  label: string
+  /** 窗口绑定的会话 ID */
+  sessionId: string
+}
+
+/**
+ * 打开新的项目窗口，每个窗口使用自己的会话
+ * @param sessionId 要在新窗口中打开的已有会话（默认新建空会话）
+ * @param title 窗口标题
+ * @returns 新窗口的标签与会话 ID
+ */
+export async function openProjectWindow(sessionId?: string, title?: string): Promise<ProjectWindow> {
+  return await invoke<ProjectWindow>('open_project_window', { sessionId, title })
+}
+
+/**
+ * 获取当前窗口绑定的会话 ID（第一次调用时新建会话）
+ * @returns 会话 ID
+ */
+export async function getWindowSessionId(): Promise<string> {
+  const session = await invoke<any>('get_window_session')
+  return session.id
+}
+
+/**
+ * 数字格式地区
+ */
+export type NumberLocale = 'zh-CN' | 'en-US' | 'de-DE' | 'fr-FR'
+
+/**
+ * 设置结果摘要使用的数字格式地区（切换界面语言时调用）
+ * @param locale 地区
+ */
+export async function setNumberLocale(locale: NumberLocale): Promise<void> {
+  await invoke('set_number_locale', { locale })
+}
+
+/**
+ * 项目文件内容（配置与区域按前端格式原样保存）
+ */
+export interface EzPlistProject {
+  /** 导入的独立图片 */
+  sprites: SpriteData[]
+  /** 独立图片的备注（按精灵 ID） */
+  notes?: Record<string, string>
+  /** 打包会话（由后端维护，打开项目时以新 ID 恢复） */
+  session?: unknown
+  /** 切分使用的图集路径 */
+  spritesheetPath?: string
+  /** 打包配置 */
+  packConfig?: PackConfig
+  /** 切分配置 */
+  splitConfig?: unknown
+  /** 合成配置 */
+  composeConfig?: ComposeConfig
+  /** 动画区域定义 */
+  regions?: unknown[]
+  /** 导出历史（由后端记录，保存时自动写入） */
+  history?: ExportRecord[]
+}
+
+/**
+ * 导出历史记录
+ */
+export interface ExportRecord {
+  id: string
+  /** 导出时间（Unix 毫秒） */
+  createdMs: number
+  /** 参与打包的精灵（后端格式） */
+  sprites: unknown[]
+  /** 打包配置 */
+  packConfig: PackConfig | null
+  /** 导出配置快照：single 为单页导出，pages 为多页导出 */
+  export: { kind: 'single' | 'pages'; config: unknown }
+  /** 导出时的输入指纹 */
+  inputsHash: string
+  /** 写出的数据文件或 ZIP 路径 */
+  outputs: string[]
+}
+
+/**
+ * 重新导出的结果
+ */
+export interface ReExportResult {
+  outputPaths: string[]
+  /** 源图或精灵列表是否与记录时不同 */
+  inputsChanged: boolean
+  /** 重新打包时的警告 */
+  warnings: string[]
+}
+
+/**
+ * 获取导出历史
+ * @returns 所有记录（按时间先后）
+ */
+export async function listExportHistory(): Promise<ExportRecord[]> {
+  return await invoke<ExportRecord[]>('list_export_history')
+}
+
+/**
+ * 按历史记录重新打包并导出
+ * @param historyId 导出记录 ID
+ * @returns 输出路径与输入是否变化
+ */
+export async function reExport(historyId: string): Promise<ReExportResult> {
+  return await invoke<ReExportResult>('re_export', { historyId })
+}
+
+/**
+ * 打开项目时找不到的来源文件
+ */
+export interface MissingSource {
+  name: string
+  path: string
+}
+
+/**
+ * 保存项目文件
+ * @param path 文件路径（没有扩展名时补上 .ezplist）
+ * @param project 项目内容
+ * @returns 实际写入的路径
+ */
+export async function saveProject(path: string, project: EzPlistProject): Promise<string> {
+  return await invoke<string>('save_project', {
+    path,
+    project: {
+      ...project,
+      sprites: project.sprites.map(s => ({
+        id: s.id,
+        name: s.name,
+        path: s.path,
+        width: s.width,
+        height: s.height,
+        trimmed_width: s.trimmedWidth,
+        trimmed_height: s.trimmedHeight,
+        anchor: s.anchor,
+        nine_slice: s.nineSlice,
+      })),
+    },
+  })
+}
+
+/**
+ * 打开项目文件
+ * @param path 项目文件路径
+ * @returns 项目内容与缺失的来源文件
+ */
+export async function loadProject(path: string): Promise<{ project: EzPlistProject; missing: MissingSource[] }> {
+  const result = await invoke<any>('load_project', { path })
+  return {
+    project: {
+      ...result.project,
+      sprites: result.project.sprites.map((s: any) => ({
+        id: s.id,
+        name: s.name,
+        path: s.path,
+        width: s.width,
+        height: s.height,
+        trimmedWidth: s.trimmed_width,
+        trimmedHeight: s.trimmed_height,
+        anchor: s.anchor,
+        nineSlice: s.nine_slice,
+      })),
+    },
+    missing: result.missing,
+  }
+}
@@ -22,6 +22,21 @@ export interface SpriteData {
   trimmedWidth: number
   /** 裁剪后高度 */
   trimmedHeight: number
+  /** 锚点（相对原始尺寸的 0~1 坐标，Y 轴向上；未设置时使用引擎默认值） */
+  anchor?: [number, number]
+  /** 九宫格边距（相对原始尺寸） */
+  nineSlice?: NineSlice
+}
+
+/**
+ * 九宫格边距（四边不拉伸的像素宽度）
+ * @interface NineSlice
+ */
+export interface NineSlice {
+  left: number
+  right: number
+  top: number
+  bottom: number
 }
 
 /**
@@ -35,6 +50,36 @@ export interface ImportResult {
   failed: string[]
   /** 总数 */
   total: number
+  /** 动图展开的动画（帧的精灵 ID 与时长，可写入动画描述文件） */
+  animations: ImportedAnimation[]
+}
+
+/**
+ * 动画中的一帧
+ * @interface AnimationFrame
+ */
+export interface AnimationFrame {
+  /** 导入后的精灵 ID（以图集方式导入或该帧导入失败时为空） */
+  spriteId?: string
+  /** 帧名称（`{文件名}_0001.png` 起） */
+  name: string
+  /** 播放时长（毫秒） */
+  durationMs: number
+}
+
+/**
+ * 导入的动画描述（GIF / APNG / 动画 WebP）
+ * @interface ImportedAnimation
+ */
+export interface ImportedAnimation {
+  /** 动画名称（源文件名，不含扩展名） */
+  name: string
+  /** 源文件路径 */
+  source: string
+  /** 按播放顺序排列的帧 */
+  frames: AnimationFrame[]
+  /** 总时长（毫秒） */
+  durationMs: number
 }
 
 /**
@@ -66,6 +111,31 @@ export interface PackedSprite {
   offsetX: number
   /** 裁剪偏移量 Y */
   offsetY: number
+  /** 水平翻转（纹理区域为源帧） */
+  flipX?: boolean
+  /** 垂直翻转（纹理区域为源帧） */
+  flipY?: boolean
+  /** 锚点（相对原始尺寸的 0~1 坐标，Y 轴向上） */
+  anchor?: [number, number]
+  /** 九宫格边距（相对裁剪后的帧尺寸） */
+  nineSlice?: NineSlice
+}
+
+/**
+ * 各阶段耗时（毫秒）
+ * @interface Timings
+ */
+export interface Timings {
+  /** 解码图像 */
+  decodeMs: number
+  /** 透明裁剪 */
+  trimMs: number
+  /** 排布精灵 */
+  packMs: number
+  /** 渲染纹理与数据文件 */
+  renderMs: number
+  /** 写入文件 */
+  writeMs: number
 }
 
 /**
@@ -81,6 +151,57 @@ export interface PackResult {
   textureHeight: number
   /** 填充率（百分比） */
   fillRate: number
+  /** 打包结果 ID（导出时传回） */
+  jobId: string
+  /** 各阶段耗时 */
+  timings: Timings
+  /** 结果摘要（如「纹理 2048×1024，2.1 MP，约 8 MB RGBA8888，填充率 83.4%」） */
+  summary: string
+  /** 因重名自动改名的帧（packedSprites 中已是新名称） */
+  renamedFrames: FrameRename[]
+}
+
+/**
+ * 帧名称重复时的处理：报错、加文件夹前缀（如 run/01.png）或加数字后缀（如 01_2.png）
+ */
+export type NameCollisionPolicy = 'error' | 'folder-prefix' | 'numeric-suffix'
+
+/**
+ * 因重名自动改名的帧
+ * @interface FrameRename
+ */
+export interface FrameRename {
+  /** 精灵 ID */
+  id: string
+  /** 原名称 */
+  from: string
+  /** 新名称 */
+  to: string
+}
+
+/**
+ * 导出结果
+ * @interface ExportResult
+ */
+export interface ExportResult {
+  /** 数据文件路径（ZIP 模式下为 ZIP 路径） */
+  outputPath: string
+  /** 各缩放版本的数据文件路径（ZIP 模式下为空） */
+  variantPaths: string[]
+  /** 分组数据文件路径（未分组或 ZIP 模式下为空） */
+  groupPaths: string[]
+  /** 各阶段耗时 */
+  timings: Timings
+}
+
+/**
+ * 缩放版本（如 { scale: 0.5, suffix: '-sd' }）
+ */
+export interface ScaleVariant {
+  /** 缩放比例（0-1） */
+  scale: number
+  /** 文件名后缀 */
+  suffix: string
 }
 
 /**
@@ -100,8 +221,75 @@ export interface ExportConfig {
   outputName: string
   /** 是否打包为 ZIP */
   zipOutput: boolean
+  /** 打包结果 ID（PackResult.jobId） */
+  jobId: string
+  /** 数据文件格式（默认 plist） */
+  format?: SheetFormat
+  /** 透明像素渗色的迭代次数（0 为关闭），消除缩放时的暗边 */
+  bleed?: number
+  /** plist 键名版本与编码（默认 xml-v3） */
+  plistFormat?: PlistFormat
+  /** 额外导出的缩放版本，与全分辨率文件写在一起 */
+  variants?: ScaleVariant[]
+  /** 额外按文件夹或自定义分组（如标签）写出数据文件，各文件引用同一张纹理 */
+  frameGroups?: FrameGrouping
+  /** 帧备注（按精灵 ID，有备注时额外写出 {outputName}.notes.json） */
+  frameNotes?: Record<string, string>
+  /** 纹理像素格式、调色板量化与 PNG 压缩级别（默认 RGBA8888） */
+  texture?: TextureOptions
+  /** plist 中 smartupdate 哈希的计算方式（默认 content） */
+  smartupdate?: SmartUpdateMode
+}
+
+/**
+ * 纹理像素格式
+ */
+export type PixelFormat = 'RGBA8888' | 'RGBA4444' | 'RGB565'
+
+/**
+ * 纹理输出选项
+ */
+export interface TextureOptions {
+  /** 像素格式（默认 RGBA8888，其他格式写入数据文件的 pixelFormat） */
+  pixelFormat?: PixelFormat
+  /** 降低色深或量化时使用抖动 */
+  dither?: boolean
+  /** 量化为索引调色板的最大颜色数（2-256，仅 RGBA8888） */
+  paletteColors?: number
+  /** PNG 压缩级别（默认 fast，.pvr.ccz 的 zlib 压缩同样适用） */
+  compression?: 'fast' | 'balanced' | 'best'
+  /** 纹理容器（默认 png），数据文件引用对应扩展名的纹理 */
+  container?: TextureContainer
 }
 
+/**
+ * 纹理容器：PNG、未压缩 PVR、Cocos2d-x .pvr.ccz 或 KTX
+ */
+export type TextureContainer = 'png' | 'pvr' | 'pvrCcz' | 'ktx'
+
+/**
+ * 帧分组方式：按名称中的文件夹，或自定义分组（精灵 ID 列表）
+ */
+export type FrameGrouping =
+  | { by: 'folder' }
+  | { by: 'groups'; groups: { name: string; spriteIds: string[] }[] }
+
+/**
+ * 数据文件格式：Cocos2d-x plist、TexturePacker JSON，或 libGDX / Spine 的 .atlas
+ */
+export type SheetFormat = 'plist' | 'jsonHash' | 'jsonArray' | 'libgdxAtlas' | 'spineAtlas'
+
+/**
+ * plist 输出格式：Format 3 XML、旧版 Format 2 XML 或二进制
+ */
+export type PlistFormat = 'xml-v3' | 'xml-v2' | 'binary'
+
+/**
+ * smartupdate 哈希：content 按纹理文件名、帧布局与像素计算，
+ * deterministic 不含纹理文件名（同一内容以不同名称导出时哈希相同）
+ */
+export type SmartUpdateMode = 'content' | 'deterministic'
+
 /**
  * 动画组（序列帧）
  * @interface AnimationGroup
@@ -147,6 +335,18 @@ export interface AutoDetectResult {
   cols: number
   /** 检测置信度 (0-100) */
   confidence: number
+  /** 推荐左右外边距 */
+  marginX: number
+  /** 推荐上下外边距 */
+  marginY: number
+  /** 推荐列间距 */
+  spacingX: number
+  /** 推荐行间距 */
+  spacingY: number
+  /** 推荐水平偏移 */
+  offsetX: number
+  /** 推荐垂直偏移 */
+  offsetY: number
 }
 
 /**
@@ -156,6 +356,8 @@ export interface AutoDetectResult {
 export interface SpritesheetInfoEx extends SpritesheetInfo {
   /** 自动检测结果 */
   autoDetect: AutoDetectResult | null
+  /** 动图的帧时长（导入 GIF、APNG 或动画 WebP 时，帧按行排入网格图集） */
+  animation?: ImportedAnimation
 }
 
 /**
@@ -177,6 +379,8 @@ export interface FrameInfo {
   row: number
   /** 列索引 */
   col: number
+  /** 锚点（相对帧尺寸的 0~1 坐标，Y 轴向上） */
+  anchor?: [number, number]
 }
 
 /**
@@ -196,6 +400,22 @@ export interface SplitConfig {
   namePrefix: string
   /** 起始编号 */
   startIndex?: number
+  /** 左右外边距 */
+  marginX?: number
+  /** 上下外边距 */
+  marginY?: number
+  /** 列间距 */
+  spacingX?: number
+  /** 行间距 */
+  spacingY?: number
+  /** 水平偏移 */
+  offsetX?: number
+  /** 垂直偏移 */
+  offsetY?: number
+  /** 跳过完全透明的格子 */
+  skipEmpty?: boolean
+  /** 所有帧的锚点（相对帧尺寸的 0~1 坐标，Y 轴向上） */
+  anchor?: [number, number]
 }
 
 /**
@@ -211,6 +431,28 @@ export interface SplitResult {
   frameHeight: number
   /** 总帧数 */
   totalFrames: number
+  /** 因完全透明而跳过的格子数 */
+  skippedEmpty?: number
+}
+
+/**
+ * 连通区域切分配置
+ * @interface BlobSplitConfig
+ */
+export interface BlobSplitConfig {
+  /** 帧名称前缀 */
+  namePrefix: string
+  /** 检测选项 */
+  options?: {
+    /** alpha 大于该值的像素视为不透明（默认 10） */
+    alphaThreshold?: number
+    /** 间距不超过该像素数的区域合并为一帧（默认 2） */
+    mergeDistance?: number
+    /** 不透明像素少于该数量的区域视为噪点（默认 4） */
+    minPixels?: number
+  }
+  /** 所有帧的锚点（相对帧尺寸的 0~1 坐标，Y 轴向上） */
+  anchor?: [number, number]
 }
 
 // ========== 多区域导出相关类型 ==========
@@ -236,6 +478,8 @@ export interface AnimationRegion {
   frameHeight: number
   /** 区域颜色（用于可视化） */
   color?: string
+  /** 区域内所有帧的锚点（相对帧尺寸的 0~1 坐标，Y 轴向上） */
+  anchor?: [number, number]
 }
 
 /**
@@ -247,8 +491,28 @@ export interface MultiExportResult {
   exportedFiles: string[]
   /** 成功导出的 PNG 文件路径列表 */
   exportedPngs: string[]
-  /** 失败的区域名称及错误信息 */
-  failed: [string, string][]
+  /** 失败的区域（其已写入的文件已被清理） */
+  failed: RegionFailure[]
   /** 总区域数 */
   total: number
+  /** 部分区域失败时的汇总警告 */
+  warning?: string | null
+}
+
+/**
+ * 区域导出失败原因代码
+ */
+export type RegionFailureCode = 'invalidRegion' | 'noFrames' | 'writePng' | 'writePlist'
+
+/**
+ * 单个区域的导出失败
+ * @interface RegionFailure
+ */
+export interface RegionFailure {
+  /** 区域名称 */
+  region: string
+  /** 错误代码 */
+  code: RegionFailureCode
+  /** 错误信息 */
+  message: string
 }